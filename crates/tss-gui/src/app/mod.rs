@@ -56,6 +56,14 @@ impl App {
     /// Called once at startup. Returns the initial state and any startup tasks.
     /// In daemon mode, we must open the main window explicitly.
     pub fn new() -> (Self, Task<Message>) {
+        // Keep an installed update now that this version has started;
+        // otherwise the updater restores the previous version
+        match tss_updater::confirm_update_success() {
+            Ok(true) => tracing::info!("Confirmed launch after update"),
+            Ok(false) => {}
+            Err(e) => tracing::warn!("Failed to confirm launch after update: {}", e),
+        }
+
         // Load settings from disk
         let settings = Settings::load();
 
@@ -90,8 +98,26 @@ pub fn new() -> (Self, Task<Message>) {
         let open_window = open_window_task.map(|_| Message::Noop);
         let init_menu = Task::perform(async {}, |_| Message::InitNativeMenu);
 
+        // Background update check, if enabled and due under the check frequency
+        let update_check_task = if app.state.settings.updates.should_auto_check() {
+            let update_settings = app.state.settings.updates.clone();
+            Task::perform(
+                async move {
+                    tss_updater::check_for_update(&update_settings)
+                        .await
+                        .map_err(|e| e.user_message_for(&update_settings.source()).into_owned())
+                },
+                Message::UpdateCheckComplete,
+            )
+        } else {
+            Task::none()
+        };
+
         // Chain the tasks
-        let startup = open_window.chain(init_menu).chain(startup_toast_task);
+        let startup = open_window
+            .chain(init_menu)
+            .chain(startup_toast_task)
+            .chain(update_check_task);
         (app, startup)
     }
 
@@ -392,9 +418,27 @@ pub fn update(&mut self, message: Message) -> Task<Message> {
                 Task::none()
             }
 
-            Message::UpdateCheckComplete(_result) => {
-                // This is now handled via UpdateMessage::CheckComplete in dialog handler
-                Task::none()
+            Message::UpdateCheckComplete(result) => {
+                // Startup check; manual checks go through UpdateMessage::CheckResult
+                self.state.settings.updates.record_check();
+                crate::util::best_effort!(self.state.settings.save(), "saving last update check");
+
+                match result {
+                    Ok(Some(info)) => {
+                        self.state.toast = Some(
+                            crate::component::feedback::toast::ToastState::info(format!(
+                                "Update v{} is available. Use Check for Updates to install it.",
+                                info.version_display()
+                            )),
+                        );
+                        schedule_toast_dismiss()
+                    }
+                    Ok(None) => Task::none(),
+                    Err(e) => {
+                        tracing::debug!("Startup update check failed: {}", e);
+                        Task::none()
+                    }
+                }
             }
 
             Message::UpdateReadyToInstall {
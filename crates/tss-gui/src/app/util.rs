@@ -39,7 +39,7 @@ pub async fn create_study_from_assignments(
     // Create study from folder
     let mut study = Study::from_folder(folder);
 
-    // Load metadata from explicitly marked Items.csv files (for column labels)
+    // Load metadata from explicitly marked Items.csv files (column labels, declared targets)
     for metadata_path in &metadata_files {
         if let Ok(items_metadata) = tss_ingest::load_items_metadata(metadata_path, header_rows) {
             study.metadata = Some(items_metadata);
@@ -51,21 +51,16 @@ pub async fn create_study_from_assignments(
     // Load standards based on workflow mode
     let (ig_domains, terminology) = match workflow_mode {
         WorkflowMode::Sdtm => {
-            let domains = tss_standards::load_sdtm_ig()
-                .map_err(|e| format!("Failed to load SDTM-IG: {}", e))?;
-            let ct_version = tss_standards::ct::CtVersion::default();
-            let ct = tss_standards::ct::load(ct_version, Some("SDTM")).map_err(|e| {
-                format!(
-                    "Failed to load Controlled Terminology ({}): {}",
-                    ct_version, e
-                )
-            })?;
+            let config = tss_standards::StandardsConfig::sdtm_only();
+            let ct_version = config.ct_version(tss_standards::Standard::Sdtm);
+            let registry = tss_standards::StandardsRegistry::load_cached(&config)
+                .map_err(|e| format!("Failed to load SDTM-IG and CT ({}): {}", ct_version, e))?;
             tracing::info!(
                 "Loaded CT {} with {} catalogs",
                 ct_version,
-                ct.catalogs.len()
+                registry.ct.catalogs.len()
             );
-            (domains, ct)
+            (registry.sdtm_domains.clone(), registry.ct.clone())
         }
         WorkflowMode::Adam | WorkflowMode::Send => {
             return Err(format!(
@@ -77,14 +72,14 @@ pub async fn create_study_from_assignments(
 
     // Process each assignment
     for (domain_code, file_path) in assignments {
-        // Load CSV file
+        // Load source file
         let file_stem = file_path
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or_default()
             .to_string();
 
-        let (df, _headers) = tss_ingest::read_csv_table(&file_path, header_rows)
+        let (df, hints) = tss_ingest::read_source_table(&file_path, header_rows)
             .map_err(|e| format!("Failed to load {}: {}", file_stem, e))?;
 
         // Find domain in IG
@@ -101,14 +96,13 @@ pub async fn create_study_from_assignments(
         let source = DomainSource::new(file_path, df.clone(), ig_domain.label.clone());
 
         // Create mapping state
-        let hints = tss_ingest::build_column_hints(&df);
         let source_columns: Vec<String> = df
             .get_column_names()
             .into_iter()
             .map(ToString::to_string)
             .collect();
 
-        let mapping = tss_submit::MappingState::new(
+        let mut mapping = tss_submit::MappingState::new(
             ig_domain.clone(),
             &study.study_id,
             &source_columns,
@@ -116,6 +110,18 @@ pub async fn create_study_from_assignments(
             confidence_threshold,
         );
 
+        // Accept mappings the sponsor declared in Items.csv
+        if let Some(metadata) = &study.metadata {
+            let declared = mapping.accept_declared(&metadata.declared_targets(&source_columns));
+            if !declared.is_empty() {
+                tracing::info!(
+                    domain = %domain_code,
+                    count = declared.len(),
+                    "Accepted mappings declared in study metadata"
+                );
+            }
+        }
+
         // Create domain and add to study
         let domain = DomainState::new(source, mapping);
         study.add_domain(domain_code.to_uppercase(), domain);
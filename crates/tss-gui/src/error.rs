@@ -385,7 +385,8 @@ fn from(err: tss_submit::SubmitError) -> Self {
             | tss_submit::SubmitError::ColumnNotFound { .. }
             | tss_submit::SubmitError::ColumnAlreadyMapped { .. }
             | tss_submit::SubmitError::CannotSetNullOnRequired { .. }
-            | tss_submit::SubmitError::CannotOmitNonPermissible { .. } => Self::Mapping {
+            | tss_submit::SubmitError::CannotOmitNonPermissible { .. }
+            | tss_submit::SubmitError::MappingConflict { .. } => Self::Mapping {
                 message: err.to_string(),
             },
             tss_submit::SubmitError::WriteError { .. }
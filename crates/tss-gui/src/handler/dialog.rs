@@ -221,6 +221,10 @@ fn handle_settings_message(state: &mut AppState, msg: SettingsMessage) -> Task<M
                     state.settings.updates.channel = channel;
                     tracing::info!("Update channel changed to: {:?}", channel);
                 }
+                UpdateSettingsMessage::FrequencyChanged(frequency) => {
+                    state.settings.updates.check_frequency = frequency;
+                    tracing::info!("Update check frequency changed to: {:?}", frequency);
+                }
                 UpdateSettingsMessage::ClearSkippedVersion => {
                     state.settings.updates.clear_skipped_version();
                     tracing::info!("Cleared skipped version");
@@ -275,7 +279,7 @@ fn handle_update_message(state: &mut AppState, msg: UpdateMessage) -> Task<Messa
                 async move {
                     tss_updater::check_for_update(&settings)
                         .await
-                        .map_err(|e| e.user_message().to_string())
+                        .map_err(|e| e.user_message_for(&settings.source()).into_owned())
                 },
                 |result| Message::Dialog(DialogMessage::Update(UpdateMessage::CheckResult(result))),
             )
@@ -316,19 +320,23 @@ fn handle_update_message(state: &mut AppState, msg: UpdateMessage) -> Task<Messa
             // Stream download with progress
             let url = info.asset.download_url.clone();
             let total = info.asset.size;
+            let source = state.settings.updates.source();
 
             let stream = tss_updater::download_with_data(url, total).map(
-                |item: Result<tss_updater::DownloadStreamItem, tss_updater::UpdateError>| match item
-                {
-                    Ok(tss_updater::DownloadStreamItem::Progress(progress)) => Message::Dialog(
-                        DialogMessage::Update(UpdateMessage::DownloadProgress(progress)),
-                    ),
-                    Ok(tss_updater::DownloadStreamItem::Complete(result)) => Message::Dialog(
-                        DialogMessage::Update(UpdateMessage::DownloadComplete(Ok(result))),
-                    ),
-                    Err(e) => Message::Dialog(DialogMessage::Update(
-                        UpdateMessage::DownloadComplete(Err(e.user_message().to_string())),
-                    )),
+                move |item: Result<tss_updater::DownloadStreamItem, tss_updater::UpdateError>| {
+                    match item {
+                        Ok(tss_updater::DownloadStreamItem::Progress(progress)) => Message::Dialog(
+                            DialogMessage::Update(UpdateMessage::DownloadProgress(progress)),
+                        ),
+                        Ok(tss_updater::DownloadStreamItem::Complete(result)) => Message::Dialog(
+                            DialogMessage::Update(UpdateMessage::DownloadComplete(Ok(result))),
+                        ),
+                        Err(e) => {
+                            Message::Dialog(DialogMessage::Update(UpdateMessage::DownloadComplete(
+                                Err(e.user_message_for(&source).into_owned()),
+                            )))
+                        }
+                    }
                 },
             );
 
@@ -455,7 +463,7 @@ fn handle_update_message(state: &mut AppState, msg: UpdateMessage) -> Task<Messa
                         async move {
                             tss_updater::check_for_update(&settings)
                                 .await
-                                .map_err(|e| e.user_message().to_string())
+                                .map_err(|e| e.user_message_for(&settings.source()).into_owned())
                         },
                         |result| {
                             Message::Dialog(DialogMessage::Update(UpdateMessage::CheckResult(
@@ -500,8 +508,9 @@ fn handle_update_message(state: &mut AppState, msg: UpdateMessage) -> Task<Messa
         // Async Operation Results
         // =================================================================
         UpdateMessage::CheckResult(result) => {
-            // Record that we checked
+            // Record that we checked (persisted so the check frequency holds across restarts)
             state.settings.updates.record_check();
+            crate::util::best_effort!(state.settings.save(), "saving last update check");
 
             match result {
                 Ok(Some(info)) => {
@@ -577,39 +586,38 @@ fn handle_update_message(state: &mut AppState, msg: UpdateMessage) -> Task<Messa
                         );
                     }
 
-                    // Spawn verification task with timeout (#149)
+                    // Spawn verification task with timeout (#149). The
+                    // update settings decide which checksums must match; a
+                    // package with none is refused.
                     let data = download_result.data;
-                    let expected_digest = info.asset.digest.clone();
+                    let asset = info.asset.clone();
+                    let settings = state.settings.updates.clone();
 
                     Task::perform(
                         async move {
+                            let checksum_file =
+                                tss_updater::download_checksum_file(&asset, &settings)
+                                    .await
+                                    .map_err(|e| {
+                                        e.user_message_for(&settings.source()).into_owned()
+                                    })?;
+
                             // 30s timeout for SHA-256 verification
                             with_timeout(30, "Verification", move || {
-                                match expected_digest {
-                                    Some(expected) => {
-                                        match tss_updater::verify_sha256(&data, &expected) {
-                                            Ok(_) => Ok(VerifyOutcome {
-                                                verified: true,
-                                                data,
-                                            }),
-                                            Err(tss_updater::UpdateError::ChecksumMismatch {
-                                                expected,
-                                                actual,
-                                            }) => Err(format!(
-                                                "Checksum mismatch: expected {}..., got {}...",
-                                                &expected[..8.min(expected.len())],
-                                                &actual[..8.min(actual.len())]
-                                            )),
-                                            Err(e) => Err(e.user_message().to_string()),
-                                        }
-                                    }
-                                    None => {
-                                        // No digest available, skip verification
-                                        Ok(VerifyOutcome {
-                                            verified: false,
-                                            data,
-                                        })
+                                match tss_updater::verify_release_asset(
+                                    &data,
+                                    &asset,
+                                    &settings,
+                                    checksum_file.as_deref(),
+                                ) {
+                                    Ok(_) => Ok(VerifyOutcome {
+                                        verified: true,
+                                        data,
+                                    }),
+                                    Err(tss_updater::UpdateError::VerificationFailed(reason)) => {
+                                        Err(format!("Verification failed: {}", reason))
                                     }
+                                    Err(e) => Err(e.user_message().to_string()),
                                 }
                             })
                             .await?
@@ -212,7 +212,7 @@ fn handle_mapping_message(state: &mut AppState, msg: MappingMessage) -> Task<Mes
                     tracing::error!(variable = %variable, error = %e, "Failed to accept suggestion");
                 }
                 domain.validation_cache = None;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
                 editor.preview_cache = None;
@@ -236,7 +236,7 @@ fn handle_mapping_message(state: &mut AppState, msg: MappingMessage) -> Task<Mes
             {
                 domain.mapping.clear_assignment(&variable);
                 domain.validation_cache = None;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
                 editor.preview_cache = None;
@@ -262,7 +262,7 @@ fn handle_mapping_message(state: &mut AppState, msg: MappingMessage) -> Task<Mes
                     tracing::error!(variable = %variable, column = %column, error = %e, "Failed to accept manual mapping");
                 }
                 domain.validation_cache = None;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
                 editor.preview_cache = None;
@@ -308,7 +308,7 @@ fn handle_mapping_message(state: &mut AppState, msg: MappingMessage) -> Task<Mes
             {
                 let _ = domain.mapping.mark_not_collected(&variable, &reason);
                 domain.validation_cache = None;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
                 editor.mapping_ui.not_collected_edit = None;
@@ -351,7 +351,7 @@ fn handle_mapping_message(state: &mut AppState, msg: MappingMessage) -> Task<Mes
             {
                 domain.mapping.clear_assignment(&variable);
                 domain.validation_cache = None;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
                 editor.preview_cache = None;
@@ -373,7 +373,7 @@ fn handle_mapping_message(state: &mut AppState, msg: MappingMessage) -> Task<Mes
             {
                 let _ = domain.mapping.mark_omit(&variable);
                 domain.validation_cache = None;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
                 editor.preview_cache = None;
@@ -395,7 +395,7 @@ fn handle_mapping_message(state: &mut AppState, msg: MappingMessage) -> Task<Mes
             {
                 domain.mapping.clear_assignment(&variable);
                 domain.validation_cache = None;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
                 editor.preview_cache = None;
@@ -735,7 +735,7 @@ fn handle_supp_message(state: &mut AppState, msg: SuppMessage) -> Task<Message>
                     return Task::none();
                 }
                 config.action = SuppAction::Include;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
                 did_change = true;
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
@@ -766,7 +766,7 @@ fn handle_supp_message(state: &mut AppState, msg: SuppMessage) -> Task<Message>
                 && let Some(config) = source.supp_config.get_mut(&col_name)
             {
                 config.action = SuppAction::Skip;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
                 did_change = true;
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
@@ -797,7 +797,7 @@ fn handle_supp_message(state: &mut AppState, msg: SuppMessage) -> Task<Message>
                 && let Some(config) = source.supp_config.get_mut(&col_name)
             {
                 config.action = SuppAction::Pending;
-                state.dirty_tracker.mark_dirty();
+                state.dirty_tracker.mark_domain_dirty(&domain_code);
                 did_change = true;
             }
             if let ViewState::DomainEditor(editor) = &mut state.view {
@@ -864,7 +864,7 @@ fn handle_supp_message(state: &mut AppState, msg: SuppMessage) -> Task<Message>
                     } else {
                         Some(draft.qeval)
                     };
-                    state.dirty_tracker.mark_dirty();
+                    state.dirty_tracker.mark_domain_dirty(&domain_code);
                     did_change = true;
                 }
             }
@@ -223,8 +223,10 @@ fn start_export(state: &mut AppState) -> Task<Message> {
         return Task::none();
     }
 
-    // Set exporting state in ViewState
+    // Set exporting state in ViewState, with a fresh token for this run
+    let cancellation = tss_submit::CancellationToken::new();
     if let ViewState::Export(export_state) = &mut state.view {
+        export_state.cancellation = cancellation.clone();
         export_state.phase = ExportPhase::Exporting {
             current_domain: None,
             current_step: "Preparing...".to_string(),
@@ -265,50 +267,37 @@ fn start_export(state: &mut AppState) -> Task<Message> {
         bypass_validation: state.settings.developer.bypass_validation,
         ct_registry: terminology,
         not_collected: not_collected_map,
+        cancellation,
     };
 
     // Start actual export task
-    let export_task = Task::perform(
+    let export_task = Task::run(
         crate::service::export::execute_export(export_input),
-        |result| Message::Export(ExportMessage::Complete(result)),
+        Message::Export,
     );
 
     Task::batch([open_task.map(|_| Message::Noop), export_task])
 }
 
 /// Cancel the export process.
+///
+/// Only signals the running export; it stops at the next domain or XPT row
+/// batch and reports [`ExportResult::Cancelled`], which closes the progress
+/// dialog through [`complete_export`].
 fn cancel_export(state: &mut AppState) -> Task<Message> {
-    let mut tasks = vec![];
-
-    // Close progress dialog if open
-    if let Some((id, _)) = state
-        .dialog_registry
-        .close_by_type(DialogType::ExportProgress)
-    {
-        tasks.push(window::close(id));
-    }
+    const CANCELLING: &str = "Cancelling...";
 
-    // Update view state
     if let ViewState::Export(export_state) = &mut state.view {
-        export_state.phase = ExportPhase::Complete;
+        export_state.cancellation.cancel();
+        if let ExportPhase::Exporting { current_step, .. } = &mut export_state.phase {
+            *current_step = CANCELLING.to_string();
+        }
+    }
+    if let Some((_, dialog_state)) = state.dialog_registry.export_progress_mut() {
+        dialog_state.current_step = CANCELLING.to_string();
     }
 
-    // Open completion dialog
-    let settings = window::Settings {
-        size: Size::new(400.0, 350.0),
-        resizable: false,
-        decorations: true,
-        level: window::Level::AlwaysOnTop,
-        exit_on_close_request: false,
-        ..Default::default()
-    };
-    let (id, open_task) = window::open(settings);
-    state
-        .dialog_registry
-        .register(id, DialogState::ExportComplete(ExportResult::Cancelled));
-    tasks.push(open_task.map(|_| Message::Noop));
-
-    Task::batch(tasks)
+    Task::none()
 }
 
 /// Validate and clamp progress to the valid 0.0-1.0 range.
@@ -171,24 +171,24 @@ pub fn load_study(state: &mut AppState, path: PathBuf) -> Task<Message> {
 
 /// Navigate to the source assignment screen.
 ///
-/// Lists CSV files in the folder and loads target domains from standards.
+/// Lists source files in the folder and loads target domains from standards.
 fn navigate_to_source_assignment(
     state: &mut AppState,
     folder: PathBuf,
     workflow_mode: WorkflowMode,
 ) -> Result<(), String> {
-    // List CSV files in the folder
-    let csv_files = list_csv_files(&folder)?;
+    // List CSV and SAS files in the folder
+    let source_files = list_source_files(&folder)?;
 
-    if csv_files.is_empty() {
-        return Err("No CSV files found in the selected folder".to_string());
+    if source_files.is_empty() {
+        return Err("No CSV or SAS files found in the selected folder".to_string());
     }
 
     // Load target domains from standards
     let target_domains = load_target_domains(workflow_mode)?;
 
     // Create assignment UI state (handles source file entries and domain grouping)
-    let assignment_ui = SourceAssignmentUiState::new(folder, csv_files, target_domains);
+    let assignment_ui = SourceAssignmentUiState::new(folder, source_files, target_domains);
 
     // Navigate to source assignment view
     state.view = ViewState::source_assignment(workflow_mode, assignment_ui);
@@ -196,31 +196,33 @@ fn navigate_to_source_assignment(
     Ok(())
 }
 
-/// List CSV files in a folder.
-fn list_csv_files(folder: &PathBuf) -> Result<Vec<PathBuf>, String> {
+/// List CSV and SAS files in a folder.
+fn list_source_files(folder: &PathBuf) -> Result<Vec<PathBuf>, String> {
     let entries = std::fs::read_dir(folder).map_err(|e| format!("Failed to read folder: {}", e))?;
 
-    let csv_files: Vec<PathBuf> = entries
+    let source_files: Vec<PathBuf> = entries
         .filter_map(std::result::Result::ok)
         .map(|entry| entry.path())
         .filter(|path| {
-            path.extension()
-                .map(|ext| ext.eq_ignore_ascii_case("csv"))
-                .unwrap_or(false)
+            // Workbooks hold several tables and are assigned per sheet elsewhere
+            tss_ingest::SourceFormat::from_path(path).is_some_and(|format| !format.is_workbook())
         })
         .collect();
 
-    Ok(csv_files)
+    Ok(source_files)
 }
 
 /// Load target domains from standards based on workflow mode.
 fn load_target_domains(workflow_mode: WorkflowMode) -> Result<Vec<TargetDomainEntry>, String> {
     match workflow_mode {
         WorkflowMode::Sdtm => {
-            let domains = tss_standards::load_sdtm_ig()
-                .map_err(|e| format!("Failed to load SDTM-IG: {}", e))?;
+            let registry = tss_standards::StandardsRegistry::load_cached(
+                &tss_standards::StandardsConfig::sdtm_only(),
+            )
+            .map_err(|e| format!("Failed to load SDTM-IG: {}", e))?;
 
-            let entries: Vec<TargetDomainEntry> = domains
+            let entries: Vec<TargetDomainEntry> = registry
+                .sdtm_domains
                 .iter()
                 .map(|d| {
                     TargetDomainEntry::new(
@@ -192,7 +192,7 @@ fn handle(&self, state: &mut AppState, action: MenuAction) -> Task<Message> {
                     async move {
                         tss_updater::check_for_update(&update_settings)
                             .await
-                            .map_err(|e| e.user_message().to_string())
+                            .map_err(|e| e.user_message_for(&update_settings.source()).into_owned())
                     },
                     |result| {
                         Message::Dialog(DialogMessage::Update(UpdateMessage::CheckResult(result)))
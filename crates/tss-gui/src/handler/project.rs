@@ -14,9 +14,9 @@
 use crate::state::{AppState, DialogState, DialogType, PendingAction};
 use tss_persistence::{
     DomainSnapshot, MappingEntry, MappingSnapshot, ProjectFile, SourceAssignment,
-    SourceDomainSnapshot, StudyMetadata, SuppActionSnapshot, SuppColumnSnapshot,
-    SuppOriginSnapshot, WorkflowTypeSnapshot, compute_file_hash, load_project_async,
-    save_project_async,
+    SourceDomainSnapshot, SourceStatus, StudyMetadata, SuppActionSnapshot, SuppColumnSnapshot,
+    SuppOriginSnapshot, WorkflowTypeSnapshot, compute_file_hash, detect_source_changes,
+    load_project_async, save_project_async, save_project_journal_async,
 };
 
 // =============================================================================
@@ -309,6 +309,13 @@ fn do_save_project(state: &mut AppState, path: PathBuf) -> Task<Message> {
     // Build the project file
     let project = create_project_file_from_state(study, state);
 
+    // Edits confined to domains are appended to the project journal;
+    // anything else (or a new path) rewrites the whole file
+    let incremental = state.project_path.as_ref() == Some(&path)
+        && state.dirty_tracker.is_dirty()
+        && !state.dirty_tracker.needs_full_save();
+    let dirty_domains = state.dirty_tracker.dirty_domains().clone();
+
     // Mark that we're saving
     state.dirty_tracker.start_save();
 
@@ -318,7 +325,14 @@ fn do_save_project(state: &mut AppState, path: PathBuf) -> Task<Message> {
 
     Task::perform(
         async move {
-            match save_project_async(project, save_path.clone()).await {
+            let result = if incremental {
+                save_project_journal_async(project, save_path.clone(), dirty_domains)
+                    .await
+                    .map(|_| ())
+            } else {
+                save_project_async(project, save_path.clone()).await
+            };
+            match result {
                 Ok(()) => Ok(save_path),
                 Err(e) => Err(e.to_string()),
             }
@@ -491,6 +505,8 @@ fn create_project_file_from_state(study: &crate::state::Study, state: &AppState)
                 omitted: mapping.all_omitted().clone(),
                 auto_generated: mapping.all_auto_generated().clone(),
             };
+            // Many-to-one concatenations have no place in the v2 project
+            // format; only their first column is saved as the accepted mapping
             source_snapshot.mapping = mapping_snapshot;
 
             // Add SUPP config
@@ -649,38 +665,18 @@ fn close_unsaved_changes_dialog(state: &mut AppState) -> Option<PendingAction> {
 ///
 /// Returns a list of file paths that have changed (different hash or missing).
 pub fn detect_changed_source_files(project: &tss_persistence::ProjectFile) -> Vec<String> {
-    let mut changed_files = Vec::new();
-
-    for assignment in &project.source_assignments {
-        // Skip if no hash was stored (legacy projects)
-        if assignment.content_hash.is_empty() {
-            continue;
-        }
-
-        let path = std::path::Path::new(&assignment.file_path);
-
-        // Check if file exists
-        if !path.exists() {
-            changed_files.push(format!("{} (missing)", assignment.file_path));
-            continue;
-        }
-
-        // Check if hash matches
-        match compute_file_hash(path) {
-            Ok(current_hash) => {
-                if current_hash != assignment.content_hash {
-                    changed_files.push(assignment.file_path.clone());
-                }
+    detect_source_changes(project)
+        .into_iter()
+        .filter_map(|change| {
+            let path = change.path.display();
+            match (change.status, change.current_hash) {
+                (SourceStatus::Unchanged, _) => None,
+                (SourceStatus::Missing, _) => Some(format!("{path} (missing)")),
+                (SourceStatus::Modified, Some(_)) => Some(path.to_string()),
+                (SourceStatus::Modified, None) => Some(format!("{path} (unreadable)")),
             }
-            Err(e) => {
-                tracing::warn!("Failed to compute hash for {}: {}", assignment.file_path, e);
-                // Consider it changed if we can't verify
-                changed_files.push(format!("{} (unreadable)", assignment.file_path));
-            }
-        }
-    }
-
-    changed_files
+        })
+        .collect()
 }
 
 // =============================================================================
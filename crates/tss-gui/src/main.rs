@@ -26,6 +26,23 @@ pub fn main() -> iced::Result {
 
     tracing::info!("Starting Trial Submission Studio");
 
+    // Count this launch against a pending update, restoring the previous
+    // version if earlier launches of it never confirmed they started
+    match tss_updater::resolve_pending_update() {
+        Ok(tss_updater::Resolution::RolledBack(pending)) => {
+            tracing::warn!(
+                "Update to {} did not confirm its launch; restarting into {}",
+                pending.version,
+                pending.previous_version
+            );
+            if let Err(e) = tss_updater::restart() {
+                tracing::error!("Failed to restart after rollback: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to check pending update: {}", e),
+    }
+
     // Run the Iced application using daemon builder for multi-window support
     // daemon() allows multiple windows with window::Id-based view/title
     iced::daemon(App::new, App::update, App::view)
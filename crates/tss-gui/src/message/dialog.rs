@@ -189,6 +189,9 @@ pub enum UpdateSettingsMessage {
     /// Change update channel (Stable/ReleaseCandidate/Beta/Alpha)
     ChannelChanged(tss_updater::UpdateChannel),
 
+    /// Change how often automatic checks run (Daily/Weekly/Manual)
+    FrequencyChanged(tss_updater::UpdateCheckFrequency),
+
     /// Clear skipped version
     ClearSkippedVersion,
 }
@@ -7,16 +7,23 @@
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use polars::prelude::{AnyValue, DataFrame, NamedFrom, Series};
+use futures_util::{Stream, StreamExt, stream};
+use polars::prelude::DataFrame;
+use tokio::sync::mpsc;
 use tss_standards::{SdtmDomain, TerminologyRegistry};
 use tss_submit::export::types::DomainFrame;
 use tss_submit::export::{
-    DatasetXmlOptions, DefineXmlOptions, build_xpt_dataset_with_name,
+    DatasetXmlOptions, DefineXmlOptions, build_xpt_dataset_with_progress,
     write_dataset_xml as write_dataset_xml_output, write_define_xml as write_define_xml_output,
 };
-use tss_submit::{NormalizationContext, execute_normalization};
+use tss_submit::{
+    CancellationToken, MappingState, NormalizationContext, ProgressEvent, XptOptions,
+    execute_normalization,
+};
 use tss_submit::{Severity, ValidationReport};
+use tss_submit::{SuppParentKey, SuppQualifier, build_suppqual};
 
+use crate::message::export::{ExportMessage, ExportProgress, ExportStep};
 use crate::state::{
     DomainState, ExportFormat, ExportResult, SdtmIgVersion, SuppColumnConfig, XptVersion,
 };
@@ -54,6 +61,8 @@ pub struct ExportInput {
     pub ct_registry: Option<TerminologyRegistry>,
     /// Variables marked as "not collected" per domain.
     pub not_collected: HashMap<String, BTreeSet<String>>,
+    /// Token checked before each domain and each XPT row batch.
+    pub cancellation: CancellationToken,
 }
 
 /// Data for a single domain to export.
@@ -67,6 +76,8 @@ pub struct DomainExportData {
     pub data: DataFrame,
     /// SUPP data if applicable.
     pub supp_data: Option<DataFrame>,
+    /// Mapping the data was built from, for Define-XML origins.
+    pub mapping: MappingState,
 }
 
 // =============================================================================
@@ -114,30 +125,44 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 // MAIN EXPORT FUNCTION
 // =============================================================================
 
-/// Execute export asynchronously.
+/// Execute export asynchronously, streaming progress.
 ///
-/// This function is designed for use with `Task::perform`:
+/// Yields [`ExportMessage::Progress`] updates while domains are written, then
+/// a single [`ExportMessage::Complete`]. Designed for use with `Task::run`:
 ///
 /// ```ignore
-/// Task::perform(
-///     execute_export(input),
-///     |result| Message::Export(ExportMessage::Complete(result)),
-/// )
+/// Task::run(execute_export(input), Message::Export)
 /// ```
-pub async fn execute_export(input: ExportInput) -> ExportResult {
-    // Run blocking export in a separate thread
-    match tokio::task::spawn_blocking(move || execute_export_sync(input)).await {
-        Ok(result) => result,
-        Err(e) => ExportResult::Error {
+pub fn execute_export(input: ExportInput) -> impl Stream<Item = ExportMessage> + Send + 'static {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    // Run blocking export in a separate thread, sending progress as it goes
+    let worker = stream::once(async move {
+        let progress_sender = sender.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            execute_export_sync(input, &|progress| {
+                let _ = progress_sender.send(ExportMessage::Progress(progress));
+            })
+        })
+        .await
+        .unwrap_or_else(|e| ExportResult::Error {
             message: format!("Export task panicked: {}", e),
             domain: None,
-        },
-    }
+        });
+        let _ = sender.send(ExportMessage::Complete(result));
+    })
+    .filter_map(|()| std::future::ready(None));
+
+    let messages = stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|message| (message, receiver))
+    });
+    stream::select(worker, messages)
 }
 
 /// Synchronous export implementation (runs on blocking thread).
-fn execute_export_sync(input: ExportInput) -> ExportResult {
+fn execute_export_sync(input: ExportInput, progress: &dyn Fn(ExportProgress)) -> ExportResult {
     let start = Instant::now();
+    progress(ExportProgress::Step(ExportStep::Validating));
 
     // Run validation on all domains first
     let validation_errors = validate_all_domains(&input);
@@ -181,7 +206,22 @@ fn execute_export_sync(input: ExportInput) -> ExportResult {
     let mut supp_frames: Vec<DomainFrame> = Vec::new();
 
     // Process each domain
-    for domain_data in &input.domains {
+    let domain_count = input.domains.len();
+    for (index, domain_data) in input.domains.iter().enumerate() {
+        if input.cancellation.is_cancelled() {
+            return ExportResult::Cancelled;
+        }
+        progress(ExportProgress::StartingDomain(domain_data.code.clone()));
+        progress(ExportProgress::Step(ExportStep::WritingFile));
+        let rows_progress = |event: ProgressEvent| {
+            if let ProgressEvent::RowsProcessed { rows, .. } = event {
+                let done = rows as f32 / domain_data.data.height().max(1) as f32;
+                progress(ExportProgress::OverallProgress(
+                    (index as f32 + done) / domain_count as f32,
+                ));
+            }
+        };
+
         // Build main domain frame
         let frame = DomainFrame::new(domain_data.code.clone(), domain_data.data.clone());
 
@@ -193,15 +233,16 @@ fn execute_export_sync(input: ExportInput) -> ExportResult {
         );
         let path = datasets_dir.join(&filename);
 
-        let ig_version = input.ig_version.as_str();
         if let Err(e) = write_data_file(
             &path,
             &frame,
             &domain_data.definition,
-            &input.study_id,
-            input.format,
-            ig_version,
+            &input,
+            &rows_progress,
         ) {
+            if input.cancellation.is_cancelled() {
+                return ExportResult::Cancelled;
+            }
             return ExportResult::Error {
                 message: e.message,
                 domain: Some(domain_data.code.clone()),
@@ -227,14 +268,8 @@ fn execute_export_sync(input: ExportInput) -> ExportResult {
             // Pass the parent domain's label for proper SUPP labeling
             let parent_label = domain_data.definition.label.as_deref();
             if let Some(supp_def) = build_supp_domain_definition(&domain_data.code, parent_label) {
-                if let Err(e) = write_data_file(
-                    &supp_path,
-                    &supp_frame,
-                    &supp_def,
-                    &input.study_id,
-                    input.format,
-                    ig_version,
-                ) {
+                if let Err(e) = write_data_file(&supp_path, &supp_frame, &supp_def, &input, &|_| {})
+                {
                     // Log SUPP file failure but continue - graceful degradation
                     tracing::warn!(
                         "SUPP{} export failed (continuing without SUPP file): {}",
@@ -247,6 +282,15 @@ fn execute_export_sync(input: ExportInput) -> ExportResult {
                 }
             }
         }
+
+        progress(ExportProgress::DomainComplete(domain_data.code.clone()));
+        progress(ExportProgress::OverallProgress(
+            (index + 1) as f32 / domain_count as f32,
+        ));
+    }
+
+    if input.cancellation.is_cancelled() {
+        return ExportResult::Cancelled;
     }
 
     // Write Define-XML (always required)
@@ -279,20 +323,26 @@ fn execute_export_sync(input: ExportInput) -> ExportResult {
 // FILE WRITERS
 // =============================================================================
 
-/// Write a data file in the specified format.
+/// Write a data file in the format of `input`.
+///
+/// `progress` receives the rows converted for XPT output, which stops
+/// between row batches once `input.cancellation` is cancelled.
 fn write_data_file(
     path: &Path,
     frame: &DomainFrame,
     domain: &SdtmDomain,
-    study_id: &str,
-    format: ExportFormat,
-    ig_version: &str,
+    input: &ExportInput,
+    progress: &dyn Fn(ProgressEvent),
 ) -> Result<(), ExportError> {
-    match format {
-        ExportFormat::Xpt => write_xpt_file(path, frame, domain),
-        ExportFormat::DatasetXml => {
-            write_dataset_xml_file(path, frame, domain, study_id, ig_version)
-        }
+    match input.format {
+        ExportFormat::Xpt => write_xpt_file(path, frame, domain, &input.cancellation, progress),
+        ExportFormat::DatasetXml => write_dataset_xml_file(
+            path,
+            frame,
+            domain,
+            &input.study_id,
+            input.ig_version.as_str(),
+        ),
     }
 }
 
@@ -301,10 +351,13 @@ fn write_xpt_file(
     path: &Path,
     frame: &DomainFrame,
     domain: &SdtmDomain,
+    cancellation: &CancellationToken,
+    progress: &dyn Fn(ProgressEvent),
 ) -> Result<(), ExportError> {
     // Use the tss-output crate's XPT builder
     let dataset_name = frame.dataset_name();
-    let dataset = build_xpt_dataset_with_name(domain, frame, &dataset_name)
+    let options = XptOptions::default().with_cancellation(cancellation.clone());
+    let dataset = build_xpt_dataset_with_progress(domain, frame, &dataset_name, &options, progress)
         .map_err(|e| ExportError::new(format!("Failed to build XPT dataset: {}", e)))?;
 
     // Write using xportrs
@@ -371,7 +424,10 @@ fn write_define_xml(
         all_frames.push(supp_frame.clone());
     }
 
-    let options = DefineXmlOptions::new(ig_version, "Submission");
+    let options = domain_data.iter().fold(
+        DefineXmlOptions::new(ig_version, "Submission"),
+        |options, data| options.with_mapping(&data.code, &data.mapping),
+    );
 
     write_define_xml_output(path, study_id, &domains, &all_frames, &options)
         .map_err(|e| ExportError::new(format!("Failed to write Define-XML: {}", e)))?;
@@ -385,8 +441,8 @@ fn write_define_xml(
 
 /// Build SUPP domain definition from CDISC standards.
 ///
-/// Loads the SUPPQUAL template from embedded standards and customizes it
-/// for the specific parent domain.
+/// Takes the SUPPQUAL template from the cached SDTM standards and customizes
+/// it for the specific parent domain.
 ///
 /// # Arguments
 /// * `parent_code` - The parent domain code (e.g., "DM", "AE")
@@ -396,8 +452,13 @@ fn build_supp_domain_definition(
     parent_label: Option<&str>,
 ) -> Option<SdtmDomain> {
     // Load SUPPQUAL template from standards
-    let domains = tss_standards::sdtm_ig::load().ok()?;
-    let suppqual = domains.iter().find(|d| d.name == "SUPPQUAL")?;
+    let registry =
+        tss_standards::StandardsRegistry::load_cached(&tss_standards::StandardsConfig::sdtm_only())
+            .ok()?;
+    let suppqual = registry
+        .sdtm_domains
+        .iter()
+        .find(|d| d.name == "SUPPQUAL")?;
 
     // Clone and customize for this specific parent domain
     let mut supp_domain = suppqual.clone();
@@ -442,6 +503,7 @@ pub fn build_domain_export_data(
 
     let mut context = NormalizationContext::new(study_id, code)
         .with_mappings(mappings)
+        .with_concatenations(domain.mapping.all_concatenations().clone())
         .with_omitted(omitted);
 
     if let Some(registry) = terminology {
@@ -461,10 +523,14 @@ pub fn build_domain_export_data(
         definition: cdisc_domain,
         data: transformed_data,
         supp_data,
+        mapping: domain.mapping.clone(),
     })
 }
 
 /// Build SUPP DataFrame from domain's supp_config.
+///
+/// Records point at their parent through --SEQ when the domain has one,
+/// otherwise they are subject-level (blank IDVAR/IDVARVAL).
 fn build_supp_dataframe(
     domain_code: &str,
     domain: &DomainState,
@@ -472,133 +538,38 @@ fn build_supp_dataframe(
     transformed_data: &DataFrame,
 ) -> Result<Option<DataFrame>, ExportError> {
     // Get included SUPP columns
-    let included: Vec<(&String, &SuppColumnConfig)> = domain
+    let qualifiers: Vec<SuppQualifier> = domain
         .supp_config
         .iter()
         .filter(|(_, config)| config.should_include())
-        .collect();
-
-    if included.is_empty() {
-        return Ok(None);
-    }
-
-    let source_df = &domain.source.data;
-    let row_count = source_df.height();
-
-    // SUPP columns: STUDYID, RDOMAIN, USUBJID, IDVAR, IDVARVAL, QNAM, QLABEL, QVAL, QORIG, QEVAL
-    let mut studyid_vec: Vec<String> = Vec::new();
-    let mut rdomain_vec: Vec<String> = Vec::new();
-    let mut usubjid_vec: Vec<String> = Vec::new();
-    let mut idvar_vec: Vec<String> = Vec::new();
-    let mut idvarval_vec: Vec<String> = Vec::new();
-    let mut qnam_vec: Vec<String> = Vec::new();
-    let mut qlabel_vec: Vec<String> = Vec::new();
-    let mut qval_vec: Vec<String> = Vec::new();
-    let mut qorig_vec: Vec<String> = Vec::new();
-    let mut qeval_vec: Vec<String> = Vec::new();
-
-    // Get USUBJID from transformed data
-    let usubjid_col = transformed_data.column("USUBJID").ok();
-
-    // Determine IDVAR (use SEQ if available, otherwise USUBJID)
-    let seq_var = format!("{}SEQ", domain_code);
-    let (idvar, idvar_col) = if let Ok(col) = transformed_data.column(&seq_var) {
-        (seq_var, Some(col))
-    } else {
-        ("USUBJID".to_string(), usubjid_col)
-    };
-
-    // Build rows for each SUPP column
-    for (source_col_name, config) in included {
-        let source_col = match source_df.column(source_col_name) {
-            Ok(col) => col,
-            Err(_) => continue,
-        };
-
-        for row_idx in 0..row_count {
-            let usubjid_val = usubjid_col
-                .and_then(|col| col.get(row_idx).ok())
-                .map(|v| any_value_to_string(&v))
-                .unwrap_or_default();
-
-            if usubjid_val.trim().is_empty() {
-                continue;
-            }
-
-            let qval = source_col
-                .get(row_idx)
-                .ok()
-                .map(|v| any_value_to_string(&v))
-                .unwrap_or_default();
-
-            if qval.trim().is_empty() {
-                continue;
+        .map(|(column, config)| {
+            let qualifier = SuppQualifier::new(
+                column.as_str(),
+                config.qnam.as_str(),
+                config.qlabel.as_str(),
+                config.qorig.code(),
+            );
+            match &config.qeval {
+                Some(qeval) => qualifier.with_qeval(qeval.as_str()),
+                None => qualifier,
             }
+        })
+        .collect();
 
-            let idvarval = idvar_col
-                .and_then(|col| col.get(row_idx).ok())
-                .map(|v| any_value_to_string(&v))
-                .unwrap_or_default();
-
-            studyid_vec.push(study_id.to_string());
-            rdomain_vec.push(domain_code.to_uppercase());
-            usubjid_vec.push(usubjid_val);
-            idvar_vec.push(idvar.clone());
-            idvarval_vec.push(idvarval);
-            qnam_vec.push(config.qnam.clone());
-            qlabel_vec.push(config.qlabel.clone());
-            qval_vec.push(qval);
-            qorig_vec.push(config.qorig.code().to_string());
-            qeval_vec.push(config.qeval.clone().unwrap_or_default());
-        }
-    }
-
-    if studyid_vec.is_empty() {
+    if qualifiers.is_empty() {
         return Ok(None);
     }
 
-    // Build DataFrame
-    let supp_df = DataFrame::new(vec![
-        Series::new("STUDYID".into(), studyid_vec).into(),
-        Series::new("RDOMAIN".into(), rdomain_vec).into(),
-        Series::new("USUBJID".into(), usubjid_vec).into(),
-        Series::new("IDVAR".into(), idvar_vec).into(),
-        Series::new("IDVARVAL".into(), idvarval_vec).into(),
-        Series::new("QNAM".into(), qnam_vec).into(),
-        Series::new("QLABEL".into(), qlabel_vec).into(),
-        Series::new("QVAL".into(), qval_vec).into(),
-        Series::new("QORIG".into(), qorig_vec).into(),
-        Series::new("QEVAL".into(), qeval_vec).into(),
-    ])
-    .map_err(|e| ExportError::for_domain(domain_code, format!("Failed to build SUPP: {}", e)))?;
-
-    Ok(Some(supp_df))
-}
-
-/// Convert AnyValue to String.
-fn any_value_to_string(value: &AnyValue) -> String {
-    match value {
-        AnyValue::Null => String::new(),
-        AnyValue::String(s) => s.to_string(),
-        AnyValue::StringOwned(s) => s.to_string(),
-        AnyValue::Int64(n) => n.to_string(),
-        AnyValue::Int32(n) => n.to_string(),
-        AnyValue::Float64(n) => {
-            if n.fract() == 0.0 {
-                format!("{:.0}", n)
-            } else {
-                n.to_string()
-            }
-        }
-        AnyValue::Float32(n) => {
-            if n.fract() == 0.0 {
-                format!("{:.0}", n)
-            } else {
-                n.to_string()
-            }
-        }
-        other => format!("{}", other),
-    }
+    let key = SuppParentKey::for_domain(domain_code, transformed_data);
+    build_suppqual(
+        study_id,
+        domain_code,
+        transformed_data,
+        &domain.source.data,
+        &qualifiers,
+        &key,
+    )
+    .map_err(|e| ExportError::for_domain(domain_code, format!("Failed to build SUPP: {}", e)))
 }
 
 // =============================================================================
@@ -639,7 +610,12 @@ fn count_validation_errors(report: &ValidationReport) -> usize {
     report
         .issues
         .iter()
-        .filter(|issue| matches!(issue.severity(), Severity::Error | Severity::Reject))
+        .filter(|issue| {
+            matches!(
+                report.severity_of(issue),
+                Severity::Error | Severity::Reject
+            )
+        })
         .count()
 }
 
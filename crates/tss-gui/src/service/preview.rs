@@ -2,13 +2,12 @@
 //!
 //! Uses `Task::perform` pattern for background computation.
 
-use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use polars::prelude::DataFrame;
 use tss_standards::TerminologyRegistry;
 use tss_submit::MappingState;
-use tss_submit::build_preview_dataframe_with_omitted;
+use tss_submit::build_preview_dataframe_for_mapping;
 
 /// Error from preview computation.
 #[derive(Debug, Clone)]
@@ -58,29 +57,8 @@ fn compute_preview_sync(input: PreviewInput) -> Result<DataFrame, PreviewError>
         ct_registry,
     } = input;
 
-    // Extract accepted mappings as variable_name -> source_column
-    let mappings: BTreeMap<String, String> = mapping
-        .all_accepted()
-        .iter()
-        .map(|(var, (col, _confidence))| (var.clone(), col.clone()))
-        .collect();
-
-    // Get omitted variables
-    let omitted = mapping.all_omitted();
-
-    // Get domain definition and study ID
-    let domain = mapping.domain();
-    let study_id = mapping.study_id();
-
-    // Build preview using normalization crate
-    // Dereference Arc to pass reference to underlying DataFrame
-    build_preview_dataframe_with_omitted(
-        &source_df,
-        &mappings,
-        omitted,
-        domain,
-        study_id,
-        ct_registry.as_ref(),
-    )
-    .map_err(|e: tss_submit::NormalizationError| PreviewError(e.to_string()))
+    // Build preview using normalization crate, with the accepted mappings,
+    // many-to-one concatenations and omitted variables of the session
+    build_preview_dataframe_for_mapping(&source_df, &mapping, None, ct_registry.as_ref())
+        .map_err(|e: tss_submit::NormalizationError| PreviewError(e.to_string()))
 }
@@ -280,12 +280,12 @@ pub fn validation_summary(&self) -> Option<(usize, usize)> {
             let warnings = report
                 .issues
                 .iter()
-                .filter(|i| matches!(i.severity(), Severity::Warning))
+                .filter(|i| matches!(report.severity_of(i), Severity::Warning))
                 .count();
             let errors = report
                 .issues
                 .iter()
-                .filter(|i| matches!(i.severity(), Severity::Error | Severity::Reject))
+                .filter(|i| matches!(report.severity_of(i), Severity::Error | Severity::Reject))
                 .count();
             (warnings, errors)
         })
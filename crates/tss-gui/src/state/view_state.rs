@@ -650,7 +650,7 @@ pub enum SourceFileStatus {
 /// A source file entry in the assignment screen.
 #[derive(Debug, Clone)]
 pub struct SourceFileEntry {
-    /// Full path to the CSV file.
+    /// Full path to the source file (CSV or SAS dataset).
     pub path: PathBuf,
     /// Filename without extension (for display).
     pub file_stem: String,
@@ -962,6 +962,8 @@ pub struct ExportViewState {
     pub output_dir: Option<PathBuf>,
     /// Current export phase.
     pub phase: ExportPhase,
+    /// Token shared with the running export, cancelled by the Cancel button.
+    pub cancellation: tss_submit::CancellationToken,
 }
 
 impl ExportViewState {
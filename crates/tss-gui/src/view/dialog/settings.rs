@@ -717,11 +717,37 @@ fn view_update_settings(settings: &Settings) -> Element<'_, Message> {
     ]
     .align_y(Alignment::Center);
 
+    let frequency_section = row![
+        column![
+            text("Check Frequency")
+                .size(14)
+                .style(|theme: &Theme| text::Style {
+                    color: Some(theme.extended_palette().background.base.text),
+                }),
+            text("How often the startup check may contact the update server")
+                .size(12)
+                .style(|theme: &Theme| text::Style {
+                    color: Some(theme.clinical().text_muted),
+                }),
+        ]
+        .width(Length::Fill),
+        pick_list(
+            tss_updater::UpdateCheckFrequency::all(),
+            Some(settings.updates.check_frequency),
+            |frequency| Message::Dialog(DialogMessage::Settings(SettingsMessage::Updates(
+                UpdateSettingsMessage::FrequencyChanged(frequency),
+            ))),
+        ),
+    ]
+    .align_y(Alignment::Center);
+
     column![
         section_header("Update Settings"),
         Space::new().height(SPACING_MD),
         check_on_startup_section,
         Space::new().height(SPACING_SM),
+        frequency_section,
+        Space::new().height(SPACING_SM),
         channel_section,
     ]
     .spacing(SPACING_SM)
@@ -21,9 +21,8 @@
 // DETAIL PANEL
 // =============================================================================
 
-/// Detail view for selected issue.
-pub(super) fn view_issue_detail<'a>(issue: &Issue) -> Element<'a, Message> {
-    let severity = issue.severity();
+/// Detail view for selected issue, shown with its severity after rule overrides.
+pub(super) fn view_issue_detail<'a>(issue: &Issue, severity: Severity) -> Element<'a, Message> {
     let severity_color = get_severity_color(severity);
 
     let variable_name = issue.variable().to_string();
@@ -90,6 +89,9 @@ fn view_issue_metadata<'a>(
         Issue::RequiredEmpty { null_count, .. } | Issue::IdentifierNull { null_count, .. } => {
             metadata = metadata.row("Null Values", null_count.to_string());
         }
+        Issue::RequiredAllNull { row_count, .. } => {
+            metadata = metadata.row("Null Values", row_count.to_string());
+        }
         Issue::InvalidDate {
             invalid_count,
             samples,
@@ -100,6 +102,18 @@ fn view_issue_metadata<'a>(
                 metadata = metadata.row("Examples", samples.join(", "));
             }
         }
+        Issue::NonHyphenDate {
+            invalid_count,
+            samples,
+            suggestions,
+            ..
+        } => {
+            metadata = metadata.row("Invalid Count", invalid_count.to_string());
+            if !samples.is_empty() {
+                metadata = metadata.row("Examples", samples.join(", "));
+                metadata = metadata.row("Suggested", suggestions.join(", "));
+            }
+        }
         Issue::TextTooLong {
             exceeded_count,
             max_found,
@@ -110,6 +124,38 @@ fn view_issue_metadata<'a>(
             metadata = metadata.row("Max Found", max_found.to_string());
             metadata = metadata.row("Max Allowed", max_allowed.to_string());
         }
+        Issue::LabelTooLong { label, length, .. } => {
+            metadata = metadata.row("Label", label.as_str());
+            metadata = metadata.row("Length", length.to_string());
+        }
+        Issue::NonAsciiText {
+            invalid_count,
+            samples,
+            ..
+        } => {
+            metadata = metadata.row("Invalid Count", invalid_count.to_string());
+            if !samples.is_empty() {
+                metadata = metadata.row("Examples", samples.join(", "));
+            }
+        }
+        Issue::ValueOutOfRange {
+            test_code,
+            min,
+            max,
+            unit,
+            invalid_count,
+            samples,
+            ..
+        } => {
+            metadata = metadata.row_opt("Test Code", test_code.as_deref());
+            metadata = metadata.row_opt("Min", min.map(|m| m.to_string()));
+            metadata = metadata.row_opt("Max", max.map(|m| m.to_string()));
+            metadata = metadata.row_opt("Unit", unit.as_deref());
+            metadata = metadata.row("Invalid Count", invalid_count.to_string());
+            if !samples.is_empty() {
+                metadata = metadata.row("Examples", samples.join(", "));
+            }
+        }
         Issue::DataTypeMismatch {
             non_numeric_count,
             samples,
@@ -125,6 +171,41 @@ fn view_issue_metadata<'a>(
         } => {
             metadata = metadata.row("Duplicate Count", duplicate_count.to_string());
         }
+        Issue::DuplicateRecord {
+            key_variables,
+            group_count,
+            duplicate_count,
+            samples,
+            ..
+        } => {
+            metadata = metadata.row("Key", key_variables.join(", "));
+            metadata = metadata.row("Duplicate Groups", group_count.to_string());
+            metadata = metadata.row("Duplicate Count", duplicate_count.to_string());
+            if !samples.is_empty() {
+                metadata = metadata.row("Examples", samples.join(", "));
+            }
+        }
+        Issue::DuplicateSubject {
+            duplicate_count,
+            samples,
+        } => {
+            metadata = metadata.row("Duplicate Count", duplicate_count.to_string());
+            if !samples.is_empty() {
+                metadata = metadata.row("Subjects", samples.join(", "));
+            }
+        }
+        Issue::DateOrderViolation {
+            end_variable,
+            invalid_count,
+            samples,
+            ..
+        } => {
+            metadata = metadata.row("End Variable", end_variable.as_str());
+            metadata = metadata.row("Invalid Count", invalid_count.to_string());
+            if !samples.is_empty() {
+                metadata = metadata.row("Subjects", samples.join(", "));
+            }
+        }
         Issue::CtViolation {
             codelist_code,
             codelist_name,
@@ -132,6 +213,7 @@ fn view_issue_metadata<'a>(
             total_invalid,
             invalid_values,
             allowed_count,
+            suggestions,
             ..
         } => {
             metadata = metadata.row("Codelist", format!("{} ({})", codelist_name, codelist_code));
@@ -151,6 +233,13 @@ fn view_issue_metadata<'a>(
                     format!("{}{}", display_values.join(", "), suffix),
                 );
             }
+            if !suggestions.is_empty() {
+                let hints: Vec<String> = suggestions
+                    .iter()
+                    .map(|(value, suggested)| format!("{} \u{2192} {}", value, suggested))
+                    .collect();
+                metadata = metadata.row("Did You Mean", hints.join(", "));
+            }
         }
         _ => {}
     }
@@ -28,18 +28,33 @@ pub(super) fn issue_category(issue: &Issue) -> &'static str {
     match issue {
         Issue::RequiredMissing { .. }
         | Issue::RequiredEmpty { .. }
+        | Issue::RequiredAllNull { .. }
         | Issue::ExpectedMissing { .. }
         | Issue::IdentifierNull { .. } => "Presence",
-        Issue::InvalidDate { .. } | Issue::TextTooLong { .. } => "Format",
+        Issue::InvalidDate { .. }
+        | Issue::NonHyphenDate { .. }
+        | Issue::TextTooLong { .. }
+        | Issue::LabelTooLong { .. }
+        | Issue::NonAsciiText { .. }
+        | Issue::ValueOutOfRange { .. } => "Format",
         Issue::DataTypeMismatch { .. } => "Type",
-        Issue::DuplicateSequence { .. } => "Consistency",
+        Issue::DuplicateSequence { .. }
+        | Issue::DuplicateRecord { .. }
+        | Issue::DuplicateSubject { .. }
+        | Issue::DomainValueMismatch { .. }
+        | Issue::TestPairingInconsistent { .. }
+        | Issue::StudyidInconsistent { .. }
+        | Issue::DateOrderViolation { .. }
+        | Issue::SuppDuplicateQnam { .. } => "Consistency",
         Issue::UsubjidNotInDm { .. }
         | Issue::ParentNotFound { .. }
         | Issue::InvalidRdomain { .. }
         | Issue::RelsubNotInDm { .. }
         | Issue::RelsubNotBidirectional { .. }
         | Issue::RelspecInvalidParent { .. }
-        | Issue::RelrecInvalidReference { .. } => "Cross Reference",
+        | Issue::RelrecInvalidReference { .. }
+        | Issue::TraceabilityBroken { .. }
+        | Issue::SuppInvalidParent { .. } => "Cross Reference",
         Issue::CtViolation { .. } => "Terminology",
     }
 }
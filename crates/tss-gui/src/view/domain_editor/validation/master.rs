@@ -144,6 +144,7 @@ fn severity_filter_button<'a>(
 /// Issues list in master panel.
 pub(super) fn view_issues_list<'a>(
     issues: &[(usize, &'a Issue)],
+    report: &ValidationReport,
     ui: &ValidationUiState,
 ) -> Element<'a, Message> {
     if issues.is_empty() {
@@ -163,7 +164,8 @@ pub(super) fn view_issues_list<'a>(
 
     for (original_idx, issue) in issues {
         let is_selected = ui.selected_issue == Some(*original_idx);
-        list = list.push(view_issue_row(issue, *original_idx, is_selected));
+        let severity = report.severity_of(issue);
+        list = list.push(view_issue_row(issue, severity, *original_idx, is_selected));
     }
 
     scrollable(list.padding([0.0, SPACING_SM]))
@@ -172,8 +174,12 @@ pub(super) fn view_issues_list<'a>(
 }
 
 /// Single issue row in the master list using SelectableRow component.
-fn view_issue_row<'a>(issue: &'a Issue, idx: usize, is_selected: bool) -> Element<'a, Message> {
-    let severity = issue.severity();
+fn view_issue_row<'a>(
+    issue: &'a Issue,
+    severity: Severity,
+    idx: usize,
+    is_selected: bool,
+) -> Element<'a, Message> {
     let severity_color = get_severity_color(severity);
 
     // Severity icon as leading element
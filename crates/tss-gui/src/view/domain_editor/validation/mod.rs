@@ -46,10 +46,13 @@ pub fn compute_validation_filtered_indices(
         .filter(|(_, issue)| match validation_ui.severity_filter {
             SeverityFilter::All => true,
             SeverityFilter::Errors => {
-                matches!(issue.severity(), Severity::Error | Severity::Reject)
+                matches!(
+                    report.severity_of(issue),
+                    Severity::Error | Severity::Reject
+                )
             }
-            SeverityFilter::Warnings => matches!(issue.severity(), Severity::Warning),
-            SeverityFilter::Info => matches!(issue.severity(), Severity::Info),
+            SeverityFilter::Warnings => matches!(report.severity_of(issue), Severity::Warning),
+            SeverityFilter::Info => matches!(report.severity_of(issue), Severity::Info),
         })
         .map(|(idx, _)| idx)
         .collect()
@@ -111,12 +114,12 @@ pub fn view_validation_tab<'a>(state: &'a AppState, domain_code: &'a str) -> Ele
     let master_header = view_master_header(report, validation_ui, filtered_issues.len());
 
     // Master panel content (list)
-    let master_content = view_issues_list(&filtered_issues, validation_ui);
+    let master_content = view_issues_list(&filtered_issues, report, validation_ui);
 
     // Detail panel
     let detail = if let Some(selected_idx) = validation_ui.selected_issue {
         if let Some(issue) = report.issues.get(selected_idx) {
-            view_issue_detail(issue)
+            view_issue_detail(issue, report.severity_of(issue))
         } else {
             detail_no_selection_default(
                 "Select an Issue",
@@ -671,7 +671,12 @@ fn view_domain_bucket<'a>(
         .enumerate()
         .filter(|(_, f)| f.assigned_domain.as_ref() == Some(&domain.code))
     {
-        content = content.push(view_assigned_file(&file.file_stem, file_idx, &domain.code));
+        let file_name = file
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&file.file_stem);
+        content = content.push(view_assigned_file(file_name, file_idx, &domain.code));
     }
 
     let bucket = container(content)
@@ -762,18 +767,16 @@ fn view_domain_bucket<'a>(
 /// Shows just the filename (no icon) - location under domain makes status clear.
 /// Unassign action via right-click context menu, not inline button.
 fn view_assigned_file<'a>(
-    file_stem: &'a str,
+    file_name: &'a str,
     file_index: usize,
     domain_code: &'a str,
 ) -> Element<'a, Message> {
     // Clean content: indent + filename only (no inline buttons)
     let content = row![
         Space::new().width(SPACING_MD), // Indent
-        text(format!("{}.csv", file_stem))
-            .size(12)
-            .style(|theme: &Theme| text::Style {
-                color: Some(theme.clinical().text_secondary),
-            }),
+        text(file_name).size(12).style(|theme: &Theme| text::Style {
+            color: Some(theme.clinical().text_secondary),
+        }),
         Space::new().width(Length::Fill),
     ]
     .align_y(Alignment::Center);
@@ -0,0 +1,372 @@
+//! CSV dialect detection (delimiter, quote character, and encoding).
+//!
+//! Site exports vary: Excel in European locales writes semicolon-delimited
+//! files, and older systems write Latin-1 instead of UTF-8. The delimiter and
+//! quote are sniffed from the start of the file when the caller doesn't
+//! supply a dialect; a UTF-8 guess is checked against the whole file.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use encoding_rs::{Decoder, UTF_16BE, UTF_16LE, WINDOWS_1252};
+
+use crate::error::{IngestError, Result};
+
+/// Number of bytes read from the start of a file to detect its dialect.
+pub const DIALECT_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Maximum number of lines used for delimiter detection.
+const SNIFF_LINES: usize = 20;
+
+/// Candidate field delimiters, in order of preference on ties.
+const DELIMITERS: [u8; 3] = [b',', b';', b'\t'];
+
+/// Character encoding of a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SourceEncoding {
+    /// UTF-8, with or without BOM.
+    #[default]
+    Utf8,
+    /// UTF-16 little-endian (with BOM).
+    Utf16Le,
+    /// UTF-16 big-endian (with BOM).
+    Utf16Be,
+    /// Latin-1, decoded as its Windows-1252 superset.
+    Latin1,
+}
+
+impl SourceEncoding {
+    /// Detect the encoding from a file's leading bytes.
+    ///
+    /// A UTF-16 BOM selects UTF-16; otherwise the sample is UTF-8 if it is
+    /// valid UTF-8 (ignoring a character cut off by the sample size), else
+    /// Latin-1. A UTF-8 result only covers the sample; [`CsvDialect::sniff`]
+    /// checks the rest of the file.
+    pub fn detect(sample: &[u8]) -> Self {
+        if sample.starts_with(&[0xFF, 0xFE]) {
+            return Self::Utf16Le;
+        }
+        if sample.starts_with(&[0xFE, 0xFF]) {
+            return Self::Utf16Be;
+        }
+        match std::str::from_utf8(sample) {
+            Ok(_) => Self::Utf8,
+            // Incomplete sequence cut off at the end of a full sample
+            Err(e) if e.error_len().is_none() && sample.len() >= DIALECT_SNIFF_BYTES => Self::Utf8,
+            Err(_) => Self::Latin1,
+        }
+    }
+
+    /// Decode bytes in this encoding to UTF-8, dropping any BOM.
+    pub fn decode<'a>(&self, bytes: &'a [u8]) -> Cow<'a, str> {
+        match self {
+            Self::Utf8 => {
+                let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+                String::from_utf8_lossy(bytes)
+            }
+            Self::Utf16Le => UTF_16LE.decode_with_bom_removal(bytes).0,
+            Self::Utf16Be => UTF_16BE.decode_with_bom_removal(bytes).0,
+            Self::Latin1 => WINDOWS_1252.decode_without_bom_handling(bytes).0,
+        }
+    }
+
+    /// Create a streaming decoder to UTF-8 for this encoding, dropping any BOM.
+    pub(crate) fn new_decoder(&self) -> Decoder {
+        match self {
+            Self::Utf8 => encoding_rs::UTF_8.new_decoder_with_bom_removal(),
+            Self::Utf16Le => UTF_16LE.new_decoder_with_bom_removal(),
+            Self::Utf16Be => UTF_16BE.new_decoder_with_bom_removal(),
+            Self::Latin1 => WINDOWS_1252.new_decoder_without_bom_handling(),
+        }
+    }
+
+    /// Whether the file can be read directly without transcoding.
+    pub fn is_utf8(&self) -> bool {
+        matches!(self, Self::Utf8)
+    }
+
+    /// Get the encoding name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Latin1 => "ISO-8859-1",
+        }
+    }
+}
+
+/// Delimiter, quote character, and encoding of a CSV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    /// Field delimiter (`,`, `;`, or tab).
+    pub delimiter: u8,
+    /// Quote character (`"` or `'`).
+    pub quote: u8,
+    /// Character encoding.
+    pub encoding: SourceEncoding,
+}
+
+impl Default for CsvDialect {
+    /// Comma-delimited UTF-8 with double quotes.
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            encoding: SourceEncoding::Utf8,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// Set the field delimiter.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quote character.
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set the encoding.
+    pub fn with_encoding(mut self, encoding: SourceEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Detect the dialect from the first [`DIALECT_SNIFF_BYTES`] of a file.
+    ///
+    /// If the sample looks like UTF-8, the rest of the file is streamed to
+    /// confirm it; a Latin-1 byte anywhere in the file selects Latin-1.
+    pub fn sniff(path: &Path) -> Result<Self> {
+        let mut file = File::open(path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                IngestError::FileNotFound {
+                    path: path.to_path_buf(),
+                }
+            } else {
+                IngestError::FileRead {
+                    path: path.to_path_buf(),
+                    source: e,
+                }
+            }
+        })?;
+
+        let mut sample = Vec::with_capacity(DIALECT_SNIFF_BYTES);
+        (&mut file)
+            .take(DIALECT_SNIFF_BYTES as u64)
+            .read_to_end(&mut sample)
+            .map_err(|e| IngestError::FileRead {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+
+        let mut dialect = Self::detect(&sample);
+        if dialect.encoding.is_utf8()
+            && !is_utf8_stream(&sample, &mut file).map_err(|e| IngestError::FileRead {
+                path: path.to_path_buf(),
+                source: e,
+            })?
+        {
+            dialect.encoding = SourceEncoding::Latin1;
+        }
+        tracing::debug!(
+            path = %path.display(),
+            delimiter = %char::from(dialect.delimiter).escape_default(),
+            quote = %char::from(dialect.quote),
+            encoding = dialect.encoding.name(),
+            "Detected CSV dialect"
+        );
+        Ok(dialect)
+    }
+
+    /// Detect the dialect from a file's leading bytes.
+    ///
+    /// The delimiter is the candidate that appears the same non-zero number
+    /// of times (outside quotes) on the most sampled lines, favouring the
+    /// one that splits lines into more fields. Falls back to a comma.
+    pub fn detect(sample: &[u8]) -> Self {
+        let encoding = SourceEncoding::detect(sample);
+        let text = encoding.decode(sample);
+
+        // Drop the last line, which may be cut off by the sample size
+        let mut lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        if lines.len() > 1 && sample.len() >= DIALECT_SNIFF_BYTES {
+            lines.pop();
+        }
+        lines.truncate(SNIFF_LINES);
+
+        let quote = detect_quote(&lines);
+        let delimiter = DELIMITERS
+            .iter()
+            .filter_map(|&delimiter| {
+                let counts: Vec<usize> = lines
+                    .iter()
+                    .map(|line| count_unquoted(line, delimiter, quote))
+                    .collect();
+                let first = *counts.first()?;
+                if first == 0 {
+                    return None;
+                }
+                let consistent = counts.iter().filter(|&&c| c == first).count();
+                Some((delimiter, consistent, first))
+            })
+            // Most consistent, then most fields; earlier candidates win ties
+            .fold(None, |best: Option<(u8, usize, usize)>, candidate| match best {
+                Some(b) if (b.1, b.2) >= (candidate.1, candidate.2) => Some(b),
+                _ => Some(candidate),
+            })
+            .map_or(b',', |(delimiter, _, _)| delimiter);
+
+        Self {
+            delimiter,
+            quote,
+            encoding,
+        }
+    }
+}
+
+/// Check that `head` followed by the rest of `reader` is valid UTF-8,
+/// reading in blocks so the file is never held in memory.
+fn is_utf8_stream(head: &[u8], reader: &mut impl Read) -> std::io::Result<bool> {
+    let mut buf = vec![0u8; DIALECT_SNIFF_BYTES];
+    let mut pending = head.to_vec();
+    loop {
+        match std::str::from_utf8(&pending) {
+            Ok(_) => pending.clear(),
+            // Keep a character split across blocks for the next read
+            Err(e) if e.error_len().is_none() => {
+                pending.drain(..e.valid_up_to());
+            }
+            Err(_) => return Ok(false),
+        }
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            return Ok(pending.is_empty());
+        }
+        pending.extend_from_slice(&buf[..n]);
+    }
+}
+
+/// Pick `'` as the quote character only if more fields start with it than with `"`.
+fn detect_quote(lines: &[&str]) -> u8 {
+    let starts_with = |quote: char| -> usize {
+        lines
+            .iter()
+            .map(|line| {
+                let chars: Vec<char> = line.chars().collect();
+                chars
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, &c)| {
+                        c == quote
+                            && (i == 0 || DELIMITERS.iter().any(|&d| chars[i - 1] == char::from(d)))
+                    })
+                    .count()
+            })
+            .sum()
+    };
+
+    if starts_with('\'') > starts_with('"') {
+        b'\''
+    } else {
+        b'"'
+    }
+}
+
+/// Count occurrences of `delimiter` outside quoted sections.
+fn count_unquoted(line: &str, delimiter: u8, quote: u8) -> usize {
+    let mut in_quotes = false;
+    let mut count = 0;
+    for byte in line.bytes() {
+        if byte == quote {
+            in_quotes = !in_quotes;
+        } else if byte == delimiter && !in_quotes {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_delimiter() {
+        let comma = CsvDialect::detect(b"A,B,C\n1,2,3\n");
+        assert_eq!(comma, CsvDialect::default());
+
+        let semicolon = CsvDialect::detect(b"A;B;C\n\"1,5\";2;3\n\"2,5\";4;6\n");
+        assert_eq!(semicolon.delimiter, b';');
+
+        let tab = CsvDialect::detect(b"A\tB\n1,0\t2\n");
+        assert_eq!(tab.delimiter, b'\t');
+
+        // A single column has no delimiter to find
+        assert_eq!(CsvDialect::detect(b"A\n1\n").delimiter, b',');
+    }
+
+    #[test]
+    fn test_detect_quote() {
+        let dialect = CsvDialect::detect(b"'A';'B'\n'x;y';'z'\n");
+        assert_eq!(dialect.quote, b'\'');
+        assert_eq!(dialect.delimiter, b';');
+
+        // Apostrophes inside values don't change the quote character
+        let dialect = CsvDialect::detect(b"NAME,SITE\nO'Brien,\"St. John's\"\n");
+        assert_eq!(dialect.quote, b'"');
+    }
+
+    #[test]
+    fn test_detect_encoding() {
+        assert_eq!(SourceEncoding::detect(b"A,B\n"), SourceEncoding::Utf8);
+        assert_eq!(
+            SourceEncoding::detect("\u{feff}Jos\u{e9}".as_bytes()),
+            SourceEncoding::Utf8
+        );
+        assert_eq!(SourceEncoding::detect(b"Jos\xe9"), SourceEncoding::Latin1);
+        assert_eq!(SourceEncoding::detect(b"Jos\xe9\n"), SourceEncoding::Latin1);
+        assert_eq!(
+            SourceEncoding::detect(b"\xff\xfeA\0"),
+            SourceEncoding::Utf16Le
+        );
+
+        // A multi-byte character cut off at the end of the sample is still UTF-8
+        let mut cut = vec![b'a'; DIALECT_SNIFF_BYTES - 1];
+        cut.push(0xC3);
+        assert_eq!(SourceEncoding::detect(&cut), SourceEncoding::Utf8);
+
+        assert_eq!(SourceEncoding::Latin1.decode(b"M\xfcller"), "M\u{fc}ller");
+    }
+
+    #[test]
+    fn test_sniff_checks_whole_file() {
+        use std::io::Write;
+
+        // Plain ASCII for the whole sample, then a Latin-1 name
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"SITEID,INVNAM\n").unwrap();
+        for _ in 0..DIALECT_SNIFF_BYTES / 8 {
+            file.write_all(b"101,Doe\n").unwrap();
+        }
+        file.write_all(b"102,M\xfcller\n").unwrap();
+        let dialect = CsvDialect::sniff(file.path()).unwrap();
+        assert_eq!(dialect.encoding, SourceEncoding::Latin1);
+
+        // A character split across read blocks is still UTF-8
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&vec![b'a'; DIALECT_SNIFF_BYTES - 1])
+            .unwrap();
+        file.write_all("\u{e9}\n".as_bytes()).unwrap();
+        let dialect = CsvDialect::sniff(file.path()).unwrap();
+        assert_eq!(dialect.encoding, SourceEncoding::Utf8);
+
+        assert!(!is_utf8_stream(&[0xC3], &mut std::io::empty()).unwrap());
+    }
+}
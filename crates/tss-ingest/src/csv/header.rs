@@ -1,5 +1,7 @@
 //! CSV header parsing and normalization.
 
+use super::dialect::CsvDialect;
+
 /// Result of CSV header analysis.
 #[derive(Debug, Clone)]
 pub struct CsvHeaders {
@@ -55,33 +57,30 @@ pub fn normalize_header(value: &str) -> String {
 }
 
 /// Parses a CSV line into fields, handling quoted values.
-pub fn parse_csv_line(line: &str) -> Vec<String> {
+pub fn parse_csv_line(line: &str, dialect: &CsvDialect) -> Vec<String> {
+    let delimiter = char::from(dialect.delimiter);
+    let quote = char::from(dialect.quote);
     let mut fields = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
     let mut chars = line.chars().peekable();
 
     while let Some(c) = chars.next() {
-        match c {
-            '"' if !in_quotes => {
+        if c == quote {
+            if !in_quotes {
                 in_quotes = true;
+            } else if chars.peek() == Some(&quote) {
+                // Escaped quote ("")
+                current.push(quote);
+                chars.next();
+            } else {
+                in_quotes = false;
             }
-            '"' if in_quotes => {
-                // Check for escaped quote ("")
-                if chars.peek() == Some(&'"') {
-                    current.push('"');
-                    chars.next();
-                } else {
-                    in_quotes = false;
-                }
-            }
-            ',' if !in_quotes => {
-                fields.push(normalize_header(&current));
-                current.clear();
-            }
-            _ => {
-                current.push(c);
-            }
+        } else if c == delimiter && !in_quotes {
+            fields.push(normalize_header(&current));
+            current.clear();
+        } else {
+            current.push(c);
         }
     }
 
@@ -129,25 +128,32 @@ fn test_normalize_header() {
 
     #[test]
     fn test_parse_csv_line_simple() {
-        let result = parse_csv_line("a,b,c");
+        let result = parse_csv_line("a,b,c", &CsvDialect::default());
         assert_eq!(result, vec!["a", "b", "c"]);
     }
 
     #[test]
     fn test_parse_csv_line_quoted() {
-        let result = parse_csv_line("\"hello, world\",b,c");
+        let result = parse_csv_line("\"hello, world\",b,c", &CsvDialect::default());
         assert_eq!(result, vec!["hello, world", "b", "c"]);
     }
 
     #[test]
     fn test_parse_csv_line_escaped_quotes() {
-        let result = parse_csv_line("\"he said \"\"hello\"\"\",b");
+        let result = parse_csv_line("\"he said \"\"hello\"\"\",b", &CsvDialect::default());
         assert_eq!(result, vec!["he said \"hello\"", "b"]);
     }
 
     #[test]
     fn test_parse_csv_line_trimmed() {
-        let result = parse_csv_line("  a  ,  b  ");
+        let result = parse_csv_line("  a  ,  b  ", &CsvDialect::default());
         assert_eq!(result, vec!["a", "b"]);
     }
+
+    #[test]
+    fn test_parse_csv_line_semicolon() {
+        let dialect = CsvDialect::default().with_delimiter(b';');
+        let result = parse_csv_line("\"1,5\";b;c", &dialect);
+        assert_eq!(result, vec!["1,5", "b", "c"]);
+    }
 }
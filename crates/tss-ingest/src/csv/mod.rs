@@ -1,11 +1,16 @@
 //! CSV reading utilities.
 
+mod dialect;
 mod header;
 mod reader;
 
+pub use dialect::{CsvDialect, DIALECT_SNIFF_BYTES, SourceEncoding};
 pub use header::CsvHeaders;
+pub(crate) use header::normalize_header;
 pub use reader::{
-    EncodingResult, MAX_CSV_FILE_SIZE, check_file_size, check_file_size_with_limit,
-    check_path_length, detect_and_transcode, read_csv_schema, read_csv_table,
-    validate_dataframe_shape,
+    CsvChunks, MAX_CSV_FILE_SIZE, check_file_size, check_file_size_with_limit, check_path_length,
+    read_csv_chunked, read_csv_chunked_with_dialect, read_csv_schema, read_csv_schema_with_dialect,
+    read_csv_table, read_csv_table_with_dialect, validate_dataframe_shape,
 };
+#[allow(deprecated)]
+pub use reader::{EncodingResult, detect_and_transcode};
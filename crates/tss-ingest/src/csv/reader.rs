@@ -1,14 +1,16 @@
 //! CSV file reading with explicit header row configuration.
 
 use std::fs::File;
-use std::io::{BufRead, BufReader, Cursor, Read};
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read, Seek};
+use std::path::{Path, PathBuf};
 
-use encoding_rs::{UTF_16BE, UTF_16LE};
+use encoding_rs::Decoder;
+use polars::io::mmap::MmapBytesReader;
 use polars::prelude::*;
 
 use crate::error::{IngestError, Result};
 
+use super::dialect::{CsvDialect, SourceEncoding};
 use super::header::{CsvHeaders, parse_csv_line};
 
 /// Maximum file size for CSV loading (500 MB default).
@@ -79,93 +81,6 @@ pub fn check_file_size_with_limit(path: &Path, max_size: u64) -> Result<()> {
     Ok(())
 }
 
-/// Encoding detection result.
-#[derive(Debug)]
-pub enum EncodingResult {
-    /// File is UTF-8 (with or without BOM), read directly.
-    Utf8,
-    /// File was transcoded from UTF-16, use this content.
-    Transcoded(String),
-}
-
-/// Detect encoding and transcode if necessary.
-///
-/// - UTF-8 (with or without BOM): Returns `Utf8`, file can be read directly
-/// - UTF-16 LE/BE: Transcodes to UTF-8 and returns `Transcoded(content)`
-///
-/// This handles Windows/Excel exports that often use UTF-16.
-pub fn detect_and_transcode(path: &Path) -> Result<EncodingResult> {
-    let mut file = File::open(path).map_err(|e| {
-        if e.kind() == std::io::ErrorKind::NotFound {
-            IngestError::FileNotFound {
-                path: path.to_path_buf(),
-            }
-        } else {
-            IngestError::FileRead {
-                path: path.to_path_buf(),
-                source: e,
-            }
-        }
-    })?;
-
-    // Read first 4 bytes to detect BOM
-    let mut bom_buffer = [0u8; 4];
-    let bytes_read = file
-        .read(&mut bom_buffer)
-        .map_err(|e| IngestError::FileRead {
-            path: path.to_path_buf(),
-            source: e,
-        })?;
-
-    if bytes_read >= 2 {
-        // UTF-16 LE BOM: FF FE
-        if bom_buffer[0..2] == [0xFF, 0xFE] {
-            return transcode_utf16(path, UTF_16LE, 2);
-        }
-        // UTF-16 BE BOM: FE FF
-        if bom_buffer[0..2] == [0xFE, 0xFF] {
-            return transcode_utf16(path, UTF_16BE, 2);
-        }
-    }
-
-    // UTF-8 (with or without BOM)
-    Ok(EncodingResult::Utf8)
-}
-
-/// Transcode a UTF-16 file to UTF-8.
-fn transcode_utf16(
-    path: &Path,
-    encoding: &'static encoding_rs::Encoding,
-    bom_size: usize,
-) -> Result<EncodingResult> {
-    // Read entire file
-    let bytes = std::fs::read(path).map_err(|e| IngestError::FileRead {
-        path: path.to_path_buf(),
-        source: e,
-    })?;
-
-    // Skip BOM and decode
-    let (decoded, _, had_errors) = encoding.decode(&bytes[bom_size..]);
-
-    if had_errors {
-        tracing::warn!(
-            path = %path.display(),
-            encoding = %encoding.name(),
-            "UTF-16 transcoding had replacement characters"
-        );
-    }
-
-    tracing::info!(
-        path = %path.display(),
-        encoding = %encoding.name(),
-        original_size = bytes.len(),
-        transcoded_size = decoded.len(),
-        "Transcoded UTF-16 file to UTF-8"
-    );
-
-    Ok(EncodingResult::Transcoded(decoded.into_owned()))
-}
-
 /// Validate DataFrame shape after loading.
 ///
 /// Checks for:
@@ -201,9 +116,9 @@ pub fn validate_dataframe_shape(df: &DataFrame, path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Reads the first N lines from a file.
-fn read_first_lines(path: &Path, n: usize) -> Result<Vec<String>> {
-    let file = File::open(path).map_err(|e| {
+/// Opens a source file, reporting a missing file as such.
+fn open_source(path: &Path) -> Result<File> {
+    File::open(path).map_err(|e| {
         if e.kind() == std::io::ErrorKind::NotFound {
             IngestError::FileNotFound {
                 path: path.to_path_buf(),
@@ -214,9 +129,105 @@ fn read_first_lines(path: &Path, n: usize) -> Result<Vec<String>> {
                 source: e,
             }
         }
-    })?;
+    })
+}
+
+/// Reader that decodes a stream to UTF-8 as it is read.
+///
+/// Holds one buffer of input and its decoded output at a time, so files of
+/// any size are transcoded in bounded memory.
+struct DecodingReader<R> {
+    inner: BufReader<R>,
+    decoder: Decoder,
+    output: Vec<u8>,
+    pos: usize,
+    finished: bool,
+}
+
+impl<R: Read> DecodingReader<R> {
+    fn new(inner: R, encoding: SourceEncoding) -> Self {
+        Self {
+            inner: BufReader::new(inner),
+            decoder: encoding.new_decoder(),
+            output: Vec::new(),
+            pos: 0,
+            finished: false,
+        }
+    }
 
-    let reader = BufReader::new(file);
+    /// Decodes the next buffer of input into `output`.
+    fn fill_output(&mut self) -> std::io::Result<()> {
+        let input = self.inner.fill_buf()?;
+        let last = input.is_empty();
+        let capacity = self
+            .decoder
+            .max_utf8_buffer_length(input.len())
+            .unwrap_or(input.len() * 3 + 16);
+        let mut decoded = String::with_capacity(capacity);
+        let (_, read, _) = self.decoder.decode_to_string(input, &mut decoded, last);
+        self.inner.consume(read);
+        self.output = decoded.into_bytes();
+        self.pos = 0;
+        self.finished = last;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos == self.output.len() && !self.finished {
+            self.fill_output()?;
+        }
+        let n = buf.len().min(self.output.len() - self.pos);
+        buf[..n].copy_from_slice(&self.output[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Encoding detection result.
+#[deprecated(note = "use `CsvDialect::sniff`; the CSV readers transcode while reading")]
+#[derive(Debug)]
+pub enum EncodingResult {
+    /// File is UTF-8 (with or without BOM), read directly.
+    Utf8,
+    /// File was transcoded from another encoding, use this content.
+    Transcoded(String),
+}
+
+/// Detect encoding and transcode if necessary.
+///
+/// - UTF-8 (with or without BOM): Returns `Utf8`, file can be read directly
+/// - UTF-16 LE/BE or Windows-1252: Transcodes to UTF-8 and returns
+///   `Transcoded(content)`
+///
+/// The encoding is detected like [`CsvDialect::sniff`] does. Unlike the CSV
+/// readers, this holds the whole transcoded file in memory.
+#[deprecated(note = "use `CsvDialect::sniff`; the CSV readers transcode while reading")]
+#[allow(deprecated)]
+pub fn detect_and_transcode(path: &Path) -> Result<EncodingResult> {
+    let dialect = CsvDialect::sniff(path)?;
+    if dialect.encoding.is_utf8() {
+        return Ok(EncodingResult::Utf8);
+    }
+    let mut content = String::new();
+    DecodingReader::new(open_source(path)?, dialect.encoding)
+        .read_to_string(&mut content)
+        .map_err(|e| IngestError::FileRead {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+    Ok(EncodingResult::Transcoded(content))
+}
+
+/// Reads the first N lines from a file, decoding them from `encoding`.
+fn read_first_lines(path: &Path, n: usize, encoding: SourceEncoding) -> Result<Vec<String>> {
+    let file = open_source(path)?;
+    let reader: Box<dyn BufRead> = if encoding.is_utf8() {
+        Box::new(BufReader::new(file))
+    } else {
+        Box::new(BufReader::new(DecodingReader::new(file, encoding)))
+    };
     let mut lines = Vec::with_capacity(n);
 
     for line_result in reader.lines().take(n) {
@@ -232,13 +243,44 @@ fn read_first_lines(path: &Path, n: usize) -> Result<Vec<String>> {
     Ok(lines)
 }
 
-/// Reads CSV headers with explicit row count.
+/// Transcodes a file to UTF-8 in an anonymous temporary file.
 ///
-/// - `header_rows = 1`: Single header row (column names only)
-/// - `header_rows = 2`: Double header (row 1 = labels, row 2 = column names)
-pub fn read_csv_schema(path: &Path, header_rows: usize) -> Result<CsvHeaders> {
-    let lines = read_first_lines(path, header_rows.max(1))?;
+/// The file is streamed through the dialect's decoder, so it is never held
+/// in memory as a whole. The returned file is positioned at its start and
+/// removed once dropped.
+fn transcode_to_temp(path: &Path, dialect: &CsvDialect) -> Result<File> {
+    let read_error = |source| IngestError::FileRead {
+        path: path.to_path_buf(),
+        source,
+    };
+    let source = open_source(path)?;
+    let original_size = source.metadata().map_err(read_error)?.len();
+    let mut decoded = tempfile::tempfile().map_err(read_error)?;
+    let transcoded_size = std::io::copy(
+        &mut DecodingReader::new(source, dialect.encoding),
+        &mut decoded,
+    )
+    .map_err(read_error)?;
+    decoded.rewind().map_err(read_error)?;
+
+    tracing::info!(
+        path = %path.display(),
+        encoding = dialect.encoding.name(),
+        original_size,
+        transcoded_size,
+        "Transcoded file to UTF-8"
+    );
 
+    Ok(decoded)
+}
+
+/// Parses header lines into [`CsvHeaders`].
+fn parse_headers(
+    path: &Path,
+    lines: &[String],
+    header_rows: usize,
+    dialect: &CsvDialect,
+) -> Result<CsvHeaders> {
     if lines.is_empty() {
         return Err(IngestError::EmptyCsv {
             path: path.to_path_buf(),
@@ -247,12 +289,12 @@ pub fn read_csv_schema(path: &Path, header_rows: usize) -> Result<CsvHeaders> {
 
     match header_rows {
         2 if lines.len() >= 2 => {
-            let labels = parse_csv_line(&lines[0]);
-            let columns = parse_csv_line(&lines[1]);
+            let labels = parse_csv_line(&lines[0], dialect);
+            let columns = parse_csv_line(&lines[1], dialect);
             Ok(CsvHeaders::double(labels, columns))
         }
         _ => {
-            let columns = parse_csv_line(&lines[0]);
+            let columns = parse_csv_line(&lines[0], dialect);
             if columns.is_empty() || columns.iter().all(String::is_empty) {
                 return Err(IngestError::NoHeaderDetected {
                     path: path.to_path_buf(),
@@ -263,64 +305,246 @@ pub fn read_csv_schema(path: &Path, header_rows: usize) -> Result<CsvHeaders> {
     }
 }
 
+/// Reads CSV headers with explicit row count.
+///
+/// - `header_rows = 1`: Single header row (column names only)
+/// - `header_rows = 2`: Double header (row 1 = labels, row 2 = column names)
+///
+/// The dialect is detected from the start of the file.
+pub fn read_csv_schema(path: &Path, header_rows: usize) -> Result<CsvHeaders> {
+    read_csv_schema_with_dialect(path, header_rows, None)
+}
+
+/// Reads CSV headers using the given dialect, or a detected one if `None`.
+pub fn read_csv_schema_with_dialect(
+    path: &Path,
+    header_rows: usize,
+    dialect: Option<CsvDialect>,
+) -> Result<CsvHeaders> {
+    let dialect = match dialect {
+        Some(dialect) => dialect,
+        None => CsvDialect::sniff(path)?,
+    };
+    let n = header_rows.max(1);
+
+    let lines = read_first_lines(path, n, dialect.encoding)?;
+    parse_headers(path, &lines, header_rows, &dialect)
+}
+
 /// Reads a CSV file into a Polars DataFrame with explicit header configuration.
 ///
 /// - `header_rows = 1`: Single header row
 /// - `header_rows = 2`: Double header (labels + column names)
 ///
 /// Automatically handles:
+/// - Comma, semicolon, and tab delimiters
 /// - UTF-8 files (with or without BOM)
-/// - UTF-16 LE/BE files (transcoded to UTF-8)
+/// - UTF-16 LE/BE and Latin-1 files (transcoded to UTF-8)
 /// - Windows path length validation
 ///
 /// Returns both the DataFrame and the header information.
 pub fn read_csv_table(path: &Path, header_rows: usize) -> Result<(DataFrame, CsvHeaders)> {
+    read_csv_table_with_dialect(path, header_rows, None)
+}
+
+/// Reads a CSV file using the given dialect, or a detected one if `None`.
+///
+/// See [`read_csv_table`] for the header row configuration.
+pub fn read_csv_table_with_dialect(
+    path: &Path,
+    header_rows: usize,
+    dialect: Option<CsvDialect>,
+) -> Result<(DataFrame, CsvHeaders)> {
     // Check path length on Windows
     check_path_length(path)?;
 
-    // Detect encoding and transcode if needed
-    let encoding_result = detect_and_transcode(path)?;
-
-    let headers = read_csv_schema(path, header_rows)?;
+    let dialect = match dialect {
+        Some(dialect) => dialect,
+        None => CsvDialect::sniff(path)?,
+    };
 
     // Skip additional rows beyond the first header row
     let skip_rows = header_rows.saturating_sub(1);
 
-    let df = match encoding_result {
-        EncodingResult::Utf8 => {
-            // Read directly from file
-            CsvReadOptions::default()
-                .with_has_header(true)
-                .with_skip_rows(skip_rows)
-                .with_infer_schema_length(None)
-                .try_into_reader_with_file_path(Some(path.to_path_buf()))
-                .map_err(|e| IngestError::CsvParse {
-                    path: path.to_path_buf(),
-                    message: e.to_string(),
-                })?
-                .finish()
-                .map_err(|e| IngestError::CsvParse {
-                    path: path.to_path_buf(),
-                    message: e.to_string(),
-                })?
+    let options = CsvReadOptions::default()
+        .with_has_header(true)
+        .with_skip_rows(skip_rows)
+        .with_infer_schema_length(None)
+        .with_parse_options(
+            CsvParseOptions::default()
+                .with_separator(dialect.delimiter)
+                .with_quote_char(Some(dialect.quote)),
+        );
+
+    let headers = parse_headers(
+        path,
+        &read_first_lines(path, header_rows.max(1), dialect.encoding)?,
+        header_rows,
+        &dialect,
+    )?;
+
+    let reader = if dialect.encoding.is_utf8() {
+        // Read directly from file
+        options
+            .try_into_reader_with_file_path(Some(path.to_path_buf()))
+            .map_err(|e| IngestError::CsvParse {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?
+    } else {
+        // Read from the transcoded copy
+        options.into_reader_with_file_handle(transcode_to_temp(path, &dialect)?)
+    };
+    let df = reader.finish().map_err(|e| IngestError::CsvParse {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+
+    Ok((df, headers))
+}
+
+/// Iterator over the row batches of a CSV file.
+///
+/// Created by [`read_csv_chunked`]. Each item holds `batch_rows` rows,
+/// except the last, which holds the remainder.
+pub struct CsvChunks {
+    path: PathBuf,
+    reader: OwnedBatchedCsvReader,
+    batch_rows: usize,
+    pending: Option<DataFrame>,
+    done: bool,
+}
+
+impl CsvChunks {
+    fn parse_error(&self, e: &PolarsError) -> IngestError {
+        IngestError::CsvParse {
+            path: self.path.clone(),
+            message: e.to_string(),
         }
-        EncodingResult::Transcoded(content) => {
-            // Read from transcoded content
-            let cursor = Cursor::new(content.as_bytes());
-            CsvReadOptions::default()
-                .with_has_header(true)
-                .with_skip_rows(skip_rows)
-                .with_infer_schema_length(None)
-                .into_reader_with_file_handle(cursor)
-                .finish()
-                .map_err(|e| IngestError::CsvParse {
-                    path: path.to_path_buf(),
-                    message: e.to_string(),
-                })?
+    }
+}
+
+impl Iterator for CsvChunks {
+    type Item = Result<DataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let buffered = self.pending.as_ref().map_or(0, DataFrame::height);
+            if buffered >= self.batch_rows || (self.done && buffered > 0) {
+                let pending = self.pending.take()?;
+                let batch_rows = self.batch_rows.min(buffered);
+                if buffered > batch_rows {
+                    self.pending = Some(pending.slice(batch_rows as i64, buffered - batch_rows));
+                }
+                return Some(Ok(pending.slice(0, batch_rows)));
+            }
+            if self.done {
+                return None;
+            }
+
+            // Polars batches follow its own chunk sizes; regroup them into
+            // batches of `batch_rows`
+            match self.reader.next_batches(1) {
+                Ok(Some(batches)) => {
+                    for batch in batches {
+                        let merged = match &mut self.pending {
+                            Some(pending) => pending.vstack_mut(&batch).map(|_| ()),
+                            None => {
+                                self.pending = Some(batch);
+                                Ok(())
+                            }
+                        };
+                        if let Err(e) = merged {
+                            self.done = true;
+                            return Some(Err(self.parse_error(&e)));
+                        }
+                    }
+                }
+                Ok(None) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(self.parse_error(&e)));
+                }
+            }
         }
+    }
+}
+
+/// Reads a CSV file in batches of `batch_rows` rows.
+///
+/// Unlike [`read_csv_table`], the file is never loaded as a whole, so files
+/// larger than memory (e.g., multi-GB lab extracts) can be processed chunk
+/// by chunk. `headers` is the result of [`read_csv_schema`] for the file.
+///
+/// All columns are read as `String`: with no full pass over the data, a
+/// type inferred from the first rows could fail to parse a later batch.
+/// Column hints can be computed over the batches with
+/// [`ColumnHintAccumulator`](crate::ColumnHintAccumulator).
+///
+/// UTF-8 files are read from disk as they are consumed; other encodings are
+/// first streamed to a UTF-8 copy in a temporary file.
+pub fn read_csv_chunked(path: &Path, headers: &CsvHeaders, batch_rows: usize) -> Result<CsvChunks> {
+    read_csv_chunked_with_dialect(path, headers, batch_rows, None)
+}
+
+/// Reads a CSV file in batches using the given dialect, or a detected one
+/// if `None`.
+///
+/// See [`read_csv_chunked`].
+pub fn read_csv_chunked_with_dialect(
+    path: &Path,
+    headers: &CsvHeaders,
+    batch_rows: usize,
+    dialect: Option<CsvDialect>,
+) -> Result<CsvChunks> {
+    check_path_length(path)?;
+
+    let dialect = match dialect {
+        Some(dialect) => dialect,
+        None => CsvDialect::sniff(path)?,
     };
+    let batch_rows = batch_rows.max(1);
+
+    let schema: Schema = headers
+        .columns
+        .iter()
+        .map(|name| Field::new(name.as_str().into(), DataType::String))
+        .collect();
+    let options = CsvReadOptions::default()
+        .with_has_header(true)
+        .with_skip_rows(headers.skip_rows.saturating_sub(1))
+        .with_schema(Some(Arc::new(schema)))
+        .with_chunk_size(batch_rows)
+        .with_parse_options(
+            CsvParseOptions::default()
+                .with_separator(dialect.delimiter)
+                .with_quote_char(Some(dialect.quote)),
+        );
 
-    Ok((df, headers))
+    let source: Box<dyn MmapBytesReader> = if dialect.encoding.is_utf8() {
+        Box::new(File::open(path).map_err(|e| IngestError::FileRead {
+            path: path.to_path_buf(),
+            source: e,
+        })?)
+    } else {
+        Box::new(transcode_to_temp(path, &dialect)?)
+    };
+
+    let reader = options
+        .into_reader_with_file_handle(source)
+        .batched(None)
+        .map_err(|e| IngestError::CsvParse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+    Ok(CsvChunks {
+        path: path.to_path_buf(),
+        reader,
+        batch_rows,
+        pending: None,
+        done: false,
+    })
 }
 
 #[cfg(test)]
@@ -402,56 +626,128 @@ fn test_read_csv_table_double_header() {
     }
 
     #[test]
-    fn test_detect_and_transcode_utf8() {
-        let file = create_temp_csv("A,B,C\n1,2,3\n");
-        let result = detect_and_transcode(file.path()).unwrap();
+    fn test_read_csv_chunked() {
+        let mut content = String::from("Subject,Result\nUSUBJID,LBORRES\n");
+        for idx in 0..2500 {
+            content.push_str(&format!("S{idx:04},{}\n", idx % 7));
+        }
+        let file = create_temp_csv(&content);
+        let headers = read_csv_schema(file.path(), 2).unwrap();
 
-        assert!(matches!(result, EncodingResult::Utf8));
+        let chunks: Vec<DataFrame> = read_csv_chunked(file.path(), &headers, 1000)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        let heights: Vec<usize> = chunks.iter().map(DataFrame::height).collect();
+        assert_eq!(heights, [1000, 1000, 500]);
+        assert_eq!(chunks[0].get_column_names(), vec!["USUBJID", "LBORRES"]);
+        let subjects = chunks[2].column("USUBJID").unwrap().str().unwrap();
+        assert_eq!(subjects.get(0), Some("S2000"));
+        assert_eq!(subjects.get(499), Some("S2499"));
+
+        let mut accumulator = crate::ColumnHintAccumulator::new();
+        for chunk in &chunks {
+            accumulator.update(chunk);
+        }
+        let hints = accumulator.finish();
+        assert!(hints["LBORRES"].is_numeric);
+        assert!((hints["USUBJID"].unique_ratio - 1.0).abs() < 0.01);
     }
 
     #[test]
-    fn test_detect_and_transcode_utf16le() {
-        // Create UTF-16 LE file with BOM
-        let content = "A,B,C\n1,2,3\n";
-        let mut bytes = vec![0xFF, 0xFE]; // UTF-16 LE BOM
-        for c in content.encode_utf16() {
-            bytes.extend_from_slice(&c.to_le_bytes());
-        }
+    fn test_read_csv_table_semicolon() {
+        let file =
+            create_temp_csv("Label A;Label B\nSTUDYID;WEIGHT\nSTUDY1;\"72,5\"\nSTUDY1;\"80,1\"\n");
+        let (df, headers) = read_csv_table(file.path(), 2).unwrap();
+
+        assert_eq!(headers.columns, vec!["STUDYID", "WEIGHT"]);
+        assert_eq!(df.width(), 2);
+        assert_eq!(df.height(), 2);
+        let weight = df.column("WEIGHT").unwrap().str().unwrap();
+        assert_eq!(weight.get(0), Some("72,5"));
+    }
 
+    #[test]
+    fn test_read_csv_table_latin1() {
+        // "Müller" and "Gonçalves" encoded as ISO-8859-1
+        let bytes = b"SITEID,INVNAM\n101,M\xfcller\n102,Gon\xe7alves\n";
         let mut file = NamedTempFile::new().unwrap();
-        file.write_all(&bytes).unwrap();
+        file.write_all(bytes).unwrap();
 
-        let result = detect_and_transcode(file.path()).unwrap();
+        let (df, headers) = read_csv_table(file.path(), 1).unwrap();
 
-        match result {
-            EncodingResult::Transcoded(s) => {
-                assert!(s.contains("A,B,C"));
-                assert!(s.contains("1,2,3"));
-            }
-            EncodingResult::Utf8 => panic!("Expected Transcoded, got Utf8"),
-        }
+        assert_eq!(headers.columns, vec!["SITEID", "INVNAM"]);
+        let names = df.column("INVNAM").unwrap().str().unwrap();
+        assert_eq!(names.get(0), Some("M\u{fc}ller"));
+        assert_eq!(names.get(1), Some("Gon\u{e7}alves"));
     }
 
     #[test]
-    fn test_detect_and_transcode_utf16be() {
-        // Create UTF-16 BE file with BOM
-        let content = "A,B,C\n1,2,3\n";
-        let mut bytes = vec![0xFE, 0xFF]; // UTF-16 BE BOM
-        for c in content.encode_utf16() {
-            bytes.extend_from_slice(&c.to_be_bytes());
-        }
+    fn test_read_csv_table_explicit_dialect() {
+        let file = create_temp_csv("A\tB\n1\t2\n");
+        let dialect = CsvDialect::default().with_delimiter(b'\t');
+        let (df, headers) = read_csv_table_with_dialect(file.path(), 1, Some(dialect)).unwrap();
 
+        assert_eq!(headers.columns, vec!["A", "B"]);
+        assert_eq!(df.width(), 2);
+    }
+
+    #[test]
+    fn test_decoding_reader_across_buffers() {
+        // Enough text to span several reads, with a surrogate pair split
+        // across the first buffer boundary
+        let text = "\u{1d11e}".repeat(3_000) + "M\u{fc}ller";
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+
+        let mut decoded = String::new();
+        DecodingReader::new(bytes.as_slice(), SourceEncoding::Utf16Le)
+            .read_to_string(&mut decoded)
+            .unwrap();
+
+        assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn test_read_csv_chunked_utf16() {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(
+            "SITEID,INVNAM\n101,M\u{fc}ller\n102,Gon\u{e7}alves\n"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(&bytes).unwrap();
 
-        let result = detect_and_transcode(file.path()).unwrap();
+        let headers = read_csv_schema(file.path(), 1).unwrap();
+        assert_eq!(headers.columns, vec!["SITEID", "INVNAM"]);
+        let batches: Vec<DataFrame> = read_csv_chunked(file.path(), &headers, 1)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 2);
+        let names = batches[1].column("INVNAM").unwrap().str().unwrap();
+        assert_eq!(names.get(0), Some("Gon\u{e7}alves"));
+    }
 
-        match result {
-            EncodingResult::Transcoded(s) => {
-                assert!(s.contains("A,B,C"));
-                assert!(s.contains("1,2,3"));
-            }
-            EncodingResult::Utf8 => panic!("Expected Transcoded, got Utf8"),
+    #[test]
+    #[allow(deprecated)]
+    fn test_detect_and_transcode() {
+        let utf8 = create_temp_csv("A,B\n1,2\n");
+        assert!(matches!(
+            detect_and_transcode(utf8.path()).unwrap(),
+            EncodingResult::Utf8
+        ));
+
+        let mut bytes = vec![0xFE, 0xFF];
+        bytes.extend("A,B\n1,\u{e9}\n".encode_utf16().flat_map(u16::to_be_bytes));
+        let mut utf16 = NamedTempFile::new().unwrap();
+        utf16.write_all(&bytes).unwrap();
+        match detect_and_transcode(utf16.path()).unwrap() {
+            EncodingResult::Transcoded(content) => assert_eq!(content, "A,B\n1,\u{e9}\n"),
+            EncodingResult::Utf8 => panic!("UTF-16 file should be transcoded"),
         }
     }
 }
@@ -3,11 +3,64 @@
 use std::path::{Path, PathBuf};
 
 use crate::error::{IngestError, Result};
+use crate::source::{SourceEntry, SourceFormat};
+use crate::xlsx::list_xlsx_sheets;
 
 /// Lists all CSV files in a directory.
 ///
 /// Returns files sorted by filename.
 pub fn list_csv_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    list_files(dir, |path| {
+        SourceFormat::from_path(path) == Some(SourceFormat::Csv)
+    })
+}
+
+/// Lists all SAS Transport (`.xpt`) files in a directory.
+///
+/// Returns files sorted by filename.
+pub fn list_xpt_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    list_files(dir, |path| {
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xpt"))
+    })
+}
+
+/// Lists all single-table source files (CSV and SAS7BDAT) in a directory.
+///
+/// Returns files sorted by filename. Workbooks are not included; see
+/// [`list_source_entries`].
+pub fn list_source_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    list_files(dir, |path| {
+        SourceFormat::from_path(path).is_some_and(|format| !format.is_workbook())
+    })
+}
+
+/// Lists source tables in a directory, optionally including workbook sheets.
+///
+/// Each CSV or SAS7BDAT file is one entry. With `include_sheets`, every
+/// sheet of each `.xlsx` workbook is listed as its own entry, in workbook
+/// order. Entries are sorted by filename.
+pub fn list_source_entries(dir: &Path, include_sheets: bool) -> Result<Vec<SourceEntry>> {
+    let files = list_files(dir, |path| match SourceFormat::from_path(path) {
+        Some(format) => include_sheets || !format.is_workbook(),
+        None => false,
+    })?;
+
+    let mut entries = Vec::with_capacity(files.len());
+    for path in files {
+        if SourceFormat::from_path(&path).is_some_and(SourceFormat::is_workbook) {
+            for sheet in list_xlsx_sheets(&path)? {
+                entries.push(SourceEntry::sheet(&path, sheet));
+            }
+        } else {
+            entries.push(SourceEntry::file(path));
+        }
+    }
+    Ok(entries)
+}
+
+/// Lists files in a directory that match `include`, sorted by filename.
+fn list_files(dir: &Path, include: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>> {
     if !dir.is_dir() {
         return Err(IngestError::DirectoryNotFound {
             path: dir.to_path_buf(),
@@ -34,14 +87,7 @@ pub fn list_csv_files(dir: &Path) -> Result<Vec<PathBuf>> {
             continue;
         }
 
-        // Check for .csv extension (case-insensitive)
-        let is_csv = path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .map(|ext| ext.eq_ignore_ascii_case("csv"))
-            .unwrap_or(false);
-
-        if is_csv {
+        if include(&path) {
             files.push(path);
         }
     }
@@ -86,6 +132,33 @@ fn test_list_csv_files() {
         );
     }
 
+    #[test]
+    fn test_list_source_files_includes_sas() {
+        let dir = create_test_dir();
+        std::fs::write(dir.path().join("STUDY_LB.sas7bdat"), b"").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"").unwrap();
+
+        assert_eq!(list_csv_files(dir.path()).unwrap().len(), 3);
+        let files = list_source_files(dir.path()).unwrap();
+        assert_eq!(files.len(), 4);
+        assert!(files.iter().any(|f| f.ends_with("STUDY_LB.sas7bdat")));
+    }
+
+    #[test]
+    fn test_list_source_entries_with_sheets() {
+        let dir = create_test_dir();
+        let workbook = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/study.xlsx");
+        std::fs::copy(&workbook, dir.path().join("STUDY.xlsx")).unwrap();
+
+        assert_eq!(list_source_entries(dir.path(), false).unwrap().len(), 3);
+        let entries = list_source_entries(dir.path(), true).unwrap();
+        let names: Vec<&str> = entries.iter().map(SourceEntry::name).collect();
+        assert_eq!(
+            names,
+            vec!["README", "DM", "AE", "Notes", "STUDY_AE", "STUDY_DM"]
+        );
+    }
+
     #[test]
     fn test_list_csv_files_empty_dir() {
         let dir = TempDir::new().unwrap();
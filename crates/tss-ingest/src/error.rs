@@ -79,6 +79,28 @@ pub enum IngestError {
         max_length: usize,
     },
 
+    // === SAS Dataset Errors ===
+    /// Failed to parse a SAS7BDAT dataset.
+    #[error("failed to parse SAS dataset {path}: {message}")]
+    SasParse { path: PathBuf, message: String },
+
+    /// Failed to parse a SAS Transport (XPT) file.
+    #[error("failed to parse XPT file {path}: {message}")]
+    XptParse { path: PathBuf, message: String },
+
+    // === Excel Workbook Errors ===
+    /// Failed to parse an `.xlsx` workbook.
+    #[error("failed to parse workbook {path}: {message}")]
+    XlsxParse { path: PathBuf, message: String },
+
+    /// Workbook has no sheet with the requested name.
+    #[error("sheet '{sheet}' not found in {path}")]
+    SheetNotFound { path: PathBuf, sheet: String },
+
+    /// Worksheet has no cells.
+    #[error("sheet '{sheet}' in {path} is empty")]
+    EmptySheet { path: PathBuf, sheet: String },
+
     // === Metadata Errors ===
     /// Required column not found in metadata file.
     #[error("required column '{column}' not found in {path}")]
@@ -2,103 +2,221 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
+use chrono::{NaiveDate, NaiveDateTime};
 use polars::prelude::*;
 use tss_standards::{ColumnHint, any_to_string};
 
+/// Non-ISO date formats commonly found in source data.
+const DATE_FORMATS: &[&str] = &["%d/%m/%Y", "%m/%d/%Y", "%d%b%Y", "%d-%b-%Y", "%d %b %Y"];
+
+/// Maximum length of a value that looks like a coded term.
+const CODED_MAX_LEN: usize = 8;
+
+/// Number of distinct values tracked per column for the unique ratio.
+const MAX_TRACKED_UNIQUE: usize = 10_000;
+
 /// Builds column hints from a DataFrame.
 ///
 /// Analyzes each column to determine:
 /// - Whether values are numeric
 /// - Ratio of unique values (cardinality)
 /// - Ratio of null/missing values
+/// - Ratio of values that parse as dates
+/// - Ratio of values that look like coded terms
 pub fn build_column_hints(df: &DataFrame) -> BTreeMap<String, ColumnHint> {
-    let mut hints = BTreeMap::new();
+    let mut accumulator = ColumnHintAccumulator::new();
+    accumulator.update(df);
+    accumulator.finish()
+}
 
-    for col in df.get_columns() {
-        let name = col.name().to_string();
-        let hint = analyze_column_for_hint(col);
-        hints.insert(name, hint);
+/// Builds column hints over a sequence of DataFrames.
+///
+/// Used with streamed sources such as [`read_csv_chunked`], where the data
+/// never sits in memory as one DataFrame. Feeding every chunk to
+/// [`update`] gives the same hints as [`build_column_hints`] on the
+/// concatenated data.
+///
+/// [`read_csv_chunked`]: crate::read_csv_chunked
+/// [`update`]: ColumnHintAccumulator::update
+#[derive(Debug, Clone, Default)]
+pub struct ColumnHintAccumulator {
+    columns: BTreeMap<String, ColumnStats>,
+}
+
+impl ColumnHintAccumulator {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    hints
-}
+    /// Adds the values of every column in `df`.
+    pub fn update(&mut self, df: &DataFrame) {
+        for col in df.get_columns() {
+            self.columns
+                .entry(col.name().to_string())
+                .or_default()
+                .update(col);
+        }
+    }
 
-/// Analyzes a column to create a ColumnHint.
-fn analyze_column_for_hint(col: &Column) -> ColumnHint {
-    let total = col.len();
-    if total == 0 {
-        return ColumnHint {
-            is_numeric: false,
-            unique_ratio: 0.0,
-            null_ratio: 1.0,
-            label: None,
-        };
+    /// Returns the hints for all columns seen so far.
+    pub fn finish(self) -> BTreeMap<String, ColumnHint> {
+        self.columns
+            .into_iter()
+            .map(|(name, stats)| (name, stats.finish()))
+            .collect()
     }
+}
 
-    // Count nulls/empty and unique values
-    let mut null_count = 0usize;
-    let mut unique_values: BTreeSet<String> = BTreeSet::new();
-    let mut numeric_count = 0usize;
-
-    // Cast to string for analysis
-    let str_col = col
-        .cast(&DataType::String)
-        .map(Column::take_materialized_series)
-        .unwrap_or_else(|_| col.as_materialized_series().clone());
-    let str_chunked = str_col.str().ok();
-
-    if let Some(chunked) = str_chunked {
-        for opt_val in chunked.iter() {
-            match opt_val {
-                Some(val) => {
-                    let trimmed = val.trim();
-                    if trimmed.is_empty() {
-                        null_count += 1;
-                    } else {
-                        unique_values.insert(trimmed.to_string());
+/// Running value counts of one column.
+///
+/// Distinct values are counted exactly up to [`MAX_TRACKED_UNIQUE`]. Beyond
+/// that, values outside the tracked set are counted as distinct without
+/// being stored, so the count is an upper bound and memory stays bounded
+/// for high-cardinality columns such as identifiers.
+#[derive(Debug, Clone, Default)]
+struct ColumnStats {
+    total: usize,
+    null_count: usize,
+    unique_values: BTreeSet<String>,
+    untracked_count: usize,
+    numeric_count: usize,
+    date_count: usize,
+    coded_count: usize,
+}
 
-                        // Check if numeric
-                        if trimmed.parse::<f64>().is_ok() {
-                            numeric_count += 1;
+impl ColumnStats {
+    fn update(&mut self, col: &Column) {
+        self.total += col.len();
+
+        // Cast to string for analysis
+        let str_col = col
+            .cast(&DataType::String)
+            .map(Column::take_materialized_series)
+            .unwrap_or_else(|_| col.as_materialized_series().clone());
+        let str_chunked = str_col.str().ok();
+
+        if let Some(chunked) = str_chunked {
+            for opt_val in chunked.iter() {
+                match opt_val {
+                    Some(val) => {
+                        let trimmed = val.trim();
+                        if trimmed.is_empty() {
+                            self.null_count += 1;
+                        } else {
+                            self.add_unique(trimmed);
+
+                            // Check if numeric
+                            if trimmed.parse::<f64>().is_ok() {
+                                self.numeric_count += 1;
+                            }
+                            if looks_like_date(trimmed) {
+                                self.date_count += 1;
+                            }
+                            if looks_like_code(trimmed) {
+                                self.coded_count += 1;
+                            }
                         }
                     }
+                    None => self.null_count += 1,
                 }
-                None => null_count += 1,
             }
-        }
-    } else {
-        // Fallback for non-string columns
-        for i in 0..total {
-            if let Ok(val) = col.get(i) {
-                let s = any_to_string(val);
-                if s.is_empty() {
-                    null_count += 1;
-                } else {
-                    unique_values.insert(s);
+        } else {
+            // Fallback for non-string columns
+            for i in 0..col.len() {
+                if let Ok(val) = col.get(i) {
+                    let s = any_to_string(val);
+                    if s.is_empty() {
+                        self.null_count += 1;
+                    } else {
+                        self.add_unique(&s);
+                    }
                 }
             }
         }
     }
 
-    let non_null = total - null_count;
-    let null_ratio = null_count as f64 / total as f64;
-    let unique_ratio = if non_null > 0 {
-        unique_values.len() as f64 / non_null as f64
-    } else {
-        0.0
-    };
+    fn add_unique(&mut self, value: &str) {
+        if self.unique_values.contains(value) {
+            return;
+        }
+        if self.unique_values.len() < MAX_TRACKED_UNIQUE {
+            self.unique_values.insert(value.to_string());
+        } else {
+            self.untracked_count += 1;
+        }
+    }
+
+    fn finish(self) -> ColumnHint {
+        let total = self.total;
+        if total == 0 {
+            return ColumnHint {
+                is_numeric: false,
+                unique_ratio: 0.0,
+                null_ratio: 1.0,
+                ..Default::default()
+            };
+        }
+
+        let non_null = total - self.null_count;
+        let null_ratio = self.null_count as f64 / total as f64;
+        let ratio = |count: usize| {
+            if non_null > 0 {
+                count as f64 / non_null as f64
+            } else {
+                0.0
+            }
+        };
 
-    // Determine if numeric (>90% of non-null values are numeric)
-    let is_numeric = non_null > 0 && (numeric_count as f64 / non_null as f64) > 0.9;
+        // Determine if numeric (>90% of non-null values are numeric)
+        let is_numeric = non_null > 0 && ratio(self.numeric_count) > 0.9;
 
-    ColumnHint {
-        is_numeric,
-        unique_ratio,
-        null_ratio,
-        label: None,
+        ColumnHint {
+            is_numeric,
+            unique_ratio: ratio(self.unique_values.len() + self.untracked_count),
+            null_ratio,
+            label: None,
+            date_ratio: ratio(self.date_count),
+            coded_ratio: ratio(self.coded_count),
+            format: None,
+        }
     }
 }
 
+/// Whether a value parses as an ISO 8601 date/datetime or a common date format.
+///
+/// Partial ISO 8601 dates (`"2024"`, `"2024-03"`) count as dates; a bare
+/// year must fall between 1900 and 2100.
+fn looks_like_date(value: &str) -> bool {
+    let iso = match value.len() {
+        4 => value
+            .parse::<u16>()
+            .is_ok_and(|year| (1900..=2100).contains(&year)),
+        7 => NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d").is_ok(),
+        10 => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        _ => [
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M:%S%.f",
+        ]
+        .iter()
+        .any(|format| NaiveDateTime::parse_from_str(value, format).is_ok()),
+    };
+    iso || DATE_FORMATS
+        .iter()
+        .any(|format| NaiveDate::parse_from_str(value, format).is_ok())
+}
+
+/// Whether a value looks like a coded term: a short all-caps token such as
+/// `"M"`, `"Y"`, or `"MILD"`.
+fn looks_like_code(value: &str) -> bool {
+    value.len() <= CODED_MAX_LEN
+        && value.bytes().any(|b| b.is_ascii_uppercase())
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_' || b == b'-')
+}
+
 /// Gets sample unique values from a column.
 ///
 /// Returns up to `limit` unique non-empty values.
@@ -158,6 +276,33 @@ fn test_build_column_hints() {
         // Name should not be numeric
         let name_hint = hints.get("name").unwrap();
         assert!(!name_hint.is_numeric);
+        assert_eq!(name_hint.coded_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_dtc_like_column() {
+        let df = df! {
+            "visit_date" => &["2024-01-15", "2024-02", "2024-03-01T09:30", "15/04/2024", "unknown"],
+        }
+        .unwrap();
+
+        let hint = &build_column_hints(&df)["visit_date"];
+        assert!((hint.date_ratio - 0.8).abs() < 0.01);
+        assert_eq!(hint.coded_ratio, 0.0);
+        assert!(!hint.is_numeric);
+    }
+
+    #[test]
+    fn test_sex_like_column() {
+        let df = df! {
+            "gender" => &[Some("M"), Some("F"), Some("F"), None, Some("U")],
+        }
+        .unwrap();
+
+        let hint = &build_column_hints(&df)["gender"];
+        assert!((hint.coded_ratio - 1.0).abs() < 0.01);
+        assert_eq!(hint.date_ratio, 0.0);
+        assert!(hint.unique_ratio < 1.0);
     }
 
     #[test]
@@ -186,4 +331,37 @@ fn test_column_with_nulls() {
         // 2 unique out of 2 non-null
         assert!((hint.unique_ratio - 1.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_accumulator_matches_single_pass() {
+        let df = df! {
+            "gender" => &[Some("M"), Some("F"), Some("F"), None, Some("U"), Some("M")],
+            "age" => &["25", "30", "", "41", "30", "unknown"],
+        }
+        .unwrap();
+
+        let mut accumulator = ColumnHintAccumulator::new();
+        accumulator.update(&df.slice(0, 4));
+        accumulator.update(&df.slice(4, 2));
+
+        // ColumnHint has no PartialEq; compare the debug output instead
+        assert_eq!(
+            format!("{:?}", accumulator.finish()),
+            format!("{:?}", build_column_hints(&df))
+        );
+    }
+
+    #[test]
+    fn test_unique_values_bounded() {
+        let ids: Vec<String> = (0..MAX_TRACKED_UNIQUE * 2)
+            .map(|i| format!("SUBJ-{i}"))
+            .collect();
+        let df = df! { "id" => &ids }.unwrap();
+
+        let mut stats = ColumnStats::default();
+        stats.update(df.column("id").unwrap());
+
+        assert_eq!(stats.unique_values.len(), MAX_TRACKED_UNIQUE);
+        assert!((stats.finish().unique_ratio - 1.0).abs() < 1e-9);
+    }
 }
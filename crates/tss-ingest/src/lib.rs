@@ -1,12 +1,16 @@
 //! SDTM data ingestion utilities.
 //!
 //! This crate provides functionality for discovering, parsing, and loading
-//! clinical trial source data (CSV files) into Polars DataFrames.
+//! clinical trial source data (CSV files, SAS datasets, and Excel workbooks)
+//! into Polars DataFrames.
 //!
 //! # Features
 //!
 //! - **CSV Loading**: Read CSV files with explicit header row configuration
-//! - **File Discovery**: List CSV files in a study folder
+//! - **Dialect Detection**: Sniff delimiter, quote character, and encoding
+//! - **SAS Datasets**: Read `.sas7bdat` and `.xpt` files with their types and labels
+//! - **Excel Workbooks**: Read `.xlsx` sheets as separate tables
+//! - **File Discovery**: List CSV and SAS files, and workbook sheets, in a study folder
 //! - **Metadata Loading**: Load Items.csv for column labels (explicit path)
 //! - **Column Hints**: Extract column statistics for mapping suggestions
 //!
@@ -34,19 +38,35 @@
 mod error;
 mod hints;
 mod metadata;
+mod sas;
+mod source;
+mod xlsx;
 
 // === Error Types ===
 pub use error::{IngestError, Result};
 
 // === CSV Reading ===
 pub use csv::{
-    CsvHeaders, EncodingResult, MAX_CSV_FILE_SIZE, check_file_size, check_file_size_with_limit,
-    check_path_length, detect_and_transcode, read_csv_schema, read_csv_table,
-    validate_dataframe_shape,
+    CsvChunks, CsvDialect, CsvHeaders, DIALECT_SNIFF_BYTES, MAX_CSV_FILE_SIZE, SourceEncoding,
+    check_file_size, check_file_size_with_limit, check_path_length, read_csv_chunked,
+    read_csv_chunked_with_dialect, read_csv_schema, read_csv_schema_with_dialect, read_csv_table,
+    read_csv_table_with_dialect, validate_dataframe_shape,
 };
 
+#[allow(deprecated)]
+pub use csv::{EncodingResult, detect_and_transcode};
+
+// === SAS Datasets ===
+pub use sas::{read_sas7bdat, read_xpt};
+
+// === Excel Workbooks ===
+pub use xlsx::{list_xlsx_sheets, read_xlsx_sheet};
+
+// === Source Files ===
+pub use source::{SourceEntry, SourceFormat, read_source_entry, read_source_table};
+
 // === File Discovery ===
-pub use discovery::list_csv_files;
+pub use discovery::{list_csv_files, list_source_entries, list_source_files, list_xpt_files};
 
 // === Metadata Types ===
 pub use metadata::{
@@ -55,4 +75,4 @@
 };
 
 // === Column Hints ===
-pub use hints::{build_column_hints, get_sample_values};
+pub use hints::{ColumnHintAccumulator, build_column_hints, get_sample_values};
@@ -1,6 +1,6 @@
 //! Application of study metadata to DataFrames.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use polars::prelude::*;
 
@@ -17,10 +17,15 @@ pub struct AppliedStudyMetadata {
     pub derived_columns: BTreeSet<String>,
     /// Codelists that were applied.
     pub applied_codelists: BTreeSet<String>,
+    /// SDTM targets declared for columns: column -> `VARIABLE` or `DOMAIN.VARIABLE`.
+    ///
+    /// Not a DataFrame change; seed mappings from these with
+    /// `MappingState::accept_declared` in `tss-submit`.
+    pub declared_targets: BTreeMap<String, String>,
 }
 
 impl AppliedStudyMetadata {
-    /// Returns true if any changes were applied.
+    /// Returns true if any changes were applied to the DataFrame.
     pub fn has_changes(&self) -> bool {
         !self.decoded_columns.is_empty()
             || !self.derived_columns.is_empty()
@@ -33,6 +38,8 @@ pub fn has_changes(&self) -> bool {
 /// For each column in the DataFrame:
 /// - If the column has an associated codelist in the metadata, decode values
 /// - If a decoded column doesn't exist, create a derived column
+/// - If the column declares an intended SDTM variable, record it in
+///   [`AppliedStudyMetadata::declared_targets`]
 ///
 /// Returns the modified DataFrame and information about what was applied.
 pub fn apply_study_metadata(
@@ -48,6 +55,7 @@ pub fn apply_study_metadata(
         .iter()
         .map(ToString::to_string)
         .collect();
+    applied.declared_targets = metadata.declared_targets(&column_names);
 
     for col_name in &column_names {
         // Look up the item definition
@@ -159,7 +167,11 @@ fn create_test_metadata() -> StudyMetadata {
         let mut meta = StudyMetadata::new();
 
         // Add items
-        meta.add_item(SourceColumn::new("SEXCD", "Sex Code").with_format("SEX"));
+        meta.add_item(
+            SourceColumn::new("SEXCD", "Sex Code")
+                .with_format("SEX")
+                .with_target("DM.SEX"),
+        );
         meta.add_item(SourceColumn::new("RACECD", "Race Code").with_format("RACE"));
 
         // Add codelists
@@ -203,6 +215,8 @@ fn test_apply_study_metadata_creates_derived() {
         assert!(applied.derived_columns.contains("RACE"));
         assert!(applied.applied_codelists.contains("SEX"));
         assert!(applied.applied_codelists.contains("RACE"));
+        assert_eq!(applied.declared_targets["SEXCD"], "DM.SEX");
+        assert!(!applied.declared_targets.contains_key("RACECD"));
     }
 
     #[test]
@@ -35,6 +35,8 @@ pub struct ItemsSchema {
     pub format_name: Option<ColumnRole>,
     /// Content length column: contains numeric lengths.
     pub content_length: Option<ColumnRole>,
+    /// Target column: intended SDTM variables as `DOMAIN.VARIABLE`.
+    pub target_variable: Option<ColumnRole>,
 }
 
 /// Column analysis scores - purely statistical, no hardcoded keywords.
@@ -54,6 +56,8 @@ struct ColumnScores {
     empty_ratio: f64,
     /// Whether all unique values are very short (<=10 chars)
     all_short_values: bool,
+    /// Ratio of non-empty values shaped like `DOMAIN.VARIABLE` (0.0-1.0)
+    qualified_ratio: f64,
 }
 
 /// Analyzes a column and returns statistical scores.
@@ -78,6 +82,7 @@ fn analyze_column(col: &Column, idx: usize) -> Option<ColumnScores> {
     let mut non_null_count = 0usize;
     let mut numeric_count = 0usize;
     let mut empty_count = 0usize;
+    let mut qualified_count = 0usize;
     let mut all_short = true;
 
     for opt_val in str_chunked.iter() {
@@ -98,6 +103,10 @@ fn analyze_column(col: &Column, idx: usize) -> Option<ColumnScores> {
                     if trimmed.parse::<f64>().is_ok() {
                         numeric_count += 1;
                     }
+
+                    if is_qualified_name(trimmed) {
+                        qualified_count += 1;
+                    }
                 }
             }
             None => empty_count += 1,
@@ -116,6 +125,12 @@ fn analyze_column(col: &Column, idx: usize) -> Option<ColumnScores> {
         0.0
     };
 
+    let qualified_ratio = if non_null_count > 0 {
+        qualified_count as f64 / non_null_count as f64
+    } else {
+        0.0
+    };
+
     let empty_ratio = empty_count as f64 / total as f64;
 
     Some(ColumnScores {
@@ -127,9 +142,25 @@ fn analyze_column(col: &Column, idx: usize) -> Option<ColumnScores> {
         cardinality: unique_count,
         empty_ratio,
         all_short_values: all_short && non_null_count > 0,
+        qualified_ratio,
     })
 }
 
+/// Whether a value is shaped like `DOMAIN.VARIABLE` (e.g., `DM.SEX`):
+/// a 2-4 letter prefix and a name of up to 8 characters, both uppercase.
+fn is_qualified_name(value: &str) -> bool {
+    let Some((prefix, name)) = value.split_once('.') else {
+        return false;
+    };
+    (2..=4).contains(&prefix.len())
+        && prefix.bytes().all(|b| b.is_ascii_uppercase())
+        && (1..=8).contains(&name.len())
+        && name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
 /// Detects the schema for an Items.csv file using purely statistical patterns.
 ///
 /// Detection strategy (no hardcoded keywords):
@@ -139,6 +170,7 @@ fn analyze_column(col: &Column, idx: usize) -> Option<ColumnScores> {
 /// - **Mandatory column**: Binary (2-3 unique values), very short
 /// - **FormatName column**: Medium cardinality, many empty values, short
 /// - **ContentLength column**: All numeric, short values
+/// - **Target column**: Values shaped like `DOMAIN.VARIABLE`
 pub fn detect_items_schema(df: &DataFrame, path: &std::path::Path) -> Result<ItemsSchema> {
     let columns = df.get_columns();
     if columns.len() < 2 {
@@ -211,12 +243,25 @@ pub fn detect_items_schema(df: &DataFrame, path: &std::path::Path) -> Result<Ite
         },
     };
 
+    // Target column: (nearly) every value is a qualified SDTM variable name
+    let target_variable = scores
+        .iter()
+        .filter(|s| s.index != id.index && s.index != label.index && s.qualified_ratio > 0.9)
+        .max_by(|a, b| a.qualified_ratio.total_cmp(&b.qualified_ratio))
+        .map(|s| ColumnRole {
+            index: s.index,
+            name: s.name.clone(),
+            confidence: s.qualified_ratio,
+        });
+    let is_target = |s: &ColumnScores| target_variable.as_ref().is_some_and(|t| s.index == t.index);
+
     // DataType column: very low cardinality (2-8 unique values), short values, not ID/Label
     let data_type = scores
         .iter()
         .filter(|s| {
             s.index != id.index
                 && s.index != label.index
+                && !is_target(s)
                 && s.cardinality >= 2
                 && s.cardinality <= 8
                 && s.avg_length < 15.0
@@ -235,6 +280,7 @@ pub fn detect_items_schema(df: &DataFrame, path: &std::path::Path) -> Result<Ite
         .find(|s| {
             s.index != id.index
                 && s.index != label.index
+                && !is_target(s)
                 && data_type.as_ref().is_none_or(|dt| s.index != dt.index)
                 && s.cardinality >= 2
                 && s.cardinality <= 3
@@ -253,6 +299,7 @@ pub fn detect_items_schema(df: &DataFrame, path: &std::path::Path) -> Result<Ite
             s.index != id.index
                 && s.index != label.index
                 && data_type.as_ref().is_none_or(|dt| s.index != dt.index)
+                && !is_target(s)
                 && mandatory.as_ref().is_none_or(|m| s.index != m.index)
                 && s.empty_ratio > 0.2 // Many empty values (not all items have formats)
                 && s.avg_length < 20.0
@@ -273,6 +320,7 @@ pub fn detect_items_schema(df: &DataFrame, path: &std::path::Path) -> Result<Ite
                 && s.index != label.index
                 && data_type.as_ref().is_none_or(|dt| s.index != dt.index)
                 && mandatory.as_ref().is_none_or(|m| s.index != m.index)
+                && !is_target(s)
                 && format_name.as_ref().is_none_or(|f| s.index != f.index)
                 && s.numeric_ratio > 0.9
                 && s.avg_length < 5.0
@@ -290,6 +338,7 @@ pub fn detect_items_schema(df: &DataFrame, path: &std::path::Path) -> Result<Ite
         mandatory,
         format_name,
         content_length,
+        target_variable,
     })
 }
 
@@ -317,6 +366,25 @@ fn test_detect_items_schema() {
 
         assert_eq!(schema.id.name, "ID");
         assert_eq!(schema.label.name, "Label");
+        assert!(schema.target_variable.is_none());
+    }
+
+    #[test]
+    fn test_detect_target_column() {
+        let mut df = create_items_df();
+        df.with_column(Series::new(
+            "SDTM".into(),
+            &["DM.AGE", "DM.SEX", "DM.RACE", ""],
+        ))
+        .unwrap();
+        let path = std::path::Path::new("test_items.csv");
+        let schema = detect_items_schema(&df, path).unwrap();
+
+        assert_eq!(schema.target_variable.unwrap().name, "SDTM");
+        assert_eq!(schema.format_name.unwrap().name, "FormatName");
+        assert!(is_qualified_name("AE.AESTDTC"));
+        assert!(!is_qualified_name("SEX"));
+        assert!(!is_qualified_name("dm.sex"));
     }
 
     #[test]
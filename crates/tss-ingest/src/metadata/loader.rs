@@ -15,8 +15,8 @@
 /// Loads metadata from an explicit Items.csv file path.
 ///
 /// Use this when the user has explicitly selected which file contains column
-/// metadata (via manual source assignment). Only loads column labels - no
-/// controlled terminology processing.
+/// metadata (via manual source assignment). Only loads column definitions,
+/// including any declared SDTM targets - no controlled terminology processing.
 ///
 /// # Arguments
 /// - `items_path`: Path to the Items.csv file
@@ -80,6 +80,10 @@ fn load_items_into(path: &Path, metadata: &mut StudyMetadata, header_rows: usize
         .content_length
         .as_ref()
         .and_then(|c| df.column(&c.name).ok());
+    let target_col = schema
+        .target_variable
+        .as_ref()
+        .and_then(|c| df.column(&c.name).ok());
 
     // Iterate and build SourceColumn entries
     for row_idx in 0..df.height() {
@@ -123,6 +127,14 @@ fn load_items_into(path: &Path, metadata: &mut StudyMetadata, header_rows: usize
             }
         }
 
+        // Intended SDTM variable
+        if let Some(col) = &target_col {
+            let val = any_to_string(col.get(row_idx)?);
+            if !val.is_empty() {
+                item = item.with_target(val);
+            }
+        }
+
         metadata.add_item(item);
     }
 
@@ -173,6 +185,26 @@ fn test_load_items_metadata() {
 
         let race = metadata.get_item("RACE").unwrap();
         assert!(!race.mandatory);
+        assert_eq!(race.target_variable, None);
+    }
+
+    #[test]
+    fn test_load_items_metadata_with_targets() {
+        let dir = TempDir::new().unwrap();
+        let items_content = r#""Item Identifier","Item Label","Data Type","Required","Format Name","SDTM Target"
+"AGE","Age in Years","integer","Yes","","DM.AGE"
+"GENDER","Gender","text","Yes","SEX","DM.SEX"
+"COMMENT","Investigator Comment","text","No","",""
+"#;
+        let items_path = dir.path().join("Items.csv");
+        std::fs::write(&items_path, items_content).unwrap();
+
+        let metadata = load_items_metadata(&items_path, 1).unwrap();
+
+        let gender = metadata.get_item("GENDER").unwrap();
+        assert_eq!(gender.target_variable.as_deref(), Some("DM.SEX"));
+        assert_eq!(gender.format_name.as_deref(), Some("SEX"));
+        assert_eq!(metadata.get_item("COMMENT").unwrap().target_variable, None);
     }
 
     #[test]
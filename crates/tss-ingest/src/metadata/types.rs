@@ -18,6 +18,9 @@ pub struct SourceColumn {
     pub format_name: Option<String>,
     /// Maximum content length (for text fields).
     pub content_length: Option<usize>,
+    /// Intended SDTM variable, as `VARIABLE` or `DOMAIN.VARIABLE`.
+    #[serde(default)]
+    pub target_variable: Option<String>,
 }
 
 impl SourceColumn {
@@ -30,6 +33,7 @@ pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
             mandatory: false,
             format_name: None,
             content_length: None,
+            target_variable: None,
         }
     }
 
@@ -56,6 +60,12 @@ pub fn with_length(mut self, length: usize) -> Self {
         self.content_length = Some(length);
         self
     }
+
+    /// Sets the intended SDTM variable (`AGE` or `DM.AGE`).
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target_variable = Some(target.into());
+        self
+    }
 }
 
 /// A study-specific codelist mapping coded values to decoded text.
@@ -213,6 +223,20 @@ pub fn get_codelist(&self, name: &str) -> Option<&StudyCodelist> {
         self.codelists.get(&name.to_uppercase())
     }
 
+    /// Returns the declared SDTM targets of `columns`: column -> target.
+    ///
+    /// Targets are returned as written in the metadata (`AGE` or `DM.AGE`).
+    pub fn declared_targets<S: AsRef<str>>(&self, columns: &[S]) -> BTreeMap<String, String> {
+        columns
+            .iter()
+            .filter_map(|column| {
+                let column = column.as_ref();
+                let target = self.get_item(column)?.target_variable.clone()?;
+                Some((column.to_string(), target))
+            })
+            .collect()
+    }
+
     /// Returns true if there are no items and no codelists.
     pub fn is_empty(&self) -> bool {
         self.items.is_empty() && self.codelists.is_empty()
@@ -278,6 +302,17 @@ fn test_study_metadata() {
         assert!(meta.get_codelist("sex").is_some());
     }
 
+    #[test]
+    fn test_declared_targets() {
+        let mut meta = StudyMetadata::new();
+        meta.add_item(SourceColumn::new("GENDER", "Gender").with_target("DM.SEX"));
+        meta.add_item(SourceColumn::new("AGE", "Age"));
+
+        let targets = meta.declared_targets(&["gender", "AGE", "OTHER"]);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets["gender"], "DM.SEX");
+    }
+
     #[test]
     fn test_normalize_numeric_key() {
         assert_eq!(normalize_numeric_key("1"), Some("1".to_string()));
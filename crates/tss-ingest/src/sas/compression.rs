@@ -0,0 +1,208 @@
+//! Row decompression for compressed SAS7BDAT datasets.
+//!
+//! SAS compresses each row separately. `COMPRESS=CHAR` uses run-length
+//! encoding (RLE) and `COMPRESS=BINARY` uses Ross Data Compression (RDC).
+
+/// Marker in the first column text block of an RLE-compressed dataset.
+const RLE_LITERAL: &[u8] = b"SASYZCRL";
+
+/// Marker in the first column text block of an RDC-compressed dataset.
+const RDC_LITERAL: &[u8] = b"SASYZCR2";
+
+/// Row compression used by a dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum Compression {
+    #[default]
+    None,
+    Rle,
+    Rdc,
+}
+
+impl Compression {
+    /// Detect the compression from the first column text block.
+    pub(super) fn detect(text_block: &[u8]) -> Self {
+        let contains = |literal: &[u8]| text_block.windows(literal.len()).any(|w| w == literal);
+        if contains(RLE_LITERAL) {
+            Self::Rle
+        } else if contains(RDC_LITERAL) {
+            Self::Rdc
+        } else {
+            Self::None
+        }
+    }
+
+    /// Decompress a row to exactly `row_length` bytes.
+    pub(super) fn decompress(self, input: &[u8], row_length: usize) -> Result<Vec<u8>, String> {
+        let row = match self {
+            Self::None => input.to_vec(),
+            Self::Rle => rle_decompress(input, row_length)?,
+            Self::Rdc => rdc_decompress(input, row_length)?,
+        };
+        if row.len() != row_length {
+            return Err(format!(
+                "decompressed row has {} bytes, expected {}",
+                row.len(),
+                row_length
+            ));
+        }
+        Ok(row)
+    }
+}
+
+/// Reads the byte at `pos`, failing on truncated input.
+fn byte_at(input: &[u8], pos: usize) -> Result<u8, String> {
+    input
+        .get(pos)
+        .copied()
+        .ok_or_else(|| "compressed row ends unexpectedly".to_string())
+}
+
+/// Copies `n` literal bytes from `input` starting at `pos`.
+fn copy_literal(output: &mut Vec<u8>, input: &[u8], pos: usize, n: usize) -> Result<usize, String> {
+    let bytes = input
+        .get(pos..pos + n)
+        .ok_or_else(|| "compressed row ends unexpectedly".to_string())?;
+    output.extend_from_slice(bytes);
+    Ok(pos + n)
+}
+
+/// Decompress an RLE (`COMPRESS=CHAR`) row.
+fn rle_decompress(input: &[u8], row_length: usize) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(row_length);
+    let mut pos = 0;
+
+    while pos < input.len() {
+        let control = input[pos] & 0xF0;
+        let low = usize::from(input[pos] & 0x0F);
+        pos += 1;
+
+        match control {
+            0x00 => {
+                let n = usize::from(byte_at(input, pos)?) + 64 + low * 256;
+                pos = copy_literal(&mut output, input, pos + 1, n)?;
+            }
+            0x40 => {
+                let n = low * 16 + usize::from(byte_at(input, pos)?) + 18;
+                let value = byte_at(input, pos + 1)?;
+                output.resize(output.len() + n, value);
+                pos += 2;
+            }
+            0x60 | 0x70 => {
+                let n = low * 256 + usize::from(byte_at(input, pos)?) + 17;
+                let value = if control == 0x60 { b' ' } else { 0 };
+                output.resize(output.len() + n, value);
+                pos += 1;
+            }
+            0x80 | 0x90 | 0xA0 | 0xB0 => {
+                let n = low + 1 + usize::from((control - 0x80) >> 4) * 16;
+                pos = copy_literal(&mut output, input, pos, n)?;
+            }
+            0xC0 => {
+                let value = byte_at(input, pos)?;
+                output.resize(output.len() + low + 3, value);
+                pos += 1;
+            }
+            0xD0 => output.resize(output.len() + low + 2, b'@'),
+            0xE0 => output.resize(output.len() + low + 2, b' '),
+            0xF0 => output.resize(output.len() + low + 2, 0),
+            _ => return Err(format!("unknown RLE control byte 0x{:02X}", control)),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Decompress an RDC (`COMPRESS=BINARY`) row.
+fn rdc_decompress(input: &[u8], row_length: usize) -> Result<Vec<u8>, String> {
+    let mut output: Vec<u8> = Vec::with_capacity(row_length);
+    let mut pos = 0;
+    let mut ctrl_bits: u16 = 0;
+    let mut ctrl_mask: u16 = 0;
+
+    while pos < input.len() {
+        ctrl_mask >>= 1;
+        if ctrl_mask == 0 {
+            ctrl_bits = u16::from_be_bytes([byte_at(input, pos)?, byte_at(input, pos + 1)?]);
+            ctrl_mask = 0x8000;
+            pos += 2;
+        }
+
+        if ctrl_bits & ctrl_mask == 0 {
+            output.push(byte_at(input, pos)?);
+            pos += 1;
+            continue;
+        }
+
+        let command = usize::from(byte_at(input, pos)? >> 4);
+        let count = usize::from(byte_at(input, pos)? & 0x0F);
+        pos += 1;
+
+        match command {
+            // Short run
+            0 => {
+                let value = byte_at(input, pos)?;
+                output.resize(output.len() + count + 3, value);
+                pos += 1;
+            }
+            // Long run
+            1 => {
+                let n = count + (usize::from(byte_at(input, pos)?) << 4) + 19;
+                let value = byte_at(input, pos + 1)?;
+                output.resize(output.len() + n, value);
+                pos += 2;
+            }
+            // Long and short back-references
+            _ => {
+                let offset = count + 3 + (usize::from(byte_at(input, pos)?) << 4);
+                pos += 1;
+                let n = if command == 2 {
+                    let n = usize::from(byte_at(input, pos)?) + 16;
+                    pos += 1;
+                    n
+                } else {
+                    command
+                };
+                let start = output
+                    .len()
+                    .checked_sub(offset)
+                    .ok_or_else(|| "RDC back-reference before start of row".to_string())?;
+                for i in 0..n {
+                    output.push(output[start + i]);
+                }
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_compression() {
+        assert_eq!(Compression::detect(b"\0\0SASYZCRL  "), Compression::Rle);
+        assert_eq!(Compression::detect(b"\0\0SASYZCR2  "), Compression::Rdc);
+        assert_eq!(Compression::detect(b"\0\0        "), Compression::None);
+    }
+
+    #[test]
+    fn test_rle_decompress() {
+        // 3 literal bytes, 4 spaces, 5 copies of 'x', 2 zero bytes
+        let input = [0x82, b'A', b'B', b'C', 0xE2, 0xC2, b'x', 0xF0];
+        let row = Compression::Rle.decompress(&input, 14).unwrap();
+        assert_eq!(row, b"ABC    xxxxx\0\0");
+
+        let err = Compression::Rle.decompress(&input, 20).unwrap_err();
+        assert!(err.contains("expected 20"));
+    }
+
+    #[test]
+    fn test_rdc_decompress() {
+        // Control bits: literal, literal, literal, short run, short back-reference
+        let input = [0b0001_1000, 0, b'A', b'B', b'C', 0x01, b'-', 0x34, 0x00];
+        let row = Compression::Rdc.decompress(&input, 10).unwrap();
+        assert_eq!(row, b"ABC----ABC");
+    }
+}
@@ -0,0 +1,12 @@
+//! SAS dataset reading.
+//!
+//! Reads raw SAS datasets (SAS7BDAT) and SAS Transport files (XPT) directly,
+//! keeping the numeric/character typing and variable labels that are lost
+//! when converting to CSV first.
+
+mod compression;
+mod reader;
+mod xpt;
+
+pub use reader::read_sas7bdat;
+pub use xpt::read_xpt;
@@ -0,0 +1,573 @@
+//! SAS7BDAT file parsing.
+//!
+//! SAS7BDAT is undocumented; the layout here follows the reverse-engineered
+//! description shared by ReadStat, Parso, and pandas. A file is a header
+//! followed by fixed-size pages. Metadata pages hold subheaders describing
+//! the columns, and data or mixed pages hold the rows.
+
+use std::path::Path;
+
+use polars::prelude::*;
+use tss_standards::ColumnHint;
+
+use crate::csv::{SourceEncoding, check_file_size, check_path_length};
+use crate::error::{IngestError, Result};
+use crate::hints::build_column_hints;
+
+use super::compression::Compression;
+
+/// Magic number at the start of every SAS7BDAT file.
+const MAGIC: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC2, 0xEA, 0x81, 0x60,
+    0xB3, 0x14, 0x11, 0xCF, 0xBD, 0x92, 0x08, 0x00, 0x09, 0xC7, 0x31, 0x8C, 0x18, 0x1F, 0x10, 0x11,
+];
+
+/// Header bytes needed to read the page layout.
+const MIN_HEADER_LENGTH: usize = 288;
+
+// Page types (masked with `PAGE_TYPE_MASK` unless compressed)
+const PAGE_TYPE_MASK: u16 = 0x0F00;
+const PAGE_TYPE_DATA: u16 = 0x0100;
+const PAGE_TYPE_MIX: u16 = 0x0200;
+const PAGE_TYPE_COMP: u16 = 0x9000;
+
+// Subheader signatures
+const SIG_ROW_SIZE: u32 = 0xF7F7_F7F7;
+const SIG_COLUMN_SIZE: u32 = 0xF6F6_F6F6;
+const SIG_COLUMN_TEXT: u32 = 0xFFFF_FFFD;
+const SIG_COLUMN_NAME: u32 = 0xFFFF_FFFF;
+const SIG_COLUMN_ATTRIBUTES: u32 = 0xFFFF_FFFC;
+const SIG_FORMAT_AND_LABEL: u32 = 0xFFFF_FBFE;
+
+// Subheader pointer compression flags
+const SUBHEADER_TRUNCATED: u8 = 1;
+const SUBHEADER_COMPRESSED: u8 = 4;
+const SUBHEADER_TYPE_DATA: u8 = 1;
+
+/// Column storage type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Numeric,
+    Character,
+}
+
+/// Reference to a string in a column text block.
+#[derive(Debug, Clone, Copy)]
+struct TextRef {
+    block: usize,
+    offset: usize,
+    length: usize,
+}
+
+/// Column position and type within a row.
+#[derive(Debug, Clone, Copy)]
+struct ColumnAttributes {
+    offset: usize,
+    length: usize,
+    kind: ColumnKind,
+}
+
+/// A fully described column.
+#[derive(Debug, Clone)]
+struct SasColumn {
+    name: String,
+    label: Option<String>,
+    format: Option<String>,
+    attributes: ColumnAttributes,
+}
+
+/// A column's display format: name, width, and decimals (e.g., DATE9., 8.2).
+#[derive(Debug, Clone, Copy)]
+struct FormatRef {
+    name: Option<TextRef>,
+    width: usize,
+    decimals: usize,
+}
+
+/// Renders a format as SAS writes it (e.g., "DATE9.", "8.2", "$CHAR20.").
+///
+/// Returns `None` when there is neither a name nor a width.
+pub(super) fn format_name(name: &str, width: usize, decimals: usize) -> Option<String> {
+    if name.is_empty() && width == 0 {
+        return None;
+    }
+    let width = if width > 0 {
+        width.to_string()
+    } else {
+        String::new()
+    };
+    let decimals = if decimals > 0 {
+        decimals.to_string()
+    } else {
+        String::new()
+    };
+    Some(format!("{name}{width}.{decimals}"))
+}
+
+/// Reads a byte slice, failing if it runs past the end.
+fn slice(bytes: &[u8], offset: usize, length: usize) -> std::result::Result<&[u8], String> {
+    offset
+        .checked_add(length)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| format!("unexpected end of data at offset {}", offset))
+}
+
+/// Parser state accumulated while walking the pages.
+struct Parser<'a> {
+    little_endian: bool,
+    u64: bool,
+    encoding: SourceEncoding,
+    compression: Compression,
+    text_blocks: Vec<&'a [u8]>,
+    names: Vec<TextRef>,
+    attributes: Vec<ColumnAttributes>,
+    labels: Vec<Option<TextRef>>,
+    formats: Vec<FormatRef>,
+    row_length: usize,
+    row_count: usize,
+    mix_page_row_count: usize,
+    rows: Vec<Vec<u8>>,
+}
+
+impl<'a> Parser<'a> {
+    fn int_length(&self) -> usize {
+        if self.u64 { 8 } else { 4 }
+    }
+
+    /// Reads an unsigned integer of 1, 2, 4, or 8 bytes.
+    fn uint(
+        &self,
+        bytes: &[u8],
+        offset: usize,
+        length: usize,
+    ) -> std::result::Result<usize, String> {
+        let raw = slice(bytes, offset, length)?;
+        let mut buf = [0u8; 8];
+        let value = if self.little_endian {
+            buf[..length].copy_from_slice(raw);
+            u64::from_le_bytes(buf)
+        } else {
+            buf[8 - length..].copy_from_slice(raw);
+            u64::from_be_bytes(buf)
+        };
+        usize::try_from(value).map_err(|_| format!("value {} out of range", value))
+    }
+
+    fn parse(bytes: &'a [u8]) -> std::result::Result<Self, String> {
+        if bytes.len() < MIN_HEADER_LENGTH || bytes[..32] != MAGIC {
+            return Err("not a SAS7BDAT file".to_string());
+        }
+
+        let u64 = bytes[32] == b'3';
+        let align = if bytes[35] == b'3' { 4 } else { 0 };
+        let little_endian = bytes[37] == 0x01;
+        let encoding = match bytes[70] {
+            20 => SourceEncoding::Utf8,
+            // Unspecified, US-ASCII, Latin-1, and Windows Latin-1
+            0 | 28 | 29 | 62 => SourceEncoding::Latin1,
+            code => return Err(format!("unsupported character encoding (code {})", code)),
+        };
+
+        let mut parser = Self {
+            little_endian,
+            u64,
+            encoding,
+            compression: Compression::None,
+            text_blocks: Vec::new(),
+            names: Vec::new(),
+            attributes: Vec::new(),
+            labels: Vec::new(),
+            formats: Vec::new(),
+            row_length: 0,
+            row_count: 0,
+            mix_page_row_count: 0,
+            rows: Vec::new(),
+        };
+
+        let header_length = parser.uint(bytes, 196 + align, 4)?;
+        let page_length = parser.uint(bytes, 200 + align, 4)?;
+        let page_count = parser.uint(bytes, 204 + align, parser.int_length())?;
+        if page_length == 0 {
+            return Err("page length is zero".to_string());
+        }
+
+        for index in 0..page_count {
+            // Saturate so corrupt counts fail as out-of-bounds instead of overflowing
+            let offset = index
+                .saturating_mul(page_length)
+                .saturating_add(header_length);
+            let page = slice(bytes, offset, page_length)?;
+            parser.read_page(page)?;
+            if parser.row_length > 0 && parser.rows.len() >= parser.row_count {
+                break;
+            }
+        }
+
+        Ok(parser)
+    }
+
+    fn read_page(&mut self, page: &'a [u8]) -> std::result::Result<(), String> {
+        let page_bit_offset = if self.u64 { 32 } else { 16 };
+        let pointer_length = if self.u64 { 24 } else { 12 };
+
+        let page_type = self.uint(page, page_bit_offset, 2)? as u16;
+        let block_count = self.uint(page, page_bit_offset + 2, 2)?;
+        let subheader_count = self.uint(page, page_bit_offset + 4, 2)?;
+
+        if page_type == PAGE_TYPE_COMP {
+            return Ok(());
+        }
+
+        let page_type = page_type & PAGE_TYPE_MASK;
+        if page_type != PAGE_TYPE_DATA {
+            for i in 0..subheader_count {
+                self.read_subheader_pointer(page, page_bit_offset + 8 + i * pointer_length)?;
+            }
+        }
+
+        let (start, count) = match page_type {
+            PAGE_TYPE_DATA => (page_bit_offset + 8, block_count),
+            PAGE_TYPE_MIX => {
+                // Rows start on an 8-byte boundary after the subheader pointers
+                let start = page_bit_offset + 8 + subheader_count * pointer_length;
+                (start.next_multiple_of(8), self.mix_page_row_count)
+            }
+            _ => return Ok(()),
+        };
+
+        if self.row_length == 0 {
+            return Err("data page found before row size subheader".to_string());
+        }
+        let count = count.min(self.row_count.saturating_sub(self.rows.len()));
+        for i in 0..count {
+            let offset = i.saturating_mul(self.row_length).saturating_add(start);
+            let row = slice(page, offset, self.row_length)?;
+            self.rows.push(row.to_vec());
+        }
+        Ok(())
+    }
+
+    fn read_subheader_pointer(
+        &mut self,
+        page: &'a [u8],
+        pointer: usize,
+    ) -> std::result::Result<(), String> {
+        let int_length = self.int_length();
+        let offset = self.uint(page, pointer, int_length)?;
+        let length = self.uint(page, pointer + int_length, int_length)?;
+        let flags = slice(page, pointer + 2 * int_length, 2)?;
+        let (compression, subheader_type) = (flags[0], flags[1]);
+
+        if length == 0 || compression == SUBHEADER_TRUNCATED {
+            return Ok(());
+        }
+        let subheader = slice(page, offset, length)?;
+
+        if compression == SUBHEADER_COMPRESSED {
+            let row = self.compression.decompress(subheader, self.row_length)?;
+            self.rows.push(row);
+            return Ok(());
+        }
+
+        // 64-bit big-endian signatures are sign-extended into the first 4 bytes
+        let signature_offset = if self.u64 && !self.little_endian {
+            4
+        } else {
+            0
+        };
+        let signature = self.uint(subheader, signature_offset, 4)? as u32;
+
+        match signature {
+            SIG_ROW_SIZE => {
+                self.row_length = self.uint(subheader, 5 * int_length, int_length)?;
+                self.row_count = self.uint(subheader, 6 * int_length, int_length)?;
+                self.mix_page_row_count = self.uint(subheader, 15 * int_length, int_length)?;
+            }
+            SIG_COLUMN_SIZE => {}
+            SIG_COLUMN_TEXT => {
+                let size = self.uint(subheader, int_length, 2)?;
+                let block = slice(subheader, int_length, size)?;
+                if self.text_blocks.is_empty() {
+                    self.compression = Compression::detect(block);
+                }
+                self.text_blocks.push(block);
+            }
+            SIG_COLUMN_NAME => {
+                let count = length.saturating_sub(2 * int_length + 12) / 8;
+                for i in 0..count {
+                    let base = int_length + 8 * (i + 1);
+                    self.names.push(TextRef {
+                        block: self.uint(subheader, base, 2)?,
+                        offset: self.uint(subheader, base + 2, 2)?,
+                        length: self.uint(subheader, base + 4, 2)?,
+                    });
+                }
+            }
+            SIG_COLUMN_ATTRIBUTES => {
+                let entry_length = int_length + 8;
+                let count = length.saturating_sub(2 * int_length + 12) / entry_length;
+                for i in 0..count {
+                    let base = int_length + 8 + i * entry_length;
+                    let kind = match self.uint(subheader, base + int_length + 6, 1)? {
+                        1 => ColumnKind::Numeric,
+                        _ => ColumnKind::Character,
+                    };
+                    self.attributes.push(ColumnAttributes {
+                        offset: self.uint(subheader, base, int_length)?,
+                        length: self.uint(subheader, base + int_length, 4)?,
+                        kind,
+                    });
+                }
+            }
+            SIG_FORMAT_AND_LABEL => {
+                let base = 3 * int_length;
+                let format_name = TextRef {
+                    block: self.uint(subheader, base + 22, 2)?,
+                    offset: self.uint(subheader, base + 24, 2)?,
+                    length: self.uint(subheader, base + 26, 2)?,
+                };
+                self.formats.push(FormatRef {
+                    name: (format_name.length > 0).then_some(format_name),
+                    width: self.uint(subheader, base + 8, 2)?,
+                    decimals: self.uint(subheader, base + 10, 2)?,
+                });
+                let label = TextRef {
+                    block: self.uint(subheader, base + 28, 2)?,
+                    offset: self.uint(subheader, base + 30, 2)?,
+                    length: self.uint(subheader, base + 32, 2)?,
+                };
+                self.labels.push((label.length > 0).then_some(label));
+            }
+            _ if subheader_type == SUBHEADER_TYPE_DATA && length == self.row_length => {
+                self.rows.push(subheader.to_vec());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Resolves a text reference to a trimmed string.
+    fn text(&self, text: TextRef) -> std::result::Result<String, String> {
+        let block = self
+            .text_blocks
+            .get(text.block)
+            .ok_or_else(|| format!("missing column text block {}", text.block))?;
+        let raw = slice(block, text.offset, text.length)?;
+        Ok(self
+            .encoding
+            .decode(raw)
+            .trim_end_matches([' ', '\0'])
+            .to_string())
+    }
+
+    /// Renders a column's format, or `None` if it has none.
+    fn format(&self, format: FormatRef) -> std::result::Result<Option<String>, String> {
+        let name = match format.name {
+            Some(name) => self.text(name)?,
+            None => String::new(),
+        };
+        Ok(format_name(&name, format.width, format.decimals))
+    }
+
+    fn columns(&self) -> std::result::Result<Vec<SasColumn>, String> {
+        if self.names.len() != self.attributes.len() {
+            return Err(format!(
+                "found {} column names but {} column attributes",
+                self.names.len(),
+                self.attributes.len()
+            ));
+        }
+
+        self.names
+            .iter()
+            .zip(&self.attributes)
+            .enumerate()
+            .map(|(i, (&name, &attributes))| {
+                let label = match self.labels.get(i).copied().flatten() {
+                    Some(label) => Some(self.text(label)?).filter(|l| !l.is_empty()),
+                    None => None,
+                };
+                let format = match self.formats.get(i).copied() {
+                    Some(format) => self.format(format)?,
+                    None => None,
+                };
+                Ok(SasColumn {
+                    name: self.text(name)?,
+                    label,
+                    format,
+                    attributes,
+                })
+            })
+            .collect()
+    }
+
+    /// Decodes a numeric value, treating SAS missing values (NaN) as null.
+    fn numeric(&self, raw: &[u8]) -> Option<f64> {
+        // Short numerics keep the most significant bytes of the double
+        let mut buf = [0u8; 8];
+        let value = if self.little_endian {
+            buf[8 - raw.len()..].copy_from_slice(raw);
+            f64::from_le_bytes(buf)
+        } else {
+            buf[..raw.len()].copy_from_slice(raw);
+            f64::from_be_bytes(buf)
+        };
+        (!value.is_nan()).then_some(value)
+    }
+
+    /// Decodes a character value, treating blanks as null.
+    fn character(&self, raw: &[u8]) -> Option<String> {
+        let value = self.encoding.decode(raw);
+        let value = value.trim_end_matches([' ', '\0']);
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn column_values(&self, column: &SasColumn) -> std::result::Result<Column, String> {
+        let ColumnAttributes {
+            offset,
+            length,
+            kind,
+        } = column.attributes;
+        let name = PlSmallStr::from(column.name.as_str());
+
+        match kind {
+            ColumnKind::Numeric => {
+                if !(1..=8).contains(&length) {
+                    return Err(format!(
+                        "numeric column {} has invalid length {}",
+                        column.name, length
+                    ));
+                }
+                let values = self
+                    .rows
+                    .iter()
+                    .map(|row| slice(row, offset, length).map(|raw| self.numeric(raw)))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(Column::new(name, values))
+            }
+            ColumnKind::Character => {
+                let values = self
+                    .rows
+                    .iter()
+                    .map(|row| slice(row, offset, length).map(|raw| self.character(raw)))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(Column::new(name, values))
+            }
+        }
+    }
+}
+
+/// Reads a SAS7BDAT dataset into a Polars DataFrame.
+///
+/// Numeric columns become `Float64` and character columns `String`, as
+/// declared in the dataset metadata. SAS missing values and blank strings
+/// are read as null.
+///
+/// Returns the DataFrame and one [`ColumnHint`] per column, in column order,
+/// with the SAS variable label in [`ColumnHint::label`] and its display
+/// format in [`ColumnHint::format`].
+pub fn read_sas7bdat(path: &Path) -> Result<(DataFrame, Vec<ColumnHint>)> {
+    check_path_length(path)?;
+    check_file_size(path)?;
+
+    let bytes = std::fs::read(path).map_err(|e| IngestError::FileRead {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let parse_error = |message: String| IngestError::SasParse {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    let parser = Parser::parse(&bytes).map_err(parse_error)?;
+    let columns = parser.columns().map_err(parse_error)?;
+    let values = columns
+        .iter()
+        .map(|column| parser.column_values(column))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(parse_error)?;
+    let df = DataFrame::new(values)?;
+
+    tracing::info!(
+        path = %path.display(),
+        rows = df.height(),
+        columns = df.width(),
+        compression = ?parser.compression,
+        "Read SAS dataset"
+    );
+
+    let mut hints = build_column_hints(&df);
+    let hints = columns
+        .into_iter()
+        .map(|column| {
+            let mut hint = hints.remove(&column.name).unwrap_or_default();
+            hint.is_numeric = column.attributes.kind == ColumnKind::Numeric;
+            hint.label = column.label;
+            hint.format = column.format;
+            hint
+        })
+        .collect();
+
+    Ok((df, hints))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data")
+            .join(name)
+    }
+
+    #[test]
+    fn test_read_sas7bdat() {
+        let (df, hints) = read_sas7bdat(&fixture("dm.sas7bdat")).unwrap();
+
+        assert_eq!(df.height(), 3);
+        assert_eq!(
+            df.get_column_names(),
+            vec!["USUBJID", "AGE", "WEIGHT", "INVNAM"]
+        );
+        assert_eq!(df.column("AGE").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("USUBJID").unwrap().dtype(), &DataType::String);
+
+        let age = df.column("AGE").unwrap().f64().unwrap();
+        assert_eq!(age.get(1), Some(58.0));
+        let weight = df.column("WEIGHT").unwrap().f64().unwrap();
+        assert_eq!(weight.get(0), Some(72.5));
+        assert_eq!(weight.get(1), None);
+        let names = df.column("INVNAM").unwrap().str().unwrap();
+        assert_eq!(names.get(0), Some("M\u{fc}ller"));
+        assert_eq!(names.get(1), Some("Gon\u{e7}alves"));
+        assert_eq!(names.get(2), None);
+
+        assert_eq!(hints.len(), 4);
+        assert_eq!(hints[0].label.as_deref(), Some("Unique Subject Identifier"));
+        assert!(!hints[0].is_numeric);
+        assert!(hints[2].is_numeric);
+        assert_eq!(hints[2].label.as_deref(), Some("Weight (kg)"));
+        assert!((hints[3].null_ratio - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_read_sas7bdat_formats() {
+        let (df, hints) = read_sas7bdat(&fixture("dm_formats.sas7bdat")).unwrap();
+
+        assert_eq!(df.height(), 3);
+        assert_eq!(hints[0].format.as_deref(), Some("$CHAR12."));
+        assert_eq!(hints[1].format, None);
+        assert_eq!(hints[2].format.as_deref(), Some("8.2"));
+    }
+
+    #[test]
+    fn test_read_sas7bdat_rejects_other_files() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &[b'A'; 512]).unwrap();
+
+        let result = read_sas7bdat(file.path());
+        assert!(matches!(result, Err(IngestError::SasParse { .. })));
+    }
+}
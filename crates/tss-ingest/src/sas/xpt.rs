@@ -0,0 +1,172 @@
+//! SAS Transport (XPT) version 5 file reading.
+//!
+//! Parsing is left to `xportrs`, which the export side already uses to write
+//! these files. Only the first member is read, as submission datasets hold
+//! one dataset per file.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use polars::prelude::*;
+use tss_standards::ColumnHint;
+use xportrs::{ColumnData, Xpt};
+
+use super::reader::format_name;
+use crate::csv::{check_file_size, check_path_length};
+use crate::error::{IngestError, Result};
+use crate::hints::build_column_hints;
+
+/// Length of an XPT record. Observations are padded with blanks to a
+/// multiple of it.
+const RECORD_LEN: usize = 80;
+
+/// Header record that opens the next member.
+const MEMBER_HEADER: &[u8] = b"HEADER RECORD*******MEMBER  HEADER RECORD!!!!!!!";
+
+/// Counts the all-blank observations following the `rows_read` already read.
+///
+/// `xportrs` stops at the first all-blank observation, taking it for the
+/// padding of the last record. Padding is always shorter than a record, so
+/// blank observations reaching past it are genuine records with every value
+/// missing. A blank observation followed by more data can't be padding
+/// either, and would be dropped along with the rest, so it is an error.
+fn trailing_blank_rows(
+    path: &Path,
+    obs_offset: u64,
+    row_len: usize,
+    rows_read: usize,
+) -> std::result::Result<usize, String> {
+    if row_len == 0 {
+        return Ok(0);
+    }
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(obs_offset + (row_len * rows_read) as u64))
+        .map_err(|e| e.to_string())?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).map_err(|e| e.to_string())?;
+
+    let end = tail
+        .windows(MEMBER_HEADER.len())
+        .position(|window| window == MEMBER_HEADER)
+        .unwrap_or(tail.len());
+    let tail = &tail[..end];
+    if tail.iter().any(|&b| b != b' ') {
+        return Err(format!(
+            "observation {} is blank but more observations follow it",
+            rows_read + 1
+        ));
+    }
+    Ok(if tail.len() < RECORD_LEN {
+        0
+    } else {
+        (tail.len() - RECORD_LEN) / row_len + 1
+    })
+}
+
+/// Converts a column read by `xportrs`, padding it with `blank_rows` nulls.
+fn to_column(column: &xportrs::Column, blank_rows: usize) -> std::result::Result<Column, String> {
+    let name = PlSmallStr::from(column.name());
+    match column.data() {
+        ColumnData::F64(values) => Ok(Column::new(
+            name,
+            values
+                .iter()
+                .copied()
+                .chain(std::iter::repeat_n(None, blank_rows))
+                .collect::<Vec<_>>(),
+        )),
+        ColumnData::String(values) => Ok(Column::new(
+            name,
+            values
+                .iter()
+                .cloned()
+                .chain(std::iter::repeat_n(None, blank_rows))
+                .collect::<Vec<_>>(),
+        )),
+        other => Err(format!("unexpected {other} data in {}", column.name())),
+    }
+}
+
+/// Reads a SAS Transport (XPT v5) file into a Polars DataFrame.
+///
+/// Numeric columns become `Float64` and character columns `String`. SAS
+/// missing values and blank strings are read as null. Character data is
+/// decoded as UTF-8, replacing invalid bytes.
+///
+/// Returns the DataFrame and one [`ColumnHint`] per column, in column order,
+/// with the variable label in [`ColumnHint::label`] and its display format
+/// in [`ColumnHint::format`].
+pub fn read_xpt(path: &Path) -> Result<(DataFrame, Vec<ColumnHint>)> {
+    check_path_length(path)?;
+    check_file_size(path)?;
+
+    let parse_error = |message: String| IngestError::XptParse {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    let reader = Xpt::reader(path).map_err(|e| parse_error(e.to_string()))?;
+    let member = reader
+        .info()
+        .members
+        .first()
+        .ok_or_else(|| parse_error("file contains no members".to_string()))?;
+    let (obs_offset, row_len) = (member.obs_offset, member.row_len);
+    // `xportrs` drops formats without a name, such as "8.2", so they are
+    // taken from the NAMESTR records. Character format names may be stored
+    // without their `$`.
+    let formats: Vec<Option<String>> = member
+        .variables
+        .iter()
+        .map(|variable| {
+            let name = variable.nform.trim();
+            let has_format = !name.is_empty() || variable.nfl > 0;
+            let name = if has_format && variable.xpt_type().is_character() && !name.starts_with('$')
+            {
+                format!("${name}")
+            } else {
+                name.to_string()
+            };
+            format_name(
+                &name,
+                usize::try_from(variable.nfl).unwrap_or(0),
+                usize::try_from(variable.nfd).unwrap_or(0),
+            )
+        })
+        .collect();
+    let dataset = reader.read().map_err(|e| parse_error(e.to_string()))?;
+    let blank_rows =
+        trailing_blank_rows(path, obs_offset, row_len, dataset.nrows()).map_err(parse_error)?;
+
+    let columns = dataset
+        .columns()
+        .iter()
+        .map(|column| to_column(column, blank_rows))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(parse_error)?;
+    let df = DataFrame::new(columns)?;
+
+    tracing::info!(
+        path = %path.display(),
+        rows = df.height(),
+        columns = df.width(),
+        "Read XPT dataset"
+    );
+
+    let mut hints = build_column_hints(&df);
+    let hints = dataset
+        .columns()
+        .iter()
+        .zip(formats)
+        .map(|(column, format)| {
+            let mut hint = hints.remove(column.name()).unwrap_or_default();
+            hint.is_numeric = column.is_numeric();
+            hint.label = column.label().map(str::to_string);
+            hint.format = format;
+            hint
+        })
+        .collect();
+
+    Ok((df, hints))
+}
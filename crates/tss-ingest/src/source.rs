@@ -0,0 +1,180 @@
+//! Source file formats.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use polars::prelude::*;
+use tss_standards::ColumnHint;
+
+use crate::csv::read_csv_table;
+use crate::error::{IngestError, Result};
+use crate::hints::build_column_hints;
+use crate::sas::read_sas7bdat;
+use crate::xlsx::{list_xlsx_sheets, read_xlsx_sheet};
+
+/// Format of a source data file, determined by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    /// Comma (or semicolon/tab) separated values.
+    Csv,
+    /// SAS dataset.
+    Sas7bdat,
+    /// Excel workbook, one table per sheet.
+    Xlsx,
+}
+
+impl SourceFormat {
+    /// Detect the format from a file extension (case-insensitive).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        if ext.eq_ignore_ascii_case("csv") {
+            Some(Self::Csv)
+        } else if ext.eq_ignore_ascii_case("sas7bdat") {
+            Some(Self::Sas7bdat)
+        } else if ext.eq_ignore_ascii_case("xlsx") {
+            Some(Self::Xlsx)
+        } else {
+            None
+        }
+    }
+
+    /// Whether files of this format hold several tables (sheets).
+    pub fn is_workbook(self) -> bool {
+        matches!(self, Self::Xlsx)
+    }
+}
+
+/// A discoverable source table: a file, or one sheet of a workbook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceEntry {
+    /// Path to the source file.
+    pub path: PathBuf,
+    /// Sheet name for workbook entries.
+    pub sheet: Option<String>,
+}
+
+impl SourceEntry {
+    /// Creates an entry for a whole file.
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            sheet: None,
+        }
+    }
+
+    /// Creates an entry for one sheet of a workbook.
+    pub fn sheet(path: impl Into<PathBuf>, sheet: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            sheet: Some(sheet.into()),
+        }
+    }
+
+    /// Name used for domain matching: the sheet name, or the file stem.
+    pub fn name(&self) -> &str {
+        match &self.sheet {
+            Some(sheet) => sheet,
+            None => self
+                .path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for SourceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        match &self.sheet {
+            Some(sheet) => write!(f, "{} [{}]", file, sheet),
+            None => write!(f, "{}", file),
+        }
+    }
+}
+
+/// Reads a CSV, SAS7BDAT, or single-sheet source file with its column hints.
+///
+/// `header_rows` applies to CSV files and worksheets; SAS datasets carry
+/// their own variable names and labels. Workbooks are read from their first
+/// sheet; use [`read_source_entry`] to pick another.
+pub fn read_source_table(
+    path: &Path,
+    header_rows: usize,
+) -> Result<(DataFrame, BTreeMap<String, ColumnHint>)> {
+    read_source_entry(&SourceEntry::file(path), header_rows)
+}
+
+/// Reads a source entry (file or workbook sheet) with its column hints.
+pub fn read_source_entry(
+    entry: &SourceEntry,
+    header_rows: usize,
+) -> Result<(DataFrame, BTreeMap<String, ColumnHint>)> {
+    let path = entry.path.as_path();
+    match SourceFormat::from_path(path) {
+        Some(SourceFormat::Sas7bdat) => {
+            let (df, hints) = read_sas7bdat(path)?;
+            let names = df.get_column_names().into_iter().map(ToString::to_string);
+            let hints = names.zip(hints).collect();
+            Ok((df, hints))
+        }
+        Some(SourceFormat::Xlsx) => {
+            let sheet = match &entry.sheet {
+                Some(sheet) => sheet.clone(),
+                None => list_xlsx_sheets(path)?.into_iter().next().ok_or_else(|| {
+                    IngestError::XlsxParse {
+                        path: path.to_path_buf(),
+                        message: "workbook has no sheets".to_string(),
+                    }
+                })?,
+            };
+            let (df, _headers) = read_xlsx_sheet(path, &sheet, header_rows)?;
+            let hints = build_column_hints(&df);
+            Ok((df, hints))
+        }
+        _ => {
+            let (df, _headers) = read_csv_table(path, header_rows)?;
+            let hints = build_column_hints(&df);
+            Ok((df, hints))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_format_from_path() {
+        assert_eq!(
+            SourceFormat::from_path(Path::new("DM.csv")),
+            Some(SourceFormat::Csv)
+        );
+        assert_eq!(
+            SourceFormat::from_path(Path::new("dm.SAS7BDAT")),
+            Some(SourceFormat::Sas7bdat)
+        );
+        assert_eq!(
+            SourceFormat::from_path(Path::new("study.xlsx")),
+            Some(SourceFormat::Xlsx)
+        );
+        assert_eq!(SourceFormat::from_path(Path::new("dm.xpt")), None);
+        assert_eq!(SourceFormat::from_path(Path::new("README")), None);
+    }
+
+    #[test]
+    fn test_source_entry_name() {
+        let file = SourceEntry::file("data/STUDY_AE.csv");
+        assert_eq!(file.name(), "STUDY_AE");
+        assert_eq!(file.to_string(), "STUDY_AE.csv");
+
+        let sheet = SourceEntry::sheet("data/study.xlsx", "DM");
+        assert_eq!(sheet.name(), "DM");
+        assert_eq!(sheet.to_string(), "study.xlsx [DM]");
+    }
+}
@@ -0,0 +1,9 @@
+//! Excel workbook (`.xlsx`) reading.
+//!
+//! Data managers often deliver one workbook with a sheet per domain. Each
+//! sheet can be listed and read like a separate CSV file.
+
+mod reader;
+mod workbook;
+
+pub use reader::{list_xlsx_sheets, read_xlsx_sheet};
@@ -0,0 +1,220 @@
+//! Worksheet reading into Polars DataFrames.
+
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+
+use polars::prelude::*;
+
+use crate::csv::{CsvHeaders, check_file_size, check_path_length, normalize_header};
+use crate::error::{IngestError, Result};
+
+use super::workbook::{Grid, Workbook};
+
+/// Opens a workbook, mapping I/O and package errors.
+fn open_workbook(path: &Path) -> Result<Workbook<File>> {
+    check_path_length(path)?;
+    check_file_size(path)?;
+
+    let file = File::open(path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            IngestError::FileNotFound {
+                path: path.to_path_buf(),
+            }
+        } else {
+            IngestError::FileRead {
+                path: path.to_path_buf(),
+                source: e,
+            }
+        }
+    })?;
+
+    Workbook::new(file).map_err(|message| IngestError::XlsxParse {
+        path: path.to_path_buf(),
+        message,
+    })
+}
+
+/// Lists the sheet names of an `.xlsx` workbook, in workbook order.
+pub fn list_xlsx_sheets(path: &Path) -> Result<Vec<String>> {
+    Ok(open_workbook(path)?.sheet_names())
+}
+
+/// Drops trailing empty rows and columns, and pads rows to a common width.
+fn trim_grid(mut grid: Grid) -> Grid {
+    for row in &mut grid {
+        while row.last().is_some_and(|cell| cell.trim().is_empty()) {
+            row.pop();
+        }
+    }
+    while grid.last().is_some_and(Vec::is_empty) {
+        grid.pop();
+    }
+
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut grid {
+        row.resize(width, String::new());
+    }
+    grid
+}
+
+/// Writes a grid as CSV text, quoting every field.
+fn grid_to_csv(grid: &Grid) -> String {
+    let mut csv = String::new();
+    for row in grid {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|cell| format!("\"{}\"", cell.replace('"', "\"\"")))
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Reads one sheet of an `.xlsx` workbook into a Polars DataFrame.
+///
+/// - `header_rows = 1`: Single header row
+/// - `header_rows = 2`: Double header (labels + column names)
+///
+/// Trailing empty rows and columns are trimmed. Cells with a date or time
+/// number format are read as ISO 8601 strings; other cells keep their
+/// displayed value, and column types are inferred as for [`read_csv_table`].
+///
+/// [`read_csv_table`]: crate::read_csv_table
+pub fn read_xlsx_sheet(
+    path: &Path,
+    sheet: &str,
+    header_rows: usize,
+) -> Result<(DataFrame, CsvHeaders)> {
+    let mut workbook = open_workbook(path)?;
+    let grid = workbook
+        .read_sheet(sheet)
+        .map_err(|message| IngestError::XlsxParse {
+            path: path.to_path_buf(),
+            message,
+        })?
+        .ok_or_else(|| IngestError::SheetNotFound {
+            path: path.to_path_buf(),
+            sheet: sheet.to_string(),
+        })?;
+
+    let grid = trim_grid(grid);
+    if grid.is_empty() {
+        return Err(IngestError::EmptySheet {
+            path: path.to_path_buf(),
+            sheet: sheet.to_string(),
+        });
+    }
+
+    let normalize = |row: &Vec<String>| -> Vec<String> {
+        row.iter().map(|cell| normalize_header(cell)).collect()
+    };
+    let headers = match header_rows {
+        2 if grid.len() >= 2 => CsvHeaders::double(normalize(&grid[0]), normalize(&grid[1])),
+        _ => {
+            let columns = normalize(&grid[0]);
+            if columns.iter().all(String::is_empty) {
+                return Err(IngestError::NoHeaderDetected {
+                    path: path.to_path_buf(),
+                });
+            }
+            CsvHeaders::single(columns)
+        }
+    };
+
+    // Skip additional rows beyond the first header row
+    let skip_rows = header_rows.saturating_sub(1);
+
+    let csv = grid_to_csv(&grid);
+    let df = CsvReadOptions::default()
+        .with_has_header(true)
+        .with_skip_rows(skip_rows)
+        .with_infer_schema_length(None)
+        .into_reader_with_file_handle(Cursor::new(csv.as_bytes()))
+        .finish()
+        .map_err(|e| IngestError::CsvParse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+    tracing::info!(
+        path = %path.display(),
+        sheet,
+        rows = df.height(),
+        columns = df.width(),
+        "Read worksheet"
+    );
+
+    Ok((df, headers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/study.xlsx")
+    }
+
+    #[test]
+    fn test_list_xlsx_sheets() {
+        let sheets = list_xlsx_sheets(&fixture()).unwrap();
+        assert_eq!(sheets, vec!["DM", "AE", "Notes"]);
+    }
+
+    #[test]
+    fn test_read_xlsx_sheet_single_header() {
+        let (df, headers) = read_xlsx_sheet(&fixture(), "DM", 1).unwrap();
+
+        // Trailing empty column and rows are trimmed
+        assert_eq!(headers.columns, vec!["USUBJID", "AGE", "SEX", "RFSTDTC"]);
+        assert_eq!(df.width(), 4);
+        assert_eq!(df.height(), 2);
+
+        let age = df.column("AGE").unwrap().i64().unwrap();
+        assert_eq!(age.get(0), Some(34));
+        let sex = df.column("SEX").unwrap().str().unwrap();
+        assert_eq!(sex.get(1), Some("F"));
+        let start = df.column("RFSTDTC").unwrap().str().unwrap();
+        assert_eq!(start.get(0), Some("2024-01-01"));
+    }
+
+    #[test]
+    fn test_read_xlsx_sheet_double_header() {
+        let (df, headers) = read_xlsx_sheet(&fixture(), "AE", 2).unwrap();
+
+        assert_eq!(headers.columns, vec!["USUBJID", "AETERM"]);
+        assert_eq!(
+            headers.labels.as_ref().unwrap(),
+            &vec!["Subject", "Reported Term"]
+        );
+        assert_eq!(df.height(), 2);
+        let terms = df.column("AETERM").unwrap().str().unwrap();
+        assert_eq!(terms.get(0), Some("Headache & nausea"));
+        // Rich text runs are joined
+        assert_eq!(terms.get(1), Some("Dizziness"));
+    }
+
+    #[test]
+    fn test_read_xlsx_sheet_errors() {
+        let result = read_xlsx_sheet(&fixture(), "LB", 1);
+        assert!(matches!(result, Err(IngestError::SheetNotFound { .. })));
+
+        let result = read_xlsx_sheet(&fixture(), "Notes", 1);
+        assert!(matches!(result, Err(IngestError::EmptySheet { .. })));
+    }
+
+    #[test]
+    fn test_trim_grid() {
+        let grid = vec![
+            vec!["A".to_string(), "B".to_string(), String::new()],
+            vec!["1".to_string()],
+            vec![String::new(), " ".to_string()],
+        ];
+        let trimmed = trim_grid(grid);
+        assert_eq!(trimmed.len(), 2);
+        assert_eq!(trimmed[1], vec!["1".to_string(), String::new()]);
+    }
+}
@@ -0,0 +1,572 @@
+//! Minimal Office Open XML workbook parsing.
+//!
+//! Reads just enough of an `.xlsx` package to turn worksheets into a grid of
+//! cell strings: the sheet list, relationships, shared strings, and the
+//! number formats needed to recognise date and time cells.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// Worksheet cells as rows of strings (empty string for blank cells).
+pub(super) type Grid = Vec<Vec<String>>;
+
+/// Built-in number formats that display dates (with or without a time).
+const BUILTIN_DATE_FORMATS: [u32; 10] = [14, 15, 16, 17, 22, 27, 30, 36, 50, 57];
+
+/// Built-in number formats that display only a time.
+const BUILTIN_TIME_FORMATS: [u32; 7] = [18, 19, 20, 21, 45, 46, 47];
+
+/// Maximum number of rows in a worksheet (the Excel limit).
+const MAX_ROWS: usize = 1_048_576;
+
+/// Maximum decompressed size of a package part, so a small compressed file
+/// can't expand without bound.
+const MAX_PART_BYTES: u64 = 512 * 1024 * 1024;
+
+/// How a cell style displays serial numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SerialFormat {
+    /// A date, or a date and time.
+    Date,
+    /// A time of day.
+    Time,
+}
+
+/// An open workbook with its sheet index and shared lookup tables.
+pub(super) struct Workbook<R> {
+    archive: ZipArchive<R>,
+    /// Sheet names and their part paths, in workbook order.
+    sheets: Vec<(String, String)>,
+    shared_strings: Vec<String>,
+    /// The serial format of each cell style (`s` attribute), if any.
+    date_styles: Vec<Option<SerialFormat>>,
+    /// Whether serial dates count from 1904 instead of 1900.
+    date_1904: bool,
+}
+
+impl<R: Read + Seek> Workbook<R> {
+    pub(super) fn new(reader: R) -> Result<Self, String> {
+        let mut archive = ZipArchive::new(reader).map_err(|e| e.to_string())?;
+
+        let workbook = read_part(&mut archive, "xl/workbook.xml")?
+            .ok_or_else(|| "missing xl/workbook.xml".to_string())?;
+        let relationships = read_part(&mut archive, "xl/_rels/workbook.xml.rels")?
+            .map(|xml| parse_relationships(&xml))
+            .transpose()?
+            .unwrap_or_default();
+        let (sheet_ids, date_1904) = parse_workbook(&workbook)?;
+
+        let sheets = sheet_ids
+            .into_iter()
+            .filter_map(|(name, id)| {
+                let target = relationships.get(&id)?;
+                let part = match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_string(),
+                    None => format!("xl/{}", target),
+                };
+                Some((name, part))
+            })
+            .collect();
+
+        let shared_strings = read_part(&mut archive, "xl/sharedStrings.xml")?
+            .map(|xml| parse_shared_strings(&xml))
+            .transpose()?
+            .unwrap_or_default();
+        let date_styles = read_part(&mut archive, "xl/styles.xml")?
+            .map(|xml| parse_date_styles(&xml))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            archive,
+            sheets,
+            shared_strings,
+            date_styles,
+            date_1904,
+        })
+    }
+
+    /// Sheet names in workbook order.
+    pub(super) fn sheet_names(&self) -> Vec<String> {
+        self.sheets.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Reads a sheet's cells, or `None` if there is no sheet with that name.
+    pub(super) fn read_sheet(&mut self, name: &str) -> Result<Option<Grid>, String> {
+        let Some((_, part)) = self.sheets.iter().find(|(sheet, _)| sheet == name) else {
+            return Ok(None);
+        };
+        let part = part.clone();
+        let xml = read_part(&mut self.archive, &part)?
+            .ok_or_else(|| format!("missing worksheet part {}", part))?;
+        self.parse_sheet(&xml).map(Some)
+    }
+
+    fn parse_sheet(&self, xml: &str) -> Result<Grid, String> {
+        let mut reader = Reader::from_str(xml);
+        let mut grid: Grid = Vec::new();
+
+        let mut row = 0usize;
+        let mut next_row = 0usize;
+        let mut next_col = 0usize;
+        let mut cell: Option<Cell> = None;
+        let mut capture = false;
+        let mut phonetic = false;
+        let mut value = String::new();
+
+        loop {
+            match reader.read_event().map_err(|e| e.to_string())? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"row" => {
+                        row = row_index(&e)?.unwrap_or(next_row);
+                        if row >= MAX_ROWS {
+                            return Err(format!(
+                                "row {} exceeds the worksheet limit of {} rows",
+                                row + 1,
+                                MAX_ROWS
+                            ));
+                        }
+                        next_row = row + 1;
+                        next_col = 0;
+                    }
+                    b"c" => {
+                        let c = Cell::from_element(&e, next_col)?;
+                        next_col = c.col + 1;
+                        cell = Some(c);
+                        value.clear();
+                    }
+                    b"v" | b"t" if cell.is_some() && !phonetic => capture = true,
+                    b"rPh" => phonetic = true,
+                    _ => {}
+                },
+                Event::Empty(e) => match e.local_name().as_ref() {
+                    b"row" => {
+                        next_row = row_index(&e)?.unwrap_or(next_row) + 1;
+                    }
+                    b"c" => next_col = Cell::from_element(&e, next_col)?.col + 1,
+                    _ => {}
+                },
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"v" | b"t" => capture = false,
+                    b"rPh" => phonetic = false,
+                    b"c" => {
+                        if let Some(c) = cell.take() {
+                            let text = self.cell_text(&c, &value)?;
+                            if !text.is_empty() {
+                                if grid.len() <= row {
+                                    grid.resize_with(row + 1, Vec::new);
+                                }
+                                let cells = &mut grid[row];
+                                if cells.len() <= c.col {
+                                    cells.resize(c.col + 1, String::new());
+                                }
+                                cells[c.col] = text;
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Text(t) if capture => {
+                    value.push_str(&t.xml_content().map_err(|e| e.to_string())?);
+                }
+                Event::GeneralRef(r) if capture => push_entity(&mut value, &r)?,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(grid)
+    }
+
+    /// Converts a cell's raw value to display text.
+    fn cell_text(&self, cell: &Cell, value: &str) -> Result<String, String> {
+        Ok(match cell.kind.as_deref() {
+            Some("s") => {
+                let index: usize = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid shared string index '{}'", value))?;
+                self.shared_strings
+                    .get(index)
+                    .cloned()
+                    .ok_or_else(|| format!("shared string {} out of range", index))?
+            }
+            Some("b") => match value.trim() {
+                "1" => "TRUE".to_string(),
+                _ => "FALSE".to_string(),
+            },
+            // Error values such as #N/A carry no data
+            Some("e") => String::new(),
+            Some("str") | Some("inlineStr") => value.to_string(),
+            _ => {
+                let format = cell
+                    .style
+                    .and_then(|s| self.date_styles.get(s).copied().flatten());
+                let serial = value.trim().parse::<f64>();
+                let text = match (format, serial) {
+                    (Some(SerialFormat::Time), Ok(serial)) if (0.0..1.0).contains(&serial) => {
+                        serial_to_time(serial)
+                    }
+                    (Some(_), Ok(serial)) => serial_to_iso8601(serial, self.date_1904),
+                    _ => None,
+                };
+                text.unwrap_or_else(|| value.trim().to_string())
+            }
+        })
+    }
+}
+
+/// A cell being read.
+struct Cell {
+    col: usize,
+    kind: Option<String>,
+    style: Option<usize>,
+}
+
+impl Cell {
+    fn from_element(e: &BytesStart, next_col: usize) -> Result<Self, String> {
+        let col = match attribute(e, b"r")? {
+            Some(reference) => column_index(&reference)
+                .ok_or_else(|| format!("invalid cell reference '{}'", reference))?,
+            None => next_col,
+        };
+        Ok(Self {
+            col,
+            kind: attribute(e, b"t")?,
+            style: attribute(e, b"s")?.and_then(|s| s.parse().ok()),
+        })
+    }
+}
+
+/// Reads a package part as a string, or `None` if it doesn't exist.
+///
+/// Parts larger than [`MAX_PART_BYTES`] once decompressed are rejected.
+fn read_part<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>, String> {
+    let file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut content = String::new();
+    file.take(MAX_PART_BYTES + 1)
+        .read_to_string(&mut content)
+        .map_err(|e| format!("failed to read {}: {}", name, e))?;
+    if content.len() as u64 > MAX_PART_BYTES {
+        return Err(format!(
+            "{} exceeds {} MB when decompressed",
+            name,
+            MAX_PART_BYTES / (1024 * 1024)
+        ));
+    }
+    Ok(Some(content))
+}
+
+/// Gets an attribute value by local name.
+fn attribute(e: &BytesStart, name: &[u8]) -> Result<Option<String>, String> {
+    for attr in e.attributes() {
+        let attr = attr.map_err(|e| e.to_string())?;
+        if attr.key.local_name().as_ref() == name {
+            let value = attr.unescape_value().map_err(|e| e.to_string())?;
+            return Ok(Some(value.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// Appends a character or predefined entity reference to `text`.
+fn push_entity(text: &mut String, r: &quick_xml::events::BytesRef) -> Result<(), String> {
+    if let Some(c) = r.resolve_char_ref().map_err(|e| e.to_string())? {
+        text.push(c);
+        return Ok(());
+    }
+    let name = r.decode().map_err(|e| e.to_string())?;
+    let resolved =
+        resolve_predefined_entity(&name).ok_or_else(|| format!("unknown entity '&{};'", name))?;
+    text.push_str(resolved);
+    Ok(())
+}
+
+/// Gets the zero-based row index from a `<row r="...">` element.
+fn row_index(e: &BytesStart) -> Result<Option<usize>, String> {
+    Ok(attribute(e, b"r")?
+        .and_then(|r| r.parse::<usize>().ok())
+        .and_then(|r| r.checked_sub(1)))
+}
+
+/// Converts the column letters of a cell reference (`"AB12"`) to a zero-based index.
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference
+        .bytes()
+        .take_while(u8::is_ascii_alphabetic)
+        .collect();
+    if letters.is_empty() || letters.len() > 3 {
+        return None;
+    }
+    let index = letters.iter().fold(0usize, |acc, b| {
+        acc * 26 + usize::from(b.to_ascii_uppercase() - b'A') + 1
+    });
+    Some(index - 1)
+}
+
+/// Parses sheet names with their relationship ids, and the 1904 date flag.
+fn parse_workbook(xml: &str) -> Result<(Vec<(String, String)>, bool), String> {
+    let mut reader = Reader::from_str(xml);
+    let mut sheets = Vec::new();
+    let mut date_1904 = false;
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"sheet" => {
+                    if let (Some(name), Some(id)) = (attribute(&e, b"name")?, attribute(&e, b"id")?)
+                    {
+                        sheets.push((name, id));
+                    }
+                }
+                b"workbookPr" => {
+                    date_1904 =
+                        matches!(attribute(&e, b"date1904")?.as_deref(), Some("1" | "true"));
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok((sheets, date_1904))
+}
+
+/// Parses workbook relationships into an id → target map.
+fn parse_relationships(xml: &str) -> Result<HashMap<String, String>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut relationships = HashMap::new();
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Relationship" => {
+                if let (Some(id), Some(target)) = (attribute(&e, b"Id")?, attribute(&e, b"Target")?)
+                {
+                    relationships.insert(id, target);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(relationships)
+}
+
+/// Parses the shared string table, joining rich text runs.
+fn parse_shared_strings(xml: &str) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut capture = false;
+    let mut phonetic = false;
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"si" => current.clear(),
+                b"t" if !phonetic => capture = true,
+                b"rPh" => phonetic = true,
+                _ => {}
+            },
+            Event::Empty(e) if e.local_name().as_ref() == b"si" => strings.push(String::new()),
+            Event::End(e) => match e.local_name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"t" => capture = false,
+                b"rPh" => phonetic = false,
+                _ => {}
+            },
+            Event::Text(t) if capture => {
+                current.push_str(&t.xml_content().map_err(|e| e.to_string())?);
+            }
+            Event::GeneralRef(r) if capture => push_entity(&mut current, &r)?,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(strings)
+}
+
+/// Parses `styles.xml` into the serial format of each cell style.
+fn parse_date_styles(xml: &str) -> Result<Vec<Option<SerialFormat>>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut custom_formats: HashMap<u32, SerialFormat> = HashMap::new();
+    let mut styles = Vec::new();
+    let mut in_cell_xfs = false;
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"numFmt" => {
+                    let id = attribute(&e, b"numFmtId")?.and_then(|id| id.parse().ok());
+                    let code = attribute(&e, b"formatCode")?.unwrap_or_default();
+                    if let Some(id) = id
+                        && let Some(format) = serial_format(&code)
+                    {
+                        custom_formats.insert(id, format);
+                    }
+                }
+                b"cellXfs" => in_cell_xfs = true,
+                b"xf" if in_cell_xfs => {
+                    let id: u32 = attribute(&e, b"numFmtId")?
+                        .and_then(|id| id.parse().ok())
+                        .unwrap_or(0);
+                    let format = if BUILTIN_DATE_FORMATS.contains(&id) {
+                        Some(SerialFormat::Date)
+                    } else if BUILTIN_TIME_FORMATS.contains(&id) {
+                        Some(SerialFormat::Time)
+                    } else {
+                        custom_formats.get(&id).copied()
+                    };
+                    styles.push(format);
+                }
+                _ => {}
+            },
+            Event::End(e) if e.local_name().as_ref() == b"cellXfs" => in_cell_xfs = false,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(styles)
+}
+
+/// Whether a custom number format code displays a date, a time, or neither.
+fn serial_format(code: &str) -> Option<SerialFormat> {
+    let mut in_quotes = false;
+    let mut in_brackets = false;
+    let mut escaped = false;
+    let mut has_time = false;
+    for c in code.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '[' if !in_quotes => in_brackets = true,
+            ']' if !in_quotes => in_brackets = false,
+            'd' | 'D' | 'y' | 'Y' if !in_quotes && !in_brackets => {
+                return Some(SerialFormat::Date);
+            }
+            'h' | 'H' | 's' | 'S' if !in_quotes && !in_brackets => has_time = true,
+            _ => {}
+        }
+    }
+    has_time.then_some(SerialFormat::Time)
+}
+
+/// Converts an Excel serial date to ISO 8601 (date, or date and time).
+fn serial_to_iso8601(serial: f64, date_1904: bool) -> Option<String> {
+    let epoch = if date_1904 {
+        NaiveDate::from_ymd_opt(1904, 1, 1)?
+    } else {
+        // Day 0 is 1899-12-30, absorbing Excel's fictitious 1900-02-29
+        NaiveDate::from_ymd_opt(1899, 12, 30)?
+    };
+    if !serial.is_finite() || serial < 0.0 {
+        return None;
+    }
+    let millis = (serial * 86_400_000.0).round() as i64;
+    let datetime: NaiveDateTime = epoch
+        .and_time(NaiveTime::MIN)
+        .checked_add_signed(Duration::milliseconds(millis))?;
+
+    if datetime.time() == NaiveTime::MIN {
+        Some(datetime.format("%Y-%m-%d").to_string())
+    } else {
+        Some(datetime.format("%Y-%m-%dT%H:%M:%S").to_string())
+    }
+}
+
+/// Converts the fraction of a day in an Excel serial time to ISO 8601 (`HH:MM:SS`).
+fn serial_to_time(serial: f64) -> Option<String> {
+    let seconds = (serial * 86_400.0).round() as u32;
+    let time = NaiveTime::from_num_seconds_from_midnight_opt(seconds % 86_400, 0)?;
+    Some(time.format("%H:%M:%S").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_column_index() {
+        assert_eq!(column_index("A1"), Some(0));
+        assert_eq!(column_index("Z9"), Some(25));
+        assert_eq!(column_index("AA10"), Some(26));
+        assert_eq!(column_index("XFD1"), Some(16383));
+        assert_eq!(column_index("12"), None);
+    }
+
+    #[test]
+    fn test_date_formats() {
+        assert_eq!(serial_format("yyyy-mm-dd"), Some(SerialFormat::Date));
+        assert_eq!(serial_format("d/m/yy h:mm"), Some(SerialFormat::Date));
+        assert_eq!(serial_format("h:mm:ss AM/PM"), Some(SerialFormat::Time));
+        assert_eq!(serial_format("0.00"), None);
+        assert_eq!(serial_format("\"days\" 0"), None);
+        assert_eq!(serial_format("[Red]#,##0"), None);
+
+        assert_eq!(serial_to_iso8601(45292.0, false).unwrap(), "2024-01-01");
+        assert_eq!(
+            serial_to_iso8601(45292.5, false).unwrap(),
+            "2024-01-01T12:00:00"
+        );
+        assert_eq!(serial_to_iso8601(0.0, true).unwrap(), "1904-01-01");
+        assert_eq!(serial_to_time(0.5).unwrap(), "12:00:00");
+        assert_eq!(serial_to_time(0.6).unwrap(), "14:24:00");
+    }
+
+    fn empty_workbook(date_styles: Vec<Option<SerialFormat>>) -> Workbook<Cursor<Vec<u8>>> {
+        let zip = zip::ZipWriter::new(Cursor::new(Vec::new()))
+            .finish()
+            .unwrap();
+        Workbook {
+            archive: ZipArchive::new(zip).unwrap(),
+            sheets: Vec::new(),
+            shared_strings: Vec::new(),
+            date_styles,
+            date_1904: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_sheet_time_cells() {
+        let workbook = empty_workbook(vec![None, Some(SerialFormat::Time)]);
+        let grid = workbook
+            .parse_sheet(r#"<sheetData><row r="1"><c r="A1" s="1"><v>0.375</v></c><c r="B1" s="0"><v>0.375</v></c></row></sheetData>"#)
+            .unwrap();
+        assert_eq!(
+            grid,
+            vec![vec!["09:00:00".to_string(), "0.375".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_parse_sheet_row_limit() {
+        let workbook = empty_workbook(Vec::new());
+        let xml = format!(
+            r#"<sheetData><row r="{}"><c r="A1"><v>1</v></c></row></sheetData>"#,
+            MAX_ROWS + 1
+        );
+        let error = workbook.parse_sheet(&xml).unwrap_err();
+        assert!(error.contains("exceeds the worksheet limit"));
+    }
+}
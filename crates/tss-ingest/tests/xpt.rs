@@ -0,0 +1,115 @@
+//! Reading SAS Transport files back into DataFrames.
+
+use std::path::{Path, PathBuf};
+
+use polars::prelude::*;
+use tss_ingest::{IngestError, read_xpt};
+use xportrs::{Column, ColumnData, Dataset, Format, Xpt};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(name)
+}
+
+#[test]
+fn test_read_xpt() {
+    let (df, hints) = read_xpt(&fixture("dm.xpt")).unwrap();
+
+    assert_eq!(df.height(), 3);
+    assert_eq!(
+        df.get_column_names(),
+        vec!["STUDYID", "DOMAIN", "USUBJID", "AGE", "SEX"]
+    );
+    assert_eq!(df.column("AGE").unwrap().dtype(), &DataType::Float64);
+    let age = df.column("AGE").unwrap().f64().unwrap();
+    assert_eq!(age.get(0), Some(34.0));
+    assert_eq!(age.get(1), Some(58.5));
+    assert_eq!(age.get(2), None);
+    let subjects = df.column("USUBJID").unwrap().str().unwrap();
+    assert_eq!(subjects.get(2), Some("CDISC01-003"));
+
+    assert_eq!(hints[2].label.as_deref(), Some("Unique Subject Identifier"));
+    assert!(hints[3].is_numeric);
+    assert_eq!(hints[3].format.as_deref(), Some("8."));
+}
+
+#[test]
+fn test_read_xpt_round_trip() {
+    let dataset = Dataset::new(
+        "VS",
+        vec![
+            Column::new(
+                "VSTESTCD",
+                ColumnData::String(vec![Some("SYSBP".into()), None]),
+            )
+            .with_label("Vital Signs Test Short Name"),
+            Column::new("VSSTRESN", ColumnData::F64(vec![Some(-120.25), None]))
+                .with_label("Numeric Result in Std Units")
+                .with_format(Format::numeric(8, 2)),
+        ],
+    )
+    .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vs.xpt");
+    Xpt::writer(dataset)
+        .finalize()
+        .unwrap()
+        .write_path(&path)
+        .unwrap();
+
+    let (df, hints) = read_xpt(&path).unwrap();
+
+    assert_eq!(df.height(), 2);
+    let testcd = df.column("VSTESTCD").unwrap().str().unwrap();
+    assert_eq!(testcd.get(0), Some("SYSBP"));
+    assert_eq!(testcd.get(1), None);
+    let result = df.column("VSSTRESN").unwrap().f64().unwrap();
+    assert_eq!(result.get(0), Some(-120.25));
+    assert_eq!(result.get(1), None);
+
+    assert_eq!(
+        hints[0].label.as_deref(),
+        Some("Vital Signs Test Short Name")
+    );
+    assert!(!hints[0].is_numeric);
+    assert!(hints[1].is_numeric);
+    assert_eq!(hints[1].format.as_deref(), Some("8.2"));
+}
+
+#[test]
+fn test_read_xpt_keeps_trailing_blank_records() {
+    // Observations as long as a record leave no padding, so the five blank
+    // ones at the end are all data.
+    let values = (0..10)
+        .map(|i| (i < 5).then(|| format!("COMMENT {i}")))
+        .collect();
+    let dataset = Dataset::new(
+        "CO",
+        vec![Column::new("COVAL", ColumnData::String(values)).with_length(80)],
+    )
+    .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("co.xpt");
+    Xpt::writer(dataset)
+        .finalize()
+        .unwrap()
+        .write_path(&path)
+        .unwrap();
+
+    let (df, _) = read_xpt(&path).unwrap();
+
+    assert_eq!(df.height(), 10);
+    let values = df.column("COVAL").unwrap().str().unwrap();
+    assert_eq!(values.get(4), Some("COMMENT 4"));
+    assert_eq!(values.null_count(), 5);
+}
+
+#[test]
+fn test_read_xpt_rejects_other_files() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, &[b'A'; 400]).unwrap();
+
+    let result = read_xpt(file.path());
+    assert!(matches!(result, Err(IngestError::XptParse { .. })));
+}
@@ -1,10 +1,14 @@
 //! Dirty state tracking for auto-save.
 
+use std::collections::BTreeSet;
 use std::time::Instant;
 
 /// Tracks unsaved changes in a project.
 ///
 /// Used to implement debounced auto-save and the "unsaved changes" indicator.
+/// Changes confined to individual domains are tracked by domain code so they
+/// can be saved incrementally with
+/// [`save_project_journal`](crate::save_project_journal).
 #[derive(Debug, Clone)]
 pub struct DirtyTracker {
     /// Whether there are unsaved changes.
@@ -19,6 +23,12 @@ pub struct DirtyTracker {
 
     /// Whether a save is currently in progress.
     saving: bool,
+
+    /// Domains changed since the last save.
+    dirty_domains: BTreeSet<String>,
+
+    /// Whether a change outside individual domains needs a full save.
+    full_save_needed: bool,
 }
 
 impl Default for DirtyTracker {
@@ -35,6 +45,8 @@ pub fn new() -> Self {
             last_change: None,
             first_unsaved_change: None,
             saving: false,
+            dirty_domains: BTreeSet::new(),
+            full_save_needed: false,
         }
     }
 
@@ -51,7 +63,33 @@ pub fn is_saving(&self) -> bool {
     }
 
     /// Mark the project as having unsaved changes.
+    ///
+    /// The next save must rewrite the whole project.
     pub fn mark_dirty(&mut self) {
+        self.full_save_needed = true;
+        self.touch();
+    }
+
+    /// Mark a single domain as having unsaved changes.
+    pub fn mark_domain_dirty(&mut self, domain_code: &str) {
+        self.dirty_domains.insert(domain_code.to_string());
+        self.touch();
+    }
+
+    /// Domains changed since the last save.
+    pub fn dirty_domains(&self) -> &BTreeSet<String> {
+        &self.dirty_domains
+    }
+
+    /// Check if the next save must rewrite the whole project.
+    ///
+    /// False when all unsaved changes are confined to [`Self::dirty_domains`].
+    pub fn needs_full_save(&self) -> bool {
+        self.full_save_needed
+    }
+
+    /// Record a change at the current time.
+    fn touch(&mut self) {
         let now = Instant::now();
         self.dirty = true;
         self.last_change = Some(now);
@@ -72,6 +110,8 @@ pub fn save_complete(&mut self) {
         self.dirty = false;
         self.saving = false;
         self.first_unsaved_change = None;
+        self.dirty_domains.clear();
+        self.full_save_needed = false;
     }
 
     /// Mark that a save has failed.
@@ -127,6 +167,26 @@ fn test_mark_dirty() {
         assert!(tracker.ms_since_first_unsaved().is_some());
     }
 
+    #[test]
+    fn test_mark_domain_dirty() {
+        let mut tracker = DirtyTracker::new();
+        tracker.mark_domain_dirty("AE");
+        tracker.mark_domain_dirty("DM");
+        tracker.mark_domain_dirty("AE");
+
+        assert!(tracker.is_dirty());
+        assert!(!tracker.needs_full_save());
+        assert_eq!(tracker.dirty_domains().len(), 2);
+
+        tracker.mark_dirty();
+        assert!(tracker.needs_full_save());
+
+        tracker.start_save();
+        tracker.save_complete();
+        assert!(tracker.dirty_domains().is_empty());
+        assert!(!tracker.needs_full_save());
+    }
+
     #[test]
     fn test_save_complete() {
         let mut tracker = DirtyTracker::new();
@@ -42,6 +42,10 @@ pub enum PersistenceError {
     #[error("Source file not found: {path}")]
     SourceFileMissing { path: PathBuf },
 
+    /// Project bundle (zip) is unreadable or malformed.
+    #[error("Invalid project bundle: {reason}")]
+    InvalidBundle { path: PathBuf, reason: String },
+
     /// Serialization error.
     #[error("Failed to serialize project data")]
     Serialization {
@@ -56,6 +60,14 @@ pub enum PersistenceError {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
 
+    /// A stored mapping with another name already uses the target file.
+    #[error("Mapping '{name}' would overwrite mapping '{existing}'")]
+    MappingNameTaken {
+        name: String,
+        existing: String,
+        path: PathBuf,
+    },
+
     /// Atomic write failed (temp file couldn't be renamed).
     #[error("Failed to complete save operation")]
     AtomicWriteFailed {
@@ -108,6 +120,13 @@ pub fn user_message(&self) -> String {
                     path.display()
                 )
             }
+            Self::InvalidBundle { path, reason } => {
+                format!(
+                    "The file at {} is not a valid project bundle: {}",
+                    path.display(),
+                    reason
+                )
+            }
             Self::Serialization { .. } => {
                 "An error occurred while saving the project data.".to_string()
             }
@@ -115,6 +134,12 @@ pub fn user_message(&self) -> String {
                 "An error occurred while reading the project data. The file may be corrupted."
                     .to_string()
             }
+            Self::MappingNameTaken { name, existing, .. } => {
+                format!(
+                    "The mapping '{}' cannot be saved because the mapping '{}' uses the same file.",
+                    name, existing
+                )
+            }
             Self::AtomicWriteFailed { target_path, .. } => {
                 format!(
                     "Could not save the file to {}. Please check disk space and permissions.",
@@ -149,10 +174,16 @@ pub fn suggestion(&self) -> Option<String> {
             Self::SourceFileMissing { .. } => {
                 Some("Locate the original source file or remove it from the project.".into())
             }
+            Self::InvalidBundle { .. } => {
+                Some("Make sure you selected a project bundle exported from Trial Submission Studio.".into())
+            }
             Self::Serialization { .. } => None,
             Self::Deserialization { .. } => {
                 Some("Try opening a backup if you have one.".into())
             }
+            Self::MappingNameTaken { .. } => {
+                Some("Save the mapping under a different name.".into())
+            }
             Self::AtomicWriteFailed { .. } => {
                 Some("Free up disk space or try saving to a different location.".into())
             }
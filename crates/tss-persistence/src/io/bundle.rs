@@ -0,0 +1,455 @@
+//! Portable project bundles.
+//!
+//! A bundle is a zip archive holding the `.tss` project file together with
+//! copies of its source files, so a project can move between machines:
+//!
+//! ```text
+//! bundle.zip
+//! ├── project.tss
+//! └── sources/
+//!     ├── 1_DM.csv
+//!     └── 2_AE.csv
+//! ```
+//!
+//! Source paths in the bundled project are relative to the archive root and
+//! are rewritten to the extraction directory on load.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+use crate::error::{PersistenceError, Result};
+use crate::types::ProjectFile;
+
+use super::hash::compute_file_hash;
+use super::load::parse_project_bytes;
+use super::save::serialize_project;
+
+/// Name of the project file inside a bundle.
+const BUNDLE_PROJECT_ENTRY: &str = "project.tss";
+
+/// Directory holding source files inside a bundle.
+const BUNDLE_SOURCES_DIR: &str = "sources";
+
+/// Maximum total uncompressed size of a bundle's entries (4 GiB).
+const MAX_BUNDLE_UNCOMPRESSED_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+/// A project extracted from a bundle.
+#[derive(Debug, Clone)]
+pub struct LoadedBundle {
+    /// The project, with source paths pointing into `directory`.
+    pub project: ProjectFile,
+
+    /// Directory the bundle was extracted to.
+    pub directory: PathBuf,
+
+    /// Source paths whose bundled content differs from the recorded hash.
+    pub changed_sources: Vec<String>,
+}
+
+/// Save a project and copies of its source files to a zip bundle.
+///
+/// Each distinct source file is stored once under `sources/`. Uses atomic
+/// write (temp file + rename) like [`save_project`](super::save_project).
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::SourceFileMissing`] if a referenced source
+/// file no longer exists.
+pub fn save_project_bundle(project: &mut ProjectFile, bundle_path: &Path) -> Result<()> {
+    project.touch();
+
+    // Bundle-relative entry name for each distinct source path
+    let mut entries: BTreeMap<String, String> = BTreeMap::new();
+    for assignment in &project.source_assignments {
+        if entries.contains_key(&assignment.file_path) {
+            continue;
+        }
+        let source = Path::new(&assignment.file_path);
+        if !source.is_file() {
+            return Err(PersistenceError::SourceFileMissing {
+                path: source.to_path_buf(),
+            });
+        }
+        let file_name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "source".to_string());
+        let entry = format!("{}/{}_{}", BUNDLE_SOURCES_DIR, entries.len() + 1, file_name);
+        entries.insert(assignment.file_path.clone(), entry);
+    }
+
+    let mut bundled = project.clone();
+    for assignment in &mut bundled.source_assignments {
+        assignment.file_path = entries[&assignment.file_path].clone();
+    }
+    let project_bytes = serialize_project(&bundled)?;
+
+    if let Some(parent) = bundle_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| PersistenceError::Io {
+            operation: "create directory",
+            path: parent.to_path_buf(),
+            source: e,
+        })?;
+    }
+
+    let temp_path = bundle_path.with_extension("zip.tmp");
+    let file = File::create(&temp_path).map_err(|e| PersistenceError::Io {
+        operation: "create",
+        path: temp_path.clone(),
+        source: e,
+    })?;
+
+    let zip_error = |e: zip::result::ZipError| PersistenceError::InvalidBundle {
+        path: bundle_path.to_path_buf(),
+        reason: e.to_string(),
+    };
+    let write_error = |e: io::Error| PersistenceError::Io {
+        operation: "write",
+        path: temp_path.clone(),
+        source: e,
+    };
+
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    zip.start_file(BUNDLE_PROJECT_ENTRY, options)
+        .map_err(zip_error)?;
+    zip.write_all(&project_bytes).map_err(write_error)?;
+
+    for (source, entry) in &entries {
+        zip.start_file(entry.as_str(), options).map_err(zip_error)?;
+        let mut reader = File::open(source).map_err(|e| PersistenceError::Io {
+            operation: "read",
+            path: PathBuf::from(source),
+            source: e,
+        })?;
+        io::copy(&mut reader, &mut zip).map_err(write_error)?;
+    }
+
+    let file = zip.finish().map_err(zip_error)?;
+    file.sync_all().map_err(|e| PersistenceError::Io {
+        operation: "sync",
+        path: temp_path.clone(),
+        source: e,
+    })?;
+
+    fs::rename(&temp_path, bundle_path).map_err(|e| PersistenceError::AtomicWriteFailed {
+        temp_path: temp_path.clone(),
+        target_path: bundle_path.to_path_buf(),
+        source: e,
+    })?;
+
+    tracing::info!(
+        sources = entries.len(),
+        "Saved project bundle to {}",
+        bundle_path.display()
+    );
+    Ok(())
+}
+
+/// Load a project bundle, extracting it to a new temporary directory.
+///
+/// The directory is kept after loading, since the project's source paths
+/// point into it. It is removed again if loading fails.
+pub fn load_project_bundle(bundle_path: &Path) -> Result<LoadedBundle> {
+    let directory = tempfile::Builder::new()
+        .prefix("tss-bundle-")
+        .tempdir()
+        .map_err(|e| PersistenceError::Io {
+            operation: "create directory",
+            path: std::env::temp_dir(),
+            source: e,
+        })?;
+
+    let loaded = extract_project_bundle(bundle_path, directory.path())?;
+    let _ = directory.keep();
+    Ok(loaded)
+}
+
+/// Extract a project bundle to `directory` and load its project.
+///
+/// Source assignment paths are rewritten to the extracted files, and each
+/// extracted source is hashed and compared with its recorded hash.
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::InvalidBundle`] if the archive is malformed,
+/// has an entry path outside `directory`, expands to more than 4 GiB, or
+/// lists a source path that is not a relative path under `sources/`.
+pub fn extract_project_bundle(bundle_path: &Path, directory: &Path) -> Result<LoadedBundle> {
+    let invalid = |reason: String| PersistenceError::InvalidBundle {
+        path: bundle_path.to_path_buf(),
+        reason,
+    };
+
+    extract_archive(bundle_path, directory, MAX_BUNDLE_UNCOMPRESSED_BYTES)?;
+
+    let project_path = directory.join(BUNDLE_PROJECT_ENTRY);
+    let bytes = fs::read(&project_path)
+        .map_err(|_| invalid(format!("bundle does not contain {}", BUNDLE_PROJECT_ENTRY)))?;
+    let mut project = parse_project_bytes(&bytes, &project_path)?;
+
+    let mut changed_sources = Vec::new();
+    for assignment in &mut project.source_assignments {
+        if !is_bundled_source_path(&assignment.file_path) {
+            return Err(invalid(format!(
+                "source path '{}' is not inside {}/",
+                assignment.file_path, BUNDLE_SOURCES_DIR
+            )));
+        }
+        let extracted = directory.join(&assignment.file_path);
+        if !extracted.is_file() {
+            return Err(PersistenceError::SourceFileMissing { path: extracted });
+        }
+        assignment.file_path = extracted.to_string_lossy().into_owned();
+
+        if !assignment.content_hash.is_empty()
+            && compute_file_hash(&extracted)? != assignment.content_hash
+        {
+            changed_sources.push(assignment.file_path.clone());
+        }
+    }
+    project.study.study_folder = directory
+        .join(BUNDLE_SOURCES_DIR)
+        .to_string_lossy()
+        .into_owned();
+
+    tracing::info!(
+        "Extracted project bundle {} to {}",
+        bundle_path.display(),
+        directory.display()
+    );
+    Ok(LoadedBundle {
+        project,
+        directory: directory.to_path_buf(),
+        changed_sources,
+    })
+}
+
+/// Whether a bundled source path is a plain relative path under `sources/`.
+fn is_bundled_source_path(path: &str) -> bool {
+    let path = Path::new(path);
+    path.starts_with(BUNDLE_SOURCES_DIR)
+        && path.components().count() > 1
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Extract every entry of a bundle to `directory`.
+///
+/// Fails once the entries expand to more than `max_bytes` in total, so a
+/// small archive cannot fill the disk.
+fn extract_archive(bundle_path: &Path, directory: &Path, max_bytes: u64) -> Result<()> {
+    let file = File::open(bundle_path).map_err(|e| PersistenceError::Io {
+        operation: "read",
+        path: bundle_path.to_path_buf(),
+        source: e,
+    })?;
+
+    let invalid = |reason: String| PersistenceError::InvalidBundle {
+        path: bundle_path.to_path_buf(),
+        reason,
+    };
+
+    let mut archive = ZipArchive::new(file).map_err(|e| invalid(e.to_string()))?;
+    let mut total = 0u64;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| invalid(e.to_string()))?;
+        if entry.is_dir() {
+            continue;
+        }
+        // Reject entries that would escape the extraction directory
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(invalid(format!("unsafe entry path '{}'", entry.name())));
+        };
+
+        let target = directory.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| PersistenceError::Io {
+                operation: "create directory",
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+        let mut output = File::create(&target).map_err(|e| PersistenceError::Io {
+            operation: "create",
+            path: target.clone(),
+            source: e,
+        })?;
+        // Count the bytes actually inflated; declared entry sizes can lie
+        let remaining = max_bytes - total;
+        let written =
+            io::copy(&mut entry.by_ref().take(remaining + 1), &mut output).map_err(|e| {
+                PersistenceError::Io {
+                    operation: "write",
+                    path: target.clone(),
+                    source: e,
+                }
+            })?;
+        if written > remaining {
+            return Err(invalid(format!(
+                "uncompressed size exceeds the limit of {} bytes",
+                max_bytes
+            )));
+        }
+        total += written;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{SourceAssignment, StudyMetadata, WorkflowTypeSnapshot};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let dir = tempdir().unwrap();
+        let study_dir = dir.path().join("study");
+        fs::create_dir_all(&study_dir).unwrap();
+        let dm_path = study_dir.join("DM.csv");
+        let ae_path = study_dir.join("AE.csv");
+        fs::write(&dm_path, "USUBJID,AGE\n001,34\n").unwrap();
+        fs::write(&ae_path, "USUBJID,AETERM\n001,Headache\n").unwrap();
+
+        let study = StudyMetadata::new(
+            "TEST_STUDY",
+            study_dir.to_string_lossy(),
+            WorkflowTypeSnapshot::Sdtm,
+        );
+        let mut project = ProjectFile::new(study);
+        for (path, domain) in [(&dm_path, "DM"), (&ae_path, "AE")] {
+            let hash = compute_file_hash(path).unwrap();
+            project.source_assignments.push(SourceAssignment::new(
+                path.to_string_lossy(),
+                domain,
+                hash,
+                fs::metadata(path).unwrap().len(),
+            ));
+        }
+        // Recorded before the file changed on disk
+        project.source_assignments[1].content_hash = "stale".to_string();
+
+        let bundle_path = dir.path().join("out/project.zip");
+        save_project_bundle(&mut project, &bundle_path).unwrap();
+        // The original project keeps its paths
+        assert_eq!(
+            project.source_assignments[0].file_path,
+            dm_path.to_string_lossy()
+        );
+
+        let extract_dir = dir.path().join("extracted");
+        let loaded = extract_project_bundle(&bundle_path, &extract_dir).unwrap();
+
+        assert_eq!(loaded.project.study.study_id, "TEST_STUDY");
+        let dm = &loaded.project.source_assignments[0];
+        assert_eq!(dm.domain_code, "DM");
+        assert!(Path::new(&dm.file_path).starts_with(&extract_dir));
+        assert_eq!(
+            fs::read_to_string(&dm.file_path).unwrap(),
+            "USUBJID,AGE\n001,34\n"
+        );
+        assert_eq!(
+            loaded.changed_sources,
+            vec![loaded.project.source_assignments[1].file_path.clone()]
+        );
+
+        let loaded = load_project_bundle(&bundle_path).unwrap();
+        assert_eq!(loaded.project.source_assignments.len(), 2);
+        fs::remove_dir_all(loaded.directory).unwrap();
+    }
+
+    #[test]
+    fn test_bundle_missing_source() {
+        let dir = tempdir().unwrap();
+        let study = StudyMetadata::new("TEST_STUDY", "/path", WorkflowTypeSnapshot::Sdtm);
+        let mut project = ProjectFile::new(study);
+        project.source_assignments.push(SourceAssignment::new(
+            dir.path().join("missing.csv").to_string_lossy(),
+            "DM",
+            "",
+            0,
+        ));
+
+        let result = save_project_bundle(&mut project, &dir.path().join("bundle.zip"));
+        assert!(matches!(
+            result,
+            Err(PersistenceError::SourceFileMissing { .. })
+        ));
+    }
+
+    /// Write a bundle whose project lists `source_path` as its only source.
+    fn bundle_with_source_path(path: &Path, source_path: &str) {
+        let study = StudyMetadata::new("TEST_STUDY", "/path", WorkflowTypeSnapshot::Sdtm);
+        let mut project = ProjectFile::new(study);
+        project
+            .source_assignments
+            .push(SourceAssignment::new(source_path, "DM", "", 0));
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        zip.start_file(BUNDLE_PROJECT_ENTRY, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&serialize_project(&project).unwrap())
+            .unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_bundle_rejects_source_paths_outside_sources() {
+        let dir = tempdir().unwrap();
+        let outside = dir.path().join("secret.csv");
+        fs::write(&outside, "USUBJID\n001\n").unwrap();
+
+        for source_path in [
+            "../secret.csv".to_string(),
+            "sources/../../secret.csv".to_string(),
+            outside.to_string_lossy().into_owned(),
+            "project.tss".to_string(),
+        ] {
+            let path = dir.path().join("bundle.zip");
+            bundle_with_source_path(&path, &source_path);
+            let result = extract_project_bundle(&path, &dir.path().join("out"));
+            assert!(
+                matches!(result, Err(PersistenceError::InvalidBundle { .. })),
+                "{source_path} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bundle_size_limit() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bundle.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file("sources/1_DM.csv", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&[b'0'; 4096]).unwrap();
+        zip.finish().unwrap();
+
+        let result = extract_archive(&path, &dir.path().join("out"), 1024);
+        assert!(matches!(
+            result,
+            Err(PersistenceError::InvalidBundle { reason, .. }) if reason.contains("1024 bytes")
+        ));
+        extract_archive(&path, &dir.path().join("out"), 4096).unwrap();
+    }
+
+    #[test]
+    fn test_load_invalid_bundle() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bundle.zip");
+        fs::write(&path, b"not a zip").unwrap();
+
+        let result = extract_project_bundle(&path, &dir.path().join("out"));
+        assert!(matches!(
+            result,
+            Err(PersistenceError::InvalidBundle { .. })
+        ));
+    }
+}
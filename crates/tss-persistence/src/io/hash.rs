@@ -2,11 +2,12 @@
 
 use std::fs::File;
 use std::io::{BufReader, Read};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use sha2::{Digest, Sha256};
 
 use crate::error::{PersistenceError, Result};
+use crate::types::ProjectFile;
 
 /// Compute SHA-256 hash of a file.
 ///
@@ -49,9 +50,84 @@ pub fn verify_file_hash(path: &Path, expected_hash: &str) -> Result<bool> {
     Ok(actual_hash == expected_hash)
 }
 
+/// Status of a source file compared with its recorded hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceStatus {
+    /// Content matches the recorded hash.
+    Unchanged,
+    /// Content differs from the recorded hash, or could not be read.
+    Modified,
+    /// The file no longer exists.
+    Missing,
+}
+
+/// Result of re-hashing one source assignment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceChange {
+    /// Path of the source file.
+    pub path: PathBuf,
+
+    /// Domain the file is assigned to.
+    pub domain_code: String,
+
+    /// Hash recorded when the project was saved.
+    pub recorded_hash: String,
+
+    /// Hash of the file as it is now (`None` if missing or unreadable).
+    pub current_hash: Option<String>,
+
+    /// Classification of the change.
+    pub status: SourceStatus,
+}
+
+impl SourceChange {
+    /// Whether the source no longer matches the recorded hash.
+    pub fn is_changed(&self) -> bool {
+        self.status != SourceStatus::Unchanged
+    }
+}
+
+/// Re-hash every source assignment and compare with its recorded hash.
+///
+/// Assignments without a recorded hash (legacy projects) are skipped.
+/// Files that exist but cannot be read are reported as
+/// [`SourceStatus::Modified`] with no current hash.
+pub fn detect_source_changes(project: &ProjectFile) -> Vec<SourceChange> {
+    project
+        .source_assignments
+        .iter()
+        .filter(|assignment| !assignment.content_hash.is_empty())
+        .map(|assignment| {
+            let path = PathBuf::from(&assignment.file_path);
+            let (current_hash, status) = if !path.exists() {
+                (None, SourceStatus::Missing)
+            } else {
+                match compute_file_hash(&path) {
+                    Ok(hash) if hash == assignment.content_hash => {
+                        (Some(hash), SourceStatus::Unchanged)
+                    }
+                    Ok(hash) => (Some(hash), SourceStatus::Modified),
+                    Err(e) => {
+                        tracing::warn!("Failed to compute hash for {}: {}", path.display(), e);
+                        (None, SourceStatus::Modified)
+                    }
+                }
+            };
+            SourceChange {
+                path,
+                domain_code: assignment.domain_code.clone(),
+                recorded_hash: assignment.content_hash.clone(),
+                current_hash,
+                status,
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{SourceAssignment, StudyMetadata, WorkflowTypeSnapshot};
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -84,4 +160,55 @@ fn test_verify_file_hash() {
         // Wrong hash should not verify
         assert!(!verify_file_hash(temp_file.path(), "wrong_hash").unwrap());
     }
+
+    #[test]
+    fn test_detect_source_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let study = StudyMetadata::new("TEST_STUDY", "/path", WorkflowTypeSnapshot::Sdtm);
+        let mut project = ProjectFile::new(study);
+        for domain in ["DM", "AE", "LB"] {
+            let path = dir.path().join(format!("{domain}.csv"));
+            std::fs::write(&path, format!("USUBJID,{domain}SEQ\n001,1\n")).unwrap();
+            project.source_assignments.push(SourceAssignment::new(
+                path.to_string_lossy(),
+                domain,
+                compute_file_hash(&path).unwrap(),
+                0,
+            ));
+        }
+        // Legacy assignment without a hash is skipped
+        project.source_assignments.push(SourceAssignment::new(
+            dir.path().join("VS.csv").to_string_lossy(),
+            "VS",
+            "",
+            0,
+        ));
+
+        std::fs::write(dir.path().join("AE.csv"), "USUBJID,AESEQ\n001,2\n").unwrap();
+        std::fs::remove_file(dir.path().join("LB.csv")).unwrap();
+
+        let changes = detect_source_changes(&project);
+        assert_eq!(changes.len(), 3);
+
+        assert_eq!(changes[0].status, SourceStatus::Unchanged);
+        assert!(!changes[0].is_changed());
+
+        let modified = &changes[1];
+        assert_eq!(modified.domain_code, "AE");
+        assert_eq!(modified.status, SourceStatus::Modified);
+        assert_eq!(
+            modified.recorded_hash,
+            project.source_assignments[1].content_hash
+        );
+        assert_eq!(
+            modified.current_hash.as_deref(),
+            Some(compute_file_hash(&modified.path).unwrap().as_str())
+        );
+
+        let missing = &changes[2];
+        assert_eq!(missing.domain_code, "LB");
+        assert_eq!(missing.status, SourceStatus::Missing);
+        assert_eq!(missing.current_hash, None);
+        assert!(missing.is_changed());
+    }
 }
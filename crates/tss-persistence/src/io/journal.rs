@@ -0,0 +1,576 @@
+//! Append-only journal of per-domain changes.
+//!
+//! Rewriting the whole `.tss` payload for every auto-save stalls on large
+//! studies. Instead, changed domains are appended to a journal next to the
+//! project file (`demo.tss.journal`) and folded back into the main file by
+//! the next full [`save_project`](super::save_project), which deletes the
+//! journal. [`load_project`](super::load_project) replays it.
+//!
+//! ```text
+//! +------------------+
+//! | Magic: "TSSJ"    | 4 bytes
+//! +------------------+
+//! | Version          | 4 bytes - u32 little-endian schema version
+//! +------------------+
+//! | Record length    | 4 bytes - u32 little-endian
+//! | rkyv JournalEntry| Variable
+//! +------------------+
+//! | ...              | One record per changed domain
+//! +------------------+
+//! ```
+//!
+//! A record cut short by a crash mid-append is ignored on replay. Each record
+//! is stamped with the `last_saved_at` of the project file it applies to, so
+//! a journal left behind by a crash between a full save and the journal's
+//! removal is recognised as stale and discarded instead of replayed over the
+//! newer file.
+
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::error::{PersistenceError, Result};
+use crate::migrate::decode;
+use crate::types::{CURRENT_SCHEMA_VERSION, DomainSnapshot, MAGIC_BYTES, ProjectFile};
+
+use super::load::parse_project_bytes;
+use super::save::save_project;
+
+/// Magic bytes at the start of journal files.
+const JOURNAL_MAGIC: [u8; 4] = *b"TSSJ";
+
+/// Journal size below which compaction is never forced (256 KiB).
+const MIN_COMPACT_BYTES: u64 = 256 * 1024;
+
+/// One journaled domain change.
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+struct JournalEntry {
+    /// When the change was saved (RFC 3339).
+    saved_at: String,
+
+    /// `last_saved_at` of the project file this change applies to.
+    base_saved_at: String,
+
+    /// Domain code of the change.
+    domain_code: String,
+
+    /// New domain state, or `None` if the domain was removed.
+    snapshot: Option<DomainSnapshot>,
+}
+
+/// Outcome of [`save_project_journal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalSave {
+    /// Changed domains were appended to the journal.
+    Appended {
+        /// Number of domain records written.
+        domains: usize,
+        /// Bytes appended to the journal.
+        bytes: u64,
+    },
+    /// The whole project was rewritten and the journal removed.
+    Compacted,
+}
+
+/// Path of the journal belonging to a project file (`demo.tss.journal`).
+pub fn journal_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".journal");
+    PathBuf::from(name)
+}
+
+/// Save only the given domains by appending them to the project's journal.
+///
+/// Falls back to a full [`save_project`] (compaction) when the project file
+/// does not exist yet, was written by an older schema version, or when the
+/// journal would grow larger than the project file itself.
+pub fn save_project_journal(
+    project: &mut ProjectFile,
+    path: &Path,
+    domains: &BTreeSet<String>,
+) -> Result<JournalSave> {
+    let Some(main_len) = current_project_len(path) else {
+        save_project(project, path)?;
+        return Ok(JournalSave::Compacted);
+    };
+
+    project.touch();
+    let journal = journal_path(path);
+    // Records are only appended after the last complete one, dropping any
+    // torn tail so it cannot swallow the new records on replay
+    let existing = fs::read(&journal).unwrap_or_default();
+    let journal_len = complete_len(&existing) as u64;
+    let existing_len = existing.len() as u64;
+
+    // Records of one journal all apply to the same project file
+    let base_saved_at = match records(&existing).last() {
+        Some(record) => decode::<JournalEntry>(record)?.base_saved_at,
+        None => project_saved_at(path)?,
+    };
+
+    let mut bytes = Vec::new();
+    if journal_len == 0 {
+        bytes.extend_from_slice(&JOURNAL_MAGIC);
+        bytes.extend_from_slice(&CURRENT_SCHEMA_VERSION.to_le_bytes());
+    }
+    for domain_code in domains {
+        let entry = JournalEntry {
+            saved_at: project.last_saved_at.clone(),
+            base_saved_at: base_saved_at.clone(),
+            domain_code: domain_code.clone(),
+            snapshot: project.domains.get(domain_code).cloned(),
+        };
+        let record = rkyv::to_bytes::<rkyv::rancor::Error>(&entry).map_err(|e| {
+            PersistenceError::Serialization {
+                source: Box::new(io::Error::other(format!("rkyv serialization failed: {e}"))),
+            }
+        })?;
+        let record_len =
+            u32::try_from(record.len()).map_err(|e| PersistenceError::Serialization {
+                source: Box::new(e),
+            })?;
+        bytes.extend_from_slice(&record_len.to_le_bytes());
+        bytes.extend_from_slice(&record);
+    }
+
+    if should_compact(journal_len + bytes.len() as u64, main_len) {
+        save_project(project, path)?;
+        return Ok(JournalSave::Compacted);
+    }
+
+    let io_error = |operation: &'static str, e: io::Error| PersistenceError::Io {
+        operation,
+        path: journal.clone(),
+        source: e,
+    };
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&journal)
+        .map_err(|e| io_error("open", e))?;
+    if journal_len < existing_len {
+        tracing::warn!(
+            "Dropping incomplete records at the end of {}",
+            journal.display()
+        );
+        file.set_len(journal_len)
+            .map_err(|e| io_error("truncate", e))?;
+    }
+    file.write_all(&bytes).map_err(|e| io_error("write", e))?;
+    file.sync_all().map_err(|e| io_error("sync", e))?;
+
+    tracing::debug!(
+        domains = domains.len(),
+        bytes = bytes.len(),
+        "Appended domain changes to {}",
+        journal.display()
+    );
+    Ok(JournalSave::Appended {
+        domains: domains.len(),
+        bytes: bytes.len() as u64,
+    })
+}
+
+/// Save domain changes to the journal asynchronously.
+///
+/// Spawns the save operation on a blocking thread pool to avoid
+/// blocking the async runtime.
+pub async fn save_project_journal_async(
+    project: ProjectFile,
+    path: PathBuf,
+    domains: BTreeSet<String>,
+) -> Result<JournalSave> {
+    tokio::task::spawn_blocking(move || {
+        let mut project = project;
+        save_project_journal(&mut project, &path, &domains)
+    })
+    .await
+    .map_err(|e| PersistenceError::Serialization {
+        source: Box::new(e),
+    })?
+}
+
+/// Size of the project file if it exists and has the current schema version.
+fn current_project_len(path: &Path) -> Option<u64> {
+    let mut header = [0u8; 8];
+    let mut file = File::open(path).ok()?;
+    file.read_exact(&mut header).ok()?;
+    let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    if header[0..4] != MAGIC_BYTES || version != CURRENT_SCHEMA_VERSION {
+        return None;
+    }
+    file.metadata().ok().map(|m| m.len())
+}
+
+/// `last_saved_at` of the project file at `path`.
+fn project_saved_at(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).map_err(|e| PersistenceError::Io {
+        operation: "read",
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(parse_project_bytes(&bytes, path)?.last_saved_at)
+}
+
+/// Whether a journal of `journal_len` bytes should be folded into the project.
+fn should_compact(journal_len: u64, main_len: u64) -> bool {
+    journal_len > main_len.max(MIN_COMPACT_BYTES)
+}
+
+/// Length of the valid prefix of a journal: the header and every complete
+/// record. Zero if the header is missing or from another schema version.
+fn complete_len(bytes: &[u8]) -> usize {
+    if bytes.len() < 8
+        || bytes[0..4] != JOURNAL_MAGIC
+        || bytes[4..8] != CURRENT_SCHEMA_VERSION.to_le_bytes()
+    {
+        return 0;
+    }
+    let mut pos = 8;
+    while let Some(len_bytes) = bytes.get(pos..pos + 4) {
+        let len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]);
+        let end = pos + 4 + len as usize;
+        if end > bytes.len() {
+            break;
+        }
+        pos = end;
+    }
+    pos
+}
+
+/// The complete records of a journal, without their length prefixes.
+fn records(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let end = complete_len(bytes);
+    let mut pos = 8;
+    std::iter::from_fn(move || {
+        if pos >= end {
+            return None;
+        }
+        let len = u32::from_le_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]]);
+        let record = &bytes[pos + 4..pos + 4 + len as usize];
+        pos += 4 + len as usize;
+        Some(record)
+    })
+}
+
+/// Remove the journal of a project file, if any.
+pub(super) fn remove_journal(path: &Path) -> Result<()> {
+    let journal = journal_path(path);
+    match fs::remove_file(&journal) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(PersistenceError::Io {
+            operation: "remove",
+            path: journal,
+            source: e,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Apply the journal of a project file, if any, to the loaded project.
+///
+/// Records stamped for another version of the project file are skipped. A
+/// journal that is unreadable, outdated, or entirely stale is discarded.
+pub(super) fn replay_journal(project: &mut ProjectFile, path: &Path) -> Result<()> {
+    let journal = journal_path(path);
+    let bytes = match fs::read(&journal) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => {
+            return Err(PersistenceError::Io {
+                operation: "read",
+                path: journal,
+                source: e,
+            });
+        }
+    };
+
+    let discard = |reason: &str| {
+        tracing::warn!("Discarding journal {}: {}", journal.display(), reason);
+        if let Err(e) = remove_journal(path) {
+            tracing::warn!("Failed to remove journal {}: {}", journal.display(), e);
+        }
+    };
+
+    if bytes.len() < 8 || bytes[0..4] != JOURNAL_MAGIC {
+        discard("not a TSS journal file (invalid magic bytes)");
+        return Ok(());
+    }
+    let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(PersistenceError::UnsupportedVersion {
+            found: version,
+            max_supported: CURRENT_SCHEMA_VERSION,
+            path: journal,
+        });
+    }
+    if version < CURRENT_SCHEMA_VERSION {
+        discard(&format!("journal schema version {version} is outdated"));
+        return Ok(());
+    }
+
+    let base_saved_at = project.last_saved_at.clone();
+    let mut replayed = 0usize;
+    let mut stale = 0usize;
+    for record in records(&bytes) {
+        let entry: JournalEntry = decode(record)?;
+        if entry.base_saved_at != base_saved_at {
+            stale += 1;
+            continue;
+        }
+        match entry.snapshot {
+            Some(snapshot) => {
+                project.domains.insert(entry.domain_code, snapshot);
+            }
+            None => {
+                project.domains.remove(&entry.domain_code);
+            }
+        }
+        project.last_saved_at = entry.saved_at;
+        replayed += 1;
+    }
+    if complete_len(&bytes) < bytes.len() {
+        tracing::warn!(
+            "Ignoring incomplete record at the end of {}",
+            journal.display()
+        );
+    }
+    if stale > 0 && replayed == 0 {
+        // Left behind by a full save that crashed before removing it
+        discard("written for an older version of the project file");
+        return Ok(());
+    }
+    if stale > 0 {
+        tracing::warn!(
+            records = stale,
+            "Skipped journal records for an older version of {}",
+            path.display()
+        );
+    }
+
+    tracing::info!(
+        records = replayed,
+        "Replayed project journal {}",
+        journal.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::load::load_project;
+    use crate::types::{MappingEntry, StudyMetadata, WorkflowTypeSnapshot};
+    use tempfile::tempdir;
+
+    /// A project with `count` source domains, each with a few mappings.
+    fn make_project(count: usize) -> ProjectFile {
+        let study = StudyMetadata::new("TEST_STUDY", "/path/to/study", WorkflowTypeSnapshot::Sdtm);
+        let mut project = ProjectFile::new(study);
+        for i in 0..count {
+            let code = format!("D{i:02}");
+            let mut domain = DomainSnapshot::new(code.clone());
+            let source = domain.as_source_mut().unwrap();
+            for v in 0..20 {
+                source.mapping.accepted.insert(
+                    format!("{code}VAR{v:02}"),
+                    MappingEntry::new(format!("COLUMN_{v:02}")),
+                );
+            }
+            project.domains.insert(code, domain);
+        }
+        project
+    }
+
+    #[test]
+    fn test_single_domain_edit_appends_small_delta() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("study.tss");
+        let mut project = make_project(20);
+        save_project(&mut project, &path).unwrap();
+        let main_len = fs::metadata(&path).unwrap().len();
+
+        // Edit one domain
+        let source = project
+            .domains
+            .get_mut("D07")
+            .unwrap()
+            .as_source_mut()
+            .unwrap();
+        source
+            .mapping
+            .accepted
+            .insert("D07VAR99".to_string(), MappingEntry::new("NEW_COLUMN"));
+        source
+            .mapping
+            .not_collected
+            .insert("D07VAR00".to_string(), "Not collected".to_string());
+        let changed = BTreeSet::from(["D07".to_string()]);
+
+        let outcome = save_project_journal(&mut project, &path, &changed).unwrap();
+        let JournalSave::Appended { domains, bytes } = outcome else {
+            panic!("expected an appended delta, got {outcome:?}");
+        };
+        assert_eq!(domains, 1);
+        assert!(bytes * 5 < main_len, "delta {bytes} vs project {main_len}");
+        // The main file is untouched
+        assert_eq!(fs::metadata(&path).unwrap().len(), main_len);
+        assert_eq!(fs::metadata(journal_path(&path)).unwrap().len(), bytes);
+
+        let loaded = load_project(&path).unwrap();
+        let d07 = loaded.domains["D07"].as_source().unwrap();
+        assert_eq!(d07.mapping.accepted["D07VAR99"].source_column, "NEW_COLUMN");
+        assert!(d07.mapping.not_collected.contains_key("D07VAR00"));
+        assert_eq!(loaded.domains.len(), 20);
+        assert_eq!(loaded.last_saved_at, project.last_saved_at);
+    }
+
+    #[test]
+    fn test_journal_replay_order_and_compaction() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("study.tss");
+        let mut project = make_project(3);
+
+        // No project file yet: falls back to a full save
+        let changed = BTreeSet::from(["D00".to_string()]);
+        assert_eq!(
+            save_project_journal(&mut project, &path, &changed).unwrap(),
+            JournalSave::Compacted
+        );
+
+        // Later records win; removed domains are dropped
+        project
+            .domains
+            .get_mut("D00")
+            .unwrap()
+            .as_source_mut()
+            .unwrap()
+            .label = Some("First".to_string());
+        save_project_journal(&mut project, &path, &changed).unwrap();
+        project
+            .domains
+            .get_mut("D00")
+            .unwrap()
+            .as_source_mut()
+            .unwrap()
+            .label = Some("Second".to_string());
+        project.domains.remove("D02");
+        let changed = BTreeSet::from(["D00".to_string(), "D02".to_string()]);
+        save_project_journal(&mut project, &path, &changed).unwrap();
+
+        let loaded = load_project(&path).unwrap();
+        let d00 = loaded.domains["D00"].as_source().unwrap();
+        assert_eq!(d00.label.as_deref(), Some("Second"));
+        assert!(!loaded.domains.contains_key("D02"));
+
+        // A full save folds the journal into the project file
+        save_project(&mut project, &path).unwrap();
+        assert!(!journal_path(&path).exists());
+        assert!(!load_project(&path).unwrap().domains.contains_key("D02"));
+    }
+
+    #[test]
+    fn test_truncated_record_is_ignored() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("study.tss");
+        let mut project = make_project(2);
+        save_project(&mut project, &path).unwrap();
+
+        project
+            .domains
+            .get_mut("D01")
+            .unwrap()
+            .as_source_mut()
+            .unwrap()
+            .label = Some("Saved".to_string());
+        save_project_journal(&mut project, &path, &BTreeSet::from(["D01".to_string()])).unwrap();
+
+        // Simulate a crash halfway through a second append
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(journal_path(&path))
+            .unwrap();
+        file.write_all(&500u32.to_le_bytes()).unwrap();
+        file.write_all(&[0u8; 10]).unwrap();
+
+        let loaded = load_project(&path).unwrap();
+        let d01 = loaded.domains["D01"].as_source().unwrap();
+        assert_eq!(d01.label.as_deref(), Some("Saved"));
+
+        // The next append replaces the torn tail
+        project
+            .domains
+            .get_mut("D00")
+            .unwrap()
+            .as_source_mut()
+            .unwrap()
+            .label = Some("After crash".to_string());
+        save_project_journal(&mut project, &path, &BTreeSet::from(["D00".to_string()])).unwrap();
+        let loaded = load_project(&path).unwrap();
+        let d00 = loaded.domains["D00"].as_source().unwrap();
+        assert_eq!(d00.label.as_deref(), Some("After crash"));
+        assert_eq!(
+            loaded.domains["D01"].as_source().unwrap().label.as_deref(),
+            Some("Saved")
+        );
+    }
+
+    #[test]
+    fn test_stale_journal_is_discarded() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("study.tss");
+        let mut project = make_project(2);
+        save_project(&mut project, &path).unwrap();
+
+        let set_label = |project: &mut ProjectFile, label: &str| {
+            project
+                .domains
+                .get_mut("D00")
+                .unwrap()
+                .as_source_mut()
+                .unwrap()
+                .label = Some(label.to_string());
+        };
+        set_label(&mut project, "Journaled");
+        save_project_journal(&mut project, &path, &BTreeSet::from(["D00".to_string()])).unwrap();
+        let stale = fs::read(journal_path(&path)).unwrap();
+
+        // A full save that crashed before removing the journal
+        set_label(&mut project, "Saved");
+        save_project(&mut project, &path).unwrap();
+        fs::write(journal_path(&path), &stale).unwrap();
+
+        let loaded = load_project(&path).unwrap();
+        let d00 = loaded.domains["D00"].as_source().unwrap();
+        assert_eq!(d00.label.as_deref(), Some("Saved"));
+        assert!(!journal_path(&path).exists());
+    }
+
+    #[test]
+    fn test_invalid_journal_is_discarded() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("study.tss");
+        let mut project = make_project(1);
+        save_project(&mut project, &path).unwrap();
+
+        fs::write(journal_path(&path), b"NOT A JOURNAL").unwrap();
+        assert_eq!(load_project(&path).unwrap().domains.len(), 1);
+        assert!(!journal_path(&path).exists());
+
+        let mut outdated = JOURNAL_MAGIC.to_vec();
+        outdated.extend_from_slice(&(CURRENT_SCHEMA_VERSION - 1).to_le_bytes());
+        fs::write(journal_path(&path), &outdated).unwrap();
+        assert_eq!(load_project(&path).unwrap().domains.len(), 1);
+        assert!(!journal_path(&path).exists());
+    }
+
+    #[test]
+    fn test_should_compact() {
+        assert!(!should_compact(1024, 10 * 1024));
+        assert!(!should_compact(200 * 1024, 10 * 1024));
+        assert!(should_compact(300 * 1024, 10 * 1024));
+        assert!(should_compact(2 * 1024 * 1024, 1024 * 1024));
+    }
+}
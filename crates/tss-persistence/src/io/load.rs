@@ -4,9 +4,16 @@
 use std::path::Path;
 
 use crate::error::{PersistenceError, Result};
+use crate::migrate::decode_payload;
 use crate::types::{CURRENT_SCHEMA_VERSION, MAGIC_BYTES, ProjectFile};
 
+use super::journal::replay_journal;
+
 /// Load a project from a .tss file.
+///
+/// Files saved with an older schema version are upgraded in memory; see
+/// the `migrate` module. Domain changes saved to the project's journal are
+/// replayed on top.
 pub fn load_project(path: &Path) -> Result<ProjectFile> {
     // Read the file
     let bytes = fs::read(path).map_err(|e| PersistenceError::Io {
@@ -15,8 +22,10 @@ pub fn load_project(path: &Path) -> Result<ProjectFile> {
         source: e,
     })?;
 
-    // Validate and parse
-    parse_project_bytes(&bytes, path)
+    // Validate and parse, then apply incremental saves
+    let mut project = parse_project_bytes(&bytes, path)?;
+    replay_journal(&mut project, path)?;
+    Ok(project)
 }
 
 /// Load a project asynchronously.
@@ -32,7 +41,7 @@ pub async fn load_project_async(path: std::path::PathBuf) -> Result<ProjectFile>
 }
 
 /// Parse project bytes and validate the format.
-fn parse_project_bytes(bytes: &[u8], path: &Path) -> Result<ProjectFile> {
+pub(super) fn parse_project_bytes(bytes: &[u8], path: &Path) -> Result<ProjectFile> {
     // Minimum size: magic (4) + version (4) + some payload
     if bytes.len() < 12 {
         return Err(PersistenceError::InvalidFormat {
@@ -41,8 +50,9 @@ fn parse_project_bytes(bytes: &[u8], path: &Path) -> Result<ProjectFile> {
         });
     }
 
-    // Check magic bytes
-    if bytes[0..4] != MAGIC_BYTES {
+    // Check magic bytes (the last byte tracks the schema version, so older
+    // files differ there and are identified by the header version instead)
+    if bytes[0..3] != MAGIC_BYTES[0..3] {
         return Err(PersistenceError::InvalidFormat {
             path: path.to_path_buf(),
             reason: "Not a TSS project file (invalid magic bytes)".to_string(),
@@ -52,6 +62,13 @@ fn parse_project_bytes(bytes: &[u8], path: &Path) -> Result<ProjectFile> {
     // Read schema version
     let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
 
+    if version == 0 {
+        return Err(PersistenceError::InvalidFormat {
+            path: path.to_path_buf(),
+            reason: "Invalid schema version 0".to_string(),
+        });
+    }
+
     if version > CURRENT_SCHEMA_VERSION {
         return Err(PersistenceError::UnsupportedVersion {
             found: version,
@@ -63,13 +80,8 @@ fn parse_project_bytes(bytes: &[u8], path: &Path) -> Result<ProjectFile> {
     // Extract rkyv payload
     let payload = &bytes[8..];
 
-    // Deserialize with rkyv high-level API
-    let project: ProjectFile = rkyv::from_bytes::<ProjectFile, rkyv::rancor::Error>(payload)
-        .map_err(|e| PersistenceError::Deserialization {
-            source: Box::new(std::io::Error::other(format!(
-                "rkyv deserialization failed: {e}"
-            ))),
-        })?;
+    // Deserialize and upgrade older schema versions
+    let project = decode_payload(version, payload, path)?;
 
     tracing::info!("Loaded project from {}", path.display());
     Ok(project)
@@ -100,6 +112,17 @@ fn test_load_project_round_trip() {
         assert_eq!(loaded.study.ct_version, Some("2024-03-29".to_string()));
     }
 
+    #[test]
+    fn test_load_v1_project_is_upgraded() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/project_v1.tss");
+
+        let loaded = load_project(&path).unwrap();
+        assert_eq!(loaded.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(loaded.study.study_id, "LEGACY01");
+        assert!(loaded.domains["DM"].is_source());
+    }
+
     #[test]
     fn test_load_invalid_magic() {
         let dir = tempdir().unwrap();
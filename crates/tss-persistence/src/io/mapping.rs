@@ -0,0 +1,303 @@
+//! Mapping repository storage.
+//!
+//! Stored mappings are JSON files, one per mapping, in a repository folder.
+//! Files written by older versions are migrated to the current schema when
+//! loaded.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::error::{PersistenceError, Result};
+use crate::types::{MAPPING_SCHEMA_VERSION, StoredMappingConfig};
+
+/// Loads stored mapping files, upgrading older schema versions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MappingConfigLoader;
+
+impl MappingConfigLoader {
+    /// Load a stored mapping from a JSON file.
+    pub fn load(path: &Path) -> Result<StoredMappingConfig> {
+        let json = fs::read_to_string(path).map_err(|e| PersistenceError::Io {
+            operation: "read",
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::from_json(&json, path)
+    }
+
+    /// Parse a stored mapping, migrating it to [`MAPPING_SCHEMA_VERSION`].
+    ///
+    /// Files without a `schema_version` are treated as version 1. `path` is
+    /// only used for error reporting.
+    pub fn from_json(json: &str, path: &Path) -> Result<StoredMappingConfig> {
+        let mut value: Value =
+            serde_json::from_str(json).map_err(|e| PersistenceError::InvalidFormat {
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            })?;
+
+        let version = value
+            .get("schema_version")
+            .and_then(Value::as_u64)
+            .map_or(1, |v| v as u32);
+        if version > MAPPING_SCHEMA_VERSION {
+            return Err(PersistenceError::UnsupportedVersion {
+                found: version,
+                max_supported: MAPPING_SCHEMA_VERSION,
+                path: path.to_path_buf(),
+            });
+        }
+
+        if version < 2 {
+            value = migrate_v1_to_v2(value);
+        }
+
+        serde_json::from_value(value).map_err(|e| PersistenceError::Deserialization {
+            source: Box::new(e),
+        })
+    }
+}
+
+/// v1 files had no timestamps; stamp them with the migration time.
+fn migrate_v1_to_v2(mut value: Value) -> Value {
+    let now = Value::String(Utc::now().to_rfc3339());
+    if let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut) {
+        metadata.entry("created_at").or_insert_with(|| now.clone());
+        metadata.entry("modified_at").or_insert(now);
+    }
+    if let Some(root) = value.as_object_mut() {
+        root.insert("schema_version".to_string(), Value::from(2));
+    }
+    value
+}
+
+/// A folder of reusable mapping configurations.
+#[derive(Debug, Clone)]
+pub struct MappingRepository {
+    dir: PathBuf,
+}
+
+impl MappingRepository {
+    /// Open a repository rooted at `dir`. The folder is created on first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Get the repository folder.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the file a mapping with this name is stored in.
+    ///
+    /// ASCII letters, digits and `-` are kept; every other byte is written
+    /// as `_` and two hex digits, so different names get different files.
+    pub fn path_for(&self, name: &str) -> PathBuf {
+        let mut stem = String::with_capacity(name.len());
+        for byte in name.bytes() {
+            if byte.is_ascii_alphanumeric() || byte == b'-' {
+                stem.push(char::from(byte));
+            } else {
+                stem.push_str(&format!("_{byte:02X}"));
+            }
+        }
+        self.dir.join(format!("{stem}.json"))
+    }
+
+    /// Save a mapping at the current schema version, updating its modified time.
+    ///
+    /// Uses atomic write (temp file + rename), like project saves.
+    pub fn save(&self, stored: &mut StoredMappingConfig) -> Result<PathBuf> {
+        stored.schema_version = MAPPING_SCHEMA_VERSION;
+        stored.metadata.touch();
+
+        let json =
+            serde_json::to_string_pretty(stored).map_err(|e| PersistenceError::Serialization {
+                source: Box::new(e),
+            })?;
+
+        fs::create_dir_all(&self.dir).map_err(|e| PersistenceError::Io {
+            operation: "create directory",
+            path: self.dir.clone(),
+            source: e,
+        })?;
+
+        let path = self.path_for(&stored.metadata.name);
+        // Case-insensitive file systems can still fold two names together
+        if path.exists() {
+            let existing = MappingConfigLoader::load(&path)?;
+            if existing.metadata.name != stored.metadata.name {
+                return Err(PersistenceError::MappingNameTaken {
+                    name: stored.metadata.name.clone(),
+                    existing: existing.metadata.name,
+                    path,
+                });
+            }
+        }
+
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, json).map_err(|e| PersistenceError::Io {
+            operation: "write",
+            path: temp_path.clone(),
+            source: e,
+        })?;
+        fs::rename(&temp_path, &path).map_err(|e| PersistenceError::AtomicWriteFailed {
+            temp_path: temp_path.clone(),
+            target_path: path.clone(),
+            source: e,
+        })?;
+
+        tracing::info!(
+            "Saved mapping '{}' to {}",
+            stored.metadata.name,
+            path.display()
+        );
+        Ok(path)
+    }
+
+    /// Load a mapping by name.
+    pub fn load(&self, name: &str) -> Result<StoredMappingConfig> {
+        MappingConfigLoader::load(&self.path_for(name))
+    }
+
+    /// Load every mapping in the repository, sorted by name.
+    ///
+    /// Files that cannot be loaded are skipped with a warning. Returns an
+    /// empty list if the folder does not exist yet.
+    pub fn list(&self) -> Result<Vec<StoredMappingConfig>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = fs::read_dir(&self.dir).map_err(|e| PersistenceError::Io {
+            operation: "read",
+            path: self.dir.clone(),
+            source: e,
+        })?;
+
+        let mut mappings = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                match MappingConfigLoader::load(&path) {
+                    Ok(stored) => mappings.push(stored),
+                    Err(e) => {
+                        tracing::warn!("Skipping mapping file {}: {e}", path.display());
+                    }
+                }
+            }
+        }
+        mappings.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+        Ok(mappings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, BTreeSet};
+    use tempfile::tempdir;
+    use tss_submit::{Mapping, MappingConfig};
+
+    /// A mapping file in the unversioned (v1) layout: no `schema_version`,
+    /// and `metadata` holds only a name and description.
+    ///
+    /// Written by hand from that layout rather than captured from a released
+    /// build, so it only covers the fields v1 and v2 differ in.
+    const V1_FIXTURE: &str = r#"{
+        "metadata": {
+            "name": "EDC adverse events",
+            "description": "AE export from the EDC"
+        },
+        "config": {
+            "domain_code": "AE",
+            "study_id": "DEMO",
+            "mappings": [
+                { "source_column": "TERM", "target_variable": "AETERM", "confidence": 0.92 }
+            ],
+            "not_collected": { "AESER": "Not captured" },
+            "omitted": []
+        }
+    }"#;
+
+    fn config() -> MappingConfig {
+        MappingConfig {
+            domain_code: "DM".to_string(),
+            study_id: "DEMO".to_string(),
+            mappings: vec![Mapping {
+                source_column: "SEX".to_string(),
+                target_variable: "SEX".to_string(),
+                confidence: 1.0,
+                concat: None,
+            }],
+            not_collected: BTreeMap::new(),
+            omitted: BTreeSet::new(),
+        }
+    }
+
+    #[test]
+    fn test_load_v1_fixture_migrates() {
+        let stored = MappingConfigLoader::from_json(V1_FIXTURE, Path::new("v1.json")).unwrap();
+
+        assert_eq!(stored.schema_version, MAPPING_SCHEMA_VERSION);
+        assert_eq!(stored.metadata.name, "EDC adverse events");
+        assert!(stored.metadata.created_at().is_some());
+        assert!(stored.metadata.modified_at().is_some());
+        assert_eq!(stored.config.mappings[0].target_variable, "AETERM");
+        assert!(stored.config.mappings[0].concat.is_none());
+        assert_eq!(stored.config.not_collected["AESER"], "Not captured");
+    }
+
+    #[test]
+    fn test_load_future_version_rejected() {
+        let json = r#"{ "schema_version": 99, "metadata": {}, "config": {} }"#;
+        let result = MappingConfigLoader::from_json(json, Path::new("future.json"));
+        assert!(matches!(
+            result,
+            Err(PersistenceError::UnsupportedVersion { found: 99, .. })
+        ));
+    }
+
+    #[test]
+    fn test_repository_round_trip() {
+        let dir = tempdir().unwrap();
+        let repo = MappingRepository::new(dir.path().join("mappings"));
+        assert!(repo.list().unwrap().is_empty());
+
+        let mut stored = StoredMappingConfig::new("DM / baseline", config());
+        let path = repo.save(&mut stored).unwrap();
+        assert_eq!(path, repo.path_for("DM / baseline"));
+
+        let loaded = repo.load("DM / baseline").unwrap();
+        assert_eq!(loaded.schema_version, MAPPING_SCHEMA_VERSION);
+        assert_eq!(loaded.metadata.created_at, stored.metadata.created_at);
+        assert_eq!(loaded.config.mappings.len(), 1);
+
+        // Old files in the repository are upgraded on load
+        fs::write(repo.path_for("legacy"), V1_FIXTURE).unwrap();
+        let names: Vec<String> = repo
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.metadata.name)
+            .collect();
+        assert_eq!(names, ["DM / baseline", "EDC adverse events"]);
+
+        // A broken file does not hide the others
+        fs::write(dir.path().join("mappings").join("broken.json"), "{").unwrap();
+        assert_eq!(repo.list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_path_for_keeps_names_apart() {
+        let repo = MappingRepository::new("mappings");
+        assert_ne!(
+            repo.path_for("DM / baseline"),
+            repo.path_for("DM _ baseline")
+        );
+        assert_ne!(repo.path_for("a b"), repo.path_for("a_b"));
+        assert_eq!(repo.path_for("AE-v2"), Path::new("mappings/AE-v2.json"));
+    }
+}
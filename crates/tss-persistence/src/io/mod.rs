@@ -4,11 +4,22 @@
 //! - Saving projects with atomic writes
 //! - Loading projects with format validation
 //! - Source file hashing for change detection
+//! - Reusable mapping repository (versioned JSON)
+//! - Portable zip bundles of a project and its source files
+//! - Incremental saves through a per-domain journal
 
+mod bundle;
 mod hash;
+mod journal;
 mod load;
+mod mapping;
 mod save;
 
-pub use hash::{compute_file_hash, verify_file_hash};
+pub use bundle::{LoadedBundle, extract_project_bundle, load_project_bundle, save_project_bundle};
+pub use hash::{
+    SourceChange, SourceStatus, compute_file_hash, detect_source_changes, verify_file_hash,
+};
+pub use journal::{JournalSave, journal_path, save_project_journal, save_project_journal_async};
 pub use load::{load_project, load_project_async};
+pub use mapping::{MappingConfigLoader, MappingRepository};
 pub use save::{save_project, save_project_async};
@@ -7,10 +7,13 @@
 use crate::error::{PersistenceError, Result};
 use crate::types::{CURRENT_SCHEMA_VERSION, MAGIC_BYTES, ProjectFile};
 
+use super::journal::remove_journal;
+
 /// Save a project to a .tss file.
 ///
 /// Uses atomic write (temp file + rename) to prevent data corruption
-/// on crash or power loss.
+/// on crash or power loss. Any journal of incremental saves is removed,
+/// since the rewritten file contains its changes.
 pub fn save_project(project: &mut ProjectFile, path: &Path) -> Result<()> {
     // Update the last saved timestamp
     project.touch();
@@ -56,6 +59,10 @@ pub fn save_project(project: &mut ProjectFile, path: &Path) -> Result<()> {
         source: e,
     })?;
 
+    // The full payload now includes every journaled change. A crash before
+    // this leaves a stale journal, which loading recognises and discards.
+    remove_journal(path)?;
+
     tracing::info!("Saved project to {}", path.display());
     Ok(())
 }
@@ -81,7 +88,7 @@ pub async fn save_project_async(project: ProjectFile, path: std::path::PathBuf)
 /// - 4 bytes: Magic ("TSS\x01")
 /// - 4 bytes: Schema version (u32 little-endian)
 /// - N bytes: rkyv payload
-fn serialize_project(project: &ProjectFile) -> Result<Vec<u8>> {
+pub(super) fn serialize_project(project: &ProjectFile) -> Result<Vec<u8>> {
     // Serialize with rkyv using high-level API
     let rkyv_bytes = rkyv::to_bytes::<rkyv::rancor::Error>(project).map_err(|e| {
         PersistenceError::Serialization {
@@ -7,8 +7,11 @@
 //!
 //! - **Zero-copy serialization** with rkyv for fast load times
 //! - **Atomic writes** to prevent data corruption
+//! - **Schema migration** of projects saved by older versions
 //! - **Source change detection** via SHA-256 hashing
-//! - **Auto-save** with debounce support
+//! - **Auto-save** with debounce support and incremental per-domain journaling
+//! - **Mapping repository** of reusable, versioned JSON mappings
+//! - **Project bundles**: zip archives of a project and its source files
 //!
 //! # File Format
 //!
@@ -16,9 +19,9 @@
 //!
 //! ```text
 //! +------------------+
-//! | Magic: "TSS\x01" | 4 bytes - file identification
+//! | Magic: "TSS\x02" | 4 bytes - file identification
 //! +------------------+
-//! | Version: 1       | 4 bytes - u32 little-endian schema version
+//! | Version: 2       | 4 bytes - u32 little-endian schema version
 //! +------------------+
 //! | rkyv Payload     | Variable - zero-copy deserializable
 //! +------------------+
@@ -46,8 +49,9 @@
 //! The crate is organized into:
 //!
 //! - `types/` - Persistence types (rkyv-serializable snapshots)
-//! - `io/` - File I/O operations (save, load, hash)
+//! - `io/` - File I/O operations (save, load, hash, bundle)
 //! - `autosave/` - Auto-save infrastructure (DirtyTracker, config)
+//! - `migrate/` - Upgrades from older schema versions
 //! - `convert.rs` - Conversion traits and helpers
 //! - `error.rs` - Error types with user-friendly messages
 
@@ -55,6 +59,7 @@
 mod convert;
 mod error;
 mod io;
+mod migrate;
 mod types;
 
 // Re-export main types
@@ -65,14 +70,16 @@
 };
 pub use error::{PersistenceError, Result};
 pub use io::{
-    compute_file_hash, load_project, load_project_async, save_project, save_project_async,
-    verify_file_hash,
+    JournalSave, LoadedBundle, MappingConfigLoader, MappingRepository, SourceChange, SourceStatus,
+    compute_file_hash, detect_source_changes, extract_project_bundle, journal_path, load_project,
+    load_project_async, load_project_bundle, save_project, save_project_async, save_project_bundle,
+    save_project_journal, save_project_journal_async, verify_file_hash,
 };
 pub use types::{
     CURRENT_SCHEMA_VERSION, CommentEntrySnapshot, DomainSnapshot, GeneratedDomainEntrySnapshot,
-    GeneratedDomainSnapshot, GeneratedDomainTypeSnapshot, MAGIC_BYTES, MappingEntry,
-    MappingSnapshot, ProjectFile, ProjectPlaceholders, RelrecEntrySnapshot, RelrecRelTypeSnapshot,
-    RelspecEntrySnapshot, RelsubEntrySnapshot, SourceAssignment, SourceDomainSnapshot,
-    StudyMetadata, SuppActionSnapshot, SuppColumnSnapshot, SuppOriginSnapshot,
-    WorkflowTypeSnapshot,
+    GeneratedDomainSnapshot, GeneratedDomainTypeSnapshot, MAGIC_BYTES, MAPPING_SCHEMA_VERSION,
+    MappingEntry, MappingMetadata, MappingSnapshot, ProjectFile, ProjectPlaceholders,
+    RelrecEntrySnapshot, RelrecRelTypeSnapshot, RelspecEntrySnapshot, RelsubEntrySnapshot,
+    SourceAssignment, SourceDomainSnapshot, StoredMappingConfig, StudyMetadata, SuppActionSnapshot,
+    SuppColumnSnapshot, SuppOriginSnapshot, WorkflowTypeSnapshot,
 };
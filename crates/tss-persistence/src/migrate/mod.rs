@@ -0,0 +1,147 @@
+//! Schema migrations for `.tss` project files.
+//!
+//! Each schema version whose payload layout changed keeps its archived type
+//! in a `vN` module, with a [`Migration`] to the next version. Loading reads
+//! the header version, deserializes the payload as that version's type, and
+//! applies the chain of migrations up to [`CURRENT_SCHEMA_VERSION`].
+//!
+//! When bumping the schema version:
+//! 1. Copy the outgoing `ProjectFile` layout into a new `vN` module
+//! 2. Implement [`Migration`] from it to the new layout
+//! 3. Point the previous version's `Next` at the `vN` type and add a
+//!    `N =>` arm to [`decode_payload`]
+
+mod v1;
+
+use std::path::Path;
+
+use rkyv::Archive;
+use rkyv::bytecheck::CheckBytes;
+use rkyv::de::Pool;
+use rkyv::rancor::{Error, Strategy};
+use rkyv::util::AlignedVec;
+use rkyv::validation::Validator;
+use rkyv::validation::archive::ArchiveValidator;
+use rkyv::validation::shared::SharedValidator;
+
+use crate::error::{PersistenceError, Result};
+use crate::types::{CURRENT_SCHEMA_VERSION, ProjectFile};
+
+use self::v1::ProjectFileV1;
+
+/// Upgrade of a project payload from one schema version to the next.
+pub(crate) trait Migration {
+    /// Schema version this payload was archived with.
+    const FROM_VERSION: u32;
+
+    /// Payload type of schema version `FROM_VERSION + 1`.
+    type Next;
+
+    /// Convert to the next schema version.
+    fn migrate(self) -> Self::Next;
+}
+
+/// Deserialize an rkyv payload of the given schema version and upgrade it
+/// to the current [`ProjectFile`].
+pub(crate) fn decode_payload(version: u32, payload: &[u8], path: &Path) -> Result<ProjectFile> {
+    let project = match version {
+        1 => {
+            tracing::info!(
+                "Migrating project {} from schema v1 to v{}",
+                path.display(),
+                CURRENT_SCHEMA_VERSION
+            );
+            decode::<ProjectFileV1>(payload)?.migrate()
+        }
+        CURRENT_SCHEMA_VERSION => decode::<ProjectFile>(payload)?,
+        _ => {
+            return Err(PersistenceError::UnsupportedVersion {
+                found: version,
+                max_supported: CURRENT_SCHEMA_VERSION,
+                path: path.to_path_buf(),
+            });
+        }
+    };
+    Ok(project)
+}
+
+/// Deserialize an rkyv payload as `T`.
+///
+/// The payload follows an 8-byte header, so it is copied to an aligned
+/// buffer before validation.
+pub(crate) fn decode<T>(payload: &[u8]) -> Result<T>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<Strategy<Validator<ArchiveValidator<'a>, SharedValidator>, Error>>
+        + rkyv::Deserialize<T, Strategy<Pool, Error>>,
+{
+    let mut aligned = AlignedVec::<16>::with_capacity(payload.len());
+    aligned.extend_from_slice(payload);
+    rkyv::from_bytes::<T, Error>(&aligned).map_err(|e| PersistenceError::Deserialization {
+        source: Box::new(std::io::Error::other(format!(
+            "rkyv deserialization failed: {e}"
+        ))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MAGIC_BYTES, WorkflowTypeSnapshot};
+
+    /// Project saved by schema v1: header followed by the rkyv payload.
+    const V1_PROJECT: &[u8] = include_bytes!("../../tests/data/project_v1.tss");
+
+    #[test]
+    fn test_migrate_v1_payload() {
+        assert_eq!(&V1_PROJECT[0..4], b"TSS\x01");
+        let version = u32::from_le_bytes(V1_PROJECT[4..8].try_into().unwrap());
+        assert_eq!(version, 1);
+
+        let project = decode_payload(version, &V1_PROJECT[8..], Path::new("v1.tss")).unwrap();
+
+        assert_eq!(project.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(project.study.study_id, "LEGACY01");
+        assert_eq!(project.study.workflow_type, WorkflowTypeSnapshot::Sdtm);
+        assert_eq!(project.study.ct_version.as_deref(), Some("2023-12-15"));
+        assert_eq!(project.created_at, "2024-05-01T09:00:00+00:00");
+        assert_eq!(project.source_assignments[0].domain_code, "DM");
+
+        // v1 domains become source domain snapshots
+        let dm = project.domains["DM"].as_source().unwrap();
+        assert_eq!(dm.label.as_deref(), Some("Demographics"));
+        assert_eq!(dm.mapping.accepted["AGE"].source_column, "AGE_YRS");
+        assert!(dm.mapping.not_collected.contains_key("ETHNIC"));
+        assert!(dm.mapping.auto_generated.contains("STUDYID"));
+    }
+
+    /// Project saved by schema v2, with a source and a generated domain.
+    const V2_PROJECT: &[u8] = include_bytes!("../../tests/data/project_v2.tss");
+
+    #[test]
+    fn test_decode_v2_payload() {
+        assert_eq!(&V2_PROJECT[0..4], &MAGIC_BYTES);
+        let version = u32::from_le_bytes(V2_PROJECT[4..8].try_into().unwrap());
+        assert_eq!(version, CURRENT_SCHEMA_VERSION);
+
+        let project = decode_payload(version, &V2_PROJECT[8..], Path::new("v2.tss")).unwrap();
+
+        assert_eq!(project.study.study_id, "STUDY02");
+        assert_eq!(project.created_at, "2025-02-03T10:00:00+00:00");
+        let ae = project.domains["AE"].as_source().unwrap();
+        assert_eq!(ae.mapping.accepted["AETERM"].source_column, "TERM");
+        assert_eq!(ae.mapping.not_collected["AESER"], "Not captured");
+        assert!(ae.mapping.omitted.contains("AESPID"));
+        let co = project.domains["CO"].as_generated().unwrap();
+        assert_eq!(co.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_decode_unknown_version() {
+        let result = decode_payload(99, &[], Path::new("future.tss"));
+        assert!(matches!(
+            result,
+            Err(PersistenceError::UnsupportedVersion { found: 99, .. })
+        ));
+    }
+}
@@ -0,0 +1,48 @@
+//! Schema v1: domains stored as plain source snapshots.
+//!
+//! Before v2, every domain was mapped from a source file, so `domains` held
+//! [`SourceDomainSnapshot`]s directly rather than the [`DomainSnapshot`] enum.
+
+use std::collections::BTreeMap;
+
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::types::{
+    DomainSnapshot, ProjectFile, ProjectPlaceholders, SourceAssignment, SourceDomainSnapshot,
+    StudyMetadata,
+};
+
+use super::Migration;
+
+/// Project file as archived by schema v1.
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+pub(crate) struct ProjectFileV1 {
+    pub schema_version: u32,
+    pub created_at: String,
+    pub last_saved_at: String,
+    pub study: StudyMetadata,
+    pub source_assignments: Vec<SourceAssignment>,
+    pub domains: BTreeMap<String, SourceDomainSnapshot>,
+    pub placeholders: ProjectPlaceholders,
+}
+
+impl Migration for ProjectFileV1 {
+    const FROM_VERSION: u32 = 1;
+    type Next = ProjectFile;
+
+    fn migrate(self) -> ProjectFile {
+        ProjectFile {
+            schema_version: Self::FROM_VERSION + 1,
+            created_at: self.created_at,
+            last_saved_at: self.last_saved_at,
+            study: self.study,
+            source_assignments: self.source_assignments,
+            domains: self
+                .domains
+                .into_iter()
+                .map(|(code, domain)| (code, DomainSnapshot::Source(domain)))
+                .collect(),
+            placeholders: self.placeholders,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Reusable mapping configuration types.
+//!
+//! Unlike project files, stored mappings are plain JSON so they can be shared
+//! between studies and inspected by hand.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tss_submit::MappingConfig;
+
+/// Current schema version of stored mapping files.
+///
+/// Increment this when changing the JSON layout and add a migration step to
+/// `MappingConfigLoader`.
+///
+/// v2: Added `schema_version` and created/modified timestamps on `MappingMetadata`
+pub const MAPPING_SCHEMA_VERSION: u32 = 2;
+
+/// A named mapping configuration saved for reuse.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMappingConfig {
+    /// Schema version of the file this was loaded from or will be saved as.
+    pub schema_version: u32,
+
+    /// Name, description, and timestamps.
+    pub metadata: MappingMetadata,
+
+    /// The mapping itself.
+    pub config: MappingConfig,
+}
+
+impl StoredMappingConfig {
+    /// Create a stored mapping at the current schema version.
+    pub fn new(name: impl Into<String>, config: MappingConfig) -> Self {
+        Self {
+            schema_version: MAPPING_SCHEMA_VERSION,
+            metadata: MappingMetadata::new(name),
+            config,
+        }
+    }
+}
+
+/// Descriptive metadata for a stored mapping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MappingMetadata {
+    /// Display name, also used to derive the file name.
+    pub name: String,
+
+    /// Optional free-text description.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// When the mapping was first saved (RFC 3339).
+    pub created_at: String,
+
+    /// When the mapping was last saved (RFC 3339).
+    pub modified_at: String,
+}
+
+impl MappingMetadata {
+    /// Create metadata stamped with the current time.
+    pub fn new(name: impl Into<String>) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            name: name.into(),
+            description: None,
+            created_at: now.clone(),
+            modified_at: now,
+        }
+    }
+
+    /// Update the modified timestamp.
+    pub fn touch(&mut self) {
+        self.modified_at = Utc::now().to_rfc3339();
+    }
+
+    /// Parse the created_at timestamp.
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Parse the modified_at timestamp.
+    pub fn modified_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.modified_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
@@ -5,6 +5,7 @@
 
 mod domain;
 mod generated_domains;
+mod mapping;
 mod placeholders;
 mod project;
 mod source;
@@ -17,6 +18,7 @@
     CommentEntrySnapshot, GeneratedDomainEntrySnapshot, GeneratedDomainTypeSnapshot,
     RelrecEntrySnapshot, RelrecRelTypeSnapshot, RelspecEntrySnapshot, RelsubEntrySnapshot,
 };
+pub use mapping::{MAPPING_SCHEMA_VERSION, MappingMetadata, StoredMappingConfig};
 pub use placeholders::ProjectPlaceholders;
 pub use project::{ProjectFile, StudyMetadata, WorkflowTypeSnapshot};
 pub use source::SourceAssignment;
@@ -25,7 +27,8 @@
 /// Current schema version.
 ///
 /// Increment this when making breaking changes to the persistence format.
-/// The loader will reject files with version > CURRENT_SCHEMA_VERSION.
+/// The loader will reject files with version > CURRENT_SCHEMA_VERSION and
+/// upgrade older ones through the `migrate` module.
 ///
 /// v2: Added generated domain support (DomainSnapshot now enum with Source/Generated variants)
 pub const CURRENT_SCHEMA_VERSION: u32 = 2;
@@ -11,13 +11,24 @@
 use crate::adam::{AdamDataset, AdamDatasetType, AdamVariable, AdamVariableSource};
 use crate::embedded;
 use crate::error::{Result, StandardsError};
+use crate::paths::{self, StandardsData};
 use crate::traits::VariableType;
 
-/// Load ADaM-IG datasets from embedded data.
+/// Load ADaM-IG datasets from embedded data, or from disk when configured
+/// (see [`paths`]).
 pub fn load() -> Result<Vec<AdamDataset>> {
-    let (datasets, long_to_short) =
-        load_data_structures_from_str(embedded::ADAM_IG_DATA_STRUCTURES)?;
-    let variables = load_variables_from_str(embedded::ADAM_IG_VARIABLES, &long_to_short)?;
+    let structures_csv = paths::read_or_embedded(
+        StandardsData::AdamIg,
+        "DataStructures.csv",
+        embedded::ADAM_IG_DATA_STRUCTURES,
+    )?;
+    let variables_csv = paths::read_or_embedded(
+        StandardsData::AdamIg,
+        "Variables.csv",
+        embedded::ADAM_IG_VARIABLES,
+    )?;
+    let (datasets, long_to_short) = load_data_structures_from_str(&structures_csv)?;
+    let variables = load_variables_from_str(&variables_csv, &long_to_short)?;
     build_datasets(&datasets, variables)
 }
 
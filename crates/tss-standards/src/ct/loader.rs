@@ -1,15 +1,20 @@
 //! Controlled Terminology (CT) loading.
 //!
 //! Loads CDISC Controlled Terminology from embedded CSV data.
-//! All CT files are embedded at compile time for offline operation.
+//! All CT files are embedded at compile time for offline operation; a CT
+//! directory on disk can be used instead (see [`crate::paths`]).
 
+use std::collections::HashMap;
 use std::io::Cursor;
+use std::path::Path;
 
 use serde::Deserialize;
 
 use super::types::{Codelist, Term, TerminologyCatalog, TerminologyRegistry};
 use crate::embedded;
 use crate::error::{Result, StandardsError};
+use crate::paths::{self, StandardsData};
+use crate::traits::Standard;
 
 // =============================================================================
 // CT Version Enum
@@ -85,8 +90,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 pub fn load(version: CtVersion, primary_set: Option<&str>) -> Result<TerminologyRegistry> {
     let mut registry = TerminologyRegistry::new();
 
-    for (filename, content) in embedded::ct_files_for_version(version) {
-        let mut catalog = load_catalog_from_str(content, filename)?;
+    for (filename, content) in paths::ct_files(version)? {
+        let mut catalog = load_catalog_from_str(&content, &filename)?;
 
         // Mark catalog as primary if it matches the requested publishing set
         if let Some(primary) = primary_set
@@ -102,6 +107,68 @@ pub fn load(version: CtVersion, primary_set: Option<&str>) -> Result<Terminology
     Ok(registry)
 }
 
+/// Load a CT registry with a CT version pinned per standard.
+///
+/// Each standard's own catalog (SDTM CT, SEND CT, ADaM CT) is loaded from
+/// the version mapped to that standard. Supporting catalogs (Define-XML,
+/// Protocol, ...) and standards missing from `versions` use the version of
+/// the `primary` standard, whose catalog is marked as primary.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::collections::HashMap;
+/// use tss_standards::Standard;
+/// use tss_standards::ct::{self, CtVersion};
+///
+/// let versions = HashMap::from([
+///     (Standard::Sdtm, CtVersion::V2024_03_29),
+///     (Standard::Send, CtVersion::V2025_09_26),
+/// ]);
+/// let registry = ct::load_per_standard(&versions, Standard::Sdtm)?;
+/// ```
+pub fn load_per_standard(
+    versions: &HashMap<Standard, CtVersion>,
+    primary: Standard,
+) -> Result<TerminologyRegistry> {
+    let base_version = versions.get(&primary).copied().unwrap_or_default();
+    let mut registry = TerminologyRegistry::new();
+
+    let mut files = Vec::new();
+    for (filename, content) in paths::ct_files(base_version)? {
+        let pinned_elsewhere = standard_for_file(&filename)
+            .and_then(|standard| versions.get(&standard))
+            .is_some_and(|version| *version != base_version);
+        if !pinned_elsewhere {
+            files.push((filename, content));
+        }
+    }
+    for (standard, version) in versions {
+        if *version == base_version {
+            continue;
+        }
+        files.extend(
+            paths::ct_files(*version)?
+                .into_iter()
+                .filter(|(filename, _)| standard_for_file(filename) == Some(*standard)),
+        );
+    }
+
+    for (filename, content) in files {
+        let mut catalog = load_catalog_from_str(&content, &filename)?;
+        if catalog
+            .publishing_set
+            .as_deref()
+            .is_some_and(|set| set.eq_ignore_ascii_case(primary.as_str()))
+        {
+            catalog.set_primary(true);
+        }
+        registry.add_catalog(catalog);
+    }
+
+    Ok(registry)
+}
+
 /// Load a single CT catalog from CSV string content.
 ///
 /// # CSV Structure
@@ -218,7 +285,12 @@ pub fn load_catalog_from_str(content: &str, filename: &str) -> Result<Terminolog
 /// Returns a single catalog containing only SDTM terminology.
 pub fn load_sdtm_only(version: CtVersion) -> Result<TerminologyCatalog> {
     let (filename, content) = embedded::sdtm_ct_for_version(version);
-    load_catalog_from_str(content, filename)
+    let content = paths::read_or_embedded(
+        StandardsData::Terminology,
+        Path::new(version.dir_name()).join(filename),
+        content,
+    )?;
+    load_catalog_from_str(&content, filename)
 }
 
 // =============================================================================
@@ -284,6 +356,17 @@ fn parse_ct_metadata_from_filename(filename: &str) -> (String, Option<String>, O
     (stem.to_string(), None, None)
 }
 
+/// Get the standard whose own catalog is stored in a CT file, if any.
+fn standard_for_file(filename: &str) -> Option<Standard> {
+    let (_, _, publishing_set) = parse_ct_metadata_from_filename(filename);
+    match publishing_set?.as_str() {
+        "SDTM" => Some(Standard::Sdtm),
+        "SEND" => Some(Standard::Send),
+        "ADaM" => Some(Standard::Adam),
+        _ => None,
+    }
+}
+
 /// Parse semicolon-separated synonyms.
 fn parse_synonyms(raw: &str) -> Vec<String> {
     let trimmed = raw.trim();
@@ -311,6 +394,7 @@ fn non_empty(s: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sdtm::test_support::{domain, variable};
 
     #[test]
     fn test_load_ct_default() {
@@ -375,6 +459,20 @@ fn test_resolve_codelist() {
         assert!(values.contains(&"N"), "NY should contain N");
     }
 
+    #[test]
+    fn test_unresolved_codes() {
+        let registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
+        let domain = domain("XX")
+            .variable(variable("XXSEX").codelist("C66731"))
+            .variable(variable("XXFLAG").codelist("C66742; C999999"))
+            .build();
+
+        let unresolved = registry.unresolved_codes(&domain);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].0.name, "XXFLAG");
+        assert_eq!(unresolved[0].1, "C999999");
+    }
+
     #[test]
     fn test_submission_value_validation() {
         let registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
@@ -392,6 +490,71 @@ fn test_submission_value_validation() {
         );
     }
 
+    #[test]
+    fn test_validate_column() {
+        let registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
+
+        // C66742 (NY) is non-extensible
+        let values = ["Y", "N", "YES", "", "MAYBE", "YES"];
+        let result = registry
+            .validate_column("C66742", &values)
+            .expect("resolve NY");
+
+        assert!(!result.extensible);
+        let rows: Vec<usize> = result.issues.iter().map(|(row, _)| *row).collect();
+        assert_eq!(rows, vec![2, 4, 5]);
+        assert_eq!(result.unknown_values.len(), 2);
+        assert!(result.unknown_values.contains("YES"));
+        assert!(result.unknown_values.contains("MAYBE"));
+        assert!(result.issues.iter().all(|(_, issue)| !issue.extensible));
+
+        assert!(registry.validate_column("XNOTACODE", &values).is_none());
+    }
+
+    #[test]
+    fn test_validation_trims_values() {
+        let registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
+
+        // Both entry points normalise the value the same way
+        for value in [" Y ", "", "  "] {
+            assert!(
+                registry
+                    .validate_submission_value("C66742", value)
+                    .is_none()
+            );
+            assert!(
+                registry
+                    .validate_column("C66742", &[value])
+                    .unwrap()
+                    .is_valid()
+            );
+        }
+        let issue = registry
+            .validate_submission_value("C66742", " MAYBE ")
+            .unwrap();
+        assert_eq!(issue.invalid_value, "MAYBE");
+    }
+
+    #[test]
+    fn test_validate_column_extensible() {
+        let registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
+
+        // C71620 (UNIT) is extensible - unknown values are informational
+        let result = registry
+            .validate_column("C71620", &["mg", "SPONSOR UNIT"])
+            .expect("resolve UNIT");
+
+        assert!(result.extensible);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].0, 1);
+        assert!(result.issues[0].1.extensible);
+        assert!(
+            registry
+                .validate_submission_value("C71620", "SPONSOR UNIT")
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_primary_catalog_marking() {
         // Load with SDTM as primary
@@ -436,6 +599,164 @@ fn test_load_with_send_primary() {
         }
     }
 
+    #[test]
+    fn test_resolve_index_invalidated_on_add_catalog() {
+        let mut registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
+
+        // Populate the index
+        assert!(registry.resolve("C66742", None).is_some());
+        assert!(registry.resolve("XTEST1", None).is_none());
+
+        let mut catalog = TerminologyCatalog::new(
+            "Sponsor CT".to_string(),
+            Some("1.0".to_string()),
+            Some("Sponsor".to_string()),
+        );
+        catalog.add_codelist(Codelist::new(
+            "XTEST1".to_string(),
+            "Sponsor Test".to_string(),
+            true,
+        ));
+        registry.add_catalog(catalog);
+
+        let resolved = registry
+            .resolve("xtest1", None)
+            .expect("resolve new codelist");
+        assert_eq!(resolved.source(), "Sponsor CT");
+
+        // Existing codes still resolve from the SDTM catalog
+        let resolved = registry.resolve("C66742", None).expect("resolve NY");
+        assert_eq!(resolved.source(), "SDTM CT");
+    }
+
+    #[test]
+    fn test_merge_extension_package() {
+        let mut registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
+
+        let mut catalog = TerminologyCatalog::new(
+            "Sponsor CT".to_string(),
+            Some("1.0".to_string()),
+            Some("SDTM".to_string()),
+        );
+        catalog.add_codelist(Codelist::new(
+            "XTEST1".to_string(),
+            "Sponsor Test".to_string(),
+            true,
+        ));
+        catalog.add_codelist(Codelist::new(
+            "C66742".to_string(),
+            "Sponsor No Yes Response".to_string(),
+            true,
+        ));
+        let mut extension = TerminologyRegistry::new();
+        extension.add_catalog(catalog);
+
+        // Populate the index before merging
+        assert!(registry.resolve("XTEST1", None).is_none());
+        registry.merge(extension);
+
+        let resolved = registry
+            .resolve("xtest1", None)
+            .expect("resolve extension codelist");
+        assert_eq!(resolved.source(), "Sponsor CT");
+        assert_eq!(resolved.catalog.version.as_deref(), Some("1.0"));
+
+        // The later package wins for duplicate codes
+        let resolved = registry.resolve("C66742", None).expect("resolve NY");
+        assert_eq!(resolved.codelist.name, "Sponsor No Yes Response");
+        let resolved = registry
+            .resolve_for_standard("C66742", Standard::Sdtm)
+            .expect("resolve NY for SDTM");
+        assert_eq!(resolved.source(), "Sponsor CT");
+
+        // Base codelists still resolve, with the base catalog as provenance
+        let resolved = registry.resolve("C66731", None).expect("resolve SEX");
+        assert_eq!(resolved.source(), "SDTM CT");
+        assert!(registry.catalogs.contains_key("SDTM CT"));
+        assert!(registry.catalogs.contains_key("SPONSOR CT"));
+    }
+
+    #[test]
+    fn test_merge_same_label() {
+        let package = |version: &str, codes: &[&str]| {
+            let mut catalog = TerminologyCatalog::new(
+                "Sponsor CT".to_string(),
+                Some(version.to_string()),
+                Some("SDTM".to_string()),
+            );
+            for code in codes {
+                catalog.add_codelist(Codelist::new(
+                    code.to_string(),
+                    format!("Sponsor {code} {version}"),
+                    true,
+                ));
+            }
+            let mut registry = TerminologyRegistry::new();
+            registry.add_catalog(catalog);
+            registry
+        };
+        let mut registry = package("1.0", &["XTEST1", "XTEST2"]);
+        registry.merge(package("2.0", &["XTEST2"]));
+
+        // Both packages are kept, each with its own version
+        assert_eq!(registry.catalogs.len(), 2);
+        let versions: Vec<_> = registry
+            .catalogs
+            .values()
+            .map(|catalog| catalog.version.as_deref().unwrap_or_default())
+            .collect();
+        assert_eq!(versions, ["1.0", "2.0"]);
+
+        // The later package wins, the earlier one still serves its own codes
+        let resolved = registry.resolve("XTEST2", None).expect("resolve XTEST2");
+        assert_eq!(resolved.catalog.version.as_deref(), Some("2.0"));
+        let resolved = registry.resolve("XTEST1", None).expect("resolve XTEST1");
+        assert_eq!(resolved.catalog.version.as_deref(), Some("1.0"));
+        let preferred = ["Sponsor CT".to_string()];
+        let resolved = registry
+            .resolve("XTEST1", Some(&preferred))
+            .expect("resolve XTEST1 from preferred");
+        assert_eq!(resolved.catalog.version.as_deref(), Some("1.0"));
+        let latest = registry.catalogs.get("SPONSOR CT#2").expect("Sponsor CT");
+        assert_eq!(latest.version.as_deref(), Some("2.0"));
+    }
+
+    #[test]
+    fn test_load_per_standard_versions() {
+        let versions = HashMap::from([
+            (Standard::Sdtm, CtVersion::V2024_03_29),
+            (Standard::Send, CtVersion::V2025_09_26),
+        ]);
+        let registry = load_per_standard(&versions, Standard::Sdtm).expect("load CT");
+
+        let sdtm = registry.catalogs.get("SDTM CT").expect("SDTM CT");
+        assert_eq!(sdtm.version.as_deref(), Some("2024-03-29"));
+        assert!(sdtm.primary);
+
+        let send = registry.catalogs.get("SEND CT").expect("SEND CT");
+        assert_eq!(send.version.as_deref(), Some("2025-09-26"));
+        assert!(!send.primary);
+
+        // Unpinned standards and supporting catalogs follow the primary version
+        let adam = registry.catalogs.get("ADAM CT").expect("ADaM CT");
+        assert_eq!(adam.version.as_deref(), Some("2024-03-29"));
+        let define = registry
+            .catalogs
+            .get("DEFINE-XML CT")
+            .expect("Define-XML CT");
+        assert_eq!(define.version.as_deref(), Some("2024-03-29"));
+
+        // C66742 (NY) exists in both SDTM and SEND CT
+        let resolved = registry
+            .resolve_for_standard("C66742", Standard::Send)
+            .expect("resolve NY for SEND");
+        assert_eq!(resolved.source(), "SEND CT");
+        let resolved = registry
+            .resolve_for_standard("C66742", Standard::Sdtm)
+            .expect("resolve NY for SDTM");
+        assert_eq!(resolved.source(), "SDTM CT");
+    }
+
     #[test]
     fn test_resolved_from_primary() {
         let registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
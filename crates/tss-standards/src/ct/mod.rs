@@ -2,7 +2,7 @@
 //!
 //! This module provides:
 //! - CT types: `Codelist`, `Term`, `TerminologyCatalog`, `TerminologyRegistry`
-//! - CT loaders: `load()`, `load_sdtm_only()`, `load_catalog_from_str()`
+//! - CT loaders: `load()`, `load_per_standard()`, `load_sdtm_only()`, `load_catalog_from_str()`
 //! - Version management: `CtVersion`
 //!
 //! All CT data is embedded at compile time - no file I/O required at runtime.
@@ -31,8 +31,9 @@
 
 // Re-export types
 pub use types::{
-    Codelist, CtValidationIssue, ResolvedCodelist, Term, TerminologyCatalog, TerminologyRegistry,
+    Codelist, CtColumnValidation, CtValidationIssue, ResolvedCodelist, Term, TerminologyCatalog,
+    TerminologyRegistry,
 };
 
 // Re-export loader
-pub use loader::{CtVersion, load, load_catalog_from_str, load_sdtm_only};
+pub use loader::{CtVersion, load, load_catalog_from_str, load_per_standard, load_sdtm_only};
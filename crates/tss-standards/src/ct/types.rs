@@ -35,8 +35,16 @@
 //! - **Synonyms** are for **mapping help only** - they should NOT be accepted in final datasets.
 //! - During normalization, synonyms should be converted to their proper submission values.
 
+use rapidfuzz::distance::jaro_winkler;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::OnceLock;
+
+use crate::sdtm::{SdtmDomain, SdtmVariable};
+use crate::traits::Standard;
+
+/// Minimum Jaro-Winkler similarity for a "did you mean" suggestion.
+const SUGGESTION_THRESHOLD: f64 = 0.8;
 
 /// A single term within a codelist.
 ///
@@ -201,6 +209,35 @@ pub fn find_submission_value(&self, value: &str) -> Option<&str> {
 
         None
     }
+
+    /// Suggest the closest submission value for an input that is not valid.
+    ///
+    /// A synonym match scores 1.0. Otherwise the input is compared by
+    /// Jaro-Winkler similarity against each term's submission value, synonyms,
+    /// and NCI preferred term, and the best match scoring at least 0.8 wins.
+    pub fn suggest_submission_value(&self, value: &str) -> Option<(&str, f64)> {
+        let key = value.trim().to_uppercase();
+        if key.is_empty() {
+            return None;
+        }
+        if let Some(submission_value) = self.find_submission_value(&key) {
+            return Some((submission_value, 1.0));
+        }
+
+        let mut best: Option<(&str, f64)> = None;
+        for term in self.terms.values() {
+            let candidates = std::iter::once(&term.submission_value)
+                .chain(&term.synonyms)
+                .chain(&term.preferred_term);
+            for candidate in candidates {
+                let score = jaro_winkler::similarity(key.chars(), candidate.to_uppercase().chars());
+                if score >= SUGGESTION_THRESHOLD && best.is_none_or(|(_, s)| score > s) {
+                    best = Some((&term.submission_value, score));
+                }
+            }
+        }
+        best
+    }
 }
 
 /// A CT catalog representing a specific CT release.
@@ -294,8 +331,26 @@ pub fn add_codelist(&mut self, codelist: Codelist) {
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TerminologyRegistry {
-    /// Catalogs by label (uppercase).
+    /// Catalogs by key: the uppercase label, with a `#n` suffix for a
+    /// merged catalog whose label was already loaded.
+    ///
+    /// Add catalogs through [`TerminologyRegistry::add_catalog`] or
+    /// [`TerminologyRegistry::merge`] so the resolve index stays in sync.
     pub catalogs: BTreeMap<String, TerminologyCatalog>,
+
+    /// Lazily built index: uppercase codelist code -> catalog key.
+    ///
+    /// Built on the first default-order `resolve` call and reset whenever
+    /// a catalog is added.
+    #[serde(skip)]
+    resolve_index: OnceLock<HashMap<String, String>>,
+
+    /// Keys of merged catalogs, highest priority first.
+    ///
+    /// Catalogs merged through [`TerminologyRegistry::merge`] take
+    /// precedence over the default order so later packages win.
+    #[serde(default)]
+    precedence: Vec<String>,
 }
 
 impl TerminologyRegistry {
@@ -305,25 +360,79 @@ pub fn new() -> Self {
     }
 
     /// Add a catalog to the registry.
+    ///
+    /// Invalidates the resolve index.
     pub fn add_catalog(&mut self, catalog: TerminologyCatalog) {
         self.catalogs.insert(catalog.label.to_uppercase(), catalog);
+        self.resolve_index = OnceLock::new();
+    }
+
+    /// Merge the catalogs of another registry into this one.
+    ///
+    /// Used to layer a sponsor extension package over a base CT. The merged
+    /// catalogs take precedence, so a codelist code defined in both resolves
+    /// from `other`. Catalogs keep their own version and publishing set, and
+    /// Define-XML lists a `def:Standard` for each catalog a codelist was
+    /// resolved from.
+    ///
+    /// A catalog whose label is already loaded is kept beside the existing
+    /// one rather than combined with it, so each keeps its own version for
+    /// Define-XML; codelists missing from the merged catalog still resolve
+    /// from the earlier one.
+    pub fn merge(&mut self, other: TerminologyRegistry) {
+        let other_order: Vec<String> = other
+            .catalogs_in_order(None)
+            .into_iter()
+            .map(|(key, _)| key.to_string())
+            .collect();
+        let mut other_catalogs = other.catalogs;
+        let mut merged_keys = Vec::with_capacity(other_order.len());
+        for other_key in other_order {
+            let Some(catalog) = other_catalogs.remove(&other_key) else {
+                continue;
+            };
+            let label = catalog.label.to_uppercase();
+            let mut key = label.clone();
+            let mut n = 1;
+            while self.catalogs.contains_key(&key) {
+                n += 1;
+                key = format!("{label}#{n}");
+            }
+            self.catalogs.insert(key.clone(), catalog);
+            merged_keys.push(key);
+        }
+        merged_keys.append(&mut self.precedence);
+        self.precedence = merged_keys;
+        self.resolve_index = OnceLock::new();
     }
 
     /// Resolve a codelist by NCI code.
     ///
     /// Searches catalogs in priority order:
     /// 1. Preferred catalogs (if specified)
-    /// 2. SDTM CT
-    /// 3. SEND CT
-    /// 4. Others alphabetically
+    /// 2. Merged catalogs, most recently merged first
+    /// 3. SDTM CT
+    /// 4. SEND CT
+    /// 5. Others alphabetically
+    ///
+    /// Lookups without preferred catalogs go through a code index that is
+    /// built once on first use, so repeated resolution is O(1).
     pub fn resolve(
         &self,
         code: &str,
         preferred: Option<&[String]>,
     ) -> Option<ResolvedCodelist<'_>> {
-        let catalogs = self.catalogs_in_order(preferred);
         let key = code.to_uppercase();
-        for catalog in catalogs {
+
+        if preferred.is_none() {
+            let catalog_key = self.resolve_index().get(&key)?;
+            let catalog = self.catalogs.get(catalog_key)?;
+            let codelist = catalog.codelists.get(&key)?;
+            return Some(ResolvedCodelist { codelist, catalog });
+        }
+
+        let catalogs = self.catalogs_in_order(preferred);
+        for (_, catalog) in catalogs {
             if let Some(codelist) = catalog.codelists.get(&key) {
                 return Some(ResolvedCodelist { codelist, catalog });
             }
@@ -331,9 +440,95 @@ pub fn resolve(
         None
     }
 
+    /// Get the standard of the primary catalogs.
+    ///
+    /// This is the standard the CT was loaded for (see
+    /// [`load_per_standard`](crate::ct::load_per_standard)), or `None` if no
+    /// catalog is marked primary.
+    pub fn primary_standard(&self) -> Option<Standard> {
+        self.catalogs
+            .values()
+            .filter(|catalog| catalog.primary)
+            .find_map(|catalog| {
+                let set = catalog.publishing_set.as_deref()?;
+                [Standard::Sdtm, Standard::Send, Standard::Adam]
+                    .into_iter()
+                    .find(|standard| set.eq_ignore_ascii_case(standard.as_str()))
+            })
+    }
+
+    /// Resolve a codelist, preferring the catalog of the given standard.
+    ///
+    /// Looks in the catalog whose publishing set matches `standard` (e.g.,
+    /// "SEND CT" for SEND variables) and falls back to the default priority
+    /// order if the codelist is not found there.
+    pub fn resolve_for_standard(
+        &self,
+        code: &str,
+        standard: Standard,
+    ) -> Option<ResolvedCodelist<'_>> {
+        let key = code.to_uppercase();
+        let own = self
+            .catalogs_in_order(None)
+            .into_iter()
+            .map(|(_, catalog)| catalog)
+            .filter(|c| {
+                c.publishing_set
+                    .as_deref()
+                    .is_some_and(|set| set.eq_ignore_ascii_case(standard.as_str()))
+            })
+            .find_map(|catalog| {
+                let codelist = catalog.codelists.get(&key)?;
+                Some(ResolvedCodelist { codelist, catalog })
+            });
+        own.or_else(|| self.resolve(code, None))
+    }
+
+    /// List the codelist codes of a domain that don't resolve.
+    ///
+    /// Each variable's `codelist_code` may hold several codes separated by
+    /// `;` or `,`; every code not found in any catalog is reported with its
+    /// variable, in variable order. Checking this before export avoids a
+    /// missing codelist error halfway through writing Define-XML.
+    pub fn unresolved_codes<'a>(&self, domain: &'a SdtmDomain) -> Vec<(&'a SdtmVariable, String)> {
+        domain
+            .variables
+            .iter()
+            .flat_map(|variable| {
+                variable
+                    .codelist_code
+                    .as_deref()
+                    .unwrap_or_default()
+                    .split([';', ','])
+                    .map(str::trim)
+                    .filter(|code| !code.is_empty() && self.resolve(code, None).is_none())
+                    .map(move |code| (variable, code.to_string()))
+            })
+            .collect()
+    }
+
+    /// Get the codelist index, building it on first access.
+    ///
+    /// The first catalog in default priority order wins for each code.
+    fn resolve_index(&self) -> &HashMap<String, String> {
+        self.resolve_index.get_or_init(|| {
+            let mut index = HashMap::new();
+            for (catalog_key, catalog) in self.catalogs_in_order(None) {
+                for code in catalog.codelists.keys() {
+                    index
+                        .entry(code.clone())
+                        .or_insert_with(|| catalog_key.to_string());
+                }
+            }
+            index
+        })
+    }
+
     /// Validate a value against a codelist.
     ///
-    /// Returns `None` if valid, or an error message if invalid.
+    /// Returns `None` if valid, or an error message if invalid. The value is
+    /// trimmed first, as in [`validate_column`](Self::validate_column), and
+    /// blank values are not checked.
     ///
     /// **Important:** Only CDISC Submission Value is valid for submission!
     /// Synonyms are for mapping help only.
@@ -342,6 +537,10 @@ pub fn validate_submission_value(
         codelist_code: &str,
         value: &str,
     ) -> Option<CtValidationIssue> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
         let resolved = self.resolve(codelist_code, None)?;
 
         // Check ONLY submission_value - synonyms are for mapping, not submission!
@@ -352,6 +551,7 @@ pub fn validate_submission_value(
             Some(CtValidationIssue {
                 codelist_code: codelist_code.to_string(),
                 codelist_name: resolved.codelist.name.clone(),
+                extensible: false,
                 invalid_value: value.to_string(),
                 valid_values: resolved
                     .codelist
@@ -359,10 +559,87 @@ pub fn validate_submission_value(
                     .into_iter()
                     .map(String::from)
                     .collect(),
+                suggestion: resolved
+                    .codelist
+                    .suggest_submission_value(value)
+                    .map(|(suggested, _)| suggested.to_string()),
             })
         }
     }
 
+    /// Validate a whole column of values against a codelist.
+    ///
+    /// Resolves the codelist once and checks every value, returning the row
+    /// index of each offender plus the distinct unknown values. Blank values
+    /// are skipped. Returns `None` if the codelist cannot be resolved.
+    ///
+    /// Unlike [`validate_submission_value`](Self::validate_submission_value),
+    /// values outside an extensible codelist are still reported, with
+    /// [`CtValidationIssue::extensible`] set so callers can treat them as
+    /// informational rather than errors.
+    pub fn validate_column(
+        &self,
+        codelist_code: &str,
+        values: &[&str],
+    ) -> Option<CtColumnValidation> {
+        let resolved = self.resolve(codelist_code, None)?;
+        let codelist = resolved.codelist;
+        let valid_values: Vec<String> = codelist
+            .submission_values()
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let mut result = CtColumnValidation {
+            extensible: codelist.extensible,
+            ..Default::default()
+        };
+        let mut suggestions: HashMap<&str, Option<String>> = HashMap::new();
+
+        for (row, value) in values.iter().enumerate() {
+            let trimmed = value.trim();
+            if trimmed.is_empty() || codelist.is_valid_submission_value(trimmed) {
+                continue;
+            }
+
+            result.unknown_values.insert(trimmed.to_string());
+            let suggestion = suggestions
+                .entry(trimmed)
+                .or_insert_with(|| {
+                    codelist
+                        .suggest_submission_value(trimmed)
+                        .map(|(suggested, _)| suggested.to_string())
+                })
+                .clone();
+            result.issues.push((
+                row,
+                CtValidationIssue {
+                    codelist_code: codelist_code.to_string(),
+                    codelist_name: codelist.name.clone(),
+                    extensible: codelist.extensible,
+                    invalid_value: trimmed.to_string(),
+                    valid_values: valid_values.clone(),
+                    suggestion,
+                },
+            ));
+        }
+
+        Some(result)
+    }
+
+    /// Suggest the closest valid submission value for a value that failed validation.
+    ///
+    /// Returns the submission value and its similarity score (1.0 for a synonym
+    /// match), or `None` if the codelist cannot be resolved or nothing scores
+    /// at least 0.8. See [`Codelist::suggest_submission_value`].
+    pub fn suggest_term(&self, codelist_code: &str, value: &str) -> Option<(String, f64)> {
+        let resolved = self.resolve(codelist_code, None)?;
+        resolved
+            .codelist
+            .suggest_submission_value(value)
+            .map(|(suggested, score)| (suggested.to_string(), score))
+    }
+
     /// Find the correct submission value for any input (submission value or synonym).
     ///
     /// Used during normalization to convert sponsor terms to CDISC terms.
@@ -374,23 +651,39 @@ pub fn find_submission_value(&self, codelist_code: &str, input: &str) -> Option<
             .map(String::from)
     }
 
-    fn catalogs_in_order(&self, preferred: Option<&[String]>) -> Vec<&TerminologyCatalog> {
-        if let Some(preferred) = preferred {
-            return preferred
-                .iter()
-                .filter_map(|label| self.catalogs.get(&label.to_uppercase()))
-                .collect();
-        }
-        let mut catalogs: Vec<&TerminologyCatalog> = self.catalogs.values().collect();
-        catalogs.sort_by_key(|c| {
+    /// Catalogs with their keys, in resolution order.
+    ///
+    /// With `preferred` labels, only catalogs of those labels are listed, in
+    /// the order given; catalogs sharing a label follow precedence.
+    fn catalogs_in_order(&self, preferred: Option<&[String]>) -> Vec<(&str, &TerminologyCatalog)> {
+        let mut catalogs: Vec<(&str, &TerminologyCatalog)> = self
+            .catalogs
+            .iter()
+            .map(|(key, catalog)| (key.as_str(), catalog))
+            .collect();
+        catalogs.sort_by_key(|(key, c)| {
             let label = c.label.to_uppercase();
+            if let Some(pos) = self.precedence.iter().position(|k| k == key) {
+                return (0, pos, label);
+            }
             match label.as_str() {
-                "SDTM CT" => (0, label),
-                "SEND CT" => (1, label),
-                _ => (2, label),
+                "SDTM CT" => (1, 0, label),
+                "SEND CT" => (2, 0, label),
+                _ => (3, 0, label),
             }
         });
-        catalogs
+        let Some(preferred) = preferred else {
+            return catalogs;
+        };
+        preferred
+            .iter()
+            .flat_map(|label| {
+                catalogs
+                    .iter()
+                    .filter(move |(_, c)| c.label.eq_ignore_ascii_case(label))
+                    .copied()
+            })
+            .collect()
     }
 }
 
@@ -438,10 +731,36 @@ pub struct CtValidationIssue {
     pub codelist_code: String,
     /// The codelist name.
     pub codelist_name: String,
+    /// Whether the codelist is extensible.
+    ///
+    /// Values outside an extensible codelist are informational, not errors.
+    pub extensible: bool,
     /// The invalid value that was found.
     pub invalid_value: String,
     /// The valid submission values for this codelist.
     pub valid_values: Vec<String>,
+    /// Closest valid submission value, if one is similar enough ("did you mean").
+    pub suggestion: Option<String>,
+}
+
+/// Result of validating a column of values against one codelist.
+///
+/// Returned by [`TerminologyRegistry::validate_column`].
+#[derive(Debug, Clone, Default)]
+pub struct CtColumnValidation {
+    /// Whether the codelist is extensible (offenders are informational).
+    pub extensible: bool,
+    /// Row index and issue for each value not in the codelist.
+    pub issues: Vec<(usize, CtValidationIssue)>,
+    /// Distinct values not in the codelist.
+    pub unknown_values: BTreeSet<String>,
+}
+
+impl CtColumnValidation {
+    /// Check if every value was a valid submission value.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
 }
 
 impl std::fmt::Display for CtValidationIssue {
@@ -450,6 +769,10 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             f,
             "Value '{}' is not a valid CDISC Submission Value for codelist {} ({})",
             self.invalid_value, self.codelist_name, self.codelist_code
-        )
+        )?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "; did you mean '{suggestion}'?")?;
+        }
+        Ok(())
     }
 }
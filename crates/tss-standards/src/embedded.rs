@@ -14,35 +14,99 @@
 //!
 //! See `data/terminology/README.md` for step-by-step instructions.
 
+// Each IG's version is written once, in its `*_dir!` macro below, and used
+// for the include paths, the `*_VERSION` constants and the `*_DIR` paths
+// (see `StandardsData::subpath`).
+
 // =============================================================================
 // SDTM-IG v3.4
 // =============================================================================
 
+macro_rules! sdtm_ig_version {
+    () => {
+        "3.4"
+    };
+}
+
+macro_rules! sdtm_ig_dir {
+    () => {
+        concat!("sdtm/ig/v", sdtm_ig_version!())
+    };
+}
+
+/// Version of the embedded SDTM-IG.
+pub const SDTM_IG_VERSION: &str = sdtm_ig_version!();
+
+/// Directory of the SDTM-IG below the `data` root.
+pub const SDTM_IG_DIR: &str = sdtm_ig_dir!();
+
 /// SDTM-IG v3.4 Datasets.csv
-pub const SDTM_IG_DATASETS: &str = include_str!("../data/sdtm/ig/v3.4/Datasets.csv");
+pub const SDTM_IG_DATASETS: &str =
+    include_str!(concat!("../data/", sdtm_ig_dir!(), "/Datasets.csv"));
 
 /// SDTM-IG v3.4 Variables.csv
-pub const SDTM_IG_VARIABLES: &str = include_str!("../data/sdtm/ig/v3.4/Variables.csv");
+pub const SDTM_IG_VARIABLES: &str =
+    include_str!(concat!("../data/", sdtm_ig_dir!(), "/Variables.csv"));
 
 // =============================================================================
 // ADaM-IG v1.3
 // =============================================================================
 
+macro_rules! adam_ig_version {
+    () => {
+        "1.3"
+    };
+}
+
+macro_rules! adam_ig_dir {
+    () => {
+        concat!("adam/ig/v", adam_ig_version!())
+    };
+}
+
+/// Version of the embedded ADaM-IG.
+pub const ADAM_IG_VERSION: &str = adam_ig_version!();
+
+/// Directory of the ADaM-IG below the `data` root.
+pub const ADAM_IG_DIR: &str = adam_ig_dir!();
+
 /// ADaM-IG v1.3 DataStructures.csv
-pub const ADAM_IG_DATA_STRUCTURES: &str = include_str!("../data/adam/ig/v1.3/DataStructures.csv");
+pub const ADAM_IG_DATA_STRUCTURES: &str =
+    include_str!(concat!("../data/", adam_ig_dir!(), "/DataStructures.csv"));
 
 /// ADaM-IG v1.3 Variables.csv
-pub const ADAM_IG_VARIABLES: &str = include_str!("../data/adam/ig/v1.3/Variables.csv");
+pub const ADAM_IG_VARIABLES: &str =
+    include_str!(concat!("../data/", adam_ig_dir!(), "/Variables.csv"));
 
 // =============================================================================
 // SEND-IG v3.1.1
 // =============================================================================
 
+macro_rules! send_ig_version {
+    () => {
+        "3.1.1"
+    };
+}
+
+macro_rules! send_ig_dir {
+    () => {
+        concat!("send/ig/v", send_ig_version!())
+    };
+}
+
+/// Version of the embedded SEND-IG.
+pub const SEND_IG_VERSION: &str = send_ig_version!();
+
+/// Directory of the SEND-IG below the `data` root.
+pub const SEND_IG_DIR: &str = send_ig_dir!();
+
 /// SEND-IG v3.1.1 Datasets.csv
-pub const SEND_IG_DATASETS: &str = include_str!("../data/send/ig/v3.1.1/Datasets.csv");
+pub const SEND_IG_DATASETS: &str =
+    include_str!(concat!("../data/", send_ig_dir!(), "/Datasets.csv"));
 
 /// SEND-IG v3.1.1 Variables.csv
-pub const SEND_IG_VARIABLES: &str = include_str!("../data/send/ig/v3.1.1/Variables.csv");
+pub const SEND_IG_VARIABLES: &str =
+    include_str!(concat!("../data/", send_ig_dir!(), "/Variables.csv"));
 
 // =============================================================================
 // Controlled Terminology - 2024-03-29 (Default)
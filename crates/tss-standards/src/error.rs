@@ -15,6 +15,14 @@ pub enum StandardsError {
     #[error("CSV file not found: {path}")]
     FileNotFound { path: PathBuf },
 
+    /// Failed to read a standards file.
+    #[error("Failed to read {path}: {source}")]
+    ReadFile {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     /// Failed to read or parse CSV file.
     #[error("Failed to read CSV {path}: {source}")]
     CsvRead {
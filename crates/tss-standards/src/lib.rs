@@ -9,6 +9,8 @@
 //!
 //! All standards data is embedded in the binary at compile time using `include_str!()`.
 //! No external files are required at runtime - the application works offline.
+//! Environment variables can point the loaders at files on disk instead
+//! (see [`paths`]).
 //!
 //! # Module Organization
 //!
@@ -17,9 +19,10 @@
 //! - [`adam`]: ADaM datasets and variables per ADaMIG v1.3
 //! - [`send`]: SEND domains and variables per SENDIG v3.1.1
 //! - [`ct`]: Controlled Terminology types and loaders
-//! - [`polars`]: Polars AnyValue utility functions
+//! - [`polars`]: Polars AnyValue utility functions and column lookup
 //! - [`registry`]: Unified standards registry
 //! - [`embedded`]: Embedded standards data (CSV content as `&'static str`)
+//! - [`paths`]: Environment overrides for loading standards data from disk
 //!
 //! # Embedded Standards
 //!
@@ -60,6 +63,7 @@
 
 // Embedded standards data
 pub mod embedded;
+pub mod paths;
 
 // Loader modules
 pub mod adam_ig;
@@ -76,12 +80,12 @@
 pub use error::{Result, StandardsError};
 
 // Registry
-pub use registry::{StandardsConfig, StandardsRegistry};
+pub use registry::{DomainRef, StandardsConfig, StandardsRegistry};
 
 // CT types and loader
 pub use ct::{
-    Codelist, CtValidationIssue, CtVersion, ResolvedCodelist, Term, TerminologyCatalog,
-    TerminologyRegistry,
+    Codelist, CtColumnValidation, CtValidationIssue, CtVersion, ResolvedCodelist, Term,
+    TerminologyCatalog, TerminologyRegistry,
 };
 
 // Convenience re-exports for loaders
@@ -89,6 +93,7 @@
 pub use ct::load as load_ct;
 pub use sdtm_ig::load as load_sdtm_ig;
 pub use send_ig::load as load_send_ig;
+pub use send_ig::load_for_study_type as load_send_ig_for_study_type;
 
 // Core traits and types
 pub use traits::{CdiscDomain, CdiscVariable, CoreDesignation, Standard, VariableType};
@@ -104,8 +109,9 @@
 
 // Polars utilities
 pub use polars::{
-    any_to_f64, any_to_i64, any_to_string, any_to_string_non_empty, format_numeric, parse_f64,
-    parse_i64,
+    ColumnIndex, DecimalConvention, NumericFormat, any_to_f64, any_to_i64, any_to_string,
+    any_to_string_for_output, any_to_string_non_empty, format_numeric, format_numeric_with,
+    parse_f64, parse_f64_with, parse_i64, parse_i64_with,
 };
 
 // ============================================================================
@@ -128,6 +134,7 @@
 ///     unique_ratio: 0.95,
 ///     null_ratio: 0.02,
 ///     label: Some("Patient Age".to_string()),
+///     ..Default::default()
 /// };
 ///
 /// assert!(hint.is_numeric);
@@ -142,4 +149,14 @@ pub struct ColumnHint {
     pub null_ratio: f64,
     /// Optional column label from source metadata.
     pub label: Option<String>,
+    /// Ratio of non-null values that parse as dates (0.0 to 1.0).
+    #[serde(default)]
+    pub date_ratio: f64,
+    /// Ratio of non-null values that look like short coded tokens such as
+    /// `"M"` or `"MILD"` (0.0 to 1.0).
+    #[serde(default)]
+    pub coded_ratio: f64,
+    /// Optional SAS display format from source metadata (e.g., "DATE9.").
+    #[serde(default)]
+    pub format: Option<String>,
 }
@@ -0,0 +1,297 @@
+//! Locations of standards data on disk.
+//!
+//! Standards are embedded in the binary (see [`crate::embedded`]), but
+//! deployments can load newer or shared files from disk instead, e.g. a CT
+//! mount shared by several installations. The directory for each kind of
+//! data is resolved in this order:
+//!
+//! 1. Its own environment variable ([`StandardsData::env_var`], e.g.
+//!    `TSS_CT_DIR`), pointing at the directory holding that data.
+//! 2. [`STANDARDS_ENV_VAR`], pointing at a root laid out like the bundled
+//!    `data` directory; the data is read from [`StandardsData::subpath`]
+//!    below it.
+//! 3. The embedded data.
+//!
+//! Empty variables are ignored. Once a directory is resolved, missing files
+//! are errors rather than falling back to the embedded data.
+
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::ct::CtVersion;
+use crate::embedded;
+use crate::error::{Result, StandardsError};
+
+/// Environment variable with the root directory of all standards data.
+pub const STANDARDS_ENV_VAR: &str = "TSS_STANDARDS_DIR";
+
+/// Kind of standards data that can be loaded from its own directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardsData {
+    /// SDTM-IG Datasets.csv and Variables.csv.
+    SdtmIg,
+    /// ADaM-IG DataStructures.csv and Variables.csv.
+    AdamIg,
+    /// SEND-IG Datasets.csv and Variables.csv.
+    SendIg,
+    /// Controlled Terminology, one subdirectory per version
+    /// (e.g., `2024-03-29/SDTM_CT_2024-03-29.csv`).
+    Terminology,
+}
+
+impl StandardsData {
+    /// Environment variable overriding the directory of this data.
+    pub const fn env_var(self) -> &'static str {
+        match self {
+            Self::SdtmIg => "TSS_SDTM_DIR",
+            Self::AdamIg => "TSS_ADAM_DIR",
+            Self::SendIg => "TSS_SEND_DIR",
+            Self::Terminology => "TSS_CT_DIR",
+        }
+    }
+
+    /// Path of this data below the [`STANDARDS_ENV_VAR`] root.
+    pub const fn subpath(self) -> &'static str {
+        match self {
+            Self::SdtmIg => embedded::SDTM_IG_DIR,
+            Self::AdamIg => embedded::ADAM_IG_DIR,
+            Self::SendIg => embedded::SEND_IG_DIR,
+            Self::Terminology => "terminology",
+        }
+    }
+}
+
+/// Get the directory to load `data` from.
+///
+/// Returns `None` when no environment variable applies and the embedded
+/// data is used.
+pub fn standards_dir(data: StandardsData) -> Option<PathBuf> {
+    resolve_dir(data, |name| std::env::var_os(name))
+}
+
+/// Resolve the directory of `data` from variables looked up with `var`.
+fn resolve_dir(data: StandardsData, var: impl Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    let dir = |name: &str| {
+        var(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    dir(data.env_var()).or_else(|| dir(STANDARDS_ENV_VAR).map(|root| root.join(data.subpath())))
+}
+
+/// Read a standards file from its resolved directory, or use the embedded
+/// content.
+pub(crate) fn read_or_embedded(
+    data: StandardsData,
+    file: impl AsRef<Path>,
+    embedded: &'static str,
+) -> Result<Cow<'static, str>> {
+    read_from(standards_dir(data).as_deref(), file.as_ref(), embedded)
+}
+
+fn read_from(dir: Option<&Path>, file: &Path, embedded: &'static str) -> Result<Cow<'static, str>> {
+    let Some(dir) = dir else {
+        return Ok(Cow::Borrowed(embedded));
+    };
+    if !dir.is_dir() {
+        return Err(StandardsError::DirectoryNotFound {
+            path: dir.to_path_buf(),
+        });
+    }
+    read_file(&dir.join(file)).map(Cow::Owned)
+}
+
+/// Read the Implementation Guide version from the `Version` column of a
+/// standards file (e.g. "SDTMIG v3.4" gives "3.4").
+pub(crate) fn read_ig_version(
+    data: StandardsData,
+    file: &str,
+    embedded: &'static str,
+) -> Result<String> {
+    parse_ig_version(&read_or_embedded(data, file, embedded)?, file)
+}
+
+fn parse_ig_version(content: &str, file: &str) -> Result<String> {
+    let parse_error = |message: String| StandardsError::CsvParse {
+        file: file.to_string(),
+        message,
+    };
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let column = reader
+        .headers()
+        .map_err(|e| parse_error(e.to_string()))?
+        .iter()
+        .position(|header| header.trim_start_matches('\u{feff}') == "Version")
+        .ok_or_else(|| parse_error("missing Version column".to_string()))?;
+    let record = reader
+        .records()
+        .next()
+        .ok_or_else(|| parse_error("no rows".to_string()))?
+        .map_err(|e| parse_error(e.to_string()))?;
+    let value = record.get(column).unwrap_or_default().trim();
+    let version = value
+        .rsplit_once(" v")
+        .map_or(value, |(_, version)| version);
+    if version.is_empty() {
+        return Err(parse_error("empty Version".to_string()));
+    }
+    Ok(version.to_string())
+}
+
+/// Get the CT files of a version as (file name, content) pairs.
+///
+/// From disk, every CSV file in the version's subdirectory is loaded, in
+/// file name order.
+pub(crate) fn ct_files(version: CtVersion) -> Result<Vec<(String, Cow<'static, str>)>> {
+    ct_files_from(
+        standards_dir(StandardsData::Terminology).as_deref(),
+        version,
+    )
+}
+
+fn ct_files_from(
+    dir: Option<&Path>,
+    version: CtVersion,
+) -> Result<Vec<(String, Cow<'static, str>)>> {
+    let Some(dir) = dir else {
+        return Ok(embedded::ct_files_for_version(version)
+            .into_iter()
+            .map(|(filename, content)| (filename.to_string(), Cow::Borrowed(content)))
+            .collect());
+    };
+    let version_dir = dir.join(version.dir_name());
+    let entries = fs::read_dir(&version_dir).map_err(|_| StandardsError::DirectoryNotFound {
+        path: version_dir.clone(),
+    })?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+        })
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let filename = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Ok((filename, Cow::Owned(read_file(&path)?)))
+        })
+        .collect()
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    if !path.is_file() {
+        return Err(StandardsError::FileNotFound {
+            path: path.to_path_buf(),
+        });
+    }
+    fs::read_to_string(path).map_err(|source| StandardsError::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve(data: StandardsData, vars: &[(&str, &str)]) -> Option<PathBuf> {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        resolve_dir(data, |name| vars.get(name).map(OsString::from))
+    }
+
+    #[test]
+    fn test_resolve_dir_precedence() {
+        let vars = [
+            (STANDARDS_ENV_VAR, "/opt/standards"),
+            ("TSS_SDTM_DIR", "/data/sdtm"),
+            ("TSS_CT_DIR", "/mnt/ct"),
+        ];
+        assert_eq!(
+            resolve(StandardsData::SdtmIg, &vars),
+            Some(PathBuf::from("/data/sdtm"))
+        );
+        assert_eq!(
+            resolve(StandardsData::Terminology, &vars),
+            Some(PathBuf::from("/mnt/ct"))
+        );
+        // No own variable: below the root
+        assert_eq!(
+            resolve(StandardsData::AdamIg, &vars),
+            Some(PathBuf::from("/opt/standards/adam/ig/v1.3"))
+        );
+        assert_eq!(
+            resolve(StandardsData::SendIg, &[("TSS_SEND_DIR", "/data/send")]),
+            Some(PathBuf::from("/data/send"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_dir_defaults_to_embedded() {
+        assert_eq!(resolve(StandardsData::SdtmIg, &[]), None);
+        assert_eq!(
+            resolve(StandardsData::Terminology, &[("TSS_CT_DIR", "")]),
+            None
+        );
+        assert_eq!(
+            resolve(
+                StandardsData::Terminology,
+                &[(STANDARDS_ENV_VAR, "/opt/std")]
+            ),
+            Some(PathBuf::from("/opt/std/terminology"))
+        );
+    }
+
+    #[test]
+    fn test_read_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_dir = dir.path().join(CtVersion::V2024_03_29.dir_name());
+        fs::create_dir(&version_dir).unwrap();
+        fs::write(version_dir.join("SDTM_CT_2024-03-29.csv"), "Code\n").unwrap();
+        fs::write(version_dir.join("notes.txt"), "ignored").unwrap();
+
+        let files = ct_files_from(Some(dir.path()), CtVersion::V2024_03_29).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "SDTM_CT_2024-03-29.csv");
+        assert_eq!(files[0].1, "Code\n");
+        assert!(matches!(
+            ct_files_from(Some(dir.path()), CtVersion::V2025_09_26),
+            Err(StandardsError::DirectoryNotFound { .. })
+        ));
+
+        let embedded = read_from(None, Path::new("Datasets.csv"), "bundled").unwrap();
+        assert!(matches!(embedded, Cow::Borrowed("bundled")));
+        assert!(matches!(
+            read_from(Some(dir.path()), Path::new("Datasets.csv"), "bundled"),
+            Err(StandardsError::FileNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_ig_version() {
+        let embedded = parse_ig_version(embedded::SDTM_IG_DATASETS, "Datasets.csv").unwrap();
+        assert_eq!(embedded, embedded::SDTM_IG_VERSION);
+
+        // A guide loaded from disk reports its own version
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Datasets.csv"),
+            "\u{feff}\"Version\",\"Dataset Name\"\n\"SDTMIG v3.3\",\"AE\"\n",
+        )
+        .unwrap();
+        let content = read_from(Some(dir.path()), Path::new("Datasets.csv"), "").unwrap();
+        assert_eq!(parse_ig_version(&content, "Datasets.csv").unwrap(), "3.3");
+
+        assert!(matches!(
+            parse_ig_version("\"Dataset Name\"\n\"AE\"\n", "Datasets.csv"),
+            Err(StandardsError::CsvParse { .. })
+        ));
+    }
+}
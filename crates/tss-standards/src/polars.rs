@@ -1,14 +1,21 @@
 //! Polars AnyValue utility functions.
 //!
 //! This module provides helper functions for working with Polars `AnyValue` types,
-//! including string conversions and numeric parsing.
+//! including string conversions and numeric parsing, and [`ColumnIndex`] for
+//! case-insensitive column lookup.
 
+use std::collections::HashMap;
+
+use chrono::DateTime;
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: i64 = 86_400;
 
 /// Converts a Polars `AnyValue` to a `String` representation.
 ///
-/// Returns an empty string for `Null`, properly formats numeric types without
-/// unnecessary trailing zeros.
+/// Returns an empty string for `Null` and properly formats numeric types
+/// without unnecessary trailing zeros.
 ///
 /// # Examples
 ///
@@ -49,18 +56,144 @@ pub fn any_to_string(value: AnyValue<'_>) -> String {
     }
 }
 
+/// Converts a Polars `AnyValue` to a `String` for a submission dataset.
+///
+/// Like [`any_to_string`], but renders `Date`/`Datetime` values as ISO 8601
+/// (`YYYY-MM-DD`, `YYYY-MM-DDThh:mm:ss`) as SDTM --DTC variables expect.
+/// Datetimes with a time zone (a fixed offset such as `+01:00` or an IANA
+/// name such as `Europe/Berlin`) are rendered in UTC, not local time in
+/// that zone; convert such columns before export if local time is needed.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::AnyValue;
+/// use tss_standards::polars::any_to_string_for_output;
+///
+/// assert_eq!(any_to_string_for_output(AnyValue::Date(19_797)), "2024-03-15");
+/// assert_eq!(any_to_string_for_output(AnyValue::Int32(42)), "42");
+/// ```
+pub fn any_to_string_for_output(value: AnyValue<'_>) -> String {
+    match value {
+        AnyValue::Date(days) => format_date(i64::from(days)),
+        AnyValue::Datetime(v, unit, _) | AnyValue::DatetimeOwned(v, unit, _) => {
+            format_datetime(v, unit)
+        }
+        other => any_to_string(other),
+    }
+}
+
+/// Format a Polars date (days since the Unix epoch) as `YYYY-MM-DD`.
+fn format_date(days: i64) -> String {
+    days.checked_mul(SECONDS_PER_DAY)
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map_or_else(
+            || days.to_string(),
+            |date| date.format("%Y-%m-%d").to_string(),
+        )
+}
+
+/// Format a Polars datetime as `YYYY-MM-DDThh:mm:ss`.
+///
+/// Fractional seconds are kept only when present, with 3, 6 or 9 digits.
+fn format_datetime(v: i64, unit: TimeUnit) -> String {
+    let datetime = match unit {
+        TimeUnit::Nanoseconds => Some(DateTime::from_timestamp_nanos(v)),
+        TimeUnit::Microseconds => DateTime::from_timestamp_micros(v),
+        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(v),
+    };
+    datetime.map_or_else(
+        || v.to_string(),
+        |datetime| datetime.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+    )
+}
+
 /// Converts `AnyValue` to `String`, returning `None` if the result is empty.
 ///
-/// Useful for XML/output generation where empty values should be omitted.
+/// Useful for XML/output generation where empty values should be omitted;
+/// values are rendered with [`any_to_string_for_output`].
 pub fn any_to_string_non_empty(value: AnyValue<'_>) -> Option<String> {
-    let s = any_to_string(value);
+    let s = any_to_string_for_output(value);
     if s.trim().is_empty() { None } else { Some(s) }
 }
 
+/// Rendering options for [`format_numeric_with`].
+///
+/// The default reproduces [`format_numeric`]: shortest round-trip digits,
+/// trailing zeros trimmed, never scientific notation.
+///
+/// # Examples
+///
+/// ```
+/// use tss_standards::polars::{NumericFormat, format_numeric_with};
+///
+/// // Preserve the collected precision of a result, e.g. --STRESN 5.10
+/// let fixed = NumericFormat::default()
+///     .with_decimals(2)
+///     .with_trim_trailing_zeros(false);
+/// assert_eq!(format_numeric_with(5.1, &fixed), "5.10");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumericFormat {
+    /// Fixed number of decimal places, rounding as needed; `None` keeps the
+    /// shortest representation that round-trips.
+    pub decimals: Option<usize>,
+    /// Remove trailing zeros (and a bare decimal point) after the decimal point.
+    pub trim_trailing_zeros: bool,
+    /// Decimal exponent from which scientific notation is used: values with
+    /// magnitude at least `10^n` or below `10^-n` render as, e.g., `1.5e7`.
+    /// `None` never uses scientific notation.
+    pub sci_threshold: Option<u32>,
+}
+
+impl Default for NumericFormat {
+    fn default() -> Self {
+        Self {
+            decimals: None,
+            trim_trailing_zeros: true,
+            sci_threshold: None,
+        }
+    }
+}
+
+impl NumericFormat {
+    /// Render with a fixed number of decimal places.
+    #[must_use]
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Set whether trailing zeros after the decimal point are removed.
+    #[must_use]
+    pub fn with_trim_trailing_zeros(mut self, trim: bool) -> Self {
+        self.trim_trailing_zeros = trim;
+        self
+    }
+
+    /// Use scientific notation from the given decimal exponent.
+    #[must_use]
+    pub fn with_sci_threshold(mut self, exponent: u32) -> Self {
+        self.sci_threshold = Some(exponent);
+        self
+    }
+
+    /// Whether a value is rendered in scientific notation.
+    fn uses_scientific(&self, v: f64) -> bool {
+        let Some(exponent) = self.sci_threshold else {
+            return false;
+        };
+        let magnitude = v.abs();
+        let bound = 10f64.powi(i32::try_from(exponent).unwrap_or(i32::MAX));
+        magnitude != 0.0 && (magnitude >= bound || magnitude < bound.recip())
+    }
+}
+
 /// Formats a floating-point number as a string without trailing zeros after decimal.
 ///
 /// Only trims trailing zeros if the number contains a decimal point.
 /// Integer-valued floats like 40.0 are formatted as "40", not "4".
+/// Use [`format_numeric_with`] to control precision.
 ///
 /// # Examples
 ///
@@ -75,7 +208,55 @@ pub fn any_to_string_non_empty(value: AnyValue<'_>) -> Option<String> {
 /// assert_eq!(format_numeric(100.0), "100");
 /// ```
 pub fn format_numeric(v: f64) -> String {
-    let s = format!("{v}");
+    format_numeric_with(v, &NumericFormat::default())
+}
+
+/// Formats a floating-point number with explicit precision options.
+///
+/// Non-finite values (NaN, infinity) are rendered as-is.
+///
+/// # Examples
+///
+/// ```
+/// use tss_standards::polars::{NumericFormat, format_numeric_with};
+///
+/// let two_places = NumericFormat::default().with_decimals(2);
+/// assert_eq!(format_numeric_with(1.256, &two_places), "1.26");
+/// assert_eq!(format_numeric_with(1.5, &two_places), "1.5");
+///
+/// let scientific = NumericFormat::default().with_sci_threshold(6);
+/// assert_eq!(format_numeric_with(15_000_000.0, &scientific), "1.5e7");
+/// ```
+pub fn format_numeric_with(v: f64, format: &NumericFormat) -> String {
+    if !v.is_finite() {
+        return format!("{v}");
+    }
+    if format.uses_scientific(v) {
+        let s = match format.decimals {
+            Some(decimals) => format!("{v:.decimals$e}"),
+            None => format!("{v:e}"),
+        };
+        if !format.trim_trailing_zeros {
+            return s;
+        }
+        return match s.split_once('e') {
+            Some((mantissa, exponent)) => format!("{}e{exponent}", trim_trailing_zeros(mantissa)),
+            None => s,
+        };
+    }
+    let s = match format.decimals {
+        Some(decimals) => format!("{v:.decimals$}"),
+        None => format!("{v}"),
+    };
+    if format.trim_trailing_zeros {
+        trim_trailing_zeros(&s)
+    } else {
+        s
+    }
+}
+
+/// Remove trailing zeros after the decimal point, and the point if bare.
+fn trim_trailing_zeros(s: &str) -> String {
     // Only trim trailing zeros if there's a decimal point
     if s.contains('.') {
         let trimmed = s.trim_end_matches('0').trim_end_matches('.');
@@ -86,7 +267,7 @@ pub fn format_numeric(v: f64) -> String {
         }
     } else {
         // No decimal point - return as-is (integer representation)
-        s
+        s.to_string()
     }
 }
 
@@ -152,10 +333,247 @@ pub fn parse_i64(value: &str) -> Option<i64> {
     trimmed.parse::<i64>().ok()
 }
 
+/// Decimal and digit grouping separators of numbers in source data.
+///
+/// US and UK files write one thousand and a half as "1,000.5"; most
+/// European files write "1.000,5". Digit groups must have three digits,
+/// so under [`Period`](Self::Period) a value like "3,14" is rejected
+/// rather than read as 314.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DecimalConvention {
+    /// `.` decimal separator, `,` digit grouping (e.g., "1,234.56").
+    #[default]
+    Period,
+    /// `,` decimal separator, `.` digit grouping (e.g., "1.234,56").
+    Comma,
+}
+
+impl DecimalConvention {
+    /// The decimal separator.
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Self::Period => '.',
+            Self::Comma => ',',
+        }
+    }
+
+    /// The digit grouping separator.
+    pub fn grouping_separator(self) -> char {
+        match self {
+            Self::Period => ',',
+            Self::Comma => '.',
+        }
+    }
+
+    /// Detects the convention of sample values, e.g. from
+    /// `tss_ingest::get_sample_values`.
+    ///
+    /// Counts the values that parse under only one convention; values valid
+    /// under both (such as "1,234") don't decide. Ties keep the default
+    /// [`Period`](Self::Period).
+    pub fn detect<'a>(values: impl IntoIterator<Item = &'a str>) -> Self {
+        let (mut period, mut comma) = (0usize, 0usize);
+        for value in values {
+            match (
+                parse_f64_with(value, Self::Period).is_some(),
+                parse_f64_with(value, Self::Comma).is_some(),
+            ) {
+                (true, false) => period += 1,
+                (false, true) => comma += 1,
+                _ => {}
+            }
+        }
+        if comma > period {
+            Self::Comma
+        } else {
+            Self::Period
+        }
+    }
+
+    /// Rewrites a number in Rust syntax: no grouping, `.` decimal separator.
+    fn to_plain(self, value: &str) -> Option<String> {
+        let trimmed = value.trim();
+        let decimal = self.decimal_separator();
+        let grouping = self.grouping_separator();
+        let (integer, fraction) = match trimmed.split_once(decimal) {
+            Some((integer, fraction)) => {
+                if fraction.contains([decimal, grouping]) {
+                    return None;
+                }
+                (integer, Some(fraction))
+            }
+            None => (trimmed, None),
+        };
+
+        let (sign, digits) = match integer.strip_prefix(['-', '+']) {
+            Some(digits) => (&integer[..1], digits),
+            None => ("", integer),
+        };
+        let mut plain = sign.to_string();
+        if digits.contains(grouping) {
+            for (idx, group) in digits.split(grouping).enumerate() {
+                let valid_length = if idx == 0 {
+                    (1..=3).contains(&group.len())
+                } else {
+                    group.len() == 3
+                };
+                if !valid_length || !group.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                plain.push_str(group);
+            }
+        } else {
+            plain.push_str(digits);
+        }
+        if let Some(fraction) = fraction {
+            plain.push('.');
+            plain.push_str(fraction);
+        }
+        Some(plain)
+    }
+}
+
+/// Parses a string as `f64` under a decimal convention, accepting digit
+/// grouping (e.g., "1.234,56" under [`DecimalConvention::Comma`]).
+///
+/// Unlike [`parse_f64`], grouping separators are allowed.
+pub fn parse_f64_with(value: &str, convention: DecimalConvention) -> Option<f64> {
+    let plain = convention.to_plain(value)?;
+    if plain.is_empty() {
+        return None;
+    }
+    plain.parse::<f64>().ok()
+}
+
+/// Parses a string as `i64` under a decimal convention, accepting digit
+/// grouping (e.g., "1.234" under [`DecimalConvention::Comma`]).
+pub fn parse_i64_with(value: &str, convention: DecimalConvention) -> Option<i64> {
+    let plain = convention.to_plain(value)?;
+    if plain.is_empty() {
+        return None;
+    }
+    plain.parse::<i64>().ok()
+}
+
+/// Case-insensitive index of DataFrame column names.
+///
+/// SDTM variable names are case-insensitive, but source columns keep
+/// whatever case they were read with. Build the index once per DataFrame
+/// and resolve variable names to the actual column names through it.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use tss_standards::ColumnIndex;
+///
+/// let df = df! { "usubjid" => &["S-001"], "AeSeq" => &[1i64] }.unwrap();
+/// let columns = ColumnIndex::new(&df);
+///
+/// assert_eq!(columns.get("USUBJID"), Some("usubjid"));
+/// assert_eq!(columns.get_prefixed("--SEQ", "AE"), Some("AeSeq"));
+/// assert!(!columns.contains("STUDYID"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ColumnIndex {
+    /// Maps uppercase name -> original name
+    inner: HashMap<String, String>,
+}
+
+impl ColumnIndex {
+    /// Index the columns of a DataFrame.
+    pub fn new(df: &DataFrame) -> Self {
+        Self::from_names(df.get_column_names())
+    }
+
+    /// Index a list of column names.
+    pub fn from_names<I, S>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            inner: iter
+                .into_iter()
+                .map(|s| (s.as_ref().to_uppercase(), s.as_ref().to_string()))
+                .collect(),
+        }
+    }
+
+    /// Add a column name to the index.
+    pub fn insert(&mut self, name: impl AsRef<str>) {
+        let s = name.as_ref();
+        self.inner.insert(s.to_uppercase(), s.to_string());
+    }
+
+    /// Check if a column exists (case-insensitive).
+    pub fn contains(&self, name: impl AsRef<str>) -> bool {
+        self.inner.contains_key(&name.as_ref().to_uppercase())
+    }
+
+    /// Get the actual column name for a variable (case-insensitive).
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&str> {
+        self.inner
+            .get(&name.as_ref().to_uppercase())
+            .map(String::as_str)
+    }
+
+    /// Get the actual column name for a canonical variable name.
+    ///
+    /// A leading `--` in `name` is replaced by the domain `prefix`, so
+    /// "--SEQ" resolves to the AESEQ column for prefix "AE". Names without
+    /// the placeholder are looked up as is.
+    pub fn get_prefixed(&self, name: &str, prefix: &str) -> Option<&str> {
+        match name.strip_prefix("--") {
+            Some(suffix) => self.get(format!("{prefix}{suffix}")),
+            None => self.get(name),
+        }
+    }
+
+    /// Number of indexed columns.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Check if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_column_index_mixed_case() {
+        let df = df! {
+            "usubjid" => &["S-001"],
+            "StudyId" => &["STUDY"],
+            "LBSEQ" => &[1i64],
+        }
+        .unwrap();
+        let columns = ColumnIndex::new(&df);
+
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns.get("USUBJID"), Some("usubjid"));
+        assert_eq!(columns.get("studyid"), Some("StudyId"));
+        assert!(columns.contains("Lbseq"));
+        assert_eq!(columns.get_prefixed("--SEQ", "lb"), Some("LBSEQ"));
+        assert_eq!(columns.get_prefixed("USUBJID", "LB"), Some("usubjid"));
+    }
+
+    #[test]
+    fn test_column_index_missing_columns() {
+        let columns = ColumnIndex::from_names(["USUBJID", "STUDYID", "Domain"]);
+
+        assert!(columns.contains("domain"));
+        assert!(!columns.contains("OTHER"));
+        assert_eq!(columns.get("AESEQ"), None);
+        assert_eq!(columns.get_prefixed("--SEQ", "AE"), None);
+        assert!(ColumnIndex::default().is_empty());
+    }
+
     #[test]
     fn test_any_to_string_null() {
         assert_eq!(any_to_string(AnyValue::Null), "");
@@ -186,6 +604,64 @@ fn test_any_to_string_boolean() {
         assert_eq!(any_to_string(AnyValue::Boolean(false)), "N");
     }
 
+    #[test]
+    fn test_any_to_string_for_output_date() {
+        assert_eq!(any_to_string_for_output(AnyValue::Date(0)), "1970-01-01");
+        assert_eq!(
+            any_to_string_for_output(AnyValue::Date(19_797)),
+            "2024-03-15"
+        );
+        assert_eq!(any_to_string_for_output(AnyValue::Date(-1)), "1969-12-31");
+        // Leap day
+        assert_eq!(
+            any_to_string_for_output(AnyValue::Date(19_782)),
+            "2024-02-29"
+        );
+    }
+
+    #[test]
+    fn test_any_to_string_for_output_datetime() {
+        // 2024-03-15T14:30:00 UTC
+        let millis = 1_710_513_000_000;
+        assert_eq!(
+            any_to_string_for_output(AnyValue::Datetime(millis, TimeUnit::Milliseconds, None)),
+            "2024-03-15T14:30:00"
+        );
+        assert_eq!(
+            any_to_string_for_output(AnyValue::Datetime(
+                millis * 1_000_000,
+                TimeUnit::Nanoseconds,
+                None
+            )),
+            "2024-03-15T14:30:00"
+        );
+        assert_eq!(
+            any_to_string_for_output(AnyValue::Datetime(
+                millis * 1_000 + 250_000,
+                TimeUnit::Microseconds,
+                None
+            )),
+            "2024-03-15T14:30:00.250"
+        );
+    }
+
+    #[test]
+    fn test_any_to_string_for_output_datetime_with_timezone() {
+        let millis = 1_710_513_000_000;
+        let zoned = |zone: &str| {
+            let tz = TimeZone::opt_try_new(Some(zone)).unwrap();
+            any_to_string_for_output(AnyValue::DatetimeOwned(
+                millis,
+                TimeUnit::Milliseconds,
+                tz.map(Arc::new),
+            ))
+        };
+        // Zoned values are rendered in UTC
+        assert_eq!(zoned("UTC"), "2024-03-15T14:30:00");
+        assert_eq!(zoned("+05:30"), "2024-03-15T14:30:00");
+        assert_eq!(zoned("Europe/Berlin"), "2024-03-15T14:30:00");
+    }
+
     #[test]
     fn test_any_to_string_non_empty() {
         assert_eq!(any_to_string_non_empty(AnyValue::Null), None);
@@ -211,6 +687,66 @@ fn test_format_numeric() {
         assert_eq!(format_numeric(40.50), "40.5");
     }
 
+    #[test]
+    fn test_format_numeric_default() {
+        let default = NumericFormat::default();
+        for (v, expected) in [
+            (0.0, "0"),
+            (1.0, "1"),
+            (1.5, "1.5"),
+            (40.0, "40"),
+            (1000.0, "1000"),
+            // Shortest round-trip representation, no rounding
+            (0.1 + 0.2, "0.30000000000000004"),
+            (-3.25, "-3.25"),
+            (1e21, "1000000000000000000000"),
+            (f64::NAN, "NaN"),
+        ] {
+            assert_eq!(format_numeric_with(v, &default), expected);
+            assert_eq!(format_numeric(v), expected);
+        }
+    }
+
+    #[test]
+    fn test_format_numeric_fixed_decimals() {
+        let fixed = NumericFormat::default()
+            .with_decimals(2)
+            .with_trim_trailing_zeros(false);
+        assert_eq!(format_numeric_with(5.1, &fixed), "5.10");
+        assert_eq!(format_numeric_with(40.0, &fixed), "40.00");
+        assert_eq!(format_numeric_with(1.256, &fixed), "1.26");
+        assert_eq!(format_numeric_with(-0.5, &fixed), "-0.50");
+
+        let whole = NumericFormat::default()
+            .with_decimals(0)
+            .with_trim_trailing_zeros(false);
+        assert_eq!(format_numeric_with(40.0, &whole), "40");
+    }
+
+    #[test]
+    fn test_format_numeric_trim_mode() {
+        let trimmed = NumericFormat::default().with_decimals(3);
+        assert_eq!(format_numeric_with(5.1, &trimmed), "5.1");
+        assert_eq!(format_numeric_with(40.0, &trimmed), "40");
+        assert_eq!(format_numeric_with(1.23456, &trimmed), "1.235");
+
+        let untrimmed = NumericFormat::default().with_trim_trailing_zeros(false);
+        assert_eq!(format_numeric_with(1.5, &untrimmed), "1.5");
+        assert_eq!(format_numeric_with(40.0, &untrimmed), "40");
+    }
+
+    #[test]
+    fn test_format_numeric_scientific() {
+        let scientific = NumericFormat::default().with_sci_threshold(6);
+        assert_eq!(format_numeric_with(15_000_000.0, &scientific), "1.5e7");
+        assert_eq!(format_numeric_with(0.000_000_25, &scientific), "2.5e-7");
+        assert_eq!(format_numeric_with(123.5, &scientific), "123.5");
+        assert_eq!(format_numeric_with(0.0, &scientific), "0");
+
+        let fixed = scientific.with_decimals(2).with_trim_trailing_zeros(false);
+        assert_eq!(format_numeric_with(15_000_000.0, &fixed), "1.50e7");
+    }
+
     #[test]
     fn test_any_to_f64() {
         assert_eq!(any_to_f64(AnyValue::Null), None);
@@ -246,4 +782,33 @@ fn test_parse_i64() {
         assert_eq!(parse_i64("  -100  "), Some(-100));
         assert_eq!(parse_i64("invalid"), None);
     }
+
+    #[test]
+    fn test_parse_with_decimal_convention() {
+        let us = DecimalConvention::Period;
+        let eu = DecimalConvention::Comma;
+        assert_eq!(parse_f64_with("1,234.56", us), Some(1234.56));
+        assert_eq!(parse_f64_with("1.234,56", eu), Some(1234.56));
+        assert_eq!(parse_f64_with(" 2,75 ", eu), Some(2.75));
+        assert_eq!(parse_f64_with("-12.000.000,5", eu), Some(-12_000_000.5));
+        assert_eq!(parse_f64_with("3.15", us), Some(3.15));
+        // Groups of other than three digits are not grouping
+        assert_eq!(parse_f64_with("3,14", us), None);
+        assert_eq!(parse_f64_with("1.234,56", us), None);
+        assert_eq!(parse_f64_with("1,234.56", eu), None);
+        assert_eq!(parse_f64_with("", eu), None);
+
+        assert_eq!(parse_i64_with("1.234", eu), Some(1234));
+        assert_eq!(parse_i64_with("1,234,567", us), Some(1_234_567));
+        assert_eq!(parse_i64_with("1,5", eu), None);
+    }
+
+    #[test]
+    fn test_detect_decimal_convention() {
+        let eu = ["3,14", "1.234,5", "1,234", "12"];
+        assert_eq!(DecimalConvention::detect(eu), DecimalConvention::Comma);
+        let us = ["3.14", "1,234.5", "1,234"];
+        assert_eq!(DecimalConvention::detect(us), DecimalConvention::Period);
+        assert_eq!(DecimalConvention::detect([]), DecimalConvention::Period);
+    }
 }
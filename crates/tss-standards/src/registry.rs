@@ -2,22 +2,27 @@
 //!
 //! Provides unified access to SDTM, ADaM, and SEND standards.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
 
 use crate::adam::{AdamDataset, AdamDatasetType};
 use crate::ct::{CtVersion, TerminologyRegistry};
 use crate::sdtm::{SdtmDatasetClass, SdtmDomain};
 use crate::send::{SendDatasetClass, SendDomain};
-use crate::traits::Standard;
+use crate::traits::{CdiscDomain, Standard};
 
 use crate::error::Result;
 use crate::{adam_ig, ct, sdtm_ig, send_ig};
 
 /// Configuration for loading standards.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StandardsConfig {
-    /// CT version to load.
+    /// CT version for standards without an entry in `ct_versions`.
     pub ct_version: CtVersion,
+    /// CT version pinned per standard.
+    ///
+    /// Standards without an entry use `ct_version`.
+    pub ct_versions: HashMap<Standard, CtVersion>,
     /// Whether to load SDTM-IG.
     pub load_sdtm: bool,
     /// Whether to load ADaM-IG.
@@ -30,6 +35,7 @@ impl Default for StandardsConfig {
     fn default() -> Self {
         Self {
             ct_version: CtVersion::default(),
+            ct_versions: HashMap::new(),
             load_sdtm: true,
             load_adam: true,
             load_send: true,
@@ -38,6 +44,60 @@ fn default() -> Self {
 }
 
 impl StandardsConfig {
+    /// Create config that uses one CT version for all standards.
+    pub fn with_ct_version(version: CtVersion) -> Self {
+        Self {
+            ct_version: version,
+            ..Default::default()
+        }
+    }
+
+    /// Pin the CT version for a single standard.
+    pub fn set_ct_version(&mut self, standard: Standard, version: CtVersion) {
+        self.ct_versions.insert(standard, version);
+    }
+
+    /// Get the CT version used for a standard.
+    pub fn ct_version(&self, standard: Standard) -> CtVersion {
+        self.ct_versions
+            .get(&standard)
+            .copied()
+            .unwrap_or(self.ct_version)
+    }
+
+    /// Get the standard whose CT catalog is primary.
+    ///
+    /// SEND for nonclinical studies (SEND loaded without SDTM), SDTM
+    /// otherwise; ADaM datasets are derived from SDTM.
+    pub fn primary_standard(&self) -> Standard {
+        if self.load_send && !self.load_sdtm {
+            Standard::Send
+        } else {
+            Standard::Sdtm
+        }
+    }
+
+    /// CT version of every standard, for the loader.
+    fn resolved_ct_versions(&self) -> HashMap<Standard, CtVersion> {
+        [Standard::Sdtm, Standard::Adam, Standard::Send]
+            .into_iter()
+            .map(|standard| (standard, self.ct_version(standard)))
+            .collect()
+    }
+
+    /// Implementation Guide version of every loaded standard.
+    fn loaded_ig_versions(&self) -> Result<HashMap<Standard, String>> {
+        [
+            (Standard::Sdtm, self.load_sdtm),
+            (Standard::Adam, self.load_adam),
+            (Standard::Send, self.load_send),
+        ]
+        .into_iter()
+        .filter(|(_, loaded)| *loaded)
+        .map(|(standard, _)| Ok((standard, standard.ig_version()?)))
+        .collect()
+    }
+
     /// Create config for SDTM only.
     pub fn sdtm_only() -> Self {
         Self {
@@ -69,6 +129,42 @@ pub fn send_only() -> Self {
     }
 }
 
+/// A domain or dataset from any loaded standard.
+#[derive(Debug, Clone, Copy)]
+pub enum DomainRef<'a> {
+    Sdtm(&'a SdtmDomain),
+    Adam(&'a AdamDataset),
+    Send(&'a SendDomain),
+}
+
+impl DomainRef<'_> {
+    /// Domain or dataset name.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Sdtm(domain) => domain.name(),
+            Self::Adam(dataset) => dataset.name(),
+            Self::Send(domain) => domain.name(),
+        }
+    }
+
+    /// Standard the domain belongs to.
+    pub fn standard(&self) -> Standard {
+        match self {
+            Self::Sdtm(_) => Standard::Sdtm,
+            Self::Adam(_) => Standard::Adam,
+            Self::Send(_) => Standard::Send,
+        }
+    }
+}
+
+/// Cached registries with the configuration each was loaded with.
+type RegistryCache = Vec<(StandardsConfig, Arc<StandardsRegistry>)>;
+
+/// Registries loaded by [`StandardsRegistry::load_cached`].
+///
+/// Few distinct configurations are used per process, so a list is enough.
+static REGISTRY_CACHE: LazyLock<Mutex<RegistryCache>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
 /// Unified registry of all loaded CDISC standards.
 #[derive(Debug)]
 pub struct StandardsRegistry {
@@ -80,6 +176,8 @@ pub struct StandardsRegistry {
     pub adam_datasets: Vec<AdamDataset>,
     /// SEND domains (if loaded).
     pub send_domains: Vec<SendDomain>,
+    /// Implementation Guide version of each loaded standard.
+    pub ig_versions: HashMap<Standard, String>,
 }
 
 impl StandardsRegistry {
@@ -91,8 +189,7 @@ impl StandardsRegistry {
     /// Errors include which standard failed to load for easier debugging.
     pub fn load(config: &StandardsConfig) -> Result<Self> {
         // Load controlled terminology first (required for validation)
-        // TODO: Primary set should come from config based on study type
-        let ct = ct::load(config.ct_version, Some("SDTM"))?;
+        let ct = ct::load_per_standard(&config.resolved_ct_versions(), config.primary_standard())?;
 
         // Load SDTM-IG if requested
         let sdtm_domains = if config.load_sdtm {
@@ -120,6 +217,106 @@ pub fn load(config: &StandardsConfig) -> Result<Self> {
             sdtm_domains,
             adam_datasets,
             send_domains,
+            ig_versions: config.loaded_ig_versions()?,
+        })
+    }
+
+    /// Load standards with the given configuration, reusing a previous load.
+    ///
+    /// The first call for a configuration parses the standards like
+    /// [`load`](Self::load); later calls return the same registry from a
+    /// process-wide cache. Use [`reload`](Self::reload) to replace a cached
+    /// registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any requested standard fails to load. Failed
+    /// loads are not cached.
+    pub fn load_cached(config: &StandardsConfig) -> Result<Arc<Self>> {
+        let mut cache = REGISTRY_CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, registry)) = cache.iter().find(|(cached, _)| cached == config) {
+            return Ok(Arc::clone(registry));
+        }
+        let registry = Arc::new(Self::load(config)?);
+        cache.push((config.clone(), Arc::clone(&registry)));
+        Ok(registry)
+    }
+
+    /// Load standards again and replace the cached registry for `config`,
+    /// e.g. after the standards directories (see [`paths`](crate::paths))
+    /// changed.
+    ///
+    /// Registries already handed out by [`load_cached`](Self::load_cached)
+    /// stay valid; only later calls get the new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any requested standard fails to load, in which
+    /// case the cached registry is kept.
+    pub fn reload(config: &StandardsConfig) -> Result<Arc<Self>> {
+        let registry = Arc::new(Self::load(config)?);
+        let mut cache = REGISTRY_CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        cache.retain(|(cached, _)| cached != config);
+        cache.push((config.clone(), Arc::clone(&registry)));
+        Ok(registry)
+    }
+
+    /// Drop every cached registry.
+    pub fn clear_cache() {
+        REGISTRY_CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Load only the requested domains for the standards enabled in `config`.
+    ///
+    /// Useful for targeted processing of a study that uses a handful of
+    /// domains. Codes are matched case-insensitively against SDTM and SEND
+    /// domain names and ADaM dataset names. Controlled Terminology is loaded
+    /// in full.
+    ///
+    /// Each standard is read from the directory in its own environment
+    /// variable (e.g. `TSS_SDTM_DIR`), then from below the
+    /// `TSS_STANDARDS_DIR` root, and only then from the embedded data; see
+    /// [`crate::paths`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any requested standard fails to load.
+    pub fn load_domains(codes: &[&str], config: &StandardsConfig) -> Result<Self> {
+        let ct = ct::load_per_standard(&config.resolved_ct_versions(), config.primary_standard())?;
+
+        let sdtm_domains = if config.load_sdtm {
+            sdtm_ig::load_domains(codes)?
+        } else {
+            Vec::new()
+        };
+
+        let adam_datasets = if config.load_adam {
+            let mut datasets = adam_ig::load()?;
+            datasets.retain(|d| codes.iter().any(|c| d.name.eq_ignore_ascii_case(c.trim())));
+            datasets
+        } else {
+            Vec::new()
+        };
+
+        let send_domains = if config.load_send {
+            send_ig::load_domains(codes)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            ct,
+            sdtm_domains,
+            adam_datasets,
+            send_domains,
+            ig_versions: config.loaded_ig_versions()?,
         })
     }
 
@@ -195,6 +392,26 @@ pub fn find_send_domain(&self, name: &str) -> Option<&SendDomain> {
             .find(|d| d.name.eq_ignore_ascii_case(name))
     }
 
+    /// Find a domain in any loaded standard, with the standard and
+    /// Implementation Guide version it comes from.
+    ///
+    /// Standards are searched in SDTM, SEND, ADaM order, so a code defined
+    /// by both SDTM and SEND (e.g., DM) resolves to SDTM when both are
+    /// loaded.
+    pub fn find_domain_with_provenance(
+        &self,
+        code: &str,
+    ) -> Option<(DomainRef<'_>, Standard, &str)> {
+        let domain = self
+            .find_sdtm_domain(code)
+            .map(DomainRef::Sdtm)
+            .or_else(|| self.find_send_domain(code).map(DomainRef::Send))
+            .or_else(|| self.find_adam_dataset(code).map(DomainRef::Adam))?;
+        let standard = domain.standard();
+        let version = self.ig_versions.get(&standard)?;
+        Some((domain, standard, version.as_str()))
+    }
+
     /// Get SDTM domains grouped by dataset class.
     ///
     /// Returns domains organized by their class for easy UI grouping.
@@ -280,6 +497,82 @@ fn test_load_adam_workflow() {
         assert!(!registry.has_standard(Standard::Send));
     }
 
+    #[test]
+    fn test_load_cached() {
+        // A configuration no other test caches
+        let mut config = StandardsConfig::send_only();
+        config.set_ct_version(Standard::Send, CtVersion::V2025_03_28);
+        let domain_names = |registry: &StandardsRegistry| -> Vec<(String, usize)> {
+            registry
+                .send_domains
+                .iter()
+                .map(|d| (d.name.clone(), d.variables.len()))
+                .collect()
+        };
+
+        let first = StandardsRegistry::load_cached(&config).expect("load registry");
+        let second = StandardsRegistry::load_cached(&config).expect("load registry");
+        assert!(Arc::ptr_eq(&first, &second), "second load should be cached");
+        assert_eq!(domain_names(&first), domain_names(&second));
+
+        let reloaded = StandardsRegistry::reload(&config).expect("reload registry");
+        assert!(!Arc::ptr_eq(&first, &reloaded));
+        assert_eq!(domain_names(&first), domain_names(&reloaded));
+        let after_reload = StandardsRegistry::load_cached(&config).expect("load registry");
+        assert!(Arc::ptr_eq(&reloaded, &after_reload));
+    }
+
+    #[test]
+    fn test_load_domains() {
+        let registry =
+            StandardsRegistry::load_domains(&["ae", "DM"], &StandardsConfig::sdtm_only())
+                .expect("load AE and DM");
+
+        let names: Vec<&str> = registry
+            .sdtm_domains
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["AE", "DM"]);
+        assert!(registry.find_sdtm_domain("LB").is_none());
+        assert!(registry.find_suppqual_template().is_none());
+
+        let ae = registry.find_sdtm_domain("AE").expect("AE domain");
+        assert!(!ae.variables.is_empty(), "AE should have variables");
+        assert!(ae.class.is_some(), "AE should keep dataset metadata");
+    }
+
+    #[test]
+    fn test_per_standard_ct_versions() {
+        let mut config = StandardsConfig::with_ct_version(CtVersion::V2025_03_28);
+        config.load_adam = false;
+        config.set_ct_version(Standard::Send, CtVersion::V2025_09_26);
+        assert_eq!(config.ct_version(Standard::Sdtm), CtVersion::V2025_03_28);
+        assert_eq!(config.ct_version(Standard::Send), CtVersion::V2025_09_26);
+
+        let registry = StandardsRegistry::load(&config).expect("load registry");
+        let send = registry.ct.catalogs.get("SEND CT").expect("SEND CT");
+        assert_eq!(send.version.as_deref(), Some("2025-09-26"));
+        let sdtm = registry.ct.catalogs.get("SDTM CT").expect("SDTM CT");
+        assert_eq!(sdtm.version.as_deref(), Some("2025-03-28"));
+        assert_eq!(registry.ct.primary_standard(), Some(Standard::Sdtm));
+    }
+
+    #[test]
+    fn test_ct_version_fallback() {
+        let mut config = StandardsConfig::send_only();
+        config.ct_version = CtVersion::V2025_03_28;
+        config.set_ct_version(Standard::Sdtm, CtVersion::V2025_09_26);
+        assert_eq!(config.ct_version(Standard::Send), CtVersion::V2025_03_28);
+        assert_eq!(config.ct_version(Standard::Sdtm), CtVersion::V2025_09_26);
+        assert_eq!(config.primary_standard(), Standard::Send);
+
+        let registry = StandardsRegistry::load(&config).expect("load registry");
+        assert_eq!(registry.ct.primary_standard(), Some(Standard::Send));
+        let send = registry.ct.catalogs.get("SEND CT").expect("SEND CT");
+        assert_eq!(send.version.as_deref(), Some("2025-03-28"));
+    }
+
     #[test]
     fn test_find_domain() {
         let registry = StandardsRegistry::load_sdtm_only().expect("load registry");
@@ -287,6 +580,36 @@ fn test_find_domain() {
         assert!(ae.is_some(), "Should find AE domain");
     }
 
+    #[test]
+    fn test_find_domain_with_provenance() {
+        let registry = StandardsRegistry::load_all().expect("load registry");
+
+        let (ae, standard, version) = registry
+            .find_domain_with_provenance("ae")
+            .expect("AE domain");
+        assert_eq!(ae.name(), "AE");
+        assert_eq!(standard, Standard::Sdtm);
+        assert_eq!((standard.ig_name(), version), ("SDTMIG", "3.4"));
+
+        let (bw, standard, version) = registry
+            .find_domain_with_provenance("BW")
+            .expect("BW domain");
+        assert!(matches!(bw, DomainRef::Send(_)));
+        assert_eq!((standard.ig_name(), version), ("SENDIG", "3.1.1"));
+
+        let (_, standard, version) = registry
+            .find_domain_with_provenance("ADSL")
+            .expect("ADSL dataset");
+        assert_eq!((standard, version), (Standard::Adam, "1.3"));
+
+        assert!(registry.find_domain_with_provenance("ZZ").is_none());
+
+        // DM is in both SDTM and SEND; without SDTM it resolves to SEND
+        let send = StandardsRegistry::load_send_only().expect("load SEND");
+        let (_, standard, _) = send.find_domain_with_provenance("DM").expect("DM");
+        assert_eq!(standard, Standard::Send);
+    }
+
     #[test]
     fn test_find_suppqual_template() {
         let registry = StandardsRegistry::load_sdtm_only().expect("load registry");
@@ -9,6 +9,7 @@
 //! - Section 4.1: Variable Naming Conventions
 
 use super::enums::{SdtmDatasetClass, VariableRole};
+use super::ordering::canonical_rank;
 use crate::traits::{CdiscDomain, CdiscVariable, CoreDesignation, VariableType};
 use serde::{Deserialize, Serialize};
 
@@ -186,26 +187,63 @@ pub fn column_name(&self, canonical: &str) -> Option<&str> {
 
     /// Order variables by SDTM role per SDTMIG v3.4 Section 2.1.
     ///
-    /// Within each role category, variables are ordered by their defined order.
+    /// Within each role category, variables are ordered by their defined order,
+    /// then by canonical SDTMIG position (see [`canonical_rank`]) for variables
+    /// without one. Qualifiers without a defined order share one category,
+    /// since the class tables interleave the qualifier roles (--ORRESU, a
+    /// Variable Qualifier, sits between the Result Qualifiers --ORRES and
+    /// --STRESC); qualifiers with a defined order keep their own role.
     pub fn variables_by_role(&self) -> Vec<&SdtmVariable> {
         let mut ordered: Vec<&SdtmVariable> = self.variables.iter().collect();
         ordered.sort_by_key(|v| {
-            let role_order = v.role.map(|r| r.sort_order()).unwrap_or(99);
+            let role_order = v
+                .role
+                .map(|r| {
+                    if r.is_qualifier() && v.order.is_none() {
+                        VariableRole::GroupingQualifier.sort_order()
+                    } else {
+                        r.sort_order()
+                    }
+                })
+                .unwrap_or(99);
             let order = v.order.unwrap_or(999);
-            (role_order, order)
+            let canonical = canonical_rank(&self.name, &v.name).unwrap_or(usize::MAX);
+            (role_order, order, canonical)
         });
         ordered
     }
 
+    /// Base domain code whose prefix the variables of this dataset use.
+    ///
+    /// Per SDTMIG v3.4 Section 4.1.7, split datasets of the general
+    /// observation classes are named with the two-character parent domain
+    /// code plus a suffix (e.g., "LBCH", "FAMH") and keep the parent's
+    /// variable prefix (LBSEQ in LBCH). Returns that parent code for such
+    /// datasets and the domain name otherwise.
+    pub fn base_code(&self) -> &str {
+        if self.name.len() > 2 && self.name.is_char_boundary(2) && self.is_general_observation() {
+            &self.name[..2]
+        } else {
+            &self.name
+        }
+    }
+
     /// Infer the sequence variable for this domain using SDTM naming rules.
     ///
-    /// Per SDTMIG v3.4 Section 4.1.7, looks for `{DOMAIN}SEQ` first,
+    /// Per SDTMIG v3.4 Section 4.1.7, looks for `{DOMAIN}SEQ` first, then
+    /// `{BASE}SEQ` for split datasets (see [`base_code`](Self::base_code)),
     /// then falls back to any variable ending in "SEQ".
     pub fn infer_seq_column(&self) -> Option<&str> {
         let expected = format!("{}SEQ", self.name);
         if let Some(name) = self.column_name(&expected) {
             return Some(name);
         }
+        let base = self.base_code();
+        if base != self.name
+            && let Some(name) = self.column_name(&format!("{base}SEQ"))
+        {
+            return Some(name);
+        }
         self.variables
             .iter()
             .map(|v| v.name.as_str())
@@ -227,3 +265,61 @@ pub fn create_supp_domain(&self, suppqual_template: &SdtmDomain) -> SdtmDomain {
         supp
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdtm::test_support;
+
+    fn domain(name: &str, class: SdtmDatasetClass, variables: &[&str]) -> SdtmDomain {
+        test_support::domain(name)
+            .class(class)
+            .variable_names(variables)
+            .build()
+    }
+
+    #[test]
+    fn test_infer_seq_column_split_dataset() {
+        // ASEQ sorts before LBSEQ, so the base prefix must win
+        let lbch = domain(
+            "LBCH",
+            SdtmDatasetClass::Findings,
+            &["USUBJID", "ASEQ", "LBSEQ", "LBTESTCD"],
+        );
+        assert_eq!(lbch.base_code(), "LB");
+        assert_eq!(lbch.infer_seq_column(), Some("LBSEQ"));
+
+        let famh = domain(
+            "FAMH",
+            SdtmDatasetClass::FindingsAbout,
+            &["USUBJID", "FASEQ", "FAOBJ"],
+        );
+        assert_eq!(famh.infer_seq_column(), Some("FASEQ"));
+
+        // Relationship datasets are not split datasets
+        let supp = domain("SUPPLB", SdtmDatasetClass::Relationship, &["USUBJID"]);
+        assert_eq!(supp.base_code(), "SUPPLB");
+    }
+
+    #[test]
+    fn test_infer_seq_column_custom_domain() {
+        let xa = domain(
+            "XA",
+            SdtmDatasetClass::Events,
+            &["USUBJID", "AESEQ", "XASEQ"],
+        );
+        assert_eq!(xa.infer_seq_column(), Some("XASEQ"));
+
+        let xapk = domain(
+            "XAPK",
+            SdtmDatasetClass::Findings,
+            &["USUBJID", "PCSEQ", "XASEQ"],
+        );
+        assert_eq!(xapk.base_code(), "XA");
+        assert_eq!(xapk.infer_seq_column(), Some("XASEQ"));
+
+        // Without a prefixed SEQ, any SEQ variable is used
+        let xb = domain("XB", SdtmDatasetClass::Events, &["USUBJID", "VISITSEQ"]);
+        assert_eq!(xb.infer_seq_column(), Some("VISITSEQ"));
+    }
+}
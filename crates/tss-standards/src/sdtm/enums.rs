@@ -76,6 +76,31 @@ pub fn general_observation_class(&self) -> Option<Self> {
         }
     }
 
+    /// Returns the variables a new domain of this class starts with.
+    ///
+    /// Lists the Required variables every domain of the class has: the
+    /// identifiers of all General Observation domains (STUDYID, DOMAIN,
+    /// USUBJID, --SEQ) followed by the class topic variables, in SDTMIG
+    /// order. `--` stands for the domain prefix (e.g., "--SEQ" is "AESEQ" in
+    /// AE). Other classes have no common variable set and return an empty
+    /// list.
+    pub fn required_skeleton(&self) -> Vec<&'static str> {
+        let mut variables = match self {
+            Self::Interventions | Self::Events | Self::Findings | Self::FindingsAbout => {
+                vec!["STUDYID", "DOMAIN", "USUBJID", "--SEQ"]
+            }
+            _ => return Vec::new(),
+        };
+        match self {
+            Self::Interventions => variables.push("--TRT"),
+            Self::Events => variables.push("--TERM"),
+            Self::Findings => variables.extend(["--TESTCD", "--TEST"]),
+            Self::FindingsAbout => variables.extend(["--TESTCD", "--TEST", "--OBJ"]),
+            _ => {}
+        }
+        variables
+    }
+
     /// Returns the canonical class name as it appears in SDTMIG.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -241,6 +266,7 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::CoreDesignation;
 
     #[test]
     fn test_variable_role_from_str() {
@@ -290,6 +316,53 @@ fn test_dataset_class_is_general_observation() {
         assert!(!SdtmDatasetClass::TrialDesign.is_general_observation());
     }
 
+    #[test]
+    fn test_dataset_class_required_skeleton() {
+        let events = SdtmDatasetClass::Events.required_skeleton();
+        let findings = SdtmDatasetClass::Findings.required_skeleton();
+        assert_eq!(events, ["STUDYID", "DOMAIN", "USUBJID", "--SEQ", "--TERM"]);
+        assert_eq!(&findings[..4], &events[..4]);
+        assert!(findings.contains(&"--TESTCD"));
+        assert!(!findings.contains(&"--TERM"));
+        assert!(!events.contains(&"--TESTCD"));
+
+        assert!(
+            SdtmDatasetClass::FindingsAbout
+                .required_skeleton()
+                .contains(&"--OBJ")
+        );
+        assert!(
+            SdtmDatasetClass::SpecialPurpose
+                .required_skeleton()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_required_skeleton_matches_ig_core() {
+        let domains = crate::sdtm_ig::load().expect("load SDTM-IG");
+        for domain in &domains {
+            let Some(class) = domain.class else {
+                continue;
+            };
+            let prefix = domain.name.get(..2).unwrap_or_default();
+            for variable in class.required_skeleton() {
+                let name = variable.replace("--", prefix);
+                let core = domain
+                    .variables
+                    .iter()
+                    .find(|v| v.name == name)
+                    .and_then(|v| v.core);
+                assert_eq!(
+                    core,
+                    Some(CoreDesignation::Required),
+                    "{name} in {}",
+                    domain.name
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_dataset_class_general_observation_class() {
         assert_eq!(
@@ -5,8 +5,12 @@
 
 pub mod domain;
 pub mod enums;
+pub mod ordering;
 pub mod reciprocal;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 pub use domain::{SdtmDomain, SdtmVariable};
 pub use enums::{SdtmDatasetClass, VariableRole};
+pub use ordering::canonical_rank;
 pub use reciprocal::{get_parent_srel_for_child, get_reciprocal_srel, is_symmetric_srel};
@@ -0,0 +1,151 @@
+//! Canonical SDTMIG variable order.
+//!
+//! Per SDTM-IG v3.4 Section 2.1, variables within a role follow the order of
+//! the general observation class tables (e.g., --ORRES, --ORRESU, --STRESC,
+//! --STRESN, --STRESU). This module ranks variable names by that order so
+//! definitions without an explicit `order` still sort sensibly.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Variables that are never prefixed with the domain code, in canonical order.
+const UNPREFIXED_ORDER: &[&str] = &[
+    "STUDYID", "DOMAIN", "APID", "USUBJID", "POOLID", "SPDEVID", "FOCID", "VISITNUM", "VISIT",
+    "VISITDY", "TAETORD", "EPOCH",
+];
+
+/// Domain-prefixed variable suffixes (the "--" part), in canonical order.
+///
+/// Follows the identifier, topic, grouping, record and result qualifier, and
+/// timing tables of the Interventions, Events and Findings classes, in that
+/// order; suffixes shared by several classes appear at their first position.
+const SUFFIX_ORDER: &[&str] = &[
+    "SEQ", "GRPID", "REFID", "RECID", "SPID", "LNKID", "LNKGRP", "TESTCD", "TEST", "TSTDTL", "TRT",
+    "TERM", "MODIFY", "LLT", "LLTCD", "DECOD", "PTCD", "HLT", "HLTCD", "HLGT", "HLGTCD", "CAT",
+    "SCAT", "PRESP", "OCCUR", "POS", "BODSYS", "BDSYCD", "SOC", "SOCCD", "ORRES", "ORRESU",
+    "ORNRLO", "ORNRHI", "STRESC", "STRESN", "STRESU", "STNRLO", "STNRHI", "STNRC", "NRIND",
+    "RESCAT", "STAT", "REASND", "XFN", "NAM", "LOINC", "SPEC", "ANTREG", "SPCCND", "SPCUFL", "LOC",
+    "LAT", "DIR", "PORTOT", "METHOD", "ANMETH", "LEAD", "CSTATE", "BLFL", "LOBXFL", "FAST",
+    "DRVFL", "EVAL", "EVALID", "ACPTFL", "TOX", "TOXGR", "SEV", "SER", "ACN", "ACNOTH", "REL",
+    "RELNST", "PATT", "OUT", "SCAN", "SCONG", "SDISAB", "SDTH", "SHOSP", "SLIFE", "SOD", "SMIE",
+    "CONTRT", "INDC", "CLAS", "CLASCD", "DOSE", "DOSTXT", "DOSU", "DOSFRM", "DOSFRQ", "DOSTOT",
+    "DOSRGM", "ROUTE", "LOT", "PSTRG", "PSTRGU", "TRTV", "VAMT", "VAMTU", "ADJ", "DTC", "STDTC",
+    "ENDTC", "DY", "STDY", "ENDY", "DUR", "TPT", "TPTNUM", "ELTM", "TPTREF", "RFTDTC", "STRF",
+    "ENRF", "EVLINT", "EVINTX", "STRTPT", "STTPT", "ENRTPT", "ENTPT",
+];
+
+/// Canonical rank of each unprefixed variable.
+static UNPREFIXED_RANKS: LazyLock<HashMap<&'static str, usize>> =
+    LazyLock::new(|| ranks(UNPREFIXED_ORDER));
+
+/// Canonical rank of each domain-prefixed suffix.
+static SUFFIX_RANKS: LazyLock<HashMap<&'static str, usize>> = LazyLock::new(|| ranks(SUFFIX_ORDER));
+
+/// Map each name to its first position in `names`.
+fn ranks(names: &[&'static str]) -> HashMap<&'static str, usize> {
+    let mut ranks = HashMap::new();
+    for (idx, name) in names.iter().enumerate() {
+        ranks.entry(*name).or_insert(idx);
+    }
+    ranks
+}
+
+/// Canonical SDTMIG position of a variable within `domain`.
+///
+/// Unprefixed variables (STUDYID, USUBJID, VISITNUM, ...) rank before all
+/// domain-prefixed ones. Returns `None` for variables not in the canonical
+/// tables, such as sponsor-defined variables.
+pub fn canonical_rank(domain: &str, variable: &str) -> Option<usize> {
+    let variable = variable.to_uppercase();
+    if let Some(rank) = UNPREFIXED_RANKS.get(variable.as_str()) {
+        return Some(*rank);
+    }
+    let suffix = variable.strip_prefix(domain.to_uppercase().as_str())?;
+    SUFFIX_RANKS
+        .get(suffix)
+        .map(|rank| UNPREFIXED_ORDER.len() + rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdtm::VariableRole;
+    use crate::sdtm::test_support::{domain, variable};
+
+    #[test]
+    fn test_canonical_rank() {
+        assert!(canonical_rank("LB", "STUDYID") < canonical_rank("LB", "USUBJID"));
+        assert!(canonical_rank("LB", "USUBJID") < canonical_rank("LB", "LBSEQ"));
+        assert!(canonical_rank("LB", "lbtestcd") < canonical_rank("LB", "LBTEST"));
+        assert_eq!(canonical_rank("LB", "LBXTRA"), None);
+        assert_eq!(canonical_rank("LB", "VSORRES"), None);
+    }
+
+    #[test]
+    fn test_result_qualifiers_follow_canonical_order() {
+        let domain = domain("LB")
+            .variables(
+                [
+                    ("LBSTRESN", VariableRole::ResultQualifier),
+                    ("LBTESTCD", VariableRole::Topic),
+                    ("LBSTRESC", VariableRole::ResultQualifier),
+                    ("LBORRESU", VariableRole::VariableQualifier),
+                    ("LBORRES", VariableRole::ResultQualifier),
+                    ("USUBJID", VariableRole::Identifier),
+                    ("LBSTRESU", VariableRole::VariableQualifier),
+                ]
+                .map(|(name, role)| variable(name).role(role)),
+            )
+            .build();
+
+        let names: Vec<&str> = domain
+            .variables_by_role()
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect();
+        // Qualifier roles interleave: --ORRESU sits between --ORRES and --STRESC
+        assert_eq!(
+            names,
+            [
+                "USUBJID", "LBTESTCD", "LBORRES", "LBORRESU", "LBSTRESC", "LBSTRESN", "LBSTRESU"
+            ]
+        );
+
+        // Without roles, the canonical order alone decides
+        let mut domain = domain;
+        for v in &mut domain.variables {
+            v.role = None;
+        }
+        let names: Vec<&str> = domain
+            .variables_by_role()
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "USUBJID", "LBTESTCD", "LBORRES", "LBORRESU", "LBSTRESC", "LBSTRESN", "LBSTRESU"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_defined_order_keeps_qualifier_roles() {
+        // DV defines an order for every variable, so qualifier roles stay
+        // grouped: DVCAT and DVSCAT (Grouping) before DVDECOD (Synonym)
+        let domains = crate::sdtm_ig::load().expect("load SDTM-IG");
+        let dv = domains.iter().find(|d| d.name == "DV").expect("DV domain");
+        let names: Vec<&str> = dv
+            .variables_by_role()
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "STUDYID", "DOMAIN", "USUBJID", "DVSEQ", "DVREFID", "DVSPID", "DVTERM", "DVCAT",
+                "DVSCAT", "DVDECOD", "TAETORD", "EPOCH", "DVSTDTC", "DVENDTC", "DVSTDY", "DVENDY"
+            ]
+        );
+    }
+}
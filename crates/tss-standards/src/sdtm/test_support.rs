@@ -0,0 +1,158 @@
+//! Builders for SDTM domain and variable fixtures in tests.
+//!
+//! Available to this crate's tests and, with the `test-support` feature, to
+//! the tests of dependent crates. Fields left unset keep the defaults of
+//! [`variable`] and [`domain`], so a fixture only names what its test cares
+//! about:
+//!
+//! ```rust,ignore
+//! use tss_standards::sdtm::test_support::{domain, variable};
+//!
+//! let ae = domain("AE")
+//!     .class(SdtmDatasetClass::Events)
+//!     .variable(variable("USUBJID").role(VariableRole::Identifier))
+//!     .variable(variable("AESEQ").data_type(VariableType::Num))
+//!     .build();
+//! ```
+
+use super::{SdtmDatasetClass, SdtmDomain, SdtmVariable, VariableRole};
+use crate::traits::{CoreDesignation, VariableType};
+
+/// Start a Char variable without label, length, role, core, codelist or order.
+pub fn variable(name: &str) -> VariableBuilder {
+    VariableBuilder(SdtmVariable {
+        name: name.to_string(),
+        label: None,
+        data_type: VariableType::Char,
+        length: None,
+        role: None,
+        core: None,
+        codelist_code: None,
+        described_value_domain: None,
+        order: None,
+    })
+}
+
+/// Start a domain without label, class, structure, dataset name or variables.
+pub fn domain(name: &str) -> DomainBuilder {
+    DomainBuilder(SdtmDomain {
+        name: name.to_string(),
+        label: None,
+        class: None,
+        structure: None,
+        dataset_name: None,
+        variables: Vec::new(),
+    })
+}
+
+/// Builder for an [`SdtmVariable`] fixture; see [`variable`].
+#[derive(Debug, Clone)]
+pub struct VariableBuilder(SdtmVariable);
+
+impl VariableBuilder {
+    pub fn label(mut self, label: &str) -> Self {
+        self.0.label = Some(label.to_string());
+        self
+    }
+
+    pub fn data_type(mut self, data_type: VariableType) -> Self {
+        self.0.data_type = data_type;
+        self
+    }
+
+    pub fn length(mut self, length: u32) -> Self {
+        self.0.length = Some(length);
+        self
+    }
+
+    pub fn role(mut self, role: VariableRole) -> Self {
+        self.0.role = Some(role);
+        self
+    }
+
+    pub fn core(mut self, core: CoreDesignation) -> Self {
+        self.0.core = Some(core);
+        self
+    }
+
+    pub fn codelist(mut self, codelist_code: &str) -> Self {
+        self.0.codelist_code = Some(codelist_code.to_string());
+        self
+    }
+
+    pub fn described_value_domain(mut self, described_value_domain: &str) -> Self {
+        self.0.described_value_domain = Some(described_value_domain.to_string());
+        self
+    }
+
+    pub fn order(mut self, order: u32) -> Self {
+        self.0.order = Some(order);
+        self
+    }
+
+    pub fn build(self) -> SdtmVariable {
+        self.0
+    }
+}
+
+impl From<VariableBuilder> for SdtmVariable {
+    fn from(builder: VariableBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Builder for an [`SdtmDomain`] fixture; see [`domain`].
+#[derive(Debug, Clone)]
+pub struct DomainBuilder(SdtmDomain);
+
+impl DomainBuilder {
+    pub fn label(mut self, label: &str) -> Self {
+        self.0.label = Some(label.to_string());
+        self
+    }
+
+    pub fn class(mut self, class: SdtmDatasetClass) -> Self {
+        self.0.class = Some(class);
+        self
+    }
+
+    pub fn structure(mut self, structure: &str) -> Self {
+        self.0.structure = Some(structure.to_string());
+        self
+    }
+
+    pub fn dataset_name(mut self, dataset_name: &str) -> Self {
+        self.0.dataset_name = Some(dataset_name.to_string());
+        self
+    }
+
+    pub fn variable(mut self, variable: impl Into<SdtmVariable>) -> Self {
+        self.0.variables.push(variable.into());
+        self
+    }
+
+    pub fn variables<V: Into<SdtmVariable>>(
+        mut self,
+        variables: impl IntoIterator<Item = V>,
+    ) -> Self {
+        self.0
+            .variables
+            .extend(variables.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add plain Char variables by name (see [`variable`]).
+    pub fn variable_names(self, names: &[&str]) -> Self {
+        self.variables(names.iter().map(|name| variable(name)))
+    }
+
+    pub fn build(self) -> SdtmDomain {
+        self.0
+    }
+}
+
+impl From<DomainBuilder> for SdtmDomain {
+    fn from(builder: DomainBuilder) -> Self {
+        builder.build()
+    }
+}
@@ -3,17 +3,20 @@
 //! Loads SDTM Implementation Guide v3.4 definitions from embedded CSV data.
 //! All data is compiled into the binary for offline operation.
 
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Cursor;
 
 use serde::Deserialize;
 
 use crate::embedded;
 use crate::error::{Result, StandardsError};
+use crate::paths::{self, StandardsData};
 use crate::sdtm::{SdtmDatasetClass, SdtmDomain, SdtmVariable};
 use crate::traits::VariableType;
 
-/// Load SDTM-IG domains from embedded data.
+/// Load SDTM-IG domains from embedded data, or from disk when configured
+/// (see [`paths`]).
 ///
 /// # Example
 ///
@@ -23,11 +26,43 @@
 /// println!("AE has {} variables", ae.variables.len());
 /// ```
 pub fn load() -> Result<Vec<SdtmDomain>> {
-    let datasets = load_datasets_from_str(embedded::SDTM_IG_DATASETS)?;
-    let variables = load_variables_from_str(embedded::SDTM_IG_VARIABLES)?;
+    let (datasets_csv, variables_csv) = read_csvs()?;
+    let datasets = load_datasets_from_str(&datasets_csv)?;
+    let variables = load_variables_from_str(&variables_csv, None)?;
     build_domains(&datasets, variables)
 }
 
+/// Load only the requested SDTM-IG domains.
+///
+/// The files are looked up like [`load`]: the directory from the
+/// data's own environment variable, then the `TSS_STANDARDS_DIR` root,
+/// then the embedded data (see [`paths`]). Variables.csv is still parsed in
+/// full, but only rows belonging to the requested domain codes
+/// (case-insensitive) are kept.
+pub fn load_domains(codes: &[&str]) -> Result<Vec<SdtmDomain>> {
+    let filter: BTreeSet<String> = codes.iter().map(|c| c.trim().to_uppercase()).collect();
+    let (datasets_csv, variables_csv) = read_csvs()?;
+    let datasets = load_datasets_from_str(&datasets_csv)?;
+    let variables = load_variables_from_str(&variables_csv, Some(&filter))?;
+    build_domains(&datasets, variables)
+}
+
+/// Read Datasets.csv and Variables.csv.
+fn read_csvs() -> Result<(Cow<'static, str>, Cow<'static, str>)> {
+    Ok((
+        paths::read_or_embedded(
+            StandardsData::SdtmIg,
+            "Datasets.csv",
+            embedded::SDTM_IG_DATASETS,
+        )?,
+        paths::read_or_embedded(
+            StandardsData::SdtmIg,
+            "Variables.csv",
+            embedded::SDTM_IG_VARIABLES,
+        )?,
+    ))
+}
+
 // =============================================================================
 // CSV Row Types
 // =============================================================================
@@ -108,7 +143,10 @@ fn load_datasets_from_str(content: &str) -> Result<BTreeMap<String, DatasetMeta>
 }
 
 /// Load Variables.csv from embedded string content.
-fn load_variables_from_str(content: &str) -> Result<BTreeMap<String, Vec<SdtmVariable>>> {
+fn load_variables_from_str(
+    content: &str,
+    filter: Option<&BTreeSet<String>>,
+) -> Result<BTreeMap<String, Vec<SdtmVariable>>> {
     let cursor = Cursor::new(content.as_bytes());
     let mut reader = csv::ReaderBuilder::new()
         .has_headers(true)
@@ -127,6 +165,9 @@ fn load_variables_from_str(content: &str) -> Result<BTreeMap<String, Vec<SdtmVar
         if dataset.is_empty() || name.is_empty() {
             continue;
         }
+        if filter.is_some_and(|codes| !codes.contains(&dataset)) {
+            continue;
+        }
 
         let order = row.variable_order.trim().parse::<u32>().ok();
 
@@ -1,8 +1,12 @@
 //! SEND domain and variable definitions per SENDIG v3.1.1.
 //!
 //! This module provides types for representing SEND nonclinical study domains.
+//!
+//! SEND domains share the SDTM structure, so [`SdtmDomain::from`] converts
+//! them for the SDTM-based validation and export pipeline.
 
 use super::enums::{SendDatasetClass, SendStudyType};
+use crate::sdtm::{SdtmDomain, SdtmVariable, VariableRole};
 use crate::traits::{CdiscDomain, CdiscVariable, CoreDesignation, VariableType};
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +27,10 @@ pub struct SendVariable {
     /// Maximum length for character variables (in bytes).
     pub length: Option<u32>,
 
+    /// Variable role (Identifier, Topic, Timing, etc.).
+    #[serde(default)]
+    pub role: Option<VariableRole>,
+
     /// Core designation: Required, Expected, or Permissible.
     pub core: Option<CoreDesignation>,
 
@@ -126,6 +134,35 @@ fn variables(&self) -> &[SendVariable] {
     }
 }
 
+impl From<&SendVariable> for SdtmVariable {
+    fn from(variable: &SendVariable) -> Self {
+        SdtmVariable {
+            name: variable.name.clone(),
+            label: variable.label.clone(),
+            data_type: variable.data_type,
+            length: variable.length,
+            role: variable.role,
+            core: variable.core,
+            codelist_code: variable.codelist_code.clone(),
+            described_value_domain: variable.described_value_domain.clone(),
+            order: variable.order,
+        }
+    }
+}
+
+impl From<&SendDomain> for SdtmDomain {
+    fn from(domain: &SendDomain) -> Self {
+        SdtmDomain {
+            name: domain.name.clone(),
+            label: domain.label.clone(),
+            class: domain.class.map(|c| c.as_sdtm_class()),
+            structure: domain.structure.clone(),
+            dataset_name: None,
+            variables: domain.variables.iter().map(SdtmVariable::from).collect(),
+        }
+    }
+}
+
 impl SendDomain {
     /// Returns the class name as a string.
     pub fn class_name(&self) -> Option<&'static str> {
@@ -3,6 +3,7 @@
 //! This module contains enumerations specific to SEND such as dataset classes
 //! and study types.
 
+use crate::sdtm::SdtmDatasetClass;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
@@ -27,6 +28,18 @@ pub enum SendDatasetClass {
 }
 
 impl SendDatasetClass {
+    /// Returns the equivalent SDTM dataset class.
+    pub fn as_sdtm_class(&self) -> SdtmDatasetClass {
+        match self {
+            SendDatasetClass::Interventions => SdtmDatasetClass::Interventions,
+            SendDatasetClass::Events => SdtmDatasetClass::Events,
+            SendDatasetClass::Findings => SdtmDatasetClass::Findings,
+            SendDatasetClass::SpecialPurpose => SdtmDatasetClass::SpecialPurpose,
+            SendDatasetClass::TrialDesign => SdtmDatasetClass::TrialDesign,
+            SendDatasetClass::Relationship => SdtmDatasetClass::Relationship,
+        }
+    }
+
     /// Returns the canonical class name as it appears in SENDIG.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -87,6 +100,20 @@ pub enum SendStudyType {
 }
 
 impl SendStudyType {
+    /// Returns the study type a domain is specific to.
+    ///
+    /// Per SENDIG v3.1.1, TF (Tumor Findings) is only submitted for
+    /// carcinogenicity studies, and CV (Cardiovascular) and RE (Respiratory)
+    /// for safety pharmacology studies. Other domains are used across study
+    /// types and return `None`.
+    pub fn for_domain(code: &str) -> Option<Self> {
+        match code.to_uppercase().as_str() {
+            "TF" => Some(SendStudyType::Carcinogenicity),
+            "CV" | "RE" => Some(SendStudyType::SafetyPharmacology),
+            _ => None,
+        }
+    }
+
     /// Returns the study type name.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -3,23 +3,68 @@
 //! Loads SEND Implementation Guide v3.1.1 definitions from embedded CSV data.
 //! All data is compiled into the binary for offline operation.
 
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Cursor;
 
 use serde::Deserialize;
 
 use crate::embedded;
 use crate::error::{Result, StandardsError};
-use crate::send::{SendDatasetClass, SendDomain, SendVariable};
+use crate::paths::{self, StandardsData};
+use crate::send::{SendDatasetClass, SendDomain, SendStudyType, SendVariable};
 use crate::traits::VariableType;
 
-/// Load SEND-IG domains from embedded data.
+/// Load SEND-IG domains from embedded data, or from disk when configured
+/// (see [`paths`]).
 pub fn load() -> Result<Vec<SendDomain>> {
-    let datasets = load_datasets_from_str(embedded::SEND_IG_DATASETS)?;
-    let variables = load_variables_from_str(embedded::SEND_IG_VARIABLES)?;
+    let (datasets_csv, variables_csv) = read_csvs()?;
+    let datasets = load_datasets_from_str(&datasets_csv)?;
+    let variables = load_variables_from_str(&variables_csv, None)?;
     build_domains(&datasets, variables)
 }
 
+/// Load only the requested SEND-IG domains.
+///
+/// The files are looked up like [`load`]: the directory from the
+/// data's own environment variable, then the `TSS_STANDARDS_DIR` root,
+/// then the embedded data (see [`paths`]). Variables.csv is still parsed in
+/// full, but only rows belonging to the requested domain codes
+/// (case-insensitive) are kept.
+pub fn load_domains(codes: &[&str]) -> Result<Vec<SendDomain>> {
+    let filter: BTreeSet<String> = codes.iter().map(|c| c.trim().to_uppercase()).collect();
+    let (datasets_csv, variables_csv) = read_csvs()?;
+    let datasets = load_datasets_from_str(&datasets_csv)?;
+    let variables = load_variables_from_str(&variables_csv, Some(&filter))?;
+    build_domains(&datasets, variables)
+}
+
+/// Read Datasets.csv and Variables.csv.
+fn read_csvs() -> Result<(Cow<'static, str>, Cow<'static, str>)> {
+    Ok((
+        paths::read_or_embedded(
+            StandardsData::SendIg,
+            "Datasets.csv",
+            embedded::SEND_IG_DATASETS,
+        )?,
+        paths::read_or_embedded(
+            StandardsData::SendIg,
+            "Variables.csv",
+            embedded::SEND_IG_VARIABLES,
+        )?,
+    ))
+}
+
+/// Load the SEND-IG domains used by a study type.
+///
+/// Domains specific to another study type (e.g., TF for carcinogenicity
+/// studies) are left out; domains common to all study types are kept.
+pub fn load_for_study_type(study_type: SendStudyType) -> Result<Vec<SendDomain>> {
+    let mut domains = load()?;
+    domains.retain(|d| d.study_type.is_none_or(|t| t == study_type));
+    Ok(domains)
+}
+
 // =============================================================================
 // CSV Row Types
 // =============================================================================
@@ -54,6 +99,8 @@ struct VariableCsvRow {
     codelist_code: String,
     #[serde(rename = "Described Value Domain(s)")]
     described_value_domain: String,
+    #[serde(rename = "Role")]
+    role: String,
     #[serde(rename = "Core")]
     core: String,
 }
@@ -105,7 +152,10 @@ fn load_datasets_from_str(content: &str) -> Result<BTreeMap<String, DatasetMeta>
 }
 
 /// Load Variables.csv from embedded string content.
-fn load_variables_from_str(content: &str) -> Result<BTreeMap<String, Vec<SendVariable>>> {
+fn load_variables_from_str(
+    content: &str,
+    filter: Option<&BTreeSet<String>>,
+) -> Result<BTreeMap<String, Vec<SendVariable>>> {
     let cursor = Cursor::new(content.as_bytes());
     let mut reader = csv::ReaderBuilder::new()
         .has_headers(true)
@@ -124,6 +174,9 @@ fn load_variables_from_str(content: &str) -> Result<BTreeMap<String, Vec<SendVar
         if dataset.is_empty() || name.is_empty() {
             continue;
         }
+        if filter.is_some_and(|codes| !codes.contains(&dataset)) {
+            continue;
+        }
 
         let order = row.variable_order.trim().parse::<u32>().ok();
 
@@ -132,6 +185,7 @@ fn load_variables_from_str(content: &str) -> Result<BTreeMap<String, Vec<SendVar
             label: non_empty(&row.variable_label),
             data_type: parse_variable_type(&row.variable_type),
             length: None,
+            role: non_empty(&row.role).and_then(|v| v.parse().ok()),
             core: non_empty(&row.core).and_then(|v| v.parse().ok()),
             codelist_code: non_empty(&row.codelist_code),
             described_value_domain: non_empty(&row.described_value_domain),
@@ -166,7 +220,7 @@ fn build_domains(
             label: meta.and_then(|m| m.label.clone()),
             class: meta.and_then(|m| m.class),
             structure: meta.and_then(|m| m.structure.clone()),
-            study_type: None, // Could be inferred from context
+            study_type: SendStudyType::for_domain(name),
             variables: std::mem::take(vars),
         });
     }
@@ -212,6 +266,7 @@ fn compare_variable_order(left: &SendVariable, right: &SendVariable) -> std::cmp
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::CdiscDomain;
 
     #[test]
     fn test_load_send_ig() {
@@ -236,4 +291,26 @@ fn test_domain_count() {
             domains.len()
         );
     }
+
+    #[test]
+    fn test_load_for_study_type() {
+        let names = |domains: &[SendDomain]| -> Vec<String> {
+            domains.iter().map(|d| d.name.clone()).collect()
+        };
+
+        let carcinogenicity =
+            names(&load_for_study_type(SendStudyType::Carcinogenicity).expect("load"));
+        assert!(carcinogenicity.contains(&"TF".to_string()));
+        assert!(carcinogenicity.contains(&"BW".to_string()));
+        assert!(!carcinogenicity.contains(&"CV".to_string()));
+
+        let safety = names(&load_for_study_type(SendStudyType::SafetyPharmacology).expect("load"));
+        assert!(safety.contains(&"CV".to_string()));
+        assert!(safety.contains(&"RE".to_string()));
+        assert!(!safety.contains(&"TF".to_string()));
+
+        let bw = load_domains(&["BW"]).expect("load BW");
+        let bwtestcd = bw[0].find_variable("BWTESTCD").expect("BWTESTCD");
+        assert_eq!(bwtestcd.role, Some(crate::sdtm::VariableRole::Topic));
+    }
 }
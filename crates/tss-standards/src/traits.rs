@@ -4,6 +4,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::embedded;
+use crate::error;
+use crate::paths::{self, StandardsData};
+
 /// CDISC foundational standard identifier.
 ///
 /// Represents the three main foundational standards for regulatory data:
@@ -48,6 +52,44 @@ pub fn dir_name(&self) -> &'static str {
         }
     }
 
+    /// Returns the Implementation Guide name used in Define-XML (e.g., "SDTMIG").
+    pub fn ig_name(&self) -> &'static str {
+        match self {
+            Standard::Sdtm => "SDTMIG",
+            Standard::Adam => "ADaMIG",
+            Standard::Send => "SENDIG",
+        }
+    }
+
+    /// Returns the version of the Implementation Guide that is loaded.
+    ///
+    /// Read from the `Version` column of the guide's datasets file, so a
+    /// guide loaded from disk (see [`paths`]) reports its own version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or has no version.
+    pub fn ig_version(&self) -> error::Result<String> {
+        let (data, file, embedded) = match self {
+            Standard::Sdtm => (
+                StandardsData::SdtmIg,
+                "Datasets.csv",
+                embedded::SDTM_IG_DATASETS,
+            ),
+            Standard::Adam => (
+                StandardsData::AdamIg,
+                "DataStructures.csv",
+                embedded::ADAM_IG_DATA_STRUCTURES,
+            ),
+            Standard::Send => (
+                StandardsData::SendIg,
+                "Datasets.csv",
+                embedded::SEND_IG_DATASETS,
+            ),
+        };
+        paths::read_ig_version(data, file, embedded)
+    }
+
     /// Returns a brief description of the standard's purpose.
     pub fn description(&self) -> &'static str {
         match self {
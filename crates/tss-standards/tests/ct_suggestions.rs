@@ -0,0 +1,31 @@
+//! "Did you mean" suggestions for values missing from a codelist.
+
+use tss_standards::ct::{CtVersion, load};
+
+#[test]
+fn test_suggest_term() {
+    let registry = load(CtVersion::default(), Some("SDTM")).expect("load CT");
+
+    // C66731 (Sex): synonym match is exact, typo matches by similarity
+    assert_eq!(
+        registry.suggest_term("C66731", "MALE"),
+        Some(("M".to_string(), 1.0))
+    );
+    let (suggested, score) = registry.suggest_term("C66731", "Femael").unwrap();
+    assert_eq!(suggested, "F");
+    assert!((0.8..1.0).contains(&score));
+    assert!(registry.suggest_term("C66731", "XYZ").is_none());
+    assert!(registry.suggest_term("XNOTACODE", "MALE").is_none());
+
+    let issue = registry
+        .validate_submission_value("C66731", "MALE")
+        .expect("MALE is not a submission value");
+    assert_eq!(issue.suggestion.as_deref(), Some("M"));
+    assert!(issue.to_string().ends_with("did you mean 'M'?"));
+
+    let column = registry
+        .validate_column("C66742", &["YES", "MAYBE"])
+        .expect("resolve NY");
+    assert_eq!(column.issues[0].1.suggestion.as_deref(), Some("Y"));
+    assert_eq!(column.issues[1].1.suggestion, None);
+}
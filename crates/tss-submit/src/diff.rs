@@ -0,0 +1,342 @@
+//! Comparison of submission outputs.
+//!
+//! Regression checks need to know whether a code change altered the exported
+//! data. [`diff_xpt_folders`] reads the XPT datasets of two output folders
+//! and reports per-dataset column, row and cell differences. Only the data is
+//! compared, so file metadata such as the creation timestamp in the XPT
+//! header never shows up as a difference.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use polars::prelude::DataFrame;
+
+use crate::error::Result;
+use crate::validate::ColumnReader;
+
+/// Maximum changed cells listed per dataset in [`FolderDiff::summary`].
+const SUMMARY_CELL_LIMIT: usize = 5;
+
+/// A cell whose value differs between the two datasets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellChange {
+    /// Key of the row (see [`diff_dataframes`]).
+    pub row: String,
+    pub column: String,
+    /// Value in the first dataset.
+    pub old: String,
+    /// Value in the second dataset.
+    pub new: String,
+}
+
+/// Differences between two versions of one dataset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatasetDiff {
+    /// Dataset name (e.g., "AE").
+    pub dataset: String,
+    /// Columns only in the second dataset.
+    pub added_columns: Vec<String>,
+    /// Columns only in the first dataset.
+    pub removed_columns: Vec<String>,
+    /// Keys of rows only in the second dataset.
+    pub added_rows: Vec<String>,
+    /// Keys of rows only in the first dataset.
+    pub removed_rows: Vec<String>,
+    /// Changed cells of rows and columns in both datasets.
+    pub changed_cells: Vec<CellChange>,
+}
+
+impl DatasetDiff {
+    /// Whether the datasets hold the same data.
+    pub fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.added_rows.is_empty()
+            && self.removed_rows.is_empty()
+            && self.changed_cells.is_empty()
+    }
+}
+
+/// Differences between two output folders.
+#[derive(Debug, Clone, Default)]
+pub struct FolderDiff {
+    /// Datasets in both folders that differ, sorted by name.
+    pub datasets: Vec<DatasetDiff>,
+    /// Datasets only in the first folder.
+    pub only_in_a: Vec<String>,
+    /// Datasets only in the second folder.
+    pub only_in_b: Vec<String>,
+}
+
+impl FolderDiff {
+    /// Whether the folders differ in any dataset.
+    pub fn has_differences(&self) -> bool {
+        !self.datasets.is_empty() || !self.only_in_a.is_empty() || !self.only_in_b.is_empty()
+    }
+
+    /// Concise text summary, one line per difference.
+    ///
+    /// Callers exiting with a status should use [`has_differences`]
+    /// (non-zero when true) rather than parse this text.
+    ///
+    /// [`has_differences`]: FolderDiff::has_differences
+    pub fn summary(&self) -> String {
+        if !self.has_differences() {
+            return "No differences\n".to_string();
+        }
+        let mut text = String::new();
+        for name in &self.only_in_a {
+            let _ = writeln!(text, "{name}: only in first folder");
+        }
+        for name in &self.only_in_b {
+            let _ = writeln!(text, "{name}: only in second folder");
+        }
+        for diff in &self.datasets {
+            let _ = writeln!(
+                text,
+                "{}: {} rows added, {} rows removed, {} cells changed",
+                diff.dataset,
+                diff.added_rows.len(),
+                diff.removed_rows.len(),
+                diff.changed_cells.len()
+            );
+            if !diff.added_columns.is_empty() {
+                let _ = writeln!(text, "  columns added: {}", diff.added_columns.join(", "));
+            }
+            if !diff.removed_columns.is_empty() {
+                let _ = writeln!(
+                    text,
+                    "  columns removed: {}",
+                    diff.removed_columns.join(", ")
+                );
+            }
+            for change in diff.changed_cells.iter().take(SUMMARY_CELL_LIMIT) {
+                let _ = writeln!(
+                    text,
+                    "  {} {}: '{}' -> '{}'",
+                    change.row, change.column, change.old, change.new
+                );
+            }
+            if diff.changed_cells.len() > SUMMARY_CELL_LIMIT {
+                let _ = writeln!(
+                    text,
+                    "  ... {} more",
+                    diff.changed_cells.len() - SUMMARY_CELL_LIMIT
+                );
+            }
+        }
+        text
+    }
+}
+
+/// Compare the XPT datasets of two folders.
+///
+/// Datasets are paired by file name, ignoring case. Each pair is compared
+/// with [`diff_dataframes`]; only pairs that differ are reported.
+pub fn diff_xpt_folders(dir_a: &Path, dir_b: &Path) -> Result<FolderDiff> {
+    let files_a = xpt_files_by_name(dir_a)?;
+    let mut files_b = xpt_files_by_name(dir_b)?;
+
+    let mut result = FolderDiff::default();
+    for (name, path_a) in files_a {
+        let Some(path_b) = files_b.remove(&name) else {
+            result.only_in_a.push(name);
+            continue;
+        };
+        let (df_a, _) = tss_ingest::read_xpt(&path_a)?;
+        let (df_b, _) = tss_ingest::read_xpt(&path_b)?;
+        let diff = diff_dataframes(&name, &df_a, &df_b);
+        if !diff.is_empty() {
+            result.datasets.push(diff);
+        }
+    }
+    result.only_in_b = files_b.into_keys().collect();
+    Ok(result)
+}
+
+fn xpt_files_by_name(dir: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    Ok(tss_ingest::list_xpt_files(dir)?
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_uppercase())
+                .unwrap_or_default();
+            (name, path)
+        })
+        .collect())
+}
+
+/// Compare two versions of a dataset.
+///
+/// Rows are matched by USUBJID and `<DATASET>SEQ` when both datasets have
+/// these columns (e.g., "S-001/3"), otherwise by position (e.g., "row 4").
+/// Rows sharing a key are matched in order. Cells are compared as text, in
+/// the columns both datasets have.
+pub fn diff_dataframes(dataset: &str, a: &DataFrame, b: &DataFrame) -> DatasetDiff {
+    let reader_a = ColumnReader::new(a);
+    let reader_b = ColumnReader::new(b);
+    let names = |df: &DataFrame| -> Vec<String> {
+        df.get_column_names()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect()
+    };
+    let columns_a = names(a);
+    let columns_b = names(b);
+
+    let seq_column = format!("{}SEQ", dataset.to_uppercase());
+    let key_columns: Vec<&str> = ["USUBJID", seq_column.as_str()]
+        .into_iter()
+        .filter(|column| reader_a.has_column(column) && reader_b.has_column(column))
+        .collect();
+    let key_columns = if key_columns.len() == 2 {
+        key_columns
+    } else {
+        Vec::new()
+    };
+
+    let keys_a = row_keys(&reader_a, &key_columns);
+    let keys_b = row_keys(&reader_b, &key_columns);
+    let mut rows_b: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, key) in keys_b.iter().enumerate().rev() {
+        rows_b.entry(key.as_str()).or_default().push(idx);
+    }
+
+    let mut diff = DatasetDiff {
+        dataset: dataset.to_string(),
+        added_columns: columns_b
+            .iter()
+            .filter(|column| !columns_a.contains(column))
+            .cloned()
+            .collect(),
+        removed_columns: columns_a
+            .iter()
+            .filter(|column| !columns_b.contains(column))
+            .cloned()
+            .collect(),
+        ..DatasetDiff::default()
+    };
+    let mut matched_b = vec![false; keys_b.len()];
+    for (idx_a, key) in keys_a.iter().enumerate() {
+        let Some(idx_b) = rows_b.get_mut(key.as_str()).and_then(Vec::pop) else {
+            diff.removed_rows.push(key.clone());
+            continue;
+        };
+        matched_b[idx_b] = true;
+        for column in columns_a.iter().filter(|c| columns_b.contains(c)) {
+            let old = reader_a.get_string(column, idx_a);
+            let new = reader_b.get_string(column, idx_b);
+            if old != new {
+                diff.changed_cells.push(CellChange {
+                    row: key.clone(),
+                    column: column.clone(),
+                    old,
+                    new,
+                });
+            }
+        }
+    }
+    diff.added_rows = keys_b
+        .into_iter()
+        .zip(matched_b)
+        .filter(|(_, matched)| !matched)
+        .map(|(key, _)| key)
+        .collect();
+    diff
+}
+
+fn row_keys(reader: &ColumnReader<'_>, key_columns: &[&str]) -> Vec<String> {
+    (0..reader.height())
+        .map(|idx| {
+            if key_columns.is_empty() {
+                format!("row {}", idx + 1)
+            } else {
+                key_columns
+                    .iter()
+                    .map(|column| reader.get_string(column, idx).trim().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data")
+            .join(name)
+    }
+
+    #[test]
+    fn test_diff_identical_folders() {
+        let diff = diff_xpt_folders(&fixture("xpt"), &fixture("xpt")).unwrap();
+        assert!(!diff.has_differences());
+        assert_eq!(diff.summary(), "No differences\n");
+    }
+
+    #[test]
+    fn test_diff_changed_folder() {
+        let diff = diff_xpt_folders(&fixture("xpt"), &fixture("xpt-changed")).unwrap();
+        assert!(diff.has_differences());
+        assert_eq!(diff.only_in_a, ["ADSL"]);
+        assert!(diff.only_in_b.is_empty());
+
+        let names: Vec<&str> = diff.datasets.iter().map(|d| d.dataset.as_str()).collect();
+        assert_eq!(names, ["AE", "DM"]);
+        let ae = &diff.datasets[0];
+        assert_eq!(ae.added_rows, ["CDISC01-002/1"]);
+        assert!(ae.removed_rows.is_empty());
+        assert!(ae.changed_cells.is_empty());
+        let dm = &diff.datasets[1];
+        assert_eq!(
+            dm.changed_cells,
+            [CellChange {
+                row: "row 2".to_string(),
+                column: "SEX".to_string(),
+                old: "M".to_string(),
+                new: "F".to_string(),
+            }]
+        );
+
+        let summary = diff.summary();
+        assert!(summary.contains("ADSL: only in first folder"));
+        assert!(summary.contains("AE: 1 rows added, 0 rows removed, 0 cells changed"));
+        assert!(summary.contains("  row 2 SEX: 'M' -> 'F'"));
+    }
+
+    #[test]
+    fn test_diff_dataframes_columns_and_keys() {
+        let a = df! {
+            "USUBJID" => &["S-001", "S-001", "S-002"],
+            "LBSEQ" => &[1i64, 2, 1],
+            "LBORRES" => &["5.0", "6.1", "4.2"],
+            "LBNAM" => &["LAB A", "LAB A", "LAB A"],
+        }
+        .unwrap();
+        // Rows reordered, one removed, one changed, LBNAM replaced by LBSTAT
+        let b = df! {
+            "USUBJID" => &["S-002", "S-001"],
+            "LBSEQ" => &[1i64, 1],
+            "LBORRES" => &["4.2", "5.5"],
+            "LBSTAT" => &["", ""],
+        }
+        .unwrap();
+
+        let diff = diff_dataframes("LB", &a, &b);
+        assert_eq!(diff.added_columns, ["LBSTAT"]);
+        assert_eq!(diff.removed_columns, ["LBNAM"]);
+        assert_eq!(diff.removed_rows, ["S-001/2"]);
+        assert!(diff.added_rows.is_empty());
+        assert_eq!(diff.changed_cells.len(), 1);
+        assert_eq!(diff.changed_cells[0].row, "S-001/1");
+        assert_eq!(diff.changed_cells[0].old, "5.0");
+        assert_eq!(diff.changed_cells[0].new, "5.5");
+    }
+}
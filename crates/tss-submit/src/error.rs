@@ -52,6 +52,28 @@ pub enum SubmitError {
         variable: String,
     },
 
+    /// Two merged mapping configurations map the same variable differently.
+    #[error("Conflicting mappings for '{variable}': '{existing}' and '{incoming}'")]
+    MappingConflict {
+        /// The variable mapped by both configurations.
+        variable: String,
+        /// Source column from the first configuration.
+        existing: String,
+        /// Source column from the second configuration.
+        incoming: String,
+    },
+
+    /// Merged mapping configurations belong to different domains or studies.
+    #[error("Cannot merge mappings with different {field}: '{first}' and '{second}'")]
+    MappingConfigMismatch {
+        /// The differing field (`domain_code` or `study_id`).
+        field: &'static str,
+        /// Value in the first configuration.
+        first: String,
+        /// Value in the second configuration.
+        second: String,
+    },
+
     // =========================================================================
     // NORMALIZATION ERRORS
     // =========================================================================
@@ -78,6 +100,19 @@ pub enum SubmitError {
         message: String,
     },
 
+    /// Two records of a long-to-wide pivot fill the same cell differently.
+    #[error("Conflicting values for '{column}' of '{index}': '{existing}' and '{incoming}'")]
+    PivotConflict {
+        /// Index value (e.g. the USUBJID) of the row.
+        index: String,
+        /// Output column the records map to.
+        column: String,
+        /// Value from the earlier record.
+        existing: String,
+        /// Value from the later record.
+        incoming: String,
+    },
+
     // =========================================================================
     // EXPORT ERRORS
     // =========================================================================
@@ -117,6 +152,13 @@ pub enum SubmitError {
         variable: String,
     },
 
+    /// Value cannot be written as a SAS string literal.
+    #[error("Cannot write SAS string literal containing a line break: {value:?}")]
+    SasLiteral {
+        /// The offending value.
+        value: String,
+    },
+
     /// XPT dataset validation failed.
     #[error("XPT validation failed for '{dataset}': {message}")]
     XptValidation {
@@ -126,6 +168,52 @@ pub enum SubmitError {
         message: String,
     },
 
+    /// XPT dataset has more variables than the configured limit.
+    #[error("XPT dataset '{dataset}' has {count} variables, more than the limit of {max}")]
+    XptTooManyVariables {
+        /// Dataset name.
+        dataset: String,
+        /// Number of variables in the dataset.
+        count: usize,
+        /// Configured maximum.
+        max: usize,
+    },
+
+    /// XPT observation length exceeds the configured limit.
+    #[error(
+        "XPT dataset '{dataset}' has a record length of {length} bytes, more than the limit of {max}"
+    )]
+    XptRecordTooLong {
+        /// Dataset name.
+        dataset: String,
+        /// Sum of the variable lengths in bytes.
+        length: usize,
+        /// Configured maximum.
+        max: usize,
+    },
+
+    /// Character value longer than its declared XPT length.
+    #[error(
+        "XPT dataset '{dataset}': {variable} value in row {row} is {length} bytes, longer than its length of {max}"
+    )]
+    XptValueTooLong {
+        /// Dataset name.
+        dataset: String,
+        /// Variable name.
+        variable: String,
+        /// Zero-based row index.
+        row: usize,
+        /// Length of the value in bytes.
+        length: usize,
+        /// Declared length of the variable.
+        max: usize,
+    },
+
+    /// Export stopped because its [`CancellationToken`](crate::CancellationToken)
+    /// was cancelled.
+    #[error("Export cancelled")]
+    Cancelled,
+
     // =========================================================================
     // WRAPPED ERRORS
     // =========================================================================
@@ -144,6 +232,18 @@ pub enum SubmitError {
     /// Standards loading error.
     #[error("Standards error: {0}")]
     Standards(#[from] tss_standards::StandardsError),
+
+    /// Source data ingestion error.
+    #[error("Ingest error: {0}")]
+    Ingest(#[from] tss_ingest::IngestError),
+
+    /// Normalization pipeline error.
+    #[error("Normalization error: {0}")]
+    Normalization(#[from] crate::normalize::NormalizationError),
+
+    /// Validation rule overlay error.
+    #[error("Rule error: {0}")]
+    Rules(#[from] crate::validate::RuleError),
 }
 
 /// Result type alias for submit operations.
@@ -175,6 +275,11 @@ pub fn is_recoverable(&self) -> bool {
                 | Self::ParseError { .. }
                 | Self::MissingContext { .. }
                 | Self::InvalidConfig { .. }
+                | Self::PivotConflict { .. }
+                | Self::SasLiteral { .. }
+                | Self::XptTooManyVariables { .. }
+                | Self::XptRecordTooLong { .. }
+                | Self::XptValueTooLong { .. }
         )
     }
 
@@ -200,10 +305,25 @@ pub fn suggestion(&self) -> Option<&'static str> {
                 Some("Check the source data format matches the expected variable type.")
             }
             Self::MissingContext { .. } => Some("Ensure all required reference data is loaded."),
+            Self::PivotConflict { .. } => {
+                Some("Filter the records to one per cell, or keep the first or last value.")
+            }
             Self::WriteError { .. } => Some("Check file permissions and available disk space."),
             Self::MissingDomain { .. } => {
                 Some("Ensure the domain definition is loaded from standards.")
             }
+            Self::XptTooManyVariables { .. } => {
+                Some("Omit unused Permissible variables or split the dataset.")
+            }
+            Self::XptRecordTooLong { .. } => {
+                Some("Shorten character variable lengths or omit unused variables.")
+            }
+            Self::XptValueTooLong { .. } => {
+                Some("Shorten the value or increase the variable length in the domain definition.")
+            }
+            Self::SasLiteral { .. } => {
+                Some("Remove line breaks from the study identifier, labels and column names.")
+            }
             _ => None,
         }
     }
@@ -0,0 +1,572 @@
+//! Define-XML output for ADaM analysis datasets.
+//!
+//! ADaM datasets are described with their ADaM class (from
+//! [`AdamDatasetType`]) instead of an SDTM domain, and variable origins come
+//! from [`AdamVariableSource`] rather than from the presence of data. The
+//! document is rendered by the same writer as SDTM Define-XML.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use tss_standards::{AdamDataset, AdamDatasetType, AdamVariable, AdamVariableSource};
+use tss_standards::{CoreDesignation, TerminologyRegistry, VariableType};
+
+use super::common::{column_length, distinct_values, has_collected_data, normalize_study_id};
+use super::define_xml::{
+    CodeListSpec, CtStandard, DefineModel, DefineSpec, ItemDefSpec, ItemGroupSpec, ItemOrigin,
+    ItemRefSpec, ResultDisplaySpec, resolve_codelist, sanitize_oid_component, write_define_spec,
+};
+use super::types::DomainFrame;
+use crate::error::{Result, SubmitError};
+use crate::validate::matches_template;
+
+/// Options for ADaM Define-XML output.
+#[derive(Debug, Clone)]
+pub struct AdamDefineOptions {
+    /// Implementation Guide version (e.g., "1.3" for ADaMIG 1.3).
+    pub ig_version: String,
+    pub context: String,
+    /// Analysis Results Metadata displays; none are written when empty.
+    pub result_displays: Vec<ResultDisplaySpec>,
+    /// CreationDateTime written to the file instead of the current time,
+    /// for reproducible output.
+    pub creation_datetime: Option<DateTime<Utc>>,
+}
+
+impl AdamDefineOptions {
+    pub fn new(ig_version: impl Into<String>, context: impl Into<String>) -> Self {
+        Self {
+            ig_version: ig_version.into(),
+            context: context.into(),
+            result_displays: Vec::new(),
+            creation_datetime: None,
+        }
+    }
+
+    /// Stamp the document with a fixed CreationDateTime.
+    #[must_use]
+    pub fn with_creation_datetime(mut self, creation_datetime: DateTime<Utc>) -> Self {
+        self.creation_datetime = Some(creation_datetime);
+        self
+    }
+
+    /// Add an Analysis Results Metadata display.
+    #[must_use]
+    pub fn with_result_display(mut self, display: ResultDisplaySpec) -> Self {
+        self.result_displays.push(display);
+        self
+    }
+}
+
+/// Write Define-XML output for ADaM datasets.
+pub fn write_adam_define_xml(
+    output_path: &Path,
+    study_id: &str,
+    datasets: &[AdamDataset],
+    frames: &[DomainFrame],
+    ct_registry: &TerminologyRegistry,
+    options: &AdamDefineOptions,
+) -> Result<()> {
+    let spec = build_adam_define_spec(study_id, datasets, frames, ct_registry, options)?;
+    write_define_spec(output_path, &spec)
+}
+
+/// Build the Define-XML metadata for ADaM datasets.
+///
+/// Each frame is matched to the dataset of the same name. Templated ADaMIG
+/// variables are described once per matching column (TRT01P and TRT02P for
+/// TRTxxP); variables not in the frame are left out. The class and keys come
+/// from the dataset type, and the origin of each variable from its source:
+/// SDTM and predecessor variables are Predecessor, assigned variables
+/// Assigned, and all others Derived. Codelists are resolved in `ct_registry`.
+pub fn build_adam_define_spec(
+    study_id: &str,
+    datasets: &[AdamDataset],
+    frames: &[DomainFrame],
+    ct_registry: &TerminologyRegistry,
+    options: &AdamDefineOptions,
+) -> Result<DefineSpec> {
+    if frames.is_empty() {
+        return Err(SubmitError::NoDatasets {
+            format: "Define-XML".to_string(),
+        });
+    }
+    let study_id = normalize_study_id(study_id);
+
+    let mut entries: Vec<(&AdamDataset, &DomainFrame)> = Vec::new();
+    for frame in frames {
+        let name = frame.dataset_name().to_uppercase();
+        let dataset = datasets
+            .iter()
+            .find(|dataset| dataset.name.eq_ignore_ascii_case(&name))
+            .ok_or_else(|| SubmitError::MissingDomain {
+                domain: name.clone(),
+            })?;
+        entries.push((dataset, frame));
+    }
+    entries.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+
+    let mut item_defs: BTreeMap<String, ItemDefSpec> = BTreeMap::new();
+    let mut code_lists: BTreeMap<String, CodeListSpec> = BTreeMap::new();
+    let mut ct_standards: BTreeMap<String, CtStandard> = BTreeMap::new();
+    let mut item_groups = Vec::new();
+
+    for (dataset, frame) in &entries {
+        let dataset_name = frame.dataset_name();
+        frame.warn_dataset_name();
+        let variables = ordered_variables(dataset, frame);
+
+        for (variable, column) in &variables {
+            let oid = item_oid(&dataset_name, column);
+            let length = match variable.data_type {
+                VariableType::Char => Some(column_length(
+                    column,
+                    variable.data_type,
+                    variable.length,
+                    &frame.data,
+                )?),
+                VariableType::Num => None,
+            };
+            let observed = match variable.data_type {
+                VariableType::Char => distinct_values(&frame.data, column),
+                VariableType::Num => BTreeSet::new(),
+            };
+            let codelist_oid = resolve_codelist(
+                &dataset.name,
+                column,
+                variable.codelist_code.as_deref(),
+                &observed,
+                ct_registry,
+                &mut code_lists,
+                &mut ct_standards,
+            )?;
+            item_defs.insert(
+                oid.clone(),
+                ItemDefSpec {
+                    oid,
+                    name: column.clone(),
+                    label: variable
+                        .label
+                        .as_deref()
+                        .map(|label| instantiate_label(&variable.name, column, label)),
+                    data_type: variable.data_type,
+                    length,
+                    codelist_oid,
+                    core: variable.core.map(|c| c.as_code().to_string()),
+                    has_data: has_collected_data(&frame.data, column),
+                    origin_override: Some(adam_origin(variable.source.as_ref())),
+                },
+            );
+        }
+
+        let keys = key_variables(dataset.dataset_type);
+        let mut key_sequence = 0usize;
+        let items = variables
+            .iter()
+            .enumerate()
+            .map(|(idx, (variable, column))| ItemRefSpec {
+                item_oid: item_oid(&dataset_name, column),
+                order_number: idx + 1,
+                mandatory: variable.core == Some(CoreDesignation::Required),
+                key_sequence: keys.contains(&column.as_str()).then(|| {
+                    key_sequence += 1;
+                    key_sequence
+                }),
+            })
+            .collect();
+        item_groups.push(ItemGroupSpec {
+            oid: format!("IG.{}", sanitize_oid_component(&dataset_name)),
+            domain: dataset.name.clone(),
+            name: dataset_name,
+            label: dataset.label.clone(),
+            class: Some(define_class(dataset.dataset_type).to_string()),
+            structure: dataset.structure.clone(),
+            is_reference_data: false,
+            items,
+        });
+    }
+
+    Ok(DefineSpec {
+        study_id,
+        ig_version: options.ig_version.clone(),
+        context: options.context.clone(),
+        model: DefineModel::Adam,
+        creation_datetime: options.creation_datetime,
+        result_displays: options.result_displays.clone(),
+        item_groups,
+        item_defs,
+        code_lists,
+        ct_standards,
+    })
+}
+
+/// Variables of `dataset` present in the frame, each with its column name,
+/// ordered by their `order` and then by declaration.
+///
+/// A templated variable (e.g., TRTxxP) yields one entry per matching column,
+/// in frame order. Frame columns no variable describes are logged and left
+/// out.
+fn ordered_variables<'a>(
+    dataset: &'a AdamDataset,
+    frame: &DomainFrame,
+) -> Vec<(&'a AdamVariable, String)> {
+    let columns: Vec<String> = frame
+        .data
+        .get_column_names()
+        .into_iter()
+        .map(ToString::to_string)
+        .collect();
+    let mut described: BTreeSet<&str> = BTreeSet::new();
+    let mut variables: Vec<(&AdamVariable, String)> = Vec::new();
+    for variable in &dataset.variables {
+        for column in &columns {
+            if !described.contains(column.as_str()) && matches_template(&variable.name, column) {
+                described.insert(column);
+                variables.push((variable, column.clone()));
+            }
+        }
+    }
+    for column in columns.iter().filter(|c| !described.contains(c.as_str())) {
+        tracing::warn!(
+            dataset = %dataset.name,
+            column = %column,
+            "Column is not an ADaMIG variable of the dataset - left out of Define-XML"
+        );
+    }
+    variables.sort_by_key(|(variable, _)| variable.order.unwrap_or(u32::MAX));
+    variables
+}
+
+/// Label of a templated variable for one of its columns.
+///
+/// Each placeholder run of the template (e.g., "xx" in TRTxxP) is replaced
+/// in the label by the digits of the column ("Period xx" becomes
+/// "Period 01" for TRT01P).
+fn instantiate_label(template: &str, column: &str, label: &str) -> String {
+    let mut label = label.to_string();
+    let mut chars = template.chars().zip(column.chars()).peekable();
+    while let Some((t, c)) = chars.next() {
+        if !t.is_ascii_lowercase() {
+            continue;
+        }
+        let mut placeholder = String::from(t);
+        let mut digits = String::from(c);
+        while let Some(&(t, c)) = chars.peek()
+            && t.is_ascii_lowercase()
+        {
+            placeholder.push(t);
+            digits.push(c);
+            chars.next();
+        }
+        label = label.replacen(&placeholder, &digits, 1);
+    }
+    label
+}
+
+/// Key variables of an ADaM dataset type, in key order.
+fn key_variables(dataset_type: AdamDatasetType) -> &'static [&'static str] {
+    match dataset_type {
+        AdamDatasetType::Adsl | AdamDatasetType::Other => &["STUDYID", "USUBJID"],
+        AdamDatasetType::Bds => &["STUDYID", "USUBJID", "PARAMCD", "AVISITN"],
+        AdamDatasetType::Tte => &["STUDYID", "USUBJID", "PARAMCD"],
+        AdamDatasetType::Occds => &["STUDYID", "USUBJID", "ASEQ"],
+    }
+}
+
+fn item_oid(dataset_name: &str, variable: &str) -> String {
+    format!(
+        "IT.{}.{}",
+        sanitize_oid_component(dataset_name),
+        sanitize_oid_component(variable)
+    )
+}
+
+/// Define-XML class of an ADaM dataset type.
+fn define_class(dataset_type: AdamDatasetType) -> &'static str {
+    match dataset_type {
+        AdamDatasetType::Adsl => "SUBJECT LEVEL ANALYSIS DATASET",
+        AdamDatasetType::Bds | AdamDatasetType::Tte => "BASIC DATA STRUCTURE",
+        AdamDatasetType::Occds => "OCCURRENCE DATA STRUCTURE",
+        AdamDatasetType::Other => "ADAM OTHER",
+    }
+}
+
+/// Origin of an ADaM variable from its source.
+///
+/// Variables without a documented source are assumed to be derived.
+fn adam_origin(source: Option<&AdamVariableSource>) -> ItemOrigin {
+    match source {
+        Some(AdamVariableSource::Sdtm(variable) | AdamVariableSource::Predecessor(variable)) => {
+            ItemOrigin::Predecessor(variable.clone())
+        }
+        Some(AdamVariableSource::Assigned) => ItemOrigin::Assigned,
+        Some(AdamVariableSource::Derived(_)) | None => ItemOrigin::Derived,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use polars::prelude::*;
+    use tss_standards::{CtVersion, load_ct};
+
+    const GOLDEN: &str = include_str!("../../tests/data/adsl_define.xml");
+
+    fn variable(
+        name: &str,
+        label: &str,
+        data_type: VariableType,
+        core: CoreDesignation,
+        codelist_code: Option<&str>,
+        source: Option<AdamVariableSource>,
+    ) -> AdamVariable {
+        AdamVariable {
+            name: name.to_string(),
+            label: Some(label.to_string()),
+            data_type,
+            length: None,
+            core: Some(core),
+            codelist_code: codelist_code.map(str::to_string),
+            described_value_domain: None,
+            source,
+            order: None,
+        }
+    }
+
+    fn adsl() -> AdamDataset {
+        use CoreDesignation::{Expected, Required};
+        use VariableType::{Char, Num};
+        AdamDataset {
+            name: "ADSL".to_string(),
+            label: Some("Subject-Level Analysis Dataset".to_string()),
+            dataset_type: AdamDatasetType::Adsl,
+            structure: Some("One record per subject".to_string()),
+            variables: vec![
+                variable(
+                    "STUDYID",
+                    "Study Identifier",
+                    Char,
+                    Required,
+                    None,
+                    Some(AdamVariableSource::Sdtm("DM.STUDYID".to_string())),
+                ),
+                variable(
+                    "USUBJID",
+                    "Unique Subject Identifier",
+                    Char,
+                    Required,
+                    None,
+                    Some(AdamVariableSource::Sdtm("DM.USUBJID".to_string())),
+                ),
+                variable(
+                    "AGE",
+                    "Age",
+                    Num,
+                    Required,
+                    None,
+                    Some(AdamVariableSource::Sdtm("DM.AGE".to_string())),
+                ),
+                variable(
+                    "SEX",
+                    "Sex",
+                    Char,
+                    Required,
+                    Some("C66731"),
+                    Some(AdamVariableSource::Sdtm("DM.SEX".to_string())),
+                ),
+                variable(
+                    "SAFFL",
+                    "Safety Population Flag",
+                    Char,
+                    Expected,
+                    None,
+                    Some(AdamVariableSource::Derived(
+                        "Y if the subject received any study drug".to_string(),
+                    )),
+                ),
+                variable(
+                    "TRT01P",
+                    "Planned Treatment for Period 01",
+                    Char,
+                    Required,
+                    None,
+                    Some(AdamVariableSource::Assigned),
+                ),
+                variable(
+                    "TRTDURD",
+                    "Total Treatment Duration (Days)",
+                    Num,
+                    Expected,
+                    None,
+                    None,
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_write_adam_define_xml_golden() {
+        let data = df! {
+            "STUDYID" => &["STUDY01", "STUDY01"],
+            "USUBJID" => &["STUDY01-001", "STUDY01-002"],
+            "AGE" => &[34.0, 58.0],
+            "SEX" => &["F", "M"],
+            "SAFFL" => &["Y", "N"],
+            "TRT01P" => &["PLACEBO", "DRUG A 10 MG"],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("ADSL", data)];
+        let options = AdamDefineOptions::new("1.3", "Submission")
+            .with_creation_datetime(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("define.xml");
+        let ct = load_ct(CtVersion::default(), Some("ADaM")).unwrap();
+        write_adam_define_xml(&path, "STUDY01", &[adsl()], &frames, &ct, &options).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(xml, GOLDEN);
+    }
+
+    #[test]
+    fn test_adam_define_result_displays() {
+        let data = df! { "STUDYID" => &["STUDY01"], "USUBJID" => &["STUDY01-001"] }.unwrap();
+        let frames = [DomainFrame::new("ADSL", data)];
+        let options =
+            AdamDefineOptions::new("1.3", "Submission").with_result_display(ResultDisplaySpec {
+                name: "Table 14.1.1".to_string(),
+                description: Some("Demographics".to_string()),
+            });
+
+        let spec = build_adam_define_spec(
+            "STUDY01",
+            &[adsl()],
+            &frames,
+            &TerminologyRegistry::default(),
+            &options,
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("define.xml");
+        write_define_spec(&path, &spec).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        assert!(xml.contains("xmlns:arm=\"http://www.cdisc.org/ns/arm/v1.0\""));
+        assert!(xml.contains("<arm:ResultDisplay OID=\"RD.Table_14.1.1\" Name=\"Table 14.1.1\">"));
+    }
+
+    #[test]
+    fn test_adam_define_unknown_dataset() {
+        let data = df! { "USUBJID" => &["STUDY01-001"] }.unwrap();
+        let frames = [DomainFrame::new("ADXX", data)];
+        let options = AdamDefineOptions::new("1.3", "Submission");
+        assert!(matches!(
+            build_adam_define_spec(
+                "STUDY01",
+                &[adsl()],
+                &frames,
+                &TerminologyRegistry::default(),
+                &options
+            ),
+            Err(SubmitError::MissingDomain { domain }) if domain == "ADXX"
+        ));
+    }
+
+    #[test]
+    fn test_adam_define_templated_variables() {
+        let mut dataset = adsl();
+        dataset.variables.push(variable(
+            "TRTxxA",
+            "Actual Treatment for Period xx",
+            VariableType::Char,
+            CoreDesignation::Permissible,
+            None,
+            None,
+        ));
+        let data = df! {
+            "STUDYID" => &["STUDY01"],
+            "USUBJID" => &["STUDY01-001"],
+            "TRT01A" => &["PLACEBO"],
+            "TRT02A" => &["DRUG A"],
+            "EXTRA" => &["X"],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("ADSL", data)];
+        let options = AdamDefineOptions::new("1.3", "Submission");
+
+        let spec = build_adam_define_spec(
+            "STUDY01",
+            &[dataset],
+            &frames,
+            &TerminologyRegistry::default(),
+            &options,
+        )
+        .unwrap();
+        let item = &spec.item_defs["IT.ADSL.TRT02A"];
+        assert_eq!(item.name, "TRT02A");
+        assert_eq!(
+            item.label.as_deref(),
+            Some("Actual Treatment for Period 02")
+        );
+        assert!(spec.item_defs.contains_key("IT.ADSL.TRT01A"));
+        assert!(!spec.item_defs.contains_key("IT.ADSL.EXTRA"));
+    }
+
+    #[test]
+    fn test_adam_define_keys_by_class() {
+        let data = df! {
+            "STUDYID" => &["STUDY01"],
+            "USUBJID" => &["STUDY01-001"],
+            "PARAMCD" => &["SYSBP"],
+            "AVISITN" => &[1.0],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("ADVS", data.clone())];
+        let key_sequence = |dataset: &AdamDataset, frames: &[DomainFrame]| {
+            let spec = build_adam_define_spec(
+                "STUDY01",
+                std::slice::from_ref(dataset),
+                frames,
+                &TerminologyRegistry::default(),
+                &AdamDefineOptions::new("1.3", "Submission"),
+            )
+            .unwrap();
+            spec.item_groups[0]
+                .items
+                .iter()
+                .filter_map(|item| Some((item.item_oid.clone(), item.key_sequence?)))
+                .collect::<Vec<_>>()
+        };
+        let with_columns = |name: &str, dataset_type: AdamDatasetType| {
+            let mut dataset = adsl();
+            dataset.name = name.to_string();
+            dataset.dataset_type = dataset_type;
+            for column in ["PARAMCD", "AVISITN"] {
+                dataset.variables.push(variable(
+                    column,
+                    column,
+                    VariableType::Char,
+                    CoreDesignation::Expected,
+                    None,
+                    None,
+                ));
+            }
+            dataset
+        };
+
+        let bds = key_sequence(&with_columns("ADVS", AdamDatasetType::Bds), &frames);
+        assert_eq!(bds.len(), 4);
+        assert_eq!(bds[3], ("IT.ADVS.AVISITN".to_string(), 4));
+
+        // ADSL has no parameter or visit keys
+        let frames = [DomainFrame::new("ADSL", data)];
+        let adsl_keys = key_sequence(&with_columns("ADSL", AdamDatasetType::Adsl), &frames);
+        assert_eq!(
+            adsl_keys,
+            [
+                ("IT.ADSL.STUDYID".to_string(), 1),
+                ("IT.ADSL.USUBJID".to_string(), 2)
+            ]
+        );
+    }
+}
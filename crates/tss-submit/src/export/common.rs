@@ -1,5 +1,7 @@
 //! Shared utilities and types for SDTM report generation.
 
+use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -9,7 +11,7 @@
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 
 use crate::error::{Result, SubmitError};
-use tss_standards::any_to_string;
+use tss_standards::any_to_string_for_output;
 use tss_standards::{CoreDesignation, SdtmDomain, SdtmVariable, VariableRole, VariableType};
 
 /// SAS numeric length constant (8 bytes).
@@ -24,6 +26,9 @@
 /// Define-XML namespace.
 pub const DEFINE_XML_NS: &str = "http://www.cdisc.org/ns/def/v2.1";
 
+/// Analysis Results Metadata namespace.
+pub const ARM_NS: &str = "http://www.cdisc.org/ns/arm/v1.0";
+
 /// XLink namespace.
 pub const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
 
@@ -79,29 +84,84 @@ pub fn is_reference_domain(domain: &SdtmDomain) -> bool {
     )
 }
 
+/// How export handles character values longer than the variable's
+/// declared length.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overlength {
+    /// Keep the declared length; longer values are truncated.
+    #[default]
+    Truncate,
+    /// Keep the declared length as a hard cap and fail the export.
+    Error,
+    /// Grow the length to fit the longest value.
+    Widen,
+}
+
 /// Calculate variable length from data.
 pub fn variable_length(variable: &SdtmVariable, df: &DataFrame) -> Result<u16> {
-    if let Some(length) = variable.length {
+    column_length(&variable.name, variable.data_type, variable.length, df)
+}
+
+/// Calculate variable length from data under an overlength policy.
+///
+/// Only [`Overlength::Widen`] changes the result: a declared length is
+/// raised to the longest value. Under [`Overlength::Error`] the declared
+/// length is a hard cap; the writer reports values exceeding it.
+pub fn variable_length_with_policy(
+    variable: &SdtmVariable,
+    df: &DataFrame,
+    policy: Overlength,
+) -> Result<u16> {
+    column_length_with_policy(
+        &variable.name,
+        variable.data_type,
+        variable.length,
+        df,
+        policy,
+    )
+}
+
+/// Calculate the length of a column under an overlength policy.
+pub(crate) fn column_length_with_policy(
+    name: &str,
+    data_type: VariableType,
+    declared: Option<u32>,
+    df: &DataFrame,
+    policy: Overlength,
+) -> Result<u16> {
+    let length = column_length(name, data_type, declared, df)?;
+    if policy == Overlength::Widen && declared.is_some() && data_type == VariableType::Char {
+        return Ok(length.max(column_length(name, data_type, None, df)?));
+    }
+    Ok(length)
+}
+
+/// Calculate the length of a column from its declared length or its data.
+pub(crate) fn column_length(
+    name: &str,
+    data_type: VariableType,
+    declared: Option<u32>,
+    df: &DataFrame,
+) -> Result<u16> {
+    if let Some(length) = declared {
         if length == 0 {
             return Err(SubmitError::InvalidConfig {
-                message: format!("variable {} has zero length", variable.name),
+                message: format!("variable {name} has zero length"),
             });
         }
         return Ok(length.min(u16::MAX as u32) as u16);
     }
-    match variable.data_type {
+    match data_type {
         VariableType::Num => Ok(SAS_NUMERIC_LEN),
         VariableType::Char => {
             // Treat Char as variable-length strings
-            let series =
-                df.column(variable.name.as_str())
-                    .map_err(|_| SubmitError::ColumnNotFound {
-                        column: variable.name.clone(),
-                    })?;
+            let series = df.column(name).map_err(|_| SubmitError::ColumnNotFound {
+                column: name.to_string(),
+            })?;
             let mut max_len = 0usize;
             for idx in 0..df.height() {
                 let value = series.get(idx).unwrap_or(AnyValue::Null);
-                let text = any_to_string(value);
+                let text = any_to_string_for_output(value);
                 let len = text.trim_end().len();
                 if len > max_len {
                     max_len = len;
@@ -110,7 +170,7 @@ pub fn variable_length(variable: &SdtmVariable, df: &DataFrame) -> Result<u16> {
             let len = max_len.max(1);
             if len > u16::MAX as usize {
                 return Err(SubmitError::InvalidConfig {
-                    message: format!("variable {} length {} exceeds maximum", variable.name, len),
+                    message: format!("variable {name} length {len} exceeds maximum"),
                 });
             }
             Ok(len as u16)
@@ -158,6 +218,20 @@ pub fn has_collected_data(df: &DataFrame, variable_name: &str) -> bool {
     false
 }
 
+/// Distinct non-empty values of a column, trimmed.
+///
+/// Returns an empty set if the column does not exist.
+pub fn distinct_values(df: &DataFrame, variable_name: &str) -> BTreeSet<String> {
+    let Ok(series) = df.column(variable_name) else {
+        return BTreeSet::new();
+    };
+    (0..df.height())
+        .filter_map(|idx| series.get(idx).ok())
+        .map(|value| any_to_string_for_output(value).trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
 /// Write a simple text element.
 pub fn write_text_element<W: Write>(writer: &mut Writer<W>, name: &str, text: &str) -> Result<()> {
     writer.write_event(Event::Start(BytesStart::new(name)))?;
@@ -182,6 +256,36 @@ pub fn write_translated_text<W: Write>(
     Ok(())
 }
 
+/// Check if a character is allowed in an XML 1.0 document.
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}')
+        || c >= '\u{10000}'
+}
+
+/// Remove characters that XML 1.0 does not allow.
+///
+/// Control characters other than tab, line feed and carriage return (e.g.,
+/// U+0000-U+0008) cannot appear in XML even escaped, so a file containing
+/// them is rejected by parsers.
+pub fn strip_xml_illegal_chars(value: &str) -> Cow<'_, str> {
+    if value.chars().all(is_xml_char) {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(value.chars().filter(|&c| is_xml_char(c)).collect())
+    }
+}
+
+/// Escape a value for an XML attribute, keeping its whitespace.
+///
+/// Parsers normalize tab, line feed and carriage return in attribute values
+/// to spaces, so these are written as character references.
+pub fn escape_attribute_value(value: &str) -> String {
+    quick_xml::escape::escape(value)
+        .replace('\t', "&#9;")
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+}
+
 /// Extension trait for VariableType to get Define-XML type.
 pub trait VariableTypeExt {
     fn as_define_type(&self) -> &'static str;
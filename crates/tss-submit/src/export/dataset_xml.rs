@@ -1,22 +1,27 @@
 //! Dataset-XML output generation.
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 
-use chrono::{SecondsFormat, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use polars::prelude::AnyValue;
 use quick_xml::Writer;
-use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::name::QName;
 
 use super::types::{DomainFrame, domain_map_by_code};
 use crate::error::{Result, SubmitError};
-use tss_standards::SdtmDomain;
 use tss_standards::any_to_string_non_empty;
+use tss_standards::{ColumnIndex, SdtmDomain};
 
 use super::common::{
     DATASET_XML_NS, DATASET_XML_VERSION, DEFINE_XML_VERSION, ODM_NS, XLINK_NS, ensure_output_dir,
-    ensure_parent_dir, is_reference_domain, normalize_study_id,
+    ensure_parent_dir, escape_attribute_value, is_reference_domain, normalize_study_id,
+    strip_xml_illegal_chars,
 };
 
 /// Options for Dataset-XML output.
@@ -25,6 +30,13 @@ pub struct DatasetXmlOptions {
     pub dataset_name: Option<String>,
     pub metadata_version_oid: Option<String>,
     pub is_reference_data: Option<bool>,
+    /// CreationDateTime written to the file instead of the current time,
+    /// for reproducible output.
+    pub creation_datetime: Option<DateTime<Utc>>,
+    /// Values longer than this many characters are written as ItemData
+    /// element text instead of the Value attribute. Dataset-XML 1.0 readers
+    /// expect the attribute, so leave unset unless the consumer accepts text.
+    pub element_text_threshold: Option<usize>,
 }
 
 /// Write Dataset-XML outputs for all domains.
@@ -35,6 +47,34 @@ pub fn write_dataset_xml_outputs(
     study_id: &str,
     ig_version: &str,
 ) -> Result<Vec<PathBuf>> {
+    write_dataset_xml_outputs_with_reference_data(
+        output_dir,
+        domains,
+        frames,
+        study_id,
+        ig_version,
+        &BTreeMap::new(),
+    )
+}
+
+/// Write Dataset-XML outputs for all domains, overriding the reference data
+/// classification per domain.
+///
+/// `reference_data` maps a domain code to whether its datasets are written as
+/// ReferenceData (`true`) or ClinicalData (`false`). Domains not in the map
+/// are classified by [`is_reference_domain`].
+pub fn write_dataset_xml_outputs_with_reference_data(
+    output_dir: &Path,
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+    study_id: &str,
+    ig_version: &str,
+    reference_data: &BTreeMap<String, bool>,
+) -> Result<Vec<PathBuf>> {
+    let reference_data: BTreeMap<String, bool> = reference_data
+        .iter()
+        .map(|(code, is_reference)| (code.to_uppercase(), *is_reference))
+        .collect();
     let domain_lookup = domain_map_by_code(domains);
     let mut frames_sorted: Vec<&DomainFrame> = frames.iter().collect();
     frames_sorted.sort_by(|a, b| a.domain_code.cmp(&b.domain_code));
@@ -51,10 +91,12 @@ pub fn write_dataset_xml_outputs(
             })?;
         // Use frame's dataset name (from metadata) for split domains
         let output_dataset_name = frame.dataset_name();
+        frame.warn_dataset_name();
         let disk_name = output_dataset_name.to_lowercase();
         let path = xml_dir.join(format!("{disk_name}.xml"));
         let options = DatasetXmlOptions {
             dataset_name: Some(output_dataset_name),
+            is_reference_data: reference_data.get(&code).copied(),
             ..Default::default()
         };
         write_dataset_xml(&path, domain, frame, study_id, ig_version, Some(&options))?;
@@ -92,7 +134,10 @@ pub fn write_dataset_xml(
         "ClinicalData"
     };
 
-    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    let timestamp = options
+        .creation_datetime
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
     ensure_parent_dir(output_path)?;
     let file = File::create(output_path).map_err(|e| {
         SubmitError::write_error("Dataset-XML", output_path.display().to_string(), e)
@@ -122,23 +167,25 @@ pub fn write_dataset_xml(
 
     let df = &frame.data;
 
-    // Filter to only variables that exist in the DataFrame
+    // Filter to only variables that exist in the DataFrame, in any case
+    let column_index = ColumnIndex::new(df);
     let existing_vars: Vec<_> = domain
         .variables
         .iter()
-        .filter(|v| df.column(&v.name).is_ok())
+        .filter_map(|v| Some((v, column_index.get(&v.name)?)))
         .collect();
 
     let mut columns = Vec::with_capacity(existing_vars.len());
-    for variable in &existing_vars {
-        let series =
-            df.column(variable.name.as_str())
-                .map_err(|_| SubmitError::ColumnNotFound {
-                    column: variable.name.clone(),
-                })?;
+    for (variable, column_name) in &existing_vars {
+        let series = df
+            .column(column_name)
+            .map_err(|_| SubmitError::ColumnNotFound {
+                column: variable.name.clone(),
+            })?;
         columns.push(series);
     }
 
+    let mut stripped_values = 0usize;
     for row_idx in 0..df.height() {
         let mut group = BytesStart::new("ItemGroupData");
         let group_oid = format!("IG.{dataset_name}");
@@ -146,20 +193,165 @@ pub fn write_dataset_xml(
         group.push_attribute(("ItemGroupOID", group_oid.as_str()));
         group.push_attribute(("data:ItemGroupDataSeq", group_seq.as_str()));
         xml.write_event(Event::Start(group))?;
-        for (variable, column) in existing_vars.iter().zip(columns.iter()) {
+        for ((variable, _), column) in existing_vars.iter().zip(columns.iter()) {
             let value = column.get(row_idx).unwrap_or(AnyValue::Null);
-            if let Some(text) = any_to_string_non_empty(value) {
+            if let Some(raw) = any_to_string_non_empty(value) {
+                let text = strip_xml_illegal_chars(&raw);
+                if let Cow::Owned(_) = text {
+                    stripped_values += 1;
+                }
                 let mut item = BytesStart::new("ItemData");
                 let item_oid = format!("IT.{dataset_name}.{}", variable.name);
                 item.push_attribute(("ItemOID", item_oid.as_str()));
-                item.push_attribute(("Value", text.as_str()));
-                xml.write_event(Event::Empty(item))?;
+                if options
+                    .element_text_threshold
+                    .is_some_and(|max| text.chars().count() > max)
+                {
+                    xml.write_event(Event::Start(item))?;
+                    xml.write_event(Event::Text(BytesText::new(&text)))?;
+                    xml.write_event(Event::End(BytesEnd::new("ItemData")))?;
+                } else {
+                    // quick-xml leaves whitespace as is, which parsers would
+                    // normalize to spaces inside an attribute
+                    item.push_attribute(Attribute {
+                        key: QName(b"Value"),
+                        value: Cow::Owned(escape_attribute_value(&text).into_bytes()),
+                    });
+                    xml.write_event(Event::Empty(item))?;
+                }
             }
         }
         xml.write_event(Event::End(BytesEnd::new("ItemGroupData")))?;
     }
 
+    if stripped_values > 0 {
+        tracing::warn!(
+            dataset = %dataset_name,
+            values = stripped_values,
+            "Removed XML-illegal control characters from Dataset-XML values"
+        );
+    }
+
     xml.write_event(Event::End(BytesEnd::new(container_name)))?;
     xml.write_event(Event::End(BytesEnd::new("ODM")))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use polars::prelude::*;
+    use quick_xml::Reader;
+
+    fn write_dm(arm: &[&str], options: &DatasetXmlOptions) -> String {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = domains.iter().find(|d| d.name == "DM").unwrap();
+        let subjects: Vec<String> = (1..=arm.len()).map(|n| format!("S-00{n}")).collect();
+        let data = df! {
+            "STUDYID" => vec!["STUDY"; arm.len()],
+            "DOMAIN" => vec!["DM"; arm.len()],
+            "USUBJID" => subjects,
+            "ARM" => arm,
+        }
+        .unwrap();
+        let frame = DomainFrame::new("DM", data);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dm.xml");
+        write_dataset_xml(&path, dm, &frame, "STUDY", "3.4", Some(options)).unwrap();
+        std::fs::read_to_string(&path).unwrap()
+    }
+
+    /// ARM values as read back by an XML parser.
+    fn parsed_arms(xml: &str) -> Vec<String> {
+        let mut reader = Reader::from_str(xml);
+        let mut arms = Vec::new();
+        let mut in_arm = false;
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Eof => break,
+                Event::Empty(item) | Event::Start(item) if item.name().as_ref() == b"ItemData" => {
+                    let oid = item.try_get_attribute("ItemOID").unwrap().unwrap();
+                    if oid.value.as_ref() == b"IT.DM.ARM" {
+                        match item.try_get_attribute("Value").unwrap() {
+                            Some(value) => arms.push(value.unescape_value().unwrap().into_owned()),
+                            None => in_arm = true,
+                        }
+                    }
+                }
+                Event::Text(text) if in_arm => {
+                    arms.push(text.decode().unwrap().into_owned());
+                    in_arm = false;
+                }
+                _ => {}
+            }
+        }
+        arms
+    }
+
+    #[test]
+    fn test_dataset_xml_fixed_creation_datetime() {
+        let options = DatasetXmlOptions {
+            creation_datetime: Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap()),
+            ..Default::default()
+        };
+        let xml = write_dm(&["PLACEBO"], &options);
+        assert!(xml.contains("CreationDateTime=\"2024-05-01T12:30:00Z\""));
+    }
+
+    #[test]
+    fn test_dataset_xml_control_chars_and_newlines() {
+        let xml = write_dm(
+            &["PLA\u{1}CEBO\u{8}", "DRUG A\nDRUG B"],
+            &DatasetXmlOptions::default(),
+        );
+        assert!(xml.contains("Value=\"PLACEBO\""));
+        assert!(xml.contains("Value=\"DRUG A&#10;DRUG B\""));
+        assert_eq!(parsed_arms(&xml), ["PLACEBO", "DRUG A\nDRUG B"]);
+    }
+
+    #[test]
+    fn test_dataset_xml_long_values_as_element_text() {
+        let options = DatasetXmlOptions {
+            element_text_threshold: Some(10),
+            ..Default::default()
+        };
+        let xml = write_dm(&["PLACEBO", "DRUG A 10 MG, DRUG B"], &options);
+        assert!(xml.contains("Value=\"PLACEBO\""));
+        assert!(xml.contains(">DRUG A 10 MG, DRUG B</ItemData>"));
+        assert_eq!(parsed_arms(&xml), ["PLACEBO", "DRUG A 10 MG, DRUG B"]);
+    }
+
+    #[test]
+    fn test_dataset_xml_outputs_reference_data_override() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let frame = |code: &str| {
+            let data = df! {
+                "STUDYID" => &["STUDY"],
+                "DOMAIN" => &[code],
+                "USUBJID" => &["S-001"],
+            }
+            .unwrap();
+            DomainFrame::new(code, data)
+        };
+        let frames = [frame("AE"), frame("DM")];
+        let overrides = BTreeMap::from([("ae".to_string(), true)]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_dataset_xml_outputs_with_reference_data(
+            dir.path(),
+            &domains,
+            &frames,
+            "STUDY",
+            "3.4",
+            &overrides,
+        )
+        .unwrap();
+
+        let ae = std::fs::read_to_string(&paths[0]).unwrap();
+        assert!(ae.contains("<ReferenceData StudyOID=\"STDY.STUDY\""));
+        let dm = std::fs::read_to_string(&paths[1]).unwrap();
+        assert!(dm.contains("<ClinicalData StudyOID=\"STDY.STUDY\""));
+    }
+}
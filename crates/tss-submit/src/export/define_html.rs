@@ -0,0 +1,212 @@
+//! HTML rendering of Define-XML metadata.
+//!
+//! Produces a single self-contained page (inline CSS, no scripts or external
+//! resources) listing the datasets, variables with their origins, and
+//! codelists of a [`DefineSpec`]. Reviewers can open it in a browser or print
+//! it to a define.pdf without an XSL stylesheet.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Result, SubmitError};
+
+use super::common::{VariableTypeExt, ensure_parent_dir};
+use super::define_xml::{DefineSpec, ItemOrigin};
+
+/// Inline stylesheet, chosen to print cleanly.
+const STYLE: &str = "body{font-family:Arial,Helvetica,sans-serif;font-size:10pt;margin:2em;color:#222}\
+h1{font-size:16pt}h2{font-size:13pt;margin-top:2em;border-bottom:1px solid #999}\
+h3{font-size:11pt;margin-top:1.5em}\
+table{border-collapse:collapse;width:100%;margin-bottom:1em;page-break-inside:auto}\
+tr{page-break-inside:avoid}\
+th,td{border:1px solid #999;padding:3px 6px;text-align:left;vertical-align:top}\
+th{background:#e8e8e8}tr:nth-child(even) td{background:#f7f7f7}\
+a{color:#1a4e8a;text-decoration:none}";
+
+/// Write an HTML rendering of Define-XML metadata.
+///
+/// Each dataset links to its variable table, and each variable with
+/// controlled terminology links to its codelist.
+pub fn write_define_html(output_path: &Path, spec: &DefineSpec) -> Result<()> {
+    ensure_parent_dir(output_path)?;
+    fs::write(output_path, render_define_html(spec))
+        .map_err(|e| SubmitError::write_error("Define HTML", output_path.display().to_string(), e))
+}
+
+/// Render the full HTML document.
+fn render_define_html(spec: &DefineSpec) -> String {
+    let study_id = escape(&spec.study_id);
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"UTF-8\">\n\
+         <title>Define-XML: {study_id}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>Study {study_id}, Data Definitions</h1>\n\
+         <p>{}-IG {} &middot; Context: {}</p>\n",
+        spec.model.as_str(),
+        escape(&spec.ig_version),
+        escape(&spec.context)
+    );
+
+    if !spec.ct_standards.is_empty() {
+        html.push_str("<h2>Standards</h2>\n<table>\n<tr><th>Name</th><th>Publishing Set</th><th>Version</th></tr>\n");
+        for standard in spec.ct_standards.values() {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&standard.name),
+                escape(&standard.publishing_set),
+                escape(&standard.version)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Datasets</h2>\n<table>\n<tr><th>Dataset</th><th>Description</th><th>Class</th><th>Structure</th><th>Reference Data</th></tr>\n");
+    for group in &spec.item_groups {
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"#{}\">{}</a></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&group.oid),
+            escape(&group.name),
+            escape(group.label.as_deref().unwrap_or("")),
+            escape(group.class.as_deref().unwrap_or("")),
+            escape(group.structure.as_deref().unwrap_or("")),
+            if group.is_reference_data { "Yes" } else { "No" }
+        );
+    }
+    html.push_str("</table>\n");
+
+    for group in &spec.item_groups {
+        let _ = writeln!(
+            html,
+            "<h3 id=\"{}\">{} ({})</h3>",
+            escape(&group.oid),
+            escape(&group.name),
+            escape(group.label.as_deref().unwrap_or(""))
+        );
+        html.push_str("<table>\n<tr><th>Order</th><th>Variable</th><th>Label</th><th>Type</th><th>Length</th><th>Key</th><th>Mandatory</th><th>Codelist</th><th>Origin</th></tr>\n");
+        for item in &group.items {
+            let Some(item_def) = spec.item_defs.get(&item.item_oid) else {
+                continue;
+            };
+            let codelist = item_def
+                .codelist_oid
+                .as_ref()
+                .and_then(|oid| spec.code_lists.get(oid).map(|list| (oid, list)))
+                .map(|(oid, list)| {
+                    format!("<a href=\"#{}\">{}</a>", escape(oid), escape(&list.name))
+                })
+                .unwrap_or_default();
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                item.order_number,
+                escape(&item_def.name),
+                escape(item_def.label.as_deref().unwrap_or("")),
+                item_def.data_type.as_define_type(),
+                item_def.length.map(|l| l.to_string()).unwrap_or_default(),
+                item.key_sequence.map(|k| k.to_string()).unwrap_or_default(),
+                if item.mandatory { "Yes" } else { "No" },
+                codelist,
+                origin_text(&item_def.origin())
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !spec.code_lists.is_empty() {
+        html.push_str("<h2>Codelists</h2>\n");
+        for (oid, list) in &spec.code_lists {
+            let _ = writeln!(
+                html,
+                "<h3 id=\"{}\">{}{}</h3>",
+                escape(oid),
+                escape(&list.name),
+                if list.extensible { " (extensible)" } else { "" }
+            );
+            html.push_str("<table>\n<tr><th>Coded Value</th></tr>\n");
+            for value in &list.values {
+                let _ = writeln!(html, "<tr><td>{}</td></tr>", escape(value));
+            }
+            for value in &list.extended_values {
+                let _ = writeln!(html, "<tr><td>{} (extended)</td></tr>", escape(value));
+            }
+            html.push_str("</table>\n");
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Origin cell text, naming the source of a predecessor.
+fn origin_text(origin: &ItemOrigin) -> String {
+    match origin.predecessor() {
+        Some(predecessor) => format!("{}: {}", origin.as_define_type(), escape(predecessor)),
+        None => origin.as_define_type().to_string(),
+    }
+}
+
+/// Escape text for HTML element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{DefineXmlOptions, DomainFrame, build_define_spec};
+    use polars::prelude::*;
+
+    #[test]
+    fn test_define_html_lists_domains_and_codelists() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = df! {
+            "STUDYID" => &["STUDY", "STUDY"],
+            "DOMAIN" => &["DM", "DM"],
+            "USUBJID" => &["S-001", "S-002"],
+            "SEX" => &["F", "M"],
+        }
+        .unwrap();
+        let ae = df! {
+            "STUDYID" => &["STUDY"],
+            "DOMAIN" => &["AE"],
+            "USUBJID" => &["S-001"],
+            "AESEQ" => &[1i64],
+            "AETERM" => &["HEADACHE"],
+            "AESER" => &["N"],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("DM", dm), DomainFrame::new("AE", ae)];
+        let options = DefineXmlOptions::new("3.4", "Submission");
+        let spec = build_define_spec("STUDY", &domains, &frames, &options).unwrap();
+        assert!(!spec.code_lists.is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("define.html");
+        write_define_html(&path, &spec).unwrap();
+        let html = fs::read_to_string(&path).unwrap();
+
+        for group in &spec.item_groups {
+            assert!(html.contains(&format!(">{}</a>", group.name)));
+        }
+        for list in spec.code_lists.values() {
+            assert!(html.contains(&escape(&list.name)));
+        }
+        assert!(html.contains("AETERM"));
+        assert!(html.contains("Collected"));
+    }
+}
@@ -5,20 +5,23 @@
 use std::io::BufWriter;
 use std::path::Path;
 
-use chrono::{SecondsFormat, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use quick_xml::Writer;
 use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
 
 use super::types::{DomainFrame, domain_map_by_code};
 use crate::error::{Result, SubmitError};
+use crate::map::{MappingState, VariableStatus};
+use crate::normalize::{NormalizationType, infer_normalization_rules};
 use tss_standards::ct::{Codelist, TerminologyCatalog, TerminologyRegistry};
 use tss_standards::{CtVersion, load_ct};
-use tss_standards::{SdtmDomain, SdtmVariable, VariableType};
+use tss_standards::{SdtmDomain, VariableType};
 
 use super::common::{
-    DEFINE_XML_NS, DEFINE_XML_VERSION, ODM_NS, VariableTypeExt, XLINK_NS, ensure_parent_dir,
-    has_collected_data, is_expected, is_identifier, is_reference_domain, is_required,
-    normalize_study_id, variable_length, write_text_element, write_translated_text,
+    ARM_NS, DEFINE_XML_NS, DEFINE_XML_VERSION, ODM_NS, Overlength, VariableTypeExt, XLINK_NS,
+    distinct_values, ensure_parent_dir, has_collected_data, is_expected, is_identifier,
+    is_reference_domain, is_required, normalize_study_id, variable_length_with_policy,
+    write_text_element, write_translated_text,
 };
 
 /// Options for Define-XML output.
@@ -27,6 +30,20 @@ pub struct DefineXmlOptions {
     /// Implementation Guide version (e.g., "3.4" for SDTM-IG 3.4).
     pub ig_version: String,
     pub context: String,
+    /// Origins overriding the data-driven default, keyed by uppercase
+    /// (dataset name, variable name).
+    pub origins: BTreeMap<(String, String), ItemOrigin>,
+    /// Variables the user marked as not collected, keyed by uppercase
+    /// (dataset or domain code, variable name). Written with origin
+    /// "Not Available" regardless of the data, matching the exemptions of
+    /// [`validate_domain_with_not_collected`](crate::validate_domain_with_not_collected).
+    pub not_collected: BTreeSet<(String, String)>,
+    /// CreationDateTime written to the file instead of the current time,
+    /// for reproducible output.
+    pub creation_datetime: Option<DateTime<Utc>>,
+    /// Overlength policy of the XPT export, so character lengths in the
+    /// Define-XML match the written datasets.
+    pub on_overlength: Overlength,
 }
 
 impl DefineXmlOptions {
@@ -34,39 +51,311 @@ pub fn new(ig_version: impl Into<String>, context: impl Into<String>) -> Self {
         Self {
             ig_version: ig_version.into(),
             context: context.into(),
+            origins: BTreeMap::new(),
+            not_collected: BTreeSet::new(),
+            creation_datetime: None,
+            on_overlength: Overlength::default(),
         }
     }
+
+    /// Mark variables of a dataset as not collected.
+    ///
+    /// Takes the same variable set as
+    /// [`validate_domain_with_not_collected`](crate::validate_domain_with_not_collected).
+    /// An origin set with [`with_origin`](Self::with_origin) takes precedence.
+    #[must_use]
+    pub fn with_not_collected<I, S>(mut self, dataset: impl AsRef<str>, variables: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let dataset = dataset.as_ref().to_uppercase();
+        for variable in variables {
+            self.not_collected
+                .insert((dataset.clone(), variable.as_ref().to_uppercase()));
+        }
+        self
+    }
+
+    /// Match character lengths to an XPT export using this overlength policy.
+    #[must_use]
+    pub fn with_on_overlength(mut self, policy: Overlength) -> Self {
+        self.on_overlength = policy;
+        self
+    }
+
+    /// Stamp the document with a fixed CreationDateTime.
+    #[must_use]
+    pub fn with_creation_datetime(mut self, creation_datetime: DateTime<Utc>) -> Self {
+        self.creation_datetime = Some(creation_datetime);
+        self
+    }
+
+    /// Take the origins of a dataset's variables from its mapping.
+    ///
+    /// Mapped variables are Collected. Variables the normalization pipeline
+    /// fills without a source column are Assigned when constant (STUDYID,
+    /// DOMAIN) and Derived otherwise (--SEQ, --DY, auto-generated
+    /// variables); not-collected variables are marked as with
+    /// [`with_not_collected`](Self::with_not_collected). Origins already set
+    /// with [`with_origin`](Self::with_origin) are kept.
+    #[must_use]
+    pub fn with_mapping(mut self, dataset: impl AsRef<str>, mapping: &MappingState) -> Self {
+        let dataset = dataset.as_ref().to_uppercase();
+        for rule in infer_normalization_rules(mapping.domain()).rules {
+            let generated = match rule.transform_type {
+                NormalizationType::Constant => Some(ItemOrigin::Assigned),
+                NormalizationType::SequenceNumber | NormalizationType::StudyDay { .. } => {
+                    Some(ItemOrigin::Derived)
+                }
+                _ => None,
+            };
+            let origin = match mapping.status(&rule.target_variable) {
+                VariableStatus::Accepted => ItemOrigin::Collected,
+                VariableStatus::AutoGenerated => generated.unwrap_or(ItemOrigin::Derived),
+                VariableStatus::Suggested | VariableStatus::Unmapped => match generated {
+                    Some(origin) => origin,
+                    None => continue,
+                },
+                VariableStatus::NotCollected | VariableStatus::Omitted => continue,
+            };
+            self.origins
+                .entry((dataset.clone(), rule.target_variable.to_uppercase()))
+                .or_insert(origin);
+        }
+        self.with_not_collected(dataset, mapping.all_not_collected().keys())
+    }
+
+    /// Set the origin of a variable, e.g. [`ItemOrigin::Predecessor`] for a
+    /// variable copied from another dataset.
+    #[must_use]
+    pub fn with_origin(
+        mut self,
+        dataset: impl AsRef<str>,
+        variable: impl AsRef<str>,
+        origin: ItemOrigin,
+    ) -> Self {
+        self.origins.insert(
+            (
+                dataset.as_ref().to_uppercase(),
+                variable.as_ref().to_uppercase(),
+            ),
+            origin,
+        );
+        self
+    }
+}
+
+/// Origin of a variable (def:Origin).
+///
+/// Define-XML 2.1 valid OriginTypes: Assigned, Collected, Derived, Not
+/// Available, Other, Predecessor, Protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemOrigin {
+    Collected,
+    Derived,
+    Assigned,
+    Protocol,
+    NotAvailable,
+    /// Copied from a variable in another dataset, described as
+    /// `DATASET.VARIABLE` (e.g., "DM.RFSTDTC").
+    Predecessor(String),
+}
+
+impl ItemOrigin {
+    /// OriginType attribute value.
+    pub fn as_define_type(&self) -> &'static str {
+        match self {
+            Self::Collected => "Collected",
+            Self::Derived => "Derived",
+            Self::Assigned => "Assigned",
+            Self::Protocol => "Protocol",
+            Self::NotAvailable => "Not Available",
+            Self::Predecessor(_) => "Predecessor",
+        }
+    }
+
+    /// Predecessor variable, for [`ItemOrigin::Predecessor`].
+    pub fn predecessor(&self) -> Option<&str> {
+        match self {
+            Self::Predecessor(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Data model a Define-XML document describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DefineModel {
+    /// SDTM tabulation datasets.
+    #[default]
+    Sdtm,
+    /// ADaM analysis datasets.
+    Adam,
+}
+
+impl DefineModel {
+    /// Model name used in descriptions (e.g., "SDTM").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sdtm => "SDTM",
+            Self::Adam => "ADaM",
+        }
+    }
+
+    /// Implementation Guide name (e.g., "SDTMIG").
+    pub fn ig_name(&self) -> &'static str {
+        match self {
+            Self::Sdtm => "SDTMIG",
+            Self::Adam => "ADaMIG",
+        }
+    }
+}
+
+/// Analysis Results Metadata display (arm:ResultDisplay).
+///
+/// Only the display level is described; analysis results within a display
+/// are not written yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultDisplaySpec {
+    /// Display identifier (e.g., "Table 14.1.1").
+    pub name: String,
+    /// Display title.
+    pub description: Option<String>,
+}
+
+/// Metadata of a Define-XML document.
+///
+/// Built by [`build_define_spec`] and rendered by [`write_define_xml`] or
+/// [`write_define_html`](super::write_define_html).
+#[derive(Debug, Clone)]
+pub struct DefineSpec {
+    /// Normalized study identifier.
+    pub study_id: String,
+    /// Implementation Guide version (e.g., "3.4").
+    pub ig_version: String,
+    /// Define-XML context (e.g., "Submission").
+    pub context: String,
+    /// Data model of the datasets.
+    pub model: DefineModel,
+    /// CreationDateTime of the document; the time of writing when `None`.
+    pub creation_datetime: Option<DateTime<Utc>>,
+    /// Analysis Results Metadata displays; empty for SDTM.
+    pub result_displays: Vec<ResultDisplaySpec>,
+    /// Datasets in output order.
+    pub item_groups: Vec<ItemGroupSpec>,
+    /// Variable definitions keyed by OID.
+    pub item_defs: BTreeMap<String, ItemDefSpec>,
+    /// Codelists keyed by OID.
+    pub code_lists: BTreeMap<String, CodeListSpec>,
+    /// Controlled Terminology standards keyed by OID.
+    pub ct_standards: BTreeMap<String, CtStandard>,
+}
+
+/// Dataset (ItemGroupDef) specification for Define-XML.
+#[derive(Debug, Clone)]
+pub struct ItemGroupSpec {
+    pub oid: String,
+    /// Output dataset name (e.g., "LBCH" for a split domain).
+    pub name: String,
+    /// Base domain code; not written for ADaM datasets.
+    pub domain: String,
+    pub label: Option<String>,
+    pub class: Option<String>,
+    pub structure: Option<String>,
+    pub is_reference_data: bool,
+    /// Variables in order.
+    pub items: Vec<ItemRefSpec>,
+}
+
+/// Variable reference (ItemRef) within a dataset.
+#[derive(Debug, Clone)]
+pub struct ItemRefSpec {
+    pub item_oid: String,
+    pub order_number: usize,
+    pub mandatory: bool,
+    pub key_sequence: Option<usize>,
 }
 
 /// Item definition specification for Define-XML.
 #[derive(Debug, Clone)]
-struct ItemDefSpec {
-    oid: String,
-    name: String,
-    label: Option<String>,
-    data_type: VariableType,
-    length: Option<u16>,
-    codelist_oid: Option<String>,
-    core: Option<String>,
-    has_data: bool,
+pub struct ItemDefSpec {
+    pub oid: String,
+    pub name: String,
+    pub label: Option<String>,
+    pub data_type: VariableType,
+    pub length: Option<u16>,
+    pub codelist_oid: Option<String>,
+    pub core: Option<String>,
+    pub has_data: bool,
+    /// Origin set through [`DefineXmlOptions::with_origin`].
+    pub origin_override: Option<ItemOrigin>,
+}
+
+impl ItemDefSpec {
+    /// Origin of this variable.
+    ///
+    /// Uses the override when set; otherwise variables with data are
+    /// Collected, Expected variables without data Not Available, and the
+    /// rest Derived.
+    pub fn origin(&self) -> ItemOrigin {
+        if let Some(origin) = &self.origin_override {
+            return origin.clone();
+        }
+        let core_designation = self.core.as_deref().and_then(|c| c.parse().ok());
+        if is_expected(core_designation) && !self.has_data {
+            ItemOrigin::NotAvailable
+        } else if self.has_data {
+            ItemOrigin::Collected
+        } else {
+            ItemOrigin::Derived
+        }
+    }
+
+    /// Define-XML origin type for this variable.
+    pub fn origin_type(&self) -> &'static str {
+        self.origin().as_define_type()
+    }
 }
 
 /// Codelist specification for Define-XML.
 #[derive(Debug, Clone)]
-struct CodeListSpec {
-    name: String,
-    values: Vec<String>,
-    extensible: bool,
-    standard_oid: Option<String>,
+#[non_exhaustive]
+pub struct CodeListSpec {
+    pub name: String,
+    pub values: Vec<String>,
+    /// Sponsor values observed in the data for an extensible codelist,
+    /// written with `def:ExtendedValue="Yes"`.
+    pub extended_values: Vec<String>,
+    pub extensible: bool,
+    pub standard_oid: Option<String>,
+}
+
+impl CodeListSpec {
+    /// Create a codelist specification without extended values.
+    pub fn new(
+        name: impl Into<String>,
+        values: Vec<String>,
+        extensible: bool,
+        standard_oid: Option<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            values,
+            extended_values: Vec::new(),
+            extensible,
+            standard_oid,
+        }
+    }
 }
 
 /// CT Standard definition for Define-XML def:Standards section.
 #[derive(Debug, Clone)]
-struct CtStandard {
-    oid: String,
-    name: String,
-    publishing_set: String,
-    version: String,
+pub struct CtStandard {
+    pub oid: String,
+    pub name: String,
+    pub publishing_set: String,
+    pub version: String,
 }
 
 /// Write Define-XML output.
@@ -77,18 +366,26 @@ pub fn write_define_xml(
     frames: &[DomainFrame],
     options: &DefineXmlOptions,
 ) -> Result<()> {
+    let spec = build_define_spec(study_id, domains, frames, options)?;
+    write_define_spec(output_path, &spec)
+}
+
+/// Build the Define-XML metadata for a set of datasets.
+///
+/// Resolves each dataset's variables, codelists and CT standards without
+/// writing anything, so the result can be rendered in several formats.
+pub fn build_define_spec(
+    study_id: &str,
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+    options: &DefineXmlOptions,
+) -> Result<DefineSpec> {
     if frames.is_empty() {
         return Err(SubmitError::NoDatasets {
             format: "Define-XML".to_string(),
         });
     }
     let study_id = normalize_study_id(study_id);
-    let sanitized_study_id = sanitize_oid_component(&study_id);
-    let sanitized_ig_version = sanitize_oid_component(&options.ig_version);
-    let study_oid = format!("STDY.{sanitized_study_id}");
-    let file_oid = format!("{study_oid}.Define-XML_{DEFINE_XML_VERSION}");
-    let mdv_oid = format!("MDV.{study_oid}.SDTMIG.{sanitized_ig_version}");
-    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
 
     let domain_lookup = domain_map_by_code(domains);
     let mut entries: Vec<(&SdtmDomain, &DomainFrame)> = Vec::new();
@@ -110,6 +407,7 @@ pub fn write_define_xml(
 
     for (domain, frame) in &entries {
         let output_dataset_name = frame.dataset_name();
+        frame.warn_dataset_name();
         for variable in &domain.variables {
             // Skip variables that don't exist in the DataFrame
             // (they weren't mapped or generated in the transformation)
@@ -123,12 +421,22 @@ pub fn write_define_xml(
                 sanitize_oid_component(&variable.name)
             );
             let length = match variable.data_type {
-                VariableType::Char => Some(variable_length(variable, &frame.data)?),
+                VariableType::Char => Some(variable_length_with_policy(
+                    variable,
+                    &frame.data,
+                    options.on_overlength,
+                )?),
                 VariableType::Num => None,
             };
+            let observed = match variable.data_type {
+                VariableType::Char => distinct_values(&frame.data, &variable.name),
+                VariableType::Num => BTreeSet::new(),
+            };
             let codelist_oid = resolve_codelist(
-                domain,
-                variable,
+                &domain.name,
+                &variable.name,
+                variable.codelist_code.as_deref(),
+                &observed,
                 &ct_registry,
                 &mut code_lists,
                 &mut ct_standards,
@@ -146,11 +454,100 @@ pub fn write_define_xml(
                     codelist_oid,
                     core: variable.core.map(|c| c.as_code().to_string()),
                     has_data,
+                    origin_override: options
+                        .origins
+                        .get(&(
+                            output_dataset_name.to_uppercase(),
+                            variable.name.to_uppercase(),
+                        ))
+                        .cloned()
+                        .or_else(|| {
+                            let variable_name = variable.name.to_uppercase();
+                            [
+                                output_dataset_name.to_uppercase(),
+                                domain.name.to_uppercase(),
+                            ]
+                            .into_iter()
+                            .any(|dataset| {
+                                options
+                                    .not_collected
+                                    .contains(&(dataset, variable_name.clone()))
+                            })
+                            .then_some(ItemOrigin::NotAvailable)
+                        }),
                 },
             );
         }
     }
 
+    let item_groups = entries
+        .iter()
+        .map(|(domain, frame)| {
+            let output_dataset_name = frame.dataset_name();
+            let mut key_sequence = 0usize;
+            // Filter to only variables that exist in the DataFrame
+            let items = domain
+                .variables_by_role()
+                .into_iter()
+                .filter(|v| frame.data.column(&v.name).is_ok())
+                .enumerate()
+                .map(|(idx, variable)| ItemRefSpec {
+                    item_oid: format!(
+                        "IT.{}.{}",
+                        sanitize_oid_component(&output_dataset_name),
+                        sanitize_oid_component(&variable.name)
+                    ),
+                    order_number: idx + 1,
+                    mandatory: is_required(variable),
+                    key_sequence: is_identifier(variable).then(|| {
+                        key_sequence += 1;
+                        key_sequence
+                    }),
+                })
+                .collect();
+            ItemGroupSpec {
+                oid: format!("IG.{}", sanitize_oid_component(&output_dataset_name)),
+                domain: frame.base_domain_code().to_string(),
+                name: output_dataset_name,
+                label: domain.label.clone(),
+                class: domain.class_name().map(str::to_string),
+                structure: domain.structure.clone(),
+                is_reference_data: is_reference_domain(domain),
+                items,
+            }
+        })
+        .collect();
+
+    Ok(DefineSpec {
+        study_id,
+        ig_version: options.ig_version.clone(),
+        context: options.context.clone(),
+        model: DefineModel::Sdtm,
+        creation_datetime: options.creation_datetime,
+        result_displays: Vec::new(),
+        item_groups,
+        item_defs,
+        code_lists,
+        ct_standards,
+    })
+}
+
+/// Write a Define-XML document from previously built metadata.
+pub fn write_define_spec(output_path: &Path, spec: &DefineSpec) -> Result<()> {
+    let study_id = spec.study_id.as_str();
+    let sanitized_study_id = sanitize_oid_component(study_id);
+    let sanitized_ig_version = sanitize_oid_component(&spec.ig_version);
+    let study_oid = format!("STDY.{sanitized_study_id}");
+    let file_oid = format!("{study_oid}.Define-XML_{DEFINE_XML_VERSION}");
+    let mdv_oid = format!(
+        "MDV.{study_oid}.{}.{sanitized_ig_version}",
+        spec.model.ig_name()
+    );
+    let timestamp = spec
+        .creation_datetime
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+
     ensure_parent_dir(output_path)?;
     let file = File::create(output_path).map_err(|e| {
         SubmitError::write_error("Define-XML", output_path.display().to_string(), e)
@@ -164,6 +561,9 @@ pub fn write_define_xml(
     root.push_attribute(("xmlns", ODM_NS));
     root.push_attribute(("xmlns:def", DEFINE_XML_NS));
     root.push_attribute(("xmlns:xlink", XLINK_NS));
+    if !spec.result_displays.is_empty() {
+        root.push_attribute(("xmlns:arm", ARM_NS));
+    }
     root.push_attribute(("FileType", "Snapshot"));
     root.push_attribute(("FileOID", file_oid.as_str()));
     root.push_attribute(("ODMVersion", "1.3.2"));
@@ -171,7 +571,7 @@ pub fn write_define_xml(
     root.push_attribute(("Originator", "Trial-Submission-Studio"));
     root.push_attribute(("SourceSystem", "Trial-Submission-Studio"));
     root.push_attribute(("SourceSystemVersion", "1.0"));
-    root.push_attribute(("def:Context", options.context.as_str()));
+    root.push_attribute(("def:Context", spec.context.as_str()));
     xml.write_event(Event::Start(root))?;
 
     let mut study = BytesStart::new("Study");
@@ -179,21 +579,22 @@ pub fn write_define_xml(
     xml.write_event(Event::Start(study))?;
 
     xml.write_event(Event::Start(BytesStart::new("GlobalVariables")))?;
-    write_text_element(&mut xml, "StudyName", &study_id)?;
+    write_text_element(&mut xml, "StudyName", study_id)?;
     write_text_element(
         &mut xml,
         "StudyDescription",
-        &format!("SDTM submission for {study_id}"),
+        &format!("{} submission for {study_id}", spec.model.as_str()),
     )?;
-    write_text_element(&mut xml, "ProtocolName", &study_id)?;
+    write_text_element(&mut xml, "ProtocolName", study_id)?;
     xml.write_event(Event::End(BytesEnd::new("GlobalVariables")))?;
 
     let mut metadata = BytesStart::new("MetaDataVersion");
     metadata.push_attribute(("OID", mdv_oid.as_str()));
     let mdv_name = format!("Study {study_id}, Data Definitions");
     let mdv_desc = format!(
-        "SDTM {} metadata definitions for {study_id}",
-        options.ig_version
+        "{} {} metadata definitions for {study_id}",
+        spec.model.as_str(),
+        spec.ig_version
     );
     metadata.push_attribute(("Name", mdv_name.as_str()));
     metadata.push_attribute(("Description", mdv_desc.as_str()));
@@ -201,9 +602,9 @@ pub fn write_define_xml(
     xml.write_event(Event::Start(metadata))?;
 
     // Write def:Standards section with CT versions
-    if !ct_standards.is_empty() {
+    if !spec.ct_standards.is_empty() {
         xml.write_event(Event::Start(BytesStart::new("def:Standards")))?;
-        for standard in ct_standards.values() {
+        for standard in spec.ct_standards.values() {
             let mut std_node = BytesStart::new("def:Standard");
             std_node.push_attribute(("OID", standard.oid.as_str()));
             std_node.push_attribute(("Name", standard.name.as_str()));
@@ -216,64 +617,47 @@ pub fn write_define_xml(
         xml.write_event(Event::End(BytesEnd::new("def:Standards")))?;
     }
 
-    for (domain, frame) in &entries {
-        let output_dataset_name = frame.dataset_name();
-        let base_domain_code = frame.base_domain_code();
+    for group in &spec.item_groups {
         let mut ig = BytesStart::new("ItemGroupDef");
-        let ig_oid = format!("IG.{}", sanitize_oid_component(&output_dataset_name));
-        let sas_dataset_name: String = output_dataset_name.chars().take(8).collect();
-        ig.push_attribute(("OID", ig_oid.as_str()));
-        ig.push_attribute(("Name", output_dataset_name.as_str()));
+        let sas_dataset_name: String = group.name.chars().take(8).collect();
+        ig.push_attribute(("OID", group.oid.as_str()));
+        ig.push_attribute(("Name", group.name.as_str()));
         ig.push_attribute(("Repeating", "Yes"));
-        ig.push_attribute(("Domain", base_domain_code));
+        match spec.model {
+            DefineModel::Sdtm => ig.push_attribute(("Domain", group.domain.as_str())),
+            DefineModel::Adam => ig.push_attribute(("Purpose", "Analysis")),
+        }
         ig.push_attribute(("SASDatasetName", sas_dataset_name.as_str()));
-        if let Some(label) = domain.label.as_ref() {
+        if let Some(label) = group.label.as_ref() {
             ig.push_attribute(("def:Label", label.as_str()));
         }
-        if let Some(class_name) = domain.class_name() {
-            ig.push_attribute(("def:Class", class_name));
+        if let Some(class_name) = group.class.as_ref() {
+            ig.push_attribute(("def:Class", class_name.as_str()));
         }
-        if let Some(structure) = domain.structure.as_ref() {
+        if let Some(structure) = group.structure.as_ref() {
             ig.push_attribute(("def:Structure", structure.as_str()));
         }
-        if is_reference_domain(domain) {
+        if group.is_reference_data {
             ig.push_attribute(("def:IsReferenceData", "Yes"));
         }
         xml.write_event(Event::Start(ig))?;
 
-        // Filter to only variables that exist in the DataFrame
-        let ordered_vars: Vec<_> = domain
-            .variables_by_role()
-            .into_iter()
-            .filter(|v| frame.data.column(&v.name).is_ok())
-            .collect();
-
-        let mut key_sequence = 1usize;
-        for (idx, variable) in ordered_vars.iter().enumerate() {
+        for item in &group.items {
             let mut item_ref = BytesStart::new("ItemRef");
-            let item_oid = format!(
-                "IT.{}.{}",
-                sanitize_oid_component(&output_dataset_name),
-                sanitize_oid_component(&variable.name)
-            );
-            let order_number = format!("{}", idx + 1);
-            item_ref.push_attribute(("ItemOID", item_oid.as_str()));
+            let order_number = format!("{}", item.order_number);
+            item_ref.push_attribute(("ItemOID", item.item_oid.as_str()));
             item_ref.push_attribute(("OrderNumber", order_number.as_str()));
-            item_ref.push_attribute((
-                "Mandatory",
-                if is_required(variable) { "Yes" } else { "No" },
-            ));
-            if is_identifier(variable) {
+            item_ref.push_attribute(("Mandatory", if item.mandatory { "Yes" } else { "No" }));
+            if let Some(key_sequence) = item.key_sequence {
                 let seq = format!("{key_sequence}");
                 item_ref.push_attribute(("KeySequence", seq.as_str()));
-                key_sequence += 1;
             }
             xml.write_event(Event::Empty(item_ref))?;
         }
         xml.write_event(Event::End(BytesEnd::new("ItemGroupDef")))?;
     }
 
-    for item_def in item_defs.values() {
+    for item_def in spec.item_defs.values() {
         let mut item = BytesStart::new("ItemDef");
         item.push_attribute(("OID", item_def.oid.as_str()));
         item.push_attribute(("Name", item_def.name.as_str()));
@@ -292,23 +676,21 @@ pub fn write_define_xml(
             xml.write_event(Event::Empty(ref_node))?;
         }
 
-        let core_designation = item_def.core.as_deref().and_then(|c| c.parse().ok());
-        // Define-XML 2.1 valid OriginTypes: Assigned, Collected, Derived, Not Available, Other, Predecessor, Protocol
-        let origin_type = if is_expected(core_designation) && !item_def.has_data {
-            "Not Available"
-        } else if item_def.has_data {
-            "Collected"
+        let origin = item_def.origin();
+        let mut origin_node = BytesStart::new("def:Origin");
+        origin_node.push_attribute(("Type", origin.as_define_type()));
+        if let Some(predecessor) = origin.predecessor() {
+            xml.write_event(Event::Start(origin_node))?;
+            write_translated_text(&mut xml, "Description", predecessor)?;
+            xml.write_event(Event::End(BytesEnd::new("def:Origin")))?;
         } else {
-            "Derived"
-        };
-        let mut origin = BytesStart::new("def:Origin");
-        origin.push_attribute(("Type", origin_type));
-        xml.write_event(Event::Empty(origin))?;
+            xml.write_event(Event::Empty(origin_node))?;
+        }
 
         xml.write_event(Event::End(BytesEnd::new("ItemDef")))?;
     }
 
-    for (oid, list) in code_lists {
+    for (oid, list) in &spec.code_lists {
         let mut node = BytesStart::new("CodeList");
         node.push_attribute(("OID", oid.as_str()));
         node.push_attribute(("Name", list.name.as_str()));
@@ -320,33 +702,63 @@ pub fn write_define_xml(
             node.push_attribute(("def:Extensible", "Yes"));
         }
         xml.write_event(Event::Start(node))?;
-        for value in list.values {
+        for value in &list.values {
+            let mut item = BytesStart::new("CodeListItem");
+            item.push_attribute(("CodedValue", value.as_str()));
+            xml.write_event(Event::Start(item))?;
+            write_translated_text(&mut xml, "Decode", value)?;
+            xml.write_event(Event::End(BytesEnd::new("CodeListItem")))?;
+        }
+        for value in &list.extended_values {
             let mut item = BytesStart::new("CodeListItem");
             item.push_attribute(("CodedValue", value.as_str()));
+            item.push_attribute(("def:ExtendedValue", "Yes"));
             xml.write_event(Event::Start(item))?;
-            write_translated_text(&mut xml, "Decode", &value)?;
+            write_translated_text(&mut xml, "Decode", value)?;
             xml.write_event(Event::End(BytesEnd::new("CodeListItem")))?;
         }
         xml.write_event(Event::End(BytesEnd::new("CodeList")))?;
     }
 
+    if !spec.result_displays.is_empty() {
+        xml.write_event(Event::Start(BytesStart::new("arm:AnalysisResultDisplays")))?;
+        for display in &spec.result_displays {
+            let oid = format!("RD.{}", sanitize_oid_component(&display.name));
+            let mut node = BytesStart::new("arm:ResultDisplay");
+            node.push_attribute(("OID", oid.as_str()));
+            node.push_attribute(("Name", display.name.as_str()));
+            xml.write_event(Event::Start(node))?;
+            if let Some(description) = display.description.as_ref() {
+                write_translated_text(&mut xml, "Description", description)?;
+            }
+            xml.write_event(Event::End(BytesEnd::new("arm:ResultDisplay")))?;
+        }
+        xml.write_event(Event::End(BytesEnd::new("arm:AnalysisResultDisplays")))?;
+    }
+
     xml.write_event(Event::End(BytesEnd::new("MetaDataVersion")))?;
     xml.write_event(Event::End(BytesEnd::new("Study")))?;
     xml.write_event(Event::End(BytesEnd::new("ODM")))?;
     Ok(())
 }
 
-/// Resolve codelist for a variable.
-fn resolve_codelist(
-    domain: &SdtmDomain,
-    variable: &SdtmVariable,
+/// Resolve codelist for a variable of a dataset.
+///
+/// `observed` holds the distinct values of the variable in the data. For an
+/// extensible codelist, values outside the CT are added as extended values;
+/// for a non-extensible codelist they are an error.
+pub(super) fn resolve_codelist(
+    dataset: &str,
+    variable: &str,
+    codelist_code: Option<&str>,
+    observed: &BTreeSet<String>,
     ct_registry: &TerminologyRegistry,
     code_lists: &mut BTreeMap<String, CodeListSpec>,
     ct_standards: &mut BTreeMap<String, CtStandard>,
 ) -> Result<Option<String>> {
     let mut ct_entries: Vec<(&Codelist, Option<&TerminologyCatalog>)> = Vec::new();
 
-    if let Some(raw) = variable.codelist_code.as_ref() {
+    if let Some(raw) = codelist_code {
         let codes = parse_codelist_codes(raw);
         for code in codes {
             if let Some(resolved) = ct_registry.resolve(&code, None) {
@@ -355,16 +767,16 @@ fn resolve_codelist(
         }
     }
     if ct_entries.is_empty() {
-        if let Some(raw) = variable.codelist_code.as_ref() {
+        if let Some(raw) = codelist_code {
             let code = raw.split(';').next().unwrap_or("").trim();
             if !code.is_empty() {
                 if let Some(resolved) = ct_registry.resolve(code, None) {
                     ct_entries.push((resolved.codelist, Some(resolved.catalog)));
                 } else {
                     return Err(SubmitError::MissingCodelist {
-                        codelist: raw.clone(),
-                        domain: domain.name.clone(),
-                        variable: variable.name.clone(),
+                        codelist: raw.to_string(),
+                        domain: dataset.to_string(),
+                        variable: variable.to_string(),
                     });
                 }
             }
@@ -402,8 +814,8 @@ fn resolve_codelist(
 
     let oid = format!(
         "CL.{}.{}",
-        sanitize_oid_component(&domain.name),
-        sanitize_oid_component(&variable.name)
+        sanitize_oid_component(dataset),
+        sanitize_oid_component(variable)
     );
     if !code_lists.contains_key(&oid) {
         let mut values = BTreeSet::new();
@@ -422,14 +834,39 @@ fn resolve_codelist(
         let name = names.into_iter().collect::<Vec<_>>().join("; ");
         code_lists.insert(
             oid.clone(),
-            CodeListSpec {
-                name,
-                values: values.into_iter().collect(),
-                extensible,
-                standard_oid,
-            },
+            CodeListSpec::new(name, values.into_iter().collect(), extensible, standard_oid),
         );
     }
+
+    let unknown: Vec<&String> = observed
+        .iter()
+        .filter(|value| {
+            !ct_entries
+                .iter()
+                .any(|(ct, _)| ct.is_valid_submission_value(value))
+        })
+        .collect();
+    if !unknown.is_empty() {
+        let list = code_lists.get_mut(&oid).expect("codelist inserted above");
+        if !list.extensible {
+            // Validation reports these as CT violations; Define-XML only
+            // lists the codelist's own terms
+            tracing::warn!(
+                codelist = codelist_code.unwrap_or_default(),
+                dataset,
+                variable,
+                values = ?unknown,
+                "Values outside a non-extensible codelist are not listed in Define-XML"
+            );
+            return Ok(Some(oid));
+        }
+        for value in unknown {
+            if !list.extended_values.contains(value) {
+                list.extended_values.push(value.clone());
+            }
+        }
+        list.extended_values.sort();
+    }
     Ok(Some(oid))
 }
 
@@ -453,7 +890,7 @@ fn parse_codelist_codes(raw: &str) -> Vec<String> {
 ///
 /// Invalid characters are replaced with underscores.
 /// Leading/trailing whitespace is trimmed.
-fn sanitize_oid_component(s: &str) -> String {
+pub(super) fn sanitize_oid_component(s: &str) -> String {
     s.trim()
         .chars()
         .map(|c| {
@@ -465,3 +902,206 @@ fn sanitize_oid_component(s: &str) -> String {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_predecessor_origin() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = df! {
+            "STUDYID" => &["STUDY"],
+            "DOMAIN" => &["DM"],
+            "USUBJID" => &["S-001"],
+            "RFXSTDTC" => &["2024-01-15"],
+            "SEX" => &["F"],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("DM", dm)];
+        let options = DefineXmlOptions::new("3.4", "Submission").with_origin(
+            "dm",
+            "rfxstdtc",
+            ItemOrigin::Predecessor("EX.EXSTDTC".to_string()),
+        );
+
+        let spec = build_define_spec("STUDY", &domains, &frames, &options).unwrap();
+        let rfxstdtc = &spec.item_defs["IT.DM.RFXSTDTC"];
+        assert_eq!(
+            rfxstdtc.origin(),
+            ItemOrigin::Predecessor("EX.EXSTDTC".to_string())
+        );
+        assert_eq!(spec.item_defs["IT.DM.SEX"].origin(), ItemOrigin::Collected);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("define.xml");
+        write_define_spec(&path, &spec).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        let item = &xml[xml.find("<ItemDef OID=\"IT.DM.RFXSTDTC\"").unwrap()..];
+        let item = &item[..item.find("</ItemDef>").unwrap()];
+        assert!(item.contains("<def:Origin Type=\"Predecessor\">"));
+        assert!(item.contains(">EX.EXSTDTC</TranslatedText>"));
+        assert!(xml.contains("<def:Origin Type=\"Collected\"/>"));
+    }
+
+    #[test]
+    fn test_not_collected_overrides_data() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = df! {
+            "STUDYID" => &["STUDY", "STUDY"],
+            "DOMAIN" => &["DM", "DM"],
+            "USUBJID" => &["S-001", "S-002"],
+            "AGEU" => &["", "YEARS"],
+            "ETHNIC" => &["", ""],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("DM", dm)];
+
+        // Without the set, the stray AGEU value makes it Collected
+        let options = DefineXmlOptions::new("3.4", "Submission");
+        let spec = build_define_spec("STUDY", &domains, &frames, &options).unwrap();
+        assert_eq!(spec.item_defs["IT.DM.AGEU"].origin(), ItemOrigin::Collected);
+
+        let options = DefineXmlOptions::new("3.4", "Submission")
+            .with_not_collected("dm", ["ageu", "ETHNIC"])
+            .with_origin("DM", "ETHNIC", ItemOrigin::Assigned);
+        let spec = build_define_spec("STUDY", &domains, &frames, &options).unwrap();
+        assert_eq!(
+            spec.item_defs["IT.DM.AGEU"].origin(),
+            ItemOrigin::NotAvailable
+        );
+        // An explicit origin wins over the not-collected mark
+        assert_eq!(
+            spec.item_defs["IT.DM.ETHNIC"].origin(),
+            ItemOrigin::Assigned
+        );
+        assert_eq!(
+            spec.item_defs["IT.DM.USUBJID"].origin(),
+            ItemOrigin::Collected
+        );
+    }
+
+    #[test]
+    fn test_origins_from_mapping() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let ae_domain = domains.iter().find(|d| d.name == "AE").unwrap().clone();
+        let columns = vec!["TERM".to_string()];
+        let mut mapping = MappingState::new(ae_domain, "STUDY", &columns, BTreeMap::new(), 0.6);
+        mapping.accept_manual("AETERM", "TERM").unwrap();
+        mapping.mark_auto_generated("USUBJID");
+        mapping.mark_not_collected("AESER", "Not on CRF").unwrap();
+
+        let ae = df! {
+            "STUDYID" => &["STUDY"],
+            "DOMAIN" => &["AE"],
+            "USUBJID" => &["STUDY-001"],
+            "AESEQ" => &[1i64],
+            "AETERM" => &["HEADACHE"],
+            "AESER" => &["N"],
+            "AESTDY" => &[3i64],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("AE", ae)];
+        let options = DefineXmlOptions::new("3.4", "Submission")
+            .with_origin("AE", "AESTDY", ItemOrigin::Protocol)
+            .with_mapping("ae", &mapping);
+        let spec = build_define_spec("STUDY", &domains, &frames, &options).unwrap();
+
+        let origin = |variable: &str| spec.item_defs[&format!("IT.AE.{variable}")].origin();
+        assert_eq!(origin("STUDYID"), ItemOrigin::Assigned);
+        assert_eq!(origin("DOMAIN"), ItemOrigin::Assigned);
+        assert_eq!(origin("USUBJID"), ItemOrigin::Derived);
+        assert_eq!(origin("AESEQ"), ItemOrigin::Derived);
+        assert_eq!(origin("AETERM"), ItemOrigin::Collected);
+        assert_eq!(origin("AESER"), ItemOrigin::NotAvailable);
+        // An explicit origin is kept
+        assert_eq!(origin("AESTDY"), ItemOrigin::Protocol);
+    }
+
+    #[test]
+    fn test_fixed_creation_datetime() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = df! {
+            "STUDYID" => &["STUDY"],
+            "DOMAIN" => &["DM"],
+            "USUBJID" => &["S-001"],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("DM", dm)];
+        let fixed = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+        let options = DefineXmlOptions::new("3.4", "Submission").with_creation_datetime(fixed);
+
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.xml");
+        let second = dir.path().join("second.xml");
+        write_define_xml(&first, "STUDY", &domains, &frames, &options).unwrap();
+        write_define_xml(&second, "STUDY", &domains, &frames, &options).unwrap();
+        let xml = std::fs::read_to_string(&first).unwrap();
+        assert!(xml.contains("CreationDateTime=\"2024-05-01T12:30:00Z\""));
+        assert_eq!(xml, std::fs::read_to_string(&second).unwrap());
+    }
+
+    #[test]
+    fn test_extensible_codelist_extension() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = df! {
+            "STUDYID" => &["STUDY", "STUDY"],
+            "DOMAIN" => &["DM", "DM"],
+            "USUBJID" => &["S-001", "S-002"],
+            "ARMNRS" => &["SCREEN FAILURE", "WITHDREW BEFORE RANDOMIZATION"],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("DM", dm)];
+        let options = DefineXmlOptions::new("3.4", "Submission");
+
+        let spec = build_define_spec("STUDY", &domains, &frames, &options).unwrap();
+        let list = &spec.code_lists["CL.DM.ARMNRS"];
+        assert!(list.extensible);
+        assert!(list.values.iter().any(|v| v == "SCREEN FAILURE"));
+        assert_eq!(list.extended_values, ["WITHDREW BEFORE RANDOMIZATION"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("define.xml");
+        write_define_spec(&path, &spec).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        assert!(xml.contains(
+            "<CodeListItem CodedValue=\"WITHDREW BEFORE RANDOMIZATION\" def:ExtendedValue=\"Yes\">"
+        ));
+        assert!(xml.contains("<CodeListItem CodedValue=\"SCREEN FAILURE\">"));
+    }
+
+    #[test]
+    fn test_non_extensible_codelist_unknown_value() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = df! {
+            "STUDYID" => &["STUDY", "STUDY"],
+            "DOMAIN" => &["DM", "DM"],
+            "USUBJID" => &["S-001", "S-002"],
+            "SEX" => &["F", "Q"],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("DM", dm)];
+        let options = DefineXmlOptions::new("3.4", "Submission");
+
+        let spec = build_define_spec("STUDY", &domains, &frames, &options).unwrap();
+        let sex = &spec.code_lists["CL.DM.SEX"];
+        assert!(!sex.values.contains(&"Q".to_string()));
+        assert!(sex.extended_values.is_empty());
+
+        // The value is left to validation
+        let report = crate::validate_domain(
+            domains.iter().find(|d| d.name == "DM").unwrap(),
+            &frames[0].data,
+            Some(
+                &tss_standards::ct::load(tss_standards::ct::CtVersion::default(), Some("SDTM"))
+                    .unwrap(),
+            ),
+        );
+        assert!(report.issues.iter().any(|issue| matches!(
+            issue,
+            crate::Issue::CtViolation { variable, extensible: false, .. } if variable == "SEX"
+        )));
+    }
+}
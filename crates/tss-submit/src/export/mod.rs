@@ -5,21 +5,53 @@
 //! - **XPT** (SAS Transport): Standard FDA submission format
 //! - **Dataset-XML**: CDISC Dataset-XML format for data exchange
 //! - **Define-XML**: CDISC Define-XML for metadata documentation
+//! - **ADaM Define-XML**: Define-XML for ADaM analysis datasets
+//! - **Define HTML**: Self-contained HTML rendering of the Define-XML metadata
+//! - **SAS program**: DATA step reproducing the column mapping
+//! - **R program**: dplyr/haven script reproducing the column mapping
+//!
+//! Findings domains can be partitioned into split datasets (e.g., LBCH/LBHE)
+//! with [`split_domain`] before export.
 
+mod adam_define;
 mod common;
 mod dataset_xml;
+mod define_html;
 mod define_xml;
+mod plan;
+mod program;
+mod r_program;
+mod sas;
+mod split;
 pub mod types;
 mod xpt;
 
 // Re-export public types and functions
-pub use dataset_xml::{DatasetXmlOptions, write_dataset_xml, write_dataset_xml_outputs};
-pub use define_xml::{DefineXmlOptions, write_define_xml};
-pub use types::{DomainFrame, domain_map_by_code};
-pub use xpt::{build_xpt_dataset_with_name, write_xpt_outputs};
+pub use adam_define::{AdamDefineOptions, build_adam_define_spec, write_adam_define_xml};
+pub use dataset_xml::{
+    DatasetXmlOptions, write_dataset_xml, write_dataset_xml_outputs,
+    write_dataset_xml_outputs_with_reference_data,
+};
+pub use define_html::write_define_html;
+pub use define_xml::{
+    CodeListSpec, CtStandard, DefineModel, DefineSpec, DefineXmlOptions, ItemDefSpec,
+    ItemGroupSpec, ItemOrigin, ItemRefSpec, ResultDisplaySpec, build_define_spec,
+    write_define_spec, write_define_xml,
+};
+pub use plan::{PlannedOutput, plan_dataset_xml_outputs, plan_xpt_outputs};
+pub use r_program::{generate_r_program, write_r_outputs};
+pub use sas::{generate_sas_program, render_assignment, sas_quote};
+pub use split::{SplitNaming, split_domain};
+pub use types::{DatasetNameIssue, DomainFrame, domain_map_by_code};
+pub use xpt::{
+    XptOptions, XptValueTooLong, build_xpt_dataset_with_name, build_xpt_dataset_with_options,
+    build_xpt_dataset_with_progress, validate_xpt_values, write_xpt_outputs,
+    write_xpt_outputs_with_options, write_xpt_outputs_with_progress,
+};
 
 // Re-export common utilities for external use
 pub use common::{
-    SAS_NUMERIC_LEN, VariableTypeExt, dataset_name, has_collected_data, is_expected, is_identifier,
-    is_reference_domain, is_required, normalize_study_id, should_upcase, variable_length,
+    Overlength, SAS_NUMERIC_LEN, VariableTypeExt, dataset_name, has_collected_data, is_expected,
+    is_identifier, is_reference_domain, is_required, normalize_study_id, should_upcase,
+    variable_length, variable_length_with_policy,
 };
@@ -0,0 +1,181 @@
+//! Output plans for dry-run exports.
+//!
+//! The `plan_*` functions mirror the matching `write_*` functions: they
+//! compute the paths, variable layouts and record counts the writer would
+//! produce, without creating directories or files. CI jobs and the UI use
+//! them to check an export before running it.
+
+use std::path::{Path, PathBuf};
+
+use tss_standards::{ColumnIndex, SdtmDomain};
+
+use super::types::{DomainFrame, domain_map_by_code};
+use super::xpt::{XptOptions, xpt_layout};
+use crate::error::{Result, SubmitError};
+
+/// Length of an XPT header record.
+const XPT_RECORD_LEN: u64 = 80;
+
+/// Length of an XPT NAMESTR (variable descriptor) record.
+const XPT_NAMESTR_LEN: u64 = 140;
+
+/// One file an export would write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedOutput {
+    /// Path the file would be written to.
+    pub path: PathBuf,
+    /// Dataset name (e.g., "AE", "LBCH").
+    pub dataset: String,
+    /// Variables written, in output order, with their byte lengths
+    /// (`None` where the format has no fixed length).
+    pub columns: Vec<(String, Option<usize>)>,
+    /// Number of records.
+    pub records: usize,
+    /// Estimated file size in bytes, where the format allows it.
+    pub estimated_bytes: Option<u64>,
+}
+
+/// Plan the output of [`write_xpt_outputs_with_options`].
+///
+/// The variable layout is the one the writer uses, with lengths following
+/// `options.on_overlength`. The size estimate counts the headers, one
+/// NAMESTR per variable and the observations, each rounded up to whole
+/// 80-byte records. A dataset exceeding the limits in `options` fails the
+/// plan with the same error as the export.
+///
+/// [`write_xpt_outputs_with_options`]: super::write_xpt_outputs_with_options
+pub fn plan_xpt_outputs(
+    output_dir: &Path,
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+    options: &XptOptions,
+) -> Result<Vec<PlannedOutput>> {
+    let xpt_dir = output_dir.join("xpt");
+    plan_outputs(domains, frames, |domain, frame| {
+        let dataset = frame.dataset_name();
+        let columns: Vec<(String, Option<usize>)> =
+            xpt_layout(domain, frame, options.on_overlength)?
+                .into_iter()
+                .map(|entry| (entry.variable.name.clone(), Some(entry.length)))
+                .collect();
+        let record_length = columns.iter().filter_map(|(_, len)| *len).sum::<usize>();
+        options.check(&dataset, columns.len(), record_length)?;
+
+        let records = frame.data.height();
+        let whole_records = |bytes: u64| bytes.div_ceil(XPT_RECORD_LEN) * XPT_RECORD_LEN;
+        // Library (3), member (4) and NAMESTR (1) headers, then OBS header
+        let estimated_bytes = 9 * XPT_RECORD_LEN
+            + whole_records(columns.len() as u64 * XPT_NAMESTR_LEN)
+            + whole_records(records as u64 * record_length as u64);
+
+        Ok(PlannedOutput {
+            path: xpt_dir.join(format!("{}.xpt", dataset.to_lowercase())),
+            dataset,
+            columns,
+            records,
+            estimated_bytes: Some(estimated_bytes),
+        })
+    })
+}
+
+/// Plan the output of [`write_dataset_xml_outputs`].
+///
+/// XML has no fixed record size, so no size is estimated.
+///
+/// [`write_dataset_xml_outputs`]: super::write_dataset_xml_outputs
+pub fn plan_dataset_xml_outputs(
+    output_dir: &Path,
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+) -> Result<Vec<PlannedOutput>> {
+    let xml_dir = output_dir.join("dataset-xml");
+    plan_outputs(domains, frames, |domain, frame| {
+        let dataset = frame.dataset_name();
+        let column_index = ColumnIndex::new(&frame.data);
+        let columns = domain
+            .variables
+            .iter()
+            .filter(|variable| column_index.contains(&variable.name))
+            .map(|variable| (variable.name.clone(), None))
+            .collect();
+        Ok(PlannedOutput {
+            path: xml_dir.join(format!("{}.xml", dataset.to_lowercase())),
+            dataset,
+            columns,
+            records: frame.data.height(),
+            estimated_bytes: None,
+        })
+    })
+}
+
+/// Plan one output per frame, in domain code order like the writers.
+fn plan_outputs(
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+    plan: impl Fn(&SdtmDomain, &DomainFrame) -> Result<PlannedOutput>,
+) -> Result<Vec<PlannedOutput>> {
+    let domain_lookup = domain_map_by_code(domains);
+    let mut frames_sorted: Vec<&DomainFrame> = frames.iter().collect();
+    frames_sorted.sort_by(|a, b| a.domain_code.cmp(&b.domain_code));
+    frames_sorted
+        .into_iter()
+        .map(|frame| {
+            let code = frame.domain_code.to_uppercase();
+            let domain = domain_lookup
+                .get(&code)
+                .ok_or(SubmitError::MissingDomain { domain: code })?;
+            plan(domain, frame)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::df;
+
+    #[test]
+    fn test_plan_xpt_outputs() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let data = df! {
+            "STUDYID" => &["STUDY", "STUDY", "STUDY"],
+            "DOMAIN" => &["AE", "AE", "AE"],
+            "USUBJID" => &["STUDY-001", "STUDY-001", "STUDY-002"],
+            "AESEQ" => &[1.0, 2.0, 1.0],
+        }
+        .unwrap();
+        let frames = [DomainFrame::new("AE", data)];
+        let dir = tempfile::tempdir().unwrap();
+
+        let plans =
+            plan_xpt_outputs(dir.path(), &domains, &frames, &XptOptions::default()).unwrap();
+
+        assert_eq!(plans.len(), 1);
+        let ae = &plans[0];
+        assert_eq!(ae.path, dir.path().join("xpt").join("ae.xpt"));
+        assert_eq!(ae.records, 3);
+        assert_eq!(
+            ae.columns,
+            [
+                ("STUDYID".to_string(), Some(5)),
+                ("DOMAIN".to_string(), Some(2)),
+                ("USUBJID".to_string(), Some(9)),
+                ("AESEQ".to_string(), Some(8)),
+            ]
+        );
+        // 9 header records, 4 NAMESTRs in 7 records, 72 bytes of data in 1
+        assert_eq!(ae.estimated_bytes, Some(17 * 80));
+        assert!(!dir.path().join("xpt").exists());
+
+        let strict = XptOptions::default().with_max_variables(3);
+        let result = plan_xpt_outputs(dir.path(), &domains, &frames, &strict);
+        assert!(matches!(
+            result,
+            Err(SubmitError::XptTooManyVariables {
+                count: 4,
+                max: 3,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+//! Shared logic for generated mapping programs (SAS, R).
+//!
+//! Both generators assign the same variables from the same sources; they only
+//! differ in syntax. [`build_assignments`] decides what each variable gets,
+//! and a [`CodeEmitter`] renders those decisions in a target language.
+
+use tss_standards::{SdtmDomain, SdtmVariable, VariableType};
+
+use crate::error::Result;
+use crate::map::{Mapping, MappingConfig};
+
+/// Where a generated variable takes its value from.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum AssignmentSource<'a> {
+    /// A constant string (STUDYID, DOMAIN).
+    Constant(&'a str),
+    /// A mapped source column or concatenation.
+    Mapped(&'a Mapping),
+    /// A "not collected" variable, set to missing.
+    Missing,
+}
+
+/// A variable assignment in a generated program.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Assignment<'a> {
+    pub variable: &'a SdtmVariable,
+    pub source: AssignmentSource<'a>,
+}
+
+/// Assignments for a domain in SDTMIG order.
+///
+/// STUDYID and DOMAIN are constants, mapped variables read their source
+/// columns, and "not collected" variables are missing. Omitted and unmapped
+/// variables get no assignment.
+pub(crate) fn build_assignments<'a>(
+    domain: &'a SdtmDomain,
+    config: &'a MappingConfig,
+) -> Vec<Assignment<'a>> {
+    domain
+        .variables_by_role()
+        .into_iter()
+        .filter(|variable| !config.omitted.contains(&variable.name))
+        .filter_map(|variable| {
+            let name = variable.name.as_str();
+            let source = if name == "STUDYID" {
+                AssignmentSource::Constant(&config.study_id)
+            } else if name == "DOMAIN" {
+                AssignmentSource::Constant(&config.domain_code)
+            } else if let Some(mapping) = config.mappings.iter().find(|m| m.target_variable == name)
+            {
+                AssignmentSource::Mapped(mapping)
+            } else if config.not_collected.contains_key(name) {
+                AssignmentSource::Missing
+            } else {
+                return None;
+            };
+            Some(Assignment { variable, source })
+        })
+        .collect()
+}
+
+/// Renders assignment expressions in a target language.
+pub(crate) trait CodeEmitter {
+    /// String literal for a value.
+    fn quote(&self, value: &str) -> Result<String>;
+
+    /// Reference to a source column.
+    fn column(&self, name: &str) -> Result<String>;
+
+    /// Trimmed, blank-skipping concatenation of column references.
+    fn concat(&self, columns: &[String], separator: &str) -> Result<String>;
+
+    /// Missing value of a type.
+    fn missing(&self, data_type: VariableType) -> &'static str;
+
+    /// Expression producing the value of an assignment.
+    fn value(&self, assignment: &Assignment<'_>) -> Result<String> {
+        match assignment.source {
+            AssignmentSource::Constant(value) => self.quote(value),
+            AssignmentSource::Mapped(mapping) => self.mapping(mapping),
+            AssignmentSource::Missing => {
+                Ok(self.missing(assignment.variable.data_type).to_string())
+            }
+        }
+    }
+
+    /// Expression reading a mapped column or concatenation.
+    fn mapping(&self, mapping: &Mapping) -> Result<String> {
+        match &mapping.concat {
+            Some(concat) => {
+                let columns = concat
+                    .columns
+                    .iter()
+                    .map(|c| self.column(c))
+                    .collect::<Result<Vec<_>>>()?;
+                self.concat(&columns, &concat.separator)
+            }
+            None => self.column(&mapping.source_column),
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! R program generation.
+//!
+//! Renders a dplyr pipeline that rebuilds an SDTM dataset from the raw source
+//! data frame using an exported [`MappingConfig`], mirroring the SAS DATA
+//! step (see [`generate_sas_program`](super::generate_sas_program)). Labels
+//! are set as `label` attributes and the result is written with
+//! `haven::write_xpt`. As with SAS, value-level normalization is not
+//! reproduced.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tss_standards::{SdtmDomain, VariableType};
+
+use crate::error::{Result, SubmitError};
+use crate::export::common::{dataset_name, ensure_output_dir};
+use crate::export::program::{AssignmentSource, CodeEmitter, build_assignments};
+use crate::export::types::domain_map_by_code;
+use crate::map::MappingConfig;
+
+/// R equivalent of SAS `catx`: trims values and joins the non-blank ones.
+const CATX_FUNCTION: &str = "catx <- function(sep, ...) {
+  values <- lapply(list(...), function(x) trimws(as.character(x)))
+  apply(do.call(cbind, values), 1, function(row) {
+    paste(row[!is.na(row) & row != \"\"], collapse = sep)
+  })
+}
+";
+
+/// Reserved words that cannot be used as bare R names.
+const R_RESERVED: &[&str] = &[
+    "if",
+    "else",
+    "repeat",
+    "while",
+    "function",
+    "for",
+    "next",
+    "break",
+    "TRUE",
+    "FALSE",
+    "NULL",
+    "Inf",
+    "NaN",
+    "NA",
+    "NA_integer_",
+    "NA_real_",
+    "NA_character_",
+    "in",
+];
+
+/// Generate an R script for a domain from its mapping configuration.
+///
+/// Assigns the same variables as the SAS program, in SDTMIG order, inside a
+/// `mutate()` over `source_data` (the name of the raw data frame), keeps
+/// them with `select()`, and writes `<dataset>.xpt` in transport version 5.
+pub fn generate_r_program(
+    domain: &SdtmDomain,
+    config: &MappingConfig,
+    source_data: &str,
+) -> Result<String> {
+    let dataset = dataset_name(domain);
+    let object = dataset.to_lowercase();
+    let assignments = build_assignments(domain, config);
+
+    let mut values = Vec::new();
+    let mut labels = Vec::new();
+    for assignment in &assignments {
+        let name = assignment.variable.name.as_str();
+        values.push(format!("{name} = {}", REmitter.value(assignment)?));
+        if let Some(label) = assignment
+            .variable
+            .label
+            .as_deref()
+            .filter(|l| !l.is_empty())
+        {
+            labels.push(format!(
+                "attr({object}${name}, \"label\") <- {}",
+                REmitter.quote(label)?
+            ));
+        }
+    }
+    let uses_catx = assignments.iter().any(|assignment| {
+        matches!(assignment.source, AssignmentSource::Mapped(mapping) if mapping.concat.is_some())
+    });
+
+    // A line break in the study id would end the comment
+    let mut program = format!(
+        "# {} - generated from mapping for study {}\n",
+        domain.name,
+        config.study_id.replace(['\n', '\r'], " ")
+    );
+    program.push_str("library(dplyr)\nlibrary(haven)\n\n");
+    if uses_catx {
+        program.push_str(CATX_FUNCTION);
+        program.push('\n');
+    }
+    program.push_str(&format!("{object} <- {source_data} |>\n  mutate(\n"));
+    program.push_str(
+        &values
+            .iter()
+            .map(|value| format!("    {value}"))
+            .collect::<Vec<_>>()
+            .join(",\n"),
+    );
+    program.push_str("\n  ) |>\n");
+    let names: Vec<&str> = assignments
+        .iter()
+        .map(|assignment| assignment.variable.name.as_str())
+        .collect();
+    program.push_str(&format!("  select({})\n\n", names.join(", ")));
+    for label in &labels {
+        program.push_str(&format!("{label}\n"));
+    }
+    if !labels.is_empty() {
+        program.push('\n');
+    }
+    program.push_str(&format!(
+        "write_xpt({object}, \"{object}.xpt\", version = 5, name = \"{dataset}\")\n"
+    ));
+    Ok(program)
+}
+
+/// Write an R script per mapping configuration.
+///
+/// Scripts are written to `output_dir/r/<dataset>.R` and read the raw data
+/// frame `raw_<dataset>`.
+pub fn write_r_outputs(
+    output_dir: &Path,
+    domains: &[SdtmDomain],
+    configs: &[MappingConfig],
+) -> Result<Vec<PathBuf>> {
+    let domain_lookup = domain_map_by_code(domains);
+    let mut configs_sorted: Vec<&MappingConfig> = configs.iter().collect();
+    configs_sorted.sort_by(|a, b| a.domain_code.cmp(&b.domain_code));
+
+    let r_dir = ensure_output_dir(output_dir, "r")?;
+
+    let mut outputs = Vec::new();
+    for config in configs_sorted {
+        let code = config.domain_code.to_uppercase();
+        let domain = domain_lookup
+            .get(&code)
+            .ok_or_else(|| SubmitError::MissingDomain {
+                domain: code.clone(),
+            })?;
+        let disk_name = dataset_name(domain).to_lowercase();
+        let program = generate_r_program(domain, config, &format!("raw_{disk_name}"))?;
+        let path = r_dir.join(format!("{disk_name}.R"));
+        fs::write(&path, program)
+            .map_err(|e| SubmitError::write_error("R", path.display().to_string(), e))?;
+        outputs.push(path);
+    }
+    Ok(outputs)
+}
+
+/// Emits R expressions.
+struct REmitter;
+
+impl CodeEmitter for REmitter {
+    /// Double-quoted R string with backslash escapes.
+    fn quote(&self, value: &str) -> Result<String> {
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('"');
+        for ch in value.chars() {
+            match ch {
+                '\\' => quoted.push_str("\\\\"),
+                '"' => quoted.push_str("\\\""),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                _ => quoted.push(ch),
+            }
+        }
+        quoted.push('"');
+        Ok(quoted)
+    }
+
+    /// Reference a source column, backtick-quoting non-syntactic names.
+    fn column(&self, name: &str) -> Result<String> {
+        let mut chars = name.chars();
+        let is_syntactic = match chars.next() {
+            Some(c) if c.is_ascii_alphabetic() => true,
+            Some('.') => !chars.next().is_some_and(|c| c.is_ascii_digit()),
+            _ => false,
+        } && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+            && !R_RESERVED.contains(&name);
+        if is_syntactic {
+            Ok(name.to_string())
+        } else {
+            Ok(format!(
+                "`{}`",
+                name.replace('\\', "\\\\").replace('`', "\\`")
+            ))
+        }
+    }
+
+    fn concat(&self, columns: &[String], separator: &str) -> Result<String> {
+        Ok(format!(
+            "catx({}, {})",
+            self.quote(separator)?,
+            columns.join(", ")
+        ))
+    }
+
+    fn missing(&self, data_type: VariableType) -> &'static str {
+        match data_type {
+            VariableType::Num => "NA_real_",
+            VariableType::Char => "\"\"",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{ColumnConcat, Mapping};
+    use std::collections::{BTreeMap, BTreeSet};
+    use tss_standards::SdtmVariable;
+    use tss_standards::sdtm::test_support::{self, domain};
+
+    const GOLDEN: &str = include_str!("../../tests/data/cm.R");
+
+    fn variable(name: &str, data_type: VariableType, label: Option<&str>) -> SdtmVariable {
+        SdtmVariable {
+            label: label.map(str::to_string),
+            ..test_support::variable(name).data_type(data_type).build()
+        }
+    }
+
+    fn mapping(target: &str, source: &str, concat: Option<ColumnConcat>) -> Mapping {
+        Mapping {
+            source_column: source.to_string(),
+            target_variable: target.to_string(),
+            confidence: 1.0,
+            concat,
+        }
+    }
+
+    fn cm_domain() -> SdtmDomain {
+        domain("CM")
+            .variables([
+                variable("STUDYID", VariableType::Char, Some("Study Identifier")),
+                variable("DOMAIN", VariableType::Char, Some("Domain Abbreviation")),
+                variable("USUBJID", VariableType::Char, None),
+                variable("CMTRT", VariableType::Char, Some("Reported Name of Drug")),
+                variable("CMDOSE", VariableType::Num, Some("Dose per Administration")),
+                variable("CMDOSU", VariableType::Char, None),
+            ])
+            .build()
+    }
+
+    fn cm_config() -> MappingConfig {
+        MappingConfig {
+            domain_code: "CM".to_string(),
+            study_id: "O'Brien \"01\"".to_string(),
+            mappings: vec![
+                mapping("USUBJID", "Subject ID", None),
+                mapping(
+                    "CMTRT",
+                    "DRUG",
+                    Some(ColumnConcat::new(["DRUG", "STRENGTH"], " ")),
+                ),
+            ],
+            not_collected: BTreeMap::from([("CMDOSE".to_string(), "Not recorded".to_string())]),
+            omitted: BTreeSet::from(["CMDOSU".to_string()]),
+        }
+    }
+
+    #[test]
+    fn test_generate_r_program_golden() {
+        let program = generate_r_program(&cm_domain(), &cm_config(), "raw_cm").unwrap();
+        assert_eq!(program, GOLDEN);
+    }
+
+    #[test]
+    fn test_write_r_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_r_outputs(dir.path(), &[cm_domain()], &[cm_config()]).unwrap();
+        assert_eq!(paths, [dir.path().join("r").join("cm.R")]);
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), GOLDEN);
+
+        let unknown = MappingConfig {
+            domain_code: "XX".to_string(),
+            ..cm_config()
+        };
+        assert!(matches!(
+            write_r_outputs(dir.path(), &[cm_domain()], &[unknown]),
+            Err(SubmitError::MissingDomain { .. })
+        ));
+    }
+}
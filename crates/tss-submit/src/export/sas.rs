@@ -0,0 +1,345 @@
+//! SAS program generation.
+//!
+//! Renders a DATA step that rebuilds an SDTM dataset from the raw source
+//! dataset using an exported [`MappingConfig`], so the mapping can be reviewed
+//! or re-run in SAS. An `attrib` statement declares each variable's length,
+//! label and, for date variables, ISO 8601 format so the output dataset is
+//! labeled like a submission dataset. Value-level normalization (ISO 8601
+//! dates, CT) is not reproduced; the program covers column assignment only.
+//!
+//! Every string literal goes through [`sas_quote`], which emits single-quoted
+//! literals so `&` and `%` in values are not resolved as macro references.
+
+use tss_standards::{SdtmDomain, SdtmVariable, VariableType};
+
+use crate::error::{Result, SubmitError};
+use crate::export::common::{SAS_NUMERIC_LEN, dataset_name};
+use crate::export::program::{CodeEmitter, build_assignments};
+use crate::map::{Mapping, MappingConfig};
+
+/// Width of a complete ISO 8601 datetime to the second (YYYY-MM-DDThh:mm:ss).
+const ISO8601_DATETIME_LEN: u32 = 19;
+
+/// Generate a SAS DATA step for a domain from its mapping configuration.
+///
+/// Variables are assigned in SDTMIG order (see
+/// [`SdtmDomain::variables_by_role`]): STUDYID and DOMAIN as constants,
+/// mapped variables from their source columns, and "not collected" variables
+/// as missing. Omitted and unmapped variables are left out of the `keep` list
+/// and the `attrib` statement.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::SasLiteral`] if the study id, a label or a source
+/// column name contains a line break.
+pub fn generate_sas_program(
+    domain: &SdtmDomain,
+    config: &MappingConfig,
+    source_dataset: &str,
+) -> Result<String> {
+    let mut assignments = Vec::new();
+    let mut attribs = Vec::new();
+    let mut keep = Vec::new();
+
+    for assignment in build_assignments(domain, config) {
+        let name = assignment.variable.name.as_str();
+        assignments.push(format!("{name} = {};", SasEmitter.value(&assignment)?));
+        attribs.push(render_attrib(assignment.variable)?);
+        keep.push(name);
+    }
+
+    let mut program = format!(
+        "/* {} - generated from mapping for study {} */\n",
+        domain.name, config.study_id
+    );
+    program.push_str(&format!("data {};\n", dataset_name(domain).to_lowercase()));
+    // Attributes must precede `set` so lengths are not taken from the source
+    if !attribs.is_empty() {
+        program.push_str("    attrib\n");
+        for attrib in &attribs {
+            program.push_str(&format!("        {attrib}\n"));
+        }
+        program.push_str("    ;\n");
+    }
+    program.push_str(&format!("    set {source_dataset};\n"));
+    for assignment in &assignments {
+        program.push_str(&format!("    {assignment}\n"));
+    }
+    if !keep.is_empty() {
+        program.push_str(&format!("    keep {};\n", keep.join(" ")));
+    }
+    program.push_str("run;\n");
+    Ok(program)
+}
+
+/// Render the assignment statement for a single mapping.
+///
+/// Concatenations use `catx`, which trims values and skips blanks the same
+/// way as the preview builders.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::SasLiteral`] if the separator or a non-standard
+/// column name contains a line break.
+pub fn render_assignment(mapping: &Mapping) -> Result<String> {
+    Ok(format!(
+        "{} = {};",
+        mapping.target_variable,
+        SasEmitter.mapping(mapping)?
+    ))
+}
+
+/// Emits SAS expressions.
+struct SasEmitter;
+
+impl CodeEmitter for SasEmitter {
+    fn quote(&self, value: &str) -> Result<String> {
+        sas_quote(value)
+    }
+
+    /// Reference a source column, using a name literal for non-standard names.
+    fn column(&self, name: &str) -> Result<String> {
+        let is_sas_name = name.len() <= 32
+            && name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_sas_name {
+            Ok(name.to_string())
+        } else {
+            Ok(format!("{}n", sas_quote(name)?))
+        }
+    }
+
+    fn concat(&self, columns: &[String], separator: &str) -> Result<String> {
+        Ok(format!(
+            "catx({}, {})",
+            sas_quote(separator)?,
+            columns.join(", ")
+        ))
+    }
+
+    fn missing(&self, data_type: VariableType) -> &'static str {
+        match data_type {
+            VariableType::Num => ".",
+            VariableType::Char => "''",
+        }
+    }
+}
+
+/// Quote a value as a SAS string literal.
+///
+/// Produces a single-quoted literal with embedded single quotes doubled
+/// (`O'Brien` becomes `'O''Brien'`). Single quotes also keep SAS from
+/// resolving `&` and `%` in the value as macro references.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::SasLiteral`] if the value contains a line break,
+/// which cannot appear in a SAS literal.
+pub fn sas_quote(value: &str) -> Result<String> {
+    if value.contains(['\n', '\r']) {
+        return Err(SubmitError::SasLiteral {
+            value: value.to_string(),
+        });
+    }
+    Ok(format!("'{}'", value.replace('\'', "''")))
+}
+
+/// Render one variable's entry in the `attrib` statement.
+///
+/// Numeric variables are 8 bytes; character variables use the length from
+/// the standard when it defines one.
+fn render_attrib(variable: &SdtmVariable) -> Result<String> {
+    let mut attrib = variable.name.clone();
+    match variable.data_type {
+        VariableType::Num => attrib.push_str(&format!(" length={SAS_NUMERIC_LEN}")),
+        VariableType::Char => {
+            if let Some(length) = variable.length {
+                attrib.push_str(&format!(" length=${length}"));
+            }
+        }
+    }
+    if let Some(label) = variable.label.as_deref().filter(|l| !l.is_empty()) {
+        attrib.push_str(&format!(" label={}", sas_quote(label)?));
+    }
+    if let Some(format) = date_format(variable) {
+        attrib.push_str(&format!(" format={format} informat={format}"));
+    }
+    Ok(attrib)
+}
+
+/// SAS format for an ISO 8601 date variable.
+///
+/// SDTM --DTC variables are ISO 8601 text and keep a character format of
+/// their length; numeric --DT, --DTM and --TM variables use the matching
+/// E8601 format.
+fn date_format(variable: &SdtmVariable) -> Option<String> {
+    let name = variable.name.to_uppercase();
+    match variable.data_type {
+        VariableType::Char if name.ends_with("DTC") => Some(format!(
+            "${}.",
+            variable.length.unwrap_or(ISO8601_DATETIME_LEN)
+        )),
+        VariableType::Char => None,
+        VariableType::Num if name.ends_with("DTM") => Some("E8601DT19.".to_string()),
+        VariableType::Num if name.ends_with("DT") => Some("E8601DA10.".to_string()),
+        VariableType::Num if name.ends_with("TM") => Some("E8601TM8.".to_string()),
+        VariableType::Num => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::ColumnConcat;
+    use std::collections::{BTreeMap, BTreeSet};
+    use tss_standards::SdtmVariable;
+    use tss_standards::sdtm::test_support::{self, domain};
+
+    fn variable(name: &str, data_type: VariableType) -> SdtmVariable {
+        test_support::variable(name).data_type(data_type).build()
+    }
+
+    fn mapping(target: &str, source: &str, concat: Option<ColumnConcat>) -> Mapping {
+        Mapping {
+            source_column: source.to_string(),
+            target_variable: target.to_string(),
+            confidence: 1.0,
+            concat,
+        }
+    }
+
+    #[test]
+    fn test_render_assignment_concat() {
+        let concat = ColumnConcat::new(["FIRST_NAME", "Last Name"], " ");
+        let rendered = render_assignment(&mapping("CMTRT", "FIRST_NAME", Some(concat))).unwrap();
+        assert_eq!(rendered, "CMTRT = catx(' ', FIRST_NAME, 'Last Name'n);");
+
+        let rendered = render_assignment(&mapping("CMDOSE", "DOSE", None)).unwrap();
+        assert_eq!(rendered, "CMDOSE = DOSE;");
+    }
+
+    #[test]
+    fn test_generate_sas_program() {
+        let domain = domain("CM")
+            .variables([
+                variable("STUDYID", VariableType::Char),
+                variable("DOMAIN", VariableType::Char),
+                variable("CMTRT", VariableType::Char),
+                variable("CMDOSE", VariableType::Num),
+                variable("CMDOSU", VariableType::Char),
+            ])
+            .build();
+        let config = MappingConfig {
+            domain_code: "CM".to_string(),
+            study_id: "CDISC01".to_string(),
+            mappings: vec![mapping(
+                "CMTRT",
+                "DRUG",
+                Some(ColumnConcat::new(["DRUG", "STRENGTH"], " ")),
+            )],
+            not_collected: BTreeMap::from([("CMDOSE".to_string(), "Not recorded".to_string())]),
+            omitted: BTreeSet::from(["CMDOSU".to_string()]),
+        };
+
+        let program = generate_sas_program(&domain, &config, "raw.cm").unwrap();
+
+        assert!(program.contains("data cm;\n"));
+        assert!(program.contains("    ;\n    set raw.cm;\n"));
+        assert!(program.contains("    STUDYID = 'CDISC01';\n"));
+        assert!(program.contains("    CMTRT = catx(' ', DRUG, STRENGTH);\n"));
+        assert!(program.contains("    CMDOSE = .;\n"));
+        assert!(program.contains("    keep STUDYID DOMAIN CMTRT CMDOSE;\n"));
+        assert!(!program.contains("CMDOSU"));
+        assert!(program.ends_with("run;\n"));
+    }
+
+    #[test]
+    fn test_generate_sas_program_attrib() {
+        let labeled = |name: &str, data_type, length, label: &str| SdtmVariable {
+            length,
+            ..test_support::variable(name)
+                .data_type(data_type)
+                .label(label)
+                .build()
+        };
+        let domain = domain("AE")
+            .variables([
+                labeled("STUDYID", VariableType::Char, Some(12), "Study Identifier"),
+                labeled(
+                    "AETERM",
+                    VariableType::Char,
+                    Some(200),
+                    "Reported Term for the \"Adverse\" Event",
+                ),
+                labeled("AESTDTC", VariableType::Char, None, "Start Date/Time"),
+                labeled("AESTDY", VariableType::Num, None, "Study Day of Start"),
+                variable("AESPID", VariableType::Char),
+            ])
+            .build();
+        let config = MappingConfig {
+            domain_code: "AE".to_string(),
+            study_id: "CDISC01".to_string(),
+            mappings: vec![
+                mapping("AETERM", "TERM", None),
+                mapping("AESTDTC", "START", None),
+                mapping("AESTDY", "DAY", None),
+                mapping("AESPID", "SPID", None),
+            ],
+            not_collected: BTreeMap::new(),
+            omitted: BTreeSet::new(),
+        };
+
+        let program = generate_sas_program(&domain, &config, "raw.ae").unwrap();
+
+        let expected = [
+            "data ae;",
+            "    attrib",
+            "        STUDYID length=$12 label='Study Identifier'",
+            "        AESPID",
+            "        AETERM length=$200 label='Reported Term for the \"Adverse\" Event'",
+            "        AESTDTC label='Start Date/Time' format=$19. informat=$19.",
+            "        AESTDY length=8 label='Study Day of Start'",
+            "    ;",
+            "    set raw.ae;",
+        ]
+        .join("\n");
+        assert!(program.contains(&expected), "{program}");
+    }
+
+    #[test]
+    fn test_sas_quote() {
+        assert_eq!(sas_quote("O'Brien-01").unwrap(), "'O''Brien-01'");
+        assert_eq!(
+            sas_quote("Subject's \"Best\" Response").unwrap(),
+            "'Subject''s \"Best\" Response'"
+        );
+        assert_eq!(sas_quote("R&D 100%").unwrap(), "'R&D 100%'");
+        assert!(matches!(
+            sas_quote("line\nbreak"),
+            Err(SubmitError::SasLiteral { .. })
+        ));
+
+        let domain = domain("DM")
+            .variable(test_support::variable("STUDYID").label("Investigator's Name"))
+            .build();
+        let config = MappingConfig {
+            domain_code: "DM".to_string(),
+            study_id: "O'Brien-01".to_string(),
+            mappings: Vec::new(),
+            not_collected: BTreeMap::new(),
+            omitted: BTreeSet::new(),
+        };
+        let program = generate_sas_program(&domain, &config, "raw.dm").unwrap();
+        assert!(program.contains("STUDYID label='Investigator''s Name'\n"));
+        assert!(program.contains("    STUDYID = 'O''Brien-01';\n"));
+
+        let config = MappingConfig {
+            study_id: "STUDY\n01".to_string(),
+            ..config
+        };
+        assert!(generate_sas_program(&domain, &config, "raw.dm").is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Splitting a domain into several datasets.
+//!
+//! Large findings domains may be submitted as split datasets, one per value
+//! of a category variable (e.g., LB split by LBCAT into LBCH and LBHE). Each
+//! split keeps the base domain code, so it is exported against the base
+//! domain definition, and carries its own dataset name. Rows are partitioned
+//! without renumbering, so --SEQ stays unique per subject across the splits.
+
+use std::collections::BTreeMap;
+
+use polars::prelude::{BooleanChunked, NewChunkedArray};
+use tss_standards::any_to_string;
+
+use crate::error::{Result, SubmitError};
+
+use super::types::DomainFrame;
+
+/// Maximum length of a split dataset name: the domain code plus up to two
+/// characters (SDTMIG 4.1.7).
+const SPLIT_NAME_MAX_LEN: usize = 4;
+
+/// How split datasets are named.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitNaming {
+    /// Domain code followed by the first two letters or digits of the
+    /// category value (e.g., "CHEMISTRY" in LB becomes LBCH).
+    Abbreviate,
+    /// Domain code followed by an explicit suffix (up to two characters) per
+    /// category value.
+    Suffixes(BTreeMap<String, String>),
+}
+
+impl SplitNaming {
+    /// Dataset name for a category value of `domain_code`.
+    fn dataset_name(&self, domain_code: &str, value: &str) -> Option<String> {
+        let suffix = match self {
+            Self::Abbreviate => value
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .take(2)
+                .collect::<String>(),
+            Self::Suffixes(suffixes) => suffixes.get(value)?.clone(),
+        };
+        if suffix.is_empty() {
+            return None;
+        }
+        Some(format!("{}{}", domain_code, suffix).to_uppercase())
+    }
+}
+
+/// Partition a domain into split datasets by a category variable.
+///
+/// Rows are grouped by the value of `by` (e.g., "LBCAT"); each group becomes
+/// a [`DomainFrame`] with the base domain code and a dataset name from
+/// `naming`. Splits are returned in dataset name order, and rows keep their
+/// original order and --SEQ values.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::ColumnNotFound`] if `by` is missing, and
+/// [`SubmitError::InvalidConfig`] if a row has no category value, a value
+/// has no dataset name, two values map to the same name, or a name is longer
+/// than 4 characters.
+pub fn split_domain(
+    frame: &DomainFrame,
+    by: &str,
+    naming: &SplitNaming,
+) -> Result<Vec<DomainFrame>> {
+    let domain_code = frame.base_domain_code().to_uppercase();
+    let column = frame
+        .data
+        .column(by)
+        .map_err(|_| SubmitError::ColumnNotFound {
+            column: by.to_string(),
+        })?;
+
+    let values: Vec<String> = (0..frame.data.height())
+        .map(|idx| {
+            column
+                .get(idx)
+                .map(|value| any_to_string(value).trim().to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+    if let Some(row) = values.iter().position(String::is_empty) {
+        return Err(SubmitError::InvalidConfig {
+            message: format!(
+                "Cannot split {domain_code}: row {} has no {by} value",
+                row + 1
+            ),
+        });
+    }
+
+    // Dataset name -> category value, checked for collisions
+    let mut names: BTreeMap<String, &str> = BTreeMap::new();
+    let mut name_by_value: BTreeMap<&str, String> = BTreeMap::new();
+    for value in &values {
+        if name_by_value.contains_key(value.as_str()) {
+            continue;
+        }
+        let name =
+            naming
+                .dataset_name(&domain_code, value)
+                .ok_or_else(|| SubmitError::InvalidConfig {
+                    message: format!("No split dataset name for {by} value '{value}'"),
+                })?;
+        if name.len() > SPLIT_NAME_MAX_LEN {
+            return Err(SubmitError::InvalidConfig {
+                message: format!(
+                    "Split dataset name '{name}' exceeds {SPLIT_NAME_MAX_LEN} characters"
+                ),
+            });
+        }
+        if let Some(existing) = names.insert(name.clone(), value) {
+            return Err(SubmitError::InvalidConfig {
+                message: format!(
+                    "{by} values '{existing}' and '{value}' both split into dataset '{name}'"
+                ),
+            });
+        }
+        name_by_value.insert(value, name);
+    }
+
+    names
+        .into_iter()
+        .map(|(name, category)| {
+            let mask: Vec<bool> = values.iter().map(|value| value == category).collect();
+            let data = frame
+                .data
+                .filter(&BooleanChunked::from_slice("split".into(), &mask))?;
+            Ok(
+                DomainFrame::with_dataset_name(domain_code.clone(), data, name)
+                    .with_formats(frame.formats.clone()),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::{Issue, load_default_rules, validate_cross_domain};
+    use polars::prelude::*;
+
+    fn lb_frame() -> DomainFrame {
+        let data = df! {
+            "USUBJID" => &["S-001", "S-001", "S-001", "S-002", "S-002"],
+            "LBSEQ" => &[1i64, 2, 3, 1, 2],
+            "LBCAT" => &["CHEMISTRY", "HEMATOLOGY", "CHEMISTRY", "HEMATOLOGY", "CHEMISTRY"],
+            "LBTESTCD" => &["ALT", "HGB", "AST", "WBC", "ALT"],
+        }
+        .unwrap();
+        DomainFrame::new("LB", data)
+    }
+
+    #[test]
+    fn test_split_domain_by_category() {
+        let splits = split_domain(&lb_frame(), "LBCAT", &SplitNaming::Abbreviate).unwrap();
+        assert_eq!(splits.len(), 2);
+
+        let chemistry = &splits[0];
+        assert_eq!(chemistry.domain_code, "LB");
+        assert_eq!(chemistry.dataset_name(), "LBCH");
+        assert_eq!(chemistry.data.height(), 3);
+        let seqs: Vec<Option<i64>> = chemistry
+            .data
+            .column("LBSEQ")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(seqs, [Some(1), Some(3), Some(2)]);
+
+        let hematology = &splits[1];
+        assert_eq!(hematology.dataset_name(), "LBHE");
+        assert_eq!(hematology.data.height(), 2);
+
+        // --SEQ stays unique per subject across the splits
+        let dm = df! { "USUBJID" => &["S-001", "S-002"] }.unwrap();
+        let mut domains: Vec<(String, &DataFrame)> = vec![("DM".to_string(), &dm)];
+        domains.extend(
+            splits
+                .iter()
+                .map(|split| (split.dataset_name(), &split.data)),
+        );
+        let domains: Vec<(&str, &DataFrame)> = domains
+            .iter()
+            .map(|(name, df)| (name.as_str(), *df))
+            .collect();
+        let results = validate_cross_domain(&domains, &load_default_rules());
+        assert!(results.iter().all(|(_, issues)| {
+            !issues
+                .iter()
+                .any(|issue| matches!(issue, Issue::DuplicateSequence { .. }))
+        }));
+    }
+
+    #[test]
+    fn test_split_domain_naming() {
+        let suffixes = SplitNaming::Suffixes(BTreeMap::from([
+            ("CHEMISTRY".to_string(), "C1".to_string()),
+            ("HEMATOLOGY".to_string(), "H1".to_string()),
+        ]));
+        let splits = split_domain(&lb_frame(), "LBCAT", &suffixes).unwrap();
+        let names: Vec<String> = splits.iter().map(DomainFrame::dataset_name).collect();
+        assert_eq!(names, ["LBC1", "LBH1"]);
+
+        // Split names are limited to 4 characters
+        let long = SplitNaming::Suffixes(BTreeMap::from([
+            ("CHEMISTRY".to_string(), "CHEM".to_string()),
+            ("HEMATOLOGY".to_string(), "HEMA".to_string()),
+        ]));
+        assert!(matches!(
+            split_domain(&lb_frame(), "LBCAT", &long),
+            Err(SubmitError::InvalidConfig { .. })
+        ));
+
+        let partial = SplitNaming::Suffixes(BTreeMap::from([(
+            "CHEMISTRY".to_string(),
+            "CH".to_string(),
+        )]));
+        assert!(matches!(
+            split_domain(&lb_frame(), "LBCAT", &partial),
+            Err(SubmitError::InvalidConfig { .. })
+        ));
+
+        let colliding = SplitNaming::Suffixes(BTreeMap::from([
+            ("CHEMISTRY".to_string(), "CH".to_string()),
+            ("HEMATOLOGY".to_string(), "CH".to_string()),
+        ]));
+        assert!(matches!(
+            split_domain(&lb_frame(), "LBCAT", &colliding),
+            Err(SubmitError::InvalidConfig { .. })
+        ));
+
+        assert!(matches!(
+            split_domain(&lb_frame(), "LBSCAT", &SplitNaming::Abbreviate),
+            Err(SubmitError::ColumnNotFound { .. })
+        ));
+    }
+}
@@ -1,10 +1,40 @@
 //! Output-related types for SDTM data export.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 
 use polars::prelude::DataFrame;
 use tss_standards::SdtmDomain;
 
+/// Maximum length of a SAS V5 transport dataset name.
+const MAX_DATASET_NAME_LEN: usize = 8;
+
+/// A dataset name that doesn't fit SAS V5 transport naming rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatasetNameIssue {
+    /// Name is longer than 8 characters; `SASDatasetName` in Define-XML
+    /// is truncated and no longer matches the file name.
+    TooLong { name: String, length: usize },
+    /// Name isn't an uppercase letter followed by uppercase letters or
+    /// digits.
+    InvalidCharacters { name: String },
+}
+
+impl fmt::Display for DatasetNameIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLong { name, length } => write!(
+                f,
+                "Dataset name '{name}' has {length} characters, exceeding the SAS limit of {MAX_DATASET_NAME_LEN}"
+            ),
+            Self::InvalidCharacters { name } => write!(
+                f,
+                "Dataset name '{name}' must be uppercase letters and digits, starting with a letter"
+            ),
+        }
+    }
+}
+
 /// A transformed domain with its data.
 ///
 /// Used for output generation (XPT, SAS, XML).
@@ -19,6 +49,10 @@ pub struct DomainFrame {
     /// Optional dataset name (for split domains like LBCH, FAAE).
     /// If None, uses domain_code.
     pub dataset_name_override: Option<String>,
+
+    /// SAS display formats by variable name (e.g., "DATE9."), written to
+    /// XPT. Only set explicitly; formats of source columns are not copied.
+    pub formats: BTreeMap<String, String>,
 }
 
 impl DomainFrame {
@@ -28,6 +62,7 @@ pub fn new(domain_code: impl Into<String>, data: DataFrame) -> Self {
             domain_code: domain_code.into(),
             data,
             dataset_name_override: None,
+            formats: BTreeMap::new(),
         }
     }
 
@@ -41,9 +76,17 @@ pub fn with_dataset_name(
             domain_code: domain_code.into(),
             data,
             dataset_name_override: Some(dataset_name.into()),
+            formats: BTreeMap::new(),
         }
     }
 
+    /// Set the SAS display formats of variables.
+    #[must_use]
+    pub fn with_formats(mut self, formats: BTreeMap<String, String>) -> Self {
+        self.formats = formats;
+        self
+    }
+
     /// Get the dataset name for output.
     /// Uses `dataset_name_override` if set, otherwise `domain_code`.
     pub fn dataset_name(&self) -> String {
@@ -52,6 +95,37 @@ pub fn dataset_name(&self) -> String {
             .unwrap_or_else(|| self.domain_code.clone())
     }
 
+    /// Check the dataset name against SAS V5 transport naming rules.
+    ///
+    /// Returns every rule the name breaks; an empty list means the name is
+    /// valid. Exporters log these as warnings.
+    pub fn validate_dataset_name(&self) -> Vec<DatasetNameIssue> {
+        let name = self.dataset_name();
+        let mut issues = Vec::new();
+        let length = name.chars().count();
+        if length > MAX_DATASET_NAME_LEN {
+            issues.push(DatasetNameIssue::TooLong {
+                name: name.clone(),
+                length,
+            });
+        }
+        let starts_with_letter = name.chars().next().is_some_and(|c| c.is_ascii_uppercase());
+        let all_valid = name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+        if !starts_with_letter || !all_valid {
+            issues.push(DatasetNameIssue::InvalidCharacters { name });
+        }
+        issues
+    }
+
+    /// Log a warning for each dataset name issue.
+    pub(crate) fn warn_dataset_name(&self) {
+        for issue in self.validate_dataset_name() {
+            tracing::warn!(domain = %self.domain_code, "{issue}");
+        }
+    }
+
     /// Get the base domain code.
     /// For split domains (e.g., "LBCH"), returns the parent domain ("LB").
     /// For regular domains, returns the domain_code.
@@ -73,3 +147,36 @@ pub fn base_domain_code(&self) -> &str {
 pub fn domain_map_by_code(domains: &[SdtmDomain]) -> HashMap<String, &SdtmDomain> {
     domains.iter().map(|d| (d.name.to_uppercase(), d)).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_dataset_name() {
+        let valid = DomainFrame::with_dataset_name("LB", DataFrame::empty(), "LBCH");
+        assert!(valid.validate_dataset_name().is_empty());
+
+        let long = DomainFrame::with_dataset_name("LB", DataFrame::empty(), "LBCHEMISTR");
+        assert_eq!(
+            long.validate_dataset_name(),
+            [DatasetNameIssue::TooLong {
+                name: "LBCHEMISTR".to_string(),
+                length: 10,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_dataset_name_lowercase() {
+        let frame = DomainFrame::with_dataset_name("LB", DataFrame::empty(), "lbch");
+        let issues = frame.validate_dataset_name();
+        assert_eq!(
+            issues,
+            [DatasetNameIssue::InvalidCharacters {
+                name: "lbch".to_string(),
+            }]
+        );
+        assert!(issues[0].to_string().contains("uppercase"));
+    }
+}
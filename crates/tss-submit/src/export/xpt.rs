@@ -2,21 +2,143 @@
 
 use std::path::{Path, PathBuf};
 
-use polars::prelude::{AnyValue, DataFrame};
+use polars::prelude::AnyValue;
 
 use super::types::{DomainFrame, domain_map_by_code};
 use crate::error::{Result, SubmitError};
-use tss_standards::{SdtmDomain, VariableType};
-use tss_standards::{any_to_f64, any_to_string};
-use xportrs::{Column, ColumnData, Dataset, Xpt};
+use crate::progress::{CancellationToken, ProgressEvent, ProgressStage};
+use tss_standards::{ColumnIndex, SdtmDomain, SdtmVariable, VariableType};
+use tss_standards::{any_to_f64, any_to_string_for_output};
+use xportrs::{Column, ColumnData, Dataset, Format, Xpt};
 
-use super::common::{ensure_output_dir, variable_length};
+use super::common::{Overlength, column_length, column_length_with_policy, ensure_output_dir};
+
+/// Limits checked before an XPT file is written.
+///
+/// SAS Transport v5 itself allows up to 9999 variables per dataset, but
+/// review tools and downstream SAS installations often accept less. Setting
+/// a limit makes the export fail up front instead of producing a file that
+/// is rejected later. All limits are off by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XptOptions {
+    /// Maximum observation length in bytes (sum of all variable lengths).
+    pub max_record_length: Option<usize>,
+    /// Maximum number of variables per dataset.
+    pub max_variables: Option<usize>,
+    /// Handling of character values longer than their declared length
+    /// (see [`validate_xpt_values`]).
+    pub on_overlength: Overlength,
+    /// Token checked before each batch of rows; once cancelled, the export
+    /// fails with [`SubmitError::Cancelled`] before writing the dataset.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl XptOptions {
+    /// Fail datasets whose observations are longer than `length` bytes.
+    #[must_use]
+    pub fn with_max_record_length(mut self, length: usize) -> Self {
+        self.max_record_length = Some(length);
+        self
+    }
+
+    /// Fail datasets with more than `count` variables.
+    #[must_use]
+    pub fn with_max_variables(mut self, count: usize) -> Self {
+        self.max_variables = Some(count);
+        self
+    }
+
+    /// Set the handling of over-length character values.
+    #[must_use]
+    pub fn with_on_overlength(mut self, policy: Overlength) -> Self {
+        self.on_overlength = policy;
+        self
+    }
+
+    /// Stop the export when `token` is cancelled.
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<()> {
+        match &self.cancellation {
+            Some(token) if token.is_cancelled() => Err(SubmitError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check a dataset layout against the limits.
+    pub(super) fn check(
+        &self,
+        dataset: &str,
+        variables: usize,
+        record_length: usize,
+    ) -> Result<()> {
+        if let Some(max) = self.max_variables
+            && variables > max
+        {
+            return Err(SubmitError::XptTooManyVariables {
+                dataset: dataset.to_string(),
+                count: variables,
+                max,
+            });
+        }
+        if let Some(max) = self.max_record_length
+            && record_length > max
+        {
+            return Err(SubmitError::XptRecordTooLong {
+                dataset: dataset.to_string(),
+                length: record_length,
+                max,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Rows converted between [`ProgressEvent::RowsProcessed`] reports.
+const PROGRESS_BATCH_ROWS: usize = 10_000;
 
 /// Write XPT outputs for all domains.
+///
+/// Datasets are written in domain code order.
 pub fn write_xpt_outputs(
     output_dir: &Path,
     domains: &[SdtmDomain],
     frames: &[DomainFrame],
+) -> Result<Vec<PathBuf>> {
+    write_xpt_outputs_with_options(output_dir, domains, frames, &XptOptions::default())
+}
+
+/// Write XPT outputs for all domains, enforcing the limits in `options`.
+///
+/// A dataset exceeding a limit fails the export with
+/// [`SubmitError::XptTooManyVariables`] or [`SubmitError::XptRecordTooLong`]
+/// before its file is written; datasets earlier in domain code order are
+/// already on disk. The same holds for [`SubmitError::Cancelled`] once the
+/// token in `options` is cancelled.
+pub fn write_xpt_outputs_with_options(
+    output_dir: &Path,
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+    options: &XptOptions,
+) -> Result<Vec<PathBuf>> {
+    write_xpt_outputs_with_progress(output_dir, domains, frames, options, &|_| {})
+}
+
+/// Write XPT outputs like [`write_xpt_outputs_with_options`], reporting
+/// progress.
+///
+/// Each dataset reports [`ProgressStage::XptExport`] events to `progress`
+/// under its dataset name.
+pub fn write_xpt_outputs_with_progress(
+    output_dir: &Path,
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+    options: &XptOptions,
+    progress: &dyn Fn(ProgressEvent),
 ) -> Result<Vec<PathBuf>> {
     let domain_lookup = domain_map_by_code(domains);
     let mut frames_sorted: Vec<&DomainFrame> = frames.iter().collect();
@@ -34,7 +156,19 @@ pub fn write_xpt_outputs(
             })?;
         // Use frame's dataset name (from metadata) for split domains, falling back to domain.name
         let output_dataset_name = frame.dataset_name();
-        let dataset = build_xpt_dataset_with_name(domain, frame, &output_dataset_name)?;
+        frame.warn_dataset_name();
+        progress(ProgressEvent::DomainStarted {
+            stage: ProgressStage::XptExport,
+            domain: output_dataset_name.clone(),
+            total_rows: frame.data.height(),
+        });
+        let dataset = build_xpt_dataset_with_progress(
+            domain,
+            frame,
+            &output_dataset_name,
+            options,
+            progress,
+        )?;
         let disk_name = output_dataset_name.to_lowercase();
         let filename = format!("{disk_name}.xpt");
         let path = xpt_dir.join(&filename);
@@ -49,6 +183,10 @@ pub fn write_xpt_outputs(
             .write_path(&path)
             .map_err(|e| SubmitError::write_error("XPT", path.display().to_string(), e))?;
 
+        progress(ProgressEvent::DomainFinished {
+            stage: ProgressStage::XptExport,
+            domain: output_dataset_name,
+        });
         outputs.push(path);
     }
     Ok(outputs)
@@ -64,8 +202,47 @@ pub fn build_xpt_dataset_with_name(
     frame: &DomainFrame,
     dataset_name: &str,
 ) -> Result<Dataset> {
-    let df = &frame.data;
-    let columns = build_xpt_columns(domain, df)?;
+    build_xpt_dataset_with_options(domain, frame, dataset_name, &XptOptions::default())
+}
+
+/// Build XPT dataset with an explicit dataset name, enforcing the limits in
+/// `options`.
+pub fn build_xpt_dataset_with_options(
+    domain: &SdtmDomain,
+    frame: &DomainFrame,
+    dataset_name: &str,
+    options: &XptOptions,
+) -> Result<Dataset> {
+    build_xpt_dataset_with_progress(domain, frame, dataset_name, options, &|_| {})
+}
+
+/// Build XPT dataset like [`build_xpt_dataset_with_options`], reporting
+/// progress.
+///
+/// Converting the rows reports [`ProgressEvent::RowsProcessed`] to
+/// `progress` under `dataset_name` every 10,000 rows and once more for the
+/// last rows.
+pub fn build_xpt_dataset_with_progress(
+    domain: &SdtmDomain,
+    frame: &DomainFrame,
+    dataset_name: &str,
+    options: &XptOptions,
+    progress: &dyn Fn(ProgressEvent),
+) -> Result<Dataset> {
+    if options.on_overlength == Overlength::Error
+        && let Some(value) = validate_xpt_values(domain, frame)?.into_iter().next()
+    {
+        return Err(SubmitError::XptValueTooLong {
+            dataset: dataset_name.to_string(),
+            variable: value.variable,
+            row: value.row,
+            length: value.length,
+            max: value.max,
+        });
+    }
+    let (columns, record_length) =
+        build_xpt_columns(domain, frame, dataset_name, options, progress)?;
+    options.check(dataset_name, columns.len(), record_length)?;
 
     // Use domain label if available, otherwise use domain name
     let dataset_label = domain.label.as_deref().unwrap_or(&domain.name);
@@ -78,44 +255,183 @@ pub fn build_xpt_dataset_with_name(
     })
 }
 
-/// Build XPT columns from domain variables.
-fn build_xpt_columns(domain: &SdtmDomain, df: &DataFrame) -> Result<Vec<Column>> {
-    // Filter to only variables that exist in the DataFrame
-    let existing_vars: Vec<_> = domain
-        .variables
-        .iter()
-        .filter(|v| df.column(&v.name).is_ok())
-        .collect();
+/// A character value longer than its XPT column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XptValueTooLong {
+    /// Variable name.
+    pub variable: String,
+    /// Zero-based row index.
+    pub row: usize,
+    /// Length of the value in bytes.
+    pub length: usize,
+    /// Declared length of the variable.
+    pub max: usize,
+}
 
-    let row_count = df.height();
-    let mut columns = Vec::with_capacity(existing_vars.len());
+/// Find character values that would be truncated when written to XPT.
+///
+/// Only variables with a declared length can be exceeded, as other
+/// character columns take the length of their longest value. Trailing
+/// blanks don't count, since XPT pads values with blanks anyway. Every row
+/// has one value per column, as the data is a DataFrame, so there are no
+/// row arity errors to report.
+pub fn validate_xpt_values(
+    domain: &SdtmDomain,
+    frame: &DomainFrame,
+) -> Result<Vec<XptValueTooLong>> {
+    let df = &frame.data;
+    let column_index = ColumnIndex::new(df);
+    let mut values = Vec::new();
+    for variable in &domain.variables {
+        if variable.data_type != VariableType::Char || variable.length.is_none() {
+            continue;
+        }
+        let Some(column_name) = column_index.get(&variable.name) else {
+            continue;
+        };
+        let max = column_length(column_name, variable.data_type, variable.length, df)? as usize;
+        let col = df.column(column_name)?;
+        for row in 0..df.height() {
+            let length = any_to_string_for_output(col.get(row).unwrap_or(AnyValue::Null))
+                .trim_end()
+                .len();
+            if length > max {
+                values.push(XptValueTooLong {
+                    variable: variable.name.clone(),
+                    row,
+                    length,
+                    max,
+                });
+            }
+        }
+    }
+    Ok(values)
+}
 
-    for variable in &existing_vars {
-        let col = df
-            .column(variable.name.as_str())
-            .map_err(|_| SubmitError::ColumnNotFound {
-                column: variable.name.clone(),
-            })?;
+/// A domain variable as laid out in an XPT dataset.
+pub(super) struct XptVariable<'a> {
+    /// Variable definition.
+    pub variable: &'a SdtmVariable,
+    /// Frame column holding its values, which may differ in case.
+    pub column: String,
+    /// Length in bytes.
+    pub length: usize,
+}
 
-        let column_data = match variable.data_type {
-            VariableType::Num => {
-                let mut values = Vec::with_capacity(row_count);
-                for row_idx in 0..row_count {
-                    let value = col.get(row_idx).unwrap_or(AnyValue::Null);
-                    let num = any_to_f64(value);
-                    values.push(num);
-                }
-                ColumnData::F64(values)
-            }
-            VariableType::Char => {
-                let mut values = Vec::with_capacity(row_count);
-                for row_idx in 0..row_count {
-                    let value = col.get(row_idx).unwrap_or(AnyValue::Null);
-                    let s = any_to_string(value);
-                    values.push(if s.is_empty() { None } else { Some(s) });
+/// Lay out the XPT variables of a frame.
+///
+/// Lists the domain variables present in the frame, in any case, in domain
+/// order. Character lengths follow `on_overlength`; numeric variables are
+/// always 8 bytes. Shared by the writer and the dry-run plan.
+pub(super) fn xpt_layout<'a>(
+    domain: &'a SdtmDomain,
+    frame: &DomainFrame,
+    on_overlength: Overlength,
+) -> Result<Vec<XptVariable<'a>>> {
+    let df = &frame.data;
+    let column_index = ColumnIndex::new(df);
+    let mut layout = Vec::new();
+    for variable in &domain.variables {
+        let Some(column) = column_index.get(&variable.name) else {
+            continue;
+        };
+        let length = match variable.data_type {
+            VariableType::Char => column_length_with_policy(
+                column,
+                variable.data_type,
+                variable.length,
+                df,
+                on_overlength,
+            )? as usize,
+            // Numeric columns should always be 8 bytes in SAS XPT format
+            VariableType::Num => 8,
+        };
+        layout.push(XptVariable {
+            variable,
+            column: column.to_string(),
+            length,
+        });
+    }
+    Ok(layout)
+}
+
+/// Values of one XPT column, filled in row batches.
+enum ColumnValues {
+    Num(Vec<Option<f64>>),
+    Char(Vec<Option<String>>),
+}
+
+/// Build XPT columns from domain variables, with the observation length.
+///
+/// Formats configured in [`DomainFrame::formats`] are set on the columns.
+/// Rows are converted in batches of [`PROGRESS_BATCH_ROWS`], each reported
+/// to `progress`. The cancellation token in `options` is checked before
+/// every batch.
+fn build_xpt_columns(
+    domain: &SdtmDomain,
+    frame: &DomainFrame,
+    dataset_name: &str,
+    options: &XptOptions,
+    progress: &dyn Fn(ProgressEvent),
+) -> Result<(Vec<Column>, usize)> {
+    let df = &frame.data;
+    let layout = xpt_layout(domain, frame, options.on_overlength)?;
+
+    let row_count = df.height();
+    let mut sources = Vec::with_capacity(layout.len());
+    for XptVariable {
+        variable, column, ..
+    } in &layout
+    {
+        let col = df.column(column).map_err(|_| SubmitError::ColumnNotFound {
+            column: variable.name.clone(),
+        })?;
+        let values = match variable.data_type {
+            VariableType::Num => ColumnValues::Num(Vec::with_capacity(row_count)),
+            VariableType::Char => ColumnValues::Char(Vec::with_capacity(row_count)),
+        };
+        sources.push((col, values));
+    }
+
+    let mut start = 0;
+    loop {
+        options.check_cancelled()?;
+        let end = (start + PROGRESS_BATCH_ROWS).min(row_count);
+        for (col, values) in &mut sources {
+            for row_idx in start..end {
+                let value = col.get(row_idx).unwrap_or(AnyValue::Null);
+                match values {
+                    ColumnValues::Num(values) => values.push(any_to_f64(value)),
+                    ColumnValues::Char(values) => {
+                        let s = any_to_string_for_output(value);
+                        values.push(if s.is_empty() { None } else { Some(s) });
+                    }
                 }
-                ColumnData::String(values)
             }
+        }
+        progress(ProgressEvent::RowsProcessed {
+            stage: ProgressStage::XptExport,
+            domain: dataset_name.to_string(),
+            rows: end,
+        });
+        if end == row_count {
+            break;
+        }
+        start = end;
+    }
+
+    let record_length = layout.iter().map(|entry| entry.length).sum();
+    let mut columns = Vec::with_capacity(layout.len());
+    for (
+        XptVariable {
+            variable, length, ..
+        },
+        (_, values),
+    ) in layout.iter().zip(sources)
+    {
+        let column_data = match values {
+            ColumnValues::Num(values) => ColumnData::F64(values),
+            ColumnValues::Char(values) => ColumnData::String(values),
         };
 
         // Create column with name and data
@@ -126,20 +442,250 @@ fn build_xpt_columns(domain: &SdtmDomain, df: &DataFrame) -> Result<Vec<Column>>
             column = column.with_label(label.as_str());
         }
 
-        // Set explicit length for all columns
-        match variable.data_type {
-            VariableType::Char => {
-                let length = variable_length(variable, df)?;
-                column = column.with_length(length as usize);
-            }
-            VariableType::Num => {
-                // Numeric columns should always be 8 bytes in SAS XPT format
-                column = column.with_length(8);
+        if let Some(format) = frame
+            .formats
+            .get(&variable.name)
+            .and_then(|format| column_format(&variable.name, variable.data_type, format))
+        {
+            column = column.with_format(format);
+        }
+
+        columns.push(column.with_length(*length));
+    }
+
+    Ok((columns, record_length))
+}
+
+/// Parse a configured SAS format for a variable.
+///
+/// Returns `None`, with a warning, for formats that don't parse or don't
+/// match the variable type (e.g., `$CHAR12.` on a numeric variable).
+fn column_format(variable: &str, data_type: VariableType, format: &str) -> Option<Format> {
+    let parsed = match Format::parse(format) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            tracing::warn!(variable, format, "Ignoring invalid SAS format: {e}");
+            return None;
+        }
+    };
+    if parsed.is_character() != (data_type == VariableType::Char) {
+        tracing::warn!(variable, format, "Ignoring SAS format of the wrong type");
+        return None;
+    }
+    Some(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::export::common::variable_length_with_policy;
+    use polars::prelude::df;
+
+    /// DM with USUBJID declared 9 bytes long.
+    fn dm_domain() -> SdtmDomain {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let mut dm = domains.into_iter().find(|d| d.name == "DM").unwrap();
+        for variable in &mut dm.variables {
+            if variable.name == "USUBJID" {
+                variable.length = Some(9);
             }
         }
+        dm
+    }
+
+    fn dm_frame() -> DomainFrame {
+        let data = df! {
+            "STUDYID" => &["STUDY", "STUDY"],
+            "DOMAIN" => &["DM", "DM"],
+            "USUBJID" => &["STUDY-001", "STUDY-0002"],
+            "AGE" => &[34.0, 58.0],
+        }
+        .unwrap();
+        DomainFrame::new("DM", data)
+    }
+
+    fn build_dm(options: &XptOptions) -> Result<Dataset> {
+        build_xpt_dataset_with_options(&dm_domain(), &dm_frame(), "DM", options)
+    }
+
+    #[test]
+    fn test_limits_off_by_default() {
+        assert!(build_dm(&XptOptions::default()).is_ok());
+        let generous = XptOptions::default()
+            .with_max_variables(4)
+            .with_max_record_length(200);
+        assert!(build_dm(&generous).is_ok());
+    }
+
+    #[test]
+    fn test_too_many_variables() {
+        let result = build_dm(&XptOptions::default().with_max_variables(3));
+        assert!(matches!(
+            result,
+            Err(SubmitError::XptTooManyVariables {
+                count: 4,
+                max: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_record_too_long() {
+        let result = build_dm(&XptOptions::default().with_max_record_length(16));
+        let Err(SubmitError::XptRecordTooLong {
+            dataset,
+            length,
+            max,
+        }) = result
+        else {
+            panic!("expected XptRecordTooLong, got {result:?}");
+        };
+        assert_eq!(dataset, "DM");
+        assert!(length > 16);
+        assert_eq!(max, 16);
+    }
+
+    #[test]
+    fn test_validate_xpt_values_over_length() {
+        let values = validate_xpt_values(&dm_domain(), &dm_frame()).unwrap();
+        assert_eq!(
+            values,
+            [XptValueTooLong {
+                variable: "USUBJID".to_string(),
+                row: 1,
+                length: 10,
+                max: 9,
+            }]
+        );
+    }
+
+    /// Record length of DM under a policy, read from the limit error.
+    fn dm_record_length(policy: Overlength) -> usize {
+        let options = XptOptions::default()
+            .with_on_overlength(policy)
+            .with_max_record_length(0);
+        match build_dm(&options) {
+            Err(SubmitError::XptRecordTooLong { length, .. }) => length,
+            other => panic!("expected XptRecordTooLong, got {other:?}"),
+        }
+    }
 
-        columns.push(column);
+    fn usubjid_length(policy: Overlength) -> u16 {
+        let usubjid = dm_domain()
+            .variables
+            .into_iter()
+            .find(|v| v.name == "USUBJID")
+            .unwrap();
+        variable_length_with_policy(&usubjid, &dm_frame().data, policy).unwrap()
     }
 
-    Ok(columns)
+    #[test]
+    fn test_overlength_truncate() {
+        assert!(build_dm(&XptOptions::default()).is_ok());
+        assert_eq!(usubjid_length(Overlength::Truncate), 9);
+    }
+
+    #[test]
+    fn test_overlength_error() {
+        let result = build_dm(&XptOptions::default().with_on_overlength(Overlength::Error));
+        assert!(matches!(
+            result,
+            Err(SubmitError::XptValueTooLong {
+                row: 1,
+                length: 10,
+                max: 9,
+                ..
+            })
+        ));
+        assert_eq!(usubjid_length(Overlength::Error), 9);
+    }
+
+    #[test]
+    fn test_overlength_widen() {
+        assert!(build_dm(&XptOptions::default().with_on_overlength(Overlength::Widen)).is_ok());
+        assert_eq!(usubjid_length(Overlength::Widen), 10);
+        assert_eq!(
+            dm_record_length(Overlength::Widen),
+            dm_record_length(Overlength::Truncate) + 1
+        );
+    }
+
+    #[test]
+    fn test_column_format() {
+        assert!(column_format("AGE", VariableType::Num, "8.2").is_some());
+        assert!(column_format("USUBJID", VariableType::Char, "$CHAR12.").is_some());
+        // Wrong type or unparsable formats are dropped
+        assert!(column_format("AGE", VariableType::Num, "$CHAR12.").is_none());
+        assert!(column_format("USUBJID", VariableType::Char, "DATE9.").is_none());
+        assert!(column_format("AGE", VariableType::Num, "DATE").is_none());
+
+        let frame = dm_frame().with_formats(BTreeMap::from([
+            ("AGE".to_string(), "8.".to_string()),
+            ("USUBJID".to_string(), "DATE".to_string()),
+        ]));
+        assert!(
+            build_xpt_dataset_with_options(&dm_domain(), &frame, "DM", &XptOptions::default())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_rows_reported_in_batches() {
+        let rows = 2 * PROGRESS_BATCH_ROWS + 5;
+        let data = df! {
+            "STUDYID" => vec!["STUDY"; rows],
+            "USUBJID" => vec!["STUDY-001"; rows],
+        }
+        .unwrap();
+        let frame = DomainFrame::new("DM", data);
+
+        let events = std::cell::RefCell::new(Vec::new());
+        build_xpt_dataset_with_progress(
+            &dm_domain(),
+            &frame,
+            "DM",
+            &XptOptions::default(),
+            &|event| events.borrow_mut().push(event),
+        )
+        .unwrap();
+
+        let reported: Vec<usize> = events
+            .into_inner()
+            .into_iter()
+            .map(|event| match event {
+                ProgressEvent::RowsProcessed { rows, .. } => rows,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            reported,
+            [PROGRESS_BATCH_ROWS, 2 * PROGRESS_BATCH_ROWS, rows]
+        );
+    }
+
+    #[test]
+    fn test_cancelled_between_batches() {
+        let rows = 2 * PROGRESS_BATCH_ROWS + 5;
+        let data = df! {
+            "STUDYID" => vec!["STUDY"; rows],
+            "USUBJID" => vec!["STUDY-001"; rows],
+        }
+        .unwrap();
+        let frame = DomainFrame::new("DM", data);
+        let token = CancellationToken::new();
+        let options = XptOptions::default().with_cancellation(token.clone());
+
+        // Cancel once the first batch is converted
+        let batches = std::cell::Cell::new(0);
+        let result = build_xpt_dataset_with_progress(&dm_domain(), &frame, "DM", &options, &|_| {
+            batches.set(batches.get() + 1);
+            token.cancel();
+        });
+
+        assert!(matches!(result, Err(SubmitError::Cancelled)));
+        assert_eq!(batches.get(), 1);
+    }
 }
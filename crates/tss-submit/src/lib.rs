@@ -5,7 +5,12 @@
 //! - **Mapping** (`map`): Fuzzy column-to-variable mapping with confidence scores
 //! - **Normalization** (`normalize`): Data transformation and standardization
 //! - **Validation** (`validate`): CDISC conformance checking
-//! - **Export** (`export`): Output generation (XPT, Dataset-XML, Define-XML)
+//! - **Export** (`export`): Output generation (XPT, Dataset-XML, Define-XML, SAS)
+//! - **Pivoting** (`pivot`): Wide-to-long and long-to-wide reshaping
+//! - **Relationships** (`relationships`): RELREC and SUPP-- generation
+//! - **Submission** (`submission`): End-to-end [`run_submission`] over a study folder
+//! - **Diff** (`diff`): Comparison of two XPT output folders
+//! - **Progress** (`progress`): Per-domain progress events and cancellation
 //!
 //! # Error Handling
 //!
@@ -24,13 +29,18 @@
 //! let report = validate_domain(&domain, &df, ct.as_ref());
 //!
 //! // 3. Export to XPT
-//! write_xpt_outputs(&domain_data, output_dir)?;
+//! write_xpt_outputs(output_dir, &domains, &frames)?;
 //! ```
 
+pub mod diff;
 pub mod error;
 pub mod export;
 pub mod map;
 pub mod normalize;
+pub mod pivot;
+pub mod progress;
+pub mod relationships;
+pub mod submission;
 pub mod validate;
 
 // Re-export unified error type
@@ -38,23 +48,51 @@
 
 // Re-export commonly used types
 pub use map::{
-    ColumnScore, Mapping, MappingConfig, MappingError, MappingState, MappingSummary,
-    ScoreComponent, ScoringEngine, Suggestion, VariableStatus,
+    ColumnConcat, ColumnScore, ConfidenceLevel, ConfidencePreset, ConfidenceThresholds, Mapping,
+    MappingConfig, MappingError, MappingState, MappingSummary, MergeStrategy, ScoreComponent,
+    ScoringEngine, Suggestion, VariableStatus, merge_mapping_configs,
 };
 
 pub use normalize::{
-    NormalizationContext, NormalizationError, NormalizationPipeline, NormalizationRule,
-    NormalizationType, build_preview_dataframe, build_preview_dataframe_with_dm,
+    CtResolutionMode, DateImputation, DomainProcessor, ImputationRule, NormalizationContext,
+    NormalizationError, NormalizationFailure, NormalizationOutput, NormalizationPipeline,
+    NormalizationRule, NormalizationType, StudyDayConfig, UnitConversion, UnitConversionTable,
+    UsubjidTemplate, build_preview_dataframe, build_preview_dataframe_for_mapping,
+    build_preview_dataframe_for_mapping_with_study_day, build_preview_dataframe_with_dm,
     build_preview_dataframe_with_dm_and_omitted, build_preview_dataframe_with_omitted,
+    build_preview_dataframe_with_study_day, convert_units, convert_units_with_overwrite,
     execute_normalization, infer_normalization_rules,
 };
 
 pub use validate::{
-    Category, Issue, Severity, ValidationReport, validate_domain,
-    validate_domain_with_not_collected,
+    Category, Issue, IssueRecord, RangeRule, RangeRuleTable, RuleError, RuleRegistry, Severity,
+    ValidationReport, load_default_rules, load_rules, validate_adam_dataset,
+    validate_adam_traceability, validate_all_domains, validate_cross_domain, validate_domain,
+    validate_domain_with_not_collected, validate_domain_with_rules, validate_study_cross_domain,
+    write_findings_csv,
 };
 
 pub use export::{
-    DatasetXmlOptions, DefineXmlOptions, DomainFrame, build_xpt_dataset_with_name,
-    write_dataset_xml, write_dataset_xml_outputs, write_define_xml, write_xpt_outputs,
+    AdamDefineOptions, DatasetNameIssue, DatasetXmlOptions, DefineModel, DefineSpec,
+    DefineXmlOptions, DomainFrame, ItemOrigin, Overlength, PlannedOutput, ResultDisplaySpec,
+    SplitNaming, XptOptions, XptValueTooLong, build_adam_define_spec, build_define_spec,
+    build_xpt_dataset_with_name, build_xpt_dataset_with_options, build_xpt_dataset_with_progress,
+    generate_r_program, generate_sas_program, plan_dataset_xml_outputs, plan_xpt_outputs,
+    split_domain, validate_xpt_values, write_adam_define_xml, write_dataset_xml,
+    write_dataset_xml_outputs, write_dataset_xml_outputs_with_reference_data, write_define_html,
+    write_define_spec, write_define_xml, write_r_outputs, write_xpt_outputs,
+    write_xpt_outputs_with_options, write_xpt_outputs_with_progress,
+};
+
+pub use diff::{CellChange, DatasetDiff, FolderDiff, diff_dataframes, diff_xpt_folders};
+
+pub use pivot::{PivotConflict, pivot_long_to_wide, pivot_wide_to_long};
+
+pub use progress::{CancellationToken, ProgressEvent, ProgressStage};
+
+pub use relationships::{SuppParentKey, SuppQualifier, build_suppqual, generate_relrec};
+
+pub use submission::{
+    DomainSubmission, FolderValidation, OutputFormat, SubmissionConfig, SubmissionResult,
+    run_submission, run_submission_with_progress, validate_xpt_folder,
 };
@@ -0,0 +1,195 @@
+//! Confidence thresholds for mapping suggestions.
+//!
+//! Scores from the [`ScoringEngine`](super::ScoringEngine) are grouped into
+//! High, Medium and Low confidence; scores below the Low cutoff are not
+//! suggested. Thresholds are serializable so a team can keep its preferred
+//! cutoffs, or a named preset, in project settings.
+
+use serde::{Deserialize, Serialize};
+
+/// Confidence level of a mapping score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ConfidenceLevel {
+    /// Below the Medium cutoff; review carefully.
+    Low,
+    /// Likely correct; review recommended.
+    Medium,
+    /// Near-certain match.
+    High,
+}
+
+/// Named threshold presets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfidencePreset {
+    /// Only close matches are suggested.
+    Strict,
+    /// Default cutoffs.
+    #[default]
+    Balanced,
+    /// Weaker matches are suggested too, for sources with unusual names.
+    Lenient,
+}
+
+impl ConfidencePreset {
+    /// Get the thresholds of this preset.
+    pub const fn thresholds(self) -> ConfidenceThresholds {
+        match self {
+            Self::Strict => ConfidenceThresholds {
+                high: 0.95,
+                medium: 0.85,
+                low: 0.75,
+            },
+            Self::Balanced => ConfidenceThresholds {
+                high: 0.9,
+                medium: 0.75,
+                low: 0.6,
+            },
+            Self::Lenient => ConfidenceThresholds {
+                high: 0.85,
+                medium: 0.6,
+                low: 0.45,
+            },
+        }
+    }
+}
+
+/// Score cutoffs (0.0 to 1.0) for each confidence level.
+///
+/// A score belongs to the highest level whose cutoff it reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceThresholds {
+    /// Minimum score for High confidence.
+    pub high: f32,
+    /// Minimum score for Medium confidence.
+    pub medium: f32,
+    /// Minimum score for Low confidence, and for a suggestion at all.
+    pub low: f32,
+}
+
+impl Default for ConfidenceThresholds {
+    fn default() -> Self {
+        ConfidencePreset::default().thresholds()
+    }
+}
+
+impl From<ConfidencePreset> for ConfidenceThresholds {
+    fn from(preset: ConfidencePreset) -> Self {
+        preset.thresholds()
+    }
+}
+
+impl ConfidenceThresholds {
+    /// Thresholds of [`ConfidencePreset::Strict`].
+    pub const fn strict() -> Self {
+        ConfidencePreset::Strict.thresholds()
+    }
+
+    /// Thresholds of [`ConfidencePreset::Balanced`].
+    pub const fn balanced() -> Self {
+        ConfidencePreset::Balanced.thresholds()
+    }
+
+    /// Thresholds of [`ConfidencePreset::Lenient`].
+    pub const fn lenient() -> Self {
+        ConfidencePreset::Lenient.thresholds()
+    }
+
+    /// Get the confidence level of a score, or `None` below the Low cutoff.
+    pub fn level(&self, score: f32) -> Option<ConfidenceLevel> {
+        if score >= self.high {
+            Some(ConfidenceLevel::High)
+        } else if score >= self.medium {
+            Some(ConfidenceLevel::Medium)
+        } else if score >= self.low {
+            Some(ConfidenceLevel::Low)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether a score is high enough to be suggested.
+    pub fn accepts(&self, score: f32) -> bool {
+        self.level(score).is_some()
+    }
+
+    /// Check that cutoffs are within 0.0 to 1.0 and ordered
+    /// `low <= medium <= high`.
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.low)
+            && (0.0..=1.0).contains(&self.high)
+            && self.low <= self.medium
+            && self.medium <= self.high
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::map::MappingState;
+
+    #[test]
+    fn test_levels() {
+        let thresholds = ConfidenceThresholds::balanced();
+        assert_eq!(thresholds.level(0.95), Some(ConfidenceLevel::High));
+        assert_eq!(thresholds.level(0.75), Some(ConfidenceLevel::Medium));
+        assert_eq!(thresholds.level(0.6), Some(ConfidenceLevel::Low));
+        assert_eq!(thresholds.level(0.59), None);
+        assert_eq!(ConfidenceThresholds::default(), thresholds);
+        for preset in [
+            ConfidencePreset::Strict,
+            ConfidencePreset::Balanced,
+            ConfidencePreset::Lenient,
+        ] {
+            assert!(preset.thresholds().is_valid());
+        }
+    }
+
+    #[test]
+    fn test_strict_rejects_borderline_match() {
+        let mut domain = tss_standards::load_sdtm_ig()
+            .unwrap()
+            .into_iter()
+            .find(|domain| domain.name == "AE")
+            .unwrap();
+        domain
+            .variables
+            .retain(|variable| variable.name == "AETERM");
+        let columns = vec!["EVENT_TERM".to_string()];
+        let state = |thresholds: &ConfidenceThresholds| {
+            MappingState::with_thresholds(
+                domain.clone(),
+                "STUDY01",
+                &columns,
+                BTreeMap::new(),
+                thresholds,
+            )
+        };
+
+        let lenient = state(&ConfidenceThresholds::lenient());
+        let (column, score) = lenient.suggestion("AETERM").unwrap();
+        assert_eq!(column, "EVENT_TERM");
+        assert!(ConfidenceThresholds::lenient().accepts(score));
+        assert!(!ConfidenceThresholds::strict().accepts(score));
+        assert!(
+            state(&ConfidenceThresholds::strict())
+                .suggestion("AETERM")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let json = serde_json::to_string(&ConfidenceThresholds::strict()).unwrap();
+        let thresholds: ConfidenceThresholds = serde_json::from_str(&json).unwrap();
+        assert_eq!(thresholds, ConfidenceThresholds::strict());
+
+        let preset: ConfidencePreset = serde_json::from_str("\"lenient\"").unwrap();
+        assert_eq!(
+            ConfidenceThresholds::from(preset),
+            ConfidenceThresholds::lenient()
+        );
+    }
+}
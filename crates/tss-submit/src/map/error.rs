@@ -30,6 +30,28 @@ pub enum MappingError {
     /// Cannot omit non-Permissible variable (only Permissible vars can be omitted).
     #[error("Cannot omit variable '{0}': only Permissible variables can be omitted")]
     CannotOmitNonPermissible(String),
+
+    /// Two merged configurations map the same variable to different columns.
+    #[error("Conflicting mappings for '{variable}': '{existing}' and '{incoming}'")]
+    MappingConflict {
+        /// The variable mapped by both configurations.
+        variable: String,
+        /// Source column from the first configuration.
+        existing: String,
+        /// Source column from the second configuration.
+        incoming: String,
+    },
+
+    /// Merged configurations belong to different domains or studies.
+    #[error("Cannot merge mappings with different {field}: '{first}' and '{second}'")]
+    ConfigMismatch {
+        /// The differing field (`domain_code` or `study_id`).
+        field: &'static str,
+        /// Value in the first configuration.
+        first: String,
+        /// Value in the second configuration.
+        second: String,
+    },
 }
 
 impl MappingError {
@@ -40,7 +62,8 @@ pub fn variable(&self) -> Option<&str> {
             Self::ColumnAlreadyUsed { variable, .. } => Some(variable),
             Self::CannotSetNullOnRequired(v) => Some(v),
             Self::CannotOmitNonPermissible(v) => Some(v),
-            Self::ColumnNotFound(_) => None,
+            Self::MappingConflict { variable, .. } => Some(variable),
+            Self::ColumnNotFound(_) | Self::ConfigMismatch { .. } => None,
         }
     }
 
@@ -73,6 +96,24 @@ fn from(err: MappingError) -> Self {
             MappingError::CannotOmitNonPermissible(variable) => {
                 crate::error::SubmitError::CannotOmitNonPermissible { variable }
             }
+            MappingError::MappingConflict {
+                variable,
+                existing,
+                incoming,
+            } => crate::error::SubmitError::MappingConflict {
+                variable,
+                existing,
+                incoming,
+            },
+            MappingError::ConfigMismatch {
+                field,
+                first,
+                second,
+            } => crate::error::SubmitError::MappingConfigMismatch {
+                field,
+                first,
+                second,
+            },
         }
     }
 }
@@ -0,0 +1,369 @@
+//! Merging of mapping configurations.
+//!
+//! Combines two [`MappingConfig`]s, e.g. a saved mapping loaded on top of
+//! fresh auto-suggestions, with a selectable [`MergeStrategy`] for variables
+//! both configurations map.
+
+use std::collections::BTreeMap;
+
+use crate::map::error::MappingError;
+use crate::map::state::{Mapping, MappingConfig};
+
+/// How to resolve a variable mapped by both configurations, or a source
+/// column mapped to different variables.
+///
+/// Mappings that agree on the source column (and concatenation) are not
+/// conflicts; the higher confidence is kept whatever the strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Keep the mapping with the higher confidence score; the first wins ties.
+    #[default]
+    PreferHigherConfidence,
+    /// Keep the mapping from the first configuration.
+    PreferFirst,
+    /// Keep the mapping from the second configuration.
+    PreferLast,
+    /// Fail with [`MappingError::MappingConflict`] or
+    /// [`MappingError::ColumnAlreadyUsed`].
+    Error,
+}
+
+/// Merge two mapping lists, resolving conflicts with `strategy`.
+///
+/// Each variable keeps one mapping, and each source column feeds at most
+/// one variable: when two variables use the same column, `strategy` picks
+/// the mapping to keep and the other variable is left unmapped. The result
+/// is ordered by target variable.
+///
+/// # Errors
+/// When `strategy` is [`MergeStrategy::Error`], returns
+/// `MappingError::MappingConflict` for the first variable mapped to
+/// different columns, or `MappingError::ColumnAlreadyUsed` for the first
+/// column mapped to different variables.
+pub fn merge_mappings(
+    first: &[Mapping],
+    second: &[Mapping],
+    strategy: MergeStrategy,
+) -> Result<Vec<Mapping>, MappingError> {
+    // Target variable -> (mapping, taken from the second configuration)
+    let mut merged: BTreeMap<String, (Mapping, bool)> = first
+        .iter()
+        .map(|m| (m.target_variable.clone(), (m.clone(), false)))
+        .collect();
+
+    for incoming in second {
+        let Some((existing, _)) = merged.get(&incoming.target_variable) else {
+            merged.insert(incoming.target_variable.clone(), (incoming.clone(), true));
+            continue;
+        };
+
+        let agrees =
+            existing.source_column == incoming.source_column && existing.concat == incoming.concat;
+        let take_incoming = if agrees {
+            incoming.confidence > existing.confidence
+        } else {
+            match strategy {
+                MergeStrategy::PreferHigherConfidence => incoming.confidence > existing.confidence,
+                MergeStrategy::PreferFirst => false,
+                MergeStrategy::PreferLast => true,
+                MergeStrategy::Error => {
+                    return Err(MappingError::MappingConflict {
+                        variable: incoming.target_variable.clone(),
+                        existing: existing.source_column.clone(),
+                        incoming: incoming.source_column.clone(),
+                    });
+                }
+            }
+        };
+
+        if take_incoming {
+            merged.insert(incoming.target_variable.clone(), (incoming.clone(), true));
+        }
+    }
+
+    // Claim source columns in order of preference; the stable sort keeps
+    // target variable order for equal preference
+    let mut candidates: Vec<(Mapping, bool)> = merged.into_values().collect();
+    candidates.sort_by(|(a, a_second), (b, b_second)| match strategy {
+        MergeStrategy::PreferHigherConfidence => b
+            .confidence
+            .total_cmp(&a.confidence)
+            .then(a_second.cmp(b_second)),
+        MergeStrategy::PreferFirst | MergeStrategy::Error => a_second.cmp(b_second),
+        MergeStrategy::PreferLast => b_second.cmp(a_second),
+    });
+
+    let mut column_owner: BTreeMap<String, String> = BTreeMap::new();
+    let mut kept: BTreeMap<String, Mapping> = BTreeMap::new();
+    for (mapping, _) in candidates {
+        let columns = source_columns(&mapping);
+        if let Some((column, owner)) = columns
+            .iter()
+            .find_map(|column| Some((*column, column_owner.get(*column)?)))
+        {
+            if strategy == MergeStrategy::Error {
+                return Err(MappingError::ColumnAlreadyUsed {
+                    column: column.to_string(),
+                    variable: owner.clone(),
+                });
+            }
+            continue;
+        }
+        for column in columns {
+            column_owner.insert(column.to_string(), mapping.target_variable.clone());
+        }
+        kept.insert(mapping.target_variable.clone(), mapping);
+    }
+
+    Ok(kept.into_values().collect())
+}
+
+/// Source columns a mapping reads.
+fn source_columns(mapping: &Mapping) -> Vec<&str> {
+    match &mapping.concat {
+        Some(concat) => concat.columns.iter().map(String::as_str).collect(),
+        None => vec![mapping.source_column.as_str()],
+    }
+}
+
+/// Merge two mapping configurations, resolving conflicts with `strategy`.
+///
+/// - Both configurations must be for the same domain (compared
+///   case-insensitively) and study; the codes are taken from `first`.
+/// - Mappings are merged with [`merge_mappings`].
+/// - "Not collected" and omitted variables are combined; when both
+///   configurations give a "not collected" reason, `strategy` picks one
+///   (the first, unless [`MergeStrategy::PreferLast`]).
+/// - A variable that ends up mapped is dropped from "not collected" and
+///   omitted, since a concrete mapping is the more specific decision.
+///
+/// # Errors
+/// - `MappingError::ConfigMismatch` if the domain codes or study IDs differ
+/// - `MappingError::MappingConflict` when `strategy` is
+///   [`MergeStrategy::Error`] and the configurations map a variable differently
+pub fn merge_mapping_configs(
+    first: &MappingConfig,
+    second: &MappingConfig,
+    strategy: MergeStrategy,
+) -> Result<MappingConfig, MappingError> {
+    if !first.domain_code.eq_ignore_ascii_case(&second.domain_code) {
+        return Err(MappingError::ConfigMismatch {
+            field: "domain_code",
+            first: first.domain_code.clone(),
+            second: second.domain_code.clone(),
+        });
+    }
+    if first.study_id != second.study_id {
+        return Err(MappingError::ConfigMismatch {
+            field: "study_id",
+            first: first.study_id.clone(),
+            second: second.study_id.clone(),
+        });
+    }
+
+    let mappings = merge_mappings(&first.mappings, &second.mappings, strategy)?;
+
+    let mut not_collected = first.not_collected.clone();
+    for (variable, reason) in &second.not_collected {
+        if strategy == MergeStrategy::PreferLast || !not_collected.contains_key(variable) {
+            not_collected.insert(variable.clone(), reason.clone());
+        }
+    }
+
+    let mut omitted = first.omitted.clone();
+    omitted.extend(second.omitted.iter().cloned());
+
+    for mapping in &mappings {
+        not_collected.remove(&mapping.target_variable);
+        omitted.remove(&mapping.target_variable);
+    }
+
+    Ok(MappingConfig {
+        domain_code: first.domain_code.clone(),
+        study_id: first.study_id.clone(),
+        mappings,
+        not_collected,
+        omitted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn mapping(target: &str, source: &str, confidence: f32) -> Mapping {
+        Mapping {
+            source_column: source.to_string(),
+            target_variable: target.to_string(),
+            confidence,
+            concat: None,
+        }
+    }
+
+    fn config(mappings: Vec<Mapping>) -> MappingConfig {
+        MappingConfig {
+            domain_code: "AE".to_string(),
+            study_id: "STUDY01".to_string(),
+            mappings,
+            not_collected: BTreeMap::new(),
+            omitted: BTreeSet::new(),
+        }
+    }
+
+    /// Auto-suggestions (first) and a saved mapping (second) that disagree on AETERM.
+    fn conflicting() -> (Vec<Mapping>, Vec<Mapping>) {
+        let suggested = vec![
+            mapping("AETERM", "TERM", 0.9),
+            mapping("USUBJID", "SUBJECT", 0.8),
+        ];
+        let saved = vec![
+            mapping("AETERM", "VERBATIM", 0.7),
+            mapping("AEDECOD", "PT", 1.0),
+        ];
+        (suggested, saved)
+    }
+
+    fn source_of<'a>(mappings: &'a [Mapping], target: &str) -> &'a str {
+        mappings
+            .iter()
+            .find(|m| m.target_variable == target)
+            .map(|m| m.source_column.as_str())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_merge_prefer_higher_confidence() {
+        let (suggested, saved) = conflicting();
+        let merged =
+            merge_mappings(&suggested, &saved, MergeStrategy::PreferHigherConfidence).unwrap();
+        assert_eq!(merged.len(), 3);
+        assert_eq!(source_of(&merged, "AETERM"), "TERM");
+
+        // Ties keep the first configuration
+        let tied = vec![mapping("AETERM", "VERBATIM", 0.9)];
+        let merged =
+            merge_mappings(&suggested, &tied, MergeStrategy::PreferHigherConfidence).unwrap();
+        assert_eq!(source_of(&merged, "AETERM"), "TERM");
+    }
+
+    #[test]
+    fn test_merge_prefer_first() {
+        let (suggested, saved) = conflicting();
+        let merged = merge_mappings(&suggested, &saved, MergeStrategy::PreferFirst).unwrap();
+        assert_eq!(source_of(&merged, "AETERM"), "TERM");
+        assert_eq!(source_of(&merged, "AEDECOD"), "PT");
+    }
+
+    #[test]
+    fn test_merge_prefer_last() {
+        let (suggested, saved) = conflicting();
+        let merged = merge_mappings(&suggested, &saved, MergeStrategy::PreferLast).unwrap();
+        assert_eq!(source_of(&merged, "AETERM"), "VERBATIM");
+        assert_eq!(source_of(&merged, "USUBJID"), "SUBJECT");
+    }
+
+    #[test]
+    fn test_merge_error() {
+        let (suggested, saved) = conflicting();
+        let err = merge_mappings(&suggested, &saved, MergeStrategy::Error).unwrap_err();
+        assert_eq!(
+            err,
+            MappingError::MappingConflict {
+                variable: "AETERM".to_string(),
+                existing: "TERM".to_string(),
+                incoming: "VERBATIM".to_string(),
+            }
+        );
+
+        // Agreeing mappings are not conflicts; the higher confidence is kept
+        let same = vec![mapping("AETERM", "TERM", 1.0)];
+        let merged = merge_mappings(&suggested, &same, MergeStrategy::Error).unwrap();
+        assert_eq!(merged[0].confidence, 1.0);
+    }
+
+    #[test]
+    fn test_merge_mapping_configs() {
+        let (suggested, saved) = conflicting();
+        let mut first = config(suggested);
+        first.omitted.insert("AEDECOD".to_string());
+        first
+            .not_collected
+            .insert("AESER".to_string(), "Not captured".to_string());
+        let mut second = config(saved);
+        second
+            .not_collected
+            .insert("AESER".to_string(), "Not in CRF".to_string());
+
+        let merged = merge_mapping_configs(&first, &second, MergeStrategy::PreferLast).unwrap();
+
+        assert_eq!(source_of(&merged.mappings, "AETERM"), "VERBATIM");
+        assert_eq!(merged.not_collected["AESER"], "Not in CRF");
+        // AEDECOD is mapped by the saved config, so it is no longer omitted
+        assert!(merged.omitted.is_empty());
+
+        let merged = merge_mapping_configs(&first, &second, MergeStrategy::PreferFirst).unwrap();
+        assert_eq!(merged.not_collected["AESER"], "Not captured");
+    }
+
+    #[test]
+    fn test_merge_mapping_configs_mismatch() {
+        let first = config(Vec::new());
+        let mut second = config(Vec::new());
+        second.domain_code = "CM".to_string();
+        assert!(matches!(
+            merge_mapping_configs(&first, &second, MergeStrategy::default()),
+            Err(MappingError::ConfigMismatch {
+                field: "domain_code",
+                ..
+            })
+        ));
+
+        let mut second = config(Vec::new());
+        second.study_id = "STUDY02".to_string();
+        assert!(matches!(
+            merge_mapping_configs(&first, &second, MergeStrategy::default()),
+            Err(MappingError::ConfigMismatch {
+                field: "study_id",
+                ..
+            })
+        ));
+
+        // Domain codes differing only in case still merge
+        let mut second = config(Vec::new());
+        second.domain_code = "ae".to_string();
+        let merged = merge_mapping_configs(&first, &second, MergeStrategy::default()).unwrap();
+        assert_eq!(merged.domain_code, "AE");
+    }
+
+    #[test]
+    fn test_merge_one_variable_per_column() {
+        // The saved mapping moved VERBATIM from AETERM to AEDECOD
+        let suggested = vec![
+            mapping("AETERM", "VERBATIM", 0.9),
+            mapping("USUBJID", "SUBJECT", 0.8),
+        ];
+        let saved = vec![mapping("AEDECOD", "VERBATIM", 0.7)];
+
+        let merged =
+            merge_mappings(&suggested, &saved, MergeStrategy::PreferHigherConfidence).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(source_of(&merged, "AETERM"), "VERBATIM");
+
+        let merged = merge_mappings(&suggested, &saved, MergeStrategy::PreferLast).unwrap();
+        assert_eq!(source_of(&merged, "AEDECOD"), "VERBATIM");
+        assert!(!merged.iter().any(|m| m.target_variable == "AETERM"));
+
+        let merged = merge_mappings(&suggested, &saved, MergeStrategy::PreferFirst).unwrap();
+        assert!(!merged.iter().any(|m| m.target_variable == "AEDECOD"));
+
+        let err = merge_mappings(&suggested, &saved, MergeStrategy::Error).unwrap_err();
+        assert_eq!(
+            err,
+            MappingError::ColumnAlreadyUsed {
+                column: "VERBATIM".to_string(),
+                variable: "AETERM".to_string(),
+            }
+        );
+    }
+}
@@ -3,7 +3,8 @@
 //! This module provides fuzzy matching and scoring to help users manually map
 //! source data columns to SDTM domain variables. It uses Jaro-Winkler similarity
 //! as the base algorithm with adjustments for label matching, suffix patterns,
-//! and type compatibility.
+//! and type compatibility. Descriptive column names are also matched against
+//! variable labels by token overlap, and through an abbreviation table.
 //!
 //! # Design Philosophy
 //!
@@ -42,10 +43,16 @@
 //! let config = state.to_config();
 //! ```
 
+mod confidence;
 mod error;
+mod merge;
 mod score;
 mod state;
 
+pub use confidence::{ConfidenceLevel, ConfidencePreset, ConfidenceThresholds};
 pub use error::MappingError;
-pub use score::{ColumnScore, ScoreComponent, ScoringEngine, Suggestion};
-pub use state::{Mapping, MappingConfig, MappingState, MappingSummary, VariableStatus};
+pub use merge::{MergeStrategy, merge_mapping_configs, merge_mappings};
+pub use score::{ColumnScore, DEFAULT_ABBREVIATIONS, ScoreComponent, ScoringEngine, Suggestion};
+pub use state::{
+    ColumnConcat, Mapping, MappingConfig, MappingState, MappingSummary, VariableStatus,
+};
@@ -2,12 +2,15 @@
 //!
 //! Uses Jaro-Winkler similarity as the base algorithm with optional
 //! adjustments for label matching, suffix patterns, and type compatibility.
+//! Descriptive column names ("Date of Birth") are also matched against
+//! variable labels by token overlap, and common abbreviations ("DOB") are
+//! resolved through an extendable table.
 
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
 use rapidfuzz::distance::jaro_winkler;
-use tss_standards::{ColumnHint, SdtmDomain, SdtmVariable};
+use tss_standards::{ColumnHint, CoreDesignation, SdtmDomain, SdtmVariable, VariableRole};
 
 /// Score for a single column-variable pair.
 #[derive(Debug, Clone)]
@@ -15,10 +18,25 @@ pub struct ColumnScore {
     /// Final confidence score (0.0 to 1.0, may slightly exceed 1.0 with boosts).
     pub score: f32,
     /// Breakdown of score components for explainability.
+    ///
+    /// The first component is the base name similarity. Each later component
+    /// is a relative adjustment applied multiplicatively, so a value of 0.10
+    /// scales the running score by 1.10 and -0.15 scales it by 0.85.
     pub explanation: Vec<ScoreComponent>,
 }
 
 impl ColumnScore {
+    /// Recompute the score from its components.
+    ///
+    /// Matches [`ColumnScore::score`] up to floating point rounding.
+    pub fn compose(&self) -> f32 {
+        let mut components = self.explanation.iter();
+        let Some(base) = components.next() else {
+            return 0.0;
+        };
+        components.fold(base.value, |score, c| score * (1.0 + c.value))
+    }
+
     /// Human-readable explanation of the score.
     pub fn explain(&self) -> String {
         self.explanation
@@ -35,11 +53,44 @@ pub struct ScoreComponent {
     /// Component name (e.g., "Name similarity").
     pub name: &'static str,
     /// Component value (can be negative for penalties).
+    ///
+    /// Absolute for the base component, relative for adjustments.
     pub value: f32,
     /// Human-readable description.
     pub description: String,
 }
 
+/// Built-in abbreviations: normalized column name -> SDTM variable name.
+///
+/// Extend per engine with [`ScoringEngine::with_abbreviation`].
+pub const DEFAULT_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("DOB", "BRTHDTC"),
+    ("DOV", "VISITDTC"),
+    ("GENDER", "SEX"),
+    ("SUBJECT", "SUBJID"),
+];
+
+/// Score assigned when a column matches a variable through the abbreviation table.
+const ABBREVIATION_SCORE: f32 = 0.95;
+
+/// Unique ratio above which a column looks like a record identifier.
+const IDENTIFIER_UNIQUE_RATIO: f64 = 0.95;
+
+/// Unique ratio above which a numeric column looks like a sequence number.
+const SEQUENCE_UNIQUE_RATIO: f64 = 0.99;
+
+/// Null ratio above which a column is considered too sparse for a Required variable.
+const SPARSE_NULL_RATIO: f64 = 0.5;
+
+/// Date ratio above which a column looks like a --DTC variable.
+const DATE_LIKE_RATIO: f64 = 0.8;
+
+/// Coded ratio above which a column looks like a controlled terminology variable.
+const CODED_LIKE_RATIO: f64 = 0.8;
+
+/// Words ignored when comparing tokens against variable labels.
+const STOPWORDS: &[&str] = &["a", "an", "and", "for", "in", "of", "or", "the", "to"];
+
 /// A suggested mapping from column to variable.
 #[derive(Debug, Clone)]
 pub struct Suggestion {
@@ -57,16 +108,50 @@ pub struct Suggestion {
 /// - Label similarity (boost if labels match well)
 /// - Suffix patterns (SEQ, CD, etc.)
 /// - Type compatibility (numeric vs character)
+/// - Column profile (unique, null, date and coded ratios from [`ColumnHint`])
+///
+/// The base score is the best of name similarity, token overlap with the
+/// variable label, and an abbreviation table lookup.
 #[derive(Debug, Clone)]
 pub struct ScoringEngine {
     domain: SdtmDomain,
     hints: BTreeMap<String, ColumnHint>,
+    abbreviations: BTreeMap<String, String>,
 }
 
 impl ScoringEngine {
     /// Create a new scoring engine for a domain.
     pub fn new(domain: SdtmDomain, hints: BTreeMap<String, ColumnHint>) -> Self {
-        Self { domain, hints }
+        let abbreviations = DEFAULT_ABBREVIATIONS
+            .iter()
+            .map(|(abbr, var)| (abbr.to_string(), var.to_string()))
+            .collect();
+        Self {
+            domain,
+            hints,
+            abbreviations,
+        }
+    }
+
+    /// Add or replace an abbreviation for a variable.
+    ///
+    /// The abbreviation is compared against the whole column name, ignoring
+    /// case and separators (`"date_of_visit"` matches `"DATE OF VISIT"`).
+    pub fn with_abbreviation(
+        mut self,
+        abbreviation: impl AsRef<str>,
+        variable: impl Into<String>,
+    ) -> Self {
+        self.abbreviations.insert(
+            normalize(abbreviation.as_ref()).to_uppercase(),
+            variable.into(),
+        );
+        self
+    }
+
+    /// Get the abbreviation table: normalized column name -> variable name.
+    pub fn abbreviations(&self) -> &BTreeMap<String, String> {
+        &self.abbreviations
     }
 
     /// Get the domain this engine is scoring against.
@@ -165,14 +250,51 @@ fn compute_score(&self, column: &str, variable: &SdtmVariable) -> ColumnScore {
         let normalized_col = normalize(column);
         let normalized_var = normalize(&variable.name);
 
-        let base = jaro_winkler::similarity(normalized_col.chars(), normalized_var.chars()) as f32;
-
-        components.push(ScoreComponent {
-            name: "Name similarity",
-            value: base,
-            description: format!("'{}' vs '{}'", column, variable.name),
+        let name_sim =
+            jaro_winkler::similarity(normalized_col.chars(), normalized_var.chars()) as f32;
+
+        // Descriptive names ("Date of Birth") and source labels are compared
+        // against the variable label word by word
+        let label_overlap = variable.label.as_deref().map_or(0.0, |var_label| {
+            let hint_label = self.hints.get(column).and_then(|h| h.label.as_deref());
+            std::iter::once(column)
+                .chain(hint_label)
+                .map(|text| token_overlap(text, var_label))
+                .fold(0.0, f32::max)
         });
 
+        let abbreviation = self
+            .abbreviations
+            .get(&normalized_col.to_uppercase())
+            .filter(|target| target.eq_ignore_ascii_case(&variable.name));
+
+        let base = if abbreviation.is_some() && ABBREVIATION_SCORE > name_sim {
+            components.push(ScoreComponent {
+                name: "Abbreviation",
+                value: ABBREVIATION_SCORE,
+                description: format!("'{}' abbreviates {}", column, variable.name),
+            });
+            ABBREVIATION_SCORE
+        } else if label_overlap > name_sim {
+            components.push(ScoreComponent {
+                name: "Label overlap",
+                value: label_overlap,
+                description: format!(
+                    "'{}' vs '{}'",
+                    column,
+                    variable.label.as_deref().unwrap_or_default()
+                ),
+            });
+            label_overlap
+        } else {
+            components.push(ScoreComponent {
+                name: "Name similarity",
+                value: name_sim,
+                description: format!("'{}' vs '{}'", column, variable.name),
+            });
+            name_sim
+        };
+
         let mut score = base;
 
         // 2. Label similarity boost (+10%)
@@ -214,6 +336,11 @@ fn compute_score(&self, column: &str, variable: &SdtmVariable) -> ColumnScore {
             }
         }
 
+        // 5. Column profile tie-breakers
+        if let Some(hint) = self.hints.get(column) {
+            score = apply_profile_adjustments(hint, variable, score, &mut components);
+        }
+
         ColumnScore {
             score,
             explanation: components,
@@ -247,7 +374,10 @@ fn apply_suffix_adjustments(
                     description: "Column has SEQ but variable doesn't".into(),
                 });
             }
-        } else if var_upper.ends_with("SEQ") {
+        } else if var_upper.ends_with("SEQ")
+            && !self.hints.get(column).is_some_and(looks_like_sequence)
+        {
+            // A unique numeric column is judged by its profile instead
             score *= 0.6;
             components.push(ScoreComponent {
                 name: "SEQ mismatch",
@@ -278,6 +408,90 @@ fn apply_suffix_adjustments(
     }
 }
 
+/// Whether a column's profile matches a --SEQ variable (numeric, all unique).
+fn looks_like_sequence(hint: &ColumnHint) -> bool {
+    hint.is_numeric && hint.unique_ratio >= SEQUENCE_UNIQUE_RATIO
+}
+
+/// Whether a variable holds ISO 8601 dates (--DTC).
+fn is_date_variable(variable: &SdtmVariable) -> bool {
+    variable.name.to_uppercase().ends_with("DTC")
+        || variable
+            .described_value_domain
+            .as_deref()
+            .is_some_and(|domain| domain.contains("ISO 8601"))
+}
+
+/// Adjust a score using the column's profile ratios.
+///
+/// - Unique numeric columns are boosted towards --SEQ (+10%)
+/// - Other highly unique columns are boosted towards identifiers (+5%),
+///   except STUDYID and DOMAIN, which are never mapped from source
+/// - Date-like columns are boosted towards --DTC variables (+10%)
+/// - Coded columns are boosted towards variables with a codelist (+5%)
+/// - Mostly null columns are penalized for Required variables (-10%)
+fn apply_profile_adjustments(
+    hint: &ColumnHint,
+    variable: &SdtmVariable,
+    mut score: f32,
+    components: &mut Vec<ScoreComponent>,
+) -> f32 {
+    if variable.role == Some(VariableRole::Identifier)
+        && !matches!(variable.name.as_str(), "STUDYID" | "DOMAIN")
+    {
+        if variable.name.to_uppercase().ends_with("SEQ") && looks_like_sequence(hint) {
+            score *= 1.10;
+            components.push(ScoreComponent {
+                name: "Sequence profile",
+                value: 0.10,
+                description: "Numeric column with unique values".into(),
+            });
+        } else if hint.unique_ratio >= IDENTIFIER_UNIQUE_RATIO {
+            score *= 1.05;
+            components.push(ScoreComponent {
+                name: "Identifier profile",
+                value: 0.05,
+                description: format!("{:.0}% unique values", hint.unique_ratio * 100.0),
+            });
+        }
+    }
+
+    if hint.date_ratio >= DATE_LIKE_RATIO && is_date_variable(variable) {
+        score *= 1.10;
+        components.push(ScoreComponent {
+            name: "Date profile",
+            value: 0.10,
+            description: format!("{:.0}% of values are dates", hint.date_ratio * 100.0),
+        });
+    }
+
+    if hint.coded_ratio >= CODED_LIKE_RATIO && variable.codelist_code.is_some() {
+        score *= 1.05;
+        components.push(ScoreComponent {
+            name: "Coded profile",
+            value: 0.05,
+            description: format!(
+                "{:.0}% of values look like coded terms",
+                hint.coded_ratio * 100.0
+            ),
+        });
+    }
+
+    if variable.core == Some(CoreDesignation::Required) && hint.null_ratio > SPARSE_NULL_RATIO {
+        score *= 0.90;
+        components.push(ScoreComponent {
+            name: "Sparse column",
+            value: -0.10,
+            description: format!(
+                "{:.0}% missing for a Required variable",
+                hint.null_ratio * 100.0
+            ),
+        });
+    }
+
+    score
+}
+
 /// Normalize a string for comparison.
 ///
 /// - Trims whitespace
@@ -292,34 +506,56 @@ fn normalize(s: &str) -> String {
         .join(" ")
 }
 
+/// Split text into lowercase alphanumeric tokens, dropping stopwords.
+fn tokens(s: &str) -> Vec<String> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .filter(|t| !STOPWORDS.contains(&t.as_str()))
+        .collect()
+}
+
+/// Dice coefficient over the word tokens of `text` and `label`.
+///
+/// A token also matches a label word it abbreviates as a prefix
+/// (`"ID"` matches `"Identifier"`).
+fn token_overlap(text: &str, label: &str) -> f32 {
+    let text_tokens = tokens(text);
+    let label_tokens = tokens(label);
+    if text_tokens.is_empty() || label_tokens.is_empty() {
+        return 0.0;
+    }
+
+    let matched = text_tokens
+        .iter()
+        .filter(|t| {
+            label_tokens
+                .iter()
+                .any(|l| l == *t || (t.len() >= 2 && l.starts_with(t.as_str())))
+        })
+        .count();
+
+    (2 * matched) as f32 / (text_tokens.len() + label_tokens.len()) as f32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tss_standards::VariableType;
+    use tss_standards::sdtm::test_support::{domain, variable};
 
     fn make_variable(name: &str, label: Option<&str>) -> SdtmVariable {
         SdtmVariable {
-            name: name.to_string(),
             label: label.map(String::from),
-            data_type: VariableType::Char,
-            length: None,
-            role: None,
-            core: None,
-            codelist_code: None,
-            described_value_domain: None,
-            order: None,
+            ..variable(name).build()
         }
     }
 
     fn make_domain(variables: Vec<SdtmVariable>) -> SdtmDomain {
-        SdtmDomain {
-            name: "TEST".to_string(),
-            label: Some("Test Domain".to_string()),
-            class: None,
-            structure: None,
-            dataset_name: None,
-            variables,
-        }
+        domain("TEST")
+            .label("Test Domain")
+            .variables(variables)
+            .build()
     }
 
     #[test]
@@ -388,4 +624,249 @@ fn test_explainability() {
             "Explanation should contain name similarity"
         );
     }
+
+    #[test]
+    fn test_components_compose_to_score() {
+        let domain = make_domain(vec![
+            make_variable("AESEQ", Some("Sequence Number")),
+            make_variable("AETERM", Some("Reported Term")),
+            make_variable("AEDECOD", Some("Dictionary-Derived Term")),
+        ]);
+        let hints = BTreeMap::from([
+            (
+                "AE_TERM".to_string(),
+                ColumnHint {
+                    is_numeric: false,
+                    unique_ratio: 0.9,
+                    null_ratio: 0.0,
+                    label: Some("Reported Term".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "SEQ".to_string(),
+                ColumnHint {
+                    is_numeric: true,
+                    unique_ratio: 1.0,
+                    null_ratio: 0.0,
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let engine = ScoringEngine::new(domain, hints);
+
+        let columns = ["AE_TERM", "SEQ", "AEDECODCD", "AESEQ"].map(String::from);
+        for variable in ["AESEQ", "AETERM", "AEDECOD"] {
+            for column in &columns {
+                let score = engine.score(column, variable).unwrap();
+                assert!(
+                    (score.compose() - score.score).abs() < 1e-5,
+                    "{column} -> {variable}: components {} compose to {}, reported {}",
+                    score.explain(),
+                    score.compose(),
+                    score.score
+                );
+            }
+        }
+
+        let term = engine.score("AE_TERM", "AETERM").unwrap();
+        assert!(term.explanation.iter().any(|c| c.name == "Label match"));
+        let seq = engine.score("SEQ", "AESEQ").unwrap();
+        assert!(seq.explanation.iter().any(|c| c.name == "SEQ match"));
+
+        for suggestion in engine.suggest_all(&columns, 0.0) {
+            assert!(
+                !suggestion.score.explanation.is_empty(),
+                "suggestions carry the full breakdown"
+            );
+            assert!((suggestion.score.compose() - suggestion.score.score).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_label_overlap_and_abbreviations() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let dm = domains.iter().find(|d| d.name == "DM").unwrap().clone();
+        let engine = ScoringEngine::new(dm, BTreeMap::new());
+
+        let best = |column: &str| {
+            let variables: Vec<String> = engine
+                .domain()
+                .variables
+                .iter()
+                .map(|v| v.name.clone())
+                .collect();
+            variables
+                .into_iter()
+                .filter_map(|v| engine.score(column, &v).map(|s| (v, s)))
+                .max_by(|a, b| a.1.score.partial_cmp(&b.1.score).unwrap_or(Ordering::Equal))
+                .unwrap()
+        };
+
+        let (variable, score) = best("Subject ID");
+        assert_eq!(variable, "SUBJID");
+        assert!(score.score >= 0.8, "got {}", score.explain());
+
+        let (variable, score) = best("Date of Birth");
+        assert_eq!(variable, "BRTHDTC");
+        assert_eq!(score.explanation[0].name, "Label overlap");
+        assert!(score.score >= 0.6, "got {}", score.explain());
+
+        let (variable, score) = best("DOB");
+        assert_eq!(variable, "BRTHDTC");
+        assert_eq!(score.explanation[0].name, "Abbreviation");
+
+        // Callers can extend the table
+        let engine = engine.with_abbreviation("birth_ctry", "COUNTRY");
+        assert_eq!(engine.abbreviations()["BIRTH CTRY"], "COUNTRY");
+        let score = engine.score("BIRTH-CTRY", "COUNTRY").unwrap();
+        assert!(score.score > 0.9);
+    }
+
+    #[test]
+    fn test_unique_numeric_column_prefers_seq() {
+        let mut seq = make_variable("LBSEQ", Some("Sequence Number"));
+        seq.data_type = VariableType::Num;
+        seq.role = Some(VariableRole::Identifier);
+        let mut result = make_variable("LBSTRESN", Some("Numeric Result/Finding in Std Units"));
+        result.data_type = VariableType::Num;
+        result.role = Some(VariableRole::ResultQualifier);
+        let domain = make_domain(vec![seq, result]);
+
+        let hint = |unique_ratio| ColumnHint {
+            is_numeric: true,
+            unique_ratio,
+            null_ratio: 0.0,
+            ..Default::default()
+        };
+        let columns = vec!["LBSEQNUM".to_string()];
+
+        let unique = ScoringEngine::new(
+            domain.clone(),
+            BTreeMap::from([("LBSEQNUM".to_string(), hint(1.0))]),
+        );
+        let best = unique.score_all_for_variable("LBSEQ", &columns)[0]
+            .1
+            .clone();
+        let other = unique.score("LBSEQNUM", "LBSTRESN").unwrap();
+        assert!(
+            best.score > other.score,
+            "unique numeric column should prefer LBSEQ: {} vs {}",
+            best.explain(),
+            other.explain()
+        );
+        assert!(
+            best.explanation
+                .iter()
+                .any(|c| c.name == "Sequence profile")
+        );
+        assert_eq!(
+            unique.suggest_all(&columns, 0.5)[0].target_variable,
+            "LBSEQ"
+        );
+
+        // Without the profile, the name-based SEQ penalty still applies
+        let repeated = ScoringEngine::new(
+            domain,
+            BTreeMap::from([("LBSEQNUM".to_string(), hint(0.2))]),
+        );
+        assert_eq!(
+            repeated.suggest_all(&columns, 0.5)[0].target_variable,
+            "LBSTRESN"
+        );
+    }
+
+    #[test]
+    fn test_sparse_column_penalized_for_required() {
+        let mut usubjid = make_variable("USUBJID", None);
+        usubjid.core = Some(CoreDesignation::Required);
+        let engine = |null_ratio| {
+            let hint = ColumnHint {
+                is_numeric: false,
+                unique_ratio: 0.5,
+                null_ratio,
+                ..Default::default()
+            };
+            ScoringEngine::new(
+                make_domain(vec![usubjid.clone()]),
+                BTreeMap::from([("USUBJID".to_string(), hint)]),
+            )
+        };
+
+        let full = engine(0.0).score("USUBJID", "USUBJID").unwrap();
+        let sparse = engine(0.8).score("USUBJID", "USUBJID").unwrap();
+        assert!(sparse.score < full.score);
+        assert!(sparse.explanation.iter().any(|c| c.name == "Sparse column"));
+    }
+
+    #[test]
+    fn test_date_and_coded_profiles() {
+        let mut dtc = make_variable("RFSTDTC", Some("Subject Reference Start Date/Time"));
+        dtc.described_value_domain = Some("ISO 8601 datetime or interval".to_string());
+        let mut sex = make_variable("SEX", Some("Sex"));
+        sex.codelist_code = Some("C66731".to_string());
+        let domain = make_domain(vec![dtc, sex]);
+
+        let hints = BTreeMap::from([
+            (
+                "FIRST_DOSE".to_string(),
+                ColumnHint {
+                    unique_ratio: 0.9,
+                    date_ratio: 1.0,
+                    ..Default::default()
+                },
+            ),
+            (
+                "SEXC".to_string(),
+                ColumnHint {
+                    unique_ratio: 0.02,
+                    coded_ratio: 1.0,
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let profiled = ScoringEngine::new(domain.clone(), hints);
+        let plain = ScoringEngine::new(domain, BTreeMap::new());
+
+        let date = profiled.score("FIRST_DOSE", "RFSTDTC").unwrap();
+        assert!(date.score > plain.score("FIRST_DOSE", "RFSTDTC").unwrap().score);
+        assert!(date.explanation.iter().any(|c| c.name == "Date profile"));
+        // Coded columns are not pushed towards date variables
+        let misfit = profiled.score("SEXC", "RFSTDTC").unwrap();
+        assert!(!misfit.explanation.iter().any(|c| c.name == "Date profile"));
+
+        let coded = profiled.score("SEXC", "SEX").unwrap();
+        assert!(coded.score > plain.score("SEXC", "SEX").unwrap().score);
+        assert!(coded.explanation.iter().any(|c| c.name == "Coded profile"));
+    }
+
+    #[test]
+    fn test_identifier_profile_skips_generated_variables() {
+        let hint = ColumnHint {
+            is_numeric: false,
+            unique_ratio: 1.0,
+            null_ratio: 0.0,
+            ..Default::default()
+        };
+        let identifier = |name: &str| {
+            let mut variable = make_variable(name, None);
+            variable.role = Some(VariableRole::Identifier);
+            variable
+        };
+        let engine = ScoringEngine::new(
+            make_domain(vec![identifier("STUDYID"), identifier("USUBJID")]),
+            BTreeMap::from([("SUBJECT_ID".to_string(), hint)]),
+        );
+
+        let boosted = |variable: &str| {
+            engine
+                .score("SUBJECT_ID", variable)
+                .unwrap()
+                .explanation
+                .iter()
+                .any(|c| c.name == "Identifier profile")
+        };
+        assert!(boosted("USUBJID"));
+        assert!(!boosted("STUDYID"));
+    }
 }
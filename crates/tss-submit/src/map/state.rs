@@ -8,8 +8,9 @@
 use serde::{Deserialize, Serialize};
 use tss_standards::{ColumnHint, CoreDesignation, SdtmDomain};
 
+use crate::map::confidence::ConfidenceThresholds;
 use crate::map::error::MappingError;
-use crate::map::score::ScoringEngine;
+use crate::map::score::{ScoreComponent, ScoringEngine};
 
 /// Status of a variable's mapping.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -49,15 +50,58 @@ pub struct MappingSummary {
     pub omitted: usize,
 }
 
+/// Several source columns joined into one target variable.
+///
+/// Blank values are skipped before joining, like SAS `catx`
+/// (e.g., first and last name into a free-text --TRT).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnConcat {
+    /// Source columns, in join order.
+    pub columns: Vec<String>,
+    /// String placed between non-blank values.
+    pub separator: String,
+}
+
+impl ColumnConcat {
+    /// Create a concatenation of `columns` joined by `separator`.
+    pub fn new(
+        columns: impl IntoIterator<Item = impl Into<String>>,
+        separator: impl Into<String>,
+    ) -> Self {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            separator: separator.into(),
+        }
+    }
+
+    /// Name of the derived column holding the joined values (e.g., `"FNAME + LNAME"`).
+    pub fn column_name(&self) -> String {
+        self.columns.join(" + ")
+    }
+
+    /// Join one row's values, skipping blanks.
+    pub fn join<'a>(&self, values: impl IntoIterator<Item = &'a str>) -> String {
+        values
+            .into_iter()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
 /// A single column-to-variable mapping.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Mapping {
-    /// Source column name.
+    /// Source column name (the first column for a concatenation).
     pub source_column: String,
     /// Target SDTM variable name.
     pub target_variable: String,
     /// Confidence score (0.0 to 1.0).
     pub confidence: f32,
+    /// Source columns joined into the target, when more than one feeds it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concat: Option<ColumnConcat>,
 }
 
 /// Exported mapping configuration.
@@ -93,9 +137,16 @@ pub struct MappingState {
     /// Engine suggestions: variable_name -> (column, score).
     suggestions: BTreeMap<String, (String, f32)>,
 
+    /// Score breakdown for each suggestion: variable_name -> components.
+    suggestion_components: BTreeMap<String, Vec<ScoreComponent>>,
+
     /// User-accepted mappings: variable_name -> (column, confidence).
     accepted: BTreeMap<String, (String, f32)>,
 
+    /// Accepted many-to-one mappings: variable_name -> joined columns.
+    /// The variable also has an `accepted` entry for its first column.
+    concatenations: BTreeMap<String, ColumnConcat>,
+
     /// Variables marked as "not collected" with Define-XML reason.
     /// Only allowed for Expected and Permissible variables.
     not_collected: BTreeMap<String, String>,
@@ -134,17 +185,21 @@ pub fn new(
         let scorer = ScoringEngine::new(domain.clone(), hints.clone());
         let auto_suggestions = scorer.suggest_all(source_columns, min_confidence);
 
-        let suggestions: BTreeMap<_, _> = auto_suggestions
-            .into_iter()
-            .map(|s| (s.target_variable, (s.source_column, s.score.score)))
-            .collect();
+        let mut suggestions = BTreeMap::new();
+        let mut suggestion_components = BTreeMap::new();
+        for s in auto_suggestions {
+            suggestions.insert(s.target_variable.clone(), (s.source_column, s.score.score));
+            suggestion_components.insert(s.target_variable, s.score.explanation);
+        }
 
         Self {
             domain,
             study_id: study_id.to_string(),
             scorer,
             suggestions,
+            suggestion_components,
             accepted: BTreeMap::new(),
+            concatenations: BTreeMap::new(),
             not_collected: BTreeMap::new(),
             omitted: BTreeSet::new(),
             auto_generated: BTreeSet::new(),
@@ -153,6 +208,20 @@ pub fn new(
         }
     }
 
+    /// Create new mapping state with suggestions limited by `thresholds`.
+    ///
+    /// Only scores reaching the Low cutoff are suggested; see
+    /// [`MappingState::new`].
+    pub fn with_thresholds(
+        domain: SdtmDomain,
+        study_id: &str,
+        source_columns: &[String],
+        hints: BTreeMap<String, ColumnHint>,
+        thresholds: &ConfidenceThresholds,
+    ) -> Self {
+        Self::new(domain, study_id, source_columns, hints, thresholds.low)
+    }
+
     /// Get the SDTM domain definition.
     pub fn domain(&self) -> &SdtmDomain {
         &self.domain
@@ -202,6 +271,7 @@ pub fn is_auto_generated(&self, variable_name: &str) -> bool {
     pub fn mark_auto_generated(&mut self, variable_name: &str) {
         // Clear any existing mappings
         self.accepted.remove(variable_name);
+        self.concatenations.remove(variable_name);
         self.not_collected.remove(variable_name);
         self.omitted.remove(variable_name);
         // Mark as auto-generated
@@ -229,6 +299,16 @@ pub fn suggestion(&self, variable_name: &str) -> Option<(&str, f32)> {
             .map(|(col, conf)| (col.as_str(), *conf))
     }
 
+    /// Get the score breakdown behind a variable's suggestion.
+    ///
+    /// Explains why the suggested column was matched (name similarity,
+    /// label boost, suffix and type penalties).
+    pub fn suggestion_components(&self, variable_name: &str) -> Option<&[ScoreComponent]> {
+        self.suggestion_components
+            .get(variable_name)
+            .map(Vec::as_slice)
+    }
+
     /// Get accepted mapping for a variable.
     ///
     /// Returns the accepted column and its confidence score.
@@ -257,6 +337,7 @@ pub fn accept_suggestion(&mut self, variable_name: &str) -> Result<(), MappingEr
             .ok_or_else(|| MappingError::VariableNotFound(variable_name.into()))?
             .clone();
 
+        self.concatenations.remove(variable_name);
         self.accepted.insert(variable_name.to_string(), (col, conf));
         Ok(())
     }
@@ -272,19 +353,110 @@ pub fn accept_manual(&mut self, variable_name: &str, column: &str) -> Result<(),
             return Err(MappingError::ColumnNotFound(column.into()));
         }
 
-        // Check if column already used by another variable
+        self.check_column_unused(variable_name, column)?;
+
+        // Manual mappings get confidence 1.0
+        self.concatenations.remove(variable_name);
+        self.accepted
+            .insert(variable_name.to_string(), (column.to_string(), 1.0));
+        Ok(())
+    }
+
+    /// Accept mappings declared by study metadata, bypassing fuzzy matching.
+    ///
+    /// `declared` maps source columns to their intended SDTM variable, written
+    /// as `VARIABLE` or `DOMAIN.VARIABLE` (see
+    /// `tss_ingest::StudyMetadata::declared_targets`). Qualified targets for
+    /// another domain, unknown variables, missing columns, and variables or
+    /// columns that are already mapped are skipped. Suggestions that compete
+    /// for a declared column are dropped.
+    ///
+    /// Returns the variables that were accepted.
+    pub fn accept_declared(&mut self, declared: &BTreeMap<String, String>) -> Vec<String> {
+        let mut accepted = Vec::new();
+        for (column, target) in declared {
+            let name = match target.trim().split_once('.') {
+                Some((domain, name)) if domain.eq_ignore_ascii_case(&self.domain.name) => name,
+                Some(_) => continue,
+                None => target.trim(),
+            };
+            let Some(variable) = self
+                .domain
+                .variables
+                .iter()
+                .find(|v| v.name.eq_ignore_ascii_case(name))
+                .map(|v| v.name.clone())
+            else {
+                continue;
+            };
+            if !self.source_columns.contains(column)
+                || self.accepted.contains_key(&variable)
+                || self.check_column_unused(&variable, column).is_err()
+            {
+                continue;
+            }
+
+            self.suggestions
+                .retain(|var, (col, _)| var == &variable || col != column);
+            self.accepted
+                .insert(variable.clone(), (column.clone(), 1.0));
+            accepted.push(variable);
+        }
+        accepted
+    }
+
+    /// Accept a many-to-one mapping that joins several columns into a variable.
+    ///
+    /// # Errors
+    /// - `MappingError::ColumnNotFound` if `concat` has no columns or a column doesn't exist
+    /// - `MappingError::ColumnAlreadyUsed` if a column is mapped to another variable
+    pub fn accept_concat(
+        &mut self,
+        variable_name: &str,
+        concat: ColumnConcat,
+    ) -> Result<(), MappingError> {
+        let Some(first) = concat.columns.first().cloned() else {
+            return Err(MappingError::ColumnNotFound(concat.column_name()));
+        };
+        for column in &concat.columns {
+            if !self.source_columns.contains(column) {
+                return Err(MappingError::ColumnNotFound(column.clone()));
+            }
+            self.check_column_unused(variable_name, column)?;
+        }
+
+        self.accepted
+            .insert(variable_name.to_string(), (first, 1.0));
+        self.concatenations
+            .insert(variable_name.to_string(), concat);
+        Ok(())
+    }
+
+    /// Get the concatenation accepted for a variable, if any.
+    pub fn concatenation(&self, variable_name: &str) -> Option<&ColumnConcat> {
+        self.concatenations.get(variable_name)
+    }
+
+    /// Get all accepted concatenations.
+    pub fn all_concatenations(&self) -> &BTreeMap<String, ColumnConcat> {
+        &self.concatenations
+    }
+
+    /// Ensure a column is not already mapped to a different variable.
+    fn check_column_unused(&self, variable_name: &str, column: &str) -> Result<(), MappingError> {
         for (var, (col, _)) in &self.accepted {
-            if col == column && var != variable_name {
+            let used = col == column
+                || self
+                    .concatenations
+                    .get(var)
+                    .is_some_and(|c| c.columns.iter().any(|c| c == column));
+            if used && var != variable_name {
                 return Err(MappingError::ColumnAlreadyUsed {
                     column: column.into(),
                     variable: var.clone(),
                 });
             }
         }
-
-        // Manual mappings get confidence 1.0
-        self.accepted
-            .insert(variable_name.to_string(), (column.to_string(), 1.0));
         Ok(())
     }
 
@@ -292,6 +464,7 @@ pub fn accept_manual(&mut self, variable_name: &str, column: &str) -> Result<(),
     ///
     /// Returns `true` if a mapping was removed.
     pub fn clear(&mut self, variable_name: &str) -> bool {
+        self.concatenations.remove(variable_name);
         self.accepted.remove(variable_name).is_some()
     }
 
@@ -299,6 +472,7 @@ pub fn clear(&mut self, variable_name: &str) -> bool {
     ///
     /// Returns `true` if any assignment was removed.
     pub fn clear_assignment(&mut self, variable_name: &str) -> bool {
+        self.concatenations.remove(variable_name);
         let a = self.accepted.remove(variable_name).is_some();
         let b = self.not_collected.remove(variable_name).is_some();
         let c = self.omitted.remove(variable_name);
@@ -333,6 +507,7 @@ pub fn mark_not_collected(
 
         // Clear any existing assignment
         self.accepted.remove(variable_name);
+        self.concatenations.remove(variable_name);
         self.omitted.remove(variable_name);
 
         // Mark as not collected
@@ -368,6 +543,7 @@ pub fn mark_omit(&mut self, variable_name: &str) -> Result<(), MappingError> {
 
         // Clear any existing assignment
         self.accepted.remove(variable_name);
+        self.concatenations.remove(variable_name);
         self.not_collected.remove(variable_name);
 
         // Mark as omitted
@@ -409,6 +585,11 @@ pub fn available_columns(&self) -> Vec<&str> {
             .accepted
             .values()
             .map(|(col, _)| col.as_str())
+            .chain(
+                self.concatenations
+                    .values()
+                    .flat_map(|c| c.columns.iter().map(String::as_str)),
+            )
             .collect();
 
         self.source_columns
@@ -470,6 +651,7 @@ pub fn to_config(&self) -> MappingConfig {
                     source_column: col.clone(),
                     target_variable: var.clone(),
                     confidence: *conf,
+                    concat: self.concatenations.get(var).cloned(),
                 })
                 .collect(),
             not_collected: self.not_collected.clone(),
@@ -545,7 +727,13 @@ pub fn restore_from_snapshot(
             self.suggestions.remove(var);
         }
 
+        let suggestions = &self.suggestions;
+        self.suggestion_components
+            .retain(|var, _| suggestions.contains_key(var));
+
         // Apply the saved decisions
+        self.concatenations
+            .retain(|var, _| accepted.contains_key(var));
         self.accepted = accepted;
         self.not_collected = not_collected;
         self.omitted = omitted;
@@ -556,31 +744,21 @@ pub fn restore_from_snapshot(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tss_standards::{SdtmVariable, VariableType};
+    use tss_standards::SdtmVariable;
+    use tss_standards::sdtm::test_support::{domain, variable};
 
     fn make_variable(name: &str, core: Option<CoreDesignation>) -> SdtmVariable {
         SdtmVariable {
-            name: name.to_string(),
-            label: None,
-            data_type: VariableType::Char,
-            length: None,
-            role: None,
             core,
-            codelist_code: None,
-            described_value_domain: None,
-            order: None,
+            ..variable(name).build()
         }
     }
 
     fn make_domain(variables: Vec<SdtmVariable>) -> SdtmDomain {
-        SdtmDomain {
-            name: "TEST".to_string(),
-            label: Some("Test Domain".to_string()),
-            class: None,
-            structure: None,
-            dataset_name: None,
-            variables,
-        }
+        domain("TEST")
+            .label("Test Domain")
+            .variables(variables)
+            .build()
     }
 
     #[test]
@@ -596,6 +774,12 @@ fn test_new_generates_suggestions() {
         // Should have suggestions for matching columns
         assert!(state.suggestion("USUBJID").is_some());
         assert!(state.suggestion("AETERM").is_some());
+
+        // Each suggestion carries the breakdown that produced its score
+        let (_, conf) = state.suggestion("AETERM").unwrap();
+        let components = state.suggestion_components("AETERM").unwrap();
+        assert_eq!(components[0].name, "Name similarity");
+        assert!((components[0].value - conf).abs() < 1e-6);
     }
 
     #[test]
@@ -611,6 +795,32 @@ fn test_accept_suggestion() {
         assert_eq!(state.status("USUBJID"), VariableStatus::Accepted);
     }
 
+    #[test]
+    fn test_accept_declared() {
+        let domain = make_domain(vec![
+            make_variable("SEX", Some(CoreDesignation::Required)),
+            make_variable("SEXCD", None),
+            make_variable("AGE", None),
+        ]);
+        let columns = vec!["GNDR".to_string(), "SEXCD".to_string()];
+        let mut state = MappingState::new(domain, "STUDY01", &columns, BTreeMap::new(), 0.5);
+        assert_ne!(state.suggestion("SEX").map(|(col, _)| col), Some("GNDR"));
+
+        let declared = BTreeMap::from([
+            ("GNDR".to_string(), "TEST.SEX".to_string()),
+            ("SEXCD".to_string(), "DM.SEXCD".to_string()),
+            ("MISSING".to_string(), "AGE".to_string()),
+        ]);
+        let accepted = state.accept_declared(&declared);
+
+        assert_eq!(accepted, vec!["SEX".to_string()]);
+        assert_eq!(state.status("SEX"), VariableStatus::Accepted);
+        assert_eq!(state.accepted("SEX"), Some(("GNDR", 1.0)));
+        // Target for another domain is ignored; column without a source is skipped
+        assert_ne!(state.status("SEXCD"), VariableStatus::Accepted);
+        assert_eq!(state.status("AGE"), VariableStatus::Unmapped);
+    }
+
     #[test]
     fn test_accept_manual() {
         let domain = make_domain(vec![make_variable("USUBJID", None)]);
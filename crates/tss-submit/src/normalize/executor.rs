@@ -4,18 +4,24 @@
 //! SDTM-compliant output DataFrames.
 
 use polars::prelude::*;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
-use tss_standards::any_to_string;
+use tss_standards::{any_to_string, parse_f64_with};
 
 use super::error::NormalizationError;
 use super::normalization::{
-    calculate_study_day_from_strings, format_iso8601_duration, normalize_ct_value,
-    normalize_without_codelist, parse_numeric, transform_to_iso8601,
+    DateTimePrecision, calculate_study_day_from_strings, format_iso8601_duration, imputation_flag,
+    impute_partial_datetime, normalize_ct_value_with_mode, normalize_without_codelist,
+    parse_date_precision, parse_numeric, transform_to_iso8601,
 };
 use super::types::{
-    NormalizationContext, NormalizationPipeline, NormalizationRule, NormalizationType,
+    DateImputation, NormalizationContext, NormalizationFailure, NormalizationOutput,
+    NormalizationPipeline, NormalizationRule, NormalizationType,
 };
 
+/// Rows (and original values) an executor could not normalize.
+type FailedCells = Vec<(usize, String)>;
+
 /// Execute transformation pipeline on source DataFrame.
 ///
 /// Returns a new DataFrame with only SDTM-compliant columns.
@@ -26,8 +32,27 @@ pub fn execute_normalization(
     pipeline: &NormalizationPipeline,
     context: &NormalizationContext,
 ) -> Result<DataFrame, NormalizationError> {
+    execute_normalization_with_failures(source_df, pipeline, context).map(|output| output.data)
+}
+
+/// Execute transformation pipeline and report cells that failed to normalize.
+///
+/// Produces the same DataFrame as [`execute_normalization`], plus one
+/// [`NormalizationFailure`] per source cell whose value could not be
+/// normalized (unparseable date, duration, or number, or a value outside a
+/// non-extensible codelist). The original value is preserved in the output
+/// exactly as [`execute_normalization`] does.
+pub fn execute_normalization_with_failures(
+    source_df: &DataFrame,
+    pipeline: &NormalizationPipeline,
+    context: &NormalizationContext,
+) -> Result<NormalizationOutput, NormalizationError> {
+    let source_df = &with_concatenated_columns(source_df, context)?;
     let mut columns: Vec<Column> = Vec::with_capacity(pipeline.rules.len());
+    let mut failures = Vec::new();
     let row_count = source_df.height();
+    // With a processor, --SEQ is assigned after it runs: (position, rule)
+    let mut deferred: Vec<(usize, &NormalizationRule)> = Vec::new();
 
     for rule in pipeline.rules_ordered() {
         // Skip omitted variables
@@ -39,11 +64,120 @@ pub fn execute_normalization(
             continue;
         }
 
-        let series = execute_rule(source_df, rule, context, row_count)?;
-        columns.push(series.into_column());
+        if context.processor.is_some() && rule.transform_type == NormalizationType::SequenceNumber {
+            deferred.push((columns.len() + deferred.len(), rule));
+            continue;
+        }
+
+        let mut failed = FailedCells::new();
+        let series = execute_rule(source_df, rule, context, row_count, &mut failed)?;
+        match context.date_imputations.get(&rule.target_variable) {
+            Some(imputation) => columns.extend(impute_dates(series, imputation)?),
+            None => columns.push(series.into_column()),
+        }
+
+        failures.extend(
+            failed
+                .into_iter()
+                .map(|(row, original)| NormalizationFailure {
+                    row,
+                    variable: rule.target_variable.clone(),
+                    original,
+                    rule: rule.transform_type.clone(),
+                }),
+        );
+    }
+
+    let mut data = DataFrame::new(columns).map_err(NormalizationError::PolarsError)?;
+    if let Some(processor) = &context.processor {
+        processor.apply(&mut data, context)?;
+        assign_deferred_sequences(&mut data, &deferred, context)?;
+    }
+    Ok(NormalizationOutput { data, failures })
+}
+
+/// Assign --SEQ on a processed frame, inserting each at its rule position.
+///
+/// Every column of the frame is mapped to itself, so subjects are grouped by
+/// the normalized USUBJID and sort keys read processed values.
+fn assign_deferred_sequences(
+    data: &mut DataFrame,
+    deferred: &[(usize, &NormalizationRule)],
+    context: &NormalizationContext,
+) -> Result<(), NormalizationError> {
+    if deferred.is_empty() {
+        return Ok(());
+    }
+    let mappings: BTreeMap<String, String> = data
+        .get_column_names()
+        .into_iter()
+        .map(|name| (name.to_string(), name.to_string()))
+        .collect();
+    let processed_context = context.clone().with_mappings(mappings);
+    let height = data.height();
+    for &(position, rule) in deferred {
+        let series = execute_sequence(data, &rule.target_variable, &processed_context, height)?;
+        data.insert_column(position.min(data.width()), series.into_column())?;
+    }
+    Ok(())
+}
+
+/// Impute partial dates of a normalized column.
+///
+/// Returns the column unchanged, followed by the derived date column and
+/// its flag column.
+fn impute_dates(
+    series: Series,
+    imputation: &DateImputation,
+) -> Result<Vec<Column>, NormalizationError> {
+    let mut values = Vec::with_capacity(series.len());
+    let mut flags = Vec::with_capacity(series.len());
+    for idx in 0..series.len() {
+        let value = any_to_string(series.get(idx)?);
+        let (imputed, was_imputed) = impute_partial_datetime(&value, imputation.rule);
+        flags.push(was_imputed.then(|| imputation_flag(&value)).flatten());
+        values.push((!imputed.is_empty()).then_some(imputed));
     }
 
-    DataFrame::new(columns).map_err(NormalizationError::PolarsError)
+    Ok(vec![
+        series.into_column(),
+        Column::new(imputation.target_variable.as_str().into(), values),
+        Column::new(imputation.flag_variable.as_str().into(), flags),
+    ])
+}
+
+/// Add one derived column per many-to-one mapping in the context.
+fn with_concatenated_columns<'a>(
+    source_df: &'a DataFrame,
+    context: &NormalizationContext,
+) -> Result<Cow<'a, DataFrame>, NormalizationError> {
+    if context.concatenations.is_empty() {
+        return Ok(Cow::Borrowed(source_df));
+    }
+
+    let mut df = source_df.clone();
+    for concat in context.concatenations.values() {
+        let sources = concat
+            .columns
+            .iter()
+            .map(|name| {
+                source_df
+                    .column(name)
+                    .map_err(|_| NormalizationError::ColumnNotFound(name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut values = Vec::with_capacity(source_df.height());
+        for idx in 0..source_df.height() {
+            let row = sources
+                .iter()
+                .map(|col| col.get(idx).map(any_to_string))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            values.push(concat.join(row.iter().map(String::as_str)));
+        }
+        df.with_column(Series::new(concat.column_name().into(), values))?;
+    }
+    Ok(Cow::Owned(df))
 }
 
 /// Execute a single transformation rule.
@@ -52,6 +186,7 @@ fn execute_rule(
     rule: &NormalizationRule,
     context: &NormalizationContext,
     row_count: usize,
+    failed: &mut FailedCells,
 ) -> Result<Series, NormalizationError> {
     let target_name = &rule.target_variable;
 
@@ -70,13 +205,13 @@ fn execute_rule(
             execute_sequence(source_df, target_name, context, row_count)
         }
         NormalizationType::Iso8601DateTime => {
-            execute_datetime(source_df, target_name, source_col, row_count)
+            execute_datetime(source_df, target_name, source_col, row_count, failed)
         }
         NormalizationType::Iso8601Date => {
-            execute_date(source_df, target_name, source_col, row_count)
+            execute_date(source_df, target_name, source_col, row_count, failed)
         }
         NormalizationType::Iso8601Duration => {
-            execute_duration(source_df, target_name, source_col, row_count)
+            execute_duration(source_df, target_name, source_col, row_count, failed)
         }
         NormalizationType::StudyDay { reference_dtc } => {
             execute_study_day(source_df, target_name, reference_dtc, context, row_count)
@@ -88,10 +223,16 @@ fn execute_rule(
             source_col,
             context,
             row_count,
+            failed,
+        ),
+        NormalizationType::NumericConversion => execute_numeric(
+            source_df,
+            target_name,
+            source_col,
+            context,
+            row_count,
+            failed,
         ),
-        NormalizationType::NumericConversion => {
-            execute_numeric(source_df, target_name, source_col, row_count)
-        }
         NormalizationType::CopyDirect => {
             execute_copy(source_df, target_name, source_col, row_count)
         }
@@ -117,7 +258,11 @@ fn execute_constant(
     Ok(Series::new(target_name.into(), vec![value; row_count]))
 }
 
-/// Execute USUBJID derivation (STUDYID-SUBJID pattern).
+/// Execute USUBJID derivation from `context.usubjid_template`.
+///
+/// With the default template this is the STUDYID-SUBJID pattern. Components
+/// other than STUDYID and SUBJID are skipped (and logged) when unmapped, and
+/// skipped per row when blank.
 ///
 /// Empty SUBJID values produce empty USUBJID (not malformed "STUDY-").
 /// This triggers downstream validation to flag the issue properly.
@@ -127,12 +272,14 @@ fn execute_usubjid(
     context: &NormalizationContext,
     row_count: usize,
 ) -> Result<Series, NormalizationError> {
+    let template = &context.usubjid_template;
+
     // Try to find SUBJID or USUBJID mapping
-    let source_col = context
+    let subjid_col = context
         .get_source_column("SUBJID")
         .or_else(|| context.get_source_column("USUBJID"));
 
-    let Some(source_col) = source_col else {
+    let Some(subjid_col) = subjid_col else {
         // No mapping - return empty strings
         tracing::warn!(
             target = %target_name,
@@ -141,24 +288,69 @@ fn execute_usubjid(
         return Ok(Series::new(target_name.into(), vec![""; row_count]));
     };
 
-    let source_series = df
-        .column(source_col)
-        .map_err(|_| NormalizationError::ColumnNotFound(source_col.to_string()))?;
+    let mut components: Vec<Option<&Column>> = Vec::with_capacity(template.components.len());
+    for component in &template.components {
+        let source_col = match component.as_str() {
+            "STUDYID" => {
+                components.push(None);
+                continue;
+            }
+            "SUBJID" => Some(subjid_col),
+            other => context.get_source_column(other),
+        };
+        let Some(source_col) = source_col else {
+            tracing::warn!(
+                target = %target_name,
+                component = %component,
+                "USUBJID template component is not mapped, skipping"
+            );
+            continue;
+        };
+        let series = df
+            .column(source_col)
+            .map_err(|_| NormalizationError::ColumnNotFound(source_col.to_string()))?;
+        components.push(Some(series));
+    }
+
+    let subjid_series = df
+        .column(subjid_col)
+        .map_err(|_| NormalizationError::ColumnNotFound(subjid_col.to_string()))?;
+
+    // A mapped USUBJID column may already carry the study prefix (re-run)
+    let existing_prefix = (context.get_source_column("SUBJID").is_none()
+        && template.components.first().is_some_and(|c| c == "STUDYID"))
+    .then(|| format!("{}{}", context.study_id, template.separator));
 
     let mut values = Vec::with_capacity(row_count);
     let mut empty_count = 0usize;
 
     for idx in 0..row_count {
-        let subjid = any_to_string(source_series.get(idx)?);
+        let subjid = any_to_string(subjid_series.get(idx)?);
         let trimmed = subjid.trim();
-
         if trimmed.is_empty() {
             // Empty SUBJID produces empty USUBJID (triggers validation)
             empty_count += 1;
             values.push(String::new());
-        } else {
-            values.push(format!("{}-{}", context.study_id, trimmed));
+            continue;
         }
+        if let Some(prefix) = &existing_prefix
+            && trimmed.starts_with(prefix.as_str())
+        {
+            values.push(trimmed.to_string());
+            continue;
+        }
+
+        let mut parts: Vec<String> = Vec::with_capacity(components.len());
+        for component in &components {
+            let part = match component {
+                None => context.study_id.clone(),
+                Some(series) => any_to_string(series.get(idx)?).trim().to_string(),
+            };
+            if !part.is_empty() {
+                parts.push(part);
+            }
+        }
+        values.push(parts.join(&template.separator));
     }
 
     // Log warning for data quality visibility (#113)
@@ -199,26 +391,84 @@ fn execute_sequence(
         .column(source_col)
         .map_err(|_| NormalizationError::ColumnNotFound(source_col.to_string()))?;
 
-    let mut counters: BTreeMap<String, i64> = BTreeMap::new();
-    let mut values = Vec::with_capacity(row_count);
-
+    let mut subjects = Vec::with_capacity(row_count);
     for idx in 0..row_count {
-        let usubjid = any_to_string(source_series.get(idx)?);
-        let key = usubjid.trim().to_string();
-        let count = counters.entry(key).or_insert(0);
+        subjects.push(any_to_string(source_series.get(idx)?).trim().to_string());
+    }
+    let sort_keys = sequence_sort_values(df, target_name, context, row_count)?;
+
+    // Stable sort: ties keep original row order
+    let mut order: Vec<usize> = (0..row_count).collect();
+    order.sort_by(|&a, &b| {
+        subjects[a]
+            .cmp(&subjects[b])
+            .then_with(|| sort_keys[a].cmp(&sort_keys[b]))
+    });
+
+    let mut counters: BTreeMap<&str, i64> = BTreeMap::new();
+    let mut values = vec![0i64; row_count];
+    for idx in order {
+        let count = counters.entry(subjects[idx].as_str()).or_insert(0);
         *count += 1;
-        values.push(*count);
+        values[idx] = *count;
     }
 
     Ok(Series::new(target_name.into(), values))
 }
 
+/// Build per-row sort keys from `context.sequence_sort_keys`.
+///
+/// Values are trimmed; date variables (*DTC) are normalized to ISO 8601 so
+/// they sort chronologically. Blank values sort after populated ones.
+/// Keys without a mapped source column are skipped.
+fn sequence_sort_values(
+    df: &DataFrame,
+    target_name: &str,
+    context: &NormalizationContext,
+    row_count: usize,
+) -> Result<Vec<Vec<(bool, String)>>, NormalizationError> {
+    let mut keys = vec![Vec::new(); row_count];
+
+    for key in &context.sequence_sort_keys {
+        let variable = match key.strip_prefix("--") {
+            Some(suffix) => format!("{}{suffix}", context.domain_code),
+            None => key.clone(),
+        };
+        let Some(source_col) = context.get_source_column(&variable) else {
+            tracing::warn!(
+                target = %target_name,
+                sort_key = %variable,
+                "Sequence sort key is not mapped, ignoring"
+            );
+            continue;
+        };
+        let source_series = df
+            .column(source_col)
+            .map_err(|_| NormalizationError::ColumnNotFound(source_col.to_string()))?;
+        let is_date = variable.ends_with("DTC");
+
+        for (idx, row_key) in keys.iter_mut().enumerate() {
+            let raw = any_to_string(source_series.get(idx)?);
+            let trimmed = raw.trim();
+            let value = if is_date && !trimmed.is_empty() {
+                transform_to_iso8601(trimmed)
+            } else {
+                trimmed.to_string()
+            };
+            row_key.push((value.is_empty(), value));
+        }
+    }
+
+    Ok(keys)
+}
+
 /// Execute ISO 8601 datetime transformation.
 fn execute_datetime(
     df: &DataFrame,
     target_name: &str,
     source_col: Option<&str>,
     row_count: usize,
+    failed: &mut FailedCells,
 ) -> Result<Series, NormalizationError> {
     let Some(source_col) = source_col else {
         return Ok(Series::new(target_name.into(), vec![""; row_count]));
@@ -237,8 +487,12 @@ fn execute_datetime(
         if trimmed.is_empty() {
             values.push(String::new());
         } else {
-            // transform_to_iso8601 preserves partial precision and original on failure
-            values.push(transform_to_iso8601(trimmed));
+            // Preserves partial precision, and the original value on failure
+            let precision = parse_date_precision(trimmed);
+            if matches!(precision, DateTimePrecision::Unknown(_)) {
+                failed.push((idx, trimmed.to_string()));
+            }
+            values.push(precision.to_iso8601());
         }
     }
 
@@ -251,9 +505,10 @@ fn execute_date(
     target_name: &str,
     source_col: Option<&str>,
     row_count: usize,
+    failed: &mut FailedCells,
 ) -> Result<Series, NormalizationError> {
     // Same as datetime but we might want to truncate time if present
-    execute_datetime(df, target_name, source_col, row_count)
+    execute_datetime(df, target_name, source_col, row_count, failed)
 }
 
 /// Execute ISO 8601 duration transformation.
@@ -262,6 +517,7 @@ fn execute_duration(
     target_name: &str,
     source_col: Option<&str>,
     row_count: usize,
+    failed: &mut FailedCells,
 ) -> Result<Series, NormalizationError> {
     let Some(source_col) = source_col else {
         return Ok(Series::new(target_name.into(), vec![""; row_count]));
@@ -287,6 +543,7 @@ fn execute_duration(
                     value = %trimmed,
                     "Failed to parse duration, preserving original"
                 );
+                failed.push((idx, trimmed.to_string()));
                 trimmed.to_string()
             });
             values.push(formatted);
@@ -297,6 +554,10 @@ fn execute_duration(
 }
 
 /// Execute study day calculation.
+///
+/// Each row is counted from its own subject's reference date, looked up by
+/// the derived USUBJID. The study-wide `reference_date` is only used when no
+/// per-subject dates are available.
 fn execute_study_day(
     df: &DataFrame,
     target_name: &str,
@@ -304,15 +565,15 @@ fn execute_study_day(
     context: &NormalizationContext,
     row_count: usize,
 ) -> Result<Series, NormalizationError> {
-    // Get reference date from context (RFSTDTC from DM)
-    let Some(ref_date) = context.reference_date else {
+    if context.subject_reference_dates.is_empty() && context.reference_date.is_none() {
         tracing::warn!(
             target = %target_name,
-            "No reference date (RFSTDTC) available for study day calculation"
+            reference = %context.study_day.reference_variable,
+            "No reference date available for study day calculation"
         );
         let nulls: Vec<Option<i32>> = vec![None; row_count];
         return Ok(Series::new(target_name.into(), nulls));
-    };
+    }
 
     // Get the source DTC column from mappings
     let source_col = context.get_source_column(reference_dtc);
@@ -331,19 +592,32 @@ fn execute_study_day(
         .column(source_col)
         .map_err(|_| NormalizationError::ColumnNotFound(source_col.to_string()))?;
 
-    let ref_date_str = ref_date.format("%Y-%m-%d").to_string();
+    // Subjects are matched on USUBJID as it will appear in the output
+    let subjects = if context.subject_reference_dates.is_empty() {
+        None
+    } else {
+        Some(execute_usubjid(df, "USUBJID", context, row_count)?)
+    };
+
     let mut values: Vec<Option<i32>> = Vec::with_capacity(row_count);
 
     for idx in 0..row_count {
+        let ref_date = match &subjects {
+            Some(subjects) => context
+                .subject_reference_dates
+                .get(any_to_string(subjects.get(idx)?).as_str())
+                .copied(),
+            None => context.reference_date,
+        };
         let event_date_str = any_to_string(source_series.get(idx)?);
         let trimmed = event_date_str.trim();
 
-        if trimmed.is_empty() {
-            values.push(None);
-        } else {
-            // Calculate study day
-            let study_day = calculate_study_day_from_strings(trimmed, &ref_date_str);
-            values.push(study_day);
+        match ref_date {
+            Some(ref_date) if !trimmed.is_empty() => {
+                let ref_date_str = ref_date.format("%Y-%m-%d").to_string();
+                values.push(calculate_study_day_from_strings(trimmed, &ref_date_str));
+            }
+            _ => values.push(None),
         }
     }
 
@@ -358,6 +632,7 @@ fn execute_ct_normalization(
     source_col: Option<&str>,
     context: &NormalizationContext,
     row_count: usize,
+    failed: &mut FailedCells,
 ) -> Result<Series, NormalizationError> {
     let Some(source_col) = source_col else {
         return Ok(Series::new(target_name.into(), vec![""; row_count]));
@@ -371,7 +646,7 @@ fn execute_ct_normalization(
     let codelist = context
         .ct_registry
         .as_ref()
-        .and_then(|registry| registry.resolve(codelist_code, None))
+        .and_then(|registry| registry.resolve_for_standard(codelist_code, context.standard))
         .map(|resolved| resolved.codelist.clone());
 
     let mut values = Vec::with_capacity(row_count);
@@ -383,7 +658,10 @@ fn execute_ct_normalization(
         if trimmed.is_empty() {
             values.push(String::new());
         } else if let Some(ref cl) = codelist {
-            let result = normalize_ct_value(trimmed, cl);
+            let result = normalize_ct_value_with_mode(trimmed, cl, context.ct_resolution_mode);
+            if result.is_error {
+                failed.push((idx, trimmed.to_string()));
+            }
             values.push(result.value);
         } else {
             // No codelist available - use normalize_without_codelist
@@ -400,7 +678,9 @@ fn execute_numeric(
     df: &DataFrame,
     target_name: &str,
     source_col: Option<&str>,
+    context: &NormalizationContext,
     row_count: usize,
+    failed: &mut FailedCells,
 ) -> Result<Series, NormalizationError> {
     let Some(source_col) = source_col else {
         let nulls: Vec<Option<f64>> = vec![None; row_count];
@@ -420,7 +700,11 @@ fn execute_numeric(
         if trimmed.is_empty() {
             values.push(None);
         } else {
-            match parse_numeric(trimmed) {
+            let parsed = match context.decimal_convention {
+                Some(convention) => parse_f64_with(trimmed, convention),
+                None => parse_numeric(trimmed),
+            };
+            match parsed {
                 Some(num) => values.push(Some(num)),
                 None => {
                     tracing::warn!(
@@ -428,6 +712,7 @@ fn execute_numeric(
                         value = %trimmed,
                         "Failed to parse numeric, setting to null"
                     );
+                    failed.push((idx, trimmed.to_string()));
                     values.push(None);
                 }
             }
@@ -466,64 +751,81 @@ fn execute_copy(
 mod tests {
     use super::*;
     use crate::normalize::infer_normalization_rules;
+    use crate::normalize::normalization::ImputationRule;
+    use crate::normalize::types::{DomainProcessor, UsubjidTemplate};
+    use tss_standards::sdtm::test_support::{domain, variable};
     use tss_standards::{
-        CoreDesignation, SdtmDatasetClass, SdtmDomain, SdtmVariable, VariableRole, VariableType,
+        CoreDesignation, DecimalConvention, SdtmDatasetClass, SdtmDomain, VariableRole,
+        VariableType,
     };
 
-    fn create_test_domain() -> SdtmDomain {
-        SdtmDomain {
-            name: "AE".to_string(),
-            label: Some("Adverse Events".to_string()),
-            class: Some(SdtmDatasetClass::Events),
-            structure: None,
-            dataset_name: None,
-            variables: vec![
-                SdtmVariable {
-                    name: "STUDYID".to_string(),
-                    label: Some("Study Identifier".to_string()),
-                    data_type: VariableType::Char,
-                    length: None,
-                    role: Some(VariableRole::Identifier),
-                    core: Some(CoreDesignation::Required),
-                    codelist_code: None,
-                    described_value_domain: None,
-                    order: Some(1),
-                },
-                SdtmVariable {
-                    name: "DOMAIN".to_string(),
-                    label: Some("Domain Abbreviation".to_string()),
-                    data_type: VariableType::Char,
-                    length: None,
-                    role: Some(VariableRole::Identifier),
-                    core: Some(CoreDesignation::Required),
-                    codelist_code: None,
-                    described_value_domain: None,
-                    order: Some(2),
-                },
-                SdtmVariable {
-                    name: "USUBJID".to_string(),
-                    label: Some("Unique Subject Identifier".to_string()),
-                    data_type: VariableType::Char,
-                    length: None,
-                    role: Some(VariableRole::Identifier),
-                    core: Some(CoreDesignation::Required),
-                    codelist_code: None,
-                    described_value_domain: None,
-                    order: Some(3),
-                },
-                SdtmVariable {
-                    name: "AESEQ".to_string(),
-                    label: Some("Sequence Number".to_string()),
-                    data_type: VariableType::Num,
-                    length: None,
-                    role: Some(VariableRole::Identifier),
-                    core: Some(CoreDesignation::Required),
-                    codelist_code: None,
-                    described_value_domain: None,
-                    order: Some(4),
-                },
-            ],
+    /// Assert that re-running a pipeline on its own output changes nothing.
+    ///
+    /// Checks the idempotency contract on [`NormalizationType`]: runs
+    /// the pipeline on `source_df`, then again on the result with every mapped
+    /// output variable mapped to itself, and panics naming the first column that differs.
+    /// Returns the normalized DataFrame from the first run.
+    fn assert_idempotent(
+        source_df: &DataFrame,
+        pipeline: &NormalizationPipeline,
+        context: &NormalizationContext,
+    ) -> Result<DataFrame, NormalizationError> {
+        let first = execute_normalization(source_df, pipeline, context)?;
+
+        let mappings: BTreeMap<String, String> = first
+            .get_column_names()
+            .into_iter()
+            .filter(|name| context.get_source_column(name).is_some())
+            .map(|name| (name.to_string(), name.to_string()))
+            .collect();
+        let mut rerun_pipeline = pipeline.clone();
+        for rule in &mut rerun_pipeline.rules {
+            rule.source_column = mappings.get(&rule.target_variable).cloned();
+        }
+        let rerun_context = context.clone().with_mappings(mappings);
+        let second = execute_normalization(&first, &rerun_pipeline, &rerun_context)?;
+
+        for column in first.get_columns() {
+            let rerun = second.column(column.name())?;
+            assert!(
+                column.equals_missing(rerun),
+                "normalization of {} is not idempotent: {:?} -> {:?}",
+                column.name(),
+                column,
+                rerun
+            );
         }
+        Ok(first)
+    }
+
+    fn create_test_domain() -> SdtmDomain {
+        domain("AE")
+            .label("Adverse Events")
+            .class(SdtmDatasetClass::Events)
+            .variables([
+                variable("STUDYID")
+                    .label("Study Identifier")
+                    .role(VariableRole::Identifier)
+                    .core(CoreDesignation::Required)
+                    .order(1),
+                variable("DOMAIN")
+                    .label("Domain Abbreviation")
+                    .role(VariableRole::Identifier)
+                    .core(CoreDesignation::Required)
+                    .order(2),
+                variable("USUBJID")
+                    .label("Unique Subject Identifier")
+                    .role(VariableRole::Identifier)
+                    .core(CoreDesignation::Required)
+                    .order(3),
+                variable("AESEQ")
+                    .label("Sequence Number")
+                    .data_type(VariableType::Num)
+                    .role(VariableRole::Identifier)
+                    .core(CoreDesignation::Required)
+                    .order(4),
+            ])
+            .build()
     }
 
     #[test]
@@ -557,6 +859,330 @@ fn test_execute_constant_unknown_returns_error() {
         assert!(matches!(err, NormalizationError::UnknownConstant { name } if name == "UNKNOWN"));
     }
 
+    #[test]
+    fn test_execute_duration_preserves_unparseable() {
+        let df = df! {
+            "DURATION" => &["2 weeks", "3 months", "1.5 hours", "", "about a day"],
+        }
+        .unwrap();
+
+        let result = execute_duration(&df, "AEDUR", Some("DURATION"), 5, &mut Vec::new()).unwrap();
+        let values: Vec<&str> = result.str().unwrap().into_no_null_iter().collect();
+        assert_eq!(values, vec!["P2W", "P3M", "PT1H30M", "", "about a day"]);
+    }
+
+    #[test]
+    fn test_execute_sequence_sorted_by_keys() {
+        let mut mappings = BTreeMap::new();
+        mappings.insert("USUBJID".to_string(), "SUBJ".to_string());
+        mappings.insert("LBDTC".to_string(), "DATE".to_string());
+        mappings.insert("LBTESTCD".to_string(), "TEST".to_string());
+        let context = NormalizationContext::new("CDISC01", "LB")
+            .with_mappings(mappings)
+            .with_sequence_sort_keys(vec!["--DTC".to_string(), "--TESTCD".to_string()]);
+
+        let rows = [
+            ("001", "2024-01-02", "HGB"),
+            ("002", "2024-01-01", "ALT"),
+            ("001", "01/01/2024", "HGB"),
+            ("001", "2024-01-01", "ALT"),
+            ("001", "", "ALT"),
+        ];
+        let expected_seq = |subj: &str, date: &str, test: &str| match (subj, date, test) {
+            ("001", "2024-01-01", "ALT") => 1,
+            ("001", "01/01/2024", "HGB") => 2,
+            ("001", "2024-01-02", "HGB") => 3,
+            ("001", "", "ALT") => 4,
+            ("002", _, _) => 1,
+            _ => unreachable!(),
+        };
+
+        // Same records in two different row orders get the same --SEQ
+        for permutation in [[0, 1, 2, 3, 4], [4, 3, 2, 1, 0]] {
+            let subj: Vec<&str> = permutation.iter().map(|&i| rows[i].0).collect();
+            let date: Vec<&str> = permutation.iter().map(|&i| rows[i].1).collect();
+            let test: Vec<&str> = permutation.iter().map(|&i| rows[i].2).collect();
+            let df = df! { "SUBJ" => &subj, "DATE" => &date, "TEST" => &test }.unwrap();
+
+            let result = execute_sequence(&df, "LBSEQ", &context, 5).unwrap();
+            for (row, &i) in permutation.iter().enumerate() {
+                let (s, d, t) = rows[i];
+                assert_eq!(
+                    result.get(row).unwrap(),
+                    AnyValue::Int64(expected_seq(s, d, t))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_sequence_ties_keep_row_order() {
+        let mut mappings = BTreeMap::new();
+        mappings.insert("USUBJID".to_string(), "SUBJ".to_string());
+        let context = NormalizationContext::new("CDISC01", "AE").with_mappings(mappings);
+
+        let df = df! { "SUBJ" => &["001", "002", "001"] }.unwrap();
+        let result = execute_sequence(&df, "AESEQ", &context, 3).unwrap();
+        let values: Vec<i64> = result.i64().unwrap().into_no_null_iter().collect();
+        assert_eq!(values, vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn test_execute_usubjid_default_template() {
+        let mut mappings = BTreeMap::new();
+        mappings.insert("SUBJID".to_string(), "SUBJ".to_string());
+        let context = NormalizationContext::new("CDISC01", "DM").with_mappings(mappings);
+
+        let df = df! { "SUBJ" => &["001", " 002 ", ""] }.unwrap();
+        let result = execute_usubjid(&df, "USUBJID", &context, 3).unwrap();
+        let values: Vec<&str> = result.str().unwrap().into_no_null_iter().collect();
+        assert_eq!(values, vec!["CDISC01-001", "CDISC01-002", ""]);
+    }
+
+    #[test]
+    fn test_execute_usubjid_site_template() {
+        let mut mappings = BTreeMap::new();
+        mappings.insert("SUBJID".to_string(), "SUBJ".to_string());
+        mappings.insert("SITEID".to_string(), "SITE".to_string());
+        let df = df! { "SUBJ" => &["001", "002"], "SITE" => &["S01", ""] }.unwrap();
+
+        let context = NormalizationContext::new("CDISC01", "DM")
+            .with_mappings(mappings.clone())
+            .with_usubjid_template(UsubjidTemplate::with_site());
+        let result = execute_usubjid(&df, "USUBJID", &context, 2).unwrap();
+        let values: Vec<&str> = result.str().unwrap().into_no_null_iter().collect();
+        // Blank SITEID falls back to STUDYID-SUBJID
+        assert_eq!(values, vec!["CDISC01-S01-001", "CDISC01-002"]);
+
+        let context = NormalizationContext::new("CDISC01", "DM")
+            .with_mappings(mappings)
+            .with_usubjid_template(UsubjidTemplate::new(["STUDYID", "SUBJID"], "."));
+        let result = execute_usubjid(&df, "USUBJID", &context, 2).unwrap();
+        let values: Vec<&str> = result.str().unwrap().into_no_null_iter().collect();
+        assert_eq!(values, vec!["CDISC01.001", "CDISC01.002"]);
+    }
+
+    #[test]
+    fn test_execute_usubjid_unmapped_component() {
+        let mut mappings = BTreeMap::new();
+        mappings.insert("SUBJID".to_string(), "SUBJ".to_string());
+        let context = NormalizationContext::new("CDISC01", "DM")
+            .with_mappings(mappings)
+            .with_usubjid_template(UsubjidTemplate::with_site());
+
+        let df = df! { "SUBJ" => &["001"] }.unwrap();
+        let result = execute_usubjid(&df, "USUBJID", &context, 1).unwrap();
+        assert_eq!(result.get(0).unwrap(), AnyValue::String("CDISC01-001"));
+    }
+
+    #[test]
+    fn test_execute_normalization_reports_failures() {
+        let mut domain = create_test_domain();
+        domain.variables.push(
+            variable("AESTDTC")
+                .label("Start Date/Time of Adverse Event")
+                .role(VariableRole::Timing)
+                .core(CoreDesignation::Expected)
+                .order(5)
+                .build(),
+        );
+        let pipeline = infer_normalization_rules(&domain);
+
+        let df = df! {
+            "SUBJECT" => &["001", "001", "002"],
+            "START" => &["01/15/2024", "not a date", ""],
+        }
+        .unwrap();
+
+        let mut mappings = BTreeMap::new();
+        mappings.insert("SUBJID".to_string(), "SUBJECT".to_string());
+        mappings.insert("AESTDTC".to_string(), "START".to_string());
+        let context = NormalizationContext::new("CDISC01", "AE").with_mappings(mappings);
+
+        let output = execute_normalization_with_failures(&df, &pipeline, &context).unwrap();
+
+        assert_eq!(output.failures.len(), 1);
+        let failure = &output.failures[0];
+        assert_eq!(failure.row, 1);
+        assert_eq!(failure.variable, "AESTDTC");
+        assert_eq!(failure.original, "not a date");
+        assert_eq!(failure.rule, NormalizationType::Iso8601DateTime);
+
+        // Original value is preserved in the output frame
+        let start = output.data.column("AESTDTC").unwrap();
+        assert_eq!(start.get(0).unwrap(), AnyValue::String("2024-01-15"));
+        assert_eq!(start.get(1).unwrap(), AnyValue::String("not a date"));
+    }
+
+    #[test]
+    fn test_execute_numeric_comma_decimals() {
+        let df = df! { "RESULT" => &["2,75", "1.234,5", "12"] }.unwrap();
+        let context = NormalizationContext::new("CDISC01", "LB")
+            .with_decimal_convention(DecimalConvention::Comma);
+        let mut failed = Vec::new();
+
+        let series =
+            execute_numeric(&df, "LBSTRESN", Some("RESULT"), &context, 3, &mut failed).unwrap();
+
+        let values: Vec<Option<f64>> = series.f64().unwrap().into_iter().collect();
+        assert_eq!(values, [Some(2.75), Some(1234.5), Some(12.0)]);
+        assert!(failed.is_empty());
+    }
+
+    #[test]
+    fn test_execute_normalization_imputes_partial_dates() {
+        let mut domain = create_test_domain();
+        domain.variables.push(
+            variable("AESTDTC")
+                .role(VariableRole::Timing)
+                .core(CoreDesignation::Expected)
+                .order(5)
+                .build(),
+        );
+        let pipeline = infer_normalization_rules(&domain);
+
+        let df = df! {
+            "SUBJECT" => &["001", "001", "002"],
+            "START" => &["2024", "2024-02", "01/15/2024"],
+        }
+        .unwrap();
+        let mut mappings = BTreeMap::new();
+        mappings.insert("SUBJID".to_string(), "SUBJECT".to_string());
+        mappings.insert("AESTDTC".to_string(), "START".to_string());
+        let context = NormalizationContext::new("CDISC01", "AE")
+            .with_mappings(mappings)
+            .with_date_imputation(
+                "AESTDTC",
+                DateImputation::new(ImputationRule::LastDay, "ASTDT"),
+            );
+
+        let result = execute_normalization(&df, &pipeline, &context).unwrap();
+        let column = |name: &str| -> Vec<String> {
+            let values = result.column(name).unwrap();
+            (0..values.len())
+                .map(|idx| any_to_string(values.get(idx).unwrap()))
+                .collect()
+        };
+        // The SDTM variable keeps its collected precision
+        assert_eq!(column("AESTDTC"), ["2024", "2024-02", "2024-01-15"]);
+        assert_eq!(column("ASTDT"), ["2024-12-31", "2024-02-29", "2024-01-15"]);
+        assert_eq!(column("ASTDTF"), ["M", "D", ""]);
+
+        // Without configuration partial dates keep their precision
+        let context =
+            NormalizationContext::new("CDISC01", "AE").with_mappings(context.mappings.clone());
+        let result = execute_normalization(&df, &pipeline, &context).unwrap();
+        assert!(result.column("ASTDT").is_err());
+        assert!(result.column("ASTDTF").is_err());
+        assert_eq!(
+            result.column("AESTDTC").unwrap().get(0).unwrap(),
+            AnyValue::String("2024")
+        );
+    }
+
+    #[test]
+    fn test_execute_normalization_processor_runs_before_sequence() {
+        let mut domain = create_test_domain();
+        domain.variables.push(
+            variable("AETERM")
+                .role(VariableRole::Topic)
+                .core(CoreDesignation::Required)
+                .order(5)
+                .build(),
+        );
+        let pipeline = infer_normalization_rules(&domain);
+
+        let df = df! {
+            "SUBJECT" => &["001", "001", "001", "002"],
+            "TERM" => &["Nausea", "Severe headache", "Rash", "Fatigue"],
+        }
+        .unwrap();
+        let mut mappings = BTreeMap::new();
+        mappings.insert("SUBJID".to_string(), "SUBJECT".to_string());
+        mappings.insert("AETERM".to_string(), "TERM".to_string());
+
+        // Sponsor derivation: order events alphabetically by term
+        let processor = DomainProcessor::new(|df, context| {
+            assert_eq!(context.domain_code, "AE");
+            let terms = df.column("AETERM")?.str()?.clone();
+            let ranks: Vec<String> = terms
+                .into_iter()
+                .map(|term| term.unwrap_or_default().to_lowercase())
+                .collect();
+            df.with_column(Series::new("SORTTERM".into(), ranks))?;
+            Ok(())
+        });
+        let context = NormalizationContext::new("CDISC01", "AE")
+            .with_mappings(mappings)
+            .with_sequence_sort_keys(vec!["SORTTERM".to_string()])
+            .with_processor(processor);
+
+        let result = execute_normalization(&df, &pipeline, &context).unwrap();
+        assert_eq!(
+            result.get_column_names(),
+            [
+                "STUDYID", "DOMAIN", "USUBJID", "AESEQ", "AETERM", "SORTTERM"
+            ]
+        );
+        let seq: Vec<i64> = result
+            .column("AESEQ")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        // Nausea, Rash, Severe headache for subject 001
+        assert_eq!(seq, [1, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_execute_normalization_idempotent() {
+        let mut domain = create_test_domain();
+        for (name, data_type, order) in [
+            ("AESTDTC", VariableType::Char, 5),
+            ("AEDUR", VariableType::Char, 6),
+            ("AESTDY", VariableType::Num, 7),
+            ("AETOXGR", VariableType::Num, 8),
+        ] {
+            domain.variables.push(
+                variable(name)
+                    .data_type(data_type)
+                    .core(CoreDesignation::Permissible)
+                    .order(order)
+                    .build(),
+            );
+        }
+        let pipeline = infer_normalization_rules(&domain);
+
+        let df = df! {
+            "SUBJECT" => &["001", "001", "002"],
+            "START" => &["2023-01-01", "01/15/2023", "2023-02"],
+            "DURATION" => &["2 weeks", "P3D", "bad"],
+            "GRADE" => &["1", "2.5", "x"],
+        }
+        .unwrap();
+
+        // USUBJID mapped directly: an existing study prefix is not added twice
+        let mut mappings = BTreeMap::new();
+        mappings.insert("USUBJID".to_string(), "SUBJECT".to_string());
+        mappings.insert("AESTDTC".to_string(), "START".to_string());
+        mappings.insert("AEDUR".to_string(), "DURATION".to_string());
+        mappings.insert("AETOXGR".to_string(), "GRADE".to_string());
+        let context = NormalizationContext::new("CDISC01", "AE")
+            .with_mappings(mappings)
+            .with_reference_date(chrono::NaiveDate::from_ymd_opt(2023, 1, 1));
+
+        let result = assert_idempotent(&df, &pipeline, &context).unwrap();
+        assert_eq!(
+            result.column("USUBJID").unwrap().get(0).unwrap(),
+            AnyValue::String("CDISC01-001")
+        );
+        assert_eq!(
+            result.column("AESTDTC").unwrap().get(0).unwrap(),
+            AnyValue::String("2023-01-01")
+        );
+    }
+
     #[test]
     fn test_execute_normalization() {
         let domain = create_test_domain();
@@ -167,19 +167,10 @@ fn generate_description(var_name: &str, transform_type: &NormalizationType) -> S
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tss_standards::sdtm::test_support::variable;
 
     fn make_variable(name: &str) -> SdtmVariable {
-        SdtmVariable {
-            name: name.to_string(),
-            label: None,
-            data_type: VariableType::Char,
-            length: None,
-            role: None,
-            core: None,
-            codelist_code: None,
-            described_value_domain: None,
-            order: None,
-        }
+        variable(name).build()
     }
 
     #[test]
@@ -44,9 +44,17 @@
 
 pub mod normalization;
 
+pub use normalization::{
+    CtResolutionMode, ImputationRule, StudyDayConfig, UnitConversion, UnitConversionTable,
+    convert_units, convert_units_with_overwrite, derive_epoch, impute_partial_datetime,
+    transliterate_to_ascii,
+};
+
 // Core types
 pub use types::{
-    NormalizationContext, NormalizationPipeline, NormalizationRule, NormalizationType,
+    DateImputation, DomainProcessor, NormalizationContext, NormalizationFailure,
+    NormalizationOutput, NormalizationPipeline, NormalizationRule, NormalizationType,
+    UsubjidTemplate,
 };
 
 // Error type
@@ -56,10 +64,12 @@
 pub use inference::infer_normalization_rules;
 
 // Execution
-pub use executor::execute_normalization;
+pub use executor::{execute_normalization, execute_normalization_with_failures};
 
 // Preview for validation
 pub use preview::{
-    build_preview_dataframe, build_preview_dataframe_with_dm,
+    build_preview_dataframe, build_preview_dataframe_for_mapping,
+    build_preview_dataframe_for_mapping_with_study_day, build_preview_dataframe_with_dm,
     build_preview_dataframe_with_dm_and_omitted, build_preview_dataframe_with_omitted,
+    build_preview_dataframe_with_study_day,
 };
@@ -0,0 +1,70 @@
+//! ASCII transliteration for XPT output.
+//!
+//! XPT V5 files are effectively ASCII. This module maps common Unicode
+//! punctuation (typically pasted in from word processors) to ASCII.
+
+use std::borrow::Cow;
+
+/// Map a character to its ASCII replacement, if it has a common one.
+fn ascii_replacement(c: char) -> Option<&'static str> {
+    let replacement = match c {
+        // Dashes and hyphens
+        '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2212}' => "-",
+        '\u{2014}' | '\u{2015}' => "--",
+        // Quotes
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{2032}' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{2033}' => "\"",
+        // Spaces and whitespace control characters
+        '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2009}' | '\t' => " ",
+        '\u{200B}' | '\u{FEFF}' => "",
+        // Other punctuation
+        '\u{2026}' => "...",
+        '\u{2022}' => "*",
+        '\u{00B5}' | '\u{03BC}' => "u",
+        '\u{00D7}' => "x",
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+/// Replace common Unicode punctuation with ASCII equivalents.
+///
+/// En dashes become "-", smart quotes become straight quotes, non-breaking
+/// spaces and tabs become spaces, and so on. Characters without a common
+/// equivalent (e.g., accented letters) are kept, so the result should still
+/// be validated. Returns the input unchanged (borrowed) if nothing was replaced.
+pub fn transliterate_to_ascii(value: &str) -> Cow<'_, str> {
+    if !value.chars().any(|c| ascii_replacement(c).is_some()) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match ascii_replacement(c) {
+            Some(replacement) => result.push_str(replacement),
+            None => result.push(c),
+        }
+    }
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate_punctuation() {
+        assert_eq!(transliterate_to_ascii("Grade 1\u{2013}2"), "Grade 1-2");
+        assert_eq!(
+            transliterate_to_ascii("\u{201C}mild\u{201D} patient\u{2019}s"),
+            "\"mild\" patient's"
+        );
+        assert_eq!(transliterate_to_ascii("HEADACHE\tMILD"), "HEADACHE MILD");
+        assert!(matches!(
+            transliterate_to_ascii("ASCII only"),
+            Cow::Borrowed(_)
+        ));
+        // No common equivalent: left for validation to report
+        assert_eq!(transliterate_to_ascii("Caf\u{00E9}"), "Caf\u{00E9}");
+    }
+}
@@ -3,9 +3,25 @@
 //! CT normalization behavior differs based on codelist extensibility:
 //! - **Non-extensible**: Only CDISC values allowed. Invalid = ERROR (compliance violation)
 //! - **Extensible**: Sponsor values allowed. Invalid = INFO (valid sponsor extension)
+//!
+//! How far a source value may be from its submission value before it is
+//! considered "not found" is controlled by [`CtResolutionMode`].
 
+use serde::{Deserialize, Serialize};
 use tss_standards::Codelist;
 
+/// How CT normalization resolves source values to submission values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CtResolutionMode {
+    /// Match submission values and CDISC synonyms (case-insensitive).
+    #[default]
+    Standard,
+
+    /// Also match decode strings: NCI preferred terms, with punctuation and
+    /// whitespace differences ignored (e.g., "Not-Applicable" -> "NA").
+    SynonymAware,
+}
+
 /// Result of CT normalization.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CtNormalizationResult {
@@ -76,6 +92,19 @@ pub fn not_found_non_extensible(value: impl Into<String>) -> Self {
 /// assert!(result.is_error); // Non-extensible: compliance violation
 /// ```
 pub fn normalize_ct_value(value: &str, codelist: &Codelist) -> CtNormalizationResult {
+    normalize_ct_value_with_mode(value, codelist, CtResolutionMode::Standard)
+}
+
+/// Normalize a value using controlled terminology, with an explicit resolution mode.
+///
+/// Behaves like [`normalize_ct_value`], but with [`CtResolutionMode::SynonymAware`]
+/// a value that is neither a submission value nor a synonym is also matched
+/// against each term's decode before being preserved as not found.
+pub fn normalize_ct_value_with_mode(
+    value: &str,
+    codelist: &Codelist,
+    mode: CtResolutionMode,
+) -> CtNormalizationResult {
     let trimmed = value.trim();
 
     // Empty values pass through
@@ -89,6 +118,18 @@ pub fn normalize_ct_value(value: &str, codelist: &Codelist) -> CtNormalizationRe
         return CtNormalizationResult::found(normalized.to_string());
     }
 
+    if mode == CtResolutionMode::SynonymAware
+        && let Some(normalized) = find_submission_value_by_decode(trimmed, codelist)
+    {
+        tracing::debug!(
+            codelist = %codelist.code,
+            value = %trimmed,
+            submission_value = %normalized,
+            "CT value resolved from decode"
+        );
+        return CtNormalizationResult::found(normalized.to_string());
+    }
+
     // Not found - behavior depends on extensibility
     if codelist.extensible {
         // Extensible: preserve exact case, log as info (valid sponsor extension)
@@ -111,6 +152,37 @@ pub fn normalize_ct_value(value: &str, codelist: &Codelist) -> CtNormalizationRe
     }
 }
 
+/// Find a submission value whose decode matches `value`.
+///
+/// Compares punctuation- and whitespace-insensitive keys of the submission
+/// value, synonyms, and NCI preferred term of every term.
+fn find_submission_value_by_decode<'a>(value: &str, codelist: &'a Codelist) -> Option<&'a str> {
+    let key = decode_key(value);
+    if key.is_empty() {
+        return None;
+    }
+    codelist
+        .terms
+        .values()
+        .find(|term| {
+            std::iter::once(&term.submission_value)
+                .chain(&term.synonyms)
+                .chain(&term.preferred_term)
+                .any(|candidate| decode_key(candidate) == key)
+        })
+        .map(|term| term.submission_value.as_str())
+}
+
+/// Uppercase alphanumeric words joined by single spaces.
+fn decode_key(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_uppercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Normalize a value without a codelist available.
 ///
 /// When the codelist cannot be found in the registry, we preserve
@@ -221,6 +293,46 @@ fn test_empty_value() {
         assert!(!result.is_error);
     }
 
+    #[test]
+    fn test_synonym_aware_preferred_term() {
+        let mut codelist = create_test_codelist(false);
+        codelist.add_term(Term {
+            code: "C48660".to_string(),
+            submission_value: "NA".to_string(),
+            synonyms: vec![],
+            definition: None,
+            preferred_term: Some("Not Applicable".to_string()),
+        });
+
+        // Standard mode does not consult the preferred term
+        let result = normalize_ct_value("Not Applicable", &codelist);
+        assert_eq!(result.value, "Not Applicable");
+        assert!(!result.found);
+
+        let result = normalize_ct_value_with_mode(
+            "not-applicable",
+            &codelist,
+            CtResolutionMode::SynonymAware,
+        );
+        assert_eq!(result.value, "NA");
+        assert!(result.found);
+        assert!(!result.is_error);
+    }
+
+    #[test]
+    fn test_synonym_aware_preserves_unmatched() {
+        let codelist = create_test_codelist(false);
+        let result =
+            normalize_ct_value_with_mode("Other", &codelist, CtResolutionMode::SynonymAware);
+        assert_eq!(result.value, "Other");
+        assert!(!result.found);
+        assert!(result.is_error);
+
+        let result =
+            normalize_ct_value_with_mode("Male", &codelist, CtResolutionMode::SynonymAware);
+        assert_eq!(result.value, "M");
+    }
+
     #[test]
     fn test_whitespace_trimming() {
         let codelist = create_test_codelist(false);
@@ -5,6 +5,7 @@
 //! while preserving partial precision (e.g., 2003-12 stays 2003-12).
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
 
 /// Result of parsing a date/time string.
 #[derive(Debug, Clone, PartialEq)]
@@ -301,6 +302,78 @@ pub fn transform_to_iso8601(value: &str) -> String {
     precision.to_iso8601()
 }
 
+/// How a missing day (and month) of a partial date is filled in.
+///
+/// Used for ADaM analysis dates; SDTM --DTC values always keep their
+/// collected precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImputationRule {
+    /// First day of the month, or January 1 for a year (2024-03 -> 2024-03-01).
+    FirstDay,
+    /// The 15th of the month, or July 1 for a year (2024-03 -> 2024-03-15).
+    MidMonth,
+    /// Last day of the month, or December 31 for a year (2024-02 -> 2024-02-29).
+    LastDay,
+}
+
+/// Impute a partial date (YYYY or YYYY-MM) to a complete YYYY-MM-DD date.
+///
+/// Returns the imputed date and `true`, or the value unchanged and `false`
+/// when it is not a partial date: complete dates and datetimes, blanks, and
+/// unparseable values are never altered.
+pub fn impute_partial_datetime(value: &str, rule: ImputationRule) -> (String, bool) {
+    let Some((year, month)) = partial_date(value) else {
+        return (value.to_string(), false);
+    };
+    let (month, day) = match (rule, month) {
+        (ImputationRule::FirstDay, month) => (month.unwrap_or(1), 1),
+        (ImputationRule::MidMonth, Some(month)) => (month, 15),
+        (ImputationRule::MidMonth, None) => (7, 1),
+        (ImputationRule::LastDay, month) => {
+            let month = month.unwrap_or(12);
+            (month, last_day_of_month(year, month))
+        }
+    };
+    match NaiveDate::from_ymd_opt(year, month, day) {
+        Some(date) => (format_iso8601_date(date), true),
+        None => (value.to_string(), false),
+    }
+}
+
+/// ADaM date imputation flag (--DTF) for a value before imputation.
+///
+/// Returns `"M"` when month and day are imputed (YYYY), `"D"` when only the
+/// day is (YYYY-MM), and `None` when the value is not a partial date.
+pub fn imputation_flag(value: &str) -> Option<&'static str> {
+    partial_date(value).map(|(_, month)| if month.is_some() { "D" } else { "M" })
+}
+
+/// Year and optional month of a partial date (YYYY or YYYY-MM).
+fn partial_date(value: &str) -> Option<(i32, Option<u32>)> {
+    match parse_date_precision(value) {
+        DateTimePrecision::Year(year) => Some((year, None)),
+        DateTimePrecision::YearMonth { year, month } => Some((year, Some(month))),
+        DateTimePrecision::Iso8601(s) if s.len() == 4 => Some((s.parse().ok()?, None)),
+        DateTimePrecision::Iso8601(s) if s.len() == 7 => {
+            let month = s[5..7].parse().ok().filter(|m| (1..=12).contains(m))?;
+            Some((s[0..4].parse().ok()?, Some(month)))
+        }
+        _ => None,
+    }
+}
+
+/// Number of the last day of a month.
+fn last_day_of_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|first| first.pred_opt())
+        .map_or(28, |last| last.day())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -366,4 +439,74 @@ fn test_format_iso8601_date() {
         let d = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
         assert_eq!(format_iso8601_date(d), "2024-01-15");
     }
+
+    #[test]
+    fn test_impute_first_day() {
+        let rule = ImputationRule::FirstDay;
+        assert_eq!(
+            impute_partial_datetime("2024", rule),
+            ("2024-01-01".to_string(), true)
+        );
+        assert_eq!(
+            impute_partial_datetime("2024-03", rule),
+            ("2024-03-01".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn test_impute_mid_month() {
+        let rule = ImputationRule::MidMonth;
+        assert_eq!(
+            impute_partial_datetime("2024", rule),
+            ("2024-07-01".to_string(), true)
+        );
+        assert_eq!(
+            impute_partial_datetime("2024-03", rule),
+            ("2024-03-15".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn test_impute_last_day() {
+        let rule = ImputationRule::LastDay;
+        assert_eq!(
+            impute_partial_datetime("2024", rule),
+            ("2024-12-31".to_string(), true)
+        );
+        assert_eq!(
+            impute_partial_datetime("2024-02", rule),
+            ("2024-02-29".to_string(), true)
+        );
+        assert_eq!(
+            impute_partial_datetime("2023-02", rule),
+            ("2023-02-28".to_string(), true)
+        );
+        assert_eq!(
+            impute_partial_datetime("2024-12", rule),
+            ("2024-12-31".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn test_impute_leaves_complete_values() {
+        for value in ["2024-03-15", "2024-03-15T10:30", "", "UNKNOWN", "2024-13"] {
+            for rule in [
+                ImputationRule::FirstDay,
+                ImputationRule::MidMonth,
+                ImputationRule::LastDay,
+            ] {
+                assert_eq!(
+                    impute_partial_datetime(value, rule),
+                    (value.to_string(), false)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_imputation_flag() {
+        assert_eq!(imputation_flag("2024"), Some("M"));
+        assert_eq!(imputation_flag("2024-03"), Some("D"));
+        assert_eq!(imputation_flag("2024-03-15"), None);
+    }
 }
@@ -103,151 +103,112 @@ fn format_duration_from_days(days: f64) -> String {
     }
 }
 
+/// Unit of a free-text duration component.
+#[derive(Debug, Clone, Copy)]
+enum DurationUnit {
+    Years,
+    Months,
+    Weeks,
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+}
+
+impl DurationUnit {
+    /// Parse a unit word or abbreviation (e.g., "weeks", "wks", "h").
+    fn parse(unit: &str) -> Option<Self> {
+        let unit = match unit.trim_end_matches('.') {
+            "y" | "yr" | "yrs" | "year" | "years" => Self::Years,
+            "mo" | "mos" | "mon" | "month" | "months" => Self::Months,
+            "w" | "wk" | "wks" | "week" | "weeks" => Self::Weeks,
+            "d" | "day" | "days" => Self::Days,
+            "h" | "hr" | "hrs" | "hour" | "hours" => Self::Hours,
+            "m" | "min" | "mins" | "minute" | "minutes" => Self::Minutes,
+            "s" | "sec" | "secs" | "second" | "seconds" => Self::Seconds,
+            _ => return None,
+        };
+        Some(unit)
+    }
+}
+
+/// Amounts per unit, indexed by [`DurationUnit`].
+type DurationAmounts = [f64; 7];
+
 /// Try to parse text duration description.
+///
+/// Accepts "<n> <unit>" pairs and combined forms like "5d", separated by
+/// whitespace, commas, or "and" (e.g., "2 weeks", "1.5 hours", "3 mos, 2 d").
+/// Any unrecognized word makes the whole value unparseable.
 fn try_parse_text_duration(value: &str) -> Option<String> {
-    let lower = value.to_lowercase();
-    let mut years = 0i64;
-    let mut months = 0i64;
-    let mut weeks = 0i64;
-    let mut days = 0i64;
-    let mut hours = 0i64;
-    let mut minutes = 0i64;
-    let mut seconds = 0i64;
-
-    // Split by spaces and parse each component
-    let parts: Vec<&str> = lower.split_whitespace().collect();
-    let mut i = 0;
-
-    while i < parts.len() {
-        let part = parts[i];
-
-        // Try to get numeric value
-        if let Ok(num) = part.parse::<i64>() {
-            // Look for unit in next part
-            if i + 1 < parts.len() {
-                let unit = parts[i + 1];
-                match unit {
-                    u if u.starts_with("year") => years = num,
-                    u if u.starts_with("month") => months = num,
-                    u if u.starts_with("week") => weeks = num,
-                    u if u.starts_with("day") => days = num,
-                    u if u.starts_with("hour") || u == "h" || u == "hr" || u == "hrs" => {
-                        hours = num
-                    }
-                    u if u.starts_with("minute") || u == "min" || u == "mins" => minutes = num,
-                    u if u.starts_with("second") || u == "sec" || u == "secs" || u == "s" => {
-                        seconds = num
-                    }
-                    _ => {}
-                }
-                i += 2;
-                continue;
-            }
+    let lower = value.to_lowercase().replace(',', " ");
+    let mut parts = lower.split_whitespace().filter(|part| *part != "and");
+    let mut amounts: DurationAmounts = [0.0; 7];
+    let mut found = false;
+
+    while let Some(part) = parts.next() {
+        let (num, unit) = match part.parse::<f64>() {
+            Ok(num) => (num, DurationUnit::parse(parts.next()?)?),
+            Err(_) => parse_combined_duration(part)?,
+        };
+        if !num.is_finite() || num < 0.0 {
+            return None;
         }
-
-        // Check for combined format like "5days" or "2h"
-        if let Some(duration) = parse_combined_duration(part) {
-            match duration {
-                DurationComponent::Years(n) => years += n,
-                DurationComponent::Months(n) => months += n,
-                DurationComponent::Weeks(n) => weeks += n,
-                DurationComponent::Days(n) => days += n,
-                DurationComponent::Hours(n) => hours += n,
-                DurationComponent::Minutes(n) => minutes += n,
-                DurationComponent::Seconds(n) => seconds += n,
-            }
-        }
-
-        i += 1;
+        amounts[unit as usize] += num;
+        found = true;
     }
 
-    // Build ISO 8601 duration string
-    build_iso8601_duration(years, months, weeks, days, hours, minutes, seconds)
+    if !found {
+        return None;
+    }
+    build_iso8601_duration(amounts)
 }
 
-#[derive(Debug)]
-enum DurationComponent {
-    Years(i64),
-    Months(i64),
-    Weeks(i64),
-    Days(i64),
-    Hours(i64),
-    Minutes(i64),
-    Seconds(i64),
+/// Parse combined format like "5days", "2h", "1.5hrs".
+fn parse_combined_duration(part: &str) -> Option<(f64, DurationUnit)> {
+    let split = part.find(|c: char| c.is_ascii_alphabetic())?;
+    let (num, unit) = part.split_at(split);
+    Some((num.parse().ok()?, DurationUnit::parse(unit)?))
 }
 
-/// Parse combined format like "5days", "2h", "30min".
-fn parse_combined_duration(part: &str) -> Option<DurationComponent> {
-    let patterns = [
-        (
-            "years",
-            DurationComponent::Years as fn(i64) -> DurationComponent,
-        ),
-        ("year", DurationComponent::Years),
-        ("months", DurationComponent::Months),
-        ("month", DurationComponent::Months),
-        ("weeks", DurationComponent::Weeks),
-        ("week", DurationComponent::Weeks),
-        ("days", DurationComponent::Days),
-        ("day", DurationComponent::Days),
-        ("hours", DurationComponent::Hours),
-        ("hour", DurationComponent::Hours),
-        ("hrs", DurationComponent::Hours),
-        ("hr", DurationComponent::Hours),
-        ("h", DurationComponent::Hours),
-        ("minutes", DurationComponent::Minutes),
-        ("minute", DurationComponent::Minutes),
-        ("mins", DurationComponent::Minutes),
-        ("min", DurationComponent::Minutes),
-        ("m", DurationComponent::Minutes),
-        ("seconds", DurationComponent::Seconds),
-        ("second", DurationComponent::Seconds),
-        ("secs", DurationComponent::Seconds),
-        ("sec", DurationComponent::Seconds),
-        ("s", DurationComponent::Seconds),
-        ("d", DurationComponent::Days),
-        ("w", DurationComponent::Weeks),
-    ];
-
-    for (suffix, constructor) in &patterns {
-        if let Some(num_str) = part.strip_suffix(suffix)
-            && let Ok(num) = num_str.parse::<i64>()
-        {
-            return Some(constructor(num));
-        }
+/// Build ISO 8601 duration string from component amounts.
+///
+/// Fractional weeks, days, hours, and minutes carry into the next smaller
+/// unit (1.5 hours -> PT1H30M). Fractional years or months have no exact
+/// smaller equivalent and are rejected.
+fn build_iso8601_duration(amounts: DurationAmounts) -> Option<String> {
+    let [years, months, weeks, days, hours, minutes, seconds] = amounts;
+    if years.fract() != 0.0 || months.fract() != 0.0 {
+        return None;
     }
 
-    None
-}
-
-/// Build ISO 8601 duration string from components.
-fn build_iso8601_duration(
-    years: i64,
-    months: i64,
-    weeks: i64,
-    days: i64,
-    hours: i64,
-    minutes: i64,
-    seconds: i64,
-) -> Option<String> {
-    // If weeks only, use week format
-    if weeks != 0
-        && years == 0
-        && months == 0
-        && days == 0
-        && hours == 0
-        && minutes == 0
-        && seconds == 0
-    {
+    // If whole weeks only, use week format
+    let others = years + months + days + hours + minutes + seconds;
+    if weeks != 0.0 && weeks.fract() == 0.0 && others == 0.0 {
         return Some(format!("P{weeks}W"));
     }
 
+    // Carry fractional parts down, rounding away float noise
+    let carry = |value: f64, factor: f64| ((value.fract() * factor) * 1e6).round() / 1e6;
+    let days = days + weeks * 7.0;
+    let hours = hours + carry(days, 24.0);
+    let minutes = minutes + carry(hours, 60.0);
+    let seconds = seconds + carry(minutes, 60.0);
+    let (years, months, days, hours, minutes) = (
+        years as i64,
+        months as i64,
+        days.trunc() as i64,
+        hours.trunc() as i64,
+        minutes.trunc() as i64,
+    );
+    let seconds = (seconds * 1000.0).round() / 1000.0;
+
     // Check if we have any components
-    let has_date = years != 0 || months != 0 || days != 0 || weeks != 0;
-    let has_time = hours != 0 || minutes != 0 || seconds != 0;
+    let has_date = years != 0 || months != 0 || days != 0;
+    let has_time = hours != 0 || minutes != 0 || seconds != 0.0;
 
     if !has_date && !has_time {
-        return None;
+        return Some("P0D".to_string());
     }
 
     let mut result = String::from("P");
@@ -259,10 +220,8 @@ fn build_iso8601_duration(
     if months != 0 {
         result.push_str(&format!("{months}M"));
     }
-    // Convert weeks to days
-    let total_days = days + weeks * 7;
-    if total_days != 0 {
-        result.push_str(&format!("{total_days}D"));
+    if days != 0 {
+        result.push_str(&format!("{days}D"));
     }
 
     // Time part
@@ -274,7 +233,7 @@ fn build_iso8601_duration(
         if minutes != 0 {
             result.push_str(&format!("{minutes}M"));
         }
-        if seconds != 0 {
+        if seconds != 0.0 {
             result.push_str(&format!("{seconds}S"));
         }
     }
@@ -331,10 +290,51 @@ fn test_combined_format() {
         assert_eq!(format_iso8601_duration("30min"), Some("PT30M".to_string()));
     }
 
+    #[test]
+    fn test_text_duration_weeks() {
+        assert_eq!(format_iso8601_duration("3 wks"), Some("P3W".to_string()));
+        assert_eq!(
+            format_iso8601_duration("1 week 2 days"),
+            Some("P9D".to_string())
+        );
+        assert_eq!(
+            format_iso8601_duration("1.5 weeks"),
+            Some("P10DT12H".to_string())
+        );
+    }
+
+    #[test]
+    fn test_text_duration_months() {
+        assert_eq!(format_iso8601_duration("3 months"), Some("P3M".to_string()));
+        assert_eq!(format_iso8601_duration("6 mos"), Some("P6M".to_string()));
+        assert_eq!(
+            format_iso8601_duration("1 year, 2 months and 3 days"),
+            Some("P1Y2M3D".to_string())
+        );
+        // No exact equivalent for fractional months
+        assert_eq!(format_iso8601_duration("1.5 months"), None);
+    }
+
+    #[test]
+    fn test_text_duration_hours() {
+        assert_eq!(format_iso8601_duration("4 hrs"), Some("PT4H".to_string()));
+        assert_eq!(
+            format_iso8601_duration("1.5 hours"),
+            Some("PT1H30M".to_string())
+        );
+        assert_eq!(
+            format_iso8601_duration("2 days 6 hours"),
+            Some("P2DT6H".to_string())
+        );
+        assert_eq!(format_iso8601_duration("1.5h"), Some("PT1H30M".to_string()));
+    }
+
     #[test]
     fn test_empty_and_invalid() {
         assert_eq!(format_iso8601_duration(""), None);
         assert_eq!(format_iso8601_duration("invalid"), None);
+        assert_eq!(format_iso8601_duration("about 3 days"), None);
+        assert_eq!(format_iso8601_duration("3 fortnights"), None);
     }
 
     #[test]
@@ -0,0 +1,246 @@
+//! EPOCH derivation from Subject Elements (SE).
+//!
+//! Per SDTMIG 5.3, EPOCH is the epoch of the element a subject was in when
+//! the observation started. Each SE record gives one element window
+//! (SESTDTC to SEENDTC) and its EPOCH, so a record's epoch is found by
+//! placing its --DTC (or --STDTC) in the subject's windows.
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use polars::prelude::{Column, DataFrame};
+use tss_standards::any_to_string;
+
+use super::datetime::{DateTimePrecision, parse_date_precision};
+use crate::normalize::error::NormalizationError;
+
+/// A point in time parsed from a --DTC value.
+#[derive(Debug, Clone, Copy)]
+enum Timepoint {
+    /// Date without a time component.
+    Date(NaiveDate),
+    /// Date with a time component.
+    DateTime(NaiveDateTime),
+}
+
+impl Timepoint {
+    /// Parse a complete date or datetime; partial dates cannot be placed.
+    fn parse(value: &str) -> Option<Self> {
+        match parse_date_precision(value) {
+            DateTimePrecision::DateTime(dt) => Some(Self::DateTime(dt)),
+            DateTimePrecision::Date(d) => Some(Self::Date(d)),
+            DateTimePrecision::Iso8601(s) if s.len() == 10 => {
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .ok()
+                    .map(Self::Date)
+            }
+            DateTimePrecision::Iso8601(s) if s.len() > 10 => {
+                ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+                    .iter()
+                    .find_map(|fmt| NaiveDateTime::parse_from_str(&s, fmt).ok())
+                    .map(Self::DateTime)
+            }
+            _ => None,
+        }
+    }
+
+    /// Compare two timepoints, at day precision when either has no time.
+    fn cmp_to(self, other: Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Self::DateTime(a), Self::DateTime(b)) => a.cmp(&b),
+            (a, b) => a.date().cmp(&b.date()),
+        }
+    }
+
+    fn date(self) -> NaiveDate {
+        match self {
+            Self::Date(d) => d,
+            Self::DateTime(dt) => dt.date(),
+        }
+    }
+}
+
+/// One element window of a subject.
+struct ElementWindow {
+    start: Timepoint,
+    end: Option<Timepoint>,
+    epoch: String,
+}
+
+impl ElementWindow {
+    fn contains(&self, point: Timepoint) -> bool {
+        self.start.cmp_to(point).is_le() && self.end.is_none_or(|end| point.cmp_to(end).is_le())
+    }
+}
+
+/// Add EPOCH to a domain from the Subject Elements dataset.
+///
+/// Each record is placed by its `<DOMAIN>DTC`, or `<DOMAIN>STDTC` if the
+/// domain has no --DTC, in the element windows of its USUBJID in `se`. A
+/// window runs from SESTDTC to SEENDTC inclusive; a missing SEENDTC leaves it
+/// open. As SEENDTC of one element is usually SESTDTC of the next, a record on
+/// such a boundary takes the epoch of the element that starts there. Dates
+/// without a time are compared at day precision.
+///
+/// Records without a complete date, or outside all windows of their
+/// subject, get a null EPOCH. An existing EPOCH column is replaced.
+///
+/// # Errors
+///
+/// Returns [`NormalizationError::ColumnNotFound`] if `frame` has no USUBJID
+/// or date column, or `se` lacks USUBJID, SESTDTC, SEENDTC or EPOCH.
+pub fn derive_epoch(
+    frame: &DataFrame,
+    domain_code: &str,
+    se: &DataFrame,
+) -> Result<DataFrame, NormalizationError> {
+    let domain = domain_code.to_uppercase();
+    let date_column = [format!("{domain}DTC"), format!("{domain}STDTC")]
+        .into_iter()
+        .find(|name| frame.column(name).is_ok())
+        .ok_or_else(|| NormalizationError::ColumnNotFound(format!("{domain}DTC")))?;
+
+    let windows = element_windows(se)?;
+    let subjects = column(frame, "USUBJID")?;
+    let dates = column(frame, &date_column)?;
+
+    let mut epochs: Vec<Option<String>> = Vec::with_capacity(frame.height());
+    for idx in 0..frame.height() {
+        let subject = any_to_string(subjects.get(idx)?);
+        let date = any_to_string(dates.get(idx)?);
+        let epoch = Timepoint::parse(date.trim()).and_then(|point| {
+            windows
+                .get(subject.trim())?
+                .iter()
+                .rev()
+                .find(|window| window.contains(point))
+                .map(|window| window.epoch.clone())
+        });
+        epochs.push(epoch);
+    }
+
+    let mut output = frame.clone();
+    output.with_column(Column::new("EPOCH".into(), epochs))?;
+    Ok(output)
+}
+
+/// Element windows per subject, ordered by start.
+///
+/// SE records without a complete SESTDTC or an EPOCH are skipped.
+fn element_windows(
+    se: &DataFrame,
+) -> Result<HashMap<String, Vec<ElementWindow>>, NormalizationError> {
+    let subjects = column(se, "USUBJID")?;
+    let starts = column(se, "SESTDTC")?;
+    let ends = column(se, "SEENDTC")?;
+    let epochs = column(se, "EPOCH")?;
+
+    let mut windows: HashMap<String, Vec<ElementWindow>> = HashMap::new();
+    for idx in 0..se.height() {
+        let epoch = any_to_string(epochs.get(idx)?).trim().to_string();
+        let Some(start) = Timepoint::parse(any_to_string(starts.get(idx)?).trim()) else {
+            continue;
+        };
+        if epoch.is_empty() {
+            continue;
+        }
+        let end = Timepoint::parse(any_to_string(ends.get(idx)?).trim());
+        windows
+            .entry(any_to_string(subjects.get(idx)?).trim().to_string())
+            .or_default()
+            .push(ElementWindow { start, end, epoch });
+    }
+    for subject_windows in windows.values_mut() {
+        subject_windows.sort_by(|a, b| a.start.cmp_to(b.start));
+    }
+    Ok(windows)
+}
+
+fn column<'a>(df: &'a DataFrame, name: &str) -> Result<&'a Column, NormalizationError> {
+    df.column(name)
+        .map_err(|_| NormalizationError::ColumnNotFound(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn se() -> DataFrame {
+        df! {
+            "USUBJID" => &["S-001", "S-001", "S-002"],
+            "SESTDTC" => &["2024-01-01", "2024-01-15", "2024-02-01T08:00"],
+            "SEENDTC" => &[Some("2024-01-15"), Some("2024-03-01"), None],
+            "EPOCH" => &["SCREENING", "TREATMENT", "SCREENING"],
+        }
+        .unwrap()
+    }
+
+    fn epochs(df: &DataFrame) -> Vec<Option<String>> {
+        df.column("EPOCH")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .map(|value| value.map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_derive_epoch_two_elements_with_boundaries() {
+        let lb = df! {
+            "USUBJID" => &["S-001", "S-001", "S-001", "S-001", "S-001", "S-001"],
+            "LBDTC" => &[
+                "2024-01-01",
+                "2024-01-14T23:59",
+                "2024-01-15",
+                "2024-03-01",
+                "2024-03-02",
+                "2023-12-31",
+            ],
+        }
+        .unwrap();
+        let result = derive_epoch(&lb, "LB", &se()).unwrap();
+        assert_eq!(
+            epochs(&result),
+            [
+                Some("SCREENING".to_string()),
+                Some("SCREENING".to_string()),
+                // SEENDTC of screening is SESTDTC of treatment
+                Some("TREATMENT".to_string()),
+                Some("TREATMENT".to_string()),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_derive_epoch_open_window_and_missing_dates() {
+        let ae = df! {
+            "USUBJID" => &["S-002", "S-002", "S-002", "S-003"],
+            "AESTDTC" => &["2024-02-01", "2030-01-01", "2024-02", "2024-02-01"],
+        }
+        .unwrap();
+        let result = derive_epoch(&ae, "AE", &se()).unwrap();
+        assert_eq!(
+            epochs(&result),
+            [
+                // Day precision: the date of the element start is inside
+                Some("SCREENING".to_string()),
+                Some("SCREENING".to_string()),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_derive_epoch_missing_date_column() {
+        let dm = df! { "USUBJID" => &["S-001"] }.unwrap();
+        assert!(matches!(
+            derive_epoch(&dm, "DM", &se()),
+            Err(NormalizationError::ColumnNotFound(column)) if column == "DMDTC"
+        ));
+    }
+}
@@ -1,24 +1,40 @@
 //! Normalization functions for SDTM data transformation.
 //!
 //! This module provides functions for normalizing data to SDTM-compliant formats:
-//! - **datetime**: ISO 8601 date/time parsing and formatting
+//! - **datetime**: ISO 8601 date/time parsing, formatting, and partial date imputation
 //! - **studyday**: Study day calculation (--DY variables)
+//! - **epoch**: EPOCH derivation from Subject Elements (SE)
 //! - **duration**: ISO 8601 duration formatting
 //! - **ct**: Controlled terminology normalization
 //! - **numeric**: Numeric type conversion
+//! - **ascii**: Unicode punctuation to ASCII transliteration
+//! - **units**: Findings result unit conversion (--ORRES to --STRESC/--STRESN)
 
+pub mod ascii;
 pub mod ct;
 pub mod datetime;
 pub mod duration;
+pub mod epoch;
 pub mod numeric;
 pub mod studyday;
+pub mod units;
 
 // Re-export commonly used items
-pub use ct::{CtNormalizationResult, normalize_ct_value, normalize_without_codelist};
+pub use ascii::transliterate_to_ascii;
+pub use ct::{
+    CtNormalizationResult, CtResolutionMode, normalize_ct_value, normalize_ct_value_with_mode,
+    normalize_without_codelist,
+};
 pub use datetime::{
-    DateTimePrecision, format_iso8601_date, format_iso8601_datetime, parse_date,
-    parse_date_precision, transform_to_iso8601,
+    DateTimePrecision, ImputationRule, format_iso8601_date, format_iso8601_datetime,
+    imputation_flag, impute_partial_datetime, parse_date, parse_date_precision,
+    transform_to_iso8601,
 };
 pub use duration::format_iso8601_duration;
+pub use epoch::derive_epoch;
 pub use numeric::{is_numeric, parse_numeric, transform_to_numeric};
-pub use studyday::{calculate_study_day, calculate_study_day_from_strings};
+pub use studyday::{
+    StudyDayConfig, calculate_study_day, calculate_study_day_from_strings,
+    extract_subject_reference_dates,
+};
+pub use units::{UnitConversion, UnitConversionTable, convert_units, convert_units_with_overwrite};
@@ -1,11 +1,80 @@
 //! Study day calculation per SDTMIG 4.4.4.
 //!
-//! Study day is calculated relative to RFSTDTC (reference start date):
+//! Study day is calculated relative to each subject's DM reference date,
+//! RFSTDTC by default (see [`StudyDayConfig`]):
 //! - If event_date >= reference_date: (event - ref) + 1 (Day 1, 2, 3...)
 //! - If event_date < reference_date: (event - ref) (Day -1, -2, -3...)
 //! - No day 0 exists
 
+use std::collections::BTreeMap;
+
 use chrono::NaiveDate;
+use polars::prelude::DataFrame;
+use serde::{Deserialize, Serialize};
+use tss_standards::any_to_string;
+
+/// Reference date configuration for --DY derivation.
+///
+/// SDTMIG uses RFSTDTC as the study day reference, but some sponsors define
+/// study day relative to first exposure (RFXSTDTC).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StudyDayConfig {
+    /// DM variable holding the reference date (e.g., "RFSTDTC").
+    pub reference_variable: String,
+}
+
+impl Default for StudyDayConfig {
+    fn default() -> Self {
+        Self::new("RFSTDTC")
+    }
+}
+
+impl StudyDayConfig {
+    /// Create a config referencing the given DM variable.
+    pub fn new(reference_variable: impl Into<String>) -> Self {
+        Self {
+            reference_variable: reference_variable.into(),
+        }
+    }
+
+    /// Reference study day to first study treatment exposure (RFXSTDTC).
+    pub fn first_exposure() -> Self {
+        Self::new("RFXSTDTC")
+    }
+}
+
+/// Extract each subject's study day reference date from a DM DataFrame.
+///
+/// Maps USUBJID to the configured reference variable. Subjects whose
+/// reference date is missing or partial (e.g., "2024-01") are left out, so
+/// their study days stay empty.
+pub fn extract_subject_reference_dates(
+    dm_df: &DataFrame,
+    config: &StudyDayConfig,
+) -> BTreeMap<String, NaiveDate> {
+    let (Ok(usubjid), Ok(reference)) = (
+        dm_df.column("USUBJID"),
+        dm_df.column(&config.reference_variable),
+    ) else {
+        return BTreeMap::new();
+    };
+
+    let mut dates = BTreeMap::new();
+    for idx in 0..dm_df.height() {
+        let (Ok(subject), Ok(value)) = (usubjid.get(idx), reference.get(idx)) else {
+            continue;
+        };
+        let subject = any_to_string(subject);
+        let subject = subject.trim();
+        if subject.is_empty() {
+            continue;
+        }
+        if let Some(date) = parse_date_for_studyday(any_to_string(value).trim()) {
+            dates.entry(subject.to_string()).or_insert(date);
+        }
+    }
+    dates
+}
 
 /// Calculate study day per SDTMIG 4.4.4 rules.
 ///
@@ -150,6 +219,60 @@ fn test_from_strings_partial_date() {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_dose_on_reference_date() {
+        // Dose at any time on the reference date is Day 1
+        let result = calculate_study_day_from_strings("2024-01-15T08:30", "2024-01-15");
+        assert_eq!(result, Some(1));
+        let result = calculate_study_day_from_strings("2024-01-14T23:59", "2024-01-15");
+        assert_eq!(result, Some(-1));
+    }
+
+    #[test]
+    fn test_from_strings_partial_collection_date() {
+        assert_eq!(calculate_study_day_from_strings("2024", "2024-01-15"), None);
+        assert_eq!(
+            calculate_study_day_from_strings("2024-01-20", "2024-01"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_subject_reference_dates() {
+        use polars::prelude::*;
+
+        let dm = df! {
+            "USUBJID" => &["CDISC01-001", "CDISC01-002", "CDISC01-003"],
+            "RFSTDTC" => &["2024-01-10", "2024-02-01T08:00", "2024-01"],
+            "RFXSTDTC" => &["2024-01-12", "", "2024-01-20"],
+        }
+        .unwrap();
+
+        // Each subject keeps its own date; partial dates are left out
+        let dates = extract_subject_reference_dates(&dm, &StudyDayConfig::default());
+        assert_eq!(
+            dates,
+            BTreeMap::from([
+                (
+                    "CDISC01-001".to_string(),
+                    NaiveDate::from_ymd_opt(2024, 1, 10).unwrap()
+                ),
+                (
+                    "CDISC01-002".to_string(),
+                    NaiveDate::from_ymd_opt(2024, 2, 1).unwrap()
+                ),
+            ])
+        );
+
+        let dates = extract_subject_reference_dates(&dm, &StudyDayConfig::first_exposure());
+        assert_eq!(dates.len(), 2);
+        assert_eq!(
+            dates.get("CDISC01-003"),
+            NaiveDate::from_ymd_opt(2024, 1, 20).as_ref()
+        );
+        assert!(extract_subject_reference_dates(&dm, &StudyDayConfig::new("TRTSDT")).is_empty());
+    }
+
     #[test]
     fn test_from_strings_invalid() {
         let result = calculate_study_day_from_strings("invalid", "2024-01-15");
@@ -0,0 +1,445 @@
+//! Unit conversion of findings results.
+//!
+//! Per SDTMIG 4.5.1, original results (--ORRES in --ORRESU) are copied to
+//! the standard format variables --STRESC, --STRESN and --STRESU, converted
+//! to the sponsor's standard unit. [`convert_units`] performs this copy
+//! using a [`UnitConversionTable`] of per-analyte factors (e.g., glucose
+//! mg/dL to mmol/L), with an offset for affine scales such as temperature.
+
+use std::collections::BTreeSet;
+
+use polars::prelude::{Column, DataFrame};
+use serde::{Deserialize, Deserializer, Serialize};
+use tss_standards::{any_to_f64, any_to_string};
+
+use super::numeric::parse_numeric;
+use crate::normalize::error::NormalizationError;
+
+/// Conversion of a result from one unit to another.
+///
+/// The standard value is the original value multiplied by `factor`, plus
+/// `offset` (e.g., °F to °C is a factor of 5/9 with an offset of -160/9).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnitConversion {
+    /// Only convert rows where --TESTCD has this value.
+    #[serde(default)]
+    pub test_code: Option<String>,
+    /// Original unit (--ORRESU, e.g., "mg/dL").
+    pub from_unit: String,
+    /// Standard unit (--STRESU, e.g., "mmol/L").
+    pub to_unit: String,
+    /// Multiplier from the original to the standard unit.
+    pub factor: f64,
+    /// Added after multiplying by `factor`; zero for proportional units.
+    #[serde(default, deserialize_with = "deserialize_offset")]
+    pub offset: f64,
+    /// Decimal places of the converted result; `None` keeps full precision.
+    #[serde(default)]
+    pub decimals: Option<usize>,
+}
+
+impl UnitConversion {
+    /// Create a conversion that applies to every test.
+    pub fn new(from_unit: impl Into<String>, to_unit: impl Into<String>, factor: f64) -> Self {
+        Self {
+            test_code: None,
+            from_unit: from_unit.into(),
+            to_unit: to_unit.into(),
+            factor,
+            offset: 0.0,
+            decimals: None,
+        }
+    }
+
+    /// Add `offset` after applying the factor.
+    pub fn with_offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Restrict the conversion to rows with this --TESTCD.
+    pub fn with_test_code(mut self, test_code: impl Into<String>) -> Self {
+        self.test_code = Some(test_code.into());
+        self
+    }
+
+    /// Round converted results to this many decimal places.
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    fn applies_to(&self, test_code: &str, unit: &str) -> bool {
+        self.from_unit.eq_ignore_ascii_case(unit)
+            && self
+                .test_code
+                .as_deref()
+                .is_none_or(|code| code.eq_ignore_ascii_case(test_code))
+    }
+
+    /// Convert a value and format it for --STRESC.
+    fn convert(&self, value: f64) -> (f64, String) {
+        let converted = value.mul_add(self.factor, self.offset);
+        match self.decimals {
+            Some(decimals) => {
+                let text = format!("{converted:.decimals$}");
+                (text.parse().unwrap_or(converted), text)
+            }
+            None => {
+                // Drop floating point noise (e.g., 0.30525000000000002)
+                let rounded = (converted * 1e10).round() / 1e10;
+                (rounded, rounded.to_string())
+            }
+        }
+    }
+}
+
+/// Read a missing or blank offset as zero.
+fn deserialize_offset<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Table of unit conversions used by [`convert_units`].
+///
+/// Conversions restricted to a test code take precedence over general ones.
+/// Units are compared ignoring case.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UnitConversionTable {
+    conversions: Vec<UnitConversion>,
+}
+
+impl UnitConversionTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a table from a JSON array of conversions.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse a table from CSV with `test_code`, `from_unit`, `to_unit`,
+    /// `factor`, `offset` and `decimals` columns; `test_code`, `offset` and
+    /// `decimals` may be blank, and the `offset` column may be left out.
+    pub fn from_csv(csv: &str) -> csv::Result<Self> {
+        let conversions = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .trim(csv::Trim::All)
+            .from_reader(csv.as_bytes())
+            .deserialize::<UnitConversion>()
+            .map(|row| {
+                row.map(|mut conversion| {
+                    conversion.test_code = conversion.test_code.filter(|code| !code.is_empty());
+                    conversion
+                })
+            })
+            .collect::<csv::Result<_>>()?;
+        Ok(Self { conversions })
+    }
+
+    /// Add a conversion.
+    pub fn push(&mut self, conversion: UnitConversion) {
+        self.conversions.push(conversion);
+    }
+
+    /// Add a conversion (builder style).
+    pub fn with_conversion(mut self, conversion: UnitConversion) -> Self {
+        self.push(conversion);
+        self
+    }
+
+    /// Get all conversions.
+    pub fn conversions(&self) -> &[UnitConversion] {
+        &self.conversions
+    }
+
+    /// Find the conversion for a test and original unit.
+    pub fn find(&self, test_code: &str, unit: &str) -> Option<&UnitConversion> {
+        let matching = || {
+            self.conversions
+                .iter()
+                .filter(|conversion| conversion.applies_to(test_code, unit))
+        };
+        matching()
+            .find(|conversion| conversion.test_code.is_some())
+            .or_else(|| matching().next())
+    }
+}
+
+/// Derive --STRESC, --STRESN and --STRESU from --ORRES and --ORRESU.
+///
+/// Numeric results with a unit in `table` are converted to its standard
+/// unit. Other results are copied as-is: character results (e.g.,
+/// "NEGATIVE") go to --STRESC only, and numeric results in a unit pair the
+/// table doesn't know keep their original value and unit, with a warning
+/// logged once per test and unit. Rows with an empty --ORRES get null
+/// standard results.
+///
+/// Rows that already have a standard result (any of the three variables
+/// populated, e.g., from the source data) are left unchanged; use
+/// [`convert_units_with_overwrite`] to replace them.
+///
+/// # Errors
+///
+/// Returns [`NormalizationError::ColumnNotFound`] if `frame` has no
+/// --ORRES column.
+pub fn convert_units(
+    frame: &DataFrame,
+    domain_code: &str,
+    table: &UnitConversionTable,
+) -> Result<DataFrame, NormalizationError> {
+    convert_units_with_overwrite(frame, domain_code, table, false)
+}
+
+/// Derive the standard results like [`convert_units`], replacing existing
+/// standard results when `overwrite` is set.
+///
+/// # Errors
+///
+/// Returns [`NormalizationError::ColumnNotFound`] if `frame` has no
+/// --ORRES column.
+pub fn convert_units_with_overwrite(
+    frame: &DataFrame,
+    domain_code: &str,
+    table: &UnitConversionTable,
+    overwrite: bool,
+) -> Result<DataFrame, NormalizationError> {
+    let domain = domain_code.to_uppercase();
+    let name = |suffix: &str| format!("{domain}{suffix}");
+    let results = frame
+        .column(&name("ORRES"))
+        .map_err(|_| NormalizationError::ColumnNotFound(name("ORRES")))?;
+    let units = frame.column(&name("ORRESU")).ok();
+    let test_codes = frame.column(&name("TESTCD")).ok();
+    let existing = |suffix: &str| {
+        if overwrite {
+            None
+        } else {
+            frame.column(&name(suffix)).ok()
+        }
+    };
+    let (existing_stresc, existing_stresn, existing_stresu) =
+        (existing("STRESC"), existing("STRESN"), existing("STRESU"));
+    let text = |column: Option<&Column>, idx: usize| -> Result<String, NormalizationError> {
+        Ok(match column {
+            Some(column) => any_to_string(column.get(idx)?).trim().to_string(),
+            None => String::new(),
+        })
+    };
+
+    let height = frame.height();
+    let mut stresc: Vec<Option<String>> = Vec::with_capacity(height);
+    let mut stresn: Vec<Option<f64>> = Vec::with_capacity(height);
+    let mut stresu: Vec<Option<String>> = Vec::with_capacity(height);
+    let mut unknown: BTreeSet<(String, String)> = BTreeSet::new();
+
+    for idx in 0..height {
+        let kept = [
+            text(existing_stresc, idx)?,
+            text(existing_stresn, idx)?,
+            text(existing_stresu, idx)?,
+        ];
+        if kept.iter().any(|value| !value.is_empty()) {
+            let [kept_stresc, _, kept_stresu] = kept;
+            stresc.push((!kept_stresc.is_empty()).then_some(kept_stresc));
+            stresn.push(match existing_stresn {
+                Some(column) => any_to_f64(column.get(idx)?),
+                None => None,
+            });
+            stresu.push((!kept_stresu.is_empty()).then_some(kept_stresu));
+            continue;
+        }
+        let result = text(Some(results), idx)?;
+        if result.is_empty() {
+            stresc.push(None);
+            stresn.push(None);
+            stresu.push(None);
+            continue;
+        }
+        let unit = text(units, idx)?;
+        let number = parse_numeric(&result).filter(|value| value.is_finite());
+
+        let conversion = match number {
+            Some(_) if !unit.is_empty() => {
+                let test_code = text(test_codes, idx)?;
+                let conversion = table.find(&test_code, &unit);
+                if conversion.is_none() {
+                    unknown.insert((test_code, unit.clone()));
+                }
+                conversion
+            }
+            _ => None,
+        };
+        match (number, conversion) {
+            (Some(value), Some(conversion)) => {
+                let (converted, formatted) = conversion.convert(value);
+                stresc.push(Some(formatted));
+                stresn.push(Some(converted));
+                stresu.push(Some(conversion.to_unit.clone()));
+            }
+            (number, _) => {
+                stresc.push(Some(result));
+                stresn.push(number);
+                stresu.push((!unit.is_empty()).then_some(unit));
+            }
+        }
+    }
+
+    for (test_code, unit) in &unknown {
+        tracing::warn!(
+            domain = %domain,
+            test_code = %test_code,
+            unit = %unit,
+            "No unit conversion; original result and unit preserved"
+        );
+    }
+
+    let mut output = frame.clone();
+    output.with_column(Column::new(name("STRESC").into(), stresc))?;
+    output.with_column(Column::new(name("STRESN").into(), stresn))?;
+    output.with_column(Column::new(name("STRESU").into(), stresu))?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn lab_frame() -> DataFrame {
+        df! {
+            "LBTESTCD" => &["GLUC", "GLUC", "ALB", "UPROT", "GLUC"],
+            "LBORRES" => &["100", "5.5", "4.2", "NEGATIVE", ""],
+            "LBORRESU" => &["mg/dL", "mmol/L", "g/dL", "", "mg/dL"],
+        }
+        .unwrap()
+    }
+
+    fn table() -> UnitConversionTable {
+        UnitConversionTable::new()
+            .with_conversion(
+                UnitConversion::new("mg/dL", "mmol/L", 0.0555)
+                    .with_test_code("GLUC")
+                    .with_decimals(2),
+            )
+            .with_conversion(UnitConversion::new("mmol/L", "mmol/L", 1.0))
+            .with_conversion(UnitConversion::new("mg/dL", "mg/L", 10.0))
+    }
+
+    #[test]
+    fn test_convert_units_known_conversion() {
+        let output = convert_units(&lab_frame(), "LB", &table()).unwrap();
+
+        let stresc = output.column("LBSTRESC").unwrap().str().unwrap();
+        let stresn = output.column("LBSTRESN").unwrap().f64().unwrap();
+        let stresu = output.column("LBSTRESU").unwrap().str().unwrap();
+        // The GLUC-specific factor wins over the general mg/dL conversion
+        assert_eq!(stresc.get(0), Some("5.55"));
+        assert_eq!(stresn.get(0), Some(5.55));
+        assert_eq!(stresu.get(0), Some("mmol/L"));
+        assert_eq!(stresc.get(1), Some("5.5"));
+        assert_eq!(stresu.get(1), Some("mmol/L"));
+        // Character results are copied to STRESC only
+        assert_eq!(stresc.get(3), Some("NEGATIVE"));
+        assert_eq!(stresn.get(3), None);
+        assert_eq!(stresu.get(3), None);
+        // Empty results stay empty
+        assert_eq!(stresc.get(4), None);
+        assert_eq!(stresn.get(4), None);
+    }
+
+    #[test]
+    fn test_convert_units_unknown_unit_preserves_original() {
+        let output = convert_units(&lab_frame(), "LB", &table()).unwrap();
+
+        let stresc = output.column("LBSTRESC").unwrap().str().unwrap();
+        let stresn = output.column("LBSTRESN").unwrap().f64().unwrap();
+        let stresu = output.column("LBSTRESU").unwrap().str().unwrap();
+        assert_eq!(stresc.get(2), Some("4.2"));
+        assert_eq!(stresn.get(2), Some(4.2));
+        assert_eq!(stresu.get(2), Some("g/dL"));
+    }
+
+    #[test]
+    fn test_conversion_table_from_csv() {
+        let table = UnitConversionTable::from_csv(
+            "test_code,from_unit,to_unit,factor,decimals\n\
+             GLUC,mg/dL,mmol/L,0.0555,2\n\
+             ,mg/dL,mg/L,10,\n",
+        )
+        .unwrap();
+        let with_offset = UnitConversionTable::from_csv(
+            "from_unit,to_unit,factor,offset,decimals\n\
+             F,C,0.5555555556,-17.7777777778,1\n\
+             mg/dL,mg/L,10,,\n",
+        )
+        .unwrap();
+
+        assert_eq!(table.conversions()[0].offset, 0.0);
+        assert!((with_offset.conversions()[0].offset + 17.7777777778).abs() < 1e-12);
+        assert_eq!(with_offset.conversions()[1].offset, 0.0);
+        assert_eq!(table.conversions().len(), 2);
+        assert_eq!(table.find("GLUC", "MG/DL").unwrap().to_unit, "mmol/L");
+        let general = table.find("CHOL", "mg/dL").unwrap();
+        assert_eq!(general.to_unit, "mg/L");
+        assert_eq!(general.decimals, None);
+        assert!(table.find("GLUC", "g/dL").is_none());
+    }
+
+    #[test]
+    fn test_convert_units_offset() {
+        let frame = df! {
+            "VSTESTCD" => &["TEMP", "TEMP"],
+            "VSORRES" => &["98.6", "212"],
+            "VSORRESU" => &["F", "F"],
+        }
+        .unwrap();
+        let table = UnitConversionTable::new().with_conversion(
+            UnitConversion::new("F", "C", 5.0 / 9.0)
+                .with_offset(-160.0 / 9.0)
+                .with_decimals(1),
+        );
+
+        let output = convert_units(&frame, "VS", &table).unwrap();
+
+        let stresc = output.column("VSSTRESC").unwrap().str().unwrap();
+        let stresn = output.column("VSSTRESN").unwrap().f64().unwrap();
+        assert_eq!(stresc.get(0), Some("37.0"));
+        assert_eq!(stresn.get(0), Some(37.0));
+        assert_eq!(stresc.get(1), Some("100.0"));
+    }
+
+    #[test]
+    fn test_convert_units_keeps_existing_results() {
+        let frame = df! {
+            "LBTESTCD" => &["GLUC", "GLUC"],
+            "LBORRES" => &["100", "100"],
+            "LBORRESU" => &["mg/dL", "mg/dL"],
+            "LBSTRESC" => &[Some("5.6"), None],
+            "LBSTRESN" => &[Some(5.6), None],
+            "LBSTRESU" => &[Some("mmol/L"), None],
+        }
+        .unwrap();
+
+        let output = convert_units(&frame, "LB", &table()).unwrap();
+        let stresc = output.column("LBSTRESC").unwrap().str().unwrap();
+        let stresn = output.column("LBSTRESN").unwrap().f64().unwrap();
+        // The sponsor's standard result is kept, the empty row is filled
+        assert_eq!(stresc.get(0), Some("5.6"));
+        assert_eq!(stresn.get(0), Some(5.6));
+        assert_eq!(stresc.get(1), Some("5.55"));
+
+        let output = convert_units_with_overwrite(&frame, "LB", &table(), true).unwrap();
+        let stresc = output.column("LBSTRESC").unwrap().str().unwrap();
+        assert_eq!(stresc.get(0), Some("5.55"));
+    }
+
+    #[test]
+    fn test_convert_units_missing_result_column() {
+        let frame = df! { "LBTESTCD" => &["GLUC"] }.unwrap();
+        let result = convert_units(&frame, "LB", &table());
+        assert!(matches!(result, Err(NormalizationError::ColumnNotFound(_))));
+    }
+}
@@ -11,7 +11,9 @@
 use super::error::NormalizationError;
 use super::executor::execute_normalization;
 use super::inference::infer_normalization_rules;
+use super::normalization::{StudyDayConfig, extract_subject_reference_dates};
 use super::types::{NormalizationContext, NormalizationPipeline};
+use crate::map::MappingState;
 
 /// Build preview DataFrame for validation tab.
 ///
@@ -100,9 +102,11 @@ fn apply_mappings_to_pipeline(
     pipeline
 }
 
-/// Build preview DataFrame with reference date from DM domain.
+/// Build preview DataFrame with reference dates from DM domain.
 ///
-/// This variant loads RFSTDTC from the DM domain for study day calculations.
+/// This variant loads each subject's RFSTDTC from the DM domain for study day
+/// calculations. To count study days from another DM variable, use
+/// [`build_preview_dataframe_with_study_day`].
 ///
 /// # Arguments
 /// * `source_df` - Source DataFrame with raw data
@@ -131,7 +135,7 @@ pub fn build_preview_dataframe_with_dm(
     )
 }
 
-/// Build preview DataFrame with reference date and omitted variable support.
+/// Build preview DataFrame with reference dates and omitted variable support.
 ///
 /// # Arguments
 /// * `source_df` - Source DataFrame with raw data
@@ -150,96 +154,170 @@ pub fn build_preview_dataframe_with_dm_and_omitted(
     dm_df: Option<&DataFrame>,
     ct_registry: Option<&TerminologyRegistry>,
 ) -> Result<DataFrame, NormalizationError> {
-    // Build pipeline from domain metadata
-    let pipeline = infer_normalization_rules(domain);
+    let context = preview_context(
+        domain,
+        study_id,
+        dm_df,
+        &StudyDayConfig::default(),
+        ct_registry,
+    )
+    .with_mappings(mappings.clone())
+    .with_omitted(omitted.clone());
 
-    // Apply mappings to pipeline
-    let pipeline_with_mappings = apply_mappings_to_pipeline(pipeline, mappings);
+    execute_preview(source_df, domain, &context)
+}
 
-    // Extract reference date from DM if available
-    let reference_date = dm_df.and_then(extract_reference_date);
+/// Build preview DataFrame with a configurable study day reference.
+///
+/// Like [`build_preview_dataframe_with_dm`], but study days are counted from
+/// the DM variable named by `study_day` (e.g., RFXSTDTC).
+///
+/// # Arguments
+/// * `source_df` - Source DataFrame with raw data
+/// * `mappings` - Column mappings (target SDTM variable -> source column)
+/// * `domain` - SDTM domain definition
+/// * `study_id` - Study identifier
+/// * `dm_df` - Optional DM domain DataFrame for reference date extraction
+/// * `study_day` - DM variable study days are counted from
+/// * `ct_registry` - Optional CT registry for normalization
+pub fn build_preview_dataframe_with_study_day(
+    source_df: &DataFrame,
+    mappings: &BTreeMap<String, String>,
+    domain: &SdtmDomain,
+    study_id: &str,
+    dm_df: Option<&DataFrame>,
+    study_day: &StudyDayConfig,
+    ct_registry: Option<&TerminologyRegistry>,
+) -> Result<DataFrame, NormalizationError> {
+    let context = preview_context(domain, study_id, dm_df, study_day, ct_registry)
+        .with_mappings(mappings.clone());
 
-    // Create execution context
-    let context = NormalizationContext::new(study_id, &domain.name)
-        .with_reference_date(reference_date)
-        .with_ct_registry(ct_registry.cloned())
-        .with_mappings(mappings.clone())
-        .with_omitted(omitted.clone());
+    execute_preview(source_df, domain, &context)
+}
 
-    // Execute pipeline
-    execute_normalization(source_df, &pipeline_with_mappings, &context)
+/// Build preview DataFrame from an interactive mapping session.
+///
+/// Uses the accepted mappings, concatenations, and omitted variables of
+/// `mapping`, so many-to-one mappings are joined the same way as on export.
+/// Study days are counted from RFSTDTC; see
+/// [`build_preview_dataframe_for_mapping_with_study_day`].
+///
+/// # Arguments
+/// * `source_df` - Source DataFrame with raw data
+/// * `mapping` - Mapping state for the domain
+/// * `dm_df` - Optional DM domain DataFrame for RFSTDTC extraction
+/// * `ct_registry` - Optional CT registry for normalization
+pub fn build_preview_dataframe_for_mapping(
+    source_df: &DataFrame,
+    mapping: &MappingState,
+    dm_df: Option<&DataFrame>,
+    ct_registry: Option<&TerminologyRegistry>,
+) -> Result<DataFrame, NormalizationError> {
+    build_preview_dataframe_for_mapping_with_study_day(
+        source_df,
+        mapping,
+        dm_df,
+        &StudyDayConfig::default(),
+        ct_registry,
+    )
 }
 
-/// Extract RFSTDTC from DM domain DataFrame.
-fn extract_reference_date(dm_df: &DataFrame) -> Option<chrono::NaiveDate> {
-    // Look for RFSTDTC column
-    let rfstdtc = dm_df.column("RFSTDTC").ok()?;
-
-    // Get first non-null value
-    for idx in 0..rfstdtc.len() {
-        if let Ok(polars::prelude::AnyValue::String(s)) = rfstdtc.get(idx)
-            && !s.is_empty()
-            && s.len() >= 10
-            && let Ok(date) = chrono::NaiveDate::parse_from_str(&s[..10], "%Y-%m-%d")
-        {
-            return Some(date);
-        }
-    }
+/// Build preview DataFrame from a mapping session with a configurable study
+/// day reference.
+///
+/// # Arguments
+/// * `source_df` - Source DataFrame with raw data
+/// * `mapping` - Mapping state for the domain
+/// * `dm_df` - Optional DM domain DataFrame for reference date extraction
+/// * `study_day` - DM variable study days are counted from
+/// * `ct_registry` - Optional CT registry for normalization
+pub fn build_preview_dataframe_for_mapping_with_study_day(
+    source_df: &DataFrame,
+    mapping: &MappingState,
+    dm_df: Option<&DataFrame>,
+    study_day: &StudyDayConfig,
+    ct_registry: Option<&TerminologyRegistry>,
+) -> Result<DataFrame, NormalizationError> {
+    let domain = mapping.domain();
+    let mappings = mapping
+        .all_accepted()
+        .iter()
+        .map(|(variable, (column, _))| (variable.clone(), column.clone()))
+        .collect();
+
+    let context = preview_context(domain, mapping.study_id(), dm_df, study_day, ct_registry)
+        .with_mappings(mappings)
+        .with_concatenations(mapping.all_concatenations().clone())
+        .with_omitted(mapping.all_omitted().clone());
+
+    execute_preview(source_df, domain, &context)
+}
+
+/// Create the execution context shared by the DM-aware preview builders.
+fn preview_context(
+    domain: &SdtmDomain,
+    study_id: &str,
+    dm_df: Option<&DataFrame>,
+    study_day: &StudyDayConfig,
+    ct_registry: Option<&TerminologyRegistry>,
+) -> NormalizationContext {
+    // Extract each subject's reference date from DM if available
+    let reference_dates = dm_df
+        .map(|dm| extract_subject_reference_dates(dm, study_day))
+        .unwrap_or_default();
+
+    NormalizationContext::new(study_id, &domain.name)
+        .with_study_day_config(study_day.clone())
+        .with_subject_reference_dates(reference_dates)
+        .with_ct_registry(ct_registry.cloned())
+}
 
-    None
+/// Run the domain's inferred pipeline with the context's mappings.
+fn execute_preview(
+    source_df: &DataFrame,
+    domain: &SdtmDomain,
+    context: &NormalizationContext,
+) -> Result<DataFrame, NormalizationError> {
+    // Build pipeline from domain metadata
+    let pipeline = infer_normalization_rules(domain);
+
+    // Apply mappings to pipeline
+    let pipeline_with_mappings = apply_mappings_to_pipeline(pipeline, &context.mappings);
+
+    // Execute pipeline
+    execute_normalization(source_df, &pipeline_with_mappings, context)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::map::ColumnConcat;
     use polars::prelude::*;
-    use tss_standards::{
-        CoreDesignation, SdtmDatasetClass, SdtmVariable, VariableRole, VariableType,
-    };
+    use tss_standards::sdtm::test_support::{domain, variable};
+    use tss_standards::{CoreDesignation, SdtmDatasetClass, VariableRole};
 
     fn create_test_domain() -> SdtmDomain {
-        SdtmDomain {
-            name: "AE".to_string(),
-            label: Some("Adverse Events".to_string()),
-            class: Some(SdtmDatasetClass::Events),
-            structure: None,
-            dataset_name: None,
-            variables: vec![
-                SdtmVariable {
-                    name: "STUDYID".to_string(),
-                    label: Some("Study Identifier".to_string()),
-                    data_type: VariableType::Char,
-                    length: None,
-                    role: Some(VariableRole::Identifier),
-                    core: Some(CoreDesignation::Required),
-                    codelist_code: None,
-                    described_value_domain: None,
-                    order: Some(1),
-                },
-                SdtmVariable {
-                    name: "USUBJID".to_string(),
-                    label: Some("Unique Subject Identifier".to_string()),
-                    data_type: VariableType::Char,
-                    length: None,
-                    role: Some(VariableRole::Identifier),
-                    core: Some(CoreDesignation::Required),
-                    codelist_code: None,
-                    described_value_domain: None,
-                    order: Some(2),
-                },
-                SdtmVariable {
-                    name: "AETERM".to_string(),
-                    label: Some("Reported Term".to_string()),
-                    data_type: VariableType::Char,
-                    length: None,
-                    role: Some(VariableRole::Topic),
-                    core: Some(CoreDesignation::Required),
-                    codelist_code: None,
-                    described_value_domain: None,
-                    order: Some(3),
-                },
-            ],
-        }
+        domain("AE")
+            .label("Adverse Events")
+            .class(SdtmDatasetClass::Events)
+            .variables([
+                variable("STUDYID")
+                    .label("Study Identifier")
+                    .role(VariableRole::Identifier)
+                    .core(CoreDesignation::Required)
+                    .order(1),
+                variable("USUBJID")
+                    .label("Unique Subject Identifier")
+                    .role(VariableRole::Identifier)
+                    .core(CoreDesignation::Required)
+                    .order(2),
+                variable("AETERM")
+                    .label("Reported Term")
+                    .role(VariableRole::Topic)
+                    .core(CoreDesignation::Required)
+                    .order(3),
+            ])
+            .build()
     }
 
     #[test]
@@ -263,4 +341,89 @@ fn test_build_preview_dataframe() {
         assert_eq!(df.height(), 2);
         assert_eq!(df.width(), 3); // STUDYID, USUBJID, AETERM
     }
+
+    #[test]
+    fn test_build_preview_dataframe_for_mapping_concat() {
+        let domain = create_test_domain();
+
+        let source_df = df! {
+            "SUBJECT" => &["001", "002"],
+            "TERM1" => &["Mild", ""],
+            "TERM2" => &["Headache", "Nausea"],
+        }
+        .unwrap();
+        let columns: Vec<String> = ["SUBJECT", "TERM1", "TERM2"].map(String::from).to_vec();
+
+        let mut mapping = MappingState::new(domain, "CDISC01", &columns, BTreeMap::new(), 0.9);
+        mapping
+            .accept_concat("AETERM", ColumnConcat::new(["TERM1", "TERM2"], " "))
+            .unwrap();
+
+        let df = build_preview_dataframe_for_mapping(&source_df, &mapping, None, None).unwrap();
+
+        let aeterm = df.column("AETERM").unwrap();
+        assert_eq!(aeterm.get(0).unwrap(), AnyValue::String("Mild Headache"));
+        // Blank values are skipped rather than leaving a stray separator
+        assert_eq!(aeterm.get(1).unwrap(), AnyValue::String("Nausea"));
+        assert!(df.column("TERM1 + TERM2").is_err());
+    }
+
+    #[test]
+    fn test_build_preview_dataframe_with_study_day_config() {
+        let domain = tss_standards::load_sdtm_ig()
+            .unwrap()
+            .into_iter()
+            .find(|domain| domain.name == "AE")
+            .unwrap();
+        let source_df = df! {
+            "SUBJECT" => &["001", "002"],
+            "START" => &["2024-01-15", "2024-01-15"],
+        }
+        .unwrap();
+        // Subjects enrolled on different dates
+        let dm_df = df! {
+            "USUBJID" => &["CDISC01-001", "CDISC01-002"],
+            "RFSTDTC" => &["2024-01-10", "2024-01-20"],
+            "RFXSTDTC" => &["2024-01-12", "2024-01-21"],
+        }
+        .unwrap();
+        let mappings = BTreeMap::from([
+            ("SUBJID".to_string(), "SUBJECT".to_string()),
+            ("AESTDTC".to_string(), "START".to_string()),
+        ]);
+        let study_days = |config: &StudyDayConfig| {
+            let df = build_preview_dataframe_with_study_day(
+                &source_df,
+                &mappings,
+                &domain,
+                "CDISC01",
+                Some(&dm_df),
+                config,
+                None,
+            )
+            .unwrap();
+            let aestdy = df.column("AESTDY").unwrap();
+            (0..2)
+                .map(|idx| tss_standards::any_to_string(aestdy.get(idx).unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(study_days(&StudyDayConfig::default()), ["6", "-5"]);
+        assert_eq!(study_days(&StudyDayConfig::first_exposure()), ["4", "-6"]);
+
+        // The existing builder counts from each subject's RFSTDTC
+        let df = build_preview_dataframe_with_dm(
+            &source_df,
+            &mappings,
+            &domain,
+            "CDISC01",
+            Some(&dm_df),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            tss_standards::any_to_string(df.column("AESTDY").unwrap().get(1).unwrap()),
+            "-5"
+        );
+    }
 }
@@ -4,15 +4,30 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
 
 use chrono::NaiveDate;
-use tss_standards::TerminologyRegistry;
+use polars::prelude::DataFrame;
+use tss_standards::{DecimalConvention, Standard, TerminologyRegistry};
+
+use super::error::NormalizationError;
+use super::normalization::{CtResolutionMode, ImputationRule, StudyDayConfig};
+use crate::map::ColumnConcat;
 
 /// Transformation type inferred from Variable metadata.
 ///
 /// Each variant represents a specific SDTM transformation. The type is
 /// automatically determined from Variable fields like `name`, `codelist_code`,
 /// `described_value_domain`, and `data_type`.
+///
+/// # Idempotency
+///
+/// Every transformation must leave values already in target form unchanged,
+/// so re-running a pipeline on its own output is a no-op: ISO 8601 dates and
+/// durations, CT submission values, and prefixed USUBJIDs pass through as-is,
+/// while derived values (--SEQ, --DY) are recomputed identically. The
+/// executor tests check pipelines against this contract.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum NormalizationType {
@@ -139,6 +154,153 @@ pub fn rules_ordered(&self) -> Vec<&NormalizationRule> {
     }
 }
 
+/// A source cell that could not be normalized.
+///
+/// The original value is preserved in the output (or set to null for
+/// numeric conversion); this record lets callers audit and fix source data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizationFailure {
+    /// Zero-based row index in the source DataFrame.
+    pub row: usize,
+
+    /// Target SDTM variable (e.g., "AESTDTC").
+    pub variable: String,
+
+    /// Original source value.
+    pub original: String,
+
+    /// Normalization that failed.
+    pub rule: NormalizationType,
+}
+
+/// Output of a pipeline run with per-cell failure reporting.
+#[derive(Debug, Clone)]
+pub struct NormalizationOutput {
+    /// Normalized DataFrame (identical to `execute_normalization` output).
+    pub data: DataFrame,
+
+    /// Cells that could not be normalized, in rule then row order.
+    pub failures: Vec<NormalizationFailure>,
+}
+
+/// Component layout for USUBJID derivation.
+///
+/// `STUDYID` resolves to the context study identifier; every other component
+/// reads its mapped source column. The default reproduces `STUDYID-SUBJID`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsubjidTemplate {
+    /// Variables joined in order (e.g., `["STUDYID", "SITEID", "SUBJID"]`).
+    pub components: Vec<String>,
+
+    /// Separator placed between components (e.g., "-" or ".").
+    pub separator: String,
+}
+
+impl Default for UsubjidTemplate {
+    fn default() -> Self {
+        Self::new(["STUDYID", "SUBJID"], "-")
+    }
+}
+
+impl UsubjidTemplate {
+    /// Create a template from component variables and a separator.
+    pub fn new<I, S>(components: I, separator: impl Into<String>) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            components: components.into_iter().map(Into::into).collect(),
+            separator: separator.into(),
+        }
+    }
+
+    /// `STUDYID-SITEID-SUBJID` layout.
+    pub fn with_site() -> Self {
+        Self::new(["STUDYID", "SITEID", "SUBJID"], "-")
+    }
+}
+
+/// Opt-in imputation of partial dates of one --DTC variable.
+///
+/// Applied after the variable is normalized to ISO 8601. The --DTC variable
+/// keeps its collected precision, as SDTM requires; the imputed dates go to
+/// a separate derived variable, with complete dates copied as-is. The flag
+/// variable holds the ADaM imputation level ("M" or "D", see
+/// [`imputation_flag`](super::normalization::imputation_flag)) and is null
+/// for values that were not imputed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateImputation {
+    /// How missing components are filled in.
+    pub rule: ImputationRule,
+
+    /// Derived variable receiving the imputed dates (e.g., "ASTDT").
+    pub target_variable: String,
+
+    /// Variable receiving the imputation flag (e.g., "ASTDTF").
+    pub flag_variable: String,
+}
+
+impl DateImputation {
+    /// Impute into `target_variable`, flagged in the same name with an `F`
+    /// suffix (ASTDT gets ASTDTF).
+    pub fn new(rule: ImputationRule, target_variable: impl Into<String>) -> Self {
+        let target_variable = target_variable.into();
+        Self {
+            rule,
+            flag_variable: format!("{target_variable}F"),
+            target_variable,
+        }
+    }
+
+    /// Record the imputation level in another flag variable.
+    pub fn with_flag_variable(mut self, variable: impl Into<String>) -> Self {
+        self.flag_variable = variable.into();
+        self
+    }
+}
+
+/// Signature of a [`DomainProcessor`] callback.
+type ProcessorFn =
+    dyn Fn(&mut DataFrame, &NormalizationContext) -> Result<(), NormalizationError> + Send + Sync;
+
+/// User-supplied transform run on a domain during normalization.
+///
+/// Lets sponsors add their own derivations without forking the pipeline.
+/// The callback receives the normalized DataFrame and may add, replace or
+/// drop columns; see [`NormalizationContext::with_processor`] for when it
+/// runs.
+#[derive(Clone)]
+pub struct DomainProcessor(Arc<ProcessorFn>);
+
+impl DomainProcessor {
+    /// Wrap a callback.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&mut DataFrame, &NormalizationContext) -> Result<(), NormalizationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+
+    /// Run the callback on a DataFrame.
+    pub fn apply(
+        &self,
+        df: &mut DataFrame,
+        context: &NormalizationContext,
+    ) -> Result<(), NormalizationError> {
+        (self.0)(df, context)
+    }
+}
+
+impl fmt::Debug for DomainProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DomainProcessor(..)")
+    }
+}
+
 /// Context for transformation execution.
 ///
 /// Contains runtime data needed during transformation, including
@@ -151,20 +313,59 @@ pub struct NormalizationContext {
     /// Domain code (e.g., "AE").
     pub domain_code: String,
 
-    /// Reference date for study day calculation (RFSTDTC from DM).
-    /// If None, study day columns will be empty.
+    /// Study-wide reference date for study day calculation, used only when
+    /// `subject_reference_dates` is empty. If neither is set, study day
+    /// columns will be empty.
     pub reference_date: Option<NaiveDate>,
 
+    /// Reference date of each subject (USUBJID -> date), from DM per
+    /// `study_day`. Subjects without an entry get empty study days.
+    pub subject_reference_dates: BTreeMap<String, NaiveDate>,
+
+    /// Which DM variable the reference date was taken from.
+    pub study_day: StudyDayConfig,
+
     /// CT registry for codelist normalization.
     /// If None, CT normalization will preserve original values.
     pub ct_registry: Option<TerminologyRegistry>,
 
+    /// How CT normalization resolves source values to submission values.
+    pub ct_resolution_mode: CtResolutionMode,
+
+    /// Standard of the domain. Codelists are resolved in the CT package of
+    /// this standard first (e.g., SEND CT for SEND domains).
+    pub standard: Standard,
+
     /// Column mappings: target_variable -> source_column.
     pub mappings: BTreeMap<String, String>,
 
+    /// Many-to-one mappings: target_variable -> joined source columns.
+    /// Each is materialized as a derived column named by
+    /// [`ColumnConcat::column_name`], which `mappings` points to.
+    pub concatenations: BTreeMap<String, ColumnConcat>,
+
     /// Variables to omit from output (Permissible only).
     /// These variables will be completely excluded from the output DataFrame.
     pub omitted: BTreeSet<String>,
+
+    /// Variables that order records within each subject before --SEQ is assigned
+    /// (e.g., `["--DTC", "--TESTCD"]`). A leading `--` is replaced with the
+    /// domain code. Empty keeps source row order.
+    pub sequence_sort_keys: Vec<String>,
+
+    /// How USUBJID is composed from STUDYID, SUBJID, and other variables.
+    pub usubjid_template: UsubjidTemplate,
+
+    /// Partial date imputation per --DTC variable (e.g., "AESTDTC"), each
+    /// adding its derived date and flag variables. Empty adds none.
+    pub date_imputations: BTreeMap<String, DateImputation>,
+
+    /// Sponsor-specific transform run before --SEQ assignment, if any.
+    pub processor: Option<DomainProcessor>,
+
+    /// Decimal convention of numeric source values. If None, values are
+    /// parsed with `.` decimals and commas are dropped as grouping.
+    pub decimal_convention: Option<DecimalConvention>,
 }
 
 impl NormalizationContext {
@@ -174,9 +375,19 @@ pub fn new(study_id: impl Into<String>, domain_code: impl Into<String>) -> Self
             study_id: study_id.into(),
             domain_code: domain_code.into(),
             reference_date: None,
+            subject_reference_dates: BTreeMap::new(),
+            study_day: StudyDayConfig::default(),
             ct_registry: None,
+            ct_resolution_mode: CtResolutionMode::default(),
+            standard: Standard::Sdtm,
             mappings: BTreeMap::new(),
+            concatenations: BTreeMap::new(),
             omitted: BTreeSet::new(),
+            sequence_sort_keys: Vec::new(),
+            usubjid_template: UsubjidTemplate::default(),
+            date_imputations: BTreeMap::new(),
+            processor: None,
+            decimal_convention: None,
         }
     }
 
@@ -186,24 +397,102 @@ pub fn with_reference_date(mut self, date: Option<NaiveDate>) -> Self {
         self
     }
 
+    /// Set each subject's reference date for study day calculations.
+    pub fn with_subject_reference_dates(mut self, dates: BTreeMap<String, NaiveDate>) -> Self {
+        self.subject_reference_dates = dates;
+        self
+    }
+
+    /// Set the study day reference configuration.
+    pub fn with_study_day_config(mut self, config: StudyDayConfig) -> Self {
+        self.study_day = config;
+        self
+    }
+
     /// Set the CT registry for normalization.
     pub fn with_ct_registry(mut self, registry: Option<TerminologyRegistry>) -> Self {
         self.ct_registry = registry;
         self
     }
 
+    /// Set the CT resolution mode.
+    pub fn with_ct_resolution_mode(mut self, mode: CtResolutionMode) -> Self {
+        self.ct_resolution_mode = mode;
+        self
+    }
+
+    /// Set the standard of the domain.
+    pub fn with_standard(mut self, standard: Standard) -> Self {
+        self.standard = standard;
+        self
+    }
+
     /// Set the column mappings.
     pub fn with_mappings(mut self, mappings: BTreeMap<String, String>) -> Self {
         self.mappings = mappings;
         self
     }
 
+    /// Set the many-to-one mappings.
+    ///
+    /// Each target is also mapped to its derived column, replacing any
+    /// single-column mapping, so call this after [`Self::with_mappings`].
+    pub fn with_concatenations(mut self, concatenations: BTreeMap<String, ColumnConcat>) -> Self {
+        for (target, concat) in &concatenations {
+            self.mappings.insert(target.clone(), concat.column_name());
+        }
+        self.concatenations = concatenations;
+        self
+    }
+
     /// Set the omitted variables.
     pub fn with_omitted(mut self, omitted: BTreeSet<String>) -> Self {
         self.omitted = omitted;
         self
     }
 
+    /// Set the within-subject sort keys for --SEQ assignment.
+    pub fn with_sequence_sort_keys(mut self, keys: Vec<String>) -> Self {
+        self.sequence_sort_keys = keys;
+        self
+    }
+
+    /// Set the USUBJID derivation template.
+    pub fn with_usubjid_template(mut self, template: UsubjidTemplate) -> Self {
+        self.usubjid_template = template;
+        self
+    }
+
+    /// Impute partial dates of a --DTC variable into a derived variable.
+    pub fn with_date_imputation(
+        mut self,
+        variable: impl Into<String>,
+        imputation: DateImputation,
+    ) -> Self {
+        self.date_imputations.insert(variable.into(), imputation);
+        self
+    }
+
+    /// Run a sponsor-specific transform on the normalized domain.
+    ///
+    /// Ordering guarantees: the processor runs once, after every other rule
+    /// (constants, USUBJID, dates, CT normalization, ...) and partial date
+    /// imputation have produced their columns, and before --SEQ is assigned.
+    /// --SEQ is then computed from the processed frame: subjects are grouped
+    /// by its USUBJID column and `sequence_sort_keys` may name columns the
+    /// processor added. Columns it adds are kept after the domain variables.
+    pub fn with_processor(mut self, processor: DomainProcessor) -> Self {
+        self.processor = Some(processor);
+        self
+    }
+
+    /// Set the decimal convention of numeric source values (e.g., from
+    /// [`DecimalConvention::detect`] on sample values).
+    pub fn with_decimal_convention(mut self, convention: DecimalConvention) -> Self {
+        self.decimal_convention = Some(convention);
+        self
+    }
+
     /// Get the source column for a target variable.
     pub fn get_source_column(&self, target: &str) -> Option<&str> {
         self.mappings.get(target).map(String::as_str)
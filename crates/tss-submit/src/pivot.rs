@@ -0,0 +1,360 @@
+//! Wide-to-long and long-to-wide pivoting.
+//!
+//! Lab and vital signs exports often hold one column per test (`HGB`,
+//! `WBC`, ...) and one row per visit. SDTM findings domains instead hold one
+//! record per test result, identified by --TESTCD with the value in --ORRES.
+//! [`pivot_wide_to_long`] melts the test columns into such records so the
+//! result can be mapped like any other findings source.
+//!
+//! [`pivot_long_to_wide`] goes the other way, collapsing records into one row
+//! per subject (e.g. baseline values per parameter for an ADSL-style
+//! summary).
+
+use std::collections::{BTreeMap, HashMap};
+
+use polars::prelude::{Column, DataFrame};
+
+use crate::error::{Result, SubmitError};
+use crate::validate::ColumnReader;
+
+/// Identifier columns that group --SEQ numbering, in order of preference.
+const SUBJECT_COLUMNS: &[&str] = &["USUBJID", "SUBJID"];
+
+/// Maximum length of a --TESTCD value.
+const TESTCD_MAX_LENGTH: usize = 8;
+
+/// How [`pivot_long_to_wide`] resolves several records filling one cell.
+///
+/// Records that agree on the value are not conflicts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotConflict {
+    /// Keep the value of the first record, in row order.
+    #[default]
+    KeepFirst,
+    /// Keep the value of the last record, in row order.
+    KeepLast,
+    /// Fail with [`SubmitError::PivotConflict`].
+    Error,
+}
+
+/// Melt one-column-per-test source data into --TESTCD/--ORRES records.
+///
+/// Each non-blank value in `value_vars` becomes one record carrying the
+/// `id_vars` of its row, `<DOMAIN>TESTCD` from `testcd_map` (keyed by source
+/// column; unmapped columns use [`testcd_from_column`]), and the value as
+/// `<DOMAIN>ORRES`. Records keep source row order, then `value_vars` order.
+///
+/// `<DOMAIN>SEQ` numbers the records from 1 per subject, taken from the
+/// USUBJID or SUBJID id column, or the first id column if neither is given.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::ColumnNotFound`] if an id or value column is not in
+/// `df`.
+pub fn pivot_wide_to_long(
+    df: &DataFrame,
+    domain_code: &str,
+    id_vars: &[&str],
+    value_vars: &[&str],
+    testcd_map: &BTreeMap<String, String>,
+) -> Result<DataFrame> {
+    let reader = ColumnReader::new(df);
+    for column in id_vars.iter().chain(value_vars) {
+        if !reader.has_column(column) {
+            return Err(SubmitError::ColumnNotFound {
+                column: (*column).to_string(),
+            });
+        }
+    }
+
+    let domain = domain_code.to_uppercase();
+    let subject_column = SUBJECT_COLUMNS
+        .iter()
+        .find_map(|name| id_vars.iter().find(|id| id.eq_ignore_ascii_case(name)))
+        .or(id_vars.first());
+    let testcds: Vec<String> = value_vars
+        .iter()
+        .map(|column| {
+            testcd_map
+                .get(*column)
+                .cloned()
+                .unwrap_or_else(|| testcd_from_column(column))
+        })
+        .collect();
+
+    let mut id_values: Vec<Vec<String>> = vec![Vec::new(); id_vars.len()];
+    let mut testcd_vec: Vec<String> = Vec::new();
+    let mut orres_vec: Vec<String> = Vec::new();
+    let mut seq_vec: Vec<i64> = Vec::new();
+    let mut counters: HashMap<String, i64> = HashMap::new();
+
+    for row_idx in 0..reader.height() {
+        let subject = subject_column
+            .map(|column| reader.get_string(column, row_idx).trim().to_string())
+            .unwrap_or_default();
+        for (column, testcd) in value_vars.iter().zip(&testcds) {
+            let value = reader.get_string(column, row_idx);
+            if value.trim().is_empty() {
+                continue;
+            }
+            for (values, id) in id_values.iter_mut().zip(id_vars) {
+                values.push(reader.get_string(id, row_idx));
+            }
+            let seq = counters.entry(subject.clone()).or_insert(0);
+            *seq += 1;
+            seq_vec.push(*seq);
+            testcd_vec.push(testcd.clone());
+            orres_vec.push(value);
+        }
+    }
+
+    let mut columns: Vec<Column> = id_vars
+        .iter()
+        .zip(id_values)
+        .map(|(id, values)| Column::new((*id).into(), values))
+        .collect();
+    columns.push(Column::new(format!("{domain}SEQ").into(), seq_vec));
+    columns.push(Column::new(format!("{domain}TESTCD").into(), testcd_vec));
+    columns.push(Column::new(format!("{domain}ORRES").into(), orres_vec));
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Derive a --TESTCD value from a source column name.
+///
+/// The name is upper-cased and reduced to `A-Z`, `0-9` and `_`, prefixed
+/// with `_` when it would start with a digit, and truncated to 8 characters
+/// (e.g. `Hemoglobin A1c` gives `HEMOGLOB`, `24h urine` gives `_24HURIN`).
+fn testcd_from_column(column: &str) -> String {
+    let mut testcd: String = column
+        .to_ascii_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || *c == '_')
+        .collect();
+    if testcd.starts_with(|c: char| c.is_ascii_digit()) {
+        testcd.insert(0, '_');
+    }
+    testcd.truncate(TESTCD_MAX_LENGTH);
+    testcd
+}
+
+/// Collapse records into one row per `index` value with a column per
+/// `columns` value.
+///
+/// Each record puts its `values` entry in the cell for its index and column
+/// value (e.g. USUBJID, PARAMCD and AVAL give one AVAL column per parameter).
+/// Rows keep the first-seen order of their index values and columns the
+/// first-seen order of their column values; cells without a record are null.
+/// Records with a blank index, column or value are skipped. When records
+/// fill a cell with different values, `conflict` decides which one is kept.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::ColumnNotFound`] if `index`, `columns` or `values`
+/// is not in `df`, and [`SubmitError::PivotConflict`] for the first conflict
+/// when `conflict` is [`PivotConflict::Error`].
+pub fn pivot_long_to_wide(
+    df: &DataFrame,
+    index: &str,
+    columns: &str,
+    values: &str,
+    conflict: PivotConflict,
+) -> Result<DataFrame> {
+    let reader = ColumnReader::new(df);
+    for column in [index, columns, values] {
+        if !reader.has_column(column) {
+            return Err(SubmitError::ColumnNotFound {
+                column: column.to_string(),
+            });
+        }
+    }
+
+    let mut rows: Vec<String> = Vec::new();
+    let mut row_positions: HashMap<String, usize> = HashMap::new();
+    let mut names: Vec<String> = Vec::new();
+    let mut cells: HashMap<String, Vec<Option<String>>> = HashMap::new();
+
+    for row_idx in 0..reader.height() {
+        let key = reader.get_string(index, row_idx).trim().to_string();
+        let name = reader.get_string(columns, row_idx).trim().to_string();
+        let value = reader.get_string(values, row_idx);
+        if key.is_empty() || name.is_empty() || value.trim().is_empty() {
+            continue;
+        }
+
+        let row = *row_positions.entry(key.clone()).or_insert_with(|| {
+            rows.push(key.clone());
+            rows.len() - 1
+        });
+        let column = cells.entry(name.clone()).or_insert_with(|| {
+            names.push(name.clone());
+            Vec::new()
+        });
+        if column.len() <= row {
+            column.resize(row + 1, None);
+        }
+
+        match &column[row] {
+            Some(existing) if *existing != value => match conflict {
+                PivotConflict::KeepFirst => {}
+                PivotConflict::KeepLast => column[row] = Some(value),
+                PivotConflict::Error => {
+                    return Err(SubmitError::PivotConflict {
+                        index: key,
+                        column: name,
+                        existing: existing.clone(),
+                        incoming: value,
+                    });
+                }
+            },
+            Some(_) => {}
+            None => column[row] = Some(value),
+        }
+    }
+
+    let height = rows.len();
+    let mut output = vec![Column::new(index.into(), rows)];
+    for name in names {
+        let mut column = cells.remove(&name).unwrap_or_default();
+        column.resize(height, None);
+        output.push(Column::new(name.into(), column));
+    }
+    Ok(DataFrame::new(output)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn column(df: &DataFrame, name: &str) -> Vec<String> {
+        ColumnReader::new(df)
+            .values(name)
+            .unwrap()
+            .map(|(_, value)| value.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_pivot_three_tests_to_long() {
+        let wide = df! {
+            "USUBJID" => &["S-001", "S-001", "S-002"],
+            "VISIT" => &["SCREENING", "WEEK 4", "SCREENING"],
+            "Hemoglobin" => &[Some("13.5"), Some("13.1"), Some("12.0")],
+            "WBC" => &[Some("6.2"), None, Some("5.4")],
+            "plt" => &[Some("250"), Some("245"), Some("")],
+        }
+        .unwrap();
+        let testcd_map = BTreeMap::from([("Hemoglobin".to_string(), "HGB".to_string())]);
+
+        let long = pivot_wide_to_long(
+            &wide,
+            "lb",
+            &["USUBJID", "VISIT"],
+            &["Hemoglobin", "WBC", "plt"],
+            &testcd_map,
+        )
+        .unwrap();
+
+        assert_eq!(
+            long.get_column_names(),
+            ["USUBJID", "VISIT", "LBSEQ", "LBTESTCD", "LBORRES"]
+        );
+        assert_eq!(
+            column(&long, "USUBJID"),
+            [
+                "S-001", "S-001", "S-001", "S-001", "S-001", "S-002", "S-002"
+            ]
+        );
+        assert_eq!(
+            column(&long, "VISIT"),
+            [
+                "SCREENING",
+                "SCREENING",
+                "SCREENING",
+                "WEEK 4",
+                "WEEK 4",
+                "SCREENING",
+                "SCREENING"
+            ]
+        );
+        assert_eq!(
+            column(&long, "LBTESTCD"),
+            ["HGB", "WBC", "PLT", "HGB", "PLT", "HGB", "WBC"]
+        );
+        assert_eq!(
+            column(&long, "LBORRES"),
+            ["13.5", "6.2", "250", "13.1", "245", "12.0", "5.4"]
+        );
+        assert_eq!(column(&long, "LBSEQ"), ["1", "2", "3", "4", "5", "1", "2"]);
+    }
+
+    #[test]
+    fn test_testcd_from_column() {
+        assert_eq!(testcd_from_column("plt"), "PLT");
+        assert_eq!(testcd_from_column("Hemoglobin A1c"), "HEMOGLOB");
+        assert_eq!(testcd_from_column("Na+ (mmol/L)"), "NAMMOLL");
+        assert_eq!(testcd_from_column("24h urine"), "_24HURIN");
+        assert_eq!(testcd_from_column("pulse_rate"), "PULSE_RA");
+    }
+
+    #[test]
+    fn test_pivot_missing_column() {
+        let wide = df! {
+            "USUBJID" => &["S-001"],
+            "HGB" => &["13.5"],
+        }
+        .unwrap();
+        let result = pivot_wide_to_long(&wide, "LB", &["USUBJID"], &["WBC"], &BTreeMap::new());
+        assert!(matches!(
+            result,
+            Err(SubmitError::ColumnNotFound { column }) if column == "WBC"
+        ));
+    }
+
+    fn baseline() -> DataFrame {
+        df! {
+            "USUBJID" => &["S-001", "S-001", "S-002", "S-002", "S-001"],
+            "PARAMCD" => &["WEIGHT", "HEIGHT", "WEIGHT", "HEIGHT", "WEIGHT"],
+            "AVAL" => &["70.5", "172", "64.0", "", "71.0"],
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pivot_long_to_wide() {
+        let long = baseline().slice(0, 4);
+        let wide =
+            pivot_long_to_wide(&long, "USUBJID", "PARAMCD", "AVAL", PivotConflict::Error).unwrap();
+
+        assert_eq!(wide.get_column_names(), ["USUBJID", "WEIGHT", "HEIGHT"]);
+        assert_eq!(column(&wide, "USUBJID"), ["S-001", "S-002"]);
+        assert_eq!(column(&wide, "WEIGHT"), ["70.5", "64.0"]);
+        assert_eq!(
+            wide.column("HEIGHT")
+                .unwrap()
+                .str()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [Some("172"), None]
+        );
+    }
+
+    #[test]
+    fn test_pivot_long_to_wide_conflict() {
+        let long = baseline();
+        let weight = |conflict| {
+            let wide = pivot_long_to_wide(&long, "USUBJID", "PARAMCD", "AVAL", conflict).unwrap();
+            column(&wide, "WEIGHT")
+        };
+        assert_eq!(weight(PivotConflict::KeepFirst), ["70.5", "64.0"]);
+        assert_eq!(weight(PivotConflict::KeepLast), ["71.0", "64.0"]);
+
+        let result = pivot_long_to_wide(&long, "USUBJID", "PARAMCD", "AVAL", PivotConflict::Error);
+        assert!(matches!(
+            result,
+            Err(SubmitError::PivotConflict { index, column, existing, incoming })
+                if index == "S-001" && column == "WEIGHT" && existing == "70.5" && incoming == "71.0"
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+//! Progress reporting and cancellation for long-running operations.
+//!
+//! [`run_submission_with_progress`](crate::run_submission_with_progress) and
+//! [`write_xpt_outputs_with_progress`](crate::write_xpt_outputs_with_progress)
+//! take a callback that receives [`ProgressEvent`]s, so the GUI can show a progress bar per
+//! domain instead of a spinner. A [`CancellationToken`]
+//! lets the caller stop a submission run from another thread.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Stage of work a [`ProgressEvent`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProgressStage {
+    /// Mapping, normalization, and validation of a source domain.
+    Processing,
+    /// Writing a domain's XPT dataset.
+    XptExport,
+}
+
+/// A progress update for one domain.
+///
+/// For each domain and stage, events arrive in order: `DomainStarted`, then
+/// one or more `RowsProcessed`, then `DomainFinished`. Domains are reported
+/// one after another, never interleaved. XPT export reports rows in batches;
+/// processing reports them once per domain, as mapping and normalization
+/// work on the whole domain at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// Work on a domain started.
+    DomainStarted {
+        /// Stage of work.
+        stage: ProgressStage,
+        /// Domain code, or dataset name for split exports (e.g., "LBCH").
+        domain: String,
+        /// Number of rows to process.
+        total_rows: usize,
+    },
+    /// Rows of a domain were processed.
+    RowsProcessed {
+        /// Stage of work.
+        stage: ProgressStage,
+        /// Domain code, or dataset name for split exports.
+        domain: String,
+        /// Rows processed so far.
+        rows: usize,
+    },
+    /// Work on a domain finished.
+    DomainFinished {
+        /// Stage of work.
+        stage: ProgressStage,
+        /// Domain code, or dataset name for split exports.
+        domain: String,
+    },
+}
+
+/// Cooperative cancellation flag shared between a caller and a running job.
+///
+/// Clones share the same flag, and compare equal to each other only. The
+/// job checks it at safe points (between domains, between output formats,
+/// and between the row batches of an XPT dataset) and stops there.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
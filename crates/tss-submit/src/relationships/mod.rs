@@ -0,0 +1,11 @@
+//! Relationship dataset generation.
+//!
+//! Builds the SDTM datasets that relate records rather than hold findings:
+//! - RELREC records linking two domains through a shared key variable
+//! - SUPP-- records attaching supplemental qualifiers to parent records
+
+mod relrec;
+mod suppqual;
+
+pub use relrec::generate_relrec;
+pub use suppqual::{SuppParentKey, SuppQualifier, build_suppqual};
@@ -0,0 +1,199 @@
+//! RELREC generation from shared key variables.
+//!
+//! Per SDTM-IG 8.2, records in two domains are related by RELREC rows that
+//! share a RELID, each pointing at one side through RDOMAIN, IDVAR and
+//! IDVARVAL. [`generate_relrec`] derives those rows from a linking variable
+//! both domains carry (e.g. AEGRPID and CMGRPID), so the user does not have
+//! to enter them by hand.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use polars::prelude::{Column, DataFrame};
+
+use crate::error::{Result, SubmitError};
+use crate::export::DomainFrame;
+use crate::validate::{ColumnReader, relrec_key_variables};
+
+/// Generate RELREC records linking two domains through a shared key variable.
+///
+/// `link` is the variable without its domain prefix, written either as
+/// `GRPID` or `--GRPID`; it is resolved to `AEGRPID`, `CMGRPID`, and so on.
+/// Each subject's key value present in both domains becomes one relationship:
+/// a RELREC row per domain with `IDVAR` set to the prefixed variable,
+/// `IDVARVAL` to the key value, and a RELID numbered within the subject.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::InvalidConfig`] if `link` is not a RELREC key
+/// variable or both domains are the same, and [`SubmitError::ColumnNotFound`]
+/// if either domain lacks USUBJID or the linking variable.
+pub fn generate_relrec(
+    study_id: &str,
+    left: (&str, &DataFrame),
+    right: (&str, &DataFrame),
+    link: &str,
+) -> Result<DomainFrame> {
+    let suffix = link.trim_start_matches('-').to_uppercase();
+    let left_code = left.0.to_uppercase();
+    let right_code = right.0.to_uppercase();
+    if left_code == right_code {
+        return Err(SubmitError::InvalidConfig {
+            message: format!("RELREC needs two different domains, got {left_code} twice"),
+        });
+    }
+
+    let left_var = format!("{left_code}{suffix}");
+    let right_var = format!("{right_code}{suffix}");
+    if !relrec_key_variables(&left_code).contains(&left_var) {
+        return Err(SubmitError::InvalidConfig {
+            message: format!("--{suffix} is not a RELREC key variable"),
+        });
+    }
+
+    let left_keys = subject_keys(left.1, &left_var)?;
+    let right_keys = subject_keys(right.1, &right_var)?;
+
+    // (USUBJID, key value) pairs linked in both domains, in stable order
+    let links: BTreeSet<&(String, String)> = left_keys
+        .iter()
+        .filter(|key| right_keys.contains(*key))
+        .collect();
+
+    let mut rdomain = Vec::with_capacity(links.len() * 2);
+    let mut usubjid = Vec::with_capacity(links.len() * 2);
+    let mut idvar = Vec::with_capacity(links.len() * 2);
+    let mut idvarval = Vec::with_capacity(links.len() * 2);
+    let mut relid = Vec::with_capacity(links.len() * 2);
+
+    let mut next_relid: BTreeMap<&str, u32> = BTreeMap::new();
+    for (subject, value) in links {
+        let counter = next_relid.entry(subject.as_str()).or_insert(0);
+        *counter += 1;
+        for (code, var) in [(&left_code, &left_var), (&right_code, &right_var)] {
+            rdomain.push(code.clone());
+            usubjid.push(subject.clone());
+            idvar.push(var.clone());
+            idvarval.push(value.clone());
+            relid.push(counter.to_string());
+        }
+    }
+
+    let height = rdomain.len();
+    let data = DataFrame::new(vec![
+        Column::new("STUDYID".into(), vec![study_id.to_string(); height]),
+        Column::new("RDOMAIN".into(), rdomain),
+        Column::new("USUBJID".into(), usubjid),
+        Column::new("IDVAR".into(), idvar),
+        Column::new("IDVARVAL".into(), idvarval),
+        Column::new("RELTYPE".into(), vec![String::new(); height]),
+        Column::new("RELID".into(), relid),
+    ])?;
+
+    Ok(DomainFrame::new("RELREC", data))
+}
+
+/// Collect the non-empty (USUBJID, key value) pairs of a domain.
+fn subject_keys(df: &DataFrame, var: &str) -> Result<BTreeSet<(String, String)>> {
+    let reader = ColumnReader::new(df);
+    let column_not_found = |column: &str| SubmitError::ColumnNotFound {
+        column: column.to_string(),
+    };
+    let subjects = reader
+        .values("USUBJID")
+        .ok_or_else(|| column_not_found("USUBJID"))?;
+    let values = reader.values(var).ok_or_else(|| column_not_found(var))?;
+
+    Ok(subjects
+        .zip(values)
+        .map(|((_, subject), (_, value))| (subject.trim().to_string(), value.trim().to_string()))
+        .filter(|(subject, value)| !subject.is_empty() && !value.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::{Issue, load_default_rules, validate_cross_domain};
+    use polars::prelude::*;
+
+    fn ae_df() -> DataFrame {
+        df! {
+            "USUBJID" => &["S-001", "S-001", "S-002", "S-003"],
+            "AESEQ" => &["1", "2", "1", "1"],
+            "AETERM" => &["HEADACHE", "NAUSEA", "RASH", "FATIGUE"],
+            "AEGRPID" => &["G1", "G2", "G1", ""],
+        }
+        .unwrap()
+    }
+
+    fn cm_df() -> DataFrame {
+        df! {
+            "USUBJID" => &["S-001", "S-002", "S-002", "S-003"],
+            "CMSEQ" => &["1", "1", "2", "1"],
+            "CMTRT" => &["ASPIRIN", "CETIRIZINE", "IBUPROFEN", "PARACETAMOL"],
+            "CMGRPID" => &["G1", "G1", "G1", "G9"],
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn test_generate_relrec_links_ae_to_cm() {
+        let ae = ae_df();
+        let cm = cm_df();
+        let frame = generate_relrec("STUDY", ("AE", &ae), ("CM", &cm), "--GRPID").unwrap();
+
+        assert_eq!(frame.domain_code, "RELREC");
+        let reader = ColumnReader::new(&frame.data);
+        let column = |name: &str| -> Vec<String> {
+            reader
+                .values(name)
+                .unwrap()
+                .map(|(_, value)| value.to_string())
+                .collect()
+        };
+
+        // S-001/G1 and S-002/G1 are in both domains; S-001/G2 and S-003 are not
+        assert_eq!(column("RDOMAIN"), ["AE", "CM", "AE", "CM"]);
+        assert_eq!(column("USUBJID"), ["S-001", "S-001", "S-002", "S-002"]);
+        assert_eq!(
+            column("IDVAR"),
+            ["AEGRPID", "CMGRPID", "AEGRPID", "CMGRPID"]
+        );
+        assert_eq!(column("IDVARVAL"), ["G1", "G1", "G1", "G1"]);
+        assert_eq!(column("RELID"), ["1", "1", "1", "1"]);
+        assert_eq!(column("STUDYID"), ["STUDY"; 4]);
+
+        // The generated records pass RELREC reference validation
+        let dm = df! { "USUBJID" => &["S-001", "S-002", "S-003"] }.unwrap();
+        let domains: Vec<(&str, &DataFrame)> = vec![
+            ("DM", &dm),
+            ("AE", &ae),
+            ("CM", &cm),
+            ("RELREC", &frame.data),
+        ];
+        let results = validate_cross_domain(&domains, &load_default_rules());
+        assert!(results.iter().all(|(_, issues)| {
+            !issues
+                .iter()
+                .any(|issue| matches!(issue, Issue::RelrecInvalidReference { .. }))
+        }));
+    }
+
+    #[test]
+    fn test_generate_relrec_errors() {
+        let ae = ae_df();
+        let cm = cm_df();
+
+        let missing = generate_relrec("STUDY", ("AE", &ae), ("CM", &cm), "LNKID");
+        assert!(matches!(
+            missing,
+            Err(SubmitError::ColumnNotFound { column }) if column == "AELNKID"
+        ));
+
+        let invalid = generate_relrec("STUDY", ("AE", &ae), ("CM", &cm), "TERM");
+        assert!(matches!(invalid, Err(SubmitError::InvalidConfig { .. })));
+
+        let same = generate_relrec("STUDY", ("AE", &ae), ("ae", &ae), "GRPID");
+        assert!(matches!(same, Err(SubmitError::InvalidConfig { .. })));
+    }
+}
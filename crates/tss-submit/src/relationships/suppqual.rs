@@ -0,0 +1,289 @@
+//! SUPP-- generation for supplemental qualifiers.
+//!
+//! Per SDTM-IG 8.4, each SUPP-- record attaches one qualifier value (QNAM,
+//! QVAL) to a parent record, identified by RDOMAIN, USUBJID, IDVAR and
+//! IDVARVAL. Record-level qualifiers point at the parent's --SEQ; subject
+//! level qualifiers (e.g. SUPPDM) leave IDVAR and IDVARVAL blank.
+
+use polars::prelude::{Column, DataFrame};
+
+use crate::error::{Result, SubmitError};
+use crate::validate::ColumnReader;
+
+/// How SUPP-- records identify their parent record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuppParentKey {
+    /// Qualifiers describe the subject; IDVAR and IDVARVAL are blank.
+    Subject,
+    /// Qualifiers describe a record; IDVAR names this parent variable and
+    /// IDVARVAL holds its value on the qualified row.
+    Record(String),
+}
+
+impl SuppParentKey {
+    /// Parent key for a domain: its --SEQ variable if present, otherwise
+    /// the subject.
+    pub fn for_domain(domain_code: &str, parent: &DataFrame) -> Self {
+        let seq_var = format!("{}SEQ", domain_code.to_uppercase());
+        if parent.column(&seq_var).is_ok() {
+            Self::Record(seq_var)
+        } else {
+            Self::Subject
+        }
+    }
+}
+
+/// A source column emitted as a supplemental qualifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppQualifier {
+    /// Source column holding the qualifier values (QVAL).
+    pub column: String,
+    /// Qualifier variable name (QNAM).
+    pub qnam: String,
+    /// Qualifier variable label (QLABEL).
+    pub qlabel: String,
+    /// Origin of the values (QORIG), e.g. "CRF".
+    pub qorig: String,
+    /// Evaluator (QEVAL), if any.
+    pub qeval: Option<String>,
+}
+
+impl SuppQualifier {
+    /// Create a qualifier for a source column.
+    pub fn new(
+        column: impl Into<String>,
+        qnam: impl Into<String>,
+        qlabel: impl Into<String>,
+        qorig: impl Into<String>,
+    ) -> Self {
+        Self {
+            column: column.into(),
+            qnam: qnam.into(),
+            qlabel: qlabel.into(),
+            qorig: qorig.into(),
+            qeval: None,
+        }
+    }
+
+    /// Set the evaluator (QEVAL).
+    #[must_use]
+    pub fn with_qeval(mut self, qeval: impl Into<String>) -> Self {
+        self.qeval = Some(qeval.into());
+        self
+    }
+}
+
+/// Build the SUPP-- dataset for a parent domain.
+///
+/// `parent` is the transformed parent domain and `source` the source data it
+/// was built from; row `i` of one must correspond to row `i` of the other.
+/// Each non-empty qualifier value becomes one record pointing at its row
+/// through `key`. Qualifiers whose column is missing from `source` and rows
+/// without a USUBJID are skipped.
+///
+/// Returns `None` if no records were produced.
+///
+/// # Errors
+///
+/// Returns [`SubmitError::ColumnNotFound`] if `parent` lacks USUBJID or the
+/// variable named by a [`SuppParentKey::Record`] key.
+pub fn build_suppqual(
+    study_id: &str,
+    domain_code: &str,
+    parent: &DataFrame,
+    source: &DataFrame,
+    qualifiers: &[SuppQualifier],
+    key: &SuppParentKey,
+) -> Result<Option<DataFrame>> {
+    let parent_reader = ColumnReader::new(parent);
+    let source_reader = ColumnReader::new(source);
+    for column in std::iter::once("USUBJID").chain(match key {
+        SuppParentKey::Subject => None,
+        SuppParentKey::Record(var) => Some(var.as_str()),
+    }) {
+        if !parent_reader.has_column(column) {
+            return Err(SubmitError::ColumnNotFound {
+                column: column.to_string(),
+            });
+        }
+    }
+
+    let rdomain = domain_code.to_uppercase();
+    let mut usubjid_vec: Vec<String> = Vec::new();
+    let mut idvar_vec: Vec<String> = Vec::new();
+    let mut idvarval_vec: Vec<String> = Vec::new();
+    let mut qnam_vec: Vec<String> = Vec::new();
+    let mut qlabel_vec: Vec<String> = Vec::new();
+    let mut qval_vec: Vec<String> = Vec::new();
+    let mut qorig_vec: Vec<String> = Vec::new();
+    let mut qeval_vec: Vec<String> = Vec::new();
+
+    for qualifier in qualifiers {
+        if !source_reader.has_column(&qualifier.column) {
+            continue;
+        }
+
+        for row_idx in 0..source.height().min(parent.height()) {
+            let usubjid = parent_reader.get_string("USUBJID", row_idx);
+            if usubjid.trim().is_empty() {
+                continue;
+            }
+
+            let qval = source_reader.get_string(&qualifier.column, row_idx);
+            if qval.trim().is_empty() {
+                continue;
+            }
+
+            let (idvar, idvarval) = match key {
+                SuppParentKey::Subject => (String::new(), String::new()),
+                SuppParentKey::Record(var) => (var.clone(), parent_reader.get_string(var, row_idx)),
+            };
+
+            usubjid_vec.push(usubjid);
+            idvar_vec.push(idvar);
+            idvarval_vec.push(idvarval);
+            qnam_vec.push(qualifier.qnam.clone());
+            qlabel_vec.push(qualifier.qlabel.clone());
+            qval_vec.push(qval);
+            qorig_vec.push(qualifier.qorig.clone());
+            qeval_vec.push(qualifier.qeval.clone().unwrap_or_default());
+        }
+    }
+
+    if usubjid_vec.is_empty() {
+        return Ok(None);
+    }
+
+    let height = usubjid_vec.len();
+    let data = DataFrame::new(vec![
+        Column::new("STUDYID".into(), vec![study_id.to_string(); height]),
+        Column::new("RDOMAIN".into(), vec![rdomain; height]),
+        Column::new("USUBJID".into(), usubjid_vec),
+        Column::new("IDVAR".into(), idvar_vec),
+        Column::new("IDVARVAL".into(), idvarval_vec),
+        Column::new("QNAM".into(), qnam_vec),
+        Column::new("QLABEL".into(), qlabel_vec),
+        Column::new("QVAL".into(), qval_vec),
+        Column::new("QORIG".into(), qorig_vec),
+        Column::new("QEVAL".into(), qeval_vec),
+    ])?;
+
+    Ok(Some(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::{Issue, load_default_rules, validate_cross_domain};
+    use polars::prelude::*;
+
+    fn column(df: &DataFrame, name: &str) -> Vec<String> {
+        ColumnReader::new(df)
+            .values(name)
+            .unwrap()
+            .map(|(_, value)| value.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_record_level_supp_references_parent_seq() {
+        let ae = df! {
+            "USUBJID" => &["S-001", "S-001", "S-002"],
+            "AESEQ" => &[1i64, 2, 1],
+            "AETERM" => &["HEADACHE", "NAUSEA", "RASH"],
+        }
+        .unwrap();
+        let source = df! {
+            "SUBJ" => &["001", "001", "002"],
+            "TERM" => &["Headache", "Nausea", "Rash"],
+            "TREATED" => &["Y", "", "N"],
+            "OUTCOME2" => &["RESOLVED", "ONGOING", ""],
+        }
+        .unwrap();
+        let qualifiers = [
+            SuppQualifier::new("TREATED", "AETRTEM", "Treatment Emergent Flag", "CRF"),
+            SuppQualifier::new("OUTCOME2", "AEOUT2", "Secondary Outcome", "DERIVED")
+                .with_qeval("INVESTIGATOR"),
+            SuppQualifier::new("MISSING", "AEMISS", "Missing Column", "CRF"),
+        ];
+
+        let key = SuppParentKey::for_domain("AE", &ae);
+        assert_eq!(key, SuppParentKey::Record("AESEQ".to_string()));
+        let supp = build_suppqual("STUDY", "AE", &ae, &source, &qualifiers, &key)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(column(&supp, "RDOMAIN"), ["AE"; 4]);
+        assert_eq!(
+            column(&supp, "USUBJID"),
+            ["S-001", "S-002", "S-001", "S-001"]
+        );
+        assert_eq!(column(&supp, "IDVAR"), ["AESEQ"; 4]);
+        assert_eq!(column(&supp, "IDVARVAL"), ["1", "1", "1", "2"]);
+        assert_eq!(
+            column(&supp, "QNAM"),
+            ["AETRTEM", "AETRTEM", "AEOUT2", "AEOUT2"]
+        );
+        assert_eq!(column(&supp, "QVAL"), ["Y", "N", "RESOLVED", "ONGOING"]);
+        assert_eq!(
+            column(&supp, "QEVAL"),
+            ["", "", "INVESTIGATOR", "INVESTIGATOR"]
+        );
+
+        // Every record resolves to a parent AESEQ and QNAMs are unique per record
+        let dm = df! { "USUBJID" => &["S-001", "S-002"] }.unwrap();
+        let domains: Vec<(&str, &DataFrame)> = vec![("DM", &dm), ("AE", &ae), ("SUPPAE", &supp)];
+        let results = validate_cross_domain(&domains, &load_default_rules());
+        assert!(results.iter().all(|(_, issues)| {
+            !issues.iter().any(|issue| {
+                matches!(
+                    issue,
+                    Issue::SuppInvalidParent { .. } | Issue::SuppDuplicateQnam { .. }
+                )
+            })
+        }));
+    }
+
+    #[test]
+    fn test_subject_level_supp_leaves_idvar_blank() {
+        let dm = df! {
+            "USUBJID" => &["S-001", "S-002", ""],
+            "AGE" => &[34i64, 51, 40],
+        }
+        .unwrap();
+        let source = df! { "RACEOTH" => &["MAORI", "", "SAMI"] }.unwrap();
+        let qualifiers = [SuppQualifier::new(
+            "RACEOTH",
+            "RACEOTH",
+            "Race, Other",
+            "CRF",
+        )];
+
+        let key = SuppParentKey::for_domain("DM", &dm);
+        assert_eq!(key, SuppParentKey::Subject);
+        let supp = build_suppqual("STUDY", "DM", &dm, &source, &qualifiers, &key)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(column(&supp, "USUBJID"), ["S-001"]);
+        assert_eq!(column(&supp, "IDVAR"), [""]);
+        assert_eq!(column(&supp, "IDVARVAL"), [""]);
+    }
+
+    #[test]
+    fn test_build_suppqual_missing_key() {
+        let ae = df! { "USUBJID" => &["S-001"] }.unwrap();
+        let source = df! { "TREATED" => &["Y"] }.unwrap();
+        let qualifiers = [SuppQualifier::new("TREATED", "AETRTEM", "", "CRF")];
+        let key = SuppParentKey::Record("AESEQ".to_string());
+
+        let result = build_suppqual("STUDY", "AE", &ae, &source, &qualifiers, &key);
+        assert!(matches!(
+            result,
+            Err(SubmitError::ColumnNotFound { column }) if column == "AESEQ"
+        ));
+
+        let empty = build_suppqual("STUDY", "AE", &ae, &source, &[], &SuppParentKey::Subject);
+        assert!(empty.unwrap().is_none());
+    }
+}
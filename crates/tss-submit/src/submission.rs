@@ -0,0 +1,795 @@
+//! End-to-end submission processing.
+//!
+//! [`run_submission`] chains the granular APIs of this crate into a single
+//! call: discover source files, auto-map them to SDTM domains, normalize,
+//! validate, and export the requested formats.
+//! [`run_submission_with_progress`] also reports per-domain progress. Callers that need control over individual steps
+//! should keep using the `map`, `normalize`, `validate`, and `export` modules
+//! directly.
+//!
+//! [`validate_xpt_folder`] checks a folder of already exported XPT datasets,
+//! e.g. from another tool, without any mapping or export.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use polars::prelude::DataFrame;
+use tss_ingest::SourceEntry;
+use tss_standards::ct::CtVersion;
+use tss_standards::{SdtmDomain, Standard};
+
+use crate::error::{Result, SubmitError};
+use crate::export::{DatasetNameIssue, DefineXmlOptions, DomainFrame, PlannedOutput, XptOptions};
+use crate::map::{MappingState, MappingSummary};
+use crate::normalize::{StudyDayConfig, build_preview_dataframe_for_mapping_with_study_day};
+use crate::progress::{CancellationToken, ProgressEvent, ProgressStage};
+use crate::validate::{
+    ValidationReport, domain_for_dataset, load_default_rules, validate_all_domains,
+    validate_cross_domain, validate_domain,
+};
+
+/// Output format produced by [`run_submission`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OutputFormat {
+    /// SAS Transport (XPT) datasets, written to `xpt/`.
+    Xpt,
+    /// CDISC Dataset-XML files, written to `dataset-xml/`.
+    DatasetXml,
+    /// A single `define.xml` in the output directory, with a `define.html`
+    /// rendering beside it.
+    DefineXml,
+}
+
+/// Configuration for [`run_submission`].
+#[derive(Debug, Clone)]
+pub struct SubmissionConfig {
+    /// Study identifier used for STUDYID and USUBJID derivation.
+    pub study_id: String,
+    /// Number of header rows in the source CSV files and worksheets.
+    pub header_rows: usize,
+    /// Minimum confidence (0.0-1.0) for auto-accepting mapping suggestions.
+    pub min_confidence: f32,
+    /// Controlled Terminology version used for normalization and validation.
+    pub ct_version: CtVersion,
+    /// DM variable that --DY study days are counted from.
+    pub study_day: StudyDayConfig,
+    /// Implementation Guide version written to Dataset-XML and Define-XML.
+    pub ig_version: String,
+    /// Formats to export.
+    pub formats: BTreeSet<OutputFormat>,
+    /// Limits and value handling for XPT export and its dry-run plan.
+    pub xpt_options: XptOptions,
+    /// Token checked between domains, output formats, and XPT row batches
+    /// to stop early.
+    pub cancellation: CancellationToken,
+    /// Plan the exports without writing anything; see
+    /// [`SubmissionResult::planned`].
+    pub dry_run: bool,
+}
+
+impl SubmissionConfig {
+    /// Create a config that exports XPT with default settings.
+    pub fn new(study_id: impl Into<String>) -> Self {
+        Self {
+            study_id: study_id.into(),
+            header_rows: 2,
+            min_confidence: 0.6,
+            ct_version: CtVersion::default(),
+            study_day: StudyDayConfig::default(),
+            ig_version: "3.4".to_string(),
+            formats: BTreeSet::from([OutputFormat::Xpt]),
+            xpt_options: XptOptions::default(),
+            cancellation: CancellationToken::new(),
+            dry_run: false,
+        }
+    }
+
+    /// Set the number of header rows in the source CSV files and worksheets.
+    pub fn with_header_rows(mut self, header_rows: usize) -> Self {
+        self.header_rows = header_rows;
+        self
+    }
+
+    /// Set the minimum confidence for auto-accepting mapping suggestions.
+    pub fn with_min_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// Set the Controlled Terminology version.
+    pub fn with_ct_version(mut self, ct_version: CtVersion) -> Self {
+        self.ct_version = ct_version;
+        self
+    }
+
+    /// Set the study day reference configuration.
+    pub fn with_study_day_config(mut self, config: StudyDayConfig) -> Self {
+        self.study_day = config;
+        self
+    }
+
+    /// Set the Implementation Guide version.
+    pub fn with_ig_version(mut self, ig_version: impl Into<String>) -> Self {
+        self.ig_version = ig_version.into();
+        self
+    }
+
+    /// Replace the set of formats to export.
+    pub fn with_formats(mut self, formats: impl IntoIterator<Item = OutputFormat>) -> Self {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+
+    /// Set the XPT export options.
+    pub fn with_xpt_options(mut self, options: XptOptions) -> Self {
+        self.xpt_options = options;
+        self
+    }
+
+    /// Plan the exports instead of writing them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Stop the run when `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+}
+
+/// Per-domain outcome of [`run_submission`].
+#[derive(Debug, Clone)]
+pub struct DomainSubmission {
+    /// Domain code (e.g., "DM", "AE").
+    pub code: String,
+    /// Source file (CSV, SAS dataset, or workbook) the domain was built from.
+    pub source: PathBuf,
+    /// Worksheet the domain was read from, for workbook sources.
+    pub sheet: Option<String>,
+    /// Mapping coverage after auto-accepting suggestions.
+    pub mapping: MappingSummary,
+    /// Domain and cross-domain validation issues.
+    pub validation: ValidationReport,
+}
+
+/// Result of [`run_submission`].
+#[derive(Debug, Clone, Default)]
+pub struct SubmissionResult {
+    /// Processed domains, sorted by domain code.
+    pub domains: Vec<DomainSubmission>,
+    /// Source files that could not be matched to an SDTM domain.
+    pub skipped_files: Vec<PathBuf>,
+    /// Workbook sheets that could not be matched to an SDTM domain.
+    pub skipped_sheets: Vec<SourceEntry>,
+    /// Files written by the export step.
+    pub outputs: Vec<PathBuf>,
+    /// Files the export step would write, for a dry run. Define-XML and
+    /// its HTML rendering are listed without a layout.
+    pub planned: Vec<PlannedOutput>,
+    /// Dataset names that break the SAS V5 transport naming rules. The
+    /// datasets are still exported; the issues are also logged as warnings.
+    pub warnings: Vec<DatasetNameIssue>,
+    /// Whether the run was cancelled before completing; `domains` and
+    /// `outputs` then hold only the work finished before cancellation.
+    pub cancelled: bool,
+}
+
+impl SubmissionResult {
+    /// Get the outcome for a domain by code.
+    pub fn domain(&self, code: &str) -> Option<&DomainSubmission> {
+        self.domains
+            .iter()
+            .find(|d| d.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Total validation errors (Error + Reject) across all domains.
+    pub fn error_count(&self) -> usize {
+        self.domains
+            .iter()
+            .map(|d| d.validation.error_count())
+            .sum()
+    }
+
+    /// Total validation warnings across all domains.
+    pub fn warning_count(&self) -> usize {
+        self.domains
+            .iter()
+            .map(|d| d.validation.warning_count())
+            .sum()
+    }
+}
+
+/// Result of [`validate_xpt_folder`].
+#[derive(Debug, Clone, Default)]
+pub struct FolderValidation {
+    /// Domain and cross-domain validation per dataset, sorted by domain code.
+    pub reports: Vec<ValidationReport>,
+    /// XPT files whose name matches no SDTM-IG domain.
+    pub skipped_files: Vec<PathBuf>,
+}
+
+impl FolderValidation {
+    /// Get the report for a domain by code.
+    pub fn report(&self, code: &str) -> Option<&ValidationReport> {
+        self.reports
+            .iter()
+            .find(|r| r.domain.eq_ignore_ascii_case(code))
+    }
+
+    /// Total validation errors (Error + Reject) across all datasets.
+    pub fn error_count(&self) -> usize {
+        self.reports.iter().map(ValidationReport::error_count).sum()
+    }
+
+    /// Total validation warnings across all datasets.
+    pub fn warning_count(&self) -> usize {
+        self.reports
+            .iter()
+            .map(ValidationReport::warning_count)
+            .sum()
+    }
+}
+
+/// Validate a folder of SAS Transport (XPT) datasets.
+///
+/// Reads every `.xpt` file in `dir`, matches it to an SDTM-IG domain by file
+/// name (e.g., `ae.xpt`), and runs domain validation with the Controlled
+/// Terminology of `ct_version` plus cross-domain reference checks. SUPP--
+/// files are validated against SUPPQUAL and split datasets against their
+/// parent domain (e.g., `lbch.xpt` against LB); each report is named after
+/// its dataset. Files that match no domain (e.g., ADaM datasets) are
+/// reported in [`FolderValidation::skipped_files`]. The reports can be
+/// written with [`write_findings_csv`](crate::write_findings_csv) for QC.
+pub fn validate_xpt_folder(dir: &Path, ct_version: CtVersion) -> Result<FolderValidation> {
+    let ig_domains = tss_standards::load_sdtm_ig()?;
+    let ct = tss_standards::ct::load(ct_version, Some("SDTM"))?;
+    let rules = load_default_rules();
+
+    let mut result = FolderValidation::default();
+    let mut frames: BTreeMap<String, DataFrame> = BTreeMap::new();
+    for path in tss_ingest::list_xpt_files(dir)? {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_uppercase())
+            .unwrap_or_default();
+        if domain_for_dataset(&ig_domains, &name).is_none() {
+            result.skipped_files.push(path);
+            continue;
+        }
+        let (df, _) = tss_ingest::read_xpt(&path)?;
+        frames.insert(name, df);
+    }
+
+    let mut reports = validate_all_domains(
+        &ig_domains,
+        &frames,
+        Some(&ct),
+        Standard::Sdtm,
+        &BTreeMap::new(),
+        &rules,
+        false,
+    );
+    let pairs: Vec<(&str, &DataFrame)> = frames
+        .iter()
+        .map(|(name, df)| (name.as_str(), df))
+        .collect();
+    for (name, issues) in validate_cross_domain(&pairs, &rules) {
+        if let Some(report) = reports.get_mut(&name) {
+            report.issues.extend(issues);
+        }
+    }
+    result.reports = reports
+        .into_iter()
+        .map(|(name, mut report)| {
+            report.domain = name;
+            report
+        })
+        .collect();
+    Ok(result)
+}
+
+/// Process a study folder end to end.
+///
+/// Discovers CSV and SAS7BDAT files and `.xlsx` sheets in `study_dir`,
+/// matches each to an SDTM-IG domain by file or sheet name suffix (e.g.,
+/// `STUDY_AE.csv`, `STUDY_DS_EOT.csv`, sheet `DM`), auto-accepts
+/// mapping suggestions above `config.min_confidence`, applies the inferred
+/// normalization pipeline, validates each domain plus cross-domain references,
+/// and writes the requested formats to `output_dir`.
+///
+/// Files that do not match a domain (e.g., `Items.csv`, `CodeLists.csv`) are
+/// reported in [`SubmissionResult::skipped_files`]. When several files match
+/// the same domain, the first in file name order is used.
+///
+/// The run checks `config.cancellation` before each domain, each output
+/// format, and each batch of XPT rows. Once cancelled it returns what was
+/// finished so far, with [`SubmissionResult::cancelled`] set and no further
+/// exports. XPT datasets written before the one being converted stay on
+/// disk but are not listed in [`SubmissionResult::outputs`].
+///
+/// Use [`run_submission_with_progress`] to receive progress events.
+pub fn run_submission(
+    study_dir: &Path,
+    output_dir: &Path,
+    config: &SubmissionConfig,
+) -> Result<SubmissionResult> {
+    run_submission_with_progress(study_dir, output_dir, config, &|_| {})
+}
+
+/// Process a study folder end to end, reporting progress.
+///
+/// Same as [`run_submission`], but each domain reports
+/// [`ProgressStage::Processing`] events to `progress` as it is processed (DM
+/// first, then by domain code), followed by [`ProgressStage::XptExport`]
+/// events per dataset when XPT output is requested.
+pub fn run_submission_with_progress(
+    study_dir: &Path,
+    output_dir: &Path,
+    config: &SubmissionConfig,
+    progress: &dyn Fn(ProgressEvent),
+) -> Result<SubmissionResult> {
+    let ig_domains = tss_standards::load_sdtm_ig()?;
+    let ct = tss_standards::ct::load(config.ct_version, Some("SDTM"))?;
+
+    let mut result = SubmissionResult::default();
+    let mut sources: BTreeMap<String, (SourceEntry, &SdtmDomain)> = BTreeMap::new();
+    for entry in tss_ingest::list_source_entries(study_dir, true)? {
+        match match_domain(&entry, &ig_domains) {
+            Some(domain) if !sources.contains_key(&domain.name) => {
+                sources.insert(domain.name.clone(), (entry, domain));
+            }
+            _ if entry.sheet.is_some() => result.skipped_sheets.push(entry),
+            _ => result.skipped_files.push(entry.path),
+        }
+    }
+
+    // DM goes first so its RFSTDTC is available for study day derivation
+    let mut order: Vec<&String> = sources.keys().collect();
+    order.sort_by_key(|code| code.as_str() != "DM");
+
+    let mut frames: Vec<DomainFrame> = Vec::new();
+    let mut define_options = DefineXmlOptions::new(&config.ig_version, "Submission");
+    let mut dm_df: Option<DataFrame> = None;
+    for code in order {
+        if config.cancellation.is_cancelled() {
+            result.cancelled = true;
+            return Ok(result);
+        }
+        let (entry, domain) = &sources[code];
+        let (source_df, hints) = tss_ingest::read_source_entry(entry, config.header_rows)?;
+        progress(ProgressEvent::DomainStarted {
+            stage: ProgressStage::Processing,
+            domain: code.clone(),
+            total_rows: source_df.height(),
+        });
+
+        let source_columns: Vec<String> = source_df
+            .get_column_names()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect();
+        let mut mapping = MappingState::new(
+            (*domain).clone(),
+            &config.study_id,
+            &source_columns,
+            hints,
+            config.min_confidence,
+        );
+        let suggested: Vec<String> = mapping.all_suggestions().keys().cloned().collect();
+        for variable in suggested {
+            // Suggestions can compete for the same column; the first one wins
+            let _ = mapping.accept_suggestion(&variable);
+        }
+
+        let df = build_preview_dataframe_for_mapping_with_study_day(
+            &source_df,
+            &mapping,
+            dm_df.as_ref(),
+            &config.study_day,
+            Some(&ct),
+        )?;
+
+        progress(ProgressEvent::RowsProcessed {
+            stage: ProgressStage::Processing,
+            domain: code.clone(),
+            rows: df.height(),
+        });
+
+        let validation = validate_domain(domain, &df, Some(&ct));
+        if code == "DM" {
+            dm_df = Some(df.clone());
+        }
+        result.domains.push(DomainSubmission {
+            code: code.clone(),
+            source: entry.path.clone(),
+            sheet: entry.sheet.clone(),
+            mapping: mapping.summary(),
+            validation,
+        });
+        frames.push(DomainFrame::new(code.clone(), df));
+        define_options = define_options.with_mapping(code, &mapping);
+        progress(ProgressEvent::DomainFinished {
+            stage: ProgressStage::Processing,
+            domain: code.clone(),
+        });
+    }
+
+    let pairs: Vec<(&str, &DataFrame)> = frames
+        .iter()
+        .map(|f| (f.domain_code.as_str(), &f.data))
+        .collect();
+    for (code, issues) in validate_cross_domain(&pairs, &load_default_rules()) {
+        if let Some(entry) = result.domains.iter_mut().find(|d| d.code == code) {
+            entry.validation.issues.extend(issues);
+        }
+    }
+    result.domains.sort_by(|a, b| a.code.cmp(&b.code));
+
+    if frames.is_empty() {
+        return Ok(result);
+    }
+    result.warnings = frames
+        .iter()
+        .flat_map(DomainFrame::validate_dataset_name)
+        .collect();
+    let xpt_options = config
+        .xpt_options
+        .clone()
+        .with_cancellation(config.cancellation.clone());
+    for format in &config.formats {
+        if config.cancellation.is_cancelled() {
+            result.cancelled = true;
+            return Ok(result);
+        }
+        if config.dry_run {
+            result.planned.extend(plan_format(
+                *format,
+                output_dir,
+                &ig_domains,
+                &frames,
+                &xpt_options,
+            )?);
+            continue;
+        }
+        match format {
+            OutputFormat::Xpt => {
+                match crate::export::write_xpt_outputs_with_progress(
+                    output_dir,
+                    &ig_domains,
+                    &frames,
+                    &xpt_options,
+                    progress,
+                ) {
+                    Ok(paths) => result.outputs.extend(paths),
+                    Err(SubmitError::Cancelled) => {
+                        result.cancelled = true;
+                        return Ok(result);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            OutputFormat::DatasetXml => {
+                let paths = crate::export::write_dataset_xml_outputs(
+                    output_dir,
+                    &ig_domains,
+                    &frames,
+                    &config.study_id,
+                    &config.ig_version,
+                )?;
+                result.outputs.extend(paths);
+            }
+            OutputFormat::DefineXml => {
+                let spec = crate::export::build_define_spec(
+                    &config.study_id,
+                    &ig_domains,
+                    &frames,
+                    &define_options,
+                )?;
+                let xml_path = output_dir.join("define.xml");
+                crate::export::write_define_spec(&xml_path, &spec)?;
+                result.outputs.push(xml_path);
+                let html_path = output_dir.join("define.html");
+                crate::export::write_define_html(&html_path, &spec)?;
+                result.outputs.push(html_path);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Plan the files one output format would write.
+fn plan_format(
+    format: OutputFormat,
+    output_dir: &Path,
+    domains: &[SdtmDomain],
+    frames: &[DomainFrame],
+    xpt_options: &XptOptions,
+) -> Result<Vec<PlannedOutput>> {
+    match format {
+        OutputFormat::Xpt => {
+            crate::export::plan_xpt_outputs(output_dir, domains, frames, xpt_options)
+        }
+        OutputFormat::DatasetXml => {
+            crate::export::plan_dataset_xml_outputs(output_dir, domains, frames)
+        }
+        OutputFormat::DefineXml => Ok(["define.xml", "define.html"]
+            .into_iter()
+            .map(|name| PlannedOutput {
+                path: output_dir.join(name),
+                dataset: String::new(),
+                columns: Vec::new(),
+                records: 0,
+                estimated_bytes: None,
+            })
+            .collect()),
+    }
+}
+
+/// Match a source file or sheet to an IG domain using the underscore-separated
+/// tokens of its name, scanning from the end (`STUDY_DS_EOT` matches DS).
+fn match_domain<'a>(entry: &SourceEntry, domains: &'a [SdtmDomain]) -> Option<&'a SdtmDomain> {
+    entry
+        .name()
+        .rsplit('_')
+        .find_map(|token| domains.iter().find(|d| d.name.eq_ignore_ascii_case(token)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::Issue;
+
+    fn mock_study_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../mockdata/DEMO_GDISC_20240903_072908")
+    }
+
+    #[test]
+    fn test_match_domain() {
+        let domains = tss_standards::load_sdtm_ig().unwrap();
+        let code =
+            |name: &str| match_domain(&SourceEntry::file(name), &domains).map(|d| d.name.as_str());
+
+        assert_eq!(code("DEMO_20240903_AE.csv"), Some("AE"));
+        assert_eq!(code("DEMO_20240903_DS_EOT.csv"), Some("DS"));
+        assert_eq!(code("DEMO_20240903_LB.sas7bdat"), Some("LB"));
+
+        let sheet = |name: &str| {
+            match_domain(&SourceEntry::sheet("study.xlsx", name), &domains).map(|d| d.name.as_str())
+        };
+        assert_eq!(sheet("DM"), Some("DM"));
+        assert_eq!(sheet("Study_VS"), Some("VS"));
+        assert_eq!(sheet("Notes"), None);
+        assert_eq!(code("DEMO_20240903_Items.csv"), None);
+        assert_eq!(code("DEMO_20240903_CodeLists.csv"), None);
+    }
+
+    #[test]
+    fn test_run_submission_mock_study() {
+        let output = tempfile::tempdir().unwrap();
+        let config = SubmissionConfig::new("DEMO_GDISC");
+
+        let result = run_submission(&mock_study_dir(), output.path(), &config).unwrap();
+
+        let dm = result.domain("DM").expect("DM should be processed");
+        assert!(dm.mapping.mapped > 0);
+        assert_eq!(dm.validation.domain, "DM");
+        assert!(result.domain("AE").is_some());
+        assert!(
+            result
+                .skipped_files
+                .iter()
+                .any(|p| p.to_string_lossy().ends_with("_Items.csv"))
+        );
+
+        let dm_xpt = output.path().join("xpt").join("dm.xpt");
+        assert!(result.outputs.contains(&dm_xpt));
+        // IG domain codes are valid dataset names
+        assert!(result.warnings.is_empty());
+        assert!(dm_xpt.exists());
+        assert_eq!(
+            result.outputs.len(),
+            result.domains.len(),
+            "one XPT per processed domain"
+        );
+    }
+
+    #[test]
+    fn test_run_submission_dry_run_writes_nothing() {
+        let study = tempfile::tempdir().unwrap();
+        std::fs::write(
+            study.path().join("STUDY_DM.csv"),
+            "SUBJID,SEX,AGE\n001,F,34\n002,M,58\n",
+        )
+        .unwrap();
+        std::fs::write(
+            study.path().join("STUDY_AE.csv"),
+            "SUBJID,AETERM\n001,HEADACHE\n001,NAUSEA\n002,RASH\n",
+        )
+        .unwrap();
+        let output = tempfile::tempdir().unwrap();
+        let config = SubmissionConfig::new("STUDY")
+            .with_header_rows(1)
+            .with_formats([OutputFormat::Xpt, OutputFormat::DefineXml])
+            .with_dry_run(true);
+
+        let result = run_submission(study.path(), output.path(), &config).unwrap();
+
+        assert!(result.outputs.is_empty());
+        assert_eq!(std::fs::read_dir(output.path()).unwrap().count(), 0);
+        let paths: Vec<PathBuf> = result.planned.iter().map(|p| p.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                output.path().join("xpt").join("ae.xpt"),
+                output.path().join("xpt").join("dm.xpt"),
+                output.path().join("define.xml"),
+                output.path().join("define.html"),
+            ]
+        );
+        let ae = &result.planned[0];
+        assert_eq!(ae.records, 3);
+        assert!(ae.columns.iter().any(|(name, _)| name == "AETERM"));
+        assert!(ae.estimated_bytes.is_some());
+    }
+
+    #[test]
+    fn test_run_submission_progress_events_in_order() {
+        let study = tempfile::tempdir().unwrap();
+        std::fs::write(
+            study.path().join("STUDY_DM.csv"),
+            "SUBJID,SEX,AGE\n001,F,34\n002,M,58\n",
+        )
+        .unwrap();
+        std::fs::write(
+            study.path().join("STUDY_AE.csv"),
+            "SUBJID,AETERM\n001,HEADACHE\n001,NAUSEA\n002,RASH\n",
+        )
+        .unwrap();
+        let output = tempfile::tempdir().unwrap();
+        let config = SubmissionConfig::new("STUDY")
+            .with_header_rows(1)
+            .with_formats([OutputFormat::Xpt]);
+
+        let events = std::cell::RefCell::new(Vec::new());
+        run_submission_with_progress(study.path(), output.path(), &config, &|event| {
+            events.borrow_mut().push(event)
+        })
+        .unwrap();
+
+        let expected = |stage, domain: &str, rows| {
+            [
+                ProgressEvent::DomainStarted {
+                    stage,
+                    domain: domain.to_string(),
+                    total_rows: rows,
+                },
+                ProgressEvent::RowsProcessed {
+                    stage,
+                    domain: domain.to_string(),
+                    rows,
+                },
+                ProgressEvent::DomainFinished {
+                    stage,
+                    domain: domain.to_string(),
+                },
+            ]
+        };
+        let mut order = Vec::new();
+        order.extend(expected(ProgressStage::Processing, "DM", 2));
+        order.extend(expected(ProgressStage::Processing, "AE", 3));
+        order.extend(expected(ProgressStage::XptExport, "AE", 3));
+        order.extend(expected(ProgressStage::XptExport, "DM", 2));
+        assert_eq!(events.into_inner(), order);
+    }
+
+    #[test]
+    fn test_run_submission_cancelled_mid_run() {
+        let study = tempfile::tempdir().unwrap();
+        for (name, content) in [
+            ("STUDY_DM.csv", "SUBJID,SEX\n001,F\n"),
+            ("STUDY_AE.csv", "SUBJID,AETERM\n001,HEADACHE\n"),
+            ("STUDY_VS.csv", "SUBJID,VSTESTCD\n001,WEIGHT\n"),
+        ] {
+            std::fs::write(study.path().join(name), content).unwrap();
+        }
+        let output = tempfile::tempdir().unwrap();
+        let token = CancellationToken::new();
+        let config = SubmissionConfig::new("STUDY")
+            .with_header_rows(1)
+            .with_cancellation(token.clone());
+
+        // Cancel as soon as the first domain is done
+        let result = run_submission_with_progress(study.path(), output.path(), &config, &|event| {
+            if matches!(event, ProgressEvent::DomainFinished { .. }) {
+                token.cancel();
+            }
+        })
+        .unwrap();
+
+        assert!(result.cancelled);
+        let codes: Vec<&str> = result.domains.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, ["DM"]);
+        assert!(result.outputs.is_empty());
+        assert!(!output.path().join("xpt").exists());
+    }
+
+    #[test]
+    fn test_run_submission_cancelled_during_xpt_export() {
+        let study = tempfile::tempdir().unwrap();
+        std::fs::write(study.path().join("STUDY_DM.csv"), "SUBJID,SEX\n001,F\n").unwrap();
+        let output = tempfile::tempdir().unwrap();
+        let token = CancellationToken::new();
+        let config = SubmissionConfig::new("STUDY")
+            .with_header_rows(1)
+            .with_cancellation(token.clone());
+
+        // Cancel while the DM rows are converted
+        let result = run_submission_with_progress(study.path(), output.path(), &config, &|event| {
+            if matches!(
+                event,
+                ProgressEvent::DomainStarted {
+                    stage: ProgressStage::XptExport,
+                    ..
+                }
+            ) {
+                token.cancel();
+            }
+        })
+        .unwrap();
+
+        assert!(result.cancelled);
+        assert_eq!(result.domains.len(), 1);
+        assert!(result.outputs.is_empty());
+        assert!(!output.path().join("xpt/dm.xpt").exists());
+    }
+
+    #[test]
+    fn test_validate_xpt_folder() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/xpt");
+        let result = validate_xpt_folder(&dir, CtVersion::default()).unwrap();
+
+        let domains: Vec<&str> = result.reports.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(domains, ["AE", "DM"]);
+        assert_eq!(result.skipped_files, [dir.join("adsl.xpt")]);
+
+        // CDISC01-009 has an AE record but no DM record
+        let ae = result.report("AE").unwrap();
+        assert!(ae.issues.iter().any(|issue| matches!(
+            issue,
+            Issue::UsubjidNotInDm {
+                missing_count: 1,
+                ..
+            }
+        )));
+        let dm = result.report("DM").unwrap();
+        assert!(
+            !dm.issues
+                .iter()
+                .any(|issue| matches!(issue, Issue::UsubjidNotInDm { .. }))
+        );
+    }
+
+    #[test]
+    fn test_validate_xpt_folder_split_datasets() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/xpt");
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(fixtures.join("dm.xpt"), dir.path().join("dm.xpt")).unwrap();
+        std::fs::copy(fixtures.join("ae.xpt"), dir.path().join("aeco.xpt")).unwrap();
+
+        let result = validate_xpt_folder(dir.path(), CtVersion::default()).unwrap();
+
+        let domains: Vec<&str> = result.reports.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(domains, ["AECO", "DM"]);
+        assert!(result.skipped_files.is_empty());
+        let split = result.report("AECO").unwrap();
+        assert!(
+            split
+                .issues
+                .iter()
+                .any(|issue| matches!(issue, Issue::UsubjidNotInDm { .. }))
+        );
+    }
+}
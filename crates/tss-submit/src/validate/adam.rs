@@ -0,0 +1,491 @@
+//! ADaM dataset structure checks (ADaMIG v1.3).
+//!
+//! Checks the rules that depend on the dataset structure rather than on
+//! individual values: Required variables for the structure, one record per
+//! subject in ADSL, and the parameter identifiers of BDS datasets. The
+//! traceability check follows SRCDOM/SRCSEQ back to the SDTM records an
+//! analysis record was derived from.
+
+use std::collections::{HashMap, HashSet};
+
+use polars::prelude::DataFrame;
+use tss_standards::{AdamDataset, AdamVariable, AdamVariableSource, CoreDesignation};
+
+use super::column_reader::ColumnReader;
+use super::issue::Issue;
+use super::report::ValidationReport;
+use super::rules::RuleRegistry;
+use crate::export::DomainFrame;
+
+/// Maximum number of sample subjects reported.
+const MAX_SAMPLES: usize = 5;
+
+/// BDS variables that identify the analysis parameter of each record.
+const BDS_PARAMETER_VARIABLES: &[&str] = &["PARAMCD", "PARAM"];
+
+/// Validate an ADaM dataset against the rules for its structure.
+///
+/// `dataset` is the ADaMIG structure the data follows (e.g., the ADSL or BDS
+/// definition from [`load_adam_ig`](tss_standards::load_adam_ig)); the report
+/// is named after it. Runs:
+/// - Required variable presence and population, with templated names such
+///   as TRTxxP matched by any digits (TRT01P)
+/// - ADSL: one record per subject
+/// - BDS and TTE: PARAMCD and PARAM present and populated on every record
+pub fn validate_adam_dataset(
+    dataset: &AdamDataset,
+    df: &DataFrame,
+    rules: &RuleRegistry,
+) -> ValidationReport {
+    let mut report = ValidationReport::new(&dataset.name).with_rules(rules.clone());
+    let reader = ColumnReader::new(df);
+    let columns: Vec<String> = df
+        .get_column_names_owned()
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    for issue in check_required(dataset, &reader, &columns) {
+        report.add(issue);
+    }
+    if dataset.is_adsl()
+        && let Some(issue) = check_one_record_per_subject(dataset, &reader, &columns)
+    {
+        report.add(issue);
+    }
+    if dataset.is_bds() {
+        for issue in check_parameter_identifiers(dataset, &reader, &columns) {
+            report.add(issue);
+        }
+    }
+
+    report
+}
+
+/// Validate the traceability variables of an ADaM dataset.
+///
+/// Per ADaMIG 3.3.9, SRCDOM and SRCSEQ identify the SDTM record an analysis
+/// record was derived from. Each populated SRCDOM must name one of
+/// `sdtm_frames`, and each populated SRCSEQ must match the domain's --SEQ
+/// for the record's USUBJID. Split datasets (e.g., LBCH) are found under
+/// their parent domain code, with the parent's --SEQ. Broken references are reported as errors;
+/// datasets without SRCDOM pass.
+pub fn validate_adam_traceability(
+    dataset: &AdamDataset,
+    df: &DataFrame,
+    sdtm_frames: &[(&str, &DataFrame)],
+    rules: &RuleRegistry,
+) -> ValidationReport {
+    let mut report = ValidationReport::new(&dataset.name).with_rules(rules.clone());
+    let context = TraceabilityContext::new(sdtm_frames);
+    for issue in check_traceability(&ColumnReader::new(df), &context) {
+        report.add(issue);
+    }
+    report
+}
+
+/// SDTM records that ADaM traceability variables can point to.
+///
+/// Like the RELREC context, keyed by domain code, but each --SEQ value is
+/// indexed together with its subject, since --SEQ is only unique within a
+/// USUBJID. Split datasets are indexed under both their own name and their
+/// parent domain code, which collects the records of all its splits.
+struct TraceabilityContext {
+    /// Domain code -> (USUBJID, --SEQ) pairs
+    domains: HashMap<String, HashSet<(String, String)>>,
+}
+
+impl TraceabilityContext {
+    fn new(sdtm_frames: &[(&str, &DataFrame)]) -> Self {
+        let mut domains: HashMap<String, HashSet<(String, String)>> = HashMap::new();
+        for (name, df) in sdtm_frames {
+            let dataset = name.to_uppercase();
+            let frame = DomainFrame::new(dataset.clone(), (*df).clone());
+            let domain = frame.base_domain_code().to_string();
+            let reader = ColumnReader::new(df);
+            let seq_variable = format!("{domain}SEQ");
+            let records: HashSet<(String, String)> = (0..reader.height())
+                .map(|idx| {
+                    (
+                        reader.get_string("USUBJID", idx).trim().to_string(),
+                        reader.get_string(&seq_variable, idx).trim().to_string(),
+                    )
+                })
+                .filter(|(subject, seq)| !subject.is_empty() && !seq.is_empty())
+                .collect();
+            if dataset != domain {
+                domains
+                    .entry(dataset)
+                    .or_default()
+                    .extend(records.iter().cloned());
+            }
+            domains.entry(domain).or_default().extend(records);
+        }
+        Self { domains }
+    }
+}
+
+/// Check SRCDOM names a known domain and SRCSEQ an existing record.
+fn check_traceability(reader: &ColumnReader, context: &TraceabilityContext) -> Vec<Issue> {
+    if !reader.has_column("SRCDOM") {
+        return Vec::new();
+    }
+    let mut unknown_domains = (0u64, Vec::new());
+    let mut missing_records = (0u64, Vec::new());
+    for idx in 0..reader.height() {
+        let domain = reader.get_string("SRCDOM", idx).trim().to_uppercase();
+        if domain.is_empty() {
+            continue;
+        }
+        let subject = reader.get_string("USUBJID", idx).trim().to_string();
+        let Some(records) = context.domains.get(&domain) else {
+            unknown_domains.0 += 1;
+            if unknown_domains.1.len() < MAX_SAMPLES {
+                unknown_domains.1.push(format!("{subject} {domain}"));
+            }
+            continue;
+        };
+        let seq = reader.get_string("SRCSEQ", idx).trim().to_string();
+        if seq.is_empty() || records.contains(&(subject.clone(), seq.clone())) {
+            continue;
+        }
+        missing_records.0 += 1;
+        if missing_records.1.len() < MAX_SAMPLES {
+            missing_records.1.push(format!("{subject} {domain}/{seq}"));
+        }
+    }
+
+    [("SRCDOM", unknown_domains), ("SRCSEQ", missing_records)]
+        .into_iter()
+        .filter(|(_, (count, _))| *count > 0)
+        .map(
+            |(variable, (invalid_count, samples))| Issue::TraceabilityBroken {
+                variable: variable.to_string(),
+                invalid_count,
+                samples,
+            },
+        )
+        .collect()
+}
+
+/// Check Required variables are present and populated.
+///
+/// A templated variable is satisfied by every matching column (TRT01P and
+/// TRT02P for TRTxxP), each of which must be populated.
+fn check_required(dataset: &AdamDataset, reader: &ColumnReader, columns: &[String]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for variable in &dataset.variables {
+        if variable.core != Some(CoreDesignation::Required) {
+            continue;
+        }
+        let matching: Vec<&String> = columns
+            .iter()
+            .filter(|column| matches_template(&variable.name, column))
+            .collect();
+        if matching.is_empty() {
+            issues.push(Issue::RequiredMissing {
+                variable: variable.name.clone(),
+            });
+        }
+        for column in matching {
+            issues.extend(check_populated(reader, column));
+        }
+    }
+    issues
+}
+
+/// Check that ADSL has one record per subject.
+///
+/// Subjects are keyed by the variable copied from DM.USUBJID, which is
+/// USUBJID unless the structure sources it otherwise. Records without a
+/// subject are left to the Required variable check.
+fn check_one_record_per_subject(
+    dataset: &AdamDataset,
+    reader: &ColumnReader,
+    columns: &[String],
+) -> Option<Issue> {
+    let subject_variable = dataset
+        .variables
+        .iter()
+        .find(|variable| is_copied_from(variable, "DM.USUBJID"))
+        .map_or("USUBJID", |variable| variable.name.as_str());
+    let column = columns
+        .iter()
+        .find(|column| column.eq_ignore_ascii_case(subject_variable))?;
+
+    let mut records: HashMap<String, u64> = HashMap::new();
+    let mut order = Vec::new();
+    for (_, subject) in reader.values(column)? {
+        let subject = subject.trim();
+        if subject.is_empty() {
+            continue;
+        }
+        let count = records.entry(subject.to_string()).or_insert(0);
+        if *count == 0 {
+            order.push(subject.to_string());
+        }
+        *count += 1;
+    }
+
+    let mut duplicate_count = 0u64;
+    let mut samples = Vec::new();
+    for subject in order {
+        let count = records[&subject];
+        if count < 2 {
+            continue;
+        }
+        duplicate_count += count - 1;
+        if samples.len() < MAX_SAMPLES {
+            samples.push(subject);
+        }
+    }
+
+    (duplicate_count > 0).then_some(Issue::DuplicateSubject {
+        duplicate_count,
+        samples,
+    })
+}
+
+/// Check that every BDS record identifies its analysis parameter.
+///
+/// PARAMCD and PARAM are Required in all BDS datasets, so they are checked
+/// even when the dataset definition at hand does not list them as Required
+/// (those that do are covered by the Required variable check).
+fn check_parameter_identifiers(
+    dataset: &AdamDataset,
+    reader: &ColumnReader,
+    columns: &[String],
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for variable in BDS_PARAMETER_VARIABLES {
+        let listed = dataset.variables.iter().any(|v| {
+            v.core == Some(CoreDesignation::Required) && v.name.eq_ignore_ascii_case(variable)
+        });
+        if listed {
+            continue;
+        }
+        match columns
+            .iter()
+            .find(|column| column.eq_ignore_ascii_case(variable))
+        {
+            Some(column) => issues.extend(check_populated(reader, column)),
+            None => issues.push(Issue::RequiredMissing {
+                variable: (*variable).to_string(),
+            }),
+        }
+    }
+    issues
+}
+
+/// Report nulls in a column that must be populated.
+///
+/// An entirely empty column is reported as all-null, as for SDTM.
+fn check_populated(reader: &ColumnReader, column: &str) -> Option<Issue> {
+    let row_count = reader.height() as u64;
+    let null_count = reader.count_nulls(column);
+    if null_count == row_count && row_count > 0 {
+        Some(Issue::RequiredAllNull {
+            variable: column.to_string(),
+            row_count,
+        })
+    } else if null_count > 0 {
+        Some(Issue::RequiredEmpty {
+            variable: column.to_string(),
+            null_count,
+        })
+    } else {
+        None
+    }
+}
+
+/// Whether a variable is copied unchanged from an SDTM variable (e.g., DM.USUBJID).
+fn is_copied_from(variable: &AdamVariable, sdtm_variable: &str) -> bool {
+    matches!(&variable.source, Some(AdamVariableSource::Sdtm(source)) if source.eq_ignore_ascii_case(sdtm_variable))
+}
+
+/// Whether a column name matches an ADaMIG variable name.
+///
+/// Lowercase placeholders in the template (xx, y, zz, w) each stand for one
+/// digit; all other characters match case-insensitively.
+pub(crate) fn matches_template(template: &str, column: &str) -> bool {
+    template.len() == column.len()
+        && template.chars().zip(column.chars()).all(|(t, c)| {
+            if t.is_ascii_lowercase() {
+                c.is_ascii_digit()
+            } else {
+                t.eq_ignore_ascii_case(&c)
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::load_default_rules;
+    use polars::prelude::*;
+
+    fn structure(name: &str) -> AdamDataset {
+        tss_standards::load_adam_ig()
+            .unwrap()
+            .into_iter()
+            .find(|dataset| dataset.name == name)
+            .unwrap()
+    }
+
+    fn adsl(subjects: &[&str]) -> DataFrame {
+        let n = subjects.len();
+        df! {
+            "STUDYID" => vec!["STUDY"; n],
+            "USUBJID" => subjects,
+            "SUBJID" => subjects.iter().map(|s| s.trim_start_matches("S-")).collect::<Vec<_>>(),
+            "SITEID" => vec!["01"; n],
+            "AGE" => vec![42i64; n],
+            "AGEU" => vec!["YEARS"; n],
+            "SEX" => vec!["F"; n],
+            "RACE" => vec!["ASIAN"; n],
+            "ARM" => vec!["Placebo"; n],
+            "TRT01P" => vec!["Placebo"; n],
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn test_compliant_adsl() {
+        let report = validate_adam_dataset(
+            &structure("ADSL"),
+            &adsl(&["S-001", "S-002", "S-003"]),
+            &load_default_rules(),
+        );
+        assert!(report.is_empty(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_non_compliant_adsl() {
+        let df = adsl(&["S-001", "S-002", "S-001", "S-001"])
+            .drop("TRT01P")
+            .unwrap();
+        let report = validate_adam_dataset(&structure("ADSL"), &df, &load_default_rules());
+
+        assert!(report.issues.iter().any(|issue| matches!(
+            issue,
+            Issue::DuplicateSubject { duplicate_count: 2, samples } if samples == &["S-001"]
+        )));
+        assert!(report.issues.iter().any(|issue| matches!(
+            issue,
+            Issue::RequiredMissing { variable } if variable == "TRTxxP"
+        )));
+        assert_eq!(report.len(), 2);
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_bds_requires_parameter() {
+        let df = df! {
+            "STUDYID" => &["STUDY", "STUDY"],
+            "USUBJID" => &["S-001", "S-001"],
+            "PARAM" => &[Some("Weight (kg)"), None],
+            "AVAL" => &[70.0, 71.0],
+        }
+        .unwrap();
+        let report = validate_adam_dataset(&structure("BDS"), &df, &load_default_rules());
+
+        let missing: Vec<&str> = report
+            .issues
+            .iter()
+            .filter_map(|issue| match issue {
+                Issue::RequiredMissing { variable } => Some(variable.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(missing, ["PARAMCD"]);
+        assert!(report.issues.iter().any(|issue| matches!(
+            issue,
+            Issue::RequiredEmpty { variable, null_count: 1 } if variable == "PARAM"
+        )));
+    }
+
+    #[test]
+    fn test_traceability_dangling_srcseq() {
+        let ae = df! {
+            "USUBJID" => &["S-001", "S-001", "S-002"],
+            "AESEQ" => &[1i64, 2, 1],
+        }
+        .unwrap();
+        let adae = df! {
+            "USUBJID" => &["S-001", "S-002", "S-002"],
+            "SRCDOM" => &["AE", "AE", ""],
+            "SRCVAR" => &["AETERM", "AETERM", ""],
+            "SRCSEQ" => &[Some(2.0), Some(3.0), None],
+        }
+        .unwrap();
+
+        let report = validate_adam_traceability(
+            &structure("BDS"),
+            &adae,
+            &[("AE", &ae)],
+            &load_default_rules(),
+        );
+
+        assert_eq!(report.len(), 1);
+        assert!(report.has_errors());
+        assert!(matches!(
+            &report.issues[0],
+            Issue::TraceabilityBroken { variable, invalid_count: 1, samples }
+                if variable == "SRCSEQ" && samples == &["S-002 AE/3"]
+        ));
+    }
+
+    #[test]
+    fn test_traceability_split_dataset() {
+        let lbch = df! {
+            "USUBJID" => &["S-001", "S-001"],
+            "LBSEQ" => &[1i64, 2],
+        }
+        .unwrap();
+        let lbhe = df! {
+            "USUBJID" => &["S-001"],
+            "LBSEQ" => &[3i64],
+        }
+        .unwrap();
+        let adlb = df! {
+            "USUBJID" => &["S-001", "S-001", "S-001"],
+            "SRCDOM" => &["LB", "LB", "LBCH"],
+            "SRCSEQ" => &[2i64, 3, 1],
+        }
+        .unwrap();
+
+        let report = validate_adam_traceability(
+            &structure("BDS"),
+            &adlb,
+            &[("LBCH", &lbch), ("LBHE", &lbhe)],
+            &load_default_rules(),
+        );
+
+        assert!(report.is_empty(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_traceability_unknown_srcdom() {
+        let adae = df! {
+            "USUBJID" => &["S-001"],
+            "SRCDOM" => &["CM"],
+            "SRCSEQ" => &[1i64],
+        }
+        .unwrap();
+        let report =
+            validate_adam_traceability(&structure("BDS"), &adae, &[], &load_default_rules());
+
+        assert!(matches!(
+            &report.issues[..],
+            [Issue::TraceabilityBroken { variable, invalid_count: 1, .. }] if variable == "SRCDOM"
+        ));
+    }
+
+    #[test]
+    fn test_matches_template() {
+        assert!(matches_template("TRTxxP", "TRT01P"));
+        assert!(matches_template("TRTxxP", "trt12p"));
+        assert!(!matches_template("TRTxxP", "TRTAP"));
+        assert!(matches_template("USUBJID", "usubjid"));
+    }
+}
@@ -0,0 +1,130 @@
+//! ASCII character validation for XPT output.
+//!
+//! XPT V5 is effectively ASCII; non-ASCII bytes (e.g., an en dash pasted
+//! into a comment) or control characters can corrupt the written dataset.
+
+use polars::prelude::{AnyValue, DataFrame};
+use tss_standards::ColumnIndex;
+use tss_standards::any_to_string_for_output;
+use tss_standards::{SdtmDomain, VariableType};
+
+use super::super::issue::Issue;
+
+/// Maximum number of sample values reported per issue.
+const MAX_SAMPLES: usize = 5;
+
+/// Check that character variables contain only printable ASCII.
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for variable in &domain.variables {
+        if variable.data_type != VariableType::Char {
+            continue;
+        }
+        let Some(column) = columns.get(&variable.name) else {
+            continue;
+        };
+        let Ok(series) = df.column(column) else {
+            continue;
+        };
+
+        let mut invalid_count = 0u64;
+        let mut samples = Vec::new();
+        for idx in 0..df.height() {
+            let value = any_to_string_for_output(series.get(idx).unwrap_or(AnyValue::Null));
+            if !value.chars().any(is_invalid_char) {
+                continue;
+            }
+            invalid_count += 1;
+            if samples.len() < MAX_SAMPLES {
+                samples.push(escape_invalid(&value));
+            }
+        }
+
+        if invalid_count > 0 {
+            issues.push(Issue::NonAsciiText {
+                variable: variable.name.clone(),
+                invalid_count,
+                samples,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Check if a character can't be written to XPT as-is.
+fn is_invalid_char(c: char) -> bool {
+    !c.is_ascii() || c.is_ascii_control()
+}
+
+/// Escape offending characters so samples show what to fix (e.g., `\u{2013}`, `\t`).
+fn escape_invalid(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if is_invalid_char(c) {
+                c.escape_default().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+    use tss_standards::SdtmVariable;
+    use tss_standards::sdtm::test_support::{self, domain};
+
+    fn variable(name: &str, data_type: VariableType) -> SdtmVariable {
+        test_support::variable(name).data_type(data_type).build()
+    }
+
+    #[test]
+    fn test_non_ascii_and_control_characters() {
+        let domain = domain("CO")
+            .variables([
+                variable("COVAL", VariableType::Char),
+                variable("COREF", VariableType::Char),
+                variable("COSEQ", VariableType::Num),
+            ])
+            .build();
+        let df = df! {
+            "COVAL" => &[
+                "Dose 10\u{2013}20 mg",
+                "Patient said \u{201C}fine\u{201D}",
+                "Line one\tline two",
+                "Plain ASCII",
+            ],
+            "COREF" => &["AE", "AE", "CM", "CM"],
+            "COSEQ" => &[1.0, 2.0, 3.0, 4.0],
+        }
+        .unwrap();
+        let columns = ColumnIndex::new(&df);
+
+        let issues = check(&domain, &df, &columns);
+        assert_eq!(issues.len(), 1);
+        match &issues[0] {
+            Issue::NonAsciiText {
+                variable,
+                invalid_count,
+                samples,
+            } => {
+                assert_eq!(variable, "COVAL");
+                assert_eq!(*invalid_count, 3);
+                assert_eq!(
+                    samples,
+                    &[
+                        "Dose 10\\u{2013}20 mg",
+                        "Patient said \\u{201c}fine\\u{201d}",
+                        "Line one\\tline two",
+                    ]
+                );
+            }
+            other => panic!("unexpected issue: {other:?}"),
+        }
+    }
+}
@@ -6,15 +6,21 @@
 //! - CO/RELREC RDOMAIN references valid domains
 //! - RELSUB RSUBJID exists in DM and relationships are bidirectional
 //! - RELSPEC PARENT references valid REFID within subject
+//! - --SEQ values stay unique per subject across split datasets
+//! - SUPP-- records reference existing parent records with unique QNAMs
+//! - All domains carry the same, populated STUDYID
 //!
 //! These checks ensure data consistency across the submission package.
 
 use polars::prelude::DataFrame;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use super::super::column_reader::ColumnReader;
 use super::super::issue::Issue;
 
+/// Maximum number of sample rows reported per issue.
+const MAX_SAMPLES: usize = 5;
+
 /// Extract all USUBJIDs from the DM domain.
 ///
 /// Returns a set of unique, non-empty USUBJID values.
@@ -296,6 +302,19 @@ pub fn check_relspec(relspec_df: &DataFrame) -> Vec<Issue> {
 // RELREC VALIDATION
 // =============================================================================
 
+/// Key variables that can identify records of `domain` in RELREC.
+///
+/// Covers the domain-prefixed --SEQ, --GRPID, --REFID, --LNKID and --SPID
+/// variables, plus VISITNUM.
+pub fn relrec_key_variables(domain: &str) -> Vec<String> {
+    let prefix = domain.to_uppercase();
+    ["SEQ", "GRPID", "REFID", "LNKID", "SPID"]
+        .iter()
+        .map(|suffix| format!("{}{}", prefix, suffix))
+        .chain(std::iter::once("VISITNUM".to_string()))
+        .collect()
+}
+
 /// Context for RELREC validation containing domain data.
 pub struct RelrecContext<'a> {
     /// Map of domain code -> (DataFrame, key_variable -> Set of key values)
@@ -311,31 +330,8 @@ pub fn new(domain_list: &[(&str, &'a DataFrame)]) -> Self {
             let reader = ColumnReader::new(df);
             let mut key_values: HashMap<String, HashSet<String>> = HashMap::new();
 
-            // Extract common key variables: --SEQ, --GRPID, --REFID, --LNKID
-            let domain_prefix = name.to_uppercase();
-            let seq_var = format!("{}SEQ", domain_prefix);
-            let grpid_var = format!("{}GRPID", domain_prefix);
-            let refid_var = format!("{}REFID", domain_prefix);
-            let lnkid_var = format!("{}LNKID", domain_prefix);
-
-            for var in [&seq_var, &grpid_var, &refid_var, &lnkid_var] {
-                if let Some(values) = reader.values(var) {
-                    let mut set = HashSet::new();
-                    for (_, val) in values {
-                        let trimmed = val.trim();
-                        if !trimmed.is_empty() {
-                            set.insert(trimmed.to_string());
-                        }
-                    }
-                    if !set.is_empty() {
-                        key_values.insert(var.clone(), set);
-                    }
-                }
-            }
-
-            // Also check generic variable names (VISITNUM, etc.)
-            for var in ["VISITNUM"] {
-                if let Some(values) = reader.values(var) {
+            for var in relrec_key_variables(name) {
+                if let Some(values) = reader.values(&var) {
                     let mut set = HashSet::new();
                     for (_, val) in values {
                         let trimmed = val.trim();
@@ -344,7 +340,7 @@ pub fn new(domain_list: &[(&str, &'a DataFrame)]) -> Self {
                         }
                     }
                     if !set.is_empty() {
-                        key_values.insert(var.to_string(), set);
+                        key_values.insert(var, set);
                     }
                 }
             }
@@ -450,10 +446,246 @@ pub fn check_relrec(relrec_df: &DataFrame, context: &RelrecContext) -> Vec<Issue
         .collect()
 }
 
+// =============================================================================
+// SPLIT DATASET VALIDATION
+// =============================================================================
+
+/// Check --SEQ values stay unique per subject across split datasets.
+///
+/// Datasets whose name starts with a two-letter domain code and that carry
+/// that domain's --SEQ variable (e.g., LBCH and LBHE with LBSEQ) are grouped.
+/// A USUBJID/--SEQ pair already used by an earlier dataset of the group is
+/// reported on the later dataset; duplicates within one dataset are left to
+/// the per-domain sequence check.
+///
+/// # Returns
+/// A vector of (dataset_name, issue) pairs.
+pub fn check_split_sequences(domains: &[(&str, &DataFrame)]) -> Vec<(String, Issue)> {
+    // Base domain code -> datasets carrying its --SEQ variable
+    let mut groups: BTreeMap<String, Vec<(&str, &DataFrame)>> = BTreeMap::new();
+    for (name, df) in domains {
+        let Some(base) = name.get(..2).map(str::to_uppercase) else {
+            continue;
+        };
+        if df.column(&format!("{}SEQ", base)).is_ok() {
+            groups.entry(base).or_default().push((name, df));
+        }
+    }
+
+    let mut issues = Vec::new();
+    for (base, mut datasets) in groups {
+        if datasets.len() < 2 {
+            continue;
+        }
+        datasets.sort_by(|a, b| a.0.cmp(b.0));
+
+        let seq_var = format!("{}SEQ", base);
+        let mut seen: HashSet<(String, String)> = HashSet::new();
+        for (name, df) in datasets {
+            let pairs: HashSet<(String, String)> = ColumnReader::new(df)
+                .values_by_subject("USUBJID", &seq_var)
+                .into_iter()
+                .flat_map(|(subject, seqs)| {
+                    seqs.into_iter()
+                        .filter(|seq| !seq.is_empty())
+                        .map(move |seq| (subject.clone(), seq))
+                })
+                .collect();
+
+            let duplicate_count = pairs.iter().filter(|pair| seen.contains(*pair)).count() as u64;
+            if duplicate_count > 0 {
+                issues.push((
+                    name.to_string(),
+                    Issue::DuplicateSequence {
+                        variable: seq_var.clone(),
+                        duplicate_count,
+                    },
+                ));
+            }
+            seen.extend(pairs);
+        }
+    }
+
+    issues
+}
+
+// =============================================================================
+// STUDYID CONSISTENCY
+// =============================================================================
+
+/// Check every domain carries the same, populated STUDYID.
+///
+/// The study's STUDYID is the value used by most records across all
+/// domains (ties go to the alphabetically first value). Each domain with
+/// other or empty values gets one issue. Domains without a STUDYID column
+/// are left to the per-domain Required variable check.
+///
+/// # Returns
+/// A vector of (dataset_name, issue) pairs.
+pub fn check_studyid_consistency(domains: &[(&str, &DataFrame)]) -> Vec<(String, Issue)> {
+    // Dataset name -> (STUDYID value -> record count); "" for empty values
+    let mut values_by_domain: Vec<(&str, BTreeMap<String, u64>)> = Vec::new();
+    let mut totals: BTreeMap<String, u64> = BTreeMap::new();
+    for (name, df) in domains {
+        let reader = ColumnReader::new(df);
+        let Some(values) = reader.values("STUDYID") else {
+            continue;
+        };
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        for (_, value) in values {
+            *counts.entry(value.trim().to_string()).or_default() += 1;
+        }
+        for (value, count) in &counts {
+            if !value.is_empty() {
+                *totals.entry(value.clone()).or_default() += count;
+            }
+        }
+        values_by_domain.push((name, counts));
+    }
+
+    let expected = totals
+        .iter()
+        .rev()
+        .max_by_key(|(_, count)| **count)
+        .map(|(value, _)| value.clone());
+
+    let mut issues = Vec::new();
+    for (name, counts) in values_by_domain {
+        let empty_count = counts.get("").copied().unwrap_or(0);
+        let others: Vec<(&String, &u64)> = counts
+            .iter()
+            .filter(|(value, _)| !value.is_empty() && Some(*value) != expected.as_ref())
+            .collect();
+        if empty_count == 0 && others.is_empty() {
+            continue;
+        }
+        issues.push((
+            name.to_string(),
+            Issue::StudyidInconsistent {
+                domain: name.to_string(),
+                expected: expected.clone(),
+                found: others.iter().map(|(value, _)| (*value).clone()).collect(),
+                mismatch_count: others.iter().map(|(_, count)| **count).sum(),
+                empty_count,
+            },
+        ));
+    }
+    issues
+}
+
+// =============================================================================
+// SUPP-- VALIDATION
+// =============================================================================
+
+/// Check SUPP-- records point at existing parent records.
+///
+/// Per SDTM-IG 8.4, IDVAR and IDVARVAL identify the parent record of the
+/// same USUBJID in RDOMAIN. Subject-level records (blank IDVAR and IDVARVAL)
+/// are skipped.
+///
+/// # Arguments
+/// * `domain_name` - Name of the SUPP-- dataset (e.g., "SUPPAE")
+/// * `supp_df` - SUPP-- DataFrame
+/// * `context` - Context containing all domain data
+pub fn check_supp_parent(
+    domain_name: &str,
+    supp_df: &DataFrame,
+    context: &RelrecContext,
+) -> Vec<Issue> {
+    let reader = ColumnReader::new(supp_df);
+
+    // (USUBJID, value) pairs of each parent key variable, built on first use
+    let mut parent_keys: HashMap<(String, String), HashSet<(String, String)>> = HashMap::new();
+
+    let mut invalid_count = 0u64;
+    let mut samples = Vec::new();
+
+    for row_idx in 0..reader.height() {
+        let rdomain = reader.get_string("RDOMAIN", row_idx).trim().to_uppercase();
+        let usubjid = reader.get_string("USUBJID", row_idx).trim().to_string();
+        let idvar = reader.get_string("IDVAR", row_idx).trim().to_uppercase();
+        let idvarval = reader.get_string("IDVARVAL", row_idx).trim().to_string();
+
+        // Subject-level qualifier
+        if idvar.is_empty() && idvarval.is_empty() {
+            continue;
+        }
+
+        let keys = parent_keys
+            .entry((rdomain.clone(), idvar.clone()))
+            .or_insert_with(|| {
+                let Some((parent_df, _)) = context.domains.get(&rdomain) else {
+                    return HashSet::new();
+                };
+                let parent = ColumnReader::new(parent_df);
+                match (parent.values("USUBJID"), parent.values(&idvar)) {
+                    (Some(subjects), Some(values)) => subjects
+                        .zip(values)
+                        .map(|((_, u), (_, v))| (u.trim().to_string(), v.trim().to_string()))
+                        .collect(),
+                    _ => HashSet::new(),
+                }
+            });
+
+        if !keys.contains(&(usubjid.clone(), idvarval.clone())) {
+            invalid_count += 1;
+            if samples.len() < MAX_SAMPLES {
+                samples.push(format!("{}:{}={}", usubjid, idvar, idvarval));
+            }
+        }
+    }
+
+    if invalid_count > 0 {
+        vec![Issue::SuppInvalidParent {
+            domain: domain_name.to_uppercase(),
+            invalid_count,
+            samples,
+        }]
+    } else {
+        vec![]
+    }
+}
+
+/// Check each QNAM appears at most once per parent record.
+///
+/// A parent record is identified by USUBJID, IDVAR and IDVARVAL.
+pub fn check_supp_qnam_unique(domain_name: &str, supp_df: &DataFrame) -> Vec<Issue> {
+    let reader = ColumnReader::new(supp_df);
+    if !reader.has_column("QNAM") {
+        return vec![];
+    }
+
+    let mut seen = HashSet::new();
+    let mut duplicate_count = 0u64;
+    let mut samples = Vec::new();
+
+    for row_idx in 0..reader.height() {
+        let key = ["USUBJID", "IDVAR", "IDVARVAL", "QNAM"]
+            .map(|column| reader.get_string(column, row_idx).trim().to_string());
+        if !seen.insert(key.clone()) {
+            duplicate_count += 1;
+            if samples.len() < MAX_SAMPLES {
+                samples.push(key.join("/"));
+            }
+        }
+    }
+
+    if duplicate_count > 0 {
+        vec![Issue::SuppDuplicateQnam {
+            domain: domain_name.to_uppercase(),
+            duplicate_count,
+            samples,
+        }]
+    } else {
+        vec![]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use polars::prelude::*;
+    use std::collections::BTreeMap;
 
     fn dm_df() -> DataFrame {
         df! {
@@ -875,4 +1107,196 @@ fn test_check_relrec_dataset_level_relationship() {
         let issues = check_relrec(&relrec, &context);
         assert!(issues.is_empty()); // Should skip dataset-level relationships
     }
+
+    // =========================================================================
+    // STUDY-LEVEL ORCHESTRATION TESTS
+    // =========================================================================
+
+    #[test]
+    fn test_validate_study_cross_domain() {
+        let lb = df! {
+            "USUBJID" => &["STUDY-002", "STUDY-777"],
+            "LBSEQ" => &["1", "1"],
+        }
+        .unwrap();
+        let co = df! {
+            "USUBJID" => &["STUDY-001"],
+            "RDOMAIN" => &["XX"],
+        }
+        .unwrap();
+        let relrec = df! {
+            "USUBJID" => &["STUDY-001", "STUDY-002"],
+            "RDOMAIN" => &["AE", "LB"],
+            "IDVAR" => &["AESEQ", "LBSEQ"],
+            "IDVARVAL" => &["1", "9"],
+            "RELID" => &["R1", "R2"],
+        }
+        .unwrap();
+        let mut ae = ae_df_invalid();
+        ae.with_column(Series::new("AESEQ".into(), &["1", "2", "3"]))
+            .unwrap();
+
+        let frames = BTreeMap::from([
+            ("DM".to_string(), dm_df()),
+            ("AE".to_string(), ae),
+            ("LB".to_string(), lb),
+            ("CO".to_string(), co),
+            ("RELREC".to_string(), relrec),
+        ]);
+
+        let issues = crate::validate::validate_study_cross_domain(
+            &frames,
+            &crate::validate::load_default_rules(),
+        );
+
+        // Issues stay grouped under the domain they were found in
+        assert!(!issues.contains_key("DM"));
+        let missing: BTreeMap<&str, u64> = issues
+            .iter()
+            .flat_map(|(name, issues)| issues.iter().map(move |issue| (name, issue)))
+            .filter_map(|(name, issue)| match issue {
+                Issue::UsubjidNotInDm { missing_count, .. } => {
+                    Some((name.as_str(), *missing_count))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(missing, BTreeMap::from([("AE", 2), ("LB", 1)]));
+        assert!(
+            issues["CO"]
+                .iter()
+                .any(|i| matches!(i, Issue::InvalidRdomain { .. }))
+        );
+        // RELREC references are resolved against LB without caller wiring
+        assert!(issues["RELREC"].iter().any(|i| matches!(
+            i,
+            Issue::RelrecInvalidReference { rdomain, samples, .. }
+                if rdomain == "LB" && samples.contains(&"LBSEQ=9".to_string())
+        )));
+    }
+
+    #[test]
+    fn test_validate_study_cross_domain_without_dm() {
+        let frames = BTreeMap::from([("AE".to_string(), ae_df_valid())]);
+
+        let issues = crate::validate::validate_study_cross_domain(
+            &frames,
+            &crate::validate::load_default_rules(),
+        );
+
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            issues["AE"].as_slice(),
+            [Issue::UsubjidNotInDm {
+                missing_count: 3,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_check_supp_parent_and_qnam() {
+        let ae = df! {
+            "USUBJID" => &["STUDY-001", "STUDY-001", "STUDY-002"],
+            "AESEQ" => &["1", "2", "1"],
+        }
+        .unwrap();
+        let supp = df! {
+            "RDOMAIN" => &["AE", "AE", "AE", "AE", "AE"],
+            "USUBJID" => &["STUDY-001", "STUDY-001", "STUDY-002", "STUDY-002", "STUDY-001"],
+            "IDVAR" => &["AESEQ", "AESEQ", "AESEQ", "AESEQ", ""],
+            "IDVARVAL" => &["2", "2", "2", "1", ""],
+            "QNAM" => &["AETRTEM", "AETRTEM", "AETRTEM", "AETRTEM", "AEOTHER"],
+        }
+        .unwrap();
+
+        let domains: Vec<(&str, &DataFrame)> = vec![("AE", &ae), ("SUPPAE", &supp)];
+        let context = RelrecContext::new(&domains);
+
+        // STUDY-002 has no AESEQ=2, even though STUDY-001 does
+        let issues = check_supp_parent("SUPPAE", &supp, &context);
+        assert_eq!(issues.len(), 1);
+        match &issues[0] {
+            Issue::SuppInvalidParent {
+                domain,
+                invalid_count,
+                samples,
+            } => {
+                assert_eq!(domain, "SUPPAE");
+                assert_eq!(*invalid_count, 1);
+                assert_eq!(samples, &["STUDY-002:AESEQ=2"]);
+            }
+            _ => panic!("Expected SuppInvalidParent"),
+        }
+
+        let issues = check_supp_qnam_unique("SUPPAE", &supp);
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            Issue::SuppDuplicateQnam { duplicate_count: 1, samples, .. }
+                if samples == &["STUDY-001/AESEQ/2/AETRTEM"]
+        ));
+    }
+
+    #[test]
+    fn test_check_studyid_consistency() {
+        let dm = df! {
+            "STUDYID" => &["CDISC01", "CDISC01"],
+            "USUBJID" => &["STUDY-001", "STUDY-002"],
+        }
+        .unwrap();
+        let ae = df! {
+            "STUDYID" => &["CDISC01", "CDISC-01", ""],
+            "USUBJID" => &["STUDY-001", "STUDY-001", "STUDY-002"],
+        }
+        .unwrap();
+        let ta = df! { "ARMCD" => &["A"] }.unwrap();
+
+        let domains: Vec<(&str, &DataFrame)> = vec![("DM", &dm), ("AE", &ae), ("TA", &ta)];
+        let issues = check_studyid_consistency(&domains);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].0, "AE");
+        assert!(matches!(
+            &issues[0].1,
+            Issue::StudyidInconsistent {
+                expected: Some(expected),
+                found,
+                mismatch_count: 1,
+                empty_count: 1,
+                ..
+            } if expected == "CDISC01" && found == &["CDISC-01"]
+        ));
+        assert!(issues[0].1.message().contains("CDISC-01"));
+    }
+
+    #[test]
+    fn test_check_split_sequences() {
+        let lbch = df! {
+            "USUBJID" => &["STUDY-001", "STUDY-001", "STUDY-002"],
+            "LBSEQ" => &["1", "2", "1"],
+        }
+        .unwrap();
+        let lbhe = df! {
+            "USUBJID" => &["STUDY-001", "STUDY-002", "STUDY-002"],
+            "LBSEQ" => &["2", "2", "3"],
+        }
+        .unwrap();
+        let ae = df! {
+            "USUBJID" => &["STUDY-001"],
+            "AESEQ" => &["1"],
+        }
+        .unwrap();
+
+        let domains: Vec<(&str, &DataFrame)> = vec![("LBHE", &lbhe), ("AE", &ae), ("LBCH", &lbch)];
+        let issues = check_split_sequences(&domains);
+
+        // STUDY-001 LBSEQ=2 is used by both splits; reported on the later one
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].0, "LBHE");
+        assert!(matches!(
+            &issues[0].1,
+            Issue::DuplicateSequence { variable, duplicate_count: 1 } if variable == "LBSEQ"
+        ));
+    }
 }
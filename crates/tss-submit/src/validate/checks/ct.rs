@@ -2,24 +2,27 @@
 //!
 //! Checks that values conform to controlled terminology.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use polars::prelude::{AnyValue, DataFrame};
-use tss_standards::any_to_string;
+use tss_standards::ColumnIndex;
+use tss_standards::any_to_string_for_output;
 use tss_standards::ct::{Codelist, ResolvedCodelist, TerminologyRegistry};
-use tss_standards::{SdtmDomain, SdtmVariable};
+use tss_standards::{SdtmDomain, SdtmVariable, Standard};
 
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
 
 const MAX_INVALID_VALUES: usize = 5;
 
 /// Check that values conform to controlled terminology.
+///
+/// Codelists are resolved in the CT package of `standard` first.
 pub fn check(
     domain: &SdtmDomain,
     df: &DataFrame,
-    columns: &CaseInsensitiveSet,
+    columns: &ColumnIndex,
     ct_registry: &TerminologyRegistry,
+    standard: Standard,
 ) -> Vec<Issue> {
     let mut issues = Vec::new();
 
@@ -28,7 +31,7 @@ pub fn check(
             continue;
         };
 
-        if let Some(resolved) = resolve_ct(ct_registry, variable)
+        if let Some(resolved) = resolve_ct(ct_registry, variable, standard)
             && let Some(issue) = check_ct_values(variable, df, column, &resolved)
         {
             issues.push(issue);
@@ -55,6 +58,13 @@ fn check_ct_values(
     // Capture total count before truncating sample list
     let total_invalid = invalid.len() as u64;
     let invalid_values: Vec<String> = invalid.into_iter().take(MAX_INVALID_VALUES).collect();
+    let suggestions: BTreeMap<String, String> = invalid_values
+        .iter()
+        .filter_map(|value| {
+            let (suggested, _) = ct.suggest_submission_value(value)?;
+            Some((value.clone(), suggested.to_string()))
+        })
+        .collect();
 
     Some(Issue::CtViolation {
         variable: variable.name.clone(),
@@ -64,6 +74,7 @@ fn check_ct_values(
         total_invalid,
         invalid_values,
         allowed_count: ct.terms.len(),
+        suggestions,
     })
 }
 
@@ -76,7 +87,7 @@ fn collect_invalid_ct_values(df: &DataFrame, column: &str, ct: &Codelist) -> BTr
     };
 
     for idx in 0..df.height() {
-        let raw = any_to_string(series.get(idx).unwrap_or(AnyValue::Null));
+        let raw = any_to_string_for_output(series.get(idx).unwrap_or(AnyValue::Null));
         let trimmed = raw.trim();
 
         if trimmed.is_empty() {
@@ -97,6 +108,7 @@ fn collect_invalid_ct_values(df: &DataFrame, column: &str, ct: &Codelist) -> BTr
 fn resolve_ct<'a>(
     registry: &'a TerminologyRegistry,
     variable: &SdtmVariable,
+    standard: Standard,
 ) -> Option<ResolvedCodelist<'a>> {
     let codelist_code = variable.codelist_code.as_ref()?;
     let code = codelist_code.split(';').next()?.trim();
@@ -105,5 +117,5 @@ fn resolve_ct<'a>(
         return None;
     }
 
-    registry.resolve(code, None)
+    registry.resolve_for_standard(code, standard)
 }
@@ -3,14 +3,14 @@
 //! Checks that Num variables contain only numeric values.
 
 use polars::prelude::{AnyValue, DataFrame, DataType};
-use tss_standards::any_to_string;
+use tss_standards::ColumnIndex;
+use tss_standards::any_to_string_for_output;
 use tss_standards::{SdtmDomain, VariableType};
 
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
 
 /// Check that Num variables contain only numeric values.
-pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet) -> Vec<Issue> {
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
     let mut issues = Vec::new();
 
     for variable in &domain.variables {
@@ -72,7 +72,7 @@ fn collect_non_numeric_values(df: &DataFrame, column: &str) -> (u64, Vec<String>
 
     for idx in 0..df.height() {
         let value = series.get(idx).unwrap_or(AnyValue::Null);
-        let str_value = any_to_string(value);
+        let str_value = any_to_string_for_output(value);
         let trimmed = str_value.trim();
 
         if trimmed.is_empty() {
@@ -1,16 +1,18 @@
 //! ISO 8601 date format validation (SDTMIG Chapter 7).
 //!
-//! Checks that date/datetime variables conform to ISO 8601 format.
+//! Checks that date/datetime variables conform to ISO 8601 format, and that
+//! paired start/end dates (--STDTC/--ENDTC) are in order.
 
+use std::cmp::Ordering;
 use std::sync::LazyLock;
 
 use polars::prelude::{AnyValue, DataFrame};
 use regex::Regex;
+use tss_standards::ColumnIndex;
 use tss_standards::SdtmDomain;
-use tss_standards::any_to_string;
+use tss_standards::any_to_string_for_output;
 
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
 
 /// ISO 8601 date patterns per SDTMIG Chapter 7.
 /// Supports partial precision: YYYY, YYYY-MM, YYYY-MM-DD, YYYY-MM-DDTHH:MM, YYYY-MM-DDTHH:MM:SS
@@ -22,11 +24,17 @@
     .expect("Invalid ISO 8601 regex")
 });
 
+/// Separators sometimes used in place of hyphens in the date portion.
+const NON_HYPHEN_SEPARATORS: &[char] = &['/', '.', '_'];
+
+/// Maximum number of sample values reported per issue.
+const MAX_SAMPLES: usize = 5;
+
 /// Known date/time variable name suffixes that require ISO 8601 validation.
 const DATE_SUFFIXES: &[&str] = &["DTC", "DTM", "DT", "TM", "STDTC", "ENDTC", "STDT", "ENDT"];
 
 /// Check that date/datetime variables conform to ISO 8601 format.
-pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet) -> Vec<Issue> {
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
     let mut issues = Vec::new();
 
     for variable in &domain.variables {
@@ -39,10 +47,80 @@ pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet)
             continue;
         };
 
-        let (invalid_count, samples) = collect_invalid_dates(df, column);
-        if invalid_count > 0 {
+        let scan = collect_invalid_dates(df, column);
+        if scan.separator_count > 0 {
+            issues.push(Issue::NonHyphenDate {
+                variable: variable.name.clone(),
+                invalid_count: scan.separator_count,
+                samples: scan.separator_samples,
+                suggestions: scan.suggestions,
+            });
+        }
+        if scan.invalid_count > 0 {
             issues.push(Issue::InvalidDate {
                 variable: variable.name.clone(),
+                invalid_count: scan.invalid_count,
+                samples: scan.invalid_samples,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Check that each --STDTC is not after its paired --ENDTC.
+///
+/// Partial dates are compared at their common precision, so "2024-03" vs
+/// "2024-03-15" is not a violation but "2024-04" vs "2024-03-15" is. Rows
+/// where either value is missing or not valid ISO 8601 are skipped (the
+/// latter are reported by [`check`]).
+pub fn check_order(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for variable in &domain.variables {
+        let upper = variable.name.to_uppercase();
+        let Some(prefix) = upper.strip_suffix("STDTC") else {
+            continue;
+        };
+        let end_name = format!("{prefix}ENDTC");
+        let (Some(start_col), Some(end_col)) =
+            (columns.get(&variable.name), columns.get(&end_name))
+        else {
+            continue;
+        };
+        let (Ok(start), Ok(end)) = (df.column(start_col), df.column(end_col)) else {
+            continue;
+        };
+        let subjects = columns.get("USUBJID").and_then(|c| df.column(c).ok());
+
+        let mut invalid_count = 0u64;
+        let mut samples: Vec<String> = Vec::new();
+        for idx in 0..df.height() {
+            let start_value = any_to_string_for_output(start.get(idx).unwrap_or(AnyValue::Null));
+            let end_value = any_to_string_for_output(end.get(idx).unwrap_or(AnyValue::Null));
+            if compare_partial_dates(start_value.trim(), end_value.trim())
+                != Some(Ordering::Greater)
+            {
+                continue;
+            }
+
+            invalid_count += 1;
+            if let Some(subjects) = subjects {
+                let usubjid = any_to_string_for_output(subjects.get(idx).unwrap_or(AnyValue::Null));
+                let usubjid = usubjid.trim();
+                if !usubjid.is_empty()
+                    && samples.len() < MAX_SAMPLES
+                    && !samples.iter().any(|s| s == usubjid)
+                {
+                    samples.push(usubjid.to_string());
+                }
+            }
+        }
+
+        if invalid_count > 0 {
+            issues.push(Issue::DateOrderViolation {
+                variable: variable.name.clone(),
+                end_variable: end_name,
                 invalid_count,
                 samples,
             });
@@ -52,38 +130,219 @@ pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet)
     issues
 }
 
+/// Compare two ISO 8601 values at their common precision.
+///
+/// Returns `None` if either value is empty or not valid ISO 8601.
+fn compare_partial_dates(a: &str, b: &str) -> Option<Ordering> {
+    let a = date_components(a)?;
+    let b = date_components(b)?;
+    let common = a.len().min(b.len());
+    Some(a[..common].cmp(&b[..common]))
+}
+
+/// Split a valid ISO 8601 value into year, month, day, hour, minute, second.
+///
+/// Fractional seconds are ignored.
+fn date_components(value: &str) -> Option<Vec<u32>> {
+    if !ISO8601_DATE_REGEX.is_match(value) {
+        return None;
+    }
+    let value = value.split_once('.').map_or(value, |(whole, _)| whole);
+    value
+        .split(['-', 'T', ':'])
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
 /// Check if a variable name indicates it's a date/time field.
 pub fn is_date_variable(name: &str) -> bool {
     let upper = name.to_uppercase();
     DATE_SUFFIXES.iter().any(|suffix| upper.ends_with(suffix))
 }
 
+/// Non-conforming date values found in a column.
+#[derive(Debug, Default)]
+struct DateScan {
+    /// Values that are not ISO 8601 in any form.
+    invalid_count: u64,
+    invalid_samples: Vec<String>,
+    /// Values that would be ISO 8601 if hyphens were used as separators.
+    separator_count: u64,
+    separator_samples: Vec<String>,
+    /// Hyphenated form of each separator sample.
+    suggestions: Vec<String>,
+}
+
+/// Rewrite the date portion of a value with hyphen separators.
+///
+/// Returns `Some` only if the value uses a non-hyphen separator and the
+/// hyphenated form is valid ISO 8601 (e.g., "2023/01/02" -> "2023-01-02").
+fn hyphenated_date(value: &str) -> Option<String> {
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    if !date.contains(NON_HYPHEN_SEPARATORS) {
+        return None;
+    }
+
+    let mut fixed = date.replace(NON_HYPHEN_SEPARATORS, "-");
+    if let Some(time) = time {
+        fixed.push('T');
+        fixed.push_str(time);
+    }
+
+    ISO8601_DATE_REGEX.is_match(&fixed).then_some(fixed)
+}
+
 /// Collect values that don't conform to ISO 8601.
-fn collect_invalid_dates(df: &DataFrame, column: &str) -> (u64, Vec<String>) {
+///
+/// Values that are well-formed apart from using slashes (or another
+/// separator) instead of hyphens are reported separately.
+fn collect_invalid_dates(df: &DataFrame, column: &str) -> DateScan {
+    let mut scan = DateScan::default();
+
     let Ok(series) = df.column(column) else {
-        return (0, vec![]);
+        return scan;
     };
 
-    let mut count = 0u64;
-    let mut samples = Vec::new();
-    const MAX_SAMPLES: usize = 5;
-
     for idx in 0..df.height() {
         let value = series.get(idx).unwrap_or(AnyValue::Null);
-        let str_value = any_to_string(value);
+        let str_value = any_to_string_for_output(value);
         let trimmed = str_value.trim();
 
         if trimmed.is_empty() {
             continue; // Nulls are OK for dates
         }
 
-        if !ISO8601_DATE_REGEX.is_match(trimmed) {
-            count += 1;
-            if samples.len() < MAX_SAMPLES {
-                samples.push(trimmed.to_string());
+        if ISO8601_DATE_REGEX.is_match(trimmed) {
+            continue;
+        }
+
+        if let Some(fixed) = hyphenated_date(trimmed) {
+            scan.separator_count += 1;
+            if scan.separator_samples.len() < MAX_SAMPLES {
+                scan.separator_samples.push(trimmed.to_string());
+                scan.suggestions.push(fixed);
+            }
+        } else {
+            scan.invalid_count += 1;
+            if scan.invalid_samples.len() < MAX_SAMPLES {
+                scan.invalid_samples.push(trimmed.to_string());
             }
         }
     }
 
-    (count, samples)
+    scan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    use tss_standards::sdtm::test_support;
+
+    fn domain(names: &[&str]) -> SdtmDomain {
+        test_support::domain("AE").variable_names(names).build()
+    }
+
+    fn order_issues(df: &DataFrame) -> Vec<Issue> {
+        let domain = domain(&["USUBJID", "AESTDTC", "AEENDTC"]);
+        let columns = ColumnIndex::new(df);
+        check_order(&domain, df, &columns)
+    }
+
+    #[test]
+    fn test_start_after_end_flagged() {
+        let df = df! {
+            "USUBJID" => &["S01", "S02", "S02", "S03"],
+            "AESTDTC" => &["2024-03-10", "2024-03-10T08:00", "2024-03-10T10:30", "2024-03-12"],
+            "AEENDTC" => &["2024-03-09", "2024-03-10T09:00", "2024-03-10T10:00", "2024-03-12"],
+        }
+        .unwrap();
+
+        let issues = order_issues(&df);
+        assert_eq!(issues.len(), 1);
+        match &issues[0] {
+            Issue::DateOrderViolation {
+                variable,
+                end_variable,
+                invalid_count,
+                samples,
+            } => {
+                assert_eq!(variable, "AESTDTC");
+                assert_eq!(end_variable, "AEENDTC");
+                assert_eq!(*invalid_count, 2);
+                assert_eq!(samples, &["S01", "S02"]);
+            }
+            other => panic!("unexpected issue: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_partial_dates_compared_at_common_precision() {
+        let df = df! {
+            "USUBJID" => &["S01", "S02", "S03", "S04", "S05"],
+            "AESTDTC" => &["2024-03", "2024-04", "2024", "2024-03-15", ""],
+            "AEENDTC" => &["2024-03-15", "2024-03-15", "2023-12", "2024-03", "2024-01-01"],
+        }
+        .unwrap();
+
+        let issues = order_issues(&df);
+        assert_eq!(issues.len(), 1);
+        // S01 and S04 overlap at month precision, S05 has no start date
+        assert_eq!(issues[0].count(), Some(2));
+        assert!(issues[0].message().contains("S02, S03"));
+    }
+
+    #[test]
+    fn test_slash_separated_date_flagged() {
+        let df = df! { "AESTDTC" => &["2023/01/02"] }.unwrap();
+        let scan = collect_invalid_dates(&df, "AESTDTC");
+
+        assert_eq!(scan.separator_count, 1);
+        assert_eq!(scan.separator_samples, vec!["2023/01/02"]);
+        assert_eq!(scan.suggestions, vec!["2023-01-02"]);
+        assert_eq!(scan.invalid_count, 0);
+    }
+
+    #[test]
+    fn test_hyphen_separated_date_accepted() {
+        let df = df! { "AESTDTC" => &["2023-01-02", "2023-01-02T10:30", ""] }.unwrap();
+        let scan = collect_invalid_dates(&df, "AESTDTC");
+
+        assert_eq!(scan.separator_count, 0);
+        assert_eq!(scan.invalid_count, 0);
+    }
+
+    #[test]
+    fn test_typed_date_columns_accepted() {
+        // 2024-03-15 and 2024-03-15T14:30:00 UTC
+        let dates = Column::new("AESTDTC".into(), [Some(19_797i32), None])
+            .cast(&DataType::Date)
+            .unwrap();
+        let datetimes = Column::new("AEENDTC".into(), [1_710_513_000_000i64])
+            .cast(&DataType::Datetime(
+                TimeUnit::Milliseconds,
+                TimeZone::opt_try_new(Some("Europe/Berlin")).unwrap(),
+            ))
+            .unwrap();
+        let df = DataFrame::new(vec![dates]).unwrap();
+        let zoned = DataFrame::new(vec![datetimes]).unwrap();
+
+        assert_eq!(collect_invalid_dates(&df, "AESTDTC").invalid_count, 0);
+        assert_eq!(collect_invalid_dates(&zoned, "AEENDTC").invalid_count, 0);
+    }
+
+    #[test]
+    fn test_separator_distinct_from_unparseable() {
+        let df = df! { "AESTDTC" => &["2023.01.02T10:30", "2023/13/45", "not a date"] }.unwrap();
+        let scan = collect_invalid_dates(&df, "AESTDTC");
+
+        assert_eq!(scan.separator_count, 1);
+        assert_eq!(scan.suggestions, vec!["2023-01-02T10:30"]);
+        assert_eq!(scan.invalid_count, 2);
+    }
 }
@@ -0,0 +1,94 @@
+//! DOMAIN value checks (SDTMIG 4.1.2).
+//!
+//! Checks that DOMAIN holds the code of the domain being validated.
+
+use polars::prelude::DataFrame;
+use tss_standards::ColumnIndex;
+use tss_standards::SdtmDomain;
+
+use super::super::column_reader::ColumnReader;
+use super::super::issue::Issue;
+
+/// Maximum number of distinct wrong values reported.
+const MAX_SAMPLES: usize = 5;
+
+/// Check that every DOMAIN value equals the domain code.
+///
+/// Split datasets (e.g., LBCH) are validated against their parent domain,
+/// whose code they must carry in DOMAIN. Empty values are left to the
+/// Required variable check, and datasets without DOMAIN are skipped.
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
+    let Some(column) = columns.get("DOMAIN") else {
+        return Vec::new();
+    };
+    let expected = domain.name.to_uppercase();
+    let reader = ColumnReader::new(df);
+
+    let mut mismatch_count = 0u64;
+    let mut samples: Vec<String> = Vec::new();
+    for (_, value) in reader.values(column).into_iter().flatten() {
+        let value = value.trim();
+        if value.is_empty() || value == expected {
+            continue;
+        }
+        mismatch_count += 1;
+        if samples.len() < MAX_SAMPLES && !samples.iter().any(|s| s == value) {
+            samples.push(value.to_string());
+        }
+    }
+
+    if mismatch_count == 0 {
+        return Vec::new();
+    }
+    vec![Issue::DomainValueMismatch {
+        expected,
+        mismatch_count,
+        samples,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn dm() -> SdtmDomain {
+        tss_standards::load_sdtm_ig()
+            .unwrap()
+            .into_iter()
+            .find(|domain| domain.name == "DM")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_domain_value_matches() {
+        let df = df! {
+            "DOMAIN" => &["DM", "DM"],
+            "USUBJID" => &["S-001", "S-002"],
+        }
+        .unwrap();
+        assert!(check(&dm(), &df, &ColumnIndex::new(&df)).is_empty());
+
+        let no_domain = df! { "USUBJID" => &["S-001"] }.unwrap();
+        assert!(check(&dm(), &no_domain, &ColumnIndex::new(&no_domain)).is_empty());
+    }
+
+    #[test]
+    fn test_domain_value_mismatch() {
+        let df = df! {
+            "DOMAIN" => &["DM", "AE", "AE", "dm"],
+            "USUBJID" => &["S-001", "S-002", "S-003", "S-004"],
+        }
+        .unwrap();
+        let issues = check(&dm(), &df, &ColumnIndex::new(&df));
+
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            Issue::DomainValueMismatch { expected, mismatch_count: 3, samples }
+                if expected == "DM" && samples == &["AE", "dm"]
+        ));
+        assert_eq!(issues[0].variable(), "DOMAIN");
+        assert!(issues[0].message().contains("expected DM"));
+    }
+}
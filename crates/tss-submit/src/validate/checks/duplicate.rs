@@ -0,0 +1,226 @@
+//! Duplicate record detection.
+//!
+//! Flags records that share a natural key (identifier, topic, and timing
+//! values), such as two LB records for the same subject, test, and date.
+//! Unlike the --SEQ check, these are genuine duplicates of the observation.
+
+use std::collections::HashMap;
+
+use polars::prelude::DataFrame;
+use tss_standards::ColumnIndex;
+use tss_standards::{SdtmDomain, VariableRole};
+
+use super::super::column_reader::ColumnReader;
+use super::super::issue::Issue;
+
+/// Maximum number of sample keys reported.
+const MAX_SAMPLES: usize = 5;
+
+/// Identifier suffixes left out of the key: --SEQ is unique by construction,
+/// and --SPID/--REFID are sponsor-assigned record IDs that can differ between
+/// otherwise identical records.
+const EXCLUDED_SUFFIXES: &[&str] = &["SEQ", "SPID", "REFID"];
+
+/// Check for records sharing the same natural key.
+///
+/// The key is built from the domain's Identifier, Topic, and Timing variables
+/// present in the data (in role order), excluding --SEQ, --SPID and --REFID.
+/// Skipped when the data has no Topic or no Timing variable,
+/// since identifiers alone don't describe an observation.
+pub fn check_duplicate_records(
+    domain: &SdtmDomain,
+    df: &DataFrame,
+    columns: &ColumnIndex,
+) -> Vec<Issue> {
+    let mut key_variables = Vec::new();
+    let mut key_columns = Vec::new();
+    let mut topic = None;
+    let mut has_timing = false;
+
+    for variable in domain.variables_by_role() {
+        let role = match variable.role {
+            Some(VariableRole::Identifier) if !is_record_id(&variable.name) => {
+                VariableRole::Identifier
+            }
+            Some(role @ (VariableRole::Topic | VariableRole::Timing)) => role,
+            _ => continue,
+        };
+        let Some(column) = columns.get(&variable.name) else {
+            continue;
+        };
+
+        match role {
+            VariableRole::Topic => {
+                topic.get_or_insert_with(|| variable.name.clone());
+            }
+            VariableRole::Timing => has_timing = true,
+            _ => {}
+        }
+        key_variables.push(variable.name.clone());
+        key_columns.push(column);
+    }
+
+    let (Some(variable), true) = (topic, has_timing) else {
+        return Vec::new();
+    };
+
+    let reader = ColumnReader::new(df);
+    let mut groups: HashMap<Vec<String>, u64> = HashMap::new();
+    let mut order: Vec<Vec<String>> = Vec::new();
+    for idx in 0..reader.height() {
+        let key: Vec<String> = key_columns
+            .iter()
+            .map(|column| reader.get_string(column, idx).trim().to_string())
+            .collect();
+        let count = groups.entry(key.clone()).or_insert(0);
+        if *count == 0 {
+            order.push(key);
+        }
+        *count += 1;
+    }
+
+    let mut group_count = 0u64;
+    let mut duplicate_count = 0u64;
+    let mut samples = Vec::new();
+    for key in order {
+        let rows = groups[&key];
+        if rows < 2 {
+            continue;
+        }
+        group_count += 1;
+        duplicate_count += rows - 1;
+        if samples.len() < MAX_SAMPLES {
+            samples.push(key.join("/"));
+        }
+    }
+
+    if group_count == 0 {
+        return Vec::new();
+    }
+
+    vec![Issue::DuplicateRecord {
+        variable,
+        key_variables,
+        group_count,
+        duplicate_count,
+        samples,
+    }]
+}
+
+/// Whether an Identifier variable names a record rather than an observation.
+fn is_record_id(name: &str) -> bool {
+    let name = name.to_uppercase();
+    EXCLUDED_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+    use tss_standards::SdtmVariable;
+    use tss_standards::sdtm::test_support::{self, domain};
+
+    fn variable(name: &str, role: VariableRole) -> SdtmVariable {
+        test_support::variable(name).role(role).build()
+    }
+
+    fn lb_domain() -> SdtmDomain {
+        domain("LB")
+            .variables([
+                variable("USUBJID", VariableRole::Identifier),
+                variable("LBSEQ", VariableRole::Identifier),
+                variable("LBSPID", VariableRole::Identifier),
+                variable("LBREFID", VariableRole::Identifier),
+                variable("LBTESTCD", VariableRole::Topic),
+                variable("LBORRES", VariableRole::ResultQualifier),
+                variable("VISITNUM", VariableRole::Timing),
+                variable("LBDTC", VariableRole::Timing),
+            ])
+            .build()
+    }
+
+    fn run(df: &DataFrame) -> Vec<Issue> {
+        let columns = ColumnIndex::new(df);
+        check_duplicate_records(&lb_domain(), df, &columns)
+    }
+
+    #[test]
+    fn test_duplicate_records_flagged() {
+        let df = df! {
+            "USUBJID" => &["S01", "S01", "S01", "S02", "S02"],
+            "LBSEQ" => &["1", "2", "3", "1", "2"],
+            "LBTESTCD" => &["GLUC", "GLUC", "GLUC", "GLUC", "ALT"],
+            "LBORRES" => &["5.1", "5.3", "5.2", "4.9", "30"],
+            "VISITNUM" => &["1", "1", "1", "1", "1"],
+            "LBDTC" => &["2024-01-05", "2024-01-05", "2024-01-05", "2024-01-05", "2024-01-05"],
+        }
+        .unwrap();
+
+        let issues = run(&df);
+        assert_eq!(issues.len(), 1);
+        match &issues[0] {
+            Issue::DuplicateRecord {
+                variable,
+                key_variables,
+                group_count,
+                duplicate_count,
+                samples,
+            } => {
+                assert_eq!(variable, "LBTESTCD");
+                assert_eq!(key_variables, &["USUBJID", "LBTESTCD", "VISITNUM", "LBDTC"]);
+                assert_eq!(*group_count, 1);
+                assert_eq!(*duplicate_count, 2);
+                assert_eq!(samples, &["S01/GLUC/1/2024-01-05"]);
+            }
+            other => panic!("unexpected issue: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_distinct_records_not_flagged() {
+        let df = df! {
+            "USUBJID" => &["S01", "S01", "S01"],
+            "LBSEQ" => &["1", "2", "3"],
+            "LBTESTCD" => &["GLUC", "GLUC", "ALT"],
+            "VISITNUM" => &["1", "2", "1"],
+            "LBDTC" => &["2024-01-05", "2024-02-05", "2024-01-05"],
+        }
+        .unwrap();
+        assert!(run(&df).is_empty());
+    }
+
+    #[test]
+    fn test_sponsor_ids_not_in_key() {
+        let df = df! {
+            "USUBJID" => &["S01", "S01"],
+            "LBSEQ" => &["1", "2"],
+            "LBSPID" => &["LAB-1", "LAB-2"],
+            "LBREFID" => &["SPEC-1", "SPEC-2"],
+            "LBTESTCD" => &["GLUC", "GLUC"],
+            "VISITNUM" => &["1", "1"],
+            "LBDTC" => &["2024-01-05", "2024-01-05"],
+        }
+        .unwrap();
+
+        let issues = run(&df);
+        assert_eq!(issues.len(), 1);
+        match &issues[0] {
+            Issue::DuplicateRecord { key_variables, .. } => {
+                assert_eq!(key_variables, &["USUBJID", "LBTESTCD", "VISITNUM", "LBDTC"]);
+            }
+            other => panic!("unexpected issue: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_skipped_without_timing() {
+        let df = df! {
+            "USUBJID" => &["S01", "S01"],
+            "LBTESTCD" => &["GLUC", "GLUC"],
+        }
+        .unwrap();
+        assert!(run(&df).is_empty());
+    }
+}
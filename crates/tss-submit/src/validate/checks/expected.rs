@@ -11,11 +11,11 @@
 
 use polars::prelude::DataFrame;
 use std::collections::BTreeSet;
+use tss_standards::ColumnIndex;
 use tss_standards::{CoreDesignation, SdtmDomain};
 
 use super::super::column_reader::ColumnReader;
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
 
 /// Check expected variables are present.
 ///
@@ -27,7 +27,7 @@
 pub fn check(
     domain: &SdtmDomain,
     df: &DataFrame,
-    columns: &CaseInsensitiveSet,
+    columns: &ColumnIndex,
     not_collected: &BTreeSet<String>,
 ) -> Vec<Issue> {
     let mut issues = Vec::new();
@@ -3,14 +3,14 @@
 //! Checks that Identifier role variables have no null values.
 
 use polars::prelude::DataFrame;
+use tss_standards::ColumnIndex;
 use tss_standards::{SdtmDomain, VariableRole};
 
 use super::super::column_reader::ColumnReader;
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
 
 /// Check that Identifier role variables have no null values.
-pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet) -> Vec<Issue> {
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
     let mut issues = Vec::new();
     let reader = ColumnReader::new(df);
 
@@ -1,16 +1,20 @@
 //! Text length validation (SDTMIG 2.4).
 //!
-//! Checks that character variables don't exceed their defined length.
+//! Checks that character variables don't exceed their defined length and
+//! that variable labels fit the XPT label limit.
 
 use polars::prelude::DataFrame;
+use tss_standards::ColumnIndex;
 use tss_standards::{SdtmDomain, VariableType};
 
 use super::super::column_reader::ColumnReader;
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
+
+/// Maximum variable label length in SAS V5 transport files.
+const MAX_LABEL_LENGTH: usize = 40;
 
 /// Check that character variables don't exceed their defined length.
-pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet) -> Vec<Issue> {
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
     let mut issues = Vec::new();
     let reader = ColumnReader::new(df);
 
@@ -41,3 +45,62 @@ pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet)
 
     issues
 }
+
+/// Check that variable labels don't exceed 40 characters.
+///
+/// Runs on the domain definition, so sponsor-defined labels are caught
+/// before XPT export truncates them.
+pub fn check_labels(domain: &SdtmDomain) -> Vec<Issue> {
+    domain
+        .variables
+        .iter()
+        .filter_map(|variable| {
+            let label = variable.label.as_ref()?;
+            let length = label.chars().count();
+            (length > MAX_LABEL_LENGTH).then(|| Issue::LabelTooLong {
+                variable: variable.name.clone(),
+                label: label.clone(),
+                length,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tss_standards::SdtmVariable;
+    use tss_standards::sdtm::test_support::{self, domain};
+
+    fn variable(name: &str, label: &str) -> SdtmVariable {
+        test_support::variable(name).label(label).build()
+    }
+
+    #[test]
+    fn test_check_labels() {
+        let long_label = "Reason for Change in Planned Study Treatments";
+        assert_eq!(long_label.len(), 45);
+        let domain = domain("XA")
+            .variables([
+                variable("USUBJID", "Unique Subject Identifier"),
+                variable("XAREAS", long_label),
+                variable("XATERM", &"A".repeat(40)),
+            ])
+            .build();
+
+        let issues = check_labels(&domain);
+        assert_eq!(issues.len(), 1);
+        let Issue::LabelTooLong {
+            variable,
+            label,
+            length,
+        } = &issues[0]
+        else {
+            panic!("unexpected issue: {:?}", issues[0]);
+        };
+        assert_eq!(variable, "XAREAS");
+        assert_eq!(label, long_label);
+        assert_eq!(*length, 45);
+        assert_eq!(issues[0].severity(None), crate::validate::Severity::Warning);
+    }
+}
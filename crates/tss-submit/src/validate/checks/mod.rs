@@ -2,39 +2,54 @@
 //!
 //! Each module performs a specific type of validation check.
 
+mod ascii;
 pub mod cross_domain;
 mod ct;
 mod datatype;
 pub mod dates;
+mod domain_code;
+mod duplicate;
 mod expected;
 mod identifier;
 mod length;
+pub mod ranges;
 mod required;
 mod sequence;
+mod test_pairing;
 
 use polars::prelude::DataFrame;
 use std::collections::BTreeSet;
-use tss_standards::SdtmDomain;
+use tss_standards::ColumnIndex;
 use tss_standards::TerminologyRegistry;
+use tss_standards::{SdtmDomain, Standard};
 
 use super::report::ValidationReport;
-use super::util::CaseInsensitiveSet;
+use super::rules::RuleRegistry;
 
 /// Run all validation checks on a domain.
+///
+/// Issues for rules disabled in `rules` are not reported.
 pub fn run_all(
     domain: &SdtmDomain,
     df: &DataFrame,
     ct_registry: Option<&TerminologyRegistry>,
+    standard: Standard,
     not_collected: &BTreeSet<String>,
+    rules: &RuleRegistry,
 ) -> ValidationReport {
-    let column_lookup = build_column_lookup(df);
-    let mut report = ValidationReport::new(&domain.name);
+    let column_lookup = ColumnIndex::new(df);
+    let mut report = ValidationReport::new(&domain.name).with_rules(rules.clone());
 
     // 1. Required variable checks (presence + population)
     for issue in required::check(domain, df, &column_lookup) {
         report.add(issue);
     }
 
+    // 1b. DOMAIN holds the domain code
+    for issue in domain_code::check(domain, df, &column_lookup) {
+        report.add(issue);
+    }
+
     // 2. Expected variable checks (presence only, warnings)
     // Skip variables marked as "not collected" by user
     for issue in expected::check(domain, df, &column_lookup, not_collected) {
@@ -46,37 +61,58 @@ pub fn run_all(
         report.add(issue);
     }
 
-    // 4. ISO 8601 date format validation
+    // 4. ISO 8601 date format and start/end order validation
     for issue in dates::check(domain, df, &column_lookup) {
         report.add(issue);
     }
+    for issue in dates::check_order(domain, df, &column_lookup) {
+        report.add(issue);
+    }
 
     // 5. Sequence uniqueness (--SEQ must be unique per USUBJID)
     for issue in sequence::check(domain, df, &column_lookup) {
         report.add(issue);
     }
 
-    // 6. Text length validation
+    // 6. Duplicate records on natural keys (identifier + topic + timing)
+    for issue in duplicate::check_duplicate_records(domain, df, &column_lookup) {
+        report.add(issue);
+    }
+
+    // 6b. One-to-one --TESTCD/--TEST pairing (Findings)
+    for issue in test_pairing::check(domain, df, &column_lookup) {
+        report.add(issue);
+    }
+
+    // 7. Text length and ASCII validation (XPT V5 limits)
     for issue in length::check(domain, df, &column_lookup) {
         report.add(issue);
     }
+    for issue in length::check_labels(domain) {
+        report.add(issue);
+    }
+    for issue in ascii::check(domain, df, &column_lookup) {
+        report.add(issue);
+    }
 
-    // 7. Identifier null checks
+    // 8. Identifier null checks
     for issue in identifier::check(domain, df, &column_lookup) {
         report.add(issue);
     }
 
-    // 8. Controlled terminology validation
+    // 9. Controlled terminology validation
     if let Some(registry) = ct_registry {
-        for issue in ct::check(domain, df, &column_lookup, registry) {
+        for issue in ct::check(domain, df, &column_lookup, registry, standard) {
             report.add(issue);
         }
     }
 
-    report
-}
+    // 10. Numeric plausibility ranges (opt-in)
+    if rules.is_enabled("value_out_of_range") {
+        for issue in ranges::check_numeric_ranges(domain, df, &column_lookup, rules.range_rules()) {
+            report.add(issue);
+        }
+    }
 
-/// Build case-insensitive column name lookup.
-fn build_column_lookup(df: &DataFrame) -> CaseInsensitiveSet {
-    CaseInsensitiveSet::from_names(df.get_column_names_owned())
+    report
 }
@@ -0,0 +1,289 @@
+//! Numeric range and plausibility checks.
+//!
+//! Flags implausible numeric values (e.g., AGE > 120, a negative weight)
+//! using a table of range rules. The check is opt-in: the
+//! `value_out_of_range` rule is disabled by default in the rule registry.
+
+use polars::prelude::DataFrame;
+use serde::{Deserialize, Serialize};
+use tss_standards::ColumnIndex;
+use tss_standards::SdtmDomain;
+
+use super::super::column_reader::ColumnReader;
+use super::super::issue::Issue;
+
+/// Maximum number of sample rows reported per issue.
+const MAX_SAMPLES: usize = 5;
+
+/// Plausible range for a numeric variable.
+///
+/// Variable names may start with `--`, which is replaced by the domain code
+/// (e.g., `--STRESN` matches VSSTRESN in VS).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeRule {
+    /// Variable to check (e.g., "AGE", "--STRESN").
+    pub variable: String,
+    /// Only check rows where --TESTCD has this value.
+    #[serde(default)]
+    pub test_code: Option<String>,
+    /// Lowest plausible value (inclusive).
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Highest plausible value (inclusive).
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Unit the range is expressed in.
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// Variable holding each row's unit (e.g., "AGEU", "--STRESU").
+    ///
+    /// Rows with a different non-empty unit are skipped, since the range
+    /// doesn't apply to them.
+    #[serde(default)]
+    pub unit_variable: Option<String>,
+}
+
+impl RangeRule {
+    /// Create a rule for a variable with no bounds.
+    pub fn new(variable: impl Into<String>) -> Self {
+        Self {
+            variable: variable.into(),
+            test_code: None,
+            min: None,
+            max: None,
+            unit: None,
+            unit_variable: None,
+        }
+    }
+
+    /// Restrict the rule to rows with this --TESTCD.
+    pub fn with_test_code(mut self, test_code: impl Into<String>) -> Self {
+        self.test_code = Some(test_code.into());
+        self
+    }
+
+    /// Set the inclusive bounds.
+    pub fn with_range(mut self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set the unit and the variable holding each row's unit.
+    pub fn with_unit(mut self, unit: impl Into<String>, unit_variable: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self.unit_variable = Some(unit_variable.into());
+        self
+    }
+
+    fn contains(&self, value: f64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// Table of range rules used by [`check_numeric_ranges`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RangeRuleTable {
+    rules: Vec<RangeRule>,
+}
+
+impl Default for RangeRuleTable {
+    /// Built-in rules for demographics and common vital signs.
+    fn default() -> Self {
+        let vital = |test_code: &str, min: f64, max: f64, unit: &str| {
+            RangeRule::new("--STRESN")
+                .with_test_code(test_code)
+                .with_range(Some(min), Some(max))
+                .with_unit(unit, "--STRESU")
+        };
+        Self {
+            rules: vec![
+                RangeRule::new("AGE")
+                    .with_range(Some(0.0), Some(120.0))
+                    .with_unit("YEARS", "AGEU"),
+                vital("HEIGHT", 0.0, 275.0, "cm"),
+                vital("WEIGHT", 0.0, 650.0, "kg"),
+                vital("TEMP", 25.0, 45.0, "C"),
+                vital("PULSE", 0.0, 300.0, "beats/min"),
+                vital("SYSBP", 0.0, 300.0, "mmHg"),
+                vital("DIABP", 0.0, 200.0, "mmHg"),
+            ],
+        }
+    }
+}
+
+impl RangeRuleTable {
+    /// Create an empty table.
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Parse a table from a JSON array of rules.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Add a rule.
+    pub fn push(&mut self, rule: RangeRule) {
+        self.rules.push(rule);
+    }
+
+    /// Add a rule (builder style).
+    pub fn with_rule(mut self, rule: RangeRule) -> Self {
+        self.push(rule);
+        self
+    }
+
+    /// Get all rules.
+    pub fn rules(&self) -> &[RangeRule] {
+        &self.rules
+    }
+}
+
+/// Check numeric values against the plausible ranges in `table`.
+///
+/// Values that are empty or non-numeric are skipped (the data type check
+/// reports the latter).
+pub fn check_numeric_ranges(
+    domain: &SdtmDomain,
+    df: &DataFrame,
+    columns: &ColumnIndex,
+    table: &RangeRuleTable,
+) -> Vec<Issue> {
+    let reader = ColumnReader::new(df);
+    let expand = |name: &str| match name.strip_prefix("--") {
+        Some(suffix) => format!("{}{}", domain.name.to_uppercase(), suffix),
+        None => name.to_uppercase(),
+    };
+    let test_code_column = columns.get(expand("--TESTCD"));
+    let mut issues = Vec::new();
+
+    for rule in table.rules() {
+        let variable = expand(&rule.variable);
+        let Some(column) = columns.get(&variable) else {
+            continue;
+        };
+        if rule.test_code.is_some() && test_code_column.is_none() {
+            continue;
+        }
+        let unit_column = rule
+            .unit_variable
+            .as_deref()
+            .and_then(|name| columns.get(expand(name)));
+
+        let mut invalid_count = 0u64;
+        let mut samples = Vec::new();
+        for idx in 0..reader.height() {
+            if let (Some(code), Some(c)) = (&rule.test_code, test_code_column)
+                && !reader.get_string(c, idx).trim().eq_ignore_ascii_case(code)
+            {
+                continue;
+            }
+            if let (Some(unit), Some(c)) = (&rule.unit, unit_column) {
+                let row_unit = reader.get_string(c, idx);
+                let row_unit = row_unit.trim();
+                if !row_unit.is_empty() && !row_unit.eq_ignore_ascii_case(unit) {
+                    continue;
+                }
+            }
+
+            let value = reader.get_string(column, idx);
+            let Ok(number) = value.trim().parse::<f64>() else {
+                continue;
+            };
+            if rule.contains(number) {
+                continue;
+            }
+
+            invalid_count += 1;
+            if samples.len() < MAX_SAMPLES {
+                samples.push(format!("row {}: {}", idx + 1, value.trim()));
+            }
+        }
+
+        if invalid_count > 0 {
+            issues.push(Issue::ValueOutOfRange {
+                variable,
+                test_code: rule.test_code.clone(),
+                min: rule.min,
+                max: rule.max,
+                unit: rule.unit.clone(),
+                invalid_count,
+                samples,
+            });
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    use tss_standards::sdtm::test_support;
+
+    fn domain(name: &str) -> SdtmDomain {
+        test_support::domain(name).build()
+    }
+
+    fn run(domain_code: &str, df: &DataFrame, table: &RangeRuleTable) -> Vec<Issue> {
+        let columns = ColumnIndex::new(df);
+        check_numeric_ranges(&domain(domain_code), df, &columns, table)
+    }
+
+    #[test]
+    fn test_age_out_of_range() {
+        let df = df! {
+            "AGE" => &["34", "121", "-1", "", "65"],
+            "AGEU" => &["YEARS", "YEARS", "YEARS", "", "MONTHS"],
+        }
+        .unwrap();
+
+        let issues = run("DM", &df, &RangeRuleTable::default());
+        assert_eq!(issues.len(), 1);
+        match &issues[0] {
+            Issue::ValueOutOfRange {
+                variable,
+                invalid_count,
+                samples,
+                ..
+            } => {
+                assert_eq!(variable, "AGE");
+                assert_eq!(*invalid_count, 2);
+                assert_eq!(samples, &["row 2: 121", "row 3: -1"]);
+            }
+            other => panic!("unexpected issue: {other:?}"),
+        }
+        assert_eq!(issues[0].severity(None), crate::validate::Severity::Warning);
+    }
+
+    #[test]
+    fn test_height_rule_from_table() {
+        let df = df! {
+            "VSTESTCD" => &["HEIGHT", "HEIGHT", "WEIGHT", "HEIGHT"],
+            "VSSTRESN" => &[172.0, 1.72, 80.0, -5.0],
+            "VSSTRESU" => &["cm", "m", "kg", "cm"],
+        }
+        .unwrap();
+
+        // Extend an empty table with a loaded rule
+        let table = RangeRuleTable::from_json(
+            r#"[{ "variable": "--STRESN", "test_code": "HEIGHT", "min": 50, "max": 250,
+                  "unit": "cm", "unit_variable": "--STRESU" }]"#,
+        )
+        .unwrap();
+
+        let issues = run("VS", &df, &table);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].variable(), "VSSTRESN");
+        // 1.72 m is in another unit, and the weight row is a different test
+        assert_eq!(issues[0].count(), Some(1));
+        assert!(issues[0].message().contains("row 4: -5"));
+
+        let issues = run("VS", &df, &RangeRuleTable::empty());
+        assert!(issues.is_empty());
+    }
+}
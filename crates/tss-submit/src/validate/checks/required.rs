@@ -3,14 +3,14 @@
 //! Checks that all Required (Req) variables are present and populated.
 
 use polars::prelude::DataFrame;
+use tss_standards::ColumnIndex;
 use tss_standards::{CoreDesignation, SdtmDomain};
 
 use super::super::column_reader::ColumnReader;
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
 
 /// Check required variables are present and populated.
-pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet) -> Vec<Issue> {
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
     let mut issues = Vec::new();
     let row_count = df.height() as u64;
     let reader = ColumnReader::new(df);
@@ -31,10 +31,11 @@ pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet)
         // Check population (no nulls allowed for Req)
         let null_count = reader.count_nulls(column);
 
-        // If ALL values are null/empty, treat as "missing" (unmapped)
+        // Present but entirely null/empty: the column exists but was not mapped
         if null_count == row_count && row_count > 0 {
-            issues.push(Issue::RequiredMissing {
+            issues.push(Issue::RequiredAllNull {
                 variable: variable.name.clone(),
+                row_count,
             });
         } else if null_count > 0 {
             issues.push(Issue::RequiredEmpty {
@@ -46,3 +47,40 @@ pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet)
 
     issues
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+    use tss_standards::sdtm::test_support;
+
+    fn domain() -> SdtmDomain {
+        let required = |name: &str| test_support::variable(name).core(CoreDesignation::Required);
+        test_support::domain("AE")
+            .variables([required("USUBJID"), required("AETERM"), required("AEDECOD")])
+            .build()
+    }
+
+    #[test]
+    fn test_required_present_but_empty_and_missing() {
+        let df = df! {
+            "USUBJID" => &["S-001", "S-002"],
+            "AETERM" => &["", " "],
+        }
+        .unwrap();
+        let issues = check(&domain(), &df, &ColumnIndex::new(&df));
+
+        assert_eq!(issues.len(), 2);
+        assert!(matches!(
+            &issues[0],
+            Issue::RequiredAllNull { variable, row_count: 2 } if variable == "AETERM"
+        ));
+        assert!(issues[0].message().contains("map source data"));
+        assert!(matches!(
+            &issues[1],
+            Issue::RequiredMissing { variable } if variable == "AEDECOD"
+        ));
+        assert!(issues[1].message().contains("add the column"));
+        assert_ne!(issues[0].rule_id(), issues[1].rule_id());
+    }
+}
@@ -5,14 +5,14 @@
 use std::collections::HashSet;
 
 use polars::prelude::DataFrame;
+use tss_standards::ColumnIndex;
 use tss_standards::SdtmDomain;
 
 use super::super::column_reader::ColumnReader;
 use super::super::issue::Issue;
-use super::super::util::CaseInsensitiveSet;
 
 /// Check that --SEQ values are unique per USUBJID.
-pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &CaseInsensitiveSet) -> Vec<Issue> {
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
     let mut issues = Vec::new();
 
     // Find the --SEQ variable for this domain
@@ -0,0 +1,131 @@
+//! --TESTCD/--TEST pairing checks (SDTMIG 4.5.2).
+//!
+//! In Findings domains each test code has exactly one test name and each
+//! test name exactly one code.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use polars::prelude::DataFrame;
+use tss_standards::ColumnIndex;
+use tss_standards::{SdtmDatasetClass, SdtmDomain, VariableRole};
+
+use super::super::column_reader::ColumnReader;
+use super::super::issue::Issue;
+
+/// Maximum number of sample values reported.
+const MAX_SAMPLES: usize = 5;
+
+/// Check that --TESTCD and --TEST map one-to-one.
+///
+/// The pair is the Topic variable ending in TESTCD and the Synonym
+/// Qualifier with the same prefix ending in TEST (e.g., LBTESTCD and
+/// LBTEST). Codes with more than one name are reported on --TESTCD, names
+/// with more than one code on --TEST. Rows missing either value are
+/// skipped. Non-Findings domains, and data without both columns, pass.
+pub fn check(domain: &SdtmDomain, df: &DataFrame, columns: &ColumnIndex) -> Vec<Issue> {
+    if !matches!(
+        domain.class,
+        Some(SdtmDatasetClass::Findings | SdtmDatasetClass::FindingsAbout)
+    ) {
+        return Vec::new();
+    }
+    let Some(code_variable) = domain.variables.iter().find(|variable| {
+        variable.role == Some(VariableRole::Topic) && variable.name.ends_with("TESTCD")
+    }) else {
+        return Vec::new();
+    };
+    let name_variable = code_variable.name.trim_end_matches("CD");
+    let Some(name_variable) = domain.variables.iter().find(|variable| {
+        variable.role == Some(VariableRole::SynonymQualifier) && variable.name == name_variable
+    }) else {
+        return Vec::new();
+    };
+    let (Some(code_column), Some(name_column)) = (
+        columns.get(&code_variable.name),
+        columns.get(&name_variable.name),
+    ) else {
+        return Vec::new();
+    };
+
+    let reader = ColumnReader::new(df);
+    let mut names_by_code: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut codes_by_name: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for idx in 0..reader.height() {
+        let code = reader.get_string(code_column, idx).trim().to_string();
+        let name = reader.get_string(name_column, idx).trim().to_string();
+        if code.is_empty() || name.is_empty() {
+            continue;
+        }
+        names_by_code
+            .entry(code.clone())
+            .or_default()
+            .insert(name.clone());
+        codes_by_name.entry(name).or_default().insert(code);
+    }
+
+    [
+        (code_variable, name_variable, names_by_code),
+        (name_variable, code_variable, codes_by_name),
+    ]
+    .into_iter()
+    .filter_map(|(variable, paired, mapping)| {
+        let conflicts: Vec<String> = mapping
+            .into_iter()
+            .filter(|(_, paired_values)| paired_values.len() > 1)
+            .map(|(value, paired_values)| {
+                let paired_values: Vec<String> = paired_values.into_iter().collect();
+                format!("{} -> {}", value, paired_values.join(" | "))
+            })
+            .collect();
+        (!conflicts.is_empty()).then(|| Issue::TestPairingInconsistent {
+            variable: variable.name.clone(),
+            paired_variable: paired.name.clone(),
+            value_count: conflicts.len() as u64,
+            samples: conflicts.into_iter().take(MAX_SAMPLES).collect(),
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn lb() -> SdtmDomain {
+        tss_standards::load_sdtm_ig()
+            .unwrap()
+            .into_iter()
+            .find(|domain| domain.name == "LB")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_consistent_pairing() {
+        let df = df! {
+            "LBTESTCD" => &["GLUC", "GLUC", "ALB", ""],
+            "LBTEST" => &["Glucose", "Glucose", "Albumin", "Sodium"],
+        }
+        .unwrap();
+        assert!(check(&lb(), &df, &ColumnIndex::new(&df)).is_empty());
+    }
+
+    #[test]
+    fn test_code_with_two_names() {
+        let df = df! {
+            "LBTESTCD" => &["GLUC", "GLUC", "ALB"],
+            "LBTEST" => &["Glucose", "Glucose, Fasting", "Albumin"],
+        }
+        .unwrap();
+        let issues = check(&lb(), &df, &ColumnIndex::new(&df));
+
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            Issue::TestPairingInconsistent { variable, paired_variable, value_count: 1, samples }
+                if variable == "LBTESTCD"
+                    && paired_variable == "LBTEST"
+                    && samples == &["GLUC -> Glucose | Glucose, Fasting"]
+        ));
+    }
+}
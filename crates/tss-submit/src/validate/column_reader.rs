@@ -23,7 +23,7 @@
 //! ```
 
 use polars::prelude::{AnyValue, Column, DataFrame};
-use tss_standards::any_to_string;
+use tss_standards::any_to_string_for_output;
 
 /// A reader for accessing DataFrame columns with common validation operations.
 ///
@@ -71,13 +71,13 @@ pub fn get_string(&self, column: &str, row_idx: usize) -> String {
             return String::new();
         };
         let value = series.get(row_idx).unwrap_or(AnyValue::Null);
-        any_to_string(value)
+        any_to_string_for_output(value)
     }
 
     /// Iterate over string values in a column.
     ///
     /// Returns an iterator that yields (row_index, string_value) pairs.
-    /// The string value is the result of `any_to_string()` on the cell value.
+    /// The string value is the result of `any_to_string_for_output()` on the cell value.
     pub fn values(&self, column_name: &str) -> Option<ColumnValueIter<'_>> {
         let column = self.column(column_name)?;
         Some(ColumnValueIter {
@@ -89,7 +89,7 @@ pub fn values(&self, column_name: &str) -> Option<ColumnValueIter<'_>> {
 
     /// Count null or empty string values in a column.
     ///
-    /// A value is considered null/empty if `any_to_string(value).trim().is_empty()`.
+    /// A value is considered null/empty if `any_to_string_for_output(value).trim().is_empty()`.
     pub fn count_nulls(&self, column: &str) -> u64 {
         let Some(series) = self.column(column) else {
             return 0;
@@ -98,7 +98,7 @@ pub fn count_nulls(&self, column: &str) -> u64 {
         let mut count = 0u64;
         for idx in 0..self.df.height() {
             let value = series.get(idx).unwrap_or(AnyValue::Null);
-            let str_value = any_to_string(value);
+            let str_value = any_to_string_for_output(value);
             if str_value.trim().is_empty() {
                 count += 1;
             }
@@ -133,7 +133,7 @@ pub fn count_matching<F>(&self, column: &str, predicate: F) -> u64
         let mut count = 0u64;
         for idx in 0..self.df.height() {
             let value = series.get(idx).unwrap_or(AnyValue::Null);
-            let str_value = any_to_string(value);
+            let str_value = any_to_string_for_output(value);
             let trimmed = str_value.trim();
 
             if !trimmed.is_empty() && predicate(trimmed) {
@@ -168,7 +168,7 @@ pub fn collect_matching<F>(
 
         for idx in 0..self.df.height() {
             let value = series.get(idx).unwrap_or(AnyValue::Null);
-            let str_value = any_to_string(value);
+            let str_value = any_to_string_for_output(value);
             let trimmed = str_value.trim();
 
             if !trimmed.is_empty() && predicate(trimmed) {
@@ -202,7 +202,7 @@ pub fn collect_unique_matching<F>(
 
         for idx in 0..self.df.height() {
             let value = series.get(idx).unwrap_or(AnyValue::Null);
-            let str_value = any_to_string(value);
+            let str_value = any_to_string_for_output(value);
             let trimmed = str_value.trim();
 
             if !trimmed.is_empty() && predicate(trimmed) {
@@ -228,7 +228,7 @@ pub fn length_violations(&self, column: &str, max_length: u32) -> (u64, usize) {
 
         for idx in 0..self.df.height() {
             let value = series.get(idx).unwrap_or(AnyValue::Null);
-            let str_value = any_to_string(value);
+            let str_value = any_to_string_for_output(value);
             let len = str_value.len();
 
             if len > max_length as usize {
@@ -265,8 +265,9 @@ pub fn values_by_subject(
         };
 
         for idx in 0..self.df.height() {
-            let subject = any_to_string(subject_series.get(idx).unwrap_or(AnyValue::Null));
-            let value = any_to_string(value_series.get(idx).unwrap_or(AnyValue::Null));
+            let subject =
+                any_to_string_for_output(subject_series.get(idx).unwrap_or(AnyValue::Null));
+            let value = any_to_string_for_output(value_series.get(idx).unwrap_or(AnyValue::Null));
 
             if subject.trim().is_empty() {
                 continue;
@@ -301,7 +302,7 @@ fn next(&mut self) -> Option<Self::Item> {
         self.current += 1;
 
         let value = self.column.get(idx).unwrap_or(AnyValue::Null);
-        Some((idx, any_to_string(value)))
+        Some((idx, any_to_string_for_output(value)))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
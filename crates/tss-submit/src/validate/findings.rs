@@ -0,0 +1,132 @@
+//! Flat CSV export of validation findings.
+//!
+//! Produces one row per issue in a layout similar to Pinnacle 21 Community
+//! reports, so QC can compare findings side by side.
+
+use std::path::Path;
+
+use super::issue::Severity;
+use super::report::ValidationReport;
+use super::rules::p21_rule_id;
+use crate::error::{Result, SubmitError};
+
+/// Column headers of the findings CSV.
+pub const FINDINGS_CSV_HEADERS: [&str; 6] = [
+    "Dataset", "Variable", "RuleID", "Severity", "Message", "Count",
+];
+
+/// Write validation findings for several domains to a CSV file.
+///
+/// Rows are sorted by dataset, then severity (Reject first). `RuleID` is the
+/// Pinnacle 21 rule ID where one exists (see [`p21_rule_id`]), otherwise the
+/// internal rule ID. Cross-domain issues are listed under the domain that
+/// owns them, e.g. RELREC for an invalid RELREC reference.
+pub fn write_findings_csv(reports: &[ValidationReport], path: &Path) -> Result<()> {
+    let write_error =
+        |e: csv::Error| SubmitError::write_error("CSV", path.display().to_string(), e);
+
+    let mut rows = Vec::new();
+    for report in reports {
+        for issue in &report.issues {
+            let dataset = issue.domain().unwrap_or(&report.domain).to_uppercase();
+            let severity = report.severity_of(issue);
+            let rule_id = p21_rule_id(issue).unwrap_or_else(|| issue.rule_id());
+            let count = issue.count().map(|c| c.to_string()).unwrap_or_default();
+            rows.push((
+                dataset,
+                severity_rank(severity),
+                [
+                    issue.variable().to_string(),
+                    rule_id.to_string(),
+                    severity.label().to_string(),
+                    issue.message(),
+                    count,
+                ],
+            ));
+        }
+    }
+    rows.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+    let mut writer = csv::Writer::from_path(path).map_err(write_error)?;
+    writer
+        .write_record(FINDINGS_CSV_HEADERS)
+        .map_err(write_error)?;
+    for (dataset, _, fields) in &rows {
+        writer
+            .write_record(std::iter::once(dataset).chain(fields))
+            .map_err(write_error)?;
+    }
+    writer
+        .flush()
+        .map_err(|e| SubmitError::write_error("CSV", path.display().to_string(), e))
+}
+
+/// Sort rank for a severity (most severe first).
+fn severity_rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Reject => 0,
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::Issue;
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    const GOLDEN: &str = include_str!("../../tests/data/findings.csv");
+
+    #[test]
+    fn test_write_findings_csv_golden() {
+        let mut lb = ValidationReport::new("LB");
+        lb.add(Issue::ExpectedMissing {
+            variable: "LBORRESU".to_string(),
+        });
+        lb.add(Issue::DuplicateSequence {
+            variable: "LBSEQ".to_string(),
+            duplicate_count: 2,
+        });
+
+        let mut ae = ValidationReport::new("AE");
+        ae.add(Issue::CtViolation {
+            variable: "AESEV".to_string(),
+            codelist_code: "C66769".to_string(),
+            codelist_name: "Severity/Intensity Scale for Adverse Events".to_string(),
+            extensible: false,
+            total_invalid: 1,
+            invalid_values: vec!["SEVERE, GRADE 3".to_string()],
+            allowed_count: 3,
+            suggestions: BTreeMap::new(),
+        });
+        ae.add(Issue::TextTooLong {
+            variable: "AETERM".to_string(),
+            exceeded_count: 1,
+            max_found: 214,
+            max_allowed: 200,
+        });
+
+        // Cross-domain issues reported together, listed under their owners
+        let mut cross = ValidationReport::new("STUDY");
+        cross.add(Issue::UsubjidNotInDm {
+            domain: "VS".to_string(),
+            missing_count: 1,
+            samples: vec!["CDISC01-099".to_string()],
+        });
+        cross.add(Issue::RelrecInvalidReference {
+            rdomain: "AE".to_string(),
+            invalid_count: 2,
+            samples: vec![],
+        });
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("findings.csv");
+        write_findings_csv(&[lb, ae, cross], &path).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written.replace("\r\n", "\n"), GOLDEN.replace("\r\n", "\n"));
+    }
+}
@@ -3,9 +3,11 @@
 //! The Issue enum provides type-safe validation issue creation where
 //! each variant carries only its needed data.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-use super::rules::Category;
+use super::rules::{Category, RuleRegistry};
 
 /// Issue severity level.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -51,6 +53,8 @@ pub enum Issue {
     RequiredMissing { variable: String },
     /// Required variable exists but has null values
     RequiredEmpty { variable: String, null_count: u64 },
+    /// Required variable exists but every value is null
+    RequiredAllNull { variable: String, row_count: u64 },
     /// Expected variable is missing from the dataset
     ExpectedMissing { variable: String },
     /// Identifier variable has null values
@@ -63,6 +67,14 @@ pub enum Issue {
         invalid_count: u64,
         samples: Vec<String>,
     },
+    /// Date values use separators other than hyphens (e.g., "2023/01/02")
+    NonHyphenDate {
+        variable: String,
+        invalid_count: u64,
+        samples: Vec<String>,
+        /// Hyphenated form of each sample
+        suggestions: Vec<String>,
+    },
     /// Text values exceed maximum length
     TextTooLong {
         variable: String,
@@ -70,6 +82,32 @@ pub enum Issue {
         max_found: usize,
         max_allowed: u32,
     },
+    /// Variable label exceeds the 40-character XPT limit
+    LabelTooLong {
+        variable: String,
+        label: String,
+        /// Label length in characters
+        length: usize,
+    },
+    /// Character values contain non-ASCII or control characters
+    NonAsciiText {
+        variable: String,
+        invalid_count: u64,
+        /// Sample values with offending characters escaped (e.g., `\u{2013}`)
+        samples: Vec<String>,
+    },
+    /// Numeric values outside their plausible range (e.g., AGE > 120)
+    ValueOutOfRange {
+        variable: String,
+        /// --TESTCD the range applies to, if any
+        test_code: Option<String>,
+        min: Option<f64>,
+        max: Option<f64>,
+        unit: Option<String>,
+        invalid_count: u64,
+        /// Sample rows, e.g. "row 3: 121"
+        samples: Vec<String>,
+    },
 
     // Type checks
     /// Numeric variable contains non-numeric values
@@ -85,6 +123,55 @@ pub enum Issue {
         variable: String,
         duplicate_count: u64,
     },
+    /// Records share the same natural key (identifier, topic, and timing values)
+    DuplicateRecord {
+        /// Topic variable of the domain
+        variable: String,
+        /// Variables making up the natural key
+        key_variables: Vec<String>,
+        /// Number of keys shared by more than one record
+        group_count: u64,
+        /// Number of records beyond the first in each group
+        duplicate_count: u64,
+        /// Sample keys, values joined with "/"
+        samples: Vec<String>,
+    },
+    /// Subject-level dataset (ADSL) has more than one record per USUBJID
+    DuplicateSubject {
+        /// Number of records beyond the first for each subject
+        duplicate_count: u64,
+        /// Sample USUBJIDs with more than one record
+        samples: Vec<String>,
+    },
+    /// --TESTCD values with more than one --TEST, or the reverse
+    TestPairingInconsistent {
+        /// Variable whose values map to several values of the other
+        variable: String,
+        /// The other variable of the pair
+        paired_variable: String,
+        /// Number of values with more than one pairing
+        value_count: u64,
+        /// Sample conflicts, e.g. "GLUC -> Glucose | Glucose, Fasting"
+        samples: Vec<String>,
+    },
+    /// DOMAIN values differ from the code of the domain (e.g., "AE" in DM)
+    DomainValueMismatch {
+        /// Expected domain code
+        expected: String,
+        mismatch_count: u64,
+        /// Distinct wrong values
+        samples: Vec<String>,
+    },
+    /// Start date/time is after the paired end date/time (e.g., AESTDTC > AEENDTC)
+    DateOrderViolation {
+        /// The --STDTC variable
+        variable: String,
+        /// The paired --ENDTC variable
+        end_variable: String,
+        invalid_count: u64,
+        /// Sample USUBJIDs of affected records
+        samples: Vec<String>,
+    },
 
     // Terminology checks
     /// Values not found in controlled terminology
@@ -98,6 +185,10 @@ pub enum Issue {
         /// Sample of invalid values (up to 5)
         invalid_values: Vec<String>,
         allowed_count: usize,
+        /// Closest submission value for each sampled invalid value that has
+        /// one similar enough ("did you mean")
+        #[serde(default)]
+        suggestions: BTreeMap<String, String>,
     },
 
     // Cross-domain reference checks (#114)
@@ -143,6 +234,37 @@ pub enum Issue {
         invalid_count: u64,
         samples: Vec<String>,
     },
+    /// ADaM traceability variables (SRCDOM/SRCSEQ) reference an SDTM
+    /// domain or record that doesn't exist
+    TraceabilityBroken {
+        /// SRCDOM for unknown domains, SRCSEQ for missing records
+        variable: String,
+        invalid_count: u64,
+        /// Sample rows, e.g. "S-001 AE/7"
+        samples: Vec<String>,
+    },
+    /// Domain has STUDYID values other than the study's, or empty ones
+    StudyidInconsistent {
+        domain: String,
+        /// STUDYID used by most records in the study
+        expected: Option<String>,
+        /// Other STUDYID values in this domain
+        found: Vec<String>,
+        mismatch_count: u64,
+        empty_count: u64,
+    },
+    /// SUPP-- IDVAR/IDVARVAL references a parent record that doesn't exist
+    SuppInvalidParent {
+        domain: String,
+        invalid_count: u64,
+        samples: Vec<String>,
+    },
+    /// SUPP-- has more than one record with the same QNAM for a parent record
+    SuppDuplicateQnam {
+        domain: String,
+        duplicate_count: u64,
+        samples: Vec<String>,
+    },
 }
 
 impl Issue {
@@ -151,12 +273,22 @@ pub fn variable(&self) -> &str {
         match self {
             Issue::RequiredMissing { variable } => variable,
             Issue::RequiredEmpty { variable, .. } => variable,
+            Issue::RequiredAllNull { variable, .. } => variable,
             Issue::ExpectedMissing { variable } => variable,
             Issue::IdentifierNull { variable, .. } => variable,
             Issue::InvalidDate { variable, .. } => variable,
+            Issue::NonHyphenDate { variable, .. } => variable,
             Issue::TextTooLong { variable, .. } => variable,
+            Issue::LabelTooLong { variable, .. } => variable,
+            Issue::NonAsciiText { variable, .. } => variable,
+            Issue::ValueOutOfRange { variable, .. } => variable,
             Issue::DataTypeMismatch { variable, .. } => variable,
             Issue::DuplicateSequence { variable, .. } => variable,
+            Issue::DuplicateRecord { variable, .. } => variable,
+            Issue::DuplicateSubject { .. } => "USUBJID",
+            Issue::DomainValueMismatch { .. } => "DOMAIN",
+            Issue::TestPairingInconsistent { variable, .. } => variable,
+            Issue::DateOrderViolation { variable, .. } => variable,
             Issue::CtViolation { variable, .. } => variable,
             // Cross-domain issues use USUBJID or the specific variable
             Issue::UsubjidNotInDm { .. } => "USUBJID",
@@ -167,6 +299,28 @@ pub fn variable(&self) -> &str {
             Issue::RelsubNotBidirectional { .. } => "SREL",
             Issue::RelspecInvalidParent { .. } => "PARENT",
             Issue::RelrecInvalidReference { .. } => "IDVARVAL",
+            Issue::TraceabilityBroken { variable, .. } => variable,
+            Issue::StudyidInconsistent { .. } => "STUDYID",
+            Issue::SuppInvalidParent { .. } => "IDVARVAL",
+            Issue::SuppDuplicateQnam { .. } => "QNAM",
+        }
+    }
+
+    /// Domain that owns a cross-domain issue.
+    ///
+    /// Returns `None` for issues found while validating a single domain,
+    /// which belong to the domain being validated.
+    pub fn domain(&self) -> Option<&str> {
+        match self {
+            Issue::UsubjidNotInDm { domain, .. } => Some(domain),
+            Issue::InvalidRdomain { domain, .. } => Some(domain),
+            Issue::RelsubNotInDm { .. } | Issue::RelsubNotBidirectional { .. } => Some("RELSUB"),
+            Issue::RelspecInvalidParent { .. } => Some("RELSPEC"),
+            Issue::RelrecInvalidReference { .. } => Some("RELREC"),
+            Issue::StudyidInconsistent { domain, .. } => Some(domain),
+            Issue::SuppInvalidParent { domain, .. } => Some(domain),
+            Issue::SuppDuplicateQnam { domain, .. } => Some(domain),
+            _ => None,
         }
     }
 
@@ -175,16 +329,30 @@ pub fn count(&self) -> Option<u64> {
         match self {
             Issue::RequiredMissing { .. } => None,
             Issue::ExpectedMissing { .. } => None,
+            Issue::LabelTooLong { .. } => None,
             Issue::RequiredEmpty { null_count, .. } => Some(*null_count),
+            Issue::RequiredAllNull { row_count, .. } => Some(*row_count),
             Issue::IdentifierNull { null_count, .. } => Some(*null_count),
             Issue::InvalidDate { invalid_count, .. } => Some(*invalid_count),
+            Issue::NonHyphenDate { invalid_count, .. } => Some(*invalid_count),
             Issue::TextTooLong { exceeded_count, .. } => Some(*exceeded_count),
+            Issue::NonAsciiText { invalid_count, .. } => Some(*invalid_count),
+            Issue::ValueOutOfRange { invalid_count, .. } => Some(*invalid_count),
             Issue::DataTypeMismatch {
                 non_numeric_count, ..
             } => Some(*non_numeric_count),
             Issue::DuplicateSequence {
                 duplicate_count, ..
             } => Some(*duplicate_count),
+            Issue::DuplicateRecord {
+                duplicate_count, ..
+            } => Some(*duplicate_count),
+            Issue::DuplicateSubject {
+                duplicate_count, ..
+            } => Some(*duplicate_count),
+            Issue::DomainValueMismatch { mismatch_count, .. } => Some(*mismatch_count),
+            Issue::TestPairingInconsistent { value_count, .. } => Some(*value_count),
+            Issue::DateOrderViolation { invalid_count, .. } => Some(*invalid_count),
             Issue::CtViolation { total_invalid, .. } => Some(*total_invalid),
             Issue::UsubjidNotInDm { missing_count, .. } => Some(*missing_count),
             Issue::ParentNotFound { missing_count, .. } => Some(*missing_count),
@@ -194,6 +362,16 @@ pub fn count(&self) -> Option<u64> {
             Issue::RelsubNotBidirectional { missing_count, .. } => Some(*missing_count),
             Issue::RelspecInvalidParent { invalid_count, .. } => Some(*invalid_count),
             Issue::RelrecInvalidReference { invalid_count, .. } => Some(*invalid_count),
+            Issue::TraceabilityBroken { invalid_count, .. } => Some(*invalid_count),
+            Issue::StudyidInconsistent {
+                mismatch_count,
+                empty_count,
+                ..
+            } => Some(mismatch_count + empty_count),
+            Issue::SuppInvalidParent { invalid_count, .. } => Some(*invalid_count),
+            Issue::SuppDuplicateQnam {
+                duplicate_count, ..
+            } => Some(*duplicate_count),
         }
     }
 
@@ -203,15 +381,25 @@ pub fn category(&self) -> Category {
             // Presence checks
             Issue::RequiredMissing { .. } => Category::Presence,
             Issue::RequiredEmpty { .. } => Category::Presence,
+            Issue::RequiredAllNull { .. } => Category::Presence,
             Issue::ExpectedMissing { .. } => Category::Presence,
             Issue::IdentifierNull { .. } => Category::Presence,
             // Format checks
             Issue::InvalidDate { .. } => Category::Format,
+            Issue::NonHyphenDate { .. } => Category::Format,
             Issue::TextTooLong { .. } => Category::Limit,
+            Issue::LabelTooLong { .. } => Category::Limit,
+            Issue::NonAsciiText { .. } => Category::Format,
+            Issue::ValueOutOfRange { .. } => Category::Limit,
             // Type checks
             Issue::DataTypeMismatch { .. } => Category::Format,
             // Consistency checks
             Issue::DuplicateSequence { .. } => Category::Consistency,
+            Issue::DuplicateRecord { .. } => Category::Consistency,
+            Issue::DuplicateSubject { .. } => Category::Consistency,
+            Issue::DomainValueMismatch { .. } => Category::Consistency,
+            Issue::TestPairingInconsistent { .. } => Category::Consistency,
+            Issue::DateOrderViolation { .. } => Category::Consistency,
             // Terminology checks
             Issue::CtViolation { .. } => Category::Terminology,
             // Cross-domain reference checks
@@ -223,14 +411,66 @@ pub fn category(&self) -> Category {
             Issue::RelsubNotBidirectional { .. } => Category::CrossReference,
             Issue::RelspecInvalidParent { .. } => Category::CrossReference,
             Issue::RelrecInvalidReference { .. } => Category::CrossReference,
+            Issue::TraceabilityBroken { .. } => Category::CrossReference,
+            Issue::StudyidInconsistent { .. } => Category::Consistency,
+            Issue::SuppInvalidParent { .. } => Category::CrossReference,
+            Issue::SuppDuplicateQnam { .. } => Category::Consistency,
         }
     }
 
-    /// Severity for this issue type.
-    pub fn severity(&self) -> Severity {
+    /// Rule ID for this issue type (see [`RuleRegistry`]).
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            Issue::RequiredMissing { .. } => "required_missing",
+            Issue::RequiredEmpty { .. } => "required_empty",
+            Issue::RequiredAllNull { .. } => "required_all_null",
+            Issue::ExpectedMissing { .. } => "expected_missing",
+            Issue::IdentifierNull { .. } => "identifier_null",
+            Issue::InvalidDate { .. } => "invalid_date",
+            Issue::NonHyphenDate { .. } => "non_hyphen_date",
+            Issue::TextTooLong { .. } => "text_too_long",
+            Issue::LabelTooLong { .. } => "label_too_long",
+            Issue::NonAsciiText { .. } => "non_ascii_text",
+            Issue::ValueOutOfRange { .. } => "value_out_of_range",
+            Issue::DataTypeMismatch { .. } => "data_type_mismatch",
+            Issue::DuplicateSequence { .. } => "duplicate_sequence",
+            Issue::DuplicateRecord { .. } => "duplicate_record",
+            Issue::DuplicateSubject { .. } => "duplicate_subject",
+            Issue::DomainValueMismatch { .. } => "domain_value_mismatch",
+            Issue::TestPairingInconsistent { .. } => "test_pairing_inconsistent",
+            Issue::DateOrderViolation { .. } => "date_order_violation",
+            Issue::CtViolation { .. } => "ct_violation",
+            Issue::UsubjidNotInDm { .. } => "usubjid_not_in_dm",
+            Issue::ParentNotFound { .. } => "parent_not_found",
+            Issue::InvalidRdomain { .. } => "invalid_rdomain",
+            Issue::RelsubNotInDm { .. } => "relsub_not_in_dm",
+            Issue::RelsubNotBidirectional { .. } => "relsub_not_bidirectional",
+            Issue::RelspecInvalidParent { .. } => "relspec_invalid_parent",
+            Issue::RelrecInvalidReference { .. } => "relrec_invalid_reference",
+            Issue::TraceabilityBroken { .. } => "traceability_broken",
+            Issue::StudyidInconsistent { .. } => "studyid_inconsistent",
+            Issue::SuppInvalidParent { .. } => "supp_invalid_parent",
+            Issue::SuppDuplicateQnam { .. } => "supp_duplicate_qnam",
+        }
+    }
+
+    /// Severity for this issue, applying any override from `rules`.
+    pub fn severity(&self, rules: Option<&RuleRegistry>) -> Severity {
+        rules
+            .and_then(|rules| rules.severity_override(self.rule_id()))
+            .unwrap_or_else(|| self.default_severity())
+    }
+
+    /// Built-in severity for this issue type.
+    fn default_severity(&self) -> Severity {
         match self {
             Issue::ExpectedMissing { .. } => Severity::Warning,
             Issue::TextTooLong { .. } => Severity::Warning,
+            // Metadata, not data; XPT output truncates the label
+            Issue::LabelTooLong { .. } => Severity::Warning,
+            Issue::ValueOutOfRange { .. } => Severity::Warning,
+            // May be legitimate repeats (e.g., unscheduled retests); needs review
+            Issue::DuplicateRecord { .. } => Severity::Warning,
             Issue::CtViolation {
                 extensible: true, ..
             } => Severity::Info,
@@ -243,6 +483,10 @@ pub fn severity(&self) -> Severity {
             Issue::RelsubNotBidirectional { .. } => Severity::Warning,
             Issue::RelspecInvalidParent { .. } => Severity::Error,
             Issue::RelrecInvalidReference { .. } => Severity::Error,
+            Issue::TraceabilityBroken { .. } => Severity::Error,
+            Issue::StudyidInconsistent { .. } => Severity::Error,
+            Issue::SuppInvalidParent { .. } => Severity::Error,
+            Issue::SuppDuplicateQnam { .. } => Severity::Error,
             _ => Severity::Error,
         }
     }
@@ -251,7 +495,20 @@ pub fn severity(&self) -> Severity {
     pub fn message(&self) -> String {
         match self {
             Issue::RequiredMissing { variable } => {
-                format!("Required variable {} is missing", variable)
+                format!(
+                    "Required variable {} is missing; add the column to the dataset",
+                    variable
+                )
+            }
+
+            Issue::RequiredAllNull {
+                variable,
+                row_count,
+            } => {
+                format!(
+                    "Required variable {} is present but all {} values are null; map source data to it",
+                    variable, row_count
+                )
             }
 
             Issue::RequiredEmpty {
@@ -294,6 +551,28 @@ pub fn message(&self) -> String {
                 )
             }
 
+            Issue::NonHyphenDate {
+                variable,
+                invalid_count,
+                samples,
+                suggestions,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    let pairs: Vec<String> = samples
+                        .iter()
+                        .zip(suggestions)
+                        .map(|(sample, fixed)| format!("{} -> {}", sample, fixed))
+                        .collect();
+                    format!(" (e.g., {})", pairs.join(", "))
+                };
+                format!(
+                    "Variable {} has {} date values not using ISO 8601 hyphen separators{}",
+                    variable, invalid_count, sample_str
+                )
+            }
+
             Issue::TextTooLong {
                 variable,
                 exceeded_count,
@@ -306,6 +585,67 @@ pub fn message(&self) -> String {
                 )
             }
 
+            Issue::LabelTooLong {
+                variable,
+                label,
+                length,
+            } => {
+                format!(
+                    "Variable {} label is {} characters, exceeding max length 40: \"{}\"",
+                    variable, length, label
+                )
+            }
+
+            Issue::NonAsciiText {
+                variable,
+                invalid_count,
+                samples,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "Variable {} has {} values with non-ASCII or control characters{}",
+                    variable, invalid_count, sample_str
+                )
+            }
+
+            Issue::ValueOutOfRange {
+                variable,
+                test_code,
+                min,
+                max,
+                unit,
+                invalid_count,
+                samples,
+            } => {
+                let test_str = test_code
+                    .as_ref()
+                    .map(|code| format!(" for {}", code))
+                    .unwrap_or_default();
+                let range_str = match (min, max) {
+                    (Some(min), Some(max)) => format!("{} to {}", min, max),
+                    (Some(min), None) => format!(">= {}", min),
+                    (None, Some(max)) => format!("<= {}", max),
+                    (None, None) => "any".to_string(),
+                };
+                let unit_str = unit
+                    .as_ref()
+                    .map(|unit| format!(" {}", unit))
+                    .unwrap_or_default();
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "Variable {}{} has {} values outside the plausible range {}{}{}",
+                    variable, test_str, invalid_count, range_str, unit_str, sample_str
+                )
+            }
+
             Issue::DataTypeMismatch {
                 variable,
                 non_numeric_count,
@@ -332,12 +672,96 @@ pub fn message(&self) -> String {
                 )
             }
 
+            Issue::DuplicateRecord {
+                key_variables,
+                group_count,
+                duplicate_count,
+                samples,
+                ..
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "{} duplicate records in {} groups sharing {}{}",
+                    duplicate_count,
+                    group_count,
+                    key_variables.join("/"),
+                    sample_str
+                )
+            }
+
+            Issue::DuplicateSubject {
+                duplicate_count,
+                samples,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "{} extra records for subjects with more than one record in a subject-level dataset{}",
+                    duplicate_count, sample_str
+                )
+            }
+
+            Issue::TestPairingInconsistent {
+                variable,
+                paired_variable,
+                value_count,
+                samples,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "{} has {} values paired with more than one {}{}",
+                    variable, value_count, paired_variable, sample_str
+                )
+            }
+
+            Issue::DomainValueMismatch {
+                expected,
+                mismatch_count,
+                samples,
+            } => {
+                format!(
+                    "DOMAIN has {} values other than the domain code (expected {}, found {})",
+                    mismatch_count,
+                    expected,
+                    samples.join(", ")
+                )
+            }
+
+            Issue::DateOrderViolation {
+                variable,
+                end_variable,
+                invalid_count,
+                samples,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "{} is after {} in {} records{}",
+                    variable, end_variable, invalid_count, sample_str
+                )
+            }
+
             Issue::CtViolation {
                 variable,
                 codelist_name,
                 extensible,
                 total_invalid,
                 invalid_values,
+                suggestions,
                 ..
             } => {
                 let sample_count = invalid_values.len() as u64;
@@ -353,18 +777,27 @@ pub fn message(&self) -> String {
                 } else {
                     format!(": {}", invalid_values.join(", "))
                 };
+                let suggestions_str = if suggestions.is_empty() {
+                    String::new()
+                } else {
+                    let hints: Vec<String> = suggestions
+                        .iter()
+                        .map(|(value, suggested)| format!("'{}' for '{}'", suggested, value))
+                        .collect();
+                    format!("; did you mean {}?", hints.join(", "))
+                };
 
                 if *extensible {
                     // Info: custom values are allowed per CDISC for extensible codelists
                     format!(
-                        "Variable {} uses {} custom values not in codelist {} (allowed - extensible codelist){}",
-                        variable, total_invalid, codelist_name, values_str
+                        "Variable {} uses {} custom values not in codelist {} (allowed - extensible codelist){}{}",
+                        variable, total_invalid, codelist_name, values_str, suggestions_str
                     )
                 } else {
                     // Error: non-extensible codelist, values must be in codelist
                     format!(
-                        "Variable {} has {} invalid values not in codelist {} (non-extensible){}",
-                        variable, total_invalid, codelist_name, values_str
+                        "Variable {} has {} invalid values not in codelist {} (non-extensible){}{}",
+                        variable, total_invalid, codelist_name, values_str, suggestions_str
                     )
                 }
             }
@@ -479,6 +912,81 @@ pub fn message(&self) -> String {
                     invalid_count, rdomain, sample_str
                 )
             }
+
+            Issue::TraceabilityBroken {
+                variable,
+                invalid_count,
+                samples,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                let target = if variable == "SRCDOM" {
+                    "unknown SDTM domains"
+                } else {
+                    "non-existent SDTM records"
+                };
+                format!(
+                    "{} has {} values referencing {}{}",
+                    variable, invalid_count, target, sample_str
+                )
+            }
+
+            Issue::StudyidInconsistent {
+                domain,
+                expected,
+                found,
+                mismatch_count,
+                empty_count,
+            } => {
+                let mut problems = Vec::new();
+                if *mismatch_count > 0 {
+                    problems.push(format!(
+                        "{} records with STUDYID {} instead of {}",
+                        mismatch_count,
+                        found.join(", "),
+                        expected.as_deref().unwrap_or_default()
+                    ));
+                }
+                if *empty_count > 0 {
+                    problems.push(format!("{} records with empty STUDYID", empty_count));
+                }
+                format!("{} has {}", domain, problems.join(" and "))
+            }
+
+            Issue::SuppInvalidParent {
+                domain,
+                invalid_count,
+                samples,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "{} has {} records referencing non-existent parent records{}",
+                    domain, invalid_count, sample_str
+                )
+            }
+
+            Issue::SuppDuplicateQnam {
+                domain,
+                duplicate_count,
+                samples,
+            } => {
+                let sample_str = if samples.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (e.g., {})", samples.join(", "))
+                };
+                format!(
+                    "{} has {} duplicate QNAM values for the same parent record{}",
+                    domain, duplicate_count, sample_str
+                )
+            }
         }
     }
 }
@@ -9,7 +9,9 @@
 //! - **ISO 8601 Date Validation**: Validates date/datetime format compliance
 //! - **Sequence Uniqueness**: Checks for duplicate --SEQ per subject
 //! - **Text Length**: Validates character field lengths
+//! - **ASCII Characters**: Flags non-ASCII and control characters that XPT can't hold
 //! - **Identifier Nulls**: Checks that ID variables have no nulls
+//! - **ADaM Structure**: ADSL one record per subject, BDS parameter identifiers
 //!
 //! # Example
 //!
@@ -21,29 +23,35 @@
 //!
 //! // Display issues
 //! for issue in &report.issues {
-//!     println!("[{:?}] {}: {}", issue.severity(), issue.category(), issue.message());
+//!     println!("[{:?}] {}: {}", issue.severity(None), issue.category(), issue.message());
 //! }
 //! ```
 
+mod adam;
 mod checks;
 mod column_reader;
+mod findings;
 mod issue;
 mod report;
 pub mod rules;
-mod util;
 
 use polars::prelude::DataFrame;
-use std::collections::{BTreeSet, HashSet};
-use tss_standards::SdtmDomain;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use tss_standards::TerminologyRegistry;
+use tss_standards::{SdtmDomain, Standard};
 
 // Re-export public types
+pub(crate) use adam::matches_template;
+pub use adam::{validate_adam_dataset, validate_adam_traceability};
+pub use checks::cross_domain::relrec_key_variables;
 pub use checks::dates::is_date_variable;
+pub use checks::ranges::{RangeRule, RangeRuleTable};
 pub use column_reader::ColumnReader;
+pub use findings::{FINDINGS_CSV_HEADERS, write_findings_csv};
 pub use issue::{Issue, Severity};
-pub use report::ValidationReport;
-pub use rules::Category;
-pub use util::CaseInsensitiveSet;
+pub use report::{IssueRecord, ValidationReport};
+pub use rules::{Category, RuleError, RuleRegistry, load_default_rules, load_rules, p21_rule_id};
 
 /// Validate a single domain against SDTM conformance rules.
 ///
@@ -56,6 +64,9 @@
 /// - Unique sequence numbers per subject
 /// - Text length limits
 /// - Identifier null checks
+///
+/// Codelists are resolved in the CT package of the standard `ct_registry`
+/// was loaded for (SDTM if it has no primary catalog).
 pub fn validate_domain(
     domain: &SdtmDomain,
     df: &DataFrame,
@@ -80,7 +91,85 @@ pub fn validate_domain_with_not_collected(
     ct_registry: Option<&TerminologyRegistry>,
     not_collected: &BTreeSet<String>,
 ) -> ValidationReport {
-    checks::run_all(domain, df, ct_registry, not_collected)
+    let standard = ct_registry
+        .and_then(TerminologyRegistry::primary_standard)
+        .unwrap_or(Standard::Sdtm);
+    validate_domain_with_rules(
+        domain,
+        df,
+        ct_registry,
+        standard,
+        not_collected,
+        &load_default_rules(),
+    )
+}
+
+/// Validate a single domain with sponsor rule settings.
+///
+/// Codelists are resolved in the CT package of `standard` first, so a
+/// SEND domain converted with [`SdtmDomain::from`] is checked against SEND
+/// CT. Disabled rules are skipped, and the report's error/warning counts
+/// use the severity overrides in `rules` (see [`load_rules`]).
+pub fn validate_domain_with_rules(
+    domain: &SdtmDomain,
+    df: &DataFrame,
+    ct_registry: Option<&TerminologyRegistry>,
+    standard: Standard,
+    not_collected: &BTreeSet<String>,
+    rules: &RuleRegistry,
+) -> ValidationReport {
+    checks::run_all(domain, df, ct_registry, standard, not_collected, rules)
+}
+
+/// Validate every dataset of a study against its domain.
+///
+/// Each dataset in `frames` (keyed by dataset name) is validated with
+/// [`validate_domain_with_rules`] against the domain of the same name. SUPP--
+/// datasets are validated against SUPPQUAL, and split datasets of
+/// general-observation domains against their parent (e.g., LB for LBCH).
+/// Codelists are resolved in the CT package of `standard` first, and each
+/// dataset's variables in `not_collected` (keyed by dataset name) are exempt
+/// from ExpectedMissing warnings. Datasets without a matching
+/// domain are skipped. With `parallel`, datasets are validated concurrently;
+/// the reports are the same either way. Cross-domain checks are not run; see
+/// [`validate_study_cross_domain`].
+pub fn validate_all_domains(
+    domains: &[SdtmDomain],
+    frames: &BTreeMap<String, DataFrame>,
+    ct_registry: Option<&TerminologyRegistry>,
+    standard: Standard,
+    not_collected: &BTreeMap<String, BTreeSet<String>>,
+    rules: &RuleRegistry,
+    parallel: bool,
+) -> BTreeMap<String, ValidationReport> {
+    let none_collected = BTreeSet::new();
+    let entries: Vec<(&String, &SdtmDomain, &DataFrame)> = frames
+        .iter()
+        .filter_map(|(name, df)| {
+            let domain = domain_for_dataset(domains, name);
+            if domain.is_none() {
+                tracing::debug!(dataset = %name, "No domain definition - skipping validation");
+            }
+            Some((name, domain?, df))
+        })
+        .collect();
+
+    let validate = |(name, domain, df): &(&String, &SdtmDomain, &DataFrame)| {
+        let report = validate_domain_with_rules(
+            domain,
+            df,
+            ct_registry,
+            standard,
+            not_collected.get(*name).unwrap_or(&none_collected),
+            rules,
+        );
+        ((*name).clone(), report)
+    };
+    if parallel {
+        entries.par_iter().map(validate).collect()
+    } else {
+        entries.iter().map(validate).collect()
+    }
 }
 
 /// Validate cross-domain references across all domains.
@@ -91,13 +180,19 @@ pub fn validate_domain_with_not_collected(
 /// - RELSUB RSUBJID exists in DM and relationships are bidirectional
 /// - RELSPEC PARENT references valid REFID within subject
 /// - RELREC references point to existing records
+/// - SUPP-- records point to existing parent records, one per QNAM
+/// - --SEQ values are unique per subject across split datasets (e.g., LBCH/LBHE)
 ///
 /// # Arguments
 /// * `domains` - List of (domain_name, DataFrame) pairs
+/// * `rules` - Rule settings; issues for disabled rules are not reported
 ///
 /// # Returns
 /// A vector of (domain_name, issues) pairs for domains with issues.
-pub fn validate_cross_domain(domains: &[(&str, &DataFrame)]) -> Vec<(String, Vec<Issue>)> {
+pub fn validate_cross_domain(
+    domains: &[(&str, &DataFrame)],
+    rules: &RuleRegistry,
+) -> Vec<(String, Vec<Issue>)> {
     // Find the DM domain
     let dm_df = domains
         .iter()
@@ -119,12 +214,103 @@ pub fn validate_cross_domain(domains: &[(&str, &DataFrame)]) -> Vec<(String, Vec
         );
     }
 
+    cross_domain_issues(domains, &dm_subjects, rules)
+}
+
+/// Validate cross-domain references for a whole study.
+///
+/// Runs the same checks as [`validate_cross_domain`] over every domain in
+/// `frames`, keyed by domain code. DM subjects are extracted once and the
+/// RELREC context is built from all domains. Unlike [`validate_cross_domain`],
+/// a study without DM is still checked: every USUBJID is then reported as
+/// missing from DM, since DM is required for a submission. Issues for rules
+/// disabled in `rules` are not reported.
+///
+/// Returns the issues keyed by the domain they belong to, like the reports
+/// of [`validate_all_domains`]; domains without issues are left out.
+pub fn validate_study_cross_domain(
+    frames: &BTreeMap<String, DataFrame>,
+    rules: &RuleRegistry,
+) -> BTreeMap<String, Vec<Issue>> {
+    let domains: Vec<(&str, &DataFrame)> = frames
+        .iter()
+        .map(|(name, df)| (name.as_str(), df))
+        .collect();
+
+    let dm_subjects = domains
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("DM"))
+        .map(|(_, df)| checks::cross_domain::extract_dm_subjects(df))
+        .unwrap_or_default();
+
+    cross_domain_issues(&domains, &dm_subjects, rules)
+        .into_iter()
+        .collect()
+}
+
+/// Find the domain definition a dataset is validated against.
+///
+/// Relationship datasets (RELREC, RELSPEC, RELSUB) only match their own
+/// definitions, SUPP-- datasets match SUPPQUAL, and other names fall back to
+/// a general-observation parent domain (e.g., LB for LBCH), so SUPPAE is not
+/// taken for SU or RELSPEC for RE.
+pub(crate) fn domain_for_dataset<'a>(
+    domains: &'a [SdtmDomain],
+    name: &str,
+) -> Option<&'a SdtmDomain> {
+    let find = |code: &str| domains.iter().find(|d| d.name.eq_ignore_ascii_case(code));
+    if let Some(domain) = find(name) {
+        return Some(domain);
+    }
+    if is_supp_dataset(name) {
+        return find("SUPPQUAL");
+    }
+    if ["RELREC", "RELSPEC", "RELSUB"]
+        .iter()
+        .any(|relationship| relationship.eq_ignore_ascii_case(name))
+    {
+        return None;
+    }
+    // Only general-observation domains are split (see SdtmDomain::base_code)
+    find(name.get(..2)?).filter(|domain| domain.is_general_observation())
+}
+
+/// Whether a dataset name is a supplemental qualifier dataset (SUPP--).
+fn is_supp_dataset(name: &str) -> bool {
+    name.len() > 4
+        && name
+            .get(..4)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("SUPP"))
+}
+
+/// Run all cross-domain checks against a known set of DM subjects.
+fn cross_domain_issues(
+    domains: &[(&str, &DataFrame)],
+    dm_subjects: &HashSet<String>,
+    rules: &RuleRegistry,
+) -> Vec<(String, Vec<Issue>)> {
     // Build set of valid domain codes for RDOMAIN validation
     let valid_domains: HashSet<String> = domains
         .iter()
         .map(|(name, _)| name.to_uppercase())
         .collect();
 
+    // RELREC and SUPP-- record references are resolved against every domain
+    let relrec_context = domains
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("RELREC") || is_supp_dataset(name))
+        .then(|| checks::cross_domain::RelrecContext::new(domains));
+
+    // Duplicate --SEQ values across split datasets and differing STUDYIDs,
+    // keyed by dataset name
+    let mut study_issues: HashMap<String, Vec<Issue>> = HashMap::new();
+    for (name, issue) in checks::cross_domain::check_split_sequences(domains)
+        .into_iter()
+        .chain(checks::cross_domain::check_studyid_consistency(domains))
+    {
+        study_issues.entry(name).or_default().push(issue);
+    }
+
     let mut results = Vec::new();
 
     // Check each domain
@@ -137,7 +323,7 @@ pub fn validate_cross_domain(domains: &[(&str, &DataFrame)]) -> Vec<(String, Vec
             domain_issues.extend(checks::cross_domain::check_usubjid_in_dm(
                 name,
                 df,
-                &dm_subjects,
+                dm_subjects,
             ));
         }
 
@@ -152,7 +338,7 @@ pub fn validate_cross_domain(domains: &[(&str, &DataFrame)]) -> Vec<(String, Vec
 
         // RELSUB-specific validation
         if name_upper == "RELSUB" {
-            domain_issues.extend(checks::cross_domain::check_relsub(df, &dm_subjects));
+            domain_issues.extend(checks::cross_domain::check_relsub(df, dm_subjects));
         }
 
         // RELSPEC-specific validation
@@ -161,11 +347,19 @@ pub fn validate_cross_domain(domains: &[(&str, &DataFrame)]) -> Vec<(String, Vec
         }
 
         // RELREC-specific validation (record references)
-        if name_upper == "RELREC" {
-            let context = checks::cross_domain::RelrecContext::new(domains);
-            domain_issues.extend(checks::cross_domain::check_relrec(df, &context));
+        if let Some(context) = relrec_context.as_ref().filter(|_| name_upper == "RELREC") {
+            domain_issues.extend(checks::cross_domain::check_relrec(df, context));
+        }
+
+        // SUPP-- validation (parent references and QNAM uniqueness)
+        if let Some(context) = relrec_context.as_ref().filter(|_| is_supp_dataset(name)) {
+            domain_issues.extend(checks::cross_domain::check_supp_parent(name, df, context));
+            domain_issues.extend(checks::cross_domain::check_supp_qnam_unique(name, df));
         }
 
+        domain_issues.extend(study_issues.remove(*name).unwrap_or_default());
+        domain_issues.retain(|issue| rules.is_enabled(issue.rule_id()));
+
         if !domain_issues.is_empty() {
             results.push((name.to_string(), domain_issues));
         }
@@ -173,3 +367,185 @@ pub fn validate_cross_domain(domains: &[(&str, &DataFrame)]) -> Vec<(String, Vec
 
     results
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+    use tss_standards::{
+        CtVersion, SdtmDatasetClass, VariableRole, load_ct, load_sdtm_ig, load_send_ig,
+    };
+
+    #[test]
+    fn test_validate_all_domains_parallel_matches_sequential() {
+        let domains = load_sdtm_ig().unwrap();
+        let ct = load_ct(CtVersion::default(), Some("SDTM")).unwrap();
+        let frames = BTreeMap::from([
+            (
+                "DM".to_string(),
+                df! {
+                    "STUDYID" => &["S1", "S1"],
+                    "DOMAIN" => &["DM", "DM"],
+                    "USUBJID" => &["S1-001", "S1-002"],
+                    "SEX" => &["M", "X"],
+                    "RFSTDTC" => &["2024-01-15", "2024-02-30"],
+                }
+                .unwrap(),
+            ),
+            (
+                "AE".to_string(),
+                df! {
+                    "STUDYID" => &["S1", "S1"],
+                    "DOMAIN" => &["AE", "AE"],
+                    "USUBJID" => &["S1-001", "S1-001"],
+                    "AESEQ" => &[1i64, 1],
+                    "AETERM" => &["HEADACHE", ""],
+                    "AESEV" => &["MILD", "SEVERE-ISH"],
+                }
+                .unwrap(),
+            ),
+            (
+                "LBCH".to_string(),
+                df! {
+                    "STUDYID" => &["S1"],
+                    "DOMAIN" => &["LB"],
+                    "USUBJID" => &["S1-002"],
+                    "LBSEQ" => &[1i64],
+                    "LBTESTCD" => &["GLUC"],
+                }
+                .unwrap(),
+            ),
+            ("ZZ".to_string(), df! { "USUBJID" => &["S1-001"] }.unwrap()),
+            (
+                "SUPPAE".to_string(),
+                df! {
+                    "STUDYID" => &["S1"],
+                    "RDOMAIN" => &["AE"],
+                    "USUBJID" => &["S1-001"],
+                    "QNAM" => &["AETRTEM"],
+                }
+                .unwrap(),
+            ),
+            (
+                "RELSPEC".to_string(),
+                df! {
+                    "STUDYID" => &["S1"],
+                    "USUBJID" => &["S1-001"],
+                    "REFID" => &["SPEC01"],
+                }
+                .unwrap(),
+            ),
+        ]);
+
+        let rules = load_default_rules();
+        let not_collected = BTreeMap::new();
+        let validate = |parallel| {
+            validate_all_domains(
+                &domains,
+                &frames,
+                Some(&ct),
+                Standard::Sdtm,
+                &not_collected,
+                &rules,
+                parallel,
+            )
+        };
+        let sequential = validate(false);
+        let parallel = validate(true);
+
+        let names: Vec<&str> = parallel.keys().map(String::as_str).collect();
+        assert_eq!(names, ["AE", "DM", "LBCH", "RELSPEC", "SUPPAE"]);
+        assert_eq!(parallel["LBCH"].domain, "LB");
+        // Not Substance Use or Respiratory System Findings
+        assert_eq!(parallel["SUPPAE"].domain, "SUPPQUAL");
+        assert_eq!(parallel["RELSPEC"].domain, "RELSPEC");
+        assert!(parallel["AE"].error_count() > 0);
+        assert_eq!(
+            serde_json::to_string(&sequential).unwrap(),
+            serde_json::to_string(&parallel).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate_all_domains_not_collected() {
+        let domains = load_sdtm_ig().unwrap();
+        let frames = BTreeMap::from([(
+            "DM".to_string(),
+            df! {
+                "STUDYID" => &["S1"],
+                "DOMAIN" => &["DM"],
+                "USUBJID" => &["S1-001"],
+            }
+            .unwrap(),
+        )]);
+        let expected_missing = |not_collected: &BTreeMap<String, BTreeSet<String>>| {
+            validate_all_domains(
+                &domains,
+                &frames,
+                None,
+                Standard::Sdtm,
+                not_collected,
+                &load_default_rules(),
+                false,
+            )["DM"]
+                .issues
+                .iter()
+                .any(|issue| matches!(issue, Issue::ExpectedMissing { variable } if variable == "AGE"))
+        };
+
+        assert!(expected_missing(&BTreeMap::new()));
+        let not_collected =
+            BTreeMap::from([("DM".to_string(), BTreeSet::from(["AGE".to_string()]))]);
+        assert!(!expected_missing(&not_collected));
+    }
+
+    #[test]
+    fn test_validate_send_domain() {
+        let send_domains = load_send_ig().unwrap();
+        let bw = send_domains.iter().find(|d| d.name == "BW").unwrap();
+        let domain = SdtmDomain::from(bw);
+        assert_eq!(domain.class, Some(SdtmDatasetClass::Findings));
+        let bwseq = domain.variables.iter().find(|v| v.name == "BWSEQ").unwrap();
+        assert_eq!(bwseq.role, Some(VariableRole::Identifier));
+
+        let df = df! {
+            "STUDYID" => &["TOX01", "TOX01"],
+            "DOMAIN" => &["BW", "BW"],
+            "USUBJID" => &["TOX01-001", "TOX01-002"],
+            "BWSEQ" => &[1i64, 1],
+            "BWTESTCD" => &["BW", "WEIGHT"],
+            "BWORRES" => &["250", "261"],
+            "BWDTC" => &["2024-01-15", "2024-13-45"],
+        }
+        .unwrap();
+        // All CT packages; BWTESTCD resolves in SEND CT first
+        let ct = load_ct(CtVersion::default(), None).unwrap();
+        let report = validate_domain_with_rules(
+            &domain,
+            &df,
+            Some(&ct),
+            Standard::Send,
+            &BTreeSet::new(),
+            &load_default_rules(),
+        );
+
+        assert!(report.issues.iter().any(
+            |issue| matches!(issue, Issue::RequiredMissing { variable } if variable == "BWTEST")
+        ));
+        assert!(report.issues.iter().any(
+            |issue| matches!(issue, Issue::InvalidDate { variable, .. } if variable == "BWDTC")
+        ));
+        assert!(report.issues.iter().any(
+            |issue| matches!(issue, Issue::CtViolation { variable, .. } if variable == "BWTESTCD")
+        ));
+
+        // With SEND CT as primary, validate_domain validates for SEND too
+        let send_ct = load_ct(CtVersion::default(), Some("SEND")).unwrap();
+        assert_eq!(send_ct.primary_standard(), Some(Standard::Send));
+        let defaulted = validate_domain(&domain, &df, Some(&send_ct));
+        assert_eq!(
+            serde_json::to_string(&defaulted).unwrap(),
+            serde_json::to_string(&report).unwrap()
+        );
+    }
+}
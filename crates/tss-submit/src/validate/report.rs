@@ -3,12 +3,19 @@
 use serde::{Deserialize, Serialize};
 
 use super::issue::{Issue, Severity};
+use super::rules::{Category, RuleRegistry};
 
 /// Validation report for a domain.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ValidationReport {
     pub domain: String,
+    /// Study the validated domain belongs to, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub study_id: Option<String>,
     pub issues: Vec<Issue>,
+    /// Rules the report was produced with (enabled state and severity overrides).
+    #[serde(skip)]
+    pub rules: Option<RuleRegistry>,
 }
 
 impl ValidationReport {
@@ -16,15 +23,41 @@ impl ValidationReport {
     pub fn new(domain: impl Into<String>) -> Self {
         Self {
             domain: domain.into(),
+            study_id: None,
             issues: Vec::new(),
+            rules: None,
         }
     }
 
+    /// Set the study ID included in machine-readable output.
+    pub fn with_study_id(mut self, study_id: impl Into<String>) -> Self {
+        self.study_id = Some(study_id.into());
+        self
+    }
+
+    /// Set the rules used for filtering and severity overrides.
+    pub fn with_rules(mut self, rules: RuleRegistry) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
     /// Add an issue to the report.
+    ///
+    /// Issues for rules disabled in the report's rules are dropped.
     pub fn add(&mut self, issue: Issue) {
+        if let Some(rules) = &self.rules
+            && !rules.is_enabled(issue.rule_id())
+        {
+            return;
+        }
         self.issues.push(issue);
     }
 
+    /// Severity of an issue, applying the report's severity overrides.
+    pub fn severity_of(&self, issue: &Issue) -> Severity {
+        issue.severity(self.rules.as_ref())
+    }
+
     /// Check if the report has any issues.
     pub fn is_empty(&self) -> bool {
         self.issues.is_empty()
@@ -39,7 +72,7 @@ pub fn len(&self) -> usize {
     pub fn error_count(&self) -> usize {
         self.issues
             .iter()
-            .filter(|i| matches!(i.severity(), Severity::Error | Severity::Reject))
+            .filter(|i| matches!(self.severity_of(i), Severity::Error | Severity::Reject))
             .count()
     }
 
@@ -47,7 +80,7 @@ pub fn error_count(&self) -> usize {
     pub fn warning_count(&self) -> usize {
         self.issues
             .iter()
-            .filter(|i| matches!(i.severity(), Severity::Warning))
+            .filter(|i| matches!(self.severity_of(i), Severity::Warning))
             .count()
     }
 
@@ -59,7 +92,7 @@ pub fn has_errors(&self) -> bool {
     /// Get issues sorted by severity (Reject first, then Error, Warning, Info).
     pub fn sorted_by_severity(&self) -> Vec<&Issue> {
         let mut issues: Vec<_> = self.issues.iter().collect();
-        issues.sort_by_key(|i| match i.severity() {
+        issues.sort_by_key(|i| match self.severity_of(i) {
             Severity::Reject => 0,
             Severity::Error => 1,
             Severity::Warning => 2,
@@ -67,4 +100,105 @@ pub fn sorted_by_severity(&self) -> Vec<&Issue> {
         });
         issues
     }
+
+    /// Flatten the issues into records for machine-readable output.
+    ///
+    /// Severities include the report's overrides.
+    pub fn issue_records(&self) -> Vec<IssueRecord> {
+        self.issues
+            .iter()
+            .map(|issue| IssueRecord {
+                rule_id: issue.rule_id().to_string(),
+                severity: self.severity_of(issue),
+                category: issue.category(),
+                variable: issue.variable().to_string(),
+                message: issue.message(),
+                count: issue.count(),
+            })
+            .collect()
+    }
+
+    /// Serialize the report as pretty-printed JSON.
+    ///
+    /// Unlike the derived `Serialize` (used for caching), this is a stable
+    /// flat shape for dashboards and diffing runs:
+    /// `{ "study_id", "domain", "error_count", "warning_count", "issues": [...] }`
+    /// with one [`IssueRecord`] per issue.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let json = ReportJson {
+            study_id: self.study_id.as_deref(),
+            domain: &self.domain,
+            error_count: self.error_count(),
+            warning_count: self.warning_count(),
+            issues: self.issue_records(),
+        };
+        serde_json::to_string_pretty(&json)
+    }
+}
+
+/// A single issue in machine-readable report output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueRecord {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub category: Category,
+    pub variable: String,
+    pub message: String,
+    /// Occurrence count, or `null` for issues without one.
+    pub count: Option<u64>,
+}
+
+/// JSON shape written by [`ValidationReport::to_json`].
+#[derive(Serialize)]
+struct ReportJson<'a> {
+    study_id: Option<&'a str>,
+    domain: &'a str,
+    error_count: usize,
+    warning_count: usize,
+    issues: Vec<IssueRecord>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    #[test]
+    fn test_to_json_shape() {
+        let mut report = ValidationReport::new("AE").with_study_id("CDISC01");
+        report.add(Issue::RequiredMissing {
+            variable: "AETERM".to_string(),
+        });
+        report.add(Issue::ExpectedMissing {
+            variable: "AEDECOD".to_string(),
+        });
+        report.add(Issue::TextTooLong {
+            variable: "AETERM".to_string(),
+            exceeded_count: 3,
+            max_found: 250,
+            max_allowed: 200,
+        });
+
+        let json: Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["study_id"], "CDISC01");
+        assert_eq!(json["domain"], "AE");
+        assert_eq!(json["error_count"], 1);
+        assert_eq!(json["warning_count"], 2);
+        assert_eq!(
+            json["issues"][0],
+            json!({
+                "rule_id": "required_missing",
+                "severity": "Error",
+                "category": "Presence",
+                "variable": "AETERM",
+                "message": "Required variable AETERM is missing; add the column to the dataset",
+                "count": null,
+            })
+        );
+        assert_eq!(json["issues"][1]["severity"], "Warning");
+        assert_eq!(json["issues"][2]["rule_id"], "text_too_long");
+        assert_eq!(json["issues"][2]["category"], "Limit");
+        assert_eq!(json["issues"][2]["count"], 3);
+    }
 }
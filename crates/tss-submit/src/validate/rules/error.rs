@@ -0,0 +1,34 @@
+//! Error types for validation rule loading.
+
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// Errors that can occur while loading a rule overlay.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum RuleError {
+    /// Overlay file could not be read.
+    #[error("Failed to read rule overlay '{path}': {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Overlay file is not valid CSV or JSON.
+    #[error("Invalid rule overlay '{path}': {message}")]
+    Parse { path: PathBuf, message: String },
+
+    /// Overlay file extension is neither `.csv` nor `.json`.
+    #[error("Unsupported rule overlay format '{path}': expected .csv or .json")]
+    UnsupportedFormat { path: PathBuf },
+
+    /// Overlay references a rule that doesn't exist.
+    #[error("Unknown rule '{rule_id}'")]
+    UnknownRule { rule_id: String },
+
+    /// Overlay severity is not one of Reject, Error, Warning, Info.
+    #[error("Invalid severity '{value}' for rule '{rule_id}'")]
+    InvalidSeverity { rule_id: String, value: String },
+}
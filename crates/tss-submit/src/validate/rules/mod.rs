@@ -1,8 +1,17 @@
-//! Validation rule categories.
+//! Validation rule categories and registry.
 //!
 //! Categories are used to classify validation issues and allow users
-//! to bypass certain categories of checks in settings.
+//! to bypass certain categories of checks in settings. The registry holds
+//! per-rule enabled state and severity overrides.
 
 mod category;
+mod error;
+mod p21;
+mod registry;
 
 pub use category::Category;
+pub use error::RuleError;
+pub use p21::p21_rule_id;
+pub use registry::{
+    OPT_IN_RULE_IDS, RULE_IDS, RuleRegistry, RuleSettings, load_default_rules, load_rules,
+};
@@ -0,0 +1,37 @@
+//! Pinnacle 21 rule IDs for built-in issues.
+//!
+//! Used when exporting findings for comparison against P21 Community
+//! reports. Only issues with a direct P21 equivalent are mapped; each
+//! mapping is commented with the message P21 Community reports for the rule
+//! in its SDTM-IG validation rules.
+
+use super::super::issue::Issue;
+
+/// Get the Pinnacle 21 rule ID equivalent to an issue, if there is one.
+pub fn p21_rule_id(issue: &Issue) -> Option<&'static str> {
+    match issue {
+        // SD0002: NULL value in variable marked as Required
+        Issue::RequiredEmpty { .. } | Issue::RequiredAllNull { .. } => Some("SD0002"),
+        // SD0003: Invalid ISO 8601 value for variable
+        Issue::InvalidDate { .. } | Issue::NonHyphenDate { .. } => Some("SD0003"),
+        // SD0004: Inconsistent value for DOMAIN
+        Issue::DomainValueMismatch { .. } => Some("SD0004"),
+        // SD0005: Duplicate value for --SEQ variable
+        Issue::DuplicateSequence { .. } => Some("SD0005"),
+        // SD0013: --STDTC is after --ENDTC
+        Issue::DateOrderViolation { .. } => Some("SD0013"),
+        // SD0056: SDTM Required variable not found
+        Issue::RequiredMissing { .. } => Some("SD0056"),
+        // SD0057: SDTM Expected variable not found
+        Issue::ExpectedMissing { .. } => Some("SD0057"),
+        // CT2001: Variable value not found in non-extensible codelist
+        Issue::CtViolation {
+            extensible: false, ..
+        } => Some("CT2001"),
+        // CT2002: Variable value not found in extensible codelist
+        Issue::CtViolation {
+            extensible: true, ..
+        } => Some("CT2002"),
+        _ => None,
+    }
+}
@@ -0,0 +1,548 @@
+//! Rule registry with sponsor overrides.
+//!
+//! Every [`Issue`](super::super::Issue) variant has a rule ID. The registry
+//! records whether each rule is enabled and an optional severity override,
+//! so sponsors can disable or downgrade checks per their SAP with an overlay
+//! file applied on top of the defaults.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::super::checks::ranges::RangeRuleTable;
+use super::super::issue::Severity;
+use super::error::RuleError;
+
+/// Rule IDs of all built-in checks, one per issue type, in [`Issue`] order.
+///
+/// [`Issue`]: super::super::Issue
+pub const RULE_IDS: &[&str] = &[
+    "required_missing",
+    "required_empty",
+    "required_all_null",
+    "expected_missing",
+    "identifier_null",
+    "invalid_date",
+    "non_hyphen_date",
+    "text_too_long",
+    "label_too_long",
+    "non_ascii_text",
+    "value_out_of_range",
+    "data_type_mismatch",
+    "duplicate_sequence",
+    "duplicate_record",
+    "duplicate_subject",
+    "domain_value_mismatch",
+    "test_pairing_inconsistent",
+    "date_order_violation",
+    "ct_violation",
+    "usubjid_not_in_dm",
+    "parent_not_found",
+    "invalid_rdomain",
+    "relsub_not_in_dm",
+    "relsub_not_bidirectional",
+    "relspec_invalid_parent",
+    "relrec_invalid_reference",
+    "traceability_broken",
+    "studyid_inconsistent",
+    "supp_invalid_parent",
+    "supp_duplicate_qnam",
+];
+
+/// Rules that are disabled unless enabled by an overlay.
+pub const OPT_IN_RULE_IDS: &[&str] = &["value_out_of_range"];
+
+/// Settings for a single rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleSettings {
+    /// Whether issues for this rule are reported.
+    pub enabled: bool,
+    /// Severity to report instead of the built-in one.
+    pub severity: Option<Severity>,
+}
+
+impl Default for RuleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: None,
+        }
+    }
+}
+
+impl RuleSettings {
+    /// Built-in settings for a rule (opt-in rules start disabled).
+    pub fn default_for(rule_id: &str) -> Self {
+        Self {
+            enabled: !OPT_IN_RULE_IDS.contains(&rule_id),
+            severity: None,
+        }
+    }
+}
+
+/// Enabled state and severity overrides for all rules.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleRegistry {
+    rules: BTreeMap<String, RuleSettings>,
+    range_rules: RangeRuleTable,
+}
+
+/// One overlay entry; missing fields leave the current setting unchanged.
+#[derive(Debug, Deserialize)]
+struct OverlayRow {
+    rule_id: String,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    enabled: Option<bool>,
+}
+
+impl RuleRegistry {
+    /// Get the settings for a rule (built-in defaults for unlisted IDs).
+    pub fn get(&self, rule_id: &str) -> RuleSettings {
+        self.rules
+            .get(rule_id)
+            .copied()
+            .unwrap_or_else(|| RuleSettings::default_for(rule_id))
+    }
+
+    /// Get the range table used by the `value_out_of_range` rule.
+    pub fn range_rules(&self) -> &RangeRuleTable {
+        &self.range_rules
+    }
+
+    /// Replace the range table used by the `value_out_of_range` rule.
+    pub fn with_range_rules(mut self, range_rules: RangeRuleTable) -> Self {
+        self.range_rules = range_rules;
+        self
+    }
+
+    /// Check whether a rule is enabled.
+    pub fn is_enabled(&self, rule_id: &str) -> bool {
+        self.get(rule_id).enabled
+    }
+
+    /// Get the severity override for a rule, if any.
+    pub fn severity_override(&self, rule_id: &str) -> Option<Severity> {
+        self.get(rule_id).severity
+    }
+
+    /// Iterate over all rules and their settings.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &RuleSettings)> {
+        self.rules
+            .iter()
+            .map(|(id, settings)| (id.as_str(), settings))
+    }
+
+    /// Apply an overlay file (CSV or JSON, by extension) on top of this registry.
+    ///
+    /// Both formats have `rule_id`, `severity`, and `enabled` fields; blank or
+    /// missing fields leave the rule's current setting unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read or parsed, or names an
+    /// unknown rule or severity. The registry is unchanged on error.
+    pub fn apply_overlay(&mut self, path: &Path) -> Result<(), RuleError> {
+        let content = fs::read_to_string(path).map_err(|source| RuleError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let parse_error = |message: String| RuleError::Parse {
+            path: path.to_path_buf(),
+            message,
+        };
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        let rows: Vec<OverlayRow> = match extension.as_deref() {
+            Some("csv") => csv::ReaderBuilder::new()
+                .has_headers(true)
+                .trim(csv::Trim::All)
+                .from_reader(content.as_bytes())
+                .deserialize()
+                .collect::<Result<_, _>>()
+                .map_err(|e| parse_error(e.to_string()))?,
+            Some("json") => {
+                serde_json::from_str(&content).map_err(|e| parse_error(e.to_string()))?
+            }
+            _ => {
+                return Err(RuleError::UnsupportedFormat {
+                    path: path.to_path_buf(),
+                });
+            }
+        };
+
+        let mut updated = self.rules.clone();
+        for row in rows {
+            let rule_id = row.rule_id.trim().to_lowercase();
+            let Some(settings) = updated.get_mut(&rule_id) else {
+                return Err(RuleError::UnknownRule {
+                    rule_id: row.rule_id,
+                });
+            };
+            if let Some(value) = row.severity.filter(|s| !s.trim().is_empty()) {
+                let severity = Severity::parse(&value).ok_or(RuleError::InvalidSeverity {
+                    rule_id: rule_id.clone(),
+                    value,
+                })?;
+                settings.severity = Some(severity);
+            }
+            if let Some(enabled) = row.enabled {
+                settings.enabled = enabled;
+            }
+        }
+
+        self.rules = updated;
+        Ok(())
+    }
+}
+
+/// Load the built-in rules with their default severities.
+///
+/// All rules are enabled except [`OPT_IN_RULE_IDS`].
+pub fn load_default_rules() -> RuleRegistry {
+    RuleRegistry {
+        rules: RULE_IDS
+            .iter()
+            .map(|id| ((*id).to_string(), RuleSettings::default_for(id)))
+            .collect(),
+        range_rules: RangeRuleTable::default(),
+    }
+}
+
+/// Load the built-in rules with an optional sponsor overlay applied.
+///
+/// # Errors
+/// See [`RuleRegistry::apply_overlay`].
+pub fn load_rules(overlay: Option<&Path>) -> Result<RuleRegistry, RuleError> {
+    let mut registry = load_default_rules();
+    if let Some(path) = overlay {
+        registry.apply_overlay(path)?;
+    }
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+    use std::collections::BTreeSet;
+    use tempfile::tempdir;
+    use tss_standards::sdtm::test_support::{self, domain};
+    use tss_standards::{CoreDesignation, SdtmVariable, Standard};
+
+    use super::super::super::{Issue, validate_cross_domain, validate_domain_with_rules};
+
+    fn variable(name: &str, core: CoreDesignation) -> SdtmVariable {
+        test_support::variable(name).core(core).build()
+    }
+
+    #[test]
+    fn test_overlay_disables_and_downgrades() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sponsor_rules.csv");
+        fs::write(
+            &path,
+            "rule_id,severity,enabled\n\
+             expected_missing,,false\n\
+             required_missing,warning,\n",
+        )
+        .unwrap();
+
+        let rules = load_rules(Some(&path)).unwrap();
+        assert!(!rules.is_enabled("expected_missing"));
+        assert_eq!(
+            rules.severity_override("required_missing"),
+            Some(Severity::Warning)
+        );
+        assert_eq!(rules.get("required_empty"), RuleSettings::default());
+
+        let domain = domain("AE")
+            .variables([
+                variable("USUBJID", CoreDesignation::Required),
+                variable("AETERM", CoreDesignation::Required),
+                variable("AEDECOD", CoreDesignation::Expected),
+            ])
+            .build();
+        let df = df! { "USUBJID" => &["S01"] }.unwrap();
+
+        let report = validate_domain_with_rules(
+            &domain,
+            &df,
+            None,
+            Standard::Sdtm,
+            &BTreeSet::new(),
+            &rules,
+        );
+        assert_eq!(report.issues.len(), 1);
+        let issue = &report.issues[0];
+        assert!(matches!(issue, Issue::RequiredMissing { .. }));
+        assert_eq!(issue.severity(None), Severity::Error);
+        assert_eq!(issue.severity(Some(&rules)), Severity::Warning);
+        assert_eq!(report.error_count(), 0);
+        assert_eq!(report.warning_count(), 1);
+    }
+
+    #[test]
+    fn test_json_overlay_and_errors() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rules.json");
+        fs::write(
+            &path,
+            r#"[{ "rule_id": "text_too_long", "severity": "Info" }]"#,
+        )
+        .unwrap();
+        let rules = load_rules(Some(&path)).unwrap();
+        assert_eq!(
+            rules.severity_override("text_too_long"),
+            Some(Severity::Info)
+        );
+        assert!(rules.is_enabled("text_too_long"));
+
+        fs::write(
+            &path,
+            r#"[{ "rule_id": "no_such_rule", "enabled": false }]"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            load_rules(Some(&path)),
+            Err(RuleError::UnknownRule { .. })
+        ));
+
+        fs::write(
+            &path,
+            r#"[{ "rule_id": "ct_violation", "severity": "low" }]"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            load_rules(Some(&path)),
+            Err(RuleError::InvalidSeverity { .. })
+        ));
+    }
+
+    /// One issue of every variant, in declaration order.
+    fn one_issue_per_variant() -> Vec<Issue> {
+        let v = String::new;
+        vec![
+            Issue::RequiredMissing { variable: v() },
+            Issue::RequiredEmpty {
+                variable: v(),
+                null_count: 0,
+            },
+            Issue::RequiredAllNull {
+                variable: v(),
+                row_count: 0,
+            },
+            Issue::ExpectedMissing { variable: v() },
+            Issue::IdentifierNull {
+                variable: v(),
+                null_count: 0,
+            },
+            Issue::InvalidDate {
+                variable: v(),
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::NonHyphenDate {
+                variable: v(),
+                invalid_count: 0,
+                samples: vec![],
+                suggestions: vec![],
+            },
+            Issue::TextTooLong {
+                variable: v(),
+                exceeded_count: 0,
+                max_found: 0,
+                max_allowed: 0,
+            },
+            Issue::LabelTooLong {
+                variable: v(),
+                label: v(),
+                length: 0,
+            },
+            Issue::NonAsciiText {
+                variable: v(),
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::ValueOutOfRange {
+                variable: v(),
+                test_code: None,
+                min: None,
+                max: None,
+                unit: None,
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::DataTypeMismatch {
+                variable: v(),
+                non_numeric_count: 0,
+                samples: vec![],
+            },
+            Issue::DuplicateSequence {
+                variable: v(),
+                duplicate_count: 0,
+            },
+            Issue::DuplicateRecord {
+                variable: v(),
+                key_variables: vec![],
+                group_count: 0,
+                duplicate_count: 0,
+                samples: vec![],
+            },
+            Issue::DuplicateSubject {
+                duplicate_count: 0,
+                samples: vec![],
+            },
+            Issue::DomainValueMismatch {
+                expected: v(),
+                mismatch_count: 0,
+                samples: vec![],
+            },
+            Issue::TestPairingInconsistent {
+                variable: v(),
+                paired_variable: v(),
+                value_count: 0,
+                samples: vec![],
+            },
+            Issue::DateOrderViolation {
+                variable: v(),
+                end_variable: v(),
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::CtViolation {
+                variable: v(),
+                codelist_code: v(),
+                codelist_name: v(),
+                extensible: false,
+                total_invalid: 0,
+                invalid_values: vec![],
+                allowed_count: 0,
+                suggestions: BTreeMap::new(),
+            },
+            Issue::UsubjidNotInDm {
+                domain: v(),
+                missing_count: 0,
+                samples: vec![],
+            },
+            Issue::ParentNotFound {
+                variable: v(),
+                parent_domain: v(),
+                missing_count: 0,
+                samples: vec![],
+            },
+            Issue::InvalidRdomain {
+                domain: v(),
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::RelsubNotInDm {
+                missing_count: 0,
+                samples: vec![],
+            },
+            Issue::RelsubNotBidirectional {
+                missing_count: 0,
+                samples: vec![],
+            },
+            Issue::RelspecInvalidParent {
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::RelrecInvalidReference {
+                rdomain: v(),
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::TraceabilityBroken {
+                variable: v(),
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::StudyidInconsistent {
+                domain: v(),
+                expected: None,
+                found: vec![],
+                mismatch_count: 0,
+                empty_count: 0,
+            },
+            Issue::SuppInvalidParent {
+                domain: v(),
+                invalid_count: 0,
+                samples: vec![],
+            },
+            Issue::SuppDuplicateQnam {
+                domain: v(),
+                duplicate_count: 0,
+                samples: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_rule_ids_match_issues() {
+        let issues = one_issue_per_variant();
+        for issue in &issues {
+            // Adding an Issue variant breaks this match: add it to
+            // one_issue_per_variant and its rule ID to RULE_IDS
+            match issue {
+                Issue::RequiredMissing { .. }
+                | Issue::RequiredEmpty { .. }
+                | Issue::RequiredAllNull { .. }
+                | Issue::ExpectedMissing { .. }
+                | Issue::IdentifierNull { .. }
+                | Issue::InvalidDate { .. }
+                | Issue::NonHyphenDate { .. }
+                | Issue::TextTooLong { .. }
+                | Issue::LabelTooLong { .. }
+                | Issue::NonAsciiText { .. }
+                | Issue::ValueOutOfRange { .. }
+                | Issue::DataTypeMismatch { .. }
+                | Issue::DuplicateSequence { .. }
+                | Issue::DuplicateRecord { .. }
+                | Issue::DuplicateSubject { .. }
+                | Issue::DomainValueMismatch { .. }
+                | Issue::TestPairingInconsistent { .. }
+                | Issue::DateOrderViolation { .. }
+                | Issue::CtViolation { .. }
+                | Issue::UsubjidNotInDm { .. }
+                | Issue::ParentNotFound { .. }
+                | Issue::InvalidRdomain { .. }
+                | Issue::RelsubNotInDm { .. }
+                | Issue::RelsubNotBidirectional { .. }
+                | Issue::RelspecInvalidParent { .. }
+                | Issue::RelrecInvalidReference { .. }
+                | Issue::TraceabilityBroken { .. }
+                | Issue::StudyidInconsistent { .. }
+                | Issue::SuppInvalidParent { .. }
+                | Issue::SuppDuplicateQnam { .. } => {}
+            }
+        }
+
+        let rule_ids: Vec<&str> = issues.iter().map(Issue::rule_id).collect();
+        assert_eq!(rule_ids, RULE_IDS);
+    }
+
+    #[test]
+    fn test_cross_domain_respects_disabled_rules() {
+        let dm = df! { "USUBJID" => &["S01"] }.unwrap();
+        let ae = df! { "USUBJID" => &["S02"] }.unwrap();
+        let domains = [("DM", &dm), ("AE", &ae)];
+
+        let issues = validate_cross_domain(&domains, &load_default_rules());
+        assert!(matches!(
+            issues[0].1.as_slice(),
+            [Issue::UsubjidNotInDm { .. }]
+        ));
+
+        let mut rules = load_default_rules();
+        if let Some(settings) = rules.rules.get_mut("usubjid_not_in_dm") {
+            settings.enabled = false;
+        }
+        assert!(validate_cross_domain(&domains, &rules).is_empty());
+    }
+}
@@ -0,0 +1,40 @@
+//! CT violations carry the closest submission value for each invalid value.
+
+use polars::prelude::df;
+use tss_standards::ct::{CtVersion, load};
+use tss_submit::{Issue, validate_domain};
+
+#[test]
+fn test_ct_violation_suggestions() {
+    let domains = tss_standards::load_sdtm_ig().unwrap();
+    let dm = domains.iter().find(|d| d.name == "DM").unwrap();
+    let ct = load(CtVersion::default(), Some("SDTM")).unwrap();
+    let data = df! {
+        "STUDYID" => &["STUDY", "STUDY", "STUDY"],
+        "DOMAIN" => &["DM", "DM", "DM"],
+        "USUBJID" => &["STUDY-001", "STUDY-002", "STUDY-003"],
+        "SEX" => &["Femael", "Q", "M"],
+    }
+    .unwrap();
+
+    let report = validate_domain(dm, &data, Some(&ct));
+
+    let issue = report
+        .issues
+        .iter()
+        .find(|issue| matches!(issue, Issue::CtViolation { variable, .. } if variable == "SEX"))
+        .expect("SEX CT violation");
+    let Issue::CtViolation {
+        invalid_values,
+        suggestions,
+        ..
+    } = issue
+    else {
+        unreachable!();
+    };
+    assert_eq!(invalid_values, &["Femael", "Q"]);
+    // "Q" is not close to any term, so only the typo gets a suggestion
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions["Femael"], "F");
+    assert!(issue.message().ends_with("did you mean 'F' for 'Femael'?"));
+}
@@ -0,0 +1,45 @@
+//! Variable labels and formats written to XPT survive reading the file back.
+
+use std::collections::BTreeMap;
+
+use polars::prelude::df;
+use tss_submit::{DomainFrame, write_xpt_outputs};
+
+#[test]
+fn test_xpt_labels_and_formats_round_trip() {
+    let domains = tss_standards::load_sdtm_ig().unwrap();
+    let data = df! {
+        "STUDYID" => &["STUDY", "STUDY"],
+        "DOMAIN" => &["DM", "DM"],
+        "USUBJID" => &["STUDY-001", "STUDY-002"],
+        "AGE" => &[34.0, 58.5],
+    }
+    .unwrap();
+    let frame = DomainFrame::new("DM", data).with_formats(BTreeMap::from([
+        ("AGE".to_string(), "8.1".to_string()),
+        ("USUBJID".to_string(), "$CHAR20.".to_string()),
+    ]));
+    let dir = tempfile::tempdir().unwrap();
+
+    let paths = write_xpt_outputs(dir.path(), &domains, &[frame]).unwrap();
+    let (df, hints) = tss_ingest::read_xpt(&paths[0]).unwrap();
+
+    assert_eq!(df.height(), 2);
+    let hint = |name: &str| {
+        let idx = df
+            .get_column_names()
+            .iter()
+            .position(|column| column.as_str() == name)
+            .unwrap();
+        &hints[idx]
+    };
+    assert_eq!(
+        hint("USUBJID").label.as_deref(),
+        Some("Unique Subject Identifier")
+    );
+    assert_eq!(hint("AGE").label.as_deref(), Some("Age"));
+    assert_eq!(hint("AGE").format.as_deref(), Some("8.1"));
+    assert_eq!(hint("USUBJID").format.as_deref(), Some("$CHAR20."));
+    // Variables without a configured format get none
+    assert_eq!(hint("STUDYID").format, None);
+}
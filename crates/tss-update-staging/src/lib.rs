@@ -0,0 +1,318 @@
+//! Staging files for an installed update awaiting launch confirmation.
+//!
+//! After swapping in a new version, the installer keeps a backup of the
+//! previous one and records a [`PendingUpdate`]. Whoever launches the new
+//! version counts the attempt with [`UpdateStaging::record_launch_attempt`],
+//! and the new version calls [`UpdateStaging::confirm`] as soon as it has
+//! started, which writes a marker file. Once the allowed number of launch
+//! attempts has passed without a marker, the update is considered failed
+//! and the launcher restores the backup. A launch that neither confirms nor
+//! exits within a time limit counts as failed too; see
+//! [`UpdateStaging::wait_for_confirmation`].
+//!
+//! Only the staging files live here; restoring and removing backups is up
+//! to the caller. This crate depends on nothing but `std`, `chrono`,
+//! `serde` and `serde_json`, so the macOS updater helper can use it without
+//! linking all of `tss-updater`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// File describing the update awaiting launch confirmation.
+pub const PENDING_UPDATE_FILE: &str = "pending_update.json";
+
+/// Marker file written by the new version once it has launched.
+pub const LAUNCH_MARKER_FILE: &str = "update_launch_ok";
+
+/// Default number of unconfirmed launches before the update is rolled back.
+pub const DEFAULT_MAX_LAUNCH_ATTEMPTS: u32 = 2;
+
+/// Default time a launch may take to confirm before it counts as failed.
+pub const DEFAULT_LAUNCH_TIMEOUT_SECS: u64 = 120;
+
+/// How often [`UpdateStaging::wait_for_confirmation`] checks for the marker.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// An installed update whose launch hasn't been confirmed yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingUpdate {
+    /// Version that was installed.
+    pub version: String,
+    /// Version the backup contains.
+    pub previous_version: String,
+    /// Where the new version was installed.
+    pub installed_path: PathBuf,
+    /// Backup of the previous version.
+    pub backup_path: PathBuf,
+    /// When the update was installed.
+    pub installed_at: DateTime<Utc>,
+    /// Launches of the new version so far, none of which confirmed.
+    #[serde(default)]
+    pub launch_attempts: u32,
+    /// Unconfirmed launches allowed before the backup is restored.
+    #[serde(default = "default_max_launch_attempts")]
+    pub max_launch_attempts: u32,
+}
+
+fn default_max_launch_attempts() -> u32 {
+    DEFAULT_MAX_LAUNCH_ATTEMPTS
+}
+
+impl PendingUpdate {
+    /// Creates a pending update installed now, not launched yet.
+    pub fn new(
+        version: impl Into<String>,
+        previous_version: impl Into<String>,
+        installed_path: impl Into<PathBuf>,
+        backup_path: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            version: version.into(),
+            previous_version: previous_version.into(),
+            installed_path: installed_path.into(),
+            backup_path: backup_path.into(),
+            installed_at: Utc::now(),
+            launch_attempts: 0,
+            max_launch_attempts: DEFAULT_MAX_LAUNCH_ATTEMPTS,
+        }
+    }
+
+    /// Sets the number of unconfirmed launches allowed (at least one).
+    #[must_use]
+    pub fn with_max_launch_attempts(mut self, max_launch_attempts: u32) -> Self {
+        self.max_launch_attempts = max_launch_attempts.max(1);
+        self
+    }
+
+    /// Gets the launch state given whether the marker exists.
+    #[must_use]
+    pub fn state(&self, confirmed: bool) -> LaunchState {
+        if confirmed {
+            LaunchState::Confirmed
+        } else if self.launch_attempts < self.max_launch_attempts {
+            LaunchState::AwaitingConfirmation
+        } else {
+            LaunchState::Failed
+        }
+    }
+}
+
+/// Launch state of a pending update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchState {
+    /// The new version hasn't confirmed yet, but may be launched again.
+    AwaitingConfirmation,
+    /// The new version confirmed a successful launch.
+    Confirmed,
+    /// Every allowed launch ended without confirmation; the backup should
+    /// be restored.
+    Failed,
+}
+
+/// How a launch of the new version ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchOutcome {
+    /// The new version wrote the launch marker.
+    Confirmed,
+    /// The launched process exited without confirming.
+    Exited,
+    /// The launch neither confirmed nor exited in time; the launched process
+    /// was killed.
+    TimedOut,
+}
+
+/// Folder holding the pending update record and the launch marker.
+#[derive(Debug, Clone)]
+pub struct UpdateStaging {
+    dir: PathBuf,
+}
+
+impl UpdateStaging {
+    /// Uses `dir` for the staging files.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The application data folder shared by the app and the updater helper.
+    ///
+    /// - macOS: `~/Library/Application Support/Trial Submission Studio`
+    /// - Windows: `%APPDATA%\Trial Submission Studio`
+    /// - Linux: `$XDG_DATA_HOME/Trial Submission Studio` (or `~/.local/share`)
+    #[must_use]
+    pub fn default_dir() -> Option<PathBuf> {
+        let env_path = |name: &str| std::env::var_os(name).map(PathBuf::from);
+        let base = if cfg!(target_os = "macos") {
+            env_path("HOME").map(|home| home.join("Library/Application Support"))
+        } else if cfg!(windows) {
+            env_path("APPDATA")
+        } else {
+            env_path("XDG_DATA_HOME").or_else(|| env_path("HOME").map(|h| h.join(".local/share")))
+        };
+        base.map(|dir| dir.join("Trial Submission Studio"))
+    }
+
+    /// Gets the staging folder.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn pending_path(&self) -> PathBuf {
+        self.dir.join(PENDING_UPDATE_FILE)
+    }
+
+    fn marker_path(&self) -> PathBuf {
+        self.dir.join(LAUNCH_MARKER_FILE)
+    }
+
+    /// Records a pending update, clearing any stale launch marker.
+    pub fn begin(&self, pending: &PendingUpdate) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        remove_if_exists(&self.marker_path())?;
+        self.write_pending(pending)
+    }
+
+    fn write_pending(&self, pending: &PendingUpdate) -> io::Result<()> {
+        fs::write(self.pending_path(), serde_json::to_string_pretty(pending)?)
+    }
+
+    /// Reads the pending update, if any.
+    pub fn pending(&self) -> io::Result<Option<PendingUpdate>> {
+        match fs::read_to_string(self.pending_path()) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Counts a launch of the new version that has yet to confirm.
+    ///
+    /// Call just before launching it. Returns the updated record, or `None`
+    /// if no update is pending or the launch is already confirmed.
+    pub fn record_launch_attempt(&self) -> io::Result<Option<PendingUpdate>> {
+        let Some(mut pending) = self.pending()? else {
+            return Ok(None);
+        };
+        if self.is_confirmed() {
+            return Ok(None);
+        }
+        pending.launch_attempts = pending.launch_attempts.saturating_add(1);
+        self.write_pending(&pending)?;
+        Ok(Some(pending))
+    }
+
+    /// Confirms that the new version launched successfully.
+    ///
+    /// Returns `false` (and writes nothing) if no update is pending.
+    pub fn confirm(&self) -> io::Result<bool> {
+        if self.pending()?.is_none() {
+            return Ok(false);
+        }
+        fs::write(self.marker_path(), Utc::now().to_rfc3339())?;
+        Ok(true)
+    }
+
+    /// Checks whether the launch marker exists.
+    #[must_use]
+    pub fn is_confirmed(&self) -> bool {
+        self.marker_path().exists()
+    }
+
+    /// Waits until the new version confirms its launch, `launched` exits, or
+    /// `timeout` passes.
+    ///
+    /// `launched` is the process that started the new version and exits
+    /// with it. It is killed once the launch is confirmed (the app keeps
+    /// running) or the wait times out.
+    pub fn wait_for_confirmation(&self, launched: &mut Child, timeout: Duration) -> LaunchOutcome {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self.is_confirmed() {
+                let _ = launched.kill();
+                return LaunchOutcome::Confirmed;
+            }
+            match launched.try_wait() {
+                Ok(None) => {}
+                Ok(Some(_)) | Err(_) if self.is_confirmed() => return LaunchOutcome::Confirmed,
+                Ok(Some(_)) | Err(_) => return LaunchOutcome::Exited,
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                let _ = launched.kill();
+                let _ = launched.wait();
+                return LaunchOutcome::TimedOut;
+            }
+            std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Gets the launch state of the pending update.
+    pub fn state(&self) -> io::Result<Option<LaunchState>> {
+        Ok(self
+            .pending()?
+            .map(|pending| pending.state(self.is_confirmed())))
+    }
+
+    /// Removes the pending record and the launch marker.
+    pub fn clear(&self) -> io::Result<()> {
+        remove_if_exists(&self.pending_path())?;
+        remove_if_exists(&self.marker_path())
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn staging(dir: &Path) -> UpdateStaging {
+        let staging = UpdateStaging::new(dir);
+        staging
+            .begin(&PendingUpdate::new("0.2.0", "0.1.0", "a", "b"))
+            .unwrap();
+        staging
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wait_times_out_and_kills_launch() {
+        let dir = tempfile::tempdir().unwrap();
+        let staging = staging(dir.path());
+        let mut launched = Command::new("sleep").arg("30").spawn().unwrap();
+
+        let started = Instant::now();
+        let outcome = staging.wait_for_confirmation(&mut launched, Duration::from_millis(200));
+
+        assert_eq!(outcome, LaunchOutcome::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(launched.try_wait().unwrap().is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wait_confirmed_and_exited() {
+        let dir = tempfile::tempdir().unwrap();
+        let staging = staging(dir.path());
+
+        let mut launched = Command::new("true").spawn().unwrap();
+        let outcome = staging.wait_for_confirmation(&mut launched, Duration::from_secs(10));
+        assert_eq!(outcome, LaunchOutcome::Exited);
+
+        staging.confirm().unwrap();
+        let mut launched = Command::new("sleep").arg("30").spawn().unwrap();
+        let outcome = staging.wait_for_confirmation(&mut launched, Duration::from_secs(10));
+        assert_eq!(outcome, LaunchOutcome::Confirmed);
+    }
+}
@@ -2,6 +2,7 @@
 
 use serde::Deserialize;
 use std::path::PathBuf;
+use tss_update_staging::{DEFAULT_LAUNCH_TIMEOUT_SECS, DEFAULT_MAX_LAUNCH_ATTEMPTS};
 
 /// Configuration passed from the main application via config file.
 #[derive(Debug, Deserialize)]
@@ -18,6 +19,24 @@ pub struct HelperConfig {
     /// Previous version (for rollback info).
     #[serde(default)]
     pub previous_version: String,
+    /// Launches of the new version that may end without confirming it
+    /// started before the previous version is restored.
+    #[serde(default = "default_max_launch_attempts")]
+    pub max_launch_attempts: u32,
+    /// Seconds a launch may run without confirming before it counts as
+    /// failed and the app is terminated.
+    #[serde(default = "default_launch_timeout_secs")]
+    pub launch_timeout_secs: u64,
+}
+
+/// Default number of unconfirmed launches allowed.
+fn default_max_launch_attempts() -> u32 {
+    DEFAULT_MAX_LAUNCH_ATTEMPTS
+}
+
+/// Default time a launch may take to confirm.
+fn default_launch_timeout_secs() -> u64 {
+    DEFAULT_LAUNCH_TIMEOUT_SECS
 }
 
 impl HelperConfig {
@@ -1,7 +1,7 @@
 //! Application relaunch functionality.
 
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Command};
 
 /// Relaunches the application using the `open` command.
 pub fn relaunch(app_path: &Path) -> Result<(), String> {
@@ -16,6 +16,37 @@ pub fn relaunch(app_path: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Launches the application and returns a process that exits with it.
+///
+/// Uses `open -W`, so the returned child lives until the app quits or
+/// crashes. Used to tell when a launch ended without confirmation.
+pub fn launch_and_wait(app_path: &Path) -> Result<Child, String> {
+    eprintln!("[helper] Launching application: {:?}", app_path);
+
+    Command::new("open")
+        .arg("-W")
+        .arg(app_path)
+        .spawn()
+        .map_err(|e| format!("Failed to launch: {}", e))
+}
+
+/// Terminates the running application at `app_path`.
+///
+/// Used when a launch timed out, so a hung new version doesn't keep running
+/// while the previous version is restored.
+pub fn terminate(app_path: &Path) {
+    let executable_dir = app_path.join("Contents/MacOS/");
+    eprintln!("[helper] Terminating application: {:?}", app_path);
+
+    if let Err(e) = Command::new("pkill")
+        .arg("-f")
+        .arg(&executable_dir)
+        .status()
+    {
+        eprintln!("[helper] Warning: Failed to terminate application: {}", e);
+    }
+}
+
 /// Waits for the parent process to exit.
 pub fn wait_for_parent(pid: u32) {
     eprintln!("[helper] Waiting for parent process {} to exit...", pid);
@@ -13,8 +13,12 @@
 //! 7. Helper verifies code signature of new bundle
 //! 8. Helper performs atomic swap: current → backup, new → current
 //! 9. Helper writes status file for post-update feedback
-//! 10. Helper relaunches the application
-//! 11. Helper cleans up backup on success
+//! 10. Helper records the pending update and relaunches the application,
+//!     counting each launch
+//! 11. Helper waits until the new version confirms it launched, exits, or
+//!     times out, relaunching it while launch attempts remain
+//! 12. Helper cleans up the backup on confirmation, or restores it and
+//!     relaunches the previous version once every attempt has failed
 
 #[cfg(target_os = "macos")]
 mod config;
@@ -27,6 +31,8 @@
 #[cfg(target_os = "macos")]
 mod signature;
 #[cfg(target_os = "macos")]
+mod staging;
+#[cfg(target_os = "macos")]
 mod status;
 #[cfg(target_os = "macos")]
 mod swap;
@@ -34,14 +40,17 @@
 #[cfg(target_os = "macos")]
 mod macos {
     use crate::config::HelperConfig;
-    use crate::launch::{relaunch, wait_for_parent};
+    use crate::launch::{launch_and_wait, relaunch, terminate, wait_for_parent};
     use crate::log::{get_log_path, init_logging, log, log_error};
     use crate::quarantine::remove_quarantine;
     use crate::signature::{get_team_id, verify_signature};
+    use crate::staging;
     use crate::status::UpdateStatus;
-    use crate::swap::{cleanup_backup, swap_bundles};
+    use crate::swap::{cleanup_backup, rollback, swap_bundles};
     use std::path::{Path, PathBuf};
     use std::process::ExitCode;
+    use std::time::Duration;
+    use tss_update_staging::{LaunchOutcome, PendingUpdate};
 
     pub fn run() -> ExitCode {
         // Initialize logging first
@@ -173,13 +182,70 @@ pub fn run() -> ExitCode {
             log("Status file written");
         }
 
-        // Relaunch the application
-        if let Err(e) = relaunch(&config.current_app_path) {
-            log_error("Relaunch", &e);
-            // Don't fail completely - the update was installed
-        } else {
-            log("Application relaunch command sent");
+        // Record the pending update so the new version can confirm its launch
+        let pending = PendingUpdate::new(
+            &config.version,
+            &config.previous_version,
+            &config.current_app_path,
+            &swap_result.backup_path,
+        )
+        .with_max_launch_attempts(config.max_launch_attempts);
+        if let Err(e) = staging::begin(&pending) {
+            log_error("Staging", &e);
+            // Non-fatal - keep the update without waiting for confirmation
+            if let Err(e) = relaunch(&config.current_app_path) {
+                log_error("Relaunch", &e);
+            }
+            cleanup_backup(&swap_result.backup_path);
+            return ExitCode::SUCCESS;
+        }
+
+        // Launch the new version until it confirms or runs out of attempts
+        let max_attempts = pending.max_launch_attempts;
+        let timeout = Duration::from_secs(config.launch_timeout_secs);
+        let mut confirmed = false;
+        for attempt in 1..=max_attempts {
+            if let Err(e) = staging::record_launch_attempt() {
+                log_error("Staging", &e);
+            }
+            confirmed = match launch_and_wait(&config.current_app_path) {
+                Ok(mut launched) => {
+                    log("Application launch command sent");
+                    match staging::wait_for_confirmation(&mut launched, timeout) {
+                        LaunchOutcome::Confirmed => true,
+                        LaunchOutcome::Exited => false,
+                        LaunchOutcome::TimedOut => {
+                            // A hung app would keep running the new version
+                            terminate(&config.current_app_path);
+                            false
+                        }
+                    }
+                }
+                Err(e) => {
+                    log_error("Relaunch", &e);
+                    false
+                }
+            };
+            if confirmed {
+                break;
+            }
+            log(&format!(
+                "Launch {} of {} ended without confirmation",
+                attempt, max_attempts
+            ));
         }
+        if !confirmed {
+            let error_msg = log_error(
+                "Launch confirmation",
+                &format!(
+                    "New version did not confirm its launch in {} attempts",
+                    max_attempts
+                ),
+            );
+            staging::clear();
+            return restore_previous_version(&config, &swap_result.backup_path, &error_msg);
+        }
+        staging::clear();
 
         // Clean up backup
         cleanup_backup(&swap_result.backup_path);
@@ -188,6 +254,38 @@ pub fn run() -> ExitCode {
         ExitCode::SUCCESS
     }
 
+    /// Restores the backup after a failed launch and relaunches the previous version.
+    fn restore_previous_version(
+        config: &HelperConfig,
+        backup_path: &Path,
+        error_msg: &str,
+    ) -> ExitCode {
+        let log_path = get_log_path().unwrap_or_else(|| PathBuf::from("/dev/null"));
+
+        if let Err(e) = rollback(backup_path, &config.current_app_path) {
+            let rollback_msg = log_error("Rollback", &e);
+            write_failure_status(
+                &config.version,
+                &config.previous_version,
+                &format!("{}; {}", error_msg, rollback_msg),
+                &log_path,
+            );
+            return ExitCode::FAILURE;
+        }
+        log(&format!("Restored version {}", config.previous_version));
+        write_failure_status(
+            &config.version,
+            &config.previous_version,
+            error_msg,
+            &log_path,
+        );
+
+        if let Err(e) = relaunch(&config.current_app_path) {
+            log_error("Relaunch", &e);
+        }
+        ExitCode::FAILURE
+    }
+
     /// Writes a failure status file.
     fn write_failure_status(version: &str, previous_version: &str, error: &str, log_path: &Path) {
         let status = UpdateStatus::failure(
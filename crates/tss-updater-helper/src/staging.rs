@@ -0,0 +1,78 @@
+//! Launch confirmation for the relaunched app.
+//!
+//! The backup of the previous version is kept until the new version confirms
+//! it launched by writing a marker file. The helper counts each launch of the
+//! new version; once every allowed launch has exited or timed out without
+//! confirming, the previous version is restored. The pending update record
+//! and the marker are managed by `tss-update-staging`, shared with
+//! `tss-updater`.
+
+use std::process::Child;
+use std::time::Duration;
+
+use tss_update_staging::{LaunchOutcome, PendingUpdate, UpdateStaging};
+
+/// Gets the staging folder shared with the app.
+fn staging() -> Option<UpdateStaging> {
+    UpdateStaging::default_dir().map(UpdateStaging::new)
+}
+
+/// Records the pending update, clearing any stale launch marker.
+///
+/// Must be called before relaunching so the new version can confirm.
+pub fn begin(pending: &PendingUpdate) -> Result<(), String> {
+    let staging = staging().ok_or_else(|| "Could not determine staging directory".to_string())?;
+    staging
+        .begin(pending)
+        .map_err(|e| format!("Failed to record pending update: {}", e))?;
+
+    eprintln!("[helper] Pending update recorded in: {:?}", staging.dir());
+    Ok(())
+}
+
+/// Counts a launch of the new version. Call just before launching it.
+pub fn record_launch_attempt() -> Result<(), String> {
+    let staging = staging().ok_or_else(|| "Could not determine staging directory".to_string())?;
+    if let Some(pending) = staging
+        .record_launch_attempt()
+        .map_err(|e| format!("Failed to record launch attempt: {}", e))?
+    {
+        eprintln!(
+            "[helper] Launch attempt {} of {}",
+            pending.launch_attempts, pending.max_launch_attempts
+        );
+    }
+    Ok(())
+}
+
+/// Waits until the new version confirms its launch, `launched` exits, or
+/// `timeout` passes.
+///
+/// `launched` is the process started by [`crate::launch::launch_and_wait`],
+/// which exits when the app does. It is killed on confirmation or timeout.
+pub fn wait_for_confirmation(launched: &mut Child, timeout: Duration) -> LaunchOutcome {
+    let Some(staging) = staging() else {
+        return LaunchOutcome::Exited;
+    };
+    eprintln!(
+        "[helper] Waiting up to {}s for launch confirmation",
+        timeout.as_secs()
+    );
+
+    let outcome = staging.wait_for_confirmation(launched, timeout);
+    match outcome {
+        LaunchOutcome::Confirmed => eprintln!("[helper] Launch confirmed"),
+        LaunchOutcome::Exited => eprintln!("[helper] App exited without confirming its launch"),
+        LaunchOutcome::TimedOut => eprintln!("[helper] App did not confirm its launch in time"),
+    }
+    outcome
+}
+
+/// Removes the pending record and the launch marker.
+pub fn clear() {
+    if let Some(staging) = staging()
+        && let Err(e) = staging.clear()
+    {
+        eprintln!("[helper] Warning: Failed to clear staging files: {}", e);
+    }
+}
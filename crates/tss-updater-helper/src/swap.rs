@@ -75,7 +75,7 @@ pub fn swap_bundles(new_app: &Path, current_app: &Path) -> Result<SwapResult, St
 }
 
 /// Performs rollback by restoring the backup.
-fn rollback(backup_path: &Path, current_app: &Path) -> Result<(), String> {
+pub fn rollback(backup_path: &Path, current_app: &Path) -> Result<(), String> {
     eprintln!("[helper] Rolling back...");
 
     // If current_app exists (partial copy), remove it first
@@ -90,13 +90,10 @@ fn rollback(backup_path: &Path, current_app: &Path) -> Result<(), String> {
     Ok(())
 }
 
-/// Cleans up the backup after successful update.
+/// Cleans up the backup after the new version confirmed its launch.
 pub fn cleanup_backup(backup_path: &Path) {
     eprintln!("[helper] Cleaning up backup: {:?}", backup_path);
 
-    // Give the new app a moment to start
-    std::thread::sleep(std::time::Duration::from_secs(2));
-
     if let Err(e) = fs::remove_dir_all(backup_path) {
         eprintln!("[helper] Warning: Failed to clean up backup: {}", e);
     } else {
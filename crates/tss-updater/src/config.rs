@@ -2,10 +2,11 @@
 
 use std::fmt;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::version::{PreRelease, Version};
+use crate::{REPO_NAME, REPO_OWNER};
 
 /// Update channel selection.
 ///
@@ -96,6 +97,103 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// How often to check for updates automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateCheckFrequency {
+    /// Check at most once every 24 hours.
+    #[default]
+    Daily,
+
+    /// Check at most once every 7 days.
+    Weekly,
+
+    /// Never check automatically; only via the menu.
+    Manual,
+}
+
+impl UpdateCheckFrequency {
+    /// Returns all frequency variants for UI enumeration.
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        &[Self::Daily, Self::Weekly, Self::Manual]
+    }
+
+    /// Minimum time between automatic checks, or `None` for manual only.
+    #[must_use]
+    pub fn interval(&self) -> Option<Duration> {
+        match self {
+            Self::Daily => Some(Duration::hours(AUTO_CHECK_INTERVAL_HOURS)),
+            Self::Weekly => Some(Duration::days(7)),
+            Self::Manual => None,
+        }
+    }
+
+    /// Get a human-readable label.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Daily => "Daily",
+            Self::Weekly => "Weekly",
+            Self::Manual => "Manually",
+        }
+    }
+}
+
+impl fmt::Display for UpdateCheckFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Where update releases are published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UpdateSource {
+    /// GitHub Releases of a repository.
+    #[serde(rename = "github")]
+    GitHub {
+        /// Repository owner.
+        owner: String,
+        /// Repository name.
+        repo: String,
+    },
+
+    /// A mirror serving a `latest.json` release manifest under a base URL.
+    ///
+    /// Used by enterprise deployments that host releases internally.
+    Manifest {
+        /// Base URL of the mirror (e.g., "https://mirror.example.com/tss").
+        base_url: String,
+    },
+}
+
+impl UpdateSource {
+    /// Name of the source for messages: "GitHub", or the mirror's host.
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self {
+            Self::GitHub { .. } => "GitHub".to_string(),
+            Self::Manifest { base_url } => {
+                let address = base_url
+                    .split_once("://")
+                    .map_or(base_url.as_str(), |(_, rest)| rest);
+                address.split('/').next().unwrap_or(address).to_string()
+            }
+        }
+    }
+}
+
+impl Default for UpdateSource {
+    /// The official GitHub repository.
+    fn default() -> Self {
+        Self::GitHub {
+            owner: REPO_OWNER.to_string(),
+            repo: REPO_NAME.to_string(),
+        }
+    }
+}
+
 /// Minimum time between automatic startup checks (in hours).
 pub const AUTO_CHECK_INTERVAL_HOURS: i64 = 24;
 
@@ -118,6 +216,10 @@ pub struct UpdateSettings {
     #[serde(default)]
     pub channel: UpdateChannel,
 
+    /// How often automatic checks may run.
+    #[serde(default)]
+    pub check_frequency: UpdateCheckFrequency,
+
     /// Version to skip (user clicked "Skip This Version").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub skipped_version: Option<String>,
@@ -125,17 +227,47 @@ pub struct UpdateSettings {
     /// Last time we checked for updates.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_check: Option<DateTime<Utc>>,
+
+    /// Where to look for releases. Defaults to the official GitHub repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<UpdateSource>,
+
+    /// Pinned SHA-256 of the next update package (hex, optionally "sha256:" prefixed).
+    ///
+    /// Used by managed deployments that approve a specific build.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_sha256: Option<String>,
 }
 
 impl UpdateSettings {
+    /// Get the configured update source, or the default GitHub repository.
+    #[must_use]
+    pub fn source(&self) -> UpdateSource {
+        self.source.clone().unwrap_or_default()
+    }
+
     /// Check if automatic startup check should run.
     ///
     /// Returns `true` only if:
     /// - Automatic startup checks are enabled
-    /// - At least 24 hours have passed since the last check
+    /// - The check frequency's interval has passed since the last check
     #[must_use]
     pub fn should_auto_check(&self) -> bool {
-        self.check_on_startup && self.hours_since_last_check() >= AUTO_CHECK_INTERVAL_HOURS
+        self.check_on_startup && self.should_check_now(Utc::now())
+    }
+
+    /// Check if an automatic check is due at `now` under the check frequency.
+    ///
+    /// Always `false` for [`UpdateCheckFrequency::Manual`]; otherwise `true`
+    /// if there was no previous check or its interval has fully elapsed.
+    #[must_use]
+    pub fn should_check_now(&self, now: DateTime<Utc>) -> bool {
+        match self.check_frequency.interval() {
+            None => false,
+            Some(interval) => self
+                .last_check
+                .is_none_or(|last| now.signed_duration_since(last) >= interval),
+        }
     }
 
     /// Check if enough time has passed to allow a manual check.
@@ -233,6 +365,94 @@ fn test_default_settings() {
         assert_eq!(settings.channel, UpdateChannel::Stable);
         assert!(settings.skipped_version.is_none());
         assert!(settings.last_check.is_none());
+        assert_eq!(
+            settings.source(),
+            UpdateSource::GitHub {
+                owner: REPO_OWNER.to_string(),
+                repo: REPO_NAME.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_source_serde() {
+        let json =
+            r#"{ "source": { "type": "manifest", "base_url": "https://mirror.example.com/tss" } }"#;
+        let settings: UpdateSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            settings.source(),
+            UpdateSource::Manifest {
+                base_url: "https://mirror.example.com/tss".to_string(),
+            }
+        );
+
+        // Unset source is not written, so existing settings files are unchanged
+        let json = serde_json::to_string(&UpdateSettings::default()).unwrap();
+        assert!(!json.contains("source"));
+    }
+
+    #[test]
+    fn test_should_check_now_daily() {
+        let last = Utc::now();
+        let settings = UpdateSettings {
+            last_check: Some(last),
+            ..Default::default()
+        };
+        assert_eq!(settings.check_frequency, UpdateCheckFrequency::Daily);
+
+        assert!(!settings.should_check_now(last + Duration::hours(24) - Duration::seconds(1)));
+        assert!(settings.should_check_now(last + Duration::hours(24)));
+    }
+
+    #[test]
+    fn test_should_check_now_weekly() {
+        let last = Utc::now();
+        let settings = UpdateSettings {
+            check_frequency: UpdateCheckFrequency::Weekly,
+            last_check: Some(last),
+            ..Default::default()
+        };
+
+        assert!(!settings.should_check_now(last + Duration::days(6)));
+        assert!(!settings.should_check_now(last + Duration::days(7) - Duration::seconds(1)));
+        assert!(settings.should_check_now(last + Duration::days(7)));
+    }
+
+    #[test]
+    fn test_should_check_now_manual() {
+        let settings = UpdateSettings {
+            check_on_startup: true,
+            check_frequency: UpdateCheckFrequency::Manual,
+            ..Default::default()
+        };
+
+        // Never checked, but manual never checks automatically
+        assert!(!settings.should_check_now(Utc::now()));
+        assert!(!settings.should_auto_check());
+    }
+
+    #[test]
+    fn test_should_check_now_never_checked() {
+        let settings = UpdateSettings::default();
+        assert!(settings.should_check_now(Utc::now()));
+    }
+
+    #[test]
+    fn test_last_check_persisted() {
+        let mut settings = UpdateSettings {
+            check_frequency: UpdateCheckFrequency::Weekly,
+            ..Default::default()
+        };
+        settings.record_check();
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let loaded: UpdateSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.last_check, settings.last_check);
+        assert_eq!(loaded.check_frequency, UpdateCheckFrequency::Weekly);
+
+        // Settings files without a frequency default to daily
+        let loaded: UpdateSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(loaded.check_frequency, UpdateCheckFrequency::Daily);
     }
 
     #[test]
@@ -314,6 +534,12 @@ fn test_channel_includes() {
         assert!(UpdateChannel::Alpha.includes(&rc));
         assert!(UpdateChannel::Alpha.includes(&beta));
         assert!(UpdateChannel::Alpha.includes(&alpha));
+
+        // Pre-releases without a dot are still pre-releases
+        let alpha6 = Version::from_tag("v1.0.0-alpha6").unwrap();
+        assert!(!UpdateChannel::Stable.includes(&alpha6));
+        assert!(!UpdateChannel::Beta.includes(&alpha6));
+        assert!(UpdateChannel::Alpha.includes(&alpha6));
     }
 
     #[test]
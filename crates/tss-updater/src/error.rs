@@ -1,7 +1,11 @@
 //! Error types for the auto-update system.
 
+use std::borrow::Cow;
+
 use thiserror::Error;
 
+use crate::config::UpdateSource;
+
 /// Suggested action for the user when an error occurs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SuggestedAction {
@@ -76,6 +80,15 @@ pub enum UpdateError {
     #[error("no digest available for verification")]
     NoDigestAvailable,
 
+    /// The downloaded package failed checksum verification, or had no
+    /// checksum to verify against.
+    #[error("verification failed: {0}")]
+    VerificationFailed(String),
+
+    /// The update source or an asset URL does not use HTTPS.
+    #[error("update source must use HTTPS: {0}")]
+    InsecureSource(String),
+
     /// Failed to install the update.
     #[error("installation error: {0}")]
     Installation(String),
@@ -163,12 +176,15 @@ impl UpdateError {
     pub fn user_message(&self) -> &str {
         match self {
             Self::Network(_) | Self::Timeout => {
-                "Could not connect to GitHub. Please check your internet connection."
+                "Could not connect to the update server. Please check your internet connection."
             }
-            Self::ChecksumMismatch { .. } => {
+            Self::ChecksumMismatch { .. } | Self::VerificationFailed(_) => {
                 "Security verification failed. The download may have been tampered with."
             }
             Self::NoDigestAvailable => "Security verification not available for this release.",
+            Self::InsecureSource(_) => {
+                "The update source is not secure. Update sources must use HTTPS."
+            }
             Self::NoAssetFound(_) => "No update available for your platform.",
             Self::Installation(_) => "Could not install the update. Please try again.",
             Self::ArchiveExtraction(_) => "Could not extract the update package.",
@@ -205,6 +221,19 @@ pub fn user_message(&self) -> &str {
         }
     }
 
+    /// Returns a user-friendly error message naming the update source the
+    /// error came from, e.g. the mirror that could not be reached.
+    #[must_use]
+    pub fn user_message_for(&self, source: &UpdateSource) -> Cow<'_, str> {
+        match self {
+            Self::Network(_) | Self::Timeout => Cow::Owned(format!(
+                "Could not connect to {}. Please check your internet connection.",
+                source.name()
+            )),
+            _ => Cow::Borrowed(self.user_message()),
+        }
+    }
+
     /// Returns whether this error is potentially recoverable with a retry.
     #[must_use]
     pub fn is_retryable(&self) -> bool {
@@ -220,8 +249,12 @@ pub fn suggested_action(&self) -> SuggestedAction {
         match self {
             Self::Network(_) | Self::Timeout => SuggestedAction::Retry,
             Self::RateLimited { retry_after } => SuggestedAction::WaitAndRetry(*retry_after),
-            Self::ChecksumMismatch { .. } => SuggestedAction::RetryDownload,
-            Self::NoCompatibleAsset | Self::NoAssetFound(_) => SuggestedAction::ManualDownload,
+            Self::ChecksumMismatch { .. } | Self::VerificationFailed(_) => {
+                SuggestedAction::RetryDownload
+            }
+            Self::NoCompatibleAsset | Self::NoAssetFound(_) | Self::InsecureSource(_) => {
+                SuggestedAction::ManualDownload
+            }
             Self::PermissionDenied(_) => SuggestedAction::RunAsAdmin,
             Self::InsufficientSpace { .. } => SuggestedAction::FreeSpace,
             Self::HelperNotFound | Self::NotInAppBundle => SuggestedAction::Reinstall,
@@ -277,6 +310,21 @@ mod tests {
     fn test_user_messages() {
         let err = UpdateError::Network("connection refused".to_string());
         assert!(err.user_message().contains("internet connection"));
+        assert!(
+            err.user_message_for(&UpdateSource::default())
+                .starts_with("Could not connect to GitHub.")
+        );
+        let mirror = UpdateSource::Manifest {
+            base_url: "https://mirror.example.com/tss".to_string(),
+        };
+        assert!(
+            err.user_message_for(&mirror)
+                .starts_with("Could not connect to mirror.example.com.")
+        );
+        assert_eq!(
+            UpdateError::Cancelled.user_message_for(&mirror),
+            UpdateError::Cancelled.user_message()
+        );
 
         let err = UpdateError::ChecksumMismatch {
             expected: "abc".to_string(),
@@ -303,6 +351,7 @@ fn test_retryable() {
             }
             .is_retryable()
         );
+        assert!(!UpdateError::VerificationFailed("bad signature".to_string()).is_retryable());
         assert!(!UpdateError::Cancelled.is_retryable());
     }
 
@@ -5,12 +5,14 @@
 //!
 //! # Overview
 //!
-//! The update system uses GitHub Releases as its source. It supports:
+//! The update system uses GitHub Releases as its source by default, or a
+//! mirror serving a release manifest (see [`UpdateSource`]). It supports:
 //!
 //! - Semantic versioning with pre-release tags (alpha, beta, rc)
 //! - Configurable update channels (stable, beta)
 //! - Automatic platform detection (macOS, Windows, Linux on x64 and ARM64)
 //! - SHA256 verification using GitHub's built-in digest feature
+//! - Checksum files published next to each asset, for sources without digests
 //! - Streaming progress reporting during downloads
 //!
 //! # Architecture
@@ -20,8 +22,11 @@
 //! - `check_for_update()` - One-shot async operation, use with `Task::perform()`
 //! - `download_with_data()` - Streaming download, use with `Task::run()`
 //! - `verify_sha256()` - Sync verification function
+//! - `verify_release_asset()` - Sync checksum check against settings
 //! - `extract_archive()` - Sync extraction function
 //! - `install_and_restart()` - Sync installation function
+//! - `resolve_pending_update()` - Call at process start to roll back a failed update
+//! - `confirm_update_success()` - Call once startup is reached to keep an installed update
 //!
 //! The GUI handles state management through its own `UpdateState` enum,
 //! using Iced's message/update loop as the state machine.
@@ -79,24 +84,35 @@
 // GitHub API
 pub mod github;
 
+// Mirrored release manifests
+pub mod manifest;
+
 // Platform-specific installation
 pub mod platform;
 
+// Launch confirmation and rollback
+pub mod rollback;
+pub use tss_update_staging as staging;
+
 // Re-export main types for convenience
-pub use config::{UpdateChannel, UpdateSettings};
+pub use config::{UpdateChannel, UpdateCheckFrequency, UpdateSettings, UpdateSource};
 pub use error::{Result, SuggestedAction, UpdateError};
-pub use release::{ReleaseAsset, UpdateInfo};
+pub use release::{ChangelogSection, ReleaseAsset, UpdateInfo, parse_changelog};
+pub use rollback::{LaunchState, PendingUpdate, Resolution, ResolveUpdate, UpdateStaging};
 pub use version::{PreRelease, Version};
 
 // Re-export step functions and types
 pub use steps::check::check_for_update;
 pub use steps::download::{
-    DownloadProgress, DownloadResult, DownloadStreamItem, download_simple, download_stream,
-    download_with_data, format_bytes, format_speed,
+    DownloadProgress, DownloadResult, DownloadStreamItem, download_checksum_file,
+    download_optional, download_simple, download_stream, download_with_data, format_bytes,
+    format_speed,
 };
 pub use steps::extract::{ArchiveType, detect_archive_type, extract_archive};
 pub use steps::signature::verify_signature;
-pub use steps::verify::{verify_download, verify_sha256};
+pub use steps::verify::{
+    parse_checksum_file, verify_download, verify_release_asset, verify_sha256,
+};
 
 /// Current version of the application.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -136,6 +152,48 @@ pub fn restart() -> Result<()> {
     steps::install::restart_application()
 }
 
+/// Settle an update installed by a previous run.
+///
+/// Call at process start, before [`confirm_update_success`] and before
+/// anything that could crash. This launch is counted against the pending
+/// update; once the allowed launches have all ended without confirmation,
+/// the previous version is restored and [`Resolution::RolledBack`] is
+/// returned, and the caller should then [`restart`] into it.
+///
+/// On macOS the updater helper launches the new version and settles the
+/// update itself, so this returns [`Resolution::Idle`].
+pub fn resolve_pending_update() -> Result<Resolution> {
+    if cfg!(target_os = "macos") {
+        return Ok(Resolution::Idle);
+    }
+    match UpdateStaging::default_dir() {
+        Some(dir) => UpdateStaging::new(dir).begin_launch(),
+        None => Ok(Resolution::Idle),
+    }
+}
+
+/// Confirm that this version launched successfully after an update.
+///
+/// Call as soon as the application has started. Until then, the updater
+/// keeps a backup of the previous version and restores it once too many
+/// launches ended without confirmation. On macOS the updater helper removes
+/// the backup once it sees the confirmation; elsewhere it is removed here.
+/// Returns `false` if no update was pending.
+pub fn confirm_update_success() -> Result<bool> {
+    let Some(dir) = UpdateStaging::default_dir() else {
+        return Ok(false);
+    };
+    let staging = UpdateStaging::new(dir);
+    let confirmed = staging.confirm()?;
+    if confirmed {
+        tracing::info!("Confirmed successful launch of updated version");
+        if !cfg!(target_os = "macos") {
+            staging.resolve()?;
+        }
+    }
+    Ok(confirmed)
+}
+
 /// Service for update operations.
 ///
 /// This is a facade that provides a simpler API for common update operations.
@@ -174,6 +232,25 @@ pub fn verify_download(data: &[u8], info: &UpdateInfo) -> VerificationStatus {
         }
     }
 
+    /// Download, verify, and install an update, then restart.
+    ///
+    /// Before anything is replaced, the package is checked against every
+    /// SHA256 source available for it (see [`verify_release_asset`]); a
+    /// package without one is refused. This complements transport security
+    /// on Windows and Linux, where no OS code signature is checked; macOS
+    /// additionally verifies the app bundle's signature during install.
+    ///
+    /// # Errors
+    /// Returns [`UpdateError::VerificationFailed`] if verification fails.
+    pub async fn download_and_install(info: &UpdateInfo, settings: &UpdateSettings) -> Result<()> {
+        let data = download_simple(&info.asset.download_url).await?;
+        let checksum_file = download_checksum_file(&info.asset, settings).await?;
+        let sha256 = verify_release_asset(&data, &info.asset, settings, checksum_file.as_deref())?;
+        tracing::info!("Verified update {} ({})", info.asset.name, sha256);
+
+        install_and_restart(&data, info)
+    }
+
     /// Install the downloaded update (writes to temp location).
     ///
     /// On macOS, this extracts the app bundle. On other platforms, this extracts
@@ -194,6 +271,20 @@ pub fn install_and_restart(data: &[u8], info: &UpdateInfo) -> Result<()> {
     pub fn restart() -> Result<()> {
         restart()
     }
+
+    /// Settle an update installed by a previous run.
+    ///
+    /// See [`resolve_pending_update`].
+    pub fn resolve_pending_update() -> Result<Resolution> {
+        resolve_pending_update()
+    }
+
+    /// Confirm that this version launched successfully after an update.
+    ///
+    /// See [`confirm_update_success`].
+    pub fn confirm_update_success() -> Result<bool> {
+        confirm_update_success()
+    }
 }
 
 #[cfg(test)]
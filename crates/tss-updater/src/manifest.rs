@@ -0,0 +1,368 @@
+//! Release manifest client for mirrored update sources.
+//!
+//! Enterprise deployments can mirror releases on an internal server instead
+//! of GitHub. The server publishes a `latest.json` manifest under a base URL;
+//! it is converted to the same [`GitHubRelease`] type so asset selection and
+//! verification work unchanged.
+//!
+//! Mirrors are held to the same bar as GitHub: the manifest and every asset
+//! must be served over HTTPS, and every asset must list its SHA256 hash.
+
+use reqwest::header::{ACCEPT, HeaderMap, HeaderValue, USER_AGENT};
+use serde::Deserialize;
+
+use crate::error::{Result, UpdateError};
+use crate::github::types::{GitHubAsset, GitHubRelease};
+
+/// File name of the manifest under the base URL.
+pub const MANIFEST_FILE_NAME: &str = "latest.json";
+
+/// User agent string for manifest requests.
+const USER_AGENT_VALUE: &str = concat!("trial-submission-studio/", env!("CARGO_PKG_VERSION"));
+
+/// Latest release as described by a mirror's manifest.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseManifest {
+    /// Release version (e.g., "0.2.0" or "v0.2.0").
+    pub version: String,
+
+    /// Release notes in markdown format.
+    #[serde(default)]
+    pub notes: Option<String>,
+
+    /// Publication timestamp.
+    #[serde(default)]
+    pub published_at: Option<String>,
+
+    /// Downloadable assets.
+    #[serde(default)]
+    pub assets: Vec<ManifestAsset>,
+}
+
+/// A downloadable file listed in a release manifest.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManifestAsset {
+    /// Asset filename, containing the target triple.
+    pub name: String,
+
+    /// Download URL, absolute or relative to the manifest's base URL.
+    pub url: String,
+
+    /// File size in bytes.
+    pub size: u64,
+
+    /// SHA256 hash as hex. Required; a manifest asset without it is rejected.
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+impl ReleaseManifest {
+    /// Convert to a release, resolving relative asset URLs against `base_url`.
+    ///
+    /// # Errors
+    /// Returns [`UpdateError::InsecureSource`] if an asset URL is not HTTPS,
+    /// or [`UpdateError::NoDigestAvailable`] if an asset has no SHA256 hash.
+    pub fn into_release(self, base_url: &str) -> Result<GitHubRelease> {
+        let base_url = base_url.trim_end_matches('/');
+        let tag_name = self.version;
+        let prerelease = tag_name.contains('-');
+        let assets = self
+            .assets
+            .into_iter()
+            .map(|asset| {
+                let url = resolve_url(base_url, &asset.url);
+                require_https(&url)?;
+                let Some(hash) = asset.sha256 else {
+                    tracing::warn!("Mirrored asset {} has no SHA256 hash", asset.name);
+                    return Err(UpdateError::NoDigestAvailable);
+                };
+                Ok(GitHubAsset {
+                    browser_download_url: url,
+                    digest: Some(format!("sha256:{hash}")),
+                    name: asset.name,
+                    state: "uploaded".to_string(),
+                    size: asset.size,
+                    content_type: String::new(),
+                    download_count: 0,
+                    created_at: String::new(),
+                    updated_at: String::new(),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(GitHubRelease {
+            tag_name,
+            name: None,
+            body: self.notes,
+            prerelease,
+            draft: false,
+            assets,
+            html_url: base_url.to_string(),
+            published_at: self.published_at,
+        })
+    }
+}
+
+/// Resolve an asset URL against the base URL unless it is already absolute.
+fn resolve_url(base_url: &str, url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("{}/{}", base_url, url.trim_start_matches('/'))
+    }
+}
+
+/// Reject URLs that are not HTTPS.
+fn require_https(url: &str) -> Result<()> {
+    if url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(UpdateError::InsecureSource(url.to_string()))
+    }
+}
+
+/// HTTP GET used by [`ManifestClient`] to fetch the manifest.
+///
+/// Implemented for [`reqwest::Client`]; tests substitute a fixture so the
+/// fetch can be exercised without a server.
+pub trait ManifestTransport {
+    /// Fetches `url`, returning the HTTP status code and the body.
+    fn get(&self, url: &str) -> impl Future<Output = Result<(u16, Vec<u8>)>> + Send;
+}
+
+impl ManifestTransport for reqwest::Client {
+    async fn get(&self, url: &str) -> Result<(u16, Vec<u8>)> {
+        let response = reqwest::Client::get(self, url).send().await?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await?;
+        Ok((status, body.to_vec()))
+    }
+}
+
+/// Client for fetching the release manifest from a mirror.
+#[derive(Debug, Clone)]
+pub struct ManifestClient<T = reqwest::Client> {
+    transport: T,
+    base_url: String,
+}
+
+impl ManifestClient {
+    /// Creates a new client for a mirror base URL.
+    ///
+    /// # Arguments
+    /// * `base_url` - HTTPS URL of the folder serving `latest.json`
+    ///   (e.g., "https://mirror.example.com/tss")
+    ///
+    /// # Errors
+    /// Returns [`UpdateError::InsecureSource`] if `base_url` is not HTTPS.
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(USER_AGENT, HeaderValue::from_static(USER_AGENT_VALUE));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|e| UpdateError::Network(format!("failed to create HTTP client: {e}")))?;
+
+        Self::with_transport(base_url, client)
+    }
+}
+
+impl<T: ManifestTransport> ManifestClient<T> {
+    /// Creates a client that fetches the manifest through `transport`.
+    ///
+    /// # Errors
+    /// Returns [`UpdateError::InsecureSource`] if `base_url` is not HTTPS.
+    pub fn with_transport(base_url: impl Into<String>, transport: T) -> Result<Self> {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        require_https(&base_url)?;
+        Ok(Self {
+            transport,
+            base_url,
+        })
+    }
+
+    /// Returns the URL of the manifest file.
+    #[must_use]
+    pub fn manifest_url(&self) -> String {
+        format!("{}/{}", self.base_url, MANIFEST_FILE_NAME)
+    }
+
+    /// Fetches the latest release from the mirror's manifest.
+    pub async fn get_latest_release(&self) -> Result<GitHubRelease> {
+        let url = self.manifest_url();
+        tracing::debug!("Fetching release manifest from {}", url);
+
+        let (status, body) = self.transport.get(&url).await?;
+        if !(200..300).contains(&status) {
+            return Err(UpdateError::Network(format!(
+                "Release manifest error ({}): {}",
+                status, url
+            )));
+        }
+
+        let manifest: ReleaseManifest = serde_json::from_slice(&body)?;
+        manifest.into_release(&self.base_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use futures_util::FutureExt;
+
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/data/manifest.json");
+
+    #[test]
+    fn test_manifest_into_release() {
+        let manifest: ReleaseManifest = serde_json::from_str(FIXTURE).unwrap();
+        let release = manifest
+            .into_release("https://mirror.example.com/tss/")
+            .unwrap();
+
+        assert_eq!(release.version(), "0.2.0");
+        assert!(!release.prerelease);
+        assert_eq!(release.changelog(), "## Changes\n- Mirrored release");
+
+        let linux = release
+            .find_asset_for_target("x86_64-unknown-linux-gnu")
+            .unwrap();
+        assert_eq!(
+            linux.browser_download_url,
+            "https://mirror.example.com/tss/0.2.0/trial-submission-studio-v0.2.0-x86_64-unknown-linux-gnu.tar.gz"
+        );
+        assert!(linux.has_verification());
+
+        let windows = release
+            .find_asset_for_target("x86_64-pc-windows-msvc")
+            .unwrap();
+        assert!(
+            windows
+                .browser_download_url
+                .starts_with("https://downloads.example.com/")
+        );
+        assert!(windows.has_verification());
+    }
+
+    #[test]
+    fn test_manifest_rejects_unverifiable_assets() {
+        let manifest = |url: &str, sha256: Option<&str>| ReleaseManifest {
+            version: "0.2.0".to_string(),
+            notes: None,
+            published_at: None,
+            assets: vec![ManifestAsset {
+                name: "trial-submission-studio-v0.2.0-x86_64-unknown-linux-gnu.tar.gz".to_string(),
+                url: url.to_string(),
+                size: 1,
+                sha256: sha256.map(str::to_string),
+            }],
+        };
+        let hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+        assert!(matches!(
+            manifest("tss.tar.gz", None).into_release("https://mirror.example.com/tss"),
+            Err(UpdateError::NoDigestAvailable)
+        ));
+        assert!(matches!(
+            manifest("http://downloads.example.com/tss.tar.gz", Some(hash))
+                .into_release("https://mirror.example.com/tss"),
+            Err(UpdateError::InsecureSource(_))
+        ));
+        assert!(matches!(
+            manifest("tss.tar.gz", Some(hash)).into_release("http://mirror.example.com/tss"),
+            Err(UpdateError::InsecureSource(_))
+        ));
+    }
+
+    /// Serves one canned response and records the requested URLs.
+    struct FixtureTransport {
+        status: u16,
+        body: &'static str,
+        requested: Mutex<Vec<String>>,
+    }
+
+    impl FixtureTransport {
+        fn new(status: u16, body: &'static str) -> Self {
+            Self {
+                status,
+                body,
+                requested: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl ManifestTransport for FixtureTransport {
+        async fn get(&self, url: &str) -> Result<(u16, Vec<u8>)> {
+            self.requested.lock().unwrap().push(url.to_string());
+            Ok((self.status, self.body.as_bytes().to_vec()))
+        }
+    }
+
+    fn fetch(transport: FixtureTransport) -> (Result<GitHubRelease>, Vec<String>) {
+        let client =
+            ManifestClient::with_transport("https://mirror.example.com/tss/", transport).unwrap();
+        let result = client
+            .get_latest_release()
+            .now_or_never()
+            .expect("fixture transport is ready immediately");
+        let requested = client.transport.requested.into_inner().unwrap();
+        (result, requested)
+    }
+
+    #[test]
+    fn test_get_latest_release_from_mirror() {
+        let (result, requested) = fetch(FixtureTransport::new(200, FIXTURE));
+        assert_eq!(requested, ["https://mirror.example.com/tss/latest.json"]);
+        let release = result.unwrap();
+        assert_eq!(release.version(), "0.2.0");
+        let linux = release
+            .find_asset_for_target("x86_64-unknown-linux-gnu")
+            .unwrap();
+        assert!(
+            linux
+                .browser_download_url
+                .starts_with("https://mirror.example.com/tss/0.2.0/")
+        );
+    }
+
+    #[test]
+    fn test_get_latest_release_errors() {
+        let (result, _) = fetch(FixtureTransport::new(404, "Not Found"));
+        assert!(matches!(
+            result,
+            Err(UpdateError::Network(message)) if message.contains("https://mirror.example.com/tss/latest.json")
+        ));
+
+        let (result, _) = fetch(FixtureTransport::new(200, "<html></html>"));
+        assert!(matches!(result, Err(UpdateError::JsonParse(_))));
+
+        let insecure = r#"{ "version": "0.2.0", "assets": [{ "name": "tss.tar.gz", "url": "http://downloads.example.com/tss.tar.gz", "size": 1, "sha256": "00" }] }"#;
+        let (result, _) = fetch(FixtureTransport::new(200, insecure));
+        assert!(matches!(result, Err(UpdateError::InsecureSource(_))));
+
+        assert!(matches!(
+            ManifestClient::with_transport(
+                "http://mirror.example.com/tss",
+                FixtureTransport::new(200, FIXTURE)
+            ),
+            Err(UpdateError::InsecureSource(_))
+        ));
+    }
+
+    #[test]
+    fn test_client_requires_https() {
+        let client = ManifestClient::new("https://mirror.example.com/tss/").unwrap();
+        assert_eq!(
+            client.manifest_url(),
+            "https://mirror.example.com/tss/latest.json"
+        );
+        assert!(matches!(
+            ManifestClient::new("http://mirror.example.com/tss"),
+            Err(UpdateError::InsecureSource(_))
+        ));
+    }
+}
@@ -2,19 +2,29 @@
 //!
 //! On Windows and Linux, we can use the simpler `self_replace` approach:
 //! 1. Extract the binary from the downloaded archive
-//! 2. Replace the current executable using self_replace
-//! 3. Restart the application
+//! 2. Back up the current executable and record the pending update
+//! 3. Replace the current executable using self_replace
+//! 4. Restart the application
+//!
+//! The backup is kept until the new version confirms its launch (see
+//! [`crate::rollback`]).
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
 
-use crate::error::Result;
+use crate::error::{Result, UpdateError};
 use crate::release::UpdateInfo;
+use crate::rollback::{PendingUpdate, UpdateStaging};
 use crate::steps::install::{extract_binary, replace_current_executable, restart_application};
 
 /// Installs the update and restarts the application.
 ///
 /// On Windows/Linux, this:
 /// 1. Extracts the binary from the archive
-/// 2. Replaces the current executable using `self_replace`
-/// 3. Restarts the application
+/// 2. Copies the current executable to `<exe>.backup`
+/// 3. Replaces the current executable using `self_replace`
+/// 4. Records the pending update and restarts the application
 ///
 /// # Arguments
 /// * `data` - The downloaded archive data
@@ -29,10 +39,36 @@ pub fn install_and_restart(data: &[u8], info: &UpdateInfo) -> Result<()> {
     let binary = extract_binary(data, &info.asset.name)?;
     tracing::info!("Extracted binary ({} bytes)", binary.len());
 
+    // Keep the previous version until the new one confirms its launch
+    let current_exe = std::env::current_exe().map_err(|e| {
+        UpdateError::Installation(format!("Failed to get current executable path: {}", e))
+    })?;
+    let backup_path = backup_path_for(&current_exe);
+    fs::copy(&current_exe, &backup_path).map_err(|e| {
+        UpdateError::Installation(format!("Failed to back up current executable: {}", e))
+    })?;
+
     // Replace current executable
     replace_current_executable(&binary)?;
     tracing::info!("Executable replaced");
 
+    match UpdateStaging::default_dir() {
+        Some(dir) => UpdateStaging::new(dir).begin(&PendingUpdate::new(
+            &info.version,
+            crate::VERSION,
+            current_exe,
+            backup_path,
+        ))?,
+        None => tracing::warn!("No staging folder; the update can't be rolled back"),
+    }
+
     // Restart application
     restart_application()
 }
+
+/// Path of the backup kept next to an executable.
+fn backup_path_for(exe: &std::path::Path) -> PathBuf {
+    let mut name = OsString::from(exe.as_os_str());
+    name.push(".backup");
+    PathBuf::from(name)
+}
@@ -84,6 +84,9 @@ pub struct UpdateInfo {
     /// Release notes/changelog in markdown format.
     pub changelog: String,
 
+    /// The changelog grouped by heading (see [`parse_changelog`]).
+    pub sections: Vec<ChangelogSection>,
+
     /// The matching asset for the current platform.
     pub asset: ReleaseAsset,
 
@@ -101,10 +104,12 @@ pub fn new(
         asset: ReleaseAsset,
     ) -> Self {
         let has_verification = asset.has_verification();
+        let changelog = changelog.into();
         Self {
             version: version.into(),
             parsed_version,
-            changelog: changelog.into(),
+            sections: parse_changelog(&changelog),
+            changelog,
             asset,
             has_verification,
         }
@@ -123,6 +128,7 @@ fn default() -> Self {
             version: String::new(),
             parsed_version: Version::default(),
             changelog: String::new(),
+            sections: Vec::new(),
             asset: ReleaseAsset {
                 name: String::new(),
                 download_url: String::new(),
@@ -134,6 +140,89 @@ fn default() -> Self {
     }
 }
 
+/// Heading used for changelog items that aren't under a heading.
+pub const NOTES_HEADING: &str = "Notes";
+
+/// A group of changelog entries under one heading (e.g., "Features").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogSection {
+    /// Heading text without the leading `#` markers.
+    pub heading: String,
+    /// Entries, one per bullet or paragraph line.
+    pub items: Vec<String>,
+}
+
+impl ChangelogSection {
+    fn new(heading: impl Into<String>) -> Self {
+        Self {
+            heading: heading.into(),
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Splits Markdown release notes into sections.
+///
+/// Each `#` heading starts a section; `-`, `*`, and `+` bullets and plain
+/// text lines become its items, with indented lines continuing the previous
+/// bullet. Items before the first heading go in a "Notes" section, so notes
+/// without any headings degrade to a single "Notes" section. Headings with no
+/// items are dropped, and an empty body gives no sections.
+#[must_use]
+pub fn parse_changelog(markdown: &str) -> Vec<ChangelogSection> {
+    let mut sections: Vec<ChangelogSection> = Vec::new();
+    let mut current = ChangelogSection::new(NOTES_HEADING);
+    let mut in_bullet = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            in_bullet = false;
+            continue;
+        }
+
+        // ATX heading: 1-6 `#` followed by a space (so "#123" is text)
+        let rest = trimmed.trim_start_matches('#');
+        let level = trimmed.len() - rest.len();
+        if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')) {
+            let heading = rest.trim();
+            if !heading.is_empty() {
+                let finished = std::mem::replace(&mut current, ChangelogSection::new(heading));
+                if !finished.items.is_empty() {
+                    sections.push(finished);
+                }
+            }
+            in_bullet = false;
+            continue;
+        }
+
+        let bullet = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker));
+        match bullet {
+            Some(item) => {
+                current.items.push(item.trim().to_string());
+                in_bullet = true;
+            }
+            None if in_bullet && line.starts_with(char::is_whitespace) => {
+                if let Some(last) = current.items.last_mut() {
+                    last.push(' ');
+                    last.push_str(trimmed);
+                }
+            }
+            None => {
+                current.items.push(trimmed.to_string());
+                in_bullet = false;
+            }
+        }
+    }
+
+    if !current.items.is_empty() {
+        sections.push(current);
+    }
+    sections
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,4 +280,67 @@ fn test_update_info_version_display() {
 
         assert_eq!(info.version_display(), "0.1.0");
     }
+
+    #[test]
+    fn test_parse_changelog_sections() {
+        let notes = "## Features\n\
+                     - Add the Define-XML export\n\
+                     - Support SAS transport v8\n\
+                     \n\
+                     ## Bug Fixes\n\
+                     * Fix date parsing for partial dates that span\n  \
+                       a year boundary\n\
+                     \n\
+                     ### Internal\n\
+                     \n\
+                     ## Links\n\
+                     **Full Changelog**: v0.1.0...v0.2.0\n";
+
+        let sections = parse_changelog(notes);
+        assert_eq!(
+            sections,
+            vec![
+                ChangelogSection {
+                    heading: "Features".to_string(),
+                    items: vec![
+                        "Add the Define-XML export".to_string(),
+                        "Support SAS transport v8".to_string(),
+                    ],
+                },
+                ChangelogSection {
+                    heading: "Bug Fixes".to_string(),
+                    items: vec![
+                        "Fix date parsing for partial dates that span a year boundary".to_string()
+                    ],
+                },
+                ChangelogSection {
+                    heading: "Links".to_string(),
+                    items: vec!["**Full Changelog**: v0.1.0...v0.2.0".to_string()],
+                },
+            ]
+        );
+
+        let info = UpdateInfo::new(
+            "v0.2.0",
+            Version::default(),
+            notes,
+            ReleaseAsset::new("test.tar.gz", "", None, 0),
+        );
+        assert_eq!(info.sections, sections);
+        assert_eq!(info.changelog, notes);
+    }
+
+    #[test]
+    fn test_parse_changelog_without_headings() {
+        let sections = parse_changelog("Bug fixes and\nperformance improvements.\n- One more");
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].heading, NOTES_HEADING);
+        assert_eq!(sections[0].items.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_changelog_empty() {
+        assert!(parse_changelog("").is_empty());
+        assert!(parse_changelog("  \n\n## \n").is_empty());
+    }
 }
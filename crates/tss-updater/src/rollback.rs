@@ -0,0 +1,252 @@
+//! Rollback of installed updates that never confirmed their launch.
+//!
+//! The staging files themselves (the [`PendingUpdate`] record and the launch
+//! marker) are managed by the `tss-update-staging` crate, which the macOS
+//! updater helper shares. This module adds what only the application needs:
+//! committing a confirmed update and restoring the backup of a failed one,
+//! through the [`ResolveUpdate`] trait on [`UpdateStaging`].
+//!
+//! On macOS the updater helper launches the new version, counts the
+//! attempts and restores the backup itself. On Windows and Linux there is
+//! no helper: the application counts its own launches with
+//! [`ResolveUpdate::begin_launch`], called by
+//! [`resolve_pending_update`](crate::resolve_pending_update) at process
+//! start, and rolls back once too many launches ended unconfirmed.
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Result, UpdateError};
+
+pub use crate::staging::{
+    DEFAULT_MAX_LAUNCH_ATTEMPTS, LAUNCH_MARKER_FILE, LaunchState, PENDING_UPDATE_FILE,
+    PendingUpdate, UpdateStaging,
+};
+
+/// Outcome of settling a pending update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// No update is pending.
+    Idle,
+    /// Still waiting for the new version to confirm.
+    Waiting(PendingUpdate),
+    /// The launch was confirmed and the backup removed.
+    Committed(PendingUpdate),
+    /// Every allowed launch ended unconfirmed and the backup was restored.
+    RolledBack(PendingUpdate),
+}
+
+/// Committing and rolling back the update recorded in an [`UpdateStaging`].
+pub trait ResolveUpdate {
+    /// Commits or rolls back the pending update, depending on its state.
+    ///
+    /// - Confirmed: the backup is removed.
+    /// - Failed: the installed version is replaced by the backup.
+    ///
+    /// The staging files are cleared in both cases.
+    fn resolve(&self) -> Result<Resolution>;
+
+    /// Settles the pending update as the new version starts.
+    ///
+    /// Call at process start, before anything that could fail. If earlier
+    /// launches used up every attempt without confirming, the backup is
+    /// restored and [`Resolution::RolledBack`] is returned. Otherwise this
+    /// launch is counted and [`Resolution::Waiting`] returned; a crash before
+    /// [`confirm`](UpdateStaging::confirm) then counts against the update on
+    /// the next start.
+    fn begin_launch(&self) -> Result<Resolution>;
+}
+
+impl ResolveUpdate for UpdateStaging {
+    fn resolve(&self) -> Result<Resolution> {
+        let Some(pending) = self.pending()? else {
+            return Ok(Resolution::Idle);
+        };
+
+        match pending.state(self.is_confirmed()) {
+            LaunchState::AwaitingConfirmation => Ok(Resolution::Waiting(pending)),
+            LaunchState::Confirmed => {
+                remove_path(&pending.backup_path)?;
+                self.clear()?;
+                tracing::info!("Update to {} committed", pending.version);
+                Ok(Resolution::Committed(pending))
+            }
+            LaunchState::Failed => {
+                restore_backup(&pending.backup_path, &pending.installed_path)?;
+                self.clear()?;
+                tracing::warn!(
+                    "Update to {} did not confirm in {} launches, restored {}",
+                    pending.version,
+                    pending.launch_attempts,
+                    pending.previous_version
+                );
+                Ok(Resolution::RolledBack(pending))
+            }
+        }
+    }
+
+    fn begin_launch(&self) -> Result<Resolution> {
+        match self.resolve()? {
+            Resolution::Waiting(_) => Ok(self
+                .record_launch_attempt()?
+                .map_or(Resolution::Idle, Resolution::Waiting)),
+            resolution => Ok(resolution),
+        }
+    }
+}
+
+/// Replaces `installed` with `backup`.
+///
+/// The running executable can't be removed on Windows, so it is replaced
+/// with `self_replace` instead.
+fn restore_backup(backup: &Path, installed: &Path) -> Result<()> {
+    if !backup.exists() {
+        return Err(UpdateError::Installation(format!(
+            "Backup not found: {}",
+            backup.display()
+        )));
+    }
+    let is_current_exe = std::env::current_exe()
+        .and_then(fs::canonicalize)
+        .ok()
+        .is_some_and(|exe| fs::canonicalize(installed).is_ok_and(|path| path == exe));
+    if is_current_exe {
+        self_replace::self_replace(backup).map_err(|e| {
+            UpdateError::Installation(format!("Failed to restore executable: {}", e))
+        })?;
+        return remove_path(backup);
+    }
+    remove_path(installed)?;
+    fs::rename(backup, installed)?;
+    Ok(())
+}
+
+/// Removes a file or folder, ignoring it if it doesn't exist.
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        remove_if_exists(path)?;
+    }
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A staging folder with an installed app and a backup of the previous one.
+    fn setup(dir: &Path) -> (UpdateStaging, PendingUpdate) {
+        let installed = dir.join("App.app");
+        let backup = dir.join("App.app.backup");
+        fs::create_dir_all(&installed).unwrap();
+        fs::write(installed.join("version"), "0.2.0").unwrap();
+        fs::create_dir_all(&backup).unwrap();
+        fs::write(backup.join("version"), "0.1.0").unwrap();
+
+        let staging = UpdateStaging::new(dir.join("staging"));
+        let pending =
+            PendingUpdate::new("0.2.0", "0.1.0", installed, backup).with_max_launch_attempts(2);
+        staging.begin(&pending).unwrap();
+        (staging, pending)
+    }
+
+    #[test]
+    fn test_launch_state_transitions() {
+        let mut pending =
+            PendingUpdate::new("0.2.0", "0.1.0", "a", "b").with_max_launch_attempts(2);
+        assert_eq!(pending.state(false), LaunchState::AwaitingConfirmation);
+
+        pending.launch_attempts = 1;
+        assert_eq!(pending.state(false), LaunchState::AwaitingConfirmation);
+
+        pending.launch_attempts = 2;
+        assert_eq!(pending.state(false), LaunchState::Failed);
+        // Confirmation wins even after every attempt was used
+        assert_eq!(pending.state(true), LaunchState::Confirmed);
+    }
+
+    #[test]
+    fn test_confirm_commits_update() {
+        let dir = tempfile::tempdir().unwrap();
+        let (staging, pending) = setup(dir.path());
+
+        let Resolution::Waiting(launched) = staging.begin_launch().unwrap() else {
+            panic!("expected the update to await confirmation");
+        };
+        assert_eq!(launched.launch_attempts, 1);
+        assert!(staging.confirm().unwrap());
+        assert_eq!(staging.state().unwrap(), Some(LaunchState::Confirmed));
+
+        assert_eq!(staging.resolve().unwrap(), Resolution::Committed(launched));
+        assert!(!pending.backup_path.exists());
+        assert_eq!(
+            fs::read_to_string(pending.installed_path.join("version")).unwrap(),
+            "0.2.0"
+        );
+        assert_eq!(staging.begin_launch().unwrap(), Resolution::Idle);
+        // Nothing left to confirm
+        assert!(!staging.confirm().unwrap());
+    }
+
+    #[test]
+    fn test_slow_launch_is_not_rolled_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let (staging, _) = setup(dir.path());
+
+        // However long the first launch takes, it is still awaiting confirmation
+        staging.begin_launch().unwrap();
+        assert_eq!(
+            staging.state().unwrap(),
+            Some(LaunchState::AwaitingConfirmation)
+        );
+        assert!(matches!(staging.resolve().unwrap(), Resolution::Waiting(_)));
+    }
+
+    #[test]
+    fn test_unconfirmed_launches_roll_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let (staging, pending) = setup(dir.path());
+
+        // Two launches that crash before confirming
+        assert!(matches!(
+            staging.begin_launch().unwrap(),
+            Resolution::Waiting(_)
+        ));
+        assert!(matches!(
+            staging.begin_launch().unwrap(),
+            Resolution::Waiting(_)
+        ));
+        assert_eq!(staging.state().unwrap(), Some(LaunchState::Failed));
+
+        // The next start restores the previous version
+        let Resolution::RolledBack(failed) = staging.begin_launch().unwrap() else {
+            panic!("expected a rollback");
+        };
+        assert_eq!(failed.launch_attempts, 2);
+        assert!(!pending.backup_path.exists());
+        assert_eq!(
+            fs::read_to_string(pending.installed_path.join("version")).unwrap(),
+            "0.1.0"
+        );
+        assert!(staging.pending().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_begin_clears_stale_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let (staging, pending) = setup(dir.path());
+        staging.confirm().unwrap();
+
+        staging.begin(&pending).unwrap();
+        assert!(!staging.is_confirmed());
+        assert_eq!(staging.pending().unwrap(), Some(pending));
+    }
+}
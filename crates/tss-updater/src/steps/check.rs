@@ -2,22 +2,23 @@
 
 use std::str::FromStr;
 
-use crate::config::UpdateSettings;
+use crate::VERSION;
+use crate::config::{UpdateSettings, UpdateSource};
 use crate::error::{Result, UpdateError};
 use crate::github::client::GitHubClient;
 use crate::github::types::GitHubRelease;
-use crate::release::{ReleaseAsset, UpdateInfo};
+use crate::manifest::ManifestClient;
+use crate::release::{ReleaseAsset, UpdateInfo, parse_changelog};
 use crate::version::Version;
-use crate::{REPO_NAME, REPO_OWNER, VERSION};
 
-/// Checks for available updates from GitHub releases.
+/// Checks for available updates from the configured update source.
 ///
+/// Uses GitHub releases unless `settings.source` points to a mirror.
 /// Returns `Some(UpdateInfo)` if an update is available, `None` if already up to date.
 pub async fn check_for_update(settings: &UpdateSettings) -> Result<Option<UpdateInfo>> {
     tracing::info!("Checking for updates (current version: {})", VERSION);
 
-    let client = GitHubClient::new(REPO_OWNER, REPO_NAME)?;
-    let release = client.get_latest_release().await?;
+    let release = fetch_latest_release(&settings.source()).await?;
 
     // Skip draft releases
     if release.draft {
@@ -79,6 +80,18 @@ pub async fn check_for_update(settings: &UpdateSettings) -> Result<Option<Update
     Ok(Some(create_update_info(&release, asset)))
 }
 
+/// Fetches the latest release from an update source.
+async fn fetch_latest_release(source: &UpdateSource) -> Result<GitHubRelease> {
+    match source {
+        UpdateSource::GitHub { owner, repo } => {
+            GitHubClient::new(owner, repo)?.get_latest_release().await
+        }
+        UpdateSource::Manifest { base_url } => {
+            ManifestClient::new(base_url)?.get_latest_release().await
+        }
+    }
+}
+
 /// Gets the current target triple for the running system.
 fn get_target_triple() -> String {
     let arch = std::env::consts::ARCH;
@@ -106,6 +119,7 @@ fn create_update_info(
         version: release.version().to_string(),
         parsed_version,
         changelog: release.changelog().to_string(),
+        sections: parse_changelog(release.changelog()),
         asset: ReleaseAsset {
             name: asset.name.clone(),
             download_url: asset.browser_download_url.clone(),
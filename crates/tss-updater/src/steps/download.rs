@@ -2,9 +2,13 @@
 
 use async_stream::stream;
 use futures_util::{Stream, StreamExt};
+use reqwest::StatusCode;
 use reqwest::header::{HeaderValue, USER_AGENT};
 
+use crate::config::UpdateSettings;
 use crate::error::{Result, UpdateError};
+use crate::release::ReleaseAsset;
+use crate::steps::verify::CHECKSUM_SUFFIX;
 
 /// User agent string for download requests.
 const USER_AGENT_VALUE: &str = concat!(
@@ -324,6 +328,54 @@ pub async fn download_simple(url: &str) -> Result<Vec<u8>> {
     Ok(data.to_vec())
 }
 
+/// Downloads a file that may not exist.
+///
+/// Returns `None` if the server responds with 404.
+pub async fn download_optional(url: &str) -> Result<Option<Vec<u8>>> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(url)
+        .header(USER_AGENT, HeaderValue::from_static(USER_AGENT_VALUE))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if status == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(UpdateError::Network(format!(
+            "Download failed with status {}",
+            status
+        )));
+    }
+
+    let data = response
+        .bytes()
+        .await
+        .map_err(|e| UpdateError::Network(e.to_string()))?;
+
+    Ok(Some(data.to_vec()))
+}
+
+/// Downloads the checksum file published next to an asset.
+///
+/// Only fetched when neither a pinned hash nor a GitHub digest is
+/// available; returns `None` otherwise or if no checksum file is published.
+pub async fn download_checksum_file(
+    asset: &ReleaseAsset,
+    settings: &UpdateSettings,
+) -> Result<Option<String>> {
+    if settings.expected_sha256.is_some() || asset.digest.is_some() {
+        return Ok(None);
+    }
+
+    let url = format!("{}{}", asset.download_url, CHECKSUM_SUFFIX);
+    Ok(download_optional(&url)
+        .await?
+        .map(|data| String::from_utf8_lossy(&data).into_owned()))
+}
+
 /// Format bytes as a human-readable string.
 #[must_use]
 pub fn format_bytes(bytes: u64) -> String {
@@ -1,9 +1,17 @@
 //! SHA256 verification for downloaded updates.
+//!
+//! Besides GitHub's asset digests, releases can publish a checksum file
+//! (`<asset>.sha256`) next to each asset. [`verify_release_asset`] checks
+//! these against the [`UpdateSettings`] before an update is installed.
 
 use sha2::{Digest, Sha256};
 
+use crate::config::UpdateSettings;
 use crate::error::{Result, UpdateError};
-use crate::release::UpdateInfo;
+use crate::release::{ReleaseAsset, UpdateInfo};
+
+/// Suffix of the checksum file published next to a release asset.
+pub const CHECKSUM_SUFFIX: &str = ".sha256";
 
 /// Verifies the downloaded data against the expected SHA256 digest.
 ///
@@ -19,22 +27,7 @@ pub fn verify_download(data: &[u8], info: &UpdateInfo) -> Result<String> {
 ///
 /// Returns the verified SHA256 hash on success.
 pub fn verify_sha256(data: &[u8], expected_digest: &str) -> Result<String> {
-    // Extract the hex hash from the digest string
-    // Support both "sha256:abc123" format and plain "abc123" format
-    let expected_hash = expected_digest
-        .strip_prefix("sha256:")
-        .unwrap_or(expected_digest)
-        .trim()
-        .to_lowercase();
-
-    // Validate that we have a valid hex string
-    if expected_hash.is_empty() {
-        return Err(UpdateError::NoDigestAvailable);
-    }
-
-    if expected_hash.len() != 64 {
-        return Err(UpdateError::NoDigestAvailable);
-    }
+    let expected_hash = normalize_digest(expected_digest).ok_or(UpdateError::NoDigestAvailable)?;
 
     // Compute SHA256 hash of the downloaded data
     let actual_hash = compute_sha256(data);
@@ -51,6 +44,102 @@ pub fn verify_sha256(data: &[u8], expected_digest: &str) -> Result<String> {
     Ok(actual_hash)
 }
 
+/// Verifies a downloaded release asset against the update settings.
+///
+/// Every available SHA256 source must match: the pinned
+/// `expected_sha256`, GitHub's asset digest, and the published checksum
+/// file (`checksum_file`, see [`download_checksum_file`]). Verification
+/// fails closed: a package with no SHA256 source at all is rejected.
+///
+/// Returns the SHA256 hash of the data on success, or
+/// [`UpdateError::VerificationFailed`] on any mismatch.
+///
+/// [`download_checksum_file`]: crate::steps::download::download_checksum_file
+pub fn verify_release_asset(
+    data: &[u8],
+    asset: &ReleaseAsset,
+    settings: &UpdateSettings,
+    checksum_file: Option<&str>,
+) -> Result<String> {
+    let published = checksum_file
+        .map(|contents| {
+            parse_checksum_file(contents, &asset.name).ok_or_else(|| {
+                UpdateError::VerificationFailed(format!(
+                    "no checksum for {} in {}{}",
+                    asset.name, asset.name, CHECKSUM_SUFFIX
+                ))
+            })
+        })
+        .transpose()?;
+
+    let expected: Vec<&str> = [
+        settings.expected_sha256.as_deref(),
+        asset.digest.as_deref(),
+        published.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if expected.is_empty() {
+        return Err(UpdateError::VerificationFailed(format!(
+            "no checksum published for {}",
+            asset.name
+        )));
+    }
+
+    let actual = compute_sha256(data);
+    for digest in expected {
+        let expected = normalize_digest(digest).ok_or_else(|| {
+            UpdateError::VerificationFailed(format!("invalid SHA256 digest: {digest}"))
+        })?;
+        if expected != actual {
+            return Err(UpdateError::VerificationFailed(format!(
+                "SHA256 mismatch: expected {expected}, got {actual}"
+            )));
+        }
+    }
+
+    Ok(actual)
+}
+
+/// Extracts the SHA256 hash for `asset_name` from a checksum file.
+///
+/// Accepts a bare hash or `sha256sum` output (`<hash>  <file name>` per
+/// line, with an optional `*` before binary file names).
+#[must_use]
+pub fn parse_checksum_file(contents: &str, asset_name: &str) -> Option<String> {
+    let is_hash = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(hash) = parts.next().filter(|h| is_hash(h)) else {
+            continue;
+        };
+        match parts.next() {
+            None => return Some(hash.to_lowercase()),
+            Some(name) if name.trim_start_matches('*') == asset_name => {
+                return Some(hash.to_lowercase());
+            }
+            Some(_) => {}
+        }
+    }
+    None
+}
+
+/// Extracts the lowercase hex hash from a digest string.
+///
+/// Supports both "sha256:abc123" format and plain "abc123" format. Returns
+/// `None` if the result isn't a 64-character hash.
+fn normalize_digest(digest: &str) -> Option<String> {
+    let hash = digest
+        .strip_prefix("sha256:")
+        .unwrap_or(digest)
+        .trim()
+        .to_lowercase();
+    (hash.len() == 64).then_some(hash)
+}
+
 /// Computes the SHA256 hash of the given data.
 #[must_use]
 pub fn compute_sha256(data: &[u8]) -> String {
@@ -101,6 +190,69 @@ fn test_verify_sha256_empty_digest() {
         assert!(matches!(result, Err(UpdateError::NoDigestAvailable)));
     }
 
+    const PACKAGE: &[u8] = include_bytes!("../../tests/data/update-package.bin");
+    const TAMPERED: &[u8] = include_bytes!("../../tests/data/update-package-tampered.bin");
+    const CHECKSUM_FILE: &str = include_str!("../../tests/data/update-package.bin.sha256");
+
+    fn asset() -> ReleaseAsset {
+        ReleaseAsset::new(
+            "update-package.bin",
+            "https://example.com/update-package.bin",
+            None,
+            PACKAGE.len() as u64,
+        )
+    }
+
+    #[test]
+    fn test_parse_checksum_file() {
+        let hash = "26273da67cad728899da7425403c7ab8a19c04ce5af10e22921ad964952a0f76";
+        assert_eq!(
+            parse_checksum_file(CHECKSUM_FILE, "update-package.bin").as_deref(),
+            Some(hash)
+        );
+        assert_eq!(
+            parse_checksum_file(&format!("{hash}\n"), "anything").as_deref(),
+            Some(hash)
+        );
+        assert!(parse_checksum_file(CHECKSUM_FILE, "other.zip").is_none());
+        assert!(parse_checksum_file("not a checksum", "update-package.bin").is_none());
+    }
+
+    #[test]
+    fn test_verify_release_asset_checksum_file() {
+        let settings = UpdateSettings::default();
+
+        let hash = verify_release_asset(PACKAGE, &asset(), &settings, Some(CHECKSUM_FILE)).unwrap();
+        assert_eq!(hash, compute_sha256(PACKAGE));
+
+        let result = verify_release_asset(TAMPERED, &asset(), &settings, Some(CHECKSUM_FILE));
+        assert!(matches!(result, Err(UpdateError::VerificationFailed(_))));
+    }
+
+    #[test]
+    fn test_verify_release_asset_pinned_hash() {
+        let settings = UpdateSettings {
+            expected_sha256: Some(format!("sha256:{}", compute_sha256(PACKAGE))),
+            ..UpdateSettings::default()
+        };
+        assert!(verify_release_asset(PACKAGE, &asset(), &settings, None).is_ok());
+        assert!(matches!(
+            verify_release_asset(TAMPERED, &asset(), &settings, None),
+            Err(UpdateError::VerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_release_asset_fails_closed() {
+        let result = verify_release_asset(PACKAGE, &asset(), &UpdateSettings::default(), None);
+        assert!(matches!(result, Err(UpdateError::VerificationFailed(_))));
+
+        // A GitHub digest alone is enough
+        let mut asset = asset();
+        asset.digest = Some(format!("sha256:{}", compute_sha256(PACKAGE)));
+        assert!(verify_release_asset(PACKAGE, &asset, &UpdateSettings::default(), None).is_ok());
+    }
+
     #[test]
     fn test_compute_sha256() {
         let data = b"Hello, World!";
@@ -22,23 +22,23 @@ pub enum PreRelease {
 
 impl PreRelease {
     /// Parse a pre-release string (e.g., "beta.1", "alpha.2", "rc.3").
+    ///
+    /// The number may also follow a hyphen or no separator ("alpha-2",
+    /// "rc3"), and defaults to 0 when omitted ("beta"). Returns `None` for
+    /// anything else, including extra identifiers ("beta.1.2").
     fn parse(s: &str) -> Option<Self> {
         let s = s.to_lowercase();
+        let digits_at = s.find(|c: char| c.is_ascii_digit()).unwrap_or(s.len());
+        let (kind, num) = s.split_at(digits_at);
+        let kind = kind.trim_end_matches(['.', '-']);
 
-        if let Some(num) = s.strip_prefix("alpha.") {
-            num.parse().ok().map(PreRelease::Alpha)
-        } else if let Some(num) = s.strip_prefix("beta.") {
-            num.parse().ok().map(PreRelease::Beta)
-        } else if let Some(num) = s.strip_prefix("rc.") {
-            num.parse().ok().map(PreRelease::ReleaseCandidate)
-        } else if s == "alpha" {
-            Some(PreRelease::Alpha(0))
-        } else if s == "beta" {
-            Some(PreRelease::Beta(0))
-        } else if s == "rc" {
-            Some(PreRelease::ReleaseCandidate(0))
-        } else {
-            None
+        let num = if num.is_empty() { 0 } else { num.parse().ok()? };
+
+        match kind {
+            "alpha" => Some(PreRelease::Alpha(num)),
+            "beta" => Some(PreRelease::Beta(num)),
+            "rc" => Some(PreRelease::ReleaseCandidate(num)),
+            _ => None,
         }
     }
 
@@ -157,12 +157,17 @@ fn from_str(s: &str) -> Result<Self> {
         let s = s.trim();
         let s = s.strip_prefix('v').unwrap_or(s);
 
-        // Split into version and pre-release parts
-        let (version_part, pre_release) = if let Some(idx) = s.find('-') {
-            let (v, p) = s.split_at(idx);
-            (v, PreRelease::parse(&p[1..]))
-        } else {
-            (s, None)
+        // Build metadata ("+...") doesn't affect precedence
+        let without_build = s.split_once('+').map_or(s, |(v, _)| v);
+
+        // Split into version and pre-release parts. An unrecognized
+        // pre-release is an error rather than being mistaken for a stable release.
+        let (version_part, pre_release) = match without_build.split_once('-') {
+            Some((v, p)) => match PreRelease::parse(p) {
+                Some(pre) => (v, Some(pre)),
+                None => return Err(UpdateError::InvalidVersion(s.to_string())),
+            },
+            None => (without_build, None),
         };
 
         // Parse major.minor.patch
@@ -319,4 +324,72 @@ fn test_invalid_version() {
         assert!(Version::from_str("1.2").is_err());
         assert!(Version::from_str("1.2.3.4").is_err());
     }
+
+    #[test]
+    fn test_parse_prerelease_forms() {
+        let parse = |s: &str| Version::from_str(s).unwrap().pre_release;
+
+        assert_eq!(parse("0.0.6-alpha"), Some(PreRelease::Alpha(0)));
+        assert_eq!(parse("1.0.0-alpha6"), Some(PreRelease::Alpha(6)));
+        assert_eq!(parse("1.0.0-alpha-6"), Some(PreRelease::Alpha(6)));
+        assert_eq!(parse("1.0.0-RC.1"), Some(PreRelease::ReleaseCandidate(1)));
+        assert_eq!(parse("1.0.0-beta.2+build.7"), Some(PreRelease::Beta(2)));
+        assert_eq!(parse("1.0.0+build.7"), None);
+    }
+
+    #[test]
+    fn test_unknown_prerelease_rejected() {
+        // Previously these parsed as stable releases
+        assert!(Version::from_str("1.0.0-nightly").is_err());
+        assert!(Version::from_str("1.0.0-beta.1.2").is_err());
+        assert!(Version::from_str("1.0.0-alpha.x").is_err());
+        assert!(Version::from_str("1.0.0-").is_err());
+    }
+
+    #[test]
+    fn test_precedence_matrix() {
+        // Strictly increasing precedence
+        let ordered = [
+            "0.9.9",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.5",
+            "1.0.0-alpha.6",
+            "1.0.0-alpha.10",
+            "1.0.0-beta",
+            "1.0.0-beta.1",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0-rc.2",
+            "1.0.0",
+            "1.0.1-alpha.1",
+            "1.0.1",
+            "1.1.0-rc.1",
+            "1.1.0",
+            "2.0.0-alpha.1",
+            "2.0.0",
+        ];
+        let versions: Vec<Version> = ordered
+            .iter()
+            .map(|s| Version::from_str(s).unwrap())
+            .collect();
+
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{} vs {}", ordered[i], ordered[j]);
+            }
+        }
+
+        // The alpha.5 -> alpha.6 update is offered
+        assert!(
+            Version::from_tag("v0.0.6-alpha.6").unwrap()
+                > Version::from_str("0.0.6-alpha.5").unwrap()
+        );
+        // Build metadata is ignored
+        assert_eq!(
+            Version::from_str("1.0.0-rc.1+abc").unwrap(),
+            Version::from_str("1.0.0-rc.1").unwrap()
+        );
+    }
 }
@@ -1,6 +1,16 @@
 use aho_corasick::AhoCorasick;
 use serde::{Deserialize, Serialize};
 
+/// Maximum edit distance between a query token and a word for a fuzzy match
+pub const FUZZY_MAX_EDIT_DISTANCE: usize = 2;
+
+/// Query tokens shorter than this must match exactly (a two-letter token is
+/// within two edits of every other short word)
+const FUZZY_MIN_TOKEN_LEN: usize = 4;
+
+/// Fuzzy matches score lower than exact ones, scaled by this factor
+const FUZZY_SCORE_FACTOR: f32 = 0.5;
+
 /// Pre-processed IG content - primarily text chunks from the PDF documents
 pub struct IgIndex {
     sdtm: IgContent,
@@ -103,11 +113,20 @@ pub fn domain_count(&self) -> usize {
     ///
     /// This searches the prose content of the guidance documents,
     /// returning relevant sections with their context.
+    ///
+    /// When the query is a domain code (e.g., "AE"), chunks whose section
+    /// heading names that domain ("6.2.1 Adverse Events (AE)") are returned
+    /// first, in document order, ahead of prose that merely mentions it.
+    ///
+    /// If nothing matches exactly, falls back to typo-tolerant matching of
+    /// each query token against the words of each chunk (see
+    /// [`FUZZY_MAX_EDIT_DISTANCE`]), scored lower than exact matches.
     pub fn search(&self, query: &str, ig: &str, limit: usize) -> Vec<SearchResult> {
         let keywords: Vec<&str> = query.split_whitespace().collect();
         if keywords.is_empty() {
             return Vec::new();
         }
+        let domain_code = domain_code_query(query);
 
         // Case-insensitive keyword matching
         let lower_keywords: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
@@ -134,33 +153,50 @@ pub fn search(&self, query: &str, ig: &str, limit: usize) -> Vec<SearchResult> {
             ],
         };
 
-        for (ig_name, ig_content) in igs_to_search {
+        for &(ig_name, ig_content) in &igs_to_search {
             for chunk in &ig_content.chunks {
+                let names_domain = domain_code
+                    .as_deref()
+                    .is_some_and(|code| heading_names_domain(&chunk.heading, code));
                 let matches: Vec<_> = ac.find_iter(&chunk.content).collect();
-                if !matches.is_empty() {
+                if !matches.is_empty() || names_domain {
                     // Score based on how many distinct keywords matched
                     let unique_patterns: std::collections::HashSet<_> =
                         matches.iter().map(|m| m.pattern().as_usize()).collect();
                     let score = unique_patterns.len() as f32 / lower_keywords.len() as f32;
 
-                    results.push(SearchResult {
-                        ig: ig_name.to_string(),
-                        index: chunk.index,
-                        heading: chunk.heading.clone(),
-                        content: truncate_around_match(&chunk.content, query, 600),
-                        domain: chunk.domain.clone(),
-                        page: chunk.page,
-                        parent_index: chunk.parent_index,
-                        score,
-                    });
+                    results.push((
+                        names_domain,
+                        SearchResult {
+                            ig: ig_name.to_string(),
+                            index: chunk.index,
+                            heading: chunk.heading.clone(),
+                            content: truncate_around_match(&chunk.content, query, 600),
+                            domain: chunk.domain.clone(),
+                            page: chunk.page,
+                            parent_index: chunk.parent_index,
+                            score,
+                        },
+                    ));
                 }
             }
         }
 
-        // Sort by score descending
-        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        if results.is_empty() {
+            results = fuzzy_search(&igs_to_search, &lower_keywords)
+                .into_iter()
+                .map(|result| (false, result))
+                .collect();
+        }
+
+        // Domain sections first, then by score descending (stable, so ties keep document order)
+        results.sort_by(|(a_domain, a), (b_domain, b)| {
+            b_domain
+                .cmp(a_domain)
+                .then_with(|| b.score.total_cmp(&a.score))
+        });
         results.truncate(limit);
-        results
+        results.into_iter().map(|(_, result)| result).collect()
     }
 
     /// Get all chunks related to a specific domain
@@ -280,6 +316,89 @@ pub fn list_sections(&self, ig: &str) -> Option<Vec<SectionInfo>> {
     }
 }
 
+/// Typo-tolerant search, matching each keyword to any word of a chunk
+/// within [`FUZZY_MAX_EDIT_DISTANCE`] edits
+fn fuzzy_search(igs: &[(&str, &IgContent)], keywords: &[String]) -> Vec<SearchResult> {
+    let tokens: Vec<Vec<char>> = keywords.iter().map(|k| k.chars().collect()).collect();
+    let mut results = Vec::new();
+
+    for &(ig_name, ig_content) in igs {
+        for chunk in &ig_content.chunks {
+            let mut matched = vec![false; tokens.len()];
+            let mut first_match: Option<&str> = None;
+            for word in chunk.content.split(|c: char| !c.is_alphanumeric()) {
+                if word.is_empty() {
+                    continue;
+                }
+                let word_chars: Vec<char> = word.to_lowercase().chars().collect();
+                for (token, found) in tokens.iter().zip(matched.iter_mut()) {
+                    if !*found && is_fuzzy_match(token, &word_chars) {
+                        *found = true;
+                        first_match.get_or_insert(word);
+                    }
+                }
+            }
+
+            let Some(first_match) = first_match else {
+                continue;
+            };
+            let matched_count = matched.iter().filter(|m| **m).count();
+            let score = matched_count as f32 / tokens.len() as f32 * FUZZY_SCORE_FACTOR;
+
+            results.push(SearchResult {
+                ig: ig_name.to_string(),
+                index: chunk.index,
+                heading: chunk.heading.clone(),
+                content: truncate_around_match(&chunk.content, first_match, 600),
+                domain: chunk.domain.clone(),
+                page: chunk.page,
+                parent_index: chunk.parent_index,
+                score,
+            });
+        }
+    }
+    results
+}
+
+/// Whether a (lowercase) word is within the fuzzy edit distance of a query token
+fn is_fuzzy_match(token: &[char], word: &[char]) -> bool {
+    token.len() >= FUZZY_MIN_TOKEN_LEN
+        && token.len().abs_diff(word.len()) <= FUZZY_MAX_EDIT_DISTANCE
+        && edit_distance(token, word) <= FUZZY_MAX_EDIT_DISTANCE
+}
+
+/// Levenshtein distance between two character sequences
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// The domain code a query consists of, if any (e.g., "ae" -> "AE")
+///
+/// Domain codes are a single 2-4 letter word; longer or multi-word queries
+/// are treated as ordinary full-text searches.
+fn domain_code_query(query: &str) -> Option<String> {
+    let query = query.trim();
+    ((2..=4).contains(&query.len()) && query.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| query.to_ascii_uppercase())
+}
+
+/// Whether a section heading names a domain, as in "6.2.1 Adverse Events (AE)"
+fn heading_names_domain(heading: &str, code: &str) -> bool {
+    heading
+        .to_ascii_uppercase()
+        .contains(&format!("({})", code))
+}
+
 /// Truncate content, trying to show the most relevant part around the first match
 fn truncate_around_match(content: &str, query: &str, max_len: usize) -> String {
     if content.len() <= max_len {
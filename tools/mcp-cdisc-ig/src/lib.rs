@@ -5,5 +5,9 @@
 //! - SEND-IG v3.1.1
 //! - ADaM-IG v1.3
 //! - Define-XML v2.1
+//!
+//! and structured variable and Controlled Terminology lookups from the TSS
+//! standards crate.
 
 pub mod index;
+pub mod standards;
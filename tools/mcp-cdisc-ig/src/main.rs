@@ -5,6 +5,8 @@
 //! - SEND-IG v3.1.1 (244 pages)
 //! - ADaM-IG v1.3 (88 pages)
 //! - Define-XML v2.1 (metadata specification)
+//!
+//! and structured variable tables and Controlled Terminology checks.
 
 use rmcp::{
     ErrorData as McpError, ServerHandler, ServiceExt,
@@ -18,7 +20,7 @@
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use mcp_cdisc_ig::index;
+use mcp_cdisc_ig::{index, standards};
 
 // Parameter structs for tools
 
@@ -62,18 +64,40 @@ struct ListSectionsParams {
     ig: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+struct GetDomainVariablesParams {
+    /// Domain code (e.g., 'DM', 'AE', 'LB'); ADaM dataset structure for adam (e.g., 'ADSL', 'BDS')
+    domain: String,
+    /// Implementation Guide: 'sdtm', 'send', or 'adam'
+    ig: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ValidateCtParams {
+    /// Codelist NCI code (e.g., 'C66731'); alternatively give domain and variable
+    codelist: Option<String>,
+    /// Domain code whose variable's codelist to use (e.g., 'DM')
+    domain: Option<String>,
+    /// Variable name within the domain (e.g., 'SEX')
+    variable: Option<String>,
+    /// Values to check (e.g., ['M', 'F', 'Male'])
+    values: Vec<String>,
+}
+
 /// CDISC Implementation Guide MCP Server
 #[derive(Clone)]
 pub struct CdiscIgServer {
     index: Arc<RwLock<index::IgIndex>>,
+    standards: Arc<standards::StandardsLookup>,
     tool_router: ToolRouter<Self>,
 }
 
 #[tool_router]
 impl CdiscIgServer {
-    pub fn new(index: index::IgIndex) -> Self {
+    pub fn new(index: index::IgIndex, standards: standards::StandardsLookup) -> Self {
         Self {
             index: Arc::new(RwLock::new(index)),
+            standards: Arc::new(standards),
             tool_router: Self::tool_router(),
         }
     }
@@ -236,6 +260,67 @@ async fn list_sections(
             ))])),
         }
     }
+
+    /// Get the variable table of a domain
+    #[tool(
+        description = "Get the variable table of a CDISC domain: name, label, type, role, core designation (Req/Exp/Perm) and codelist for each variable, in IG order. Use to answer questions like which variables of a domain are required."
+    )]
+    async fn get_domain_variables(
+        &self,
+        params: Parameters<GetDomainVariablesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let GetDomainVariablesParams { domain, ig } = params.0;
+        let ig_lower = ig.to_lowercase();
+        if !["sdtm", "send", "adam"].contains(&ig_lower.as_str()) {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Invalid IG '{}'. Must be one of: sdtm, send, adam",
+                ig
+            ))]));
+        }
+
+        match self.standards.domain_variables(&ig_lower, &domain) {
+            Some(variables) => {
+                let content = serde_json::to_string_pretty(&variables)
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+            None => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Domain '{}' not found in {}-IG.",
+                domain.to_uppercase(),
+                ig.to_uppercase()
+            ))])),
+        }
+    }
+
+    /// Check values against CDISC Controlled Terminology
+    #[tool(
+        description = "Check values against a CDISC Controlled Terminology codelist, given its NCI code (e.g., 'C66731') or a domain and variable (e.g., DM and SEX). Returns which values are valid CDISC Submission Values and, for invalid ones, the closest valid value. Values outside an extensible codelist are allowed but should be reviewed."
+    )]
+    async fn validate_ct(
+        &self,
+        params: Parameters<ValidateCtParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let ValidateCtParams {
+            codelist,
+            domain,
+            variable,
+            values,
+        } = params.0;
+
+        match self.standards.check_values(
+            codelist.as_deref(),
+            domain.as_deref(),
+            variable.as_deref(),
+            &values,
+        ) {
+            Ok(result) => {
+                let content = serde_json::to_string_pretty(&result)
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+            Err(message) => Ok(CallToolResult::error(vec![Content::text(message)])),
+        }
+    }
 }
 
 #[tool_handler]
@@ -259,7 +344,8 @@ fn get_info(&self) -> ServerInfo {
             instructions: Some(
                 "CDISC standards documentation server. \
                  Provides searchable access to SDTM-IG v3.4, SEND-IG v3.1.1, \
-                 ADaM-IG v1.3, and Define-XML v2.1 specifications."
+                 ADaM-IG v1.3, and Define-XML v2.1 specifications, \
+                 with structured variable tables and CDISC Controlled Terminology checks."
                     .to_string(),
             ),
         }
@@ -287,8 +373,11 @@ async fn main() -> anyhow::Result<()> {
         index.domain_count()
     );
 
+    // Load standards and Controlled Terminology
+    let standards = standards::StandardsLookup::load()?;
+
     // Create the MCP server
-    let server = CdiscIgServer::new(index);
+    let server = CdiscIgServer::new(index, standards);
 
     // Start server on stdio transport (MCP standard transport)
     let transport = stdio();
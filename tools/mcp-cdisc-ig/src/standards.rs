@@ -0,0 +1,219 @@
+use serde::Serialize;
+use tss_standards::{CdiscDomain, CdiscVariable, DomainRef, StandardsRegistry};
+
+/// Structured standards lookups backed by the TSS standards registry
+///
+/// Holds the SDTM, SEND and ADaM definitions and the bundled CDISC CT
+/// packages, complementing the prose in the IG index.
+pub struct StandardsLookup {
+    standards: StandardsRegistry,
+}
+
+/// Variable table of a domain or dataset
+#[derive(Debug, Serialize)]
+pub struct DomainVariables {
+    pub domain: String,
+    pub label: Option<String>,
+    pub variables: Vec<VariableInfo>,
+}
+
+/// A variable as listed in the IG's domain table
+#[derive(Debug, Serialize)]
+pub struct VariableInfo {
+    pub name: String,
+    pub label: Option<String>,
+    /// 'Char' or 'Num'
+    pub data_type: String,
+    /// SDTM/SEND role (e.g., 'Identifier', 'Topic')
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// 'Req', 'Exp' or 'Perm'
+    pub core: Option<String>,
+    /// CDISC CT codelist code(s) (e.g., 'C66731')
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codelist: Option<String>,
+    /// ADaM source or derivation (e.g., 'SDTM.DM.USUBJID')
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Result of checking values against one codelist
+#[derive(Debug, Serialize)]
+pub struct CtCheckResult {
+    pub codelist_code: String,
+    pub codelist_name: String,
+    /// Values outside an extensible codelist are allowed, but should be reviewed
+    pub extensible: bool,
+    /// CT package the codelist was found in (e.g., "SDTM CT")
+    pub source: String,
+    /// Values that are CDISC Submission Values
+    pub valid: Vec<String>,
+    /// Values that are not, with the closest Submission Value if any
+    pub invalid: Vec<InvalidValue>,
+}
+
+/// A value that is not a CDISC Submission Value
+#[derive(Debug, Serialize)]
+pub struct InvalidValue {
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl StandardsLookup {
+    /// Load all standards and the default CT version (embedded at compile time)
+    pub fn load() -> anyhow::Result<Self> {
+        Ok(Self::new(StandardsRegistry::load_all()?))
+    }
+
+    pub fn new(standards: StandardsRegistry) -> Self {
+        Self { standards }
+    }
+
+    /// Check values against a codelist, given either its NCI code (e.g.,
+    /// 'C66731') or a domain and variable (e.g., DM and SEX)
+    ///
+    /// Domain variables resolve their codelist in the CT package of their
+    /// standard, so SEND variables use SEND CT. Returns a message suitable for
+    /// the caller if the codelist cannot be determined.
+    pub fn check_values(
+        &self,
+        codelist: Option<&str>,
+        domain: Option<&str>,
+        variable: Option<&str>,
+        values: &[String],
+    ) -> Result<CtCheckResult, String> {
+        let resolved = match (codelist, domain, variable) {
+            (Some(code), _, _) => {
+                let code = code.trim();
+                self.standards
+                    .ct
+                    .resolve(code, None)
+                    .ok_or_else(|| format!("Codelist '{}' not found in CDISC CT.", code))?
+            }
+            (None, Some(domain), Some(variable)) => {
+                let (domain_ref, standard, _) = self
+                    .standards
+                    .find_domain_with_provenance(domain.trim())
+                    .ok_or_else(|| format!("Domain '{}' not found.", domain.to_uppercase()))?;
+                let code = variable_codelist(domain_ref, variable.trim()).ok_or_else(|| {
+                    format!(
+                        "Variable {} in {} has no controlled terminology.",
+                        variable.to_uppercase(),
+                        domain_ref.name()
+                    )
+                })?;
+                self.standards
+                    .ct
+                    .resolve_for_standard(&code, standard)
+                    .ok_or_else(|| format!("Codelist '{}' not found in CDISC CT.", code))?
+            }
+            _ => {
+                return Err(
+                    "Provide either 'codelist' or both 'domain' and 'variable'.".to_string()
+                );
+            }
+        };
+
+        let codelist = resolved.codelist;
+        let mut result = CtCheckResult {
+            codelist_code: codelist.code.clone(),
+            codelist_name: codelist.name.clone(),
+            extensible: codelist.extensible,
+            source: resolved.source().to_string(),
+            valid: Vec::new(),
+            invalid: Vec::new(),
+        };
+        for value in values {
+            let value = value.trim();
+            if codelist.is_valid_submission_value(value) {
+                result.valid.push(value.to_string());
+            } else {
+                result.invalid.push(InvalidValue {
+                    value: value.to_string(),
+                    suggestion: codelist
+                        .suggest_submission_value(value)
+                        .map(|(suggested, _)| suggested.to_string()),
+                });
+            }
+        }
+        Ok(result)
+    }
+
+    /// Variable table of a domain in one IG ('sdtm', 'send' or 'adam')
+    ///
+    /// Returns `None` if the IG has no such domain (or is not one of these).
+    pub fn domain_variables(&self, ig: &str, domain: &str) -> Option<DomainVariables> {
+        let domain = domain.trim();
+        let (name, label, variables) = match ig {
+            "sdtm" => {
+                let domain = self.standards.find_sdtm_domain(domain)?;
+                let variables = domain
+                    .variables
+                    .iter()
+                    .map(|v| VariableInfo {
+                        role: v.role.map(|role| role.as_str().to_string()),
+                        ..VariableInfo::from_variable(v)
+                    })
+                    .collect();
+                (&domain.name, &domain.label, variables)
+            }
+            "send" => {
+                let domain = self.standards.find_send_domain(domain)?;
+                let variables = domain
+                    .variables
+                    .iter()
+                    .map(|v| VariableInfo {
+                        role: v.role.map(|role| role.as_str().to_string()),
+                        ..VariableInfo::from_variable(v)
+                    })
+                    .collect();
+                (&domain.name, &domain.label, variables)
+            }
+            "adam" => {
+                let dataset = self.standards.find_adam_dataset(domain)?;
+                let variables = dataset
+                    .variables
+                    .iter()
+                    .map(|v| VariableInfo {
+                        source: v.source.as_ref().map(ToString::to_string),
+                        ..VariableInfo::from_variable(v)
+                    })
+                    .collect();
+                (&dataset.name, &dataset.label, variables)
+            }
+            _ => return None,
+        };
+        Some(DomainVariables {
+            domain: name.clone(),
+            label: label.clone(),
+            variables,
+        })
+    }
+}
+
+impl VariableInfo {
+    /// Attributes shared by all standards
+    fn from_variable(variable: &impl CdiscVariable) -> Self {
+        Self {
+            name: variable.name().to_string(),
+            label: variable.label().map(str::to_string),
+            data_type: variable.data_type().as_str().to_string(),
+            role: None,
+            core: variable.core().map(|core| core.as_code().to_string()),
+            codelist: variable.codelist_code().map(str::to_string),
+            source: None,
+        }
+    }
+}
+
+/// First codelist code of a domain variable (multiple codes are separated by ';')
+fn variable_codelist(domain: DomainRef<'_>, variable: &str) -> Option<String> {
+    let code = match domain {
+        DomainRef::Sdtm(domain) => domain.find_variable(variable)?.codelist_code(),
+        DomainRef::Send(domain) => domain.find_variable(variable)?.codelist_code(),
+        DomainRef::Adam(dataset) => dataset.find_variable(variable)?.codelist_code(),
+    }?;
+    let code = code.split(';').next()?.trim();
+    (!code.is_empty()).then(|| code.to_string())
+}
@@ -269,6 +269,64 @@ fn test_search_results_sorted_by_score() {
     }
 }
 
+#[test]
+fn test_search_domain_code_ranks_domain_section_first() {
+    let index = get_test_index();
+    let results = index.search("AE", "sdtm", 10);
+
+    assert!(!results.is_empty(), "AE search should find results");
+    assert!(
+        results[0].heading.contains("Adverse Events (AE)"),
+        "Adverse Events section should rank first, got '{}'",
+        results[0].heading
+    );
+    assert!(
+        results[0].parent_index.is_none(),
+        "Section start should come before its continuation chunks"
+    );
+
+    let lower = index.search("ae", "sdtm", 10);
+    assert_eq!(
+        lower[0].index, results[0].index,
+        "Domain code match should be case-insensitive"
+    );
+}
+
+#[test]
+fn test_search_fuzzy_fallback() {
+    let index = get_test_index();
+    let exact = index.search("demographics", "sdtm", 1000);
+    let fuzzy = index.search("demografics", "sdtm", 1000);
+
+    assert!(
+        !fuzzy.is_empty(),
+        "Misspelled query should still find results"
+    );
+    let dm = fuzzy
+        .iter()
+        .find(|r| r.heading.contains("Demographics (DM)"))
+        .expect("Misspelled query should find the Demographics section");
+    let dm_exact = exact
+        .iter()
+        .find(|r| r.index == dm.index)
+        .expect("Exact query should find the same chunk");
+    assert!(
+        dm.score < dm_exact.score,
+        "Fuzzy match should score lower than an exact match"
+    );
+}
+
+#[test]
+fn test_search_non_code_query_unchanged() {
+    let index = get_test_index();
+    // Five letters: ordinary full-text search, sorted by score alone
+    let results = index.search("adverse", "sdtm", 20);
+    assert!(!results.is_empty());
+    for pair in results.windows(2) {
+        assert!(pair[0].score >= pair[1].score);
+    }
+}
+
 // =============================================================================
 // 3. Domain Lookup Tests (get_domain_spec tool)
 // =============================================================================
@@ -0,0 +1,179 @@
+//! End-to-end tests of the MCP server binary over its stdio transport
+//!
+//! Each test starts the server, performs the MCP handshake and calls tools
+//! with JSON-RPC messages, one per line, as an MCP client would.
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use serde_json::{Value, json};
+
+/// A running server with an initialized MCP session
+struct Server {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Server {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-cdisc-ig"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Failed to start the MCP server");
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let mut server = Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+        };
+
+        let init = server.request(
+            "initialize",
+            json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "server-tests", "version": "0.0.0" }
+            }),
+        );
+        assert_eq!(init["result"]["serverInfo"]["name"], "cdisc-ig");
+        server.send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }));
+        server
+    }
+
+    fn send(&mut self, message: &Value) {
+        writeln!(self.stdin, "{}", message).expect("Failed to write to the server");
+        self.stdin
+            .flush()
+            .expect("Failed to flush the server's stdin");
+    }
+
+    /// Send a request and wait for the response with its id
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        loop {
+            let mut line = String::new();
+            let read = self
+                .stdout
+                .read_line(&mut line)
+                .expect("Failed to read from the server");
+            assert!(read > 0, "Server closed stdout before answering {method}");
+            let message: Value = serde_json::from_str(&line).expect("Server wrote invalid JSON");
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+
+    /// Call a tool and return its result
+    fn call_tool(&mut self, name: &str, arguments: Value) -> Value {
+        let response = self.request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        );
+        assert!(
+            response.get("error").is_none(),
+            "tools/call {name} failed: {response}"
+        );
+        response["result"].clone()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Text of the first content item of a tool result
+fn text(result: &Value) -> &str {
+    result["content"][0]["text"]
+        .as_str()
+        .expect("Tool result should have text content")
+}
+
+#[test]
+fn test_validate_ct_is_listed() {
+    let mut server = Server::start();
+    let response = server.request("tools/list", json!({}));
+    let tools = response["result"]["tools"]
+        .as_array()
+        .expect("tools/list should return tools");
+
+    let validate_ct = tools
+        .iter()
+        .find(|tool| tool["name"] == "validate_ct")
+        .expect("validate_ct should be listed");
+    let properties = &validate_ct["inputSchema"]["properties"];
+    for parameter in ["codelist", "domain", "variable", "values"] {
+        assert!(
+            properties.get(parameter).is_some(),
+            "validate_ct should take '{parameter}'"
+        );
+    }
+}
+
+#[test]
+fn test_validate_ct_by_codelist() {
+    let mut server = Server::start();
+    let result = server.call_tool(
+        "validate_ct",
+        json!({ "codelist": "C66731", "values": ["M", "F", "Male"] }),
+    );
+
+    assert_ne!(
+        result["isError"],
+        true,
+        "Unexpected error: {}",
+        text(&result)
+    );
+    let check: Value = serde_json::from_str(text(&result)).expect("Result should be JSON");
+    assert_eq!(check["codelist_code"], "C66731");
+    assert_eq!(check["extensible"], false);
+    assert_eq!(check["valid"], json!(["M", "F"]));
+    assert_eq!(check["invalid"][0]["value"], "Male");
+    assert_eq!(check["invalid"][0]["suggestion"], "M");
+}
+
+#[test]
+fn test_validate_ct_by_domain_variable() {
+    let mut server = Server::start();
+    let result = server.call_tool(
+        "validate_ct",
+        json!({ "domain": "dm", "variable": "sex", "values": ["F"] }),
+    );
+
+    assert_ne!(
+        result["isError"],
+        true,
+        "Unexpected error: {}",
+        text(&result)
+    );
+    let check: Value = serde_json::from_str(text(&result)).expect("Result should be JSON");
+    assert_eq!(check["codelist_code"], "C66731");
+    assert_eq!(check["invalid"], json!([]));
+}
+
+#[test]
+fn test_validate_ct_reports_tool_errors() {
+    let mut server = Server::start();
+
+    let result = server.call_tool("validate_ct", json!({ "values": ["M"] }));
+    assert_eq!(result["isError"], true);
+
+    let result = server.call_tool(
+        "validate_ct",
+        json!({ "codelist": "C00000", "values": ["M"] }),
+    );
+    assert_eq!(result["isError"], true);
+    assert!(text(&result).contains("C00000"));
+}
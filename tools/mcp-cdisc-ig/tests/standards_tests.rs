@@ -0,0 +1,115 @@
+//! Tests for the structured standards lookups behind the validate_ct and
+//! get_domain_variables MCP tools
+
+use mcp_cdisc_ig::standards::StandardsLookup;
+
+fn get_test_lookup() -> StandardsLookup {
+    StandardsLookup::load().expect("Failed to load standards and CT")
+}
+
+fn values(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_validate_by_codelist_code() {
+    let lookup = get_test_lookup();
+    let result = lookup
+        .check_values(
+            Some("C66731"),
+            None,
+            None,
+            &values(&["M", "F", "Male", "X"]),
+        )
+        .expect("C66731 should resolve");
+
+    assert_eq!(result.codelist_code, "C66731");
+    assert!(!result.extensible, "Sex codelist is non-extensible");
+    assert_eq!(result.valid, ["M", "F"]);
+    assert_eq!(result.invalid.len(), 2);
+    assert_eq!(result.invalid[0].value, "Male");
+    assert_eq!(
+        result.invalid[0].suggestion.as_deref(),
+        Some("M"),
+        "Synonym should suggest its Submission Value"
+    );
+    assert_eq!(result.invalid[1].value, "X");
+}
+
+#[test]
+fn test_validate_by_domain_variable() {
+    let lookup = get_test_lookup();
+    let result = lookup
+        .check_values(None, Some("dm"), Some("sex"), &values(&["F"]))
+        .expect("DM.SEX should have a codelist");
+
+    assert_eq!(result.codelist_code, "C66731");
+    assert_eq!(result.valid, ["F"]);
+    assert!(result.invalid.is_empty());
+}
+
+#[test]
+fn test_validate_unresolved_inputs() {
+    let lookup = get_test_lookup();
+    assert!(
+        lookup
+            .check_values(Some("C00000"), None, None, &values(&["M"]))
+            .is_err(),
+        "Unknown codelist should be an error"
+    );
+    assert!(
+        lookup
+            .check_values(None, Some("DM"), Some("USUBJID"), &values(&["S-001"]))
+            .is_err(),
+        "Variable without CT should be an error"
+    );
+    assert!(
+        lookup
+            .check_values(None, Some("DM"), None, &values(&["M"]))
+            .is_err(),
+        "Domain without variable should be an error"
+    );
+}
+
+#[test]
+fn test_domain_variables_ae() {
+    let lookup = get_test_lookup();
+    let ae = lookup
+        .domain_variables("sdtm", "ae")
+        .expect("AE should be in SDTM-IG");
+    assert_eq!(ae.domain, "AE");
+
+    let usubjid = ae
+        .variables
+        .iter()
+        .find(|v| v.name == "USUBJID")
+        .expect("AE should list USUBJID");
+    assert_eq!(usubjid.core.as_deref(), Some("Req"));
+    assert_eq!(usubjid.role.as_deref(), Some("Identifier"));
+
+    let aeterm = ae
+        .variables
+        .iter()
+        .find(|v| v.name == "AETERM")
+        .expect("AE should list AETERM");
+    assert_eq!(aeterm.core.as_deref(), Some("Req"));
+    assert_eq!(aeterm.role.as_deref(), Some("Topic"));
+    assert_eq!(aeterm.data_type, "Char");
+}
+
+#[test]
+fn test_domain_variables_other_igs() {
+    let lookup = get_test_lookup();
+    let adsl = lookup
+        .domain_variables("adam", "ADSL")
+        .expect("ADSL should be in ADaM-IG");
+    assert!(adsl.variables.iter().any(|v| v.name == "TRTxxP"));
+
+    let bw = lookup
+        .domain_variables("send", "BW")
+        .expect("BW should be in SEND-IG");
+    assert!(bw.variables.iter().any(|v| v.name == "BWTESTCD"));
+
+    assert!(lookup.domain_variables("sdtm", "XX").is_none());
+    assert!(lookup.domain_variables("define", "AE").is_none());
+}